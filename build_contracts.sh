@@ -8,10 +8,10 @@ set -e
 CHECK_ONLY=false
 if [ "$1" == "--check" ]; then
     CHECK_ONLY=true
-    COMMAND="cargo check"
+    COMMAND="cargo check --no-default-features"
     echo "🔍 Running check-only mode..."
 else
-    COMMAND="cargo build --target wasm32-unknown-unknown --release"
+    COMMAND="cargo build --target wasm32-unknown-unknown --release --no-default-features"
 fi
 
 echo "🚀 Building BoxMeOut Stella Smart Contracts..."
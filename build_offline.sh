@@ -12,7 +12,7 @@ cd "$(dirname "$0")/contracts/contracts/boxmeout"
 echo "Building all 5 contracts..."
 for contract in market oracle amm factory treasury; do
     echo "📦 $contract..."
-    cargo build --target wasm32-unknown-unknown --release --features $contract --offline 2>&1 | grep -v "warning:" || true
+    cargo build --target wasm32-unknown-unknown --release --no-default-features --features $contract --offline 2>&1 | grep -v "warning:" || true
     if [ -f "target/wasm32-unknown-unknown/release/boxmeout.wasm" ]; then
         cp target/wasm32-unknown-unknown/release/boxmeout.wasm target/wasm32-unknown-unknown/release/$contract.wasm
         echo "✅ $contract.wasm"
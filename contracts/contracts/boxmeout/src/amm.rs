@@ -1,7 +1,13 @@
 // contracts/amm.rs - Automated Market Maker for Outcome Shares
 // Enables trading YES/NO outcome shares with dynamic odds pricing (Polymarket model)
 
-use soroban_sdk::{contract, contractevent, contractimpl, token, Address, BytesN, Env, Symbol};
+use crate::helpers::fixed::{exp_wad, ln_wad, LN2_WAD, WAD};
+use crate::helpers::{events, guard, kyc, math, safe_add_i128, safe_add_u128, safe_sub_u128, time};
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contract, contractevent, contractimpl, contracttype, token, Address, BytesN, Env, IntoVal,
+    Symbol, Vec,
+};
 
 #[contractevent]
 pub struct AmmInitializedEvent {
@@ -26,6 +32,8 @@ pub struct BuySharesEvent {
     pub shares_out: u128,
     pub amount: u128,
     pub fee_amount: u128,
+    /// Price of the bought outcome after the trade, in basis points
+    pub new_price: u32,
 }
 
 #[contractevent]
@@ -64,6 +72,162 @@ const POOL_K_KEY: &str = "pool_k";
 const POOL_LP_SUPPLY_KEY: &str = "pool_lp_supply";
 const POOL_LP_TOKENS_KEY: &str = "pool_lp_tokens";
 const USER_SHARES_KEY: &str = "user_shares";
+const POOL_LAST_TRADE_KEY: &str = "pool_last_trade";
+const POOL_KYC_REGISTRY_KEY: &str = "pool_kyc_registry";
+const POOL_MARKET_KEY: &str = "pool_market";
+const POOL_COLLATERAL_TOKEN_KEY: &str = "pool_collateral_token";
+const POOL_COLLATERAL_KEY: &str = "pool_collateral";
+const POOL_FEE_BPS_KEY: &str = "pool_fee_bps";
+const POOL_PRICING_MODE_KEY: &str = "pool_pricing_mode";
+const POOL_LMSR_SHARES_KEY: &str = "pool_lmsr_shares";
+const POOL_SEED_LP_KEY: &str = "pool_seed_lp";
+const POOL_TREASURY_FEES_KEY: &str = "pool_treasury_fees";
+const TREASURY_FEE_SHARE_KEY: &str = "treasury_fee_share";
+const POOL_RESTING_FLOOR_KEY: &str = "pool_resting_floor";
+const POOL_SETTLED_KEY: &str = "pool_settled";
+const POOL_SETTLEMENT_KEY: &str = "pool_settlement";
+const POOL_OUTSTANDING_KEY: &str = "pool_outstanding";
+const POOL_OBSERVATIONS_KEY: &str = "pool_observations";
+const OPEN_INTEREST_CACHE_KEY: &str = "open_interest_cache";
+
+// LP fee accounting keys
+const POOL_FEE_ACC_KEY: &str = "pool_fee_acc";
+const POOL_LP_FEES_KEY: &str = "pool_lp_fees";
+const POOL_LP_FEES_CLAIMED_KEY: &str = "pool_lp_fees_claimed";
+const POOL_PAUSED_KEY: &str = "pool_paused";
+const LP_FEE_DEBT_KEY: &str = "lp_fee_debt";
+const LP_FEE_PENDING_KEY: &str = "lp_fee_pending";
+const LP_STATEMENT_KEY: &str = "lp_statement";
+const LP_POOLS_KEY: &str = "lp_pools";
+
+/// Highest swap fee a pool can charge, in basis points
+pub const MAX_POOL_FEE_BPS: u32 = 300;
+
+/// Fixed-point scale for the per-LP-token fee accumulator
+const FEE_ACC_PRECISION: u128 = 1_000_000_000_000;
+
+/// Fixed-point scale of settlement redemption rates (collateral per share)
+pub const SETTLEMENT_RATE_PRECISION: u128 = 1_000_000_000;
+
+/// Price observations a pool keeps for get_twap (oldest dropped first)
+pub const MAX_OBSERVATIONS: u32 = 64;
+
+/// Reserves at which a pool scores full depth, unless the factory sets one
+/// for the market's category (1,000 USDC)
+pub const DEFAULT_REFERENCE_DEPTH: u128 = 10_000_000_000;
+
+/// Time without trades after which a pool's freshness score reaches 0 (7 days)
+pub const STALE_AFTER: u64 = 7 * 24 * 60 * 60;
+
+/// Read-only health summary of a pool for listing badges
+///
+/// `score` (0-100) weighs depth 50%, balance 25% and freshness 25%:
+/// depth_score = min(100, 100 * (yes + no) / reference_depth),
+/// balance = 100 - imbalance_bps / 100,
+/// freshness = 100 * (STALE_AFTER - min(seconds_since_trade, STALE_AFTER)) / STALE_AFTER.
+/// Frozen pools (missing, or a reserve drained to zero) score 0.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolHealth {
+    pub score: u32,
+    pub depth_score: u32,
+    /// |yes - no| / (yes + no) in basis points
+    pub imbalance_bps: u32,
+    /// Since the last trade, or since creation if never traded
+    pub seconds_since_trade: u64,
+    pub frozen: bool,
+}
+
+/// Cumulative per-provider LP accounting across all pools (USDC base units)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LpStatement {
+    pub total_deposited: u128,
+    pub total_withdrawn: u128,
+    /// Share of trading fees accrued while holding LP tokens (claimed or not)
+    pub fees_earned: u128,
+    /// Fees paid out through claim_lp_fees
+    pub rewards_claimed: u128,
+}
+
+/// A pool's swap fee and how each fee is split
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeConfig {
+    pub fee_bps: u32,
+    /// Share of each fee credited to LPs, in basis points of the fee
+    pub lp_share_bps: u32,
+    /// Share of each fee set aside for the treasury, in basis points of the fee
+    pub treasury_share_bps: u32,
+}
+
+/// Preview of a trade from quote_buy / quote_sell
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QuoteResult {
+    /// Shares a buy receives (0 for sells)
+    pub shares_out: u128,
+    /// Collateral a sell pays out after the fee (0 for buys)
+    pub collateral_out: u128,
+    pub fee: u128,
+    /// Price of the traded outcome after the trade, in basis points
+    pub new_price: u32,
+    /// Move of the traded outcome's price, in basis points of its pre-trade price
+    pub price_impact_bps: u32,
+}
+
+/// Preview of swap_outcome from quote_swap_outcome
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapQuote {
+    /// Shares of the target outcome received
+    pub shares_out: u128,
+    /// Net collateral the sell leg hands to the buy leg
+    pub collateral_routed: u128,
+    pub sell_fee: u128,
+    pub buy_fee: u128,
+    /// Price of the target outcome after both legs, in basis points
+    pub new_price: u32,
+}
+
+/// Pricing curve of a pool, chosen at initialize_pool
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PricingMode {
+    /// Constant product x * y = k over the YES/NO reserves
+    Cpmm,
+    /// Logarithmic market scoring rule with liquidity parameter `b`, in
+    /// collateral units; the pool can lose at most b * ln(2)
+    Lmsr(u128),
+}
+
+/// A point in a pool's YES price history, recorded by trades and poke
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Observation {
+    /// Ledger timestamp of the observation
+    pub timestamp: u64,
+    /// Sum of YES price (basis points) * seconds from the first observation up to `timestamp`
+    pub cumulative: u128,
+    /// YES price in effect from `timestamp` on; the last trade in a second wins
+    pub price: u32,
+}
+
+/// How a settled pool's outcome shares redeem, fixed by settle_pool
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolSettlement {
+    /// Market state value the pool settled against (Resolved, Cancelled, Refunding)
+    pub market_state: u32,
+    /// Winning outcome for a Resolved market, None for refund-style settlement
+    pub winning_outcome: Option<u32>,
+    /// Collateral per YES / NO share, scaled by SETTLEMENT_RATE_PRECISION
+    pub yes_rate: u128,
+    pub no_rate: u128,
+    /// Collateral still held back for shares not yet redeemed
+    pub redemption_reserve: u128,
+    pub settled_at: u64,
+}
 
 // Pool data structure
 #[derive(Clone)]
@@ -98,10 +262,1005 @@ fn calculate_lp_tokens_to_mint(
         panic!("invalid pool liquidity");
     }
 
-    usdc_amount
-        .checked_mul(current_lp_supply)
-        .and_then(|v| v.checked_div(current_total_liquidity))
-        .expect("lp mint calculation overflow")
+    mul_div_floor(usdc_amount, current_lp_supply, current_total_liquidity)
+}
+
+/// `a * b / c` on u128 amounts through helpers::math, rounded down
+fn mul_div_floor(a: u128, b: u128, c: u128) -> u128 {
+    math::mul_div_floor(signed_amount(a), signed_amount(b), signed_amount(c)) as u128
+}
+
+/// `a * b / c` on u128 amounts through helpers::math, rounded up
+fn mul_div_ceil(a: u128, b: u128, c: u128) -> u128 {
+    math::mul_div_ceil(signed_amount(a), signed_amount(b), signed_amount(c)) as u128
+}
+
+/// `basis_points` of a u128 amount, rounded down
+fn bps_of(amount: u128, basis_points: u32) -> u128 {
+    math::bps(signed_amount(amount), basis_points) as u128
+}
+
+fn signed_amount(amount: u128) -> i128 {
+    i128::try_from(amount).expect("amount overflow")
+}
+
+/// Credit a trade fee to a pool's LPs via the fee-per-LP-token accumulator
+fn accrue_lp_fees(env: &Env, market_id: &BytesN<32>, fee_amount: u128) {
+    if fee_amount == 0 {
+        return;
+    }
+    let lp_supply: u128 = env
+        .storage()
+        .persistent()
+        .get(&(Symbol::new(env, POOL_LP_SUPPLY_KEY), market_id.clone()))
+        .unwrap_or(0);
+    if lp_supply == 0 {
+        return;
+    }
+
+    let acc_key = (Symbol::new(env, POOL_FEE_ACC_KEY), market_id.clone());
+    let acc: u128 = env.storage().persistent().get(&acc_key).unwrap_or(0);
+    let increment = fee_amount
+        .checked_mul(FEE_ACC_PRECISION)
+        .expect("fee accumulator overflow")
+        / lp_supply;
+    env.storage()
+        .persistent()
+        .set(&acc_key, &safe_add_u128(acc, increment));
+
+    let total_key = (Symbol::new(env, POOL_LP_FEES_KEY), market_id.clone());
+    let total: u128 = env.storage().persistent().get(&total_key).unwrap_or(0);
+    env.storage()
+        .persistent()
+        .set(&total_key, &safe_add_u128(total, fee_amount));
+}
+
+/// Split a trade fee between the treasury (held until flushed) and the pool's LPs
+///
+/// The treasury part is floored and LPs get the remainder, so the two always
+/// add up to `fee`. A fee that rounds to zero moves nothing.
+fn collect_fee(env: &Env, market_id: &BytesN<32>, fee: u128) {
+    let treasury_share: u32 = env
+        .storage()
+        .persistent()
+        .get(&Symbol::new(env, TREASURY_FEE_SHARE_KEY))
+        .unwrap_or(0);
+    let treasury_part = bps_of(fee, treasury_share);
+    if treasury_part > 0 {
+        let key = (Symbol::new(env, POOL_TREASURY_FEES_KEY), market_id.clone());
+        let pending: u128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&key, &safe_add_u128(pending, treasury_part));
+    }
+    accrue_lp_fees(env, market_id, fee - treasury_part);
+}
+
+/// Fees accrued to `lp_balance` since the provider's last checkpoint in this pool
+fn unsettled_lp_fees(
+    env: &Env,
+    market_id: &BytesN<32>,
+    lp_provider: &Address,
+    lp_balance: u128,
+) -> u128 {
+    let acc: u128 = env
+        .storage()
+        .persistent()
+        .get(&(Symbol::new(env, POOL_FEE_ACC_KEY), market_id.clone()))
+        .unwrap_or(0);
+    let debt: u128 = env
+        .storage()
+        .persistent()
+        .get(&(
+            Symbol::new(env, LP_FEE_DEBT_KEY),
+            market_id.clone(),
+            lp_provider.clone(),
+        ))
+        .unwrap_or(0);
+    (lp_balance * acc / FEE_ACC_PRECISION).saturating_sub(debt)
+}
+
+/// Settle accrued fees for `old_balance` and reset the debt for `new_balance`
+fn checkpoint_lp_fees(
+    env: &Env,
+    market_id: &BytesN<32>,
+    lp_provider: &Address,
+    old_balance: u128,
+    new_balance: u128,
+) {
+    let accrued = unsettled_lp_fees(env, market_id, lp_provider, old_balance);
+    if accrued > 0 {
+        let pending_key = (
+            Symbol::new(env, LP_FEE_PENDING_KEY),
+            market_id.clone(),
+            lp_provider.clone(),
+        );
+        let pending: u128 = env.storage().persistent().get(&pending_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&pending_key, &safe_add_u128(pending, accrued));
+        update_lp_statement(env, lp_provider, |statement| {
+            statement.fees_earned = safe_add_u128(statement.fees_earned, accrued);
+        });
+    }
+
+    let acc: u128 = env
+        .storage()
+        .persistent()
+        .get(&(Symbol::new(env, POOL_FEE_ACC_KEY), market_id.clone()))
+        .unwrap_or(0);
+    env.storage().persistent().set(
+        &(
+            Symbol::new(env, LP_FEE_DEBT_KEY),
+            market_id.clone(),
+            lp_provider.clone(),
+        ),
+        &(new_balance * acc / FEE_ACC_PRECISION),
+    );
+
+    // Remember the pool so get_lp_statement can include unsettled fees
+    let pools_key = (Symbol::new(env, LP_POOLS_KEY), lp_provider.clone());
+    let mut pools: Vec<BytesN<32>> = env
+        .storage()
+        .persistent()
+        .get(&pools_key)
+        .unwrap_or_else(|| Vec::new(env));
+    if !pools.contains(market_id) {
+        pools.push_back(market_id.clone());
+        env.storage().persistent().set(&pools_key, &pools);
+    }
+}
+
+/// Stamp the pool's last trade time with the current ledger timestamp
+fn record_trade_time(env: &Env, market_id: &BytesN<32>) {
+    env.storage().persistent().set(
+        &(Symbol::new(env, POOL_LAST_TRADE_KEY), market_id.clone()),
+        &env.ledger().timestamp(),
+    );
+}
+
+/// Reference depth for the market's category from the factory, if it answers
+fn reference_depth(env: &Env, market_id: &BytesN<32>) -> u128 {
+    let factory: Option<Address> = env
+        .storage()
+        .persistent()
+        .get(&Symbol::new(env, FACTORY_KEY));
+    factory
+        .and_then(|factory| {
+            env.try_invoke_contract::<Option<u128>, soroban_sdk::Error>(
+                &factory,
+                &Symbol::new(env, "get_market_reference_depth"),
+                (market_id.clone(),).into_val(env),
+            )
+            .ok()
+            .and_then(|result| result.ok())
+            .flatten()
+        })
+        .filter(|depth| *depth > 0)
+        .unwrap_or(DEFAULT_REFERENCE_DEPTH)
+}
+
+fn load_lp_statement(env: &Env, lp_provider: &Address) -> LpStatement {
+    env.storage()
+        .persistent()
+        .get(&(Symbol::new(env, LP_STATEMENT_KEY), lp_provider.clone()))
+        .unwrap_or(LpStatement {
+            total_deposited: 0,
+            total_withdrawn: 0,
+            fees_earned: 0,
+            rewards_claimed: 0,
+        })
+}
+
+fn update_lp_statement(env: &Env, lp_provider: &Address, update: impl FnOnce(&mut LpStatement)) {
+    let mut statement = load_lp_statement(env, lp_provider);
+    update(&mut statement);
+    env.storage().persistent().set(
+        &(Symbol::new(env, LP_STATEMENT_KEY), lp_provider.clone()),
+        &statement,
+    );
+}
+
+/// Open a 50/50 pool for `market_id` funded by `creator`, who receives the initial LP tokens
+///
+/// The pool's collateral token must already be recorded (defaults to USDC).
+fn open_pool(env: &Env, creator: &Address, market_id: &BytesN<32>, initial_liquidity: u128) {
+    // Check if pool already exists
+    let pool_exists_key = (Symbol::new(env, POOL_EXISTS_KEY), market_id.clone());
+    if env.storage().persistent().has(&pool_exists_key) {
+        panic!("pool already exists");
+    }
+
+    // Validate initial liquidity
+    if initial_liquidity == 0 {
+        panic!("initial liquidity must be greater than 0");
+    }
+
+    // Initialize 50/50 split
+    let yes_reserve = initial_liquidity / 2;
+    let no_reserve = initial_liquidity / 2;
+
+    // Calculate constant product k = x * y
+    let k = yes_reserve * no_reserve;
+
+    // Create storage keys for this pool using tuples
+    let yes_key = (Symbol::new(env, POOL_YES_RESERVE_KEY), market_id.clone());
+    let no_key = (Symbol::new(env, POOL_NO_RESERVE_KEY), market_id.clone());
+    let k_key = (Symbol::new(env, POOL_K_KEY), market_id.clone());
+    let lp_supply_key = (Symbol::new(env, POOL_LP_SUPPLY_KEY), market_id.clone());
+    let lp_balance_key = (
+        Symbol::new(env, POOL_LP_TOKENS_KEY),
+        market_id.clone(),
+        creator.clone(),
+    );
+
+    // Store reserves
+    env.storage().persistent().set(&yes_key, &yes_reserve);
+    env.storage().persistent().set(&no_key, &no_reserve);
+    env.storage().persistent().set(&k_key, &k);
+    env.storage().persistent().set(&pool_exists_key, &true);
+    record_trade_time(env, market_id);
+    record_observation(env, market_id);
+
+    // Mint LP tokens to creator (equal to initial_liquidity for first LP)
+    let lp_tokens = initial_liquidity;
+    env.storage().persistent().set(&lp_supply_key, &lp_tokens);
+    env.storage().persistent().set(&lp_balance_key, &lp_tokens);
+    env.storage().persistent().set(
+        &(Symbol::new(env, POOL_SEED_LP_KEY), market_id.clone()),
+        &lp_tokens,
+    );
+    checkpoint_lp_fees(env, market_id, creator, 0, lp_tokens);
+    update_lp_statement(env, creator, |statement| {
+        statement.total_deposited = safe_add_u128(statement.total_deposited, initial_liquidity);
+    });
+
+    // Transfer collateral from creator to contract
+    let token_client = token::Client::new(env, &pool_collateral(env, market_id));
+    adjust_pool_collateral(env, market_id, initial_liquidity as i128);
+    token_client.transfer(
+        creator,
+        env.current_contract_address(),
+        &(initial_liquidity as i128),
+    );
+
+    PoolCreatedEvent {
+        market_id: market_id.clone(),
+        initial_liquidity,
+        yes_reserve,
+        no_reserve,
+    }
+    .publish(env);
+}
+
+/// Collateral token of a pool (USDC unless initialize_pool chose another)
+fn pool_collateral(env: &Env, market_id: &BytesN<32>) -> Address {
+    env.storage()
+        .persistent()
+        .get(&(
+            Symbol::new(env, POOL_COLLATERAL_TOKEN_KEY),
+            market_id.clone(),
+        ))
+        .unwrap_or_else(|| {
+            env.storage()
+                .persistent()
+                .get(&Symbol::new(env, USDC_KEY))
+                .expect("usdc token not set")
+        })
+}
+
+/// Track collateral moving into (positive) or out of (negative) a pool
+fn adjust_pool_collateral(env: &Env, market_id: &BytesN<32>, delta: i128) {
+    let key = (Symbol::new(env, POOL_COLLATERAL_KEY), market_id.clone());
+    let balance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage()
+        .persistent()
+        .set(&key, &safe_add_i128(balance, delta));
+}
+
+/// Market state value meaning "accepting predictions" (`MarketStatus::Open`)
+const MARKET_STATE_OPEN: u32 = 0;
+
+/// Market state value meaning "outcome settled" (`MarketStatus::Resolved`)
+const MARKET_STATE_RESOLVED: u32 = 2;
+
+/// Market state value of a resolved market whose unclaimed funds were swept (`MarketStatus::Swept`)
+const MARKET_STATE_SWEPT: u32 = 7;
+
+/// State value reported by a pool's pinned market, if it has one and it answers
+fn market_state(env: &Env, market: &Address) -> Option<u32> {
+    env.try_invoke_contract::<Option<u32>, soroban_sdk::Error>(
+        market,
+        &Symbol::new(env, "get_market_state_value"),
+        Vec::new(env),
+    )
+    .ok()
+    .and_then(|result| result.ok())
+    .flatten()
+}
+
+/// Market a pool was pinned to by initialize_pool
+fn pool_market(env: &Env, market_id: &BytesN<32>) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&(Symbol::new(env, POOL_MARKET_KEY), market_id.clone()))
+}
+
+/// Whether a pool's market has reached a terminal state
+fn market_terminal(env: &Env, market_id: &BytesN<32>) -> bool {
+    pool_market(env, market_id)
+        .and_then(|market| market_state(env, &market))
+        .is_some_and(|state| MARKET_TERMINAL_STATES.contains(&state))
+}
+
+/// Panic unless the pool's market still accepts bets
+///
+/// Pools without a pinned market (create_pool) are not gated. A market that
+/// does not answer is treated as open, like the market's own operator-pause check.
+fn require_market_open(env: &Env, market_id: &BytesN<32>) {
+    let Some(market) = pool_market(env, market_id) else {
+        return;
+    };
+    let cutoff = env
+        .try_invoke_contract::<u64, soroban_sdk::Error>(
+            &market,
+            &Symbol::new(env, "get_betting_cutoff"),
+            Vec::new(env),
+        )
+        .ok()
+        .and_then(|result| result.ok());
+    if cutoff.is_some_and(|cutoff| time::has_reached(env, cutoff)) {
+        panic!("market closed");
+    }
+    if market_state(env, &market).is_some_and(|state| state != MARKET_STATE_OPEN) {
+        panic!("market closed");
+    }
+}
+
+/// Market state values after which nothing is left to trade (Resolved, Cancelled, Refunding, Swept)
+const MARKET_TERMINAL_STATES: [u32; 4] = [2, 4, 6, 7];
+
+/// Open interest (YES + NO stake) of a market, read once per ledger
+///
+/// Cached in temporary storage like KYC answers. A market that does not
+/// answer counts as having none.
+fn open_interest(env: &Env, market: &Address) -> u128 {
+    let key = (Symbol::new(env, OPEN_INTEREST_CACHE_KEY), market.clone());
+    let sequence = env.ledger().sequence();
+    if let Some((cached_at, interest)) = env.storage().temporary().get::<_, (u32, u128)>(&key) {
+        if cached_at == sequence {
+            return interest;
+        }
+    }
+
+    let interest = env
+        .try_invoke_contract::<i128, soroban_sdk::Error>(
+            market,
+            &Symbol::new(env, "get_open_interest"),
+            Vec::new(env),
+        )
+        .ok()
+        .and_then(|result| result.ok())
+        .map(|interest| interest.max(0) as u128)
+        .unwrap_or(0);
+    env.storage().temporary().set(&key, &(sequence, interest));
+    interest
+}
+
+/// Reserves (YES + NO) a pool must keep while its market is live
+///
+/// 0 when the pool has no rule or pinned market, is frozen (a reserve at
+/// zero), or its market is terminal.
+fn resting_floor(env: &Env, market_id: &BytesN<32>, yes_reserve: u128, no_reserve: u128) -> u128 {
+    let floor_bps: u32 = env
+        .storage()
+        .persistent()
+        .get(&(Symbol::new(env, POOL_RESTING_FLOOR_KEY), market_id.clone()))
+        .unwrap_or(0);
+    if floor_bps == 0 || yes_reserve == 0 || no_reserve == 0 {
+        return 0;
+    }
+    let Some(market) = pool_market(env, market_id) else {
+        return 0;
+    };
+    if market_state(env, &market).is_some_and(|state| MARKET_TERMINAL_STATES.contains(&state)) {
+        return 0;
+    }
+    bps_of(open_interest(env, &market), floor_bps)
+}
+
+/// Whether LPs may withdraw: not while the market is terminal and the pool unsettled
+fn withdrawals_open(env: &Env, market_id: &BytesN<32>) -> bool {
+    env.storage()
+        .persistent()
+        .get(&(Symbol::new(env, POOL_SETTLED_KEY), market_id.clone()))
+        .unwrap_or(false)
+        || !market_terminal(env, market_id)
+}
+
+/// Track outcome shares a pool minted (positive) or burned (negative)
+fn adjust_outstanding(env: &Env, market_id: &BytesN<32>, outcome: u32, delta: i128) {
+    let key = (
+        Symbol::new(env, POOL_OUTSTANDING_KEY),
+        market_id.clone(),
+        outcome,
+    );
+    let outstanding: u128 = env.storage().persistent().get(&key).unwrap_or(0);
+    let updated = if delta >= 0 {
+        outstanding + delta as u128
+    } else {
+        outstanding.saturating_sub(delta.unsigned_abs())
+    };
+    env.storage().persistent().set(&key, &updated);
+}
+
+/// (yes, no) outcome shares held by traders of a pool
+fn outstanding_shares(env: &Env, market_id: &BytesN<32>) -> (u128, u128) {
+    let read = |outcome: u32| -> u128 {
+        env.storage()
+            .persistent()
+            .get(&(
+                Symbol::new(env, POOL_OUTSTANDING_KEY),
+                market_id.clone(),
+                outcome,
+            ))
+            .unwrap_or(0)
+    };
+    (read(1), read(0))
+}
+
+fn pool_settlement(env: &Env, market_id: &BytesN<32>) -> Option<PoolSettlement> {
+    env.storage()
+        .persistent()
+        .get(&(Symbol::new(env, POOL_SETTLEMENT_KEY), market_id.clone()))
+}
+
+/// Collateral a pool holds for fees: unflushed treasury fees and unclaimed LP fees
+fn committed_fees(env: &Env, market_id: &BytesN<32>) -> u128 {
+    let read = |key: &str| -> u128 {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(env, key), market_id.clone()))
+            .unwrap_or(0)
+    };
+    read(POOL_TREASURY_FEES_KEY)
+        + read(POOL_LP_FEES_KEY).saturating_sub(read(POOL_LP_FEES_CLAIMED_KEY))
+}
+
+/// Collateral not owed to share holders or as fees; LPs split it after settlement
+fn lp_collateral(env: &Env, market_id: &BytesN<32>, redemption_reserve: u128) -> u128 {
+    let collateral: i128 = env
+        .storage()
+        .persistent()
+        .get(&(Symbol::new(env, POOL_COLLATERAL_KEY), market_id.clone()))
+        .unwrap_or(0);
+    (collateral.max(0) as u128)
+        .saturating_sub(redemption_reserve)
+        .saturating_sub(committed_fees(env, market_id))
+}
+
+fn observations(env: &Env, market_id: &BytesN<32>) -> Vec<Observation> {
+    env.storage()
+        .persistent()
+        .get(&(Symbol::new(env, POOL_OBSERVATIONS_KEY), market_id.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Record the pool's current YES price in its observation ring
+///
+/// The accumulator advances by the previous price times the seconds since the
+/// last observation. Several updates in one ledger second share a single
+/// observation, which keeps the last price.
+fn record_observation(env: &Env, market_id: &BytesN<32>) {
+    let mode = pricing_mode(env, market_id);
+    let (yes_state, no_state) = curve_state(env, market_id, &mode);
+    let price = curve_price(&mode, yes_state, no_state, 1);
+    let now = env.ledger().timestamp();
+
+    let mut observations = observations(env, market_id);
+    match observations.last() {
+        Some(mut last) if last.timestamp == now => {
+            last.price = price;
+            observations.set(observations.len() - 1, last);
+        }
+        last => {
+            let cumulative = last.map_or(0, |last| {
+                last.cumulative + last.price as u128 * (now - last.timestamp) as u128
+            });
+            observations.push_back(Observation {
+                timestamp: now,
+                cumulative,
+                price,
+            });
+            if observations.len() > MAX_OBSERVATIONS {
+                observations.pop_front();
+            }
+        }
+    }
+    env.storage().persistent().set(
+        &(Symbol::new(env, POOL_OBSERVATIONS_KEY), market_id.clone()),
+        &observations,
+    );
+}
+
+/// Price accumulator extrapolated to `at` from the latest observation at or before it
+fn cumulative_at(observations: &Vec<Observation>, at: u64) -> Option<u128> {
+    observations
+        .iter()
+        .rev()
+        .find(|observation| observation.timestamp <= at)
+        .map(|observation| {
+            observation.cumulative
+                + observation.price as u128 * (at - observation.timestamp) as u128
+        })
+}
+
+/// Trading fee of a pool: fixed at initialize_pool, else the global fee
+fn pool_fee_bps(env: &Env, market_id: &BytesN<32>) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&(Symbol::new(env, POOL_FEE_BPS_KEY), market_id.clone()))
+        .unwrap_or_else(|| {
+            env.storage()
+                .persistent()
+                .get(&Symbol::new(env, TRADING_FEE_KEY))
+                .unwrap_or(20)
+        })
+}
+
+/// Trade run through a pool's curve, before any state changes
+struct Fill {
+    quote: QuoteResult,
+    /// Curve state after the trade: reserves for CPMM, outstanding shares for LMSR
+    yes_reserve: u128,
+    no_reserve: u128,
+}
+
+/// Pricing curve of a pool; CPMM unless initialize_pool chose LMSR
+fn pricing_mode(env: &Env, market_id: &BytesN<32>) -> PricingMode {
+    env.storage()
+        .persistent()
+        .get(&(Symbol::new(env, POOL_PRICING_MODE_KEY), market_id.clone()))
+        .unwrap_or(PricingMode::Cpmm)
+}
+
+/// (yes, no) reserves of a pool that can be traded against
+fn trading_reserves(env: &Env, market_id: &BytesN<32>) -> (u128, u128) {
+    if !env
+        .storage()
+        .persistent()
+        .has(&(Symbol::new(env, POOL_EXISTS_KEY), market_id.clone()))
+    {
+        panic!("pool does not exist");
+    }
+    let yes_reserve: u128 = env
+        .storage()
+        .persistent()
+        .get(&(Symbol::new(env, POOL_YES_RESERVE_KEY), market_id.clone()))
+        .unwrap_or(0);
+    let no_reserve: u128 = env
+        .storage()
+        .persistent()
+        .get(&(Symbol::new(env, POOL_NO_RESERVE_KEY), market_id.clone()))
+        .unwrap_or(0);
+    if yes_reserve == 0 || no_reserve == 0 {
+        panic!("insufficient liquidity");
+    }
+    (yes_reserve, no_reserve)
+}
+
+/// (yes, no) shares an LMSR pool has sold
+fn lmsr_shares(env: &Env, market_id: &BytesN<32>) -> (u128, u128) {
+    if !env
+        .storage()
+        .persistent()
+        .has(&(Symbol::new(env, POOL_EXISTS_KEY), market_id.clone()))
+    {
+        panic!("pool does not exist");
+    }
+    env.storage()
+        .persistent()
+        .get(&(Symbol::new(env, POOL_LMSR_SHARES_KEY), market_id.clone()))
+        .unwrap_or((0, 0))
+}
+
+/// (yes, no) curve state a trade runs against
+fn curve_state(env: &Env, market_id: &BytesN<32>, mode: &PricingMode) -> (u128, u128) {
+    match mode {
+        PricingMode::Cpmm => trading_reserves(env, market_id),
+        PricingMode::Lmsr(_) => lmsr_shares(env, market_id),
+    }
+}
+
+fn set_reserves(env: &Env, market_id: &BytesN<32>, yes_reserve: u128, no_reserve: u128) {
+    env.storage().persistent().set(
+        &(Symbol::new(env, POOL_YES_RESERVE_KEY), market_id.clone()),
+        &yes_reserve,
+    );
+    env.storage().persistent().set(
+        &(Symbol::new(env, POOL_NO_RESERVE_KEY), market_id.clone()),
+        &no_reserve,
+    );
+}
+
+/// Write a fill's post-trade curve state
+fn store_fill(env: &Env, market_id: &BytesN<32>, fill: &Fill) {
+    match pricing_mode(env, market_id) {
+        PricingMode::Cpmm => set_reserves(env, market_id, fill.yes_reserve, fill.no_reserve),
+        PricingMode::Lmsr(_) => env.storage().persistent().set(
+            &(Symbol::new(env, POOL_LMSR_SHARES_KEY), market_id.clone()),
+            &(fill.yes_reserve, fill.no_reserve),
+        ),
+    }
+}
+
+/// Marginal price of `outcome` in basis points: the opposite reserve's share
+fn outcome_price(yes_reserve: u128, no_reserve: u128, outcome: u32) -> u32 {
+    let opposite = if outcome == 1 {
+        no_reserve
+    } else {
+        yes_reserve
+    };
+    (opposite.checked_mul(10000).expect("price overflow") / (yes_reserve + no_reserve)) as u32
+}
+
+/// Marginal price of `outcome` in basis points for either curve
+fn curve_price(mode: &PricingMode, yes_state: u128, no_state: u128, outcome: u32) -> u32 {
+    match mode {
+        PricingMode::Cpmm => outcome_price(yes_state, no_state, outcome),
+        PricingMode::Lmsr(b) => lmsr_price(*b, yes_state, no_state, outcome),
+    }
+}
+
+/// How far a trade moved `outcome`'s price, in basis points of the pre-trade price
+fn price_impact_bps(
+    mode: &PricingMode,
+    yes_before: u128,
+    no_before: u128,
+    fill: &Fill,
+    outcome: u32,
+) -> u32 {
+    let before = curve_price(mode, yes_before, no_before, outcome);
+    if before == 0 {
+        return 0;
+    }
+    before.abs_diff(fill.quote.new_price) * 10000 / before
+}
+
+/// (traded, opposite) sides of a (yes, no) pair
+fn sides(yes: u128, no: u128, outcome: u32) -> (u128, u128) {
+    if outcome == 1 {
+        (yes, no)
+    } else {
+        (no, yes)
+    }
+}
+
+/// LMSR spread x = (q_outcome - q_other) / b, in WAD
+fn lmsr_spread(b: u128, q_outcome: u128, q_other: u128) -> i128 {
+    (q_outcome as i128 - q_other as i128)
+        .checked_mul(WAD)
+        .expect("lmsr overflow")
+        / b as i128
+}
+
+/// ln(1 + e^x) in WAD, arranged so exp only sees non-positive arguments
+fn softplus(x: i128) -> i128 {
+    if x >= 0 {
+        x + ln_wad(WAD + exp_wad(-x))
+    } else {
+        ln_wad(WAD + exp_wad(x))
+    }
+}
+
+/// WAD amount scaled by `b` back to collateral units, floored
+fn lmsr_units(b: u128, amount_wad: i128) -> u128 {
+    if amount_wad <= 0 {
+        return 0;
+    }
+    mul_div_floor(amount_wad as u128, b, WAD as u128)
+}
+
+/// Worst-case LMSR subsidy, b * ln(2), rounded up
+fn lmsr_max_loss(b: u128) -> u128 {
+    mul_div_ceil(b, LN2_WAD as u128, WAD as u128)
+}
+
+/// LMSR marginal price of `outcome` in basis points: 1 / (1 + e^-x)
+fn lmsr_price(b: u128, q_yes: u128, q_no: u128, outcome: u32) -> u32 {
+    let (q_outcome, q_other) = sides(q_yes, q_no, outcome);
+    let x = lmsr_spread(b, q_outcome, q_other);
+    let price = if x >= 0 {
+        WAD * WAD / (WAD + exp_wad(-x))
+    } else {
+        let e = exp_wad(x);
+        e * WAD / (WAD + e)
+    };
+    (price * 10000 / WAD) as u32
+}
+
+/// CPMM buy: shares_out and the (yes, no) reserves after the trade
+///
+/// shares_out = amount * reserve_out / (reserve_in + amount), floored. Buying
+/// YES pays into the NO reserve and takes from the YES reserve.
+fn cpmm_buy(yes_reserve: u128, no_reserve: u128, outcome: u32, amount: u128) -> (u128, u128, u128) {
+    let (reserve_out, reserve_in) = sides(yes_reserve, no_reserve, outcome);
+    let new_reserve_in = reserve_in.checked_add(amount).expect("reserve overflow");
+    let shares_out = mul_div_floor(amount, reserve_out, new_reserve_in);
+    let new_reserve_out = reserve_out - shares_out;
+
+    // k should increase due to fees, never decrease
+    let old_k = yes_reserve.checked_mul(no_reserve).expect("k overflow");
+    let new_k = new_reserve_in
+        .checked_mul(new_reserve_out)
+        .expect("k overflow");
+    if new_k < old_k {
+        panic!("invariant violation");
+    }
+
+    let (new_yes, new_no) = if outcome == 1 {
+        (new_reserve_out, new_reserve_in)
+    } else {
+        (new_reserve_in, new_reserve_out)
+    };
+    (shares_out, new_yes, new_no)
+}
+
+/// LMSR buy: shares_out and the (yes, no) shares sold after the trade
+///
+/// Solves C(q + shares) - C(q) = amount for the cost function
+/// C(q) = b * ln(e^(q_yes/b) + e^(q_no/b)). One base unit is held back from
+/// the floored result to cover the fixed-point error, so rounding favors the pool.
+fn lmsr_buy(b: u128, q_yes: u128, q_no: u128, outcome: u32, amount: u128) -> (u128, u128, u128) {
+    let (q_outcome, q_other) = sides(q_yes, q_no, outcome);
+    let x = lmsr_spread(b, q_outcome, q_other);
+    let alpha = (amount as i128).checked_mul(WAD).expect("lmsr overflow") / b as i128;
+
+    // softplus(x + delta) = softplus(x) + alpha, inverted as y + ln(1 - e^-y)
+    let y = softplus(x) + alpha;
+    let tail = WAD - exp_wad(-y);
+    let shares_out = if tail > 0 {
+        lmsr_units(b, y + ln_wad(tail) - x).saturating_sub(1)
+    } else {
+        0
+    };
+
+    let new_outcome = q_outcome.checked_add(shares_out).expect("lmsr overflow");
+    if outcome == 1 {
+        (shares_out, new_outcome, q_no)
+    } else {
+        (shares_out, q_yes, new_outcome)
+    }
+}
+
+/// CPMM sell: the payout and the (yes, no) reserves after the trade
+///
+/// payout = shares * reserve_out / (reserve_in + shares), floored. Selling YES
+/// adds to the YES reserve and pays out of the NO reserve.
+fn cpmm_sell(
+    yes_reserve: u128,
+    no_reserve: u128,
+    outcome: u32,
+    shares: u128,
+) -> (u128, u128, u128) {
+    let (reserve_in, reserve_out) = sides(yes_reserve, no_reserve, outcome);
+    let new_reserve_in = reserve_in.checked_add(shares).expect("reserve overflow");
+    let payout = mul_div_floor(shares, reserve_out, new_reserve_in);
+    let new_reserve_out = reserve_out - payout;
+    if new_reserve_out == 0 {
+        panic!("insufficient pool liquidity");
+    }
+
+    let (new_yes, new_no) = if outcome == 1 {
+        (new_reserve_in, new_reserve_out)
+    } else {
+        (new_reserve_out, new_reserve_in)
+    };
+    (payout, new_yes, new_no)
+}
+
+/// LMSR sell: the payout C(q) - C(q - shares) and the shares sold after the trade
+///
+/// Floored with one base unit held back, like lmsr_buy.
+fn lmsr_sell(b: u128, q_yes: u128, q_no: u128, outcome: u32, shares: u128) -> (u128, u128, u128) {
+    let (q_outcome, q_other) = sides(q_yes, q_no, outcome);
+    let new_outcome = q_outcome
+        .checked_sub(shares)
+        .expect("insufficient pool liquidity");
+    let before = softplus(lmsr_spread(b, q_outcome, q_other));
+    let after = softplus(lmsr_spread(b, new_outcome, q_other));
+    let payout = lmsr_units(b, before - after).saturating_sub(1);
+
+    if outcome == 1 {
+        (payout, new_outcome, q_no)
+    } else {
+        (payout, q_yes, new_outcome)
+    }
+}
+
+/// LMSR liquidity is the fixed subsidy behind `b`; it cannot be topped up or
+/// withdrawn pro rata against reserves the curve does not use
+fn require_cpmm_liquidity(env: &Env, market_id: &BytesN<32>) {
+    if pricing_mode(env, market_id) != PricingMode::Cpmm {
+        panic!("lmsr pool liquidity is fixed");
+    }
+}
+
+/// Panic while the admin has the pool paused
+fn require_pool_unpaused(env: &Env, market_id: &BytesN<32>) {
+    if pool_paused(env, market_id) {
+        panic!("pool paused");
+    }
+}
+
+fn pool_paused(env: &Env, market_id: &BytesN<32>) -> bool {
+    env.storage()
+        .persistent()
+        .has(&(Symbol::new(env, POOL_PAUSED_KEY), market_id.clone()))
+}
+
+/// Panic once the pool's market is terminal; pools without a pinned market are not gated
+fn require_market_live(env: &Env, market_id: &BytesN<32>) {
+    if market_terminal(env, market_id) {
+        panic!("market closed");
+    }
+}
+
+/// Buy shared by buy_shares and quote_buy, on the pool's curve
+///
+/// The fee comes off the collateral first; the rest buys shares.
+fn buy_fill(env: &Env, market_id: &BytesN<32>, outcome: u32, amount: u128) -> Fill {
+    if outcome > 1 {
+        panic!("outcome must be 0 (NO) or 1 (YES)");
+    }
+    if amount == 0 {
+        panic!("amount must be greater than 0");
+    }
+    require_pool_unpaused(env, market_id);
+    require_market_open(env, market_id);
+    let mode = pricing_mode(env, market_id);
+    let (yes_before, no_before) = curve_state(env, market_id, &mode);
+    buy_on(
+        &mode,
+        pool_fee_bps(env, market_id),
+        yes_before,
+        no_before,
+        outcome,
+        amount,
+    )
+}
+
+/// Buy against a given curve state, without reading or writing storage
+fn buy_on(
+    mode: &PricingMode,
+    fee_bps: u32,
+    yes_before: u128,
+    no_before: u128,
+    outcome: u32,
+    amount: u128,
+) -> Fill {
+    let fee = bps_of(amount, fee_bps);
+    let amount_after_fee = amount - fee;
+
+    let (shares_out, new_yes, new_no) = match mode {
+        PricingMode::Cpmm => cpmm_buy(yes_before, no_before, outcome, amount_after_fee),
+        PricingMode::Lmsr(b) => lmsr_buy(*b, yes_before, no_before, outcome, amount_after_fee),
+    };
+    let mut fill = Fill {
+        quote: QuoteResult {
+            shares_out,
+            collateral_out: 0,
+            fee,
+            new_price: curve_price(mode, new_yes, new_no, outcome),
+            price_impact_bps: 0,
+        },
+        yes_reserve: new_yes,
+        no_reserve: new_no,
+    };
+    fill.quote.price_impact_bps = price_impact_bps(mode, yes_before, no_before, &fill, outcome);
+    fill
+}
+
+/// Sell shared by sell_shares and quote_sell, on the pool's curve
+///
+/// The fee is taken from the payout.
+fn sell_fill(env: &Env, market_id: &BytesN<32>, outcome: u32, shares: u128) -> Fill {
+    if outcome > 1 {
+        panic!("Invalid outcome: must be 0 (NO) or 1 (YES)");
+    }
+    if shares == 0 {
+        panic!("Shares execution amount must be positive");
+    }
+    require_pool_unpaused(env, market_id);
+    require_market_live(env, market_id);
+    let mode = pricing_mode(env, market_id);
+    let (yes_before, no_before) = curve_state(env, market_id, &mode);
+    sell_on(
+        &mode,
+        pool_fee_bps(env, market_id),
+        yes_before,
+        no_before,
+        outcome,
+        shares,
+    )
+}
+
+/// Sell against a given curve state, without reading or writing storage
+fn sell_on(
+    mode: &PricingMode,
+    fee_bps: u32,
+    yes_before: u128,
+    no_before: u128,
+    outcome: u32,
+    shares: u128,
+) -> Fill {
+    let (payout, new_yes, new_no) = match mode {
+        PricingMode::Cpmm => cpmm_sell(yes_before, no_before, outcome, shares),
+        PricingMode::Lmsr(b) => lmsr_sell(*b, yes_before, no_before, outcome, shares),
+    };
+    let fee = bps_of(payout, fee_bps);
+    let mut fill = Fill {
+        quote: QuoteResult {
+            shares_out: 0,
+            collateral_out: payout - fee,
+            fee,
+            new_price: curve_price(mode, new_yes, new_no, outcome),
+            price_impact_bps: 0,
+        },
+        yes_reserve: new_yes,
+        no_reserve: new_no,
+    };
+    fill.quote.price_impact_bps = price_impact_bps(mode, yes_before, no_before, &fill, outcome);
+    fill
+}
+
+/// Swap shared by swap_outcome and quote_swap_outcome: sell `shares_in` of
+/// `from_outcome`, then buy `to_outcome` with the whole net payout, each leg
+/// on the curve state the previous one left
+///
+/// Returns the (sell, buy) legs; the buy leg carries the pool's final state.
+fn swap_fill(
+    env: &Env,
+    market_id: &BytesN<32>,
+    from_outcome: u32,
+    to_outcome: u32,
+    shares_in: u128,
+) -> (Fill, Fill) {
+    if from_outcome > 1 || to_outcome > 1 {
+        panic!("outcome must be 0 (NO) or 1 (YES)");
+    }
+    if from_outcome == to_outcome {
+        panic!("outcomes must differ");
+    }
+    if shares_in == 0 {
+        panic!("Shares execution amount must be positive");
+    }
+    require_pool_unpaused(env, market_id);
+    require_market_open(env, market_id);
+    let mode = pricing_mode(env, market_id);
+    let fee_bps = pool_fee_bps(env, market_id);
+    let (yes_before, no_before) = curve_state(env, market_id, &mode);
+
+    let sell = sell_on(
+        &mode,
+        fee_bps,
+        yes_before,
+        no_before,
+        from_outcome,
+        shares_in,
+    );
+    if sell.quote.collateral_out == 0 {
+        panic!("swap amount too small");
+    }
+    let buy = buy_on(
+        &mode,
+        fee_bps,
+        sell.yes_reserve,
+        sell.no_reserve,
+        to_outcome,
+        sell.quote.collateral_out,
+    );
+    (sell, buy)
 }
 
 /// AUTOMATED MARKET MAKER - Manages liquidity pools and share trading
@@ -173,73 +1332,302 @@ impl AMM {
     pub fn create_pool(env: Env, creator: Address, market_id: BytesN<32>, initial_liquidity: u128) {
         // Require creator auth to transfer USDC
         creator.require_auth();
+        open_pool(&env, &creator, &market_id, initial_liquidity);
+    }
 
-        // Check if pool already exists
-        let pool_exists_key = (Symbol::new(&env, POOL_EXISTS_KEY), market_id.clone());
-        if env.storage().persistent().has(&pool_exists_key) {
+    /// Admin: Open the pool for `market` with its own collateral token, swap fee and curve
+    ///
+    /// `admin` supplies `initial_liquidity` of `collateral_token` and holds the
+    /// initial LP tokens. `fee_bps` is capped at MAX_POOL_FEE_BPS. An LMSR pool's
+    /// liquidity must cover its worst-case loss of b * ln(2), and is fixed from then on.
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize_pool(
+        env: Env,
+        admin: Address,
+        market_id: BytesN<32>,
+        market: Address,
+        collateral_token: Address,
+        initial_liquidity: u128,
+        fee_bps: u32,
+        mode: PricingMode,
+    ) {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, ADMIN_KEY))
+            .expect("amm not initialized");
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&(Symbol::new(&env, POOL_EXISTS_KEY), market_id.clone()))
+        {
             panic!("pool already exists");
         }
-
-        // Validate initial liquidity
-        if initial_liquidity == 0 {
-            panic!("initial liquidity must be greater than 0");
+        if fee_bps > MAX_POOL_FEE_BPS {
+            panic!("fee exceeds maximum");
+        }
+        if let PricingMode::Lmsr(b) = mode {
+            if b == 0 {
+                panic!("lmsr liquidity parameter must be positive");
+            }
+            if initial_liquidity < lmsr_max_loss(b) {
+                panic!("liquidity below lmsr max loss");
+            }
         }
 
-        // Initialize 50/50 split
-        let yes_reserve = initial_liquidity / 2;
-        let no_reserve = initial_liquidity / 2;
+        env.storage().persistent().set(
+            &(Symbol::new(&env, POOL_MARKET_KEY), market_id.clone()),
+            &market,
+        );
+        env.storage().persistent().set(
+            &(
+                Symbol::new(&env, POOL_COLLATERAL_TOKEN_KEY),
+                market_id.clone(),
+            ),
+            &collateral_token,
+        );
+        env.storage().persistent().set(
+            &(Symbol::new(&env, POOL_FEE_BPS_KEY), market_id.clone()),
+            &fee_bps,
+        );
+        env.storage().persistent().set(
+            &(Symbol::new(&env, POOL_PRICING_MODE_KEY), market_id.clone()),
+            &mode,
+        );
+        open_pool(&env, &admin, &market_id, initial_liquidity);
 
-        // Calculate constant product k = x * y
-        let k = yes_reserve * no_reserve;
+        events::amm::pool_initialized(&env, market_id, market, collateral_token, initial_liquidity);
+    }
 
-        // Create storage keys for this pool using tuples
-        let yes_key = (Symbol::new(&env, POOL_YES_RESERVE_KEY), market_id.clone());
-        let no_key = (Symbol::new(&env, POOL_NO_RESERVE_KEY), market_id.clone());
-        let k_key = (Symbol::new(&env, POOL_K_KEY), market_id.clone());
-        let lp_supply_key = (Symbol::new(&env, POOL_LP_SUPPLY_KEY), market_id.clone());
-        let lp_balance_key = (
-            Symbol::new(&env, POOL_LP_TOKENS_KEY),
-            market_id.clone(),
-            creator.clone(),
-        );
+    /// Get a pool's pricing curve
+    pub fn get_pricing_mode(env: Env, market_id: BytesN<32>) -> PricingMode {
+        pricing_mode(&env, &market_id)
+    }
 
-        // Store reserves
-        env.storage().persistent().set(&yes_key, &yes_reserve);
-        env.storage().persistent().set(&no_key, &no_reserve);
-        env.storage().persistent().set(&k_key, &k);
-        env.storage().persistent().set(&pool_exists_key, &true);
+    /// Get the (YES, NO) shares an LMSR pool has sold; (0, 0) for CPMM pools
+    pub fn get_lmsr_shares(env: Env, market_id: BytesN<32>) -> (u128, u128) {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, POOL_LMSR_SHARES_KEY), market_id))
+            .unwrap_or((0, 0))
+    }
 
-        // Mint LP tokens to creator (equal to initial_liquidity for first LP)
-        let lp_tokens = initial_liquidity;
-        env.storage().persistent().set(&lp_supply_key, &lp_tokens);
-        env.storage().persistent().set(&lp_balance_key, &lp_tokens);
+    /// Get a pool's (YES, NO) share reserves
+    pub fn get_reserves(env: Env, market_id: BytesN<32>) -> (i128, i128) {
+        let yes: u128 = env
+            .storage()
+            .persistent()
+            .get(&(Symbol::new(&env, POOL_YES_RESERVE_KEY), market_id.clone()))
+            .unwrap_or(0);
+        let no: u128 = env
+            .storage()
+            .persistent()
+            .get(&(Symbol::new(&env, POOL_NO_RESERVE_KEY), market_id))
+            .unwrap_or(0);
+        (yes as i128, no as i128)
+    }
 
-        // Transfer USDC from creator to contract
-        let usdc_token: Address = env
+    /// Collateral held for a pool: liquidity plus trade flow, unclaimed LP fees
+    /// and treasury fees not yet flushed
+    pub fn get_collateral_balance(env: Env, market_id: BytesN<32>) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, POOL_COLLATERAL_KEY), market_id))
+            .unwrap_or(0)
+    }
+
+    /// Get the collateral token a pool trades in (USDC unless set by initialize_pool)
+    pub fn get_pool_collateral_token(env: Env, market_id: BytesN<32>) -> Address {
+        pool_collateral(&env, &market_id)
+    }
+
+    /// Get the market contract bound to a pool by initialize_pool
+    pub fn get_pool_market(env: Env, market_id: BytesN<32>) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, POOL_MARKET_KEY), market_id))
+    }
+
+    /// Get a pool's trading fee in basis points
+    pub fn get_pool_fee_bps(env: Env, market_id: BytesN<32>) -> u32 {
+        pool_fee_bps(&env, &market_id)
+    }
+
+    /// Admin: Change a pool's swap fee while its only liquidity is the initial seed
+    pub fn set_pool_fee(env: Env, admin: Address, market_id: BytesN<32>, fee_bps: u32) {
+        admin.require_auth();
+        let stored_admin: Address = env
             .storage()
             .persistent()
-            .get(&Symbol::new(&env, USDC_KEY))
-            .expect("usdc token not set");
-
-        let token_client = token::Client::new(&env, &usdc_token);
-        token_client.transfer(
-            &creator,
-            env.current_contract_address(),
-            &(initial_liquidity as i128),
+            .get(&Symbol::new(&env, ADMIN_KEY))
+            .expect("amm not initialized");
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        if fee_bps > MAX_POOL_FEE_BPS {
+            panic!("fee exceeds maximum");
+        }
+        let seed: u128 = env
+            .storage()
+            .persistent()
+            .get(&(Symbol::new(&env, POOL_SEED_LP_KEY), market_id.clone()))
+            .expect("pool does not exist");
+        if Self::get_lp_total_supply(env.clone(), market_id.clone()) != seed {
+            panic!("pool has outside liquidity");
+        }
+
+        env.storage().persistent().set(
+            &(Symbol::new(&env, POOL_FEE_BPS_KEY), market_id.clone()),
+            &fee_bps,
         );
+        events::amm::pool_fee_set(&env, market_id, fee_bps);
+    }
 
-        // Emit PoolCreated event
-        PoolCreatedEvent {
-            market_id,
-            initial_liquidity,
-            yes_reserve,
-            no_reserve,
+    /// Admin: Set the share of every swap fee (basis points of the fee) kept for the treasury
+    ///
+    /// Applies to fees taken from now on; LPs get the rest.
+    pub fn set_treasury_fee_share(env: Env, admin: Address, share_bps: u32) {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, ADMIN_KEY))
+            .expect("amm not initialized");
+        if admin != stored_admin {
+            panic!("unauthorized");
         }
-        .publish(&env);
+        if share_bps > 10000 {
+            panic!("share exceeds 100%");
+        }
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, TREASURY_FEE_SHARE_KEY), &share_bps);
+    }
+
+    /// Admin: Freeze a pool's trading and deposits
+    ///
+    /// buy_shares, sell_shares, their quotes and add_liquidity are rejected
+    /// until unpause_pool. LPs can still exit through remove_liquidity, and
+    /// settle_pool and redeem_shares work as usual.
+    pub fn pause_pool(env: Env, admin: Address, market_id: BytesN<32>) {
+        Self::set_pool_paused(&env, &admin, market_id, true);
+    }
+
+    /// Admin: Lift a pause set by pause_pool
+    pub fn unpause_pool(env: Env, admin: Address, market_id: BytesN<32>) {
+        Self::set_pool_paused(&env, &admin, market_id, false);
+    }
+
+    /// Whether a pool is paused by the admin
+    pub fn is_pool_paused(env: Env, market_id: BytesN<32>) -> bool {
+        pool_paused(&env, &market_id)
+    }
+
+    fn set_pool_paused(env: &Env, admin: &Address, market_id: BytesN<32>, paused: bool) {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, ADMIN_KEY))
+            .expect("amm not initialized");
+        if *admin != stored_admin {
+            panic!("unauthorized");
+        }
+        if !env
+            .storage()
+            .persistent()
+            .has(&(Symbol::new(env, POOL_EXISTS_KEY), market_id.clone()))
+        {
+            panic!("pool does not exist");
+        }
+
+        let key = (Symbol::new(env, POOL_PAUSED_KEY), market_id.clone());
+        if paused {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        events::amm::pool_paused(env, market_id, paused);
+    }
+
+    /// Get a pool's swap fee and its LP / treasury split
+    pub fn get_fee_config(env: Env, market_id: BytesN<32>) -> FeeConfig {
+        let treasury_share_bps: u32 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, TREASURY_FEE_SHARE_KEY))
+            .unwrap_or(0);
+        FeeConfig {
+            fee_bps: pool_fee_bps(&env, &market_id),
+            lp_share_bps: 10000 - treasury_share_bps,
+            treasury_share_bps,
+        }
+    }
+
+    /// Get the treasury fees a pool has collected but not yet flushed
+    pub fn get_pending_treasury_fees(env: Env, market_id: BytesN<32>) -> u128 {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, POOL_TREASURY_FEES_KEY), market_id))
+            .unwrap_or(0)
+    }
+
+    /// Send a pool's pending treasury fees to the factory's treasury (permissionless)
+    ///
+    /// The treasury pulls them through `deposit_fee`, so the AMM must be one of
+    /// its depositors and the pool's collateral a supported token. Returns the
+    /// amount sent (0 when nothing is pending).
+    pub fn flush_fees_to_treasury(env: Env, market_id: BytesN<32>) -> u128 {
+        let guard = guard::enter(&env);
+        let key = (Symbol::new(&env, POOL_TREASURY_FEES_KEY), market_id.clone());
+        let amount: u128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount == 0 {
+            return 0;
+        }
+        let factory: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, FACTORY_KEY))
+            .expect("amm not initialized");
+        let treasury: Address =
+            env.invoke_contract(&factory, &Symbol::new(&env, "get_treasury"), Vec::new(&env));
+
+        env.storage().persistent().set(&key, &0u128);
+        adjust_pool_collateral(&env, &market_id, -(amount as i128));
+
+        let token = pool_collateral(&env, &market_id);
+        let amm = env.current_contract_address();
+        env.authorize_as_current_contract(soroban_sdk::vec![
+            &env,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: token.clone(),
+                    fn_name: Symbol::new(&env, "transfer"),
+                    args: (amm.clone(), treasury.clone(), amount as i128).into_val(&env),
+                },
+                sub_invocations: Vec::new(&env),
+            }),
+        ]);
+        // The treasury pulls the fees itself, so the guard stays held across the call
+        env.invoke_contract::<()>(
+            &treasury,
+            &Symbol::new(&env, "deposit_fee"),
+            (amm, market_id.clone(), token, amount as i128).into_val(&env),
+        );
+        guard.commit();
+
+        events::amm::treasury_fees_flushed(&env, market_id, treasury, amount);
+        amount
     }
 
     /// Buy outcome shares (YES or NO)
-    /// Uses Constant Product Market Maker (CPMM) formula: x * y = k
+    /// Prices on the pool's curve: constant product x * y = k, or LMSR if chosen at initialize_pool
+    /// Share output is floored, so rounding always favors the pool.
+    /// Rejected once the pool's market passes its betting cutoff or leaves Open.
     /// Returns number of shares purchased
     pub fn buy_shares(
         env: Env,
@@ -251,110 +1639,29 @@ impl AMM {
     ) -> u128 {
         // Require buyer authentication
         buyer.require_auth();
+        let mut guard = guard::enter(&env);
 
-        // Validate inputs
-        if outcome > 1 {
-            panic!("outcome must be 0 (NO) or 1 (YES)");
-        }
-        if amount == 0 {
-            panic!("amount must be greater than 0");
-        }
-
-        // Check if pool exists
-        let pool_exists_key = (Symbol::new(&env, POOL_EXISTS_KEY), market_id.clone());
-        if !env.storage().persistent().has(&pool_exists_key) {
-            panic!("pool does not exist");
-        }
-
-        // Get current reserves
-        let yes_key = (Symbol::new(&env, POOL_YES_RESERVE_KEY), market_id.clone());
-        let no_key = (Symbol::new(&env, POOL_NO_RESERVE_KEY), market_id.clone());
-
-        let yes_reserve: u128 = env.storage().persistent().get(&yes_key).unwrap_or(0);
-        let no_reserve: u128 = env.storage().persistent().get(&no_key).unwrap_or(0);
-
-        if yes_reserve == 0 || no_reserve == 0 {
-            panic!("insufficient liquidity");
+        let fill = buy_fill(&env, &market_id, outcome, amount);
+        if let Some(registry) = Self::get_pool_kyc_registry(env.clone(), market_id.clone()) {
+            if !kyc::is_verified(&env, &registry, &buyer) {
+                panic!("buyer not verified");
+            }
         }
 
-        // Calculate trading fee (20 basis points = 0.2%)
-        let trading_fee_bps: u128 = env
-            .storage()
-            .persistent()
-            .get(&Symbol::new(&env, TRADING_FEE_KEY))
-            .unwrap_or(20);
-
-        let fee_amount = (amount * trading_fee_bps) / 10000;
-        let amount_after_fee = amount - fee_amount;
-
-        // CPMM calculation: shares_out = (amount_in * reserve_out) / (reserve_in + amount_in)
-        let (reserve_in, reserve_out, new_reserve_in, new_reserve_out) = if outcome == 1 {
-            // Buying YES shares: pay with USDC, get YES shares
-            // Input reserve is NO (what we're paying with conceptually in CPMM mapping)
-            // Output reserve is YES (what we're getting)
-            let shares_out = (amount_after_fee * yes_reserve) / (no_reserve + amount_after_fee);
-            (
-                no_reserve,
-                yes_reserve,
-                no_reserve + amount_after_fee,
-                yes_reserve - shares_out,
-            )
-        } else {
-            // Buying NO shares: pay with USDC, get NO shares
-            let shares_out = (amount_after_fee * no_reserve) / (yes_reserve + amount_after_fee);
-            (
-                yes_reserve,
-                no_reserve,
-                yes_reserve + amount_after_fee,
-                no_reserve - shares_out,
-            )
-        };
-
-        let shares_out = (amount_after_fee * reserve_out) / (reserve_in + amount_after_fee);
-
         // Slippage protection
+        let shares_out = fill.quote.shares_out;
         if shares_out < min_shares {
             panic!(
                 "Slippage exceeded: would receive {} shares, minimum is {}",
                 shares_out, min_shares
             );
         }
+        store_fill(&env, &market_id, &fill);
 
-        // Verify CPMM invariant (k should increase due to fees, never decrease)
-        let old_k = yes_reserve * no_reserve;
-        let new_k = new_reserve_in * new_reserve_out;
-        if new_k < old_k {
-            panic!("invariant violation");
-        }
-
-        // Update reserves
-        if outcome == 1 {
-            // Bought YES: increase NO reserve, decrease YES reserve
-            env.storage()
-                .persistent()
-                .set(&no_key, &(no_reserve + amount_after_fee));
-            env.storage()
-                .persistent()
-                .set(&yes_key, &(yes_reserve - shares_out));
-        } else {
-            // Bought NO: increase YES reserve, decrease NO reserve
-            env.storage()
-                .persistent()
-                .set(&yes_key, &(yes_reserve + amount_after_fee));
-            env.storage()
-                .persistent()
-                .set(&no_key, &(no_reserve - shares_out));
-        }
-
-        // Transfer USDC from buyer to contract
-        let usdc_token: Address = env
-            .storage()
-            .persistent()
-            .get(&Symbol::new(&env, USDC_KEY))
-            .expect("usdc token not set");
-
-        let token_client = token::Client::new(&env, &usdc_token);
-        token_client.transfer(&buyer, env.current_contract_address(), &(amount as i128));
+        adjust_pool_collateral(&env, &market_id, amount as i128);
+        collect_fee(&env, &market_id, fill.quote.fee);
+        record_trade_time(&env, &market_id);
+        record_observation(&env, &market_id);
 
         // Update User Shares Balance
         let user_share_key = (
@@ -366,7 +1673,17 @@ impl AMM {
         let current_shares: u128 = env.storage().persistent().get(&user_share_key).unwrap_or(0);
         env.storage()
             .persistent()
-            .set(&user_share_key, &(current_shares + shares_out));
+            .set(&user_share_key, &safe_add_u128(current_shares, shares_out));
+        adjust_outstanding(&env, &market_id, outcome, shares_out as i128);
+
+        // Transfer collateral from buyer to contract
+        guard.transfer(
+            &pool_collateral(&env, &market_id),
+            &buyer,
+            &env.current_contract_address(),
+            amount as i128,
+        );
+        guard.commit();
 
         // Record trade (Optional: Simplified to event only for this resolution)
         BuySharesEvent {
@@ -375,14 +1692,24 @@ impl AMM {
             outcome,
             shares_out,
             amount,
-            fee_amount,
+            fee_amount: fill.quote.fee,
+            new_price: fill.quote.new_price,
         }
         .publish(&env);
 
         shares_out
     }
 
+    /// Shares of `outcome` a user holds in a pool
+    pub fn get_user_shares(env: Env, market_id: BytesN<32>, user: Address, outcome: u32) -> u128 {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, USER_SHARES_KEY), market_id, user, outcome))
+            .unwrap_or(0)
+    }
+
     /// Sell outcome shares back to AMM
+    /// Rejected once the pool's market is terminal (Resolved, Cancelled, Refunding).
     /// Returns USDC payout amount
     pub fn sell_shares(
         env: Env,
@@ -393,19 +1720,9 @@ impl AMM {
         min_payout: u128,
     ) -> u128 {
         seller.require_auth();
+        let mut guard = guard::enter(&env);
 
-        if outcome > 1 {
-            panic!("Invalid outcome: must be 0 (NO) or 1 (YES)");
-        }
-        if shares == 0 {
-            panic!("Shares execution amount must be positive");
-        }
-
-        // Check if pool exists
-        let pool_exists_key = (Symbol::new(&env, POOL_EXISTS_KEY), market_id.clone());
-        if !env.storage().persistent().has(&pool_exists_key) {
-            panic!("pool does not exist");
-        }
+        let fill = sell_fill(&env, &market_id, outcome, shares);
 
         // Check user share balance
         let user_share_key = (
@@ -419,104 +1736,189 @@ impl AMM {
             panic!("Insufficient shares balance");
         }
 
-        // Get current reserves
-        let yes_key = (Symbol::new(&env, POOL_YES_RESERVE_KEY), market_id.clone());
-        let no_key = (Symbol::new(&env, POOL_NO_RESERVE_KEY), market_id.clone());
-
-        let yes_reserve: u128 = env.storage().persistent().get(&yes_key).unwrap_or(0);
-        let no_reserve: u128 = env.storage().persistent().get(&no_key).unwrap_or(0);
-
-        if yes_reserve == 0 || no_reserve == 0 {
-            panic!("insufficient liquidity");
-        }
-
-        // CPMM calculation for selling: payout = (shares * reserve_out) / (reserve_in + shares)
-        let payout = if outcome == 1 {
-            // Selling YES shares: get USDC back
-            // Input reserve is YES (what we're selling)
-            // Output reserve is NO (what we're getting paid from)
-            (shares * no_reserve) / (yes_reserve + shares)
-        } else {
-            // Selling NO shares: get USDC back
-            (shares * yes_reserve) / (no_reserve + shares)
-        };
-
-        // Calculate trading fee (20 basis points = 0.2%)
-        let trading_fee_bps: u128 = env
-            .storage()
-            .persistent()
-            .get(&Symbol::new(&env, TRADING_FEE_KEY))
-            .unwrap_or(20);
-
-        let fee_amount = (payout * trading_fee_bps) / 10000;
-        let payout_after_fee = payout - fee_amount;
-
         // Slippage protection
+        let payout_after_fee = fill.quote.collateral_out;
         if payout_after_fee < min_payout {
             panic!(
                 "Slippage exceeded: would receive {} USDC, minimum is {}",
                 payout_after_fee, min_payout
             );
         }
+        store_fill(&env, &market_id, &fill);
 
-        // Update reserves
-        if outcome == 1 {
-            // Sold YES: increase YES reserve, decrease NO reserve
-            env.storage()
-                .persistent()
-                .set(&yes_key, &(yes_reserve + shares));
-            env.storage()
-                .persistent()
-                .set(&no_key, &(no_reserve - payout));
-        } else {
-            // Sold NO: increase NO reserve, decrease YES reserve
-            env.storage()
-                .persistent()
-                .set(&no_key, &(no_reserve + shares));
-            env.storage()
-                .persistent()
-                .set(&yes_key, &(yes_reserve - payout));
+        // Burn user shares
+        env.storage()
+            .persistent()
+            .set(&user_share_key, &safe_sub_u128(user_shares, shares));
+        adjust_outstanding(&env, &market_id, outcome, -(shares as i128));
+
+        collect_fee(&env, &market_id, fill.quote.fee);
+        record_trade_time(&env, &market_id);
+        record_observation(&env, &market_id);
+
+        // Transfer collateral to seller
+        adjust_pool_collateral(&env, &market_id, -(payout_after_fee as i128));
+        guard.transfer(
+            &pool_collateral(&env, &market_id),
+            &env.current_contract_address(),
+            &seller,
+            payout_after_fee as i128,
+        );
+        guard.commit();
+
+        // Emit SellShares event
+        SellSharesEvent {
+            seller,
+            market_id,
+            outcome,
+            shares,
+            payout_after_fee,
+            fee_amount: fill.quote.fee,
         }
+        .publish(&env);
 
-        // Verify reserves remain positive
-        let new_yes: u128 = env.storage().persistent().get(&yes_key).unwrap_or(0);
-        let new_no: u128 = env.storage().persistent().get(&no_key).unwrap_or(0);
+        payout_after_fee
+    }
+
+    /// Flip shares of `from_outcome` into `to_outcome` in one call
+    ///
+    /// Runs sell_shares then buy_shares on the curve back to back, the buy
+    /// spending the sell's whole net payout; each leg pays the pool fee. No
+    /// collateral moves. Reverts entirely below `min_shares_out`, and where
+    /// either leg would: missing pool, paused pool, market past its cutoff or
+    /// not Open. Returns the shares of `to_outcome` received.
+    pub fn swap_outcome(
+        env: Env,
+        user: Address,
+        market_id: BytesN<32>,
+        from_outcome: u32,
+        to_outcome: u32,
+        shares_in: u128,
+        min_shares_out: u128,
+    ) -> u128 {
+        user.require_auth();
+        let guard = guard::enter(&env);
 
-        if new_yes == 0 || new_no == 0 {
-            panic!("insufficient pool liquidity");
+        let (sell, buy) = swap_fill(&env, &market_id, from_outcome, to_outcome, shares_in);
+        if let Some(registry) = Self::get_pool_kyc_registry(env.clone(), market_id.clone()) {
+            if !kyc::is_verified(&env, &registry, &user) {
+                panic!("buyer not verified");
+            }
         }
 
-        // Burn user shares
-        env.storage()
-            .persistent()
-            .set(&user_share_key, &(user_shares - shares));
+        let from_key = (
+            Symbol::new(&env, USER_SHARES_KEY),
+            market_id.clone(),
+            user.clone(),
+            from_outcome,
+        );
+        let from_shares: u128 = env.storage().persistent().get(&from_key).unwrap_or(0);
+        if from_shares < shares_in {
+            panic!("Insufficient shares balance");
+        }
+        let shares_out = buy.quote.shares_out;
+        if shares_out < min_shares_out {
+            panic!(
+                "Slippage exceeded: would receive {} shares, minimum is {}",
+                shares_out, min_shares_out
+            );
+        }
+        store_fill(&env, &market_id, &buy);
+
+        env.storage()
+            .persistent()
+            .set(&from_key, &safe_sub_u128(from_shares, shares_in));
+        adjust_outstanding(&env, &market_id, from_outcome, -(shares_in as i128));
+        let to_key = (
+            Symbol::new(&env, USER_SHARES_KEY),
+            market_id.clone(),
+            user.clone(),
+            to_outcome,
+        );
+        let to_shares: u128 = env.storage().persistent().get(&to_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&to_key, &safe_add_u128(to_shares, shares_out));
+        adjust_outstanding(&env, &market_id, to_outcome, shares_out as i128);
+
+        // The routed collateral leaves and re-enters the pool, so only fees are booked
+        collect_fee(&env, &market_id, sell.quote.fee);
+        collect_fee(&env, &market_id, buy.quote.fee);
+        record_trade_time(&env, &market_id);
+        record_observation(&env, &market_id);
+        guard.commit();
+
+        events::amm::outcome_swapped(
+            &env,
+            market_id,
+            user,
+            from_outcome,
+            to_outcome,
+            shares_in,
+            shares_out,
+            sell.quote.fee + buy.quote.fee,
+        );
+
+        shares_out
+    }
 
-        // Transfer USDC to seller
-        let usdc_address: Address = env
-            .storage()
-            .persistent()
-            .get(&Symbol::new(&env, USDC_KEY))
-            .expect("USDC token not configured");
-        let usdc_client = soroban_sdk::token::Client::new(&env, &usdc_address);
+    /// Preview buy_shares without trading
+    ///
+    /// Runs the same curve math as the executing call and panics where it
+    /// would: missing or drained pool, market past its cutoff or not Open.
+    pub fn quote_buy(
+        env: Env,
+        market_id: BytesN<32>,
+        outcome: u32,
+        collateral_in: u128,
+    ) -> QuoteResult {
+        buy_fill(&env, &market_id, outcome, collateral_in).quote
+    }
 
-        usdc_client.transfer(
-            &env.current_contract_address(),
-            &seller,
-            &(payout_after_fee as i128),
-        );
+    /// Preview sell_shares without trading (the seller's balance is not checked)
+    ///
+    /// Runs the same curve math as the executing call and panics where it
+    /// would: missing or drained pool, terminal market.
+    pub fn quote_sell(
+        env: Env,
+        market_id: BytesN<32>,
+        outcome: u32,
+        shares_in: u128,
+    ) -> QuoteResult {
+        sell_fill(&env, &market_id, outcome, shares_in).quote
+    }
 
-        // Emit SellShares event
-        SellSharesEvent {
-            seller,
-            market_id,
-            outcome,
-            shares,
-            payout_after_fee,
-            fee_amount,
+    /// Preview swap_outcome without trading (the user's balance is not checked)
+    pub fn quote_swap_outcome(
+        env: Env,
+        market_id: BytesN<32>,
+        from_outcome: u32,
+        to_outcome: u32,
+        shares_in: u128,
+    ) -> SwapQuote {
+        let (sell, buy) = swap_fill(&env, &market_id, from_outcome, to_outcome, shares_in);
+        SwapQuote {
+            shares_out: buy.quote.shares_out,
+            collateral_routed: sell.quote.collateral_out,
+            sell_fee: sell.quote.fee,
+            buy_fee: buy.quote.fee,
+            new_price: buy.quote.new_price,
         }
-        .publish(&env);
+    }
 
-        payout_after_fee
+    /// Marginal price of `outcome` net of the pool's trading fee, in basis points of probability
+    ///
+    /// YES + NO comes to 10000 minus the fee spread (and rounding).
+    /// Panics for a missing or drained pool, or a terminal market.
+    pub fn get_spot_price(env: Env, market_id: BytesN<32>, outcome: u32) -> u32 {
+        if outcome > 1 {
+            panic!("outcome must be 0 (NO) or 1 (YES)");
+        }
+        require_market_live(&env, &market_id);
+        let mode = pricing_mode(&env, &market_id);
+        let (yes_state, no_state) = curve_state(&env, &market_id, &mode);
+        let fee_bps = pool_fee_bps(&env, &market_id);
+        curve_price(&mode, yes_state, no_state, outcome) * (10000 - fee_bps) / 10000
     }
 
     /// Calculate current odds for an outcome
@@ -530,6 +1932,11 @@ impl AMM {
             // No pool exists - return 50/50 odds
             return (5000, 5000);
         }
+        if let PricingMode::Lmsr(b) = pricing_mode(&env, &market_id) {
+            let (q_yes, q_no) = lmsr_shares(&env, &market_id);
+            let yes_odds = lmsr_price(b, q_yes, q_no, 1);
+            return (yes_odds, 10000 - yes_odds);
+        }
 
         // Get pool reserves
         let yes_key = (Symbol::new(&env, POOL_YES_RESERVE_KEY), market_id.clone());
@@ -576,14 +1983,17 @@ impl AMM {
     }
 
     /// Add USDC liquidity to an existing pool and mint LP tokens proportionally.
+    /// Reverts if fewer than `min_lp_out` tokens would be minted.
     /// Returns minted LP token amount.
     pub fn add_liquidity(
         env: Env,
         lp_provider: Address,
         market_id: BytesN<32>,
         usdc_amount: u128,
+        min_lp_out: u128,
     ) -> u128 {
         lp_provider.require_auth();
+        let mut guard = guard::enter(&env);
 
         if usdc_amount == 0 {
             panic!("usdc amount must be greater than 0");
@@ -593,6 +2003,8 @@ impl AMM {
         if !env.storage().persistent().has(&pool_exists_key) {
             panic!("pool does not exist");
         }
+        require_pool_unpaused(&env, &market_id);
+        require_cpmm_liquidity(&env, &market_id);
 
         let yes_reserve_key = (Symbol::new(&env, POOL_YES_RESERVE_KEY), market_id.clone());
         let no_reserve_key = (Symbol::new(&env, POOL_NO_RESERVE_KEY), market_id.clone());
@@ -624,6 +2036,12 @@ impl AMM {
         if lp_tokens_to_mint == 0 {
             panic!("lp tokens to mint must be positive");
         }
+        if lp_tokens_to_mint < min_lp_out {
+            panic!(
+                "Slippage exceeded: would mint {} lp tokens, minimum is {}",
+                lp_tokens_to_mint, min_lp_out
+            );
+        }
 
         // Add liquidity proportionally to preserve pool pricing.
         let yes_add = if current_total_liquidity == 0 {
@@ -674,18 +2092,25 @@ impl AMM {
         env.storage()
             .persistent()
             .set(&lp_balance_key, &new_lp_balance);
+        checkpoint_lp_fees(
+            &env,
+            &market_id,
+            &lp_provider,
+            current_lp_balance,
+            new_lp_balance,
+        );
+        update_lp_statement(&env, &lp_provider, |statement| {
+            statement.total_deposited = safe_add_u128(statement.total_deposited, usdc_amount);
+        });
 
-        let usdc_token: Address = env
-            .storage()
-            .persistent()
-            .get(&Symbol::new(&env, USDC_KEY))
-            .expect("usdc token not set");
-        let token_client = token::Client::new(&env, &usdc_token);
-        token_client.transfer(
+        adjust_pool_collateral(&env, &market_id, usdc_amount as i128);
+        guard.transfer(
+            &pool_collateral(&env, &market_id),
             &lp_provider,
-            env.current_contract_address(),
-            &(usdc_amount as i128),
+            &env.current_contract_address(),
+            usdc_amount as i128,
         );
+        guard.commit();
 
         let event = LiquidityAdded {
             provider: lp_provider.clone(),
@@ -703,14 +2128,21 @@ impl AMM {
     ///
     /// Validates LP token ownership, calculates proportional YES/NO withdrawal,
     /// burns LP tokens, updates reserves and k, transfers tokens to user.
+    /// Amounts are floored so the pool keeps any rounding dust. Reverts below
+    /// `min_collateral_out`, and once the market is terminal until the pool is settled.
+    /// Stays open while the pool is paused, without the resting liquidity floor.
+    /// After settle_pool, LPs (LMSR pools included) split the collateral left
+    /// over redemptions and fees pro rata, and may drain the pool completely.
     pub fn remove_liquidity(
         env: Env,
         lp_provider: Address,
         market_id: BytesN<32>,
         lp_tokens: u128,
+        min_collateral_out: u128,
     ) -> (u128, u128) {
         // Require LP provider authentication
         lp_provider.require_auth();
+        let mut guard = guard::enter(&env);
 
         // Validate lp_tokens > 0
         if lp_tokens == 0 {
@@ -722,6 +2154,13 @@ impl AMM {
         if !env.storage().persistent().has(&pool_exists_key) {
             panic!("pool does not exist");
         }
+        if !withdrawals_open(&env, &market_id) {
+            panic!("pool not settled");
+        }
+        let settlement = pool_settlement(&env, &market_id);
+        if settlement.is_none() {
+            require_cpmm_liquidity(&env, &market_id);
+        }
 
         // Create storage keys for this pool
         let yes_reserve_key = (Symbol::new(&env, POOL_YES_RESERVE_KEY), market_id.clone());
@@ -761,23 +2200,69 @@ impl AMM {
             .get(&lp_supply_key)
             .expect("lp supply not found");
 
-        // Calculate proportional YES and NO amounts to withdraw
-        // yes_amount = (lp_tokens / current_lp_supply) * yes_reserve
-        let yes_amount = (lp_tokens * yes_reserve) / current_lp_supply;
-        let no_amount = (lp_tokens * no_reserve) / current_lp_supply;
+        let (yes_amount, no_amount, new_yes_reserve, new_no_reserve) = if let Some(settlement) =
+            settlement
+        {
+            // Settled: share what redemptions and fees leave, down to nothing
+            let payout = lp_tokens * lp_collateral(&env, &market_id, settlement.redemption_reserve)
+                / current_lp_supply;
+            if payout == 0 {
+                panic!("withdrawal amount too small");
+            }
+            if payout < min_collateral_out {
+                panic!(
+                    "Slippage exceeded: would receive {} collateral, minimum is {}",
+                    payout, min_collateral_out
+                );
+            }
+            let yes_amount = payout * yes_reserve / (yes_reserve + no_reserve);
+            (
+                yes_amount,
+                payout - yes_amount,
+                safe_sub_u128(
+                    yes_reserve,
+                    mul_div_floor(lp_tokens, yes_reserve, current_lp_supply),
+                ),
+                safe_sub_u128(
+                    no_reserve,
+                    mul_div_floor(lp_tokens, no_reserve, current_lp_supply),
+                ),
+            )
+        } else {
+            // Calculate proportional YES and NO amounts to withdraw
+            // yes_amount = (lp_tokens / current_lp_supply) * yes_reserve
+            let yes_amount = (lp_tokens * yes_reserve) / current_lp_supply;
+            let no_amount = (lp_tokens * no_reserve) / current_lp_supply;
+
+            if yes_amount == 0 || no_amount == 0 {
+                panic!("withdrawal amount too small");
+            }
+            if yes_amount + no_amount < min_collateral_out {
+                panic!(
+                    "Slippage exceeded: would receive {} collateral, minimum is {}",
+                    yes_amount + no_amount,
+                    min_collateral_out
+                );
+            }
 
-        if yes_amount == 0 || no_amount == 0 {
-            panic!("withdrawal amount too small");
-        }
+            // Update reserves
+            let new_yes_reserve = safe_sub_u128(yes_reserve, yes_amount);
+            let new_no_reserve = safe_sub_u128(no_reserve, no_amount);
 
-        // Update reserves
-        let new_yes_reserve = yes_reserve - yes_amount;
-        let new_no_reserve = no_reserve - no_amount;
+            // Validate minimum liquidity remains (prevent draining pool completely)
+            if new_yes_reserve == 0 || new_no_reserve == 0 {
+                panic!("cannot drain pool completely");
+            }
+            // A paused pool has no traders to keep liquidity for
+            if !pool_paused(&env, &market_id)
+                && new_yes_reserve + new_no_reserve
+                    < resting_floor(&env, &market_id, yes_reserve, no_reserve)
+            {
+                panic!("withdrawal breaches resting liquidity floor");
+            }
 
-        // Validate minimum liquidity remains (prevent draining pool completely)
-        if new_yes_reserve == 0 || new_no_reserve == 0 {
-            panic!("cannot drain pool completely");
-        }
+            (yes_amount, no_amount, new_yes_reserve, new_no_reserve)
+        };
 
         // Update k
         let new_k = new_yes_reserve * new_no_reserve;
@@ -792,7 +2277,7 @@ impl AMM {
         env.storage().persistent().set(&k_key, &new_k);
 
         // Burn LP tokens from provider
-        let new_lp_balance = lp_balance - lp_tokens;
+        let new_lp_balance = safe_sub_u128(lp_balance, lp_tokens);
         if new_lp_balance == 0 {
             env.storage().persistent().remove(&lp_balance_key);
         } else {
@@ -802,26 +2287,27 @@ impl AMM {
         }
 
         // Update LP token supply
-        let new_lp_supply = current_lp_supply - lp_tokens;
+        let new_lp_supply = safe_sub_u128(current_lp_supply, lp_tokens);
         env.storage()
             .persistent()
             .set(&lp_supply_key, &new_lp_supply);
 
+        checkpoint_lp_fees(&env, &market_id, &lp_provider, lp_balance, new_lp_balance);
+
         // Transfer USDC back to user (YES and NO reserves are in USDC)
         // The user receives their proportional share of the pool's liquidity
-        let usdc_token: Address = env
-            .storage()
-            .persistent()
-            .get(&Symbol::new(&env, USDC_KEY))
-            .expect("usdc token not set");
-
-        let token_client = token::Client::new(&env, &usdc_token);
         let total_withdrawal = yes_amount + no_amount;
-        token_client.transfer(
+        adjust_pool_collateral(&env, &market_id, -(total_withdrawal as i128));
+        update_lp_statement(&env, &lp_provider, |statement| {
+            statement.total_withdrawn = safe_add_u128(statement.total_withdrawn, total_withdrawal);
+        });
+        guard.transfer(
+            &pool_collateral(&env, &market_id),
             &env.current_contract_address(),
             &lp_provider,
-            &(total_withdrawal as i128),
+            total_withdrawal as i128,
         );
+        guard.commit();
 
         // Emit LiquidityRemoved event
         LiquidityRemovedEvent {
@@ -836,6 +2322,255 @@ impl AMM {
         (yes_amount, no_amount)
     }
 
+    /// Factory: Require a pool to keep `floor_bps` of its market's open interest
+    ///
+    /// While the market is live, remove_liquidity may not take YES + NO reserves
+    /// below open interest * floor_bps / 10000. 0 removes the rule.
+    pub fn set_resting_floor(env: Env, factory: Address, market_id: BytesN<32>, floor_bps: u32) {
+        factory.require_auth();
+        let stored: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, FACTORY_KEY))
+            .expect("not initialized");
+        if factory != stored {
+            panic!("unauthorized");
+        }
+        if floor_bps > 10000 {
+            panic!("floor exceeds 100%");
+        }
+        if !env
+            .storage()
+            .persistent()
+            .has(&(Symbol::new(&env, POOL_EXISTS_KEY), market_id.clone()))
+        {
+            panic!("pool does not exist");
+        }
+
+        env.storage().persistent().set(
+            &(Symbol::new(&env, POOL_RESTING_FLOOR_KEY), market_id.clone()),
+            &floor_bps,
+        );
+
+        events::amm::resting_floor_set(&env, market_id, floor_bps);
+    }
+
+    /// A pool's resting floor in basis points of open interest (0 = none)
+    pub fn get_resting_floor(env: Env, market_id: BytesN<32>) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, POOL_RESTING_FLOOR_KEY), market_id))
+            .unwrap_or(0)
+    }
+
+    /// LP tokens `lp_provider` holds in a pool
+    pub fn get_lp_balance(env: Env, market_id: BytesN<32>, lp_provider: Address) -> u128 {
+        env.storage()
+            .persistent()
+            .get(&(
+                Symbol::new(&env, POOL_LP_TOKENS_KEY),
+                market_id,
+                lp_provider,
+            ))
+            .unwrap_or(0)
+    }
+
+    /// Total LP tokens outstanding for a pool
+    pub fn get_lp_total_supply(env: Env, market_id: BytesN<32>) -> u128 {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, POOL_LP_SUPPLY_KEY), market_id))
+            .unwrap_or(0)
+    }
+
+    /// Whether a pool has been settled after its market finished
+    pub fn is_pool_settled(env: Env, market_id: BytesN<32>) -> bool {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, POOL_SETTLED_KEY), market_id))
+            .unwrap_or(false)
+    }
+
+    /// Settle a pool once its market is finished (permissionless)
+    ///
+    /// Trading already stops when the market goes terminal; settlement fixes
+    /// what each outcome share redeems for. A Resolved (or Swept) market pays 1
+    /// collateral per winning share and nothing per losing share. A Cancelled or
+    /// Refunding market refunds both sides at the pool's last prices. If the pool
+    /// cannot cover that in full, both rates are scaled down pro rata. Whatever
+    /// is left after redemptions and fees goes to LPs through remove_liquidity.
+    pub fn settle_pool(env: Env, market_id: BytesN<32>) -> PoolSettlement {
+        if !env
+            .storage()
+            .persistent()
+            .has(&(Symbol::new(&env, POOL_EXISTS_KEY), market_id.clone()))
+        {
+            panic!("pool does not exist");
+        }
+        if Self::is_pool_settled(env.clone(), market_id.clone()) {
+            panic!("pool already settled");
+        }
+        let market = pool_market(&env, &market_id).expect("pool has no market");
+        let state = market_state(&env, &market)
+            .filter(|state| MARKET_TERMINAL_STATES.contains(state))
+            .expect("market not finished");
+
+        let (mut yes_rate, mut no_rate, winning_outcome) = if state == MARKET_STATE_RESOLVED
+            || state == MARKET_STATE_SWEPT
+        {
+            let outcome: u32 = env
+                .invoke_contract::<Option<u32>>(
+                    &market,
+                    &Symbol::new(&env, "get_winning_outcome"),
+                    Vec::new(&env),
+                )
+                .expect("market outcome unavailable");
+            let (yes_rate, no_rate) = sides(SETTLEMENT_RATE_PRECISION, 0, outcome);
+            (yes_rate, no_rate, Some(outcome))
+        } else {
+            let mode = pricing_mode(&env, &market_id);
+            let (yes_state, no_state) = curve_state(&env, &market_id, &mode);
+            let rate = |outcome: u32| -> u128 {
+                curve_price(&mode, yes_state, no_state, outcome) as u128 * SETTLEMENT_RATE_PRECISION
+                    / 10000
+            };
+            (rate(1), rate(0), None)
+        };
+
+        let (yes_out, no_out) = outstanding_shares(&env, &market_id);
+        let owed = |yes_rate: u128, no_rate: u128| -> u128 {
+            (yes_out.checked_mul(yes_rate).expect("settlement overflow")
+                + no_out.checked_mul(no_rate).expect("settlement overflow"))
+                / SETTLEMENT_RATE_PRECISION
+        };
+        let available = lp_collateral(&env, &market_id, 0);
+        let full = owed(yes_rate, no_rate);
+        if full > available {
+            yes_rate = yes_rate * available / full;
+            no_rate = no_rate * available / full;
+        }
+
+        let settlement = PoolSettlement {
+            market_state: state,
+            winning_outcome,
+            yes_rate,
+            no_rate,
+            redemption_reserve: owed(yes_rate, no_rate),
+            settled_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(
+            &(Symbol::new(&env, POOL_SETTLEMENT_KEY), market_id.clone()),
+            &settlement,
+        );
+        env.storage().persistent().set(
+            &(Symbol::new(&env, POOL_SETTLED_KEY), market_id.clone()),
+            &true,
+        );
+
+        events::amm::pool_settled(&env, market_id, winning_outcome, yes_rate, no_rate);
+        settlement
+    }
+
+    /// How a pool settled, None until settle_pool
+    pub fn get_pool_settlement(env: Env, market_id: BytesN<32>) -> Option<PoolSettlement> {
+        pool_settlement(&env, &market_id)
+    }
+
+    /// Burn all of a user's shares in a settled pool for collateral
+    ///
+    /// Pays yes_shares * yes_rate + no_shares * no_rate, each floored; losing
+    /// shares burn for nothing. Returns the payout.
+    pub fn redeem_shares(env: Env, user: Address, market_id: BytesN<32>) -> u128 {
+        user.require_auth();
+        let mut guard = guard::enter(&env);
+        let mut settlement = pool_settlement(&env, &market_id).expect("pool not settled");
+
+        let mut redeemed = [0u128; 2];
+        for outcome in [0u32, 1] {
+            let key = (
+                Symbol::new(&env, USER_SHARES_KEY),
+                market_id.clone(),
+                user.clone(),
+                outcome,
+            );
+            let shares: u128 = env.storage().persistent().get(&key).unwrap_or(0);
+            if shares > 0 {
+                env.storage().persistent().remove(&key);
+                adjust_outstanding(&env, &market_id, outcome, -(shares as i128));
+                redeemed[outcome as usize] = shares;
+            }
+        }
+        let [no_shares, yes_shares] = redeemed;
+        if yes_shares == 0 && no_shares == 0 {
+            panic!("no shares to redeem");
+        }
+
+        let payout = yes_shares * settlement.yes_rate / SETTLEMENT_RATE_PRECISION
+            + no_shares * settlement.no_rate / SETTLEMENT_RATE_PRECISION;
+        settlement.redemption_reserve = settlement.redemption_reserve.saturating_sub(payout);
+        env.storage().persistent().set(
+            &(Symbol::new(&env, POOL_SETTLEMENT_KEY), market_id.clone()),
+            &settlement,
+        );
+
+        if payout > 0 {
+            adjust_pool_collateral(&env, &market_id, -(payout as i128));
+            guard.transfer(
+                &pool_collateral(&env, &market_id),
+                &env.current_contract_address(),
+                &user,
+                payout as i128,
+            );
+        }
+        guard.commit();
+
+        events::amm::shares_redeemed(&env, market_id, user, yes_shares, no_shares, payout);
+        payout
+    }
+
+    /// Most collateral `lp_provider` could take out of a pool right now
+    ///
+    /// The provider's share of the reserves, clamped by the resting floor.
+    /// Once settled, their share of what redemptions and fees leave.
+    pub fn get_max_withdrawable(env: Env, market_id: BytesN<32>, lp_provider: Address) -> u128 {
+        if !withdrawals_open(&env, &market_id) {
+            return 0;
+        }
+        if let Some(settlement) = pool_settlement(&env, &market_id) {
+            let lp_supply = Self::get_lp_total_supply(env.clone(), market_id.clone());
+            if lp_supply == 0 {
+                return 0;
+            }
+            let lp_balance = Self::get_lp_balance(env.clone(), market_id.clone(), lp_provider);
+            return lp_balance
+                .checked_mul(lp_collateral(
+                    &env,
+                    &market_id,
+                    settlement.redemption_reserve,
+                ))
+                .expect("withdrawable overflow")
+                / lp_supply;
+        }
+        let (yes_reserve, no_reserve) = Self::get_reserves(env.clone(), market_id.clone());
+        let (yes_reserve, no_reserve) = (yes_reserve as u128, no_reserve as u128);
+        let lp_supply = Self::get_lp_total_supply(env.clone(), market_id.clone());
+        if lp_supply == 0 {
+            return 0;
+        }
+        let lp_balance = Self::get_lp_balance(env.clone(), market_id.clone(), lp_provider);
+        let share = lp_balance
+            .checked_mul(yes_reserve + no_reserve)
+            .expect("withdrawable overflow")
+            / lp_supply;
+        let headroom = (yes_reserve + no_reserve).saturating_sub(resting_floor(
+            &env,
+            &market_id,
+            yes_reserve,
+            no_reserve,
+        ));
+        share.min(headroom)
+    }
+
     /// Get current pool state (reserves, liquidity depth)
     /// Returns pool information for frontend display
     pub fn get_pool_state(env: Env, market_id: BytesN<32>) -> (u128, u128, u128, u32, u32) {
@@ -860,6 +2595,110 @@ impl AMM {
         (yes_reserve, no_reserve, total_liquidity, yes_odds, no_odds)
     }
 
+    /// Admin: Restrict buying into a pool to users verified by `registry`
+    ///
+    /// Mirrors the market's `set_kyc_registry`; selling is never gated.
+    pub fn set_pool_kyc_registry(
+        env: Env,
+        admin: Address,
+        market_id: BytesN<32>,
+        registry: Option<Address>,
+    ) {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, ADMIN_KEY))
+            .expect("not initialized");
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+
+        let key = (Symbol::new(&env, POOL_KYC_REGISTRY_KEY), market_id);
+        match registry {
+            Some(registry) => env.storage().persistent().set(&key, &registry),
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    /// Get the KYC registry gating buys into a pool, if any
+    pub fn get_pool_kyc_registry(env: Env, market_id: BytesN<32>) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, POOL_KYC_REGISTRY_KEY), market_id))
+    }
+
+    /// Health of a pool: depth against its category's reference, reserve
+    /// imbalance and time since the last trade (see `PoolHealth`)
+    ///
+    /// The reference depth is read from the factory.
+    pub fn get_health(env: Env, market_id: BytesN<32>) -> PoolHealth {
+        let depth = reference_depth(&env, &market_id);
+        Self::get_health_for_depth(env, market_id, depth)
+    }
+
+    /// Health of a pool scored against a caller-supplied reference depth
+    ///
+    /// Used by the factory's batch view, which cannot be re-entered for the
+    /// depth lookup. A zero depth falls back to DEFAULT_REFERENCE_DEPTH.
+    pub fn get_health_for_depth(
+        env: Env,
+        market_id: BytesN<32>,
+        reference_depth: u128,
+    ) -> PoolHealth {
+        let pool_exists_key = (Symbol::new(&env, POOL_EXISTS_KEY), market_id.clone());
+        if !env.storage().persistent().has(&pool_exists_key) {
+            return PoolHealth {
+                score: 0,
+                depth_score: 0,
+                imbalance_bps: 0,
+                seconds_since_trade: 0,
+                frozen: true,
+            };
+        }
+
+        let yes_key = (Symbol::new(&env, POOL_YES_RESERVE_KEY), market_id.clone());
+        let no_key = (Symbol::new(&env, POOL_NO_RESERVE_KEY), market_id.clone());
+        let yes_reserve: u128 = env.storage().persistent().get(&yes_key).unwrap_or(0);
+        let no_reserve: u128 = env.storage().persistent().get(&no_key).unwrap_or(0);
+        let total = yes_reserve + no_reserve;
+
+        let reference_depth = if reference_depth == 0 {
+            DEFAULT_REFERENCE_DEPTH
+        } else {
+            reference_depth
+        };
+        let depth_score = (total * 100 / reference_depth).min(100) as u32;
+        let imbalance_bps = (yes_reserve.abs_diff(no_reserve) * 10_000)
+            .checked_div(total)
+            .unwrap_or(0) as u32;
+
+        let last_trade: u64 = env
+            .storage()
+            .persistent()
+            .get(&(Symbol::new(&env, POOL_LAST_TRADE_KEY), market_id))
+            .unwrap_or(0);
+        let seconds_since_trade = env.ledger().timestamp().saturating_sub(last_trade);
+
+        let frozen = yes_reserve == 0 || no_reserve == 0;
+        let score = if frozen {
+            0
+        } else {
+            let balance = 100 - imbalance_bps / 100;
+            let freshness =
+                (100 * (STALE_AFTER - seconds_since_trade.min(STALE_AFTER)) / STALE_AFTER) as u32;
+            (depth_score * 50 + balance * 25 + freshness * 25) / 100
+        };
+
+        PoolHealth {
+            score,
+            depth_score,
+            imbalance_bps,
+            seconds_since_trade,
+            frozen,
+        }
+    }
+
     /// Get current pool constant product value.
     pub fn get_pool_k(env: Env, market_id: BytesN<32>) -> u128 {
         let pool_exists_key = (Symbol::new(&env, POOL_EXISTS_KEY), market_id.clone());
@@ -886,6 +2725,15 @@ impl AMM {
         if !env.storage().persistent().has(&pool_exists_key) {
             return (0, 0); // No pool exists
         }
+        if let PricingMode::Lmsr(b) = pricing_mode(&env, &market_id) {
+            let (q_yes, q_no) = lmsr_shares(&env, &market_id);
+            let fee_bps = pool_fee_bps(&env, &market_id);
+            let with_fee = |price: u32| price * (10000 + fee_bps) / 10000;
+            return (
+                with_fee(lmsr_price(b, q_yes, q_no, 1)),
+                with_fee(lmsr_price(b, q_yes, q_no, 0)),
+            );
+        }
 
         // Get pool reserves
         let yes_key = (Symbol::new(&env, POOL_YES_RESERVE_KEY), market_id.clone());
@@ -900,11 +2748,7 @@ impl AMM {
         }
 
         // Get trading fee (default 20 basis points = 0.2%)
-        let trading_fee_bps: u128 = env
-            .storage()
-            .persistent()
-            .get(&Symbol::new(&env, TRADING_FEE_KEY))
-            .unwrap_or(20);
+        let trading_fee_bps = pool_fee_bps(&env, &market_id) as u128;
 
         let total_liquidity = yes_reserve + no_reserve;
 
@@ -926,9 +2770,133 @@ impl AMM {
         (yes_price, no_price)
     }
 
+    /// Record the pool's current YES price without trading (permissionless)
+    ///
+    /// Keeps the TWAP history fresh on quiet pools.
+    pub fn poke(env: Env, market_id: BytesN<32>) {
+        record_observation(&env, &market_id);
+    }
+
+    /// Time-weighted average YES price over the last `window_seconds`, in basis points
+    ///
+    /// Built from the observation ring, so a single trade moves it only in
+    /// proportion to how long its price held. A window of 0 is the current
+    /// price. Panics when the window reaches back before the oldest observation.
+    pub fn get_twap(env: Env, market_id: BytesN<32>, window_seconds: u64) -> u32 {
+        let observations = observations(&env, &market_id);
+        let latest = observations.last().expect("pool does not exist");
+        if window_seconds == 0 {
+            return latest.price;
+        }
+
+        let now = env.ledger().timestamp();
+        let start = now
+            .checked_sub(window_seconds)
+            .expect("twap window exceeds history");
+        let start_cumulative =
+            cumulative_at(&observations, start).expect("twap window exceeds history");
+        let end_cumulative =
+            cumulative_at(&observations, now).expect("twap window exceeds history");
+        ((end_cumulative - start_cumulative) / window_seconds as u128) as u32
+    }
+
+    /// A pool's stored price observations, oldest first
+    pub fn get_observations(env: Env, market_id: BytesN<32>) -> Vec<Observation> {
+        observations(&env, &market_id)
+    }
+
+    /// Claim the caller's accrued share of a pool's trading fees
+    ///
+    /// Returns the amount paid out; counted as `rewards_claimed` in the LP statement.
+    pub fn claim_lp_fees(env: Env, lp_provider: Address, market_id: BytesN<32>) -> u128 {
+        lp_provider.require_auth();
+        let mut guard = guard::enter(&env);
+
+        let lp_balance: u128 = env
+            .storage()
+            .persistent()
+            .get(&(
+                Symbol::new(&env, POOL_LP_TOKENS_KEY),
+                market_id.clone(),
+                lp_provider.clone(),
+            ))
+            .unwrap_or(0);
+        checkpoint_lp_fees(&env, &market_id, &lp_provider, lp_balance, lp_balance);
+
+        let pending_key = (
+            Symbol::new(&env, LP_FEE_PENDING_KEY),
+            market_id.clone(),
+            lp_provider.clone(),
+        );
+        let amount: u128 = env.storage().persistent().get(&pending_key).unwrap_or(0);
+        if amount == 0 {
+            return 0;
+        }
+        env.storage().persistent().remove(&pending_key);
+        update_lp_statement(&env, &lp_provider, |statement| {
+            statement.rewards_claimed = safe_add_u128(statement.rewards_claimed, amount);
+        });
+        let claimed_key = (
+            Symbol::new(&env, POOL_LP_FEES_CLAIMED_KEY),
+            market_id.clone(),
+        );
+        let claimed: u128 = env.storage().persistent().get(&claimed_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&claimed_key, &safe_add_u128(claimed, amount));
+
+        adjust_pool_collateral(&env, &market_id, -(amount as i128));
+        guard.transfer(
+            &pool_collateral(&env, &market_id),
+            &env.current_contract_address(),
+            &lp_provider,
+            amount as i128,
+        );
+        guard.commit();
+
+        events::amm::lp_fees_claimed(&env, market_id, lp_provider, amount);
+
+        amount
+    }
+
+    /// Get a provider's cumulative LP statement across every pool they joined
+    ///
+    /// `fees_earned` includes fees accrued since the provider's last checkpoint.
+    pub fn get_lp_statement(env: Env, lp_provider: Address) -> LpStatement {
+        let mut statement = load_lp_statement(&env, &lp_provider);
+        let pools: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&(Symbol::new(&env, LP_POOLS_KEY), lp_provider.clone()))
+            .unwrap_or_else(|| Vec::new(&env));
+        for market_id in pools.iter() {
+            let lp_balance: u128 = env
+                .storage()
+                .persistent()
+                .get(&(
+                    Symbol::new(&env, POOL_LP_TOKENS_KEY),
+                    market_id.clone(),
+                    lp_provider.clone(),
+                ))
+                .unwrap_or(0);
+            statement.fees_earned = safe_add_u128(
+                statement.fees_earned,
+                unsettled_lp_fees(&env, &market_id, &lp_provider, lp_balance),
+            );
+        }
+        statement
+    }
+
+    /// Get the total trading fees a pool has accrued to its LPs
+    pub fn get_pool_lp_fees(env: Env, market_id: BytesN<32>) -> u128 {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, POOL_LP_FEES_KEY), market_id))
+            .unwrap_or(0)
+    }
+
     // TODO: Implement remaining AMM functions
-    // - add_liquidity()
-    // - get_lp_position() / claim_lp_fees()
+    // - get_lp_position()
     // - calculate_spot_price()
     // - get_trade_history()
 }
@@ -936,7 +2904,7 @@ impl AMM {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::testutils::{Address as _, Ledger};
     use soroban_sdk::{token, Address, Env};
 
     fn create_token_contract<'a>(env: &Env, admin: &Address) -> token::StellarAssetClient<'a> {
@@ -1007,7 +2975,7 @@ mod tests {
         assert_eq!(no_before, 500_000);
         assert_eq!(total_before, 1_000_000);
 
-        let minted = amm.add_liquidity(&second_lp, &market_id, &500_000u128);
+        let minted = amm.add_liquidity(&second_lp, &market_id, &500_000u128, &0u128);
         assert_eq!(minted, 500_000u128);
 
         let (yes_after, no_after, total_after, _, _) = amm.get_pool_state(&market_id);
@@ -1026,7 +2994,7 @@ mod tests {
         let old_k = amm.get_pool_k(&market_id);
         assert_eq!(old_k, 250_000_000_000);
 
-        amm.add_liquidity(&second_lp, &market_id, &500_000u128);
+        amm.add_liquidity(&second_lp, &market_id, &500_000u128, &0u128);
 
         let (yes_after, no_after, _, _, _) = amm.get_pool_state(&market_id);
         let new_k = amm.get_pool_k(&market_id);
@@ -1034,4 +3002,110 @@ mod tests {
         assert_eq!(new_k, 562_500_000_000);
         assert!(new_k > old_k);
     }
+
+    #[test]
+    fn test_lp_statements_attribute_fees_across_overlapping_deposits() {
+        let env = Env::default();
+        let (amm, usdc, lp_a, _admin, market_id) = setup_amm_pool(&env);
+        let lp_b = Address::generate(&env);
+        let trader = Address::generate(&env);
+        usdc.mint(&lp_b, &2_000_000i128);
+        usdc.mint(&trader, &1_000_000i128);
+
+        // Only A is in the pool: the whole first fee is A's
+        amm.buy_shares(&trader, &market_id, &1u32, &100_000u128, &0u128);
+        assert_eq!(amm.get_pool_lp_fees(&market_id), 200);
+        assert_eq!(amm.get_lp_statement(&lp_a).fees_earned, 200);
+
+        // B joins, both share subsequent fees
+        amm.add_liquidity(&lp_b, &market_id, &1_000_000u128, &0u128);
+        amm.buy_shares(&trader, &market_id, &0u32, &50_000u128, &0u128);
+        let shares = amm.buy_shares(&trader, &market_id, &1u32, &200_000u128, &0u128);
+
+        // A exits half its position, then more trading
+        amm.remove_liquidity(&lp_a, &market_id, &500_000u128, &0u128);
+        amm.sell_shares(&trader, &market_id, &1u32, &(shares / 2), &0u128);
+
+        let statement_a = amm.get_lp_statement(&lp_a);
+        let statement_b = amm.get_lp_statement(&lp_b);
+        let total_fees = amm.get_pool_lp_fees(&market_id);
+
+        // Fee attribution sums to the pool total, up to 1 stroop of rounding per LP
+        let attributed = statement_a.fees_earned + statement_b.fees_earned;
+        assert!(attributed <= total_fees);
+        assert!(total_fees - attributed <= 2);
+        assert!(statement_b.fees_earned > 0);
+
+        assert_eq!(statement_a.total_deposited, 1_000_000);
+        assert_eq!(statement_b.total_deposited, 1_000_000);
+        assert!(statement_a.total_withdrawn > 0);
+        assert_eq!(statement_b.total_withdrawn, 0);
+    }
+
+    #[test]
+    fn test_claim_lp_fees_updates_statement() {
+        let env = Env::default();
+        let (amm, usdc, lp_a, _admin, market_id) = setup_amm_pool(&env);
+        let trader = Address::generate(&env);
+        usdc.mint(&trader, &1_000_000i128);
+
+        amm.buy_shares(&trader, &market_id, &1u32, &500_000u128, &0u128);
+        let balance_before = usdc.balance(&lp_a);
+
+        let claimed = amm.claim_lp_fees(&lp_a, &market_id);
+        assert_eq!(claimed, 1_000);
+        assert_eq!(usdc.balance(&lp_a) - balance_before, 1_000);
+
+        let statement = amm.get_lp_statement(&lp_a);
+        assert_eq!(statement.fees_earned, 1_000);
+        assert_eq!(statement.rewards_claimed, 1_000);
+
+        // Nothing left to claim until more fees accrue
+        assert_eq!(amm.claim_lp_fees(&lp_a, &market_id), 0);
+    }
+
+    #[test]
+    fn test_health_ranks_deep_thin_and_stale_pools() {
+        let env = Env::default();
+        let (amm, usdc, initial_lp, _admin, thin) = setup_amm_pool(&env);
+
+        let deep = BytesN::from_array(&env, &[8u8; 32]);
+        usdc.mint(&initial_lp, &(DEFAULT_REFERENCE_DEPTH as i128));
+        amm.create_pool(&initial_lp, &deep, &DEFAULT_REFERENCE_DEPTH);
+
+        let deep_health = amm.get_health(&deep);
+        assert_eq!(deep_health.depth_score, 100);
+        assert_eq!(deep_health.imbalance_bps, 0);
+        assert_eq!(deep_health.score, 100);
+        assert!(!deep_health.frozen);
+
+        // 1 USDC pool against a 1,000 USDC reference: depth rounds to 0
+        let thin_health = amm.get_health(&thin);
+        assert_eq!(thin_health.depth_score, 0);
+        assert_eq!(thin_health.score, 50);
+
+        // The deep pool goes stale after a week without trades
+        env.ledger().with_mut(|li| li.timestamp += STALE_AFTER);
+        let stale_health = amm.get_health(&deep);
+        assert_eq!(stale_health.seconds_since_trade, STALE_AFTER);
+        assert_eq!(stale_health.score, 75);
+
+        // A trade refreshes the pool and skews its reserves
+        let trader = Address::generate(&env);
+        usdc.mint(&trader, &100_000i128);
+        amm.buy_shares(&trader, &thin, &1u32, &100_000u128, &0u128);
+        let traded = amm.get_health(&thin);
+        assert_eq!(traded.seconds_since_trade, 0);
+        assert!(traded.imbalance_bps > 0);
+    }
+
+    #[test]
+    fn test_health_of_missing_pool_is_frozen() {
+        let env = Env::default();
+        let (amm, _usdc, _initial_lp, _admin, _market_id) = setup_amm_pool(&env);
+
+        let health = amm.get_health(&BytesN::from_array(&env, &[9u8; 32]));
+        assert!(health.frozen);
+        assert_eq!(health.score, 0);
+    }
 }
@@ -1,8 +1,10 @@
 // contract/src/factory.rs - Market Factory Contract Implementation
 // Handles market creation and lifecycle management
 
+use crate::helpers::{hashing, time};
 use soroban_sdk::{
-    contract, contractevent, contractimpl, Address, Bytes, BytesN, Env, IntoVal, Symbol, Vec,
+    contract, contracterror, contractevent, contractimpl, contracttype, token, Address, BytesN,
+    Env, IntoVal, Symbol, Val, Vec,
 };
 
 #[contractevent]
@@ -15,15 +17,623 @@ pub struct FactoryInitializedEvent {
 #[contractevent]
 pub struct MarketCreatedEvent {
     pub market_id: BytesN<32>,
+    /// Deployed market contract (None when no market template is set)
+    pub market_address: Option<Address>,
     pub creator: Address,
     pub closing_time: u64,
 }
 
+#[contractevent]
+pub struct BatchCreatedEvent {
+    pub count: u32,
+    pub creator: Address,
+}
+
+#[contractevent]
+pub struct EmergencyShutdownEvent {
+    pub admin: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+pub struct CreationPausedEvent {
+    pub admin: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+pub struct CreationResumedEvent {
+    pub admin: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+pub struct FactorySunsetEvent {
+    pub admin: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+pub struct MarketTemplateUpdatedEvent {
+    pub old_template: Option<BytesN<32>>,
+    pub new_template: BytesN<32>,
+}
+
+#[contractevent]
+pub struct MarketWasmVersionEvent {
+    pub wasm_hash: BytesN<32>,
+    pub version: u32,
+}
+
+#[contractevent]
+pub struct IncidentReportedEvent {
+    pub index: u32,
+    pub market_id: BytesN<32>,
+    pub kind: Symbol,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+pub struct JurisdictionRuleSetEvent {
+    pub region: Symbol,
+    pub category: Symbol,
+    pub allowed: bool,
+}
+
+#[contractevent]
+pub struct MarketUpgradedEvent {
+    pub market_id: BytesN<32>,
+    pub from_version: u32,
+    pub to_version: u32,
+}
+
+#[contractevent]
+pub struct BatchClaimedEvent {
+    pub user: Address,
+    pub market_count: u32,
+    pub total_amount: i128,
+}
+
+#[contractevent]
+pub struct OperatorRegisteredEvent {
+    pub operator_id: Symbol,
+    pub admin: Address,
+    pub fee_share_bps: u32,
+}
+
+#[contractevent]
+pub struct CreationFeeChargedEvent {
+    pub creator: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+pub struct FeeScheduleProposedEvent {
+    pub schedule: FeeSchedule,
+    pub eta: u64,
+}
+
+#[contractevent]
+pub struct FeeScheduleAppliedEvent {
+    pub schedule: FeeSchedule,
+}
+
+#[contractevent]
+pub struct CreatorBondPostedEvent {
+    pub market_id: BytesN<32>,
+    pub creator: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+pub struct CreatorBondReleasedEvent {
+    pub market_id: BytesN<32>,
+    pub creator: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+pub struct CreatorApprovedEvent {
+    pub creator: Address,
+}
+
+#[contractevent]
+pub struct CreatorRemovedEvent {
+    pub creator: Address,
+}
+
+#[contractevent]
+pub struct CategoryAddedEvent {
+    pub category: Symbol,
+}
+
+#[contractevent]
+pub struct CategoryRemovedEvent {
+    pub category: Symbol,
+}
+
+#[contractevent]
+pub struct PermissionlessSetEvent {
+    pub enabled: bool,
+}
+
+#[contractevent]
+pub struct MarketPausedEvent {
+    pub market_id: BytesN<32>,
+    pub paused: bool,
+}
+
+#[cfg(feature = "staging-tools")]
+#[contractevent]
+pub struct RegistryImportedEvent {
+    pub entries: u32,
+    pub markets_imported: u32,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum FactoryError {
+    /// Confirmation hash does not match the network this factory was bootstrapped on
+    NetworkMismatch = 1,
+}
+
 // Storage keys
 const ADMIN_KEY: &str = "admin";
 const USDC_KEY: &str = "usdc";
 const TREASURY_KEY: &str = "treasury";
 const MARKET_COUNT_KEY: &str = "market_count";
+const MARKET_ADDRESS_PREFIX: &str = "market_addr";
+const DEFAULT_PARAMS_KEY: &str = "default_params";
+const MARKET_CONFIG_PREFIX: &str = "market_config";
+const NETWORK_BINDING_KEY: &str = "network_binding";
+const SHUTDOWN_KEY: &str = "shutdown";
+const CREATION_PAUSED_KEY: &str = "creation_paused";
+const SUNSET_KEY: &str = "sunset";
+const MARKET_TEMPLATE_KEY: &str = "market_template";
+const MARKET_WASM_VERSION_KEY: &str = "market_wasm_version";
+const MARKET_VERSION_PREFIX: &str = "market_version";
+const INCIDENT_PREFIX: &str = "incident";
+const INCIDENT_COUNT_KEY: &str = "incident_count";
+const ORACLE_KEY: &str = "oracle";
+const OPERATOR_PREFIX: &str = "operator";
+const OPERATOR_MARKETS_PREFIX: &str = "operator_markets";
+const OPERATOR_IDS_KEY: &str = "operator_ids";
+const MARKET_OPERATOR_PREFIX: &str = "market_operator";
+const MARKET_PAUSED_PREFIX: &str = "market_paused";
+const MARKET_INDEX_PREFIX: &str = "market_index";
+const MARKET_CREATED_AT_PREFIX: &str = "market_created_at";
+const APPROVED_CREATOR_PREFIX: &str = "approved_creator";
+const PERMISSIONLESS_KEY: &str = "permissionless";
+const CREATION_FEE_KEY: &str = "creation_fee";
+const FEES_PAID_PREFIX: &str = "fees_paid";
+const CATEGORIES_KEY: &str = "categories";
+const FEE_SCHEDULE_KEY: &str = "fee_schedule";
+const PENDING_FEE_SCHEDULE_KEY: &str = "pending_fee_schedule";
+const BOND_RELEASED_PREFIX: &str = "bond_released";
+const CREATOR_MARKETS_PREFIX: &str = "creator_markets";
+const CATEGORY_MARKETS_PREFIX: &str = "category_markets";
+const REFERENCE_DEPTH_PREFIX: &str = "reference_depth";
+const RESTING_FLOOR_PREFIX: &str = "resting_floor";
+const AMM_KEY: &str = "amm";
+const JURISDICTION_RULE_PREFIX: &str = "jurisdiction_rule";
+const MARKET_REGION_PREFIX: &str = "market_region";
+const REGION_MARKETS_PREFIX: &str = "region_markets";
+
+/// Wildcard region/category in jurisdiction rules
+pub const JURISDICTION_ANY: &str = "any";
+
+/// Incident kind logged when a rule change disallows a live market
+const JURISDICTION_INCIDENT: &str = "jurisdiction_blocked";
+
+/// Categories accepted by create_market until the admin changes the set
+const DEFAULT_CATEGORIES: [&str; 2] = ["Boxing", "MMA"];
+
+/// Delay between proposing a parameter change and being able to apply it (24h)
+pub const PARAM_CHANGE_DELAY: u64 = 24 * 60 * 60;
+
+/// Upper edges of the duration buckets in the fee schedule (resolution_time - now)
+pub const DURATION_DAY: u64 = 24 * 60 * 60;
+pub const DURATION_WEEK: u64 = 7 * DURATION_DAY;
+pub const DURATION_MONTH: u64 = 30 * DURATION_DAY;
+
+/// Outcomes per market; every factory market is binary (YES/NO)
+const BINARY_OUTCOME_COUNT: u32 = 2;
+
+/// Upper bound on the admin-managed category set
+pub const MAX_CATEGORIES: u32 = 32;
+
+/// Creation fee charged until the admin configures one (1 USDC, 7 decimals)
+pub const DEFAULT_CREATION_FEE: i128 = 10_000_000;
+
+/// Oracle capabilities the factory relies on (see OracleManager::get_capabilities)
+const REQUIRED_ORACLE_CAPABILITIES: [&str; 2] = ["binary", "challenge"];
+
+/// Upper bound for the default platform fee on winnings profit (5%)
+pub const MAX_DEFAULT_FEE_BPS: u32 = 500;
+
+/// Maximum number of markets accepted by a single get_user_exposure call
+pub const MAX_EXPOSURE_MARKETS: u32 = 20;
+
+/// Maximum number of markets accepted by a single claim_all call
+pub const MAX_CLAIM_BATCH: u32 = 20;
+
+/// Maximum pools per get_pools_health call
+pub const MAX_HEALTH_BATCH: u32 = 20;
+
+/// Maximum markets per get_market_overviews call
+pub const MAX_OVERVIEW_BATCH: u32 = 10;
+
+/// Maximum markets per create_markets call (each one is a contract deployment)
+pub const MAX_BATCH_MARKETS: u32 = 5;
+
+/// Default gap between the betting cutoff and resolution_time (1 hour)
+pub const DEFAULT_BETTING_CUTOFF_OFFSET: u64 = 3600;
+
+/// Default dispute window after resolution (7 days, matching the market contract)
+pub const DEFAULT_DISPUTE_WINDOW: u64 = 7 * 86400;
+
+/// Maximum number of records returned by a single list_markets call
+pub const MAX_LIST_MARKETS: u32 = 50;
+
+/// Exposure row status codes
+pub const EXPOSURE_STATUS_OK: u32 = 0;
+pub const EXPOSURE_STATUS_UNKNOWN_MARKET: u32 = 1;
+pub const EXPOSURE_STATUS_READ_FAILED: u32 = 2;
+
+/// Market overview error flags (0 = every read succeeded)
+pub const OVERVIEW_UNKNOWN_MARKET: u32 = 1;
+pub const OVERVIEW_MARKET_READ_FAILED: u32 = 2;
+pub const OVERVIEW_ORACLE_READ_FAILED: u32 = 4;
+
+/// Parameters for a new market
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarketParams {
+    pub title: Symbol,
+    pub description: Symbol,
+    pub category: Symbol,
+    /// Betting cutoff; 0 derives it from the betting_cutoff_offset default/override
+    pub closing_time: u64,
+    pub resolution_time: u64,
+    /// Operator (partner brand) running this market, if any
+    pub operator: Option<Symbol>,
+    /// Bump to re-list a question that already has a market (e.g. after cancellation)
+    pub nonce: u32,
+    /// Jurisdiction the market is offered in, checked against the rules table
+    pub region: Symbol,
+}
+
+/// A validated market waiting to be stored and deployed
+#[contracttype(export = false)]
+#[derive(Clone)]
+struct PreparedMarket {
+    market_id: BytesN<32>,
+    params: MarketParams,
+    config: MarketConfig,
+}
+
+/// Admin-configured defaults applied to new markets
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DefaultParams {
+    /// Platform fee on winnings profit (bps)
+    pub fee_bps: u32,
+    pub min_bet: i128,
+    /// Seconds between the betting cutoff and resolution_time
+    pub betting_cutoff_offset: u64,
+    /// Seconds after resolution during which the outcome can be disputed
+    pub dispute_window: u64,
+    /// Token the market is denominated in
+    pub token: Address,
+}
+
+/// Optional per-market overrides; unset fields fall back to DefaultParams
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarketOverrides {
+    pub fee_bps: Option<u32>,
+    pub min_bet: Option<i128>,
+    pub betting_cutoff_offset: Option<u64>,
+    pub dispute_window: Option<u64>,
+    pub token: Option<Address>,
+}
+
+/// Settings a market was created with (defaults merged with overrides)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarketConfig {
+    pub fee_bps: u32,
+    pub min_bet: i128,
+    pub closing_time: u64,
+    pub dispute_window: u64,
+    pub token: Address,
+    /// Creation fee charged for this market (in the CreationFee token)
+    pub creation_fee: i128,
+    /// USDC bond held by the factory until the market settles
+    pub bond: i128,
+}
+
+/// Economics applied to markets in one duration bucket
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DurationTier {
+    pub creation_fee: i128,
+    pub bond: i128,
+    /// Default platform fee; per-market overrides still take precedence
+    pub fee_bps: u32,
+}
+
+/// Duration-based economics, selected from resolution_time at creation
+///
+/// Buckets are inclusive of their upper edge: a market resolving exactly
+/// DURATION_DAY from now falls in `up_to_day`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeSchedule {
+    pub up_to_day: DurationTier,
+    pub up_to_week: DurationTier,
+    pub up_to_month: DurationTier,
+    pub longer: DurationTier,
+}
+
+/// Fee schedule waiting out PARAM_CHANGE_DELAY
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingFeeSchedule {
+    pub schedule: FeeSchedule,
+    /// Earliest time apply_fee_schedule accepts it
+    pub eta: u64,
+}
+
+/// Registry entry for a market created by the factory
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarketRecord {
+    pub market_id: BytesN<32>,
+    /// Deployed market contract, once known
+    pub address: Option<Address>,
+    pub creator: Address,
+    pub created_at: u64,
+    pub category: Symbol,
+    /// Market wasm version the contract runs (0 when not deployed by the factory)
+    pub wasm_version: u32,
+}
+
+/// Entry in the factory's incident log, reported by a deployed market
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Incident {
+    pub market_id: BytesN<32>,
+    /// What went wrong, e.g. `oracle_timeout`
+    pub kind: Symbol,
+    pub timestamp: u64,
+}
+
+/// Flat fee charged to creators by create_market
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreationFee {
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// Partner brand running markets on the protocol
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Operator {
+    /// Manages the operator's own markets
+    pub admin: Address,
+    /// Share of platform fees on the operator's markets paid to the operator (bps)
+    pub fee_share_bps: u32,
+}
+
+/// Mirror of the market contract's `Portfolio` (decoded from get_portfolio)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PortfolioSnapshot {
+    pub stake: i128,
+    pub side: u32,
+    pub implied_probability_bps: u32,
+    pub claimable: i128,
+}
+
+/// Mirror of the market contract's `BetRecord` (passed to rebuild_position)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BetRecordSnapshot {
+    pub outcome: u32,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+/// Mirror of the market contract's `MarketMetadata` (passed to its initialize)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarketMetadataInit {
+    pub question_hash: BytesN<32>,
+    pub category: Symbol,
+    pub creator: Address,
+    pub created_at: u64,
+    pub resolution_time: u64,
+    pub outcome_count: u32,
+    pub oracle: Address,
+}
+
+/// Mirror of the AMM's `PoolHealth` (decoded from get_health)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolHealthSnapshot {
+    pub score: u32,
+    pub depth_score: u32,
+    pub imbalance_bps: u32,
+    pub seconds_since_trade: u64,
+    pub frozen: bool,
+}
+
+/// Mirror of the market contract's `MarketState` (decoded from get_market_state)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarketStateSnapshot {
+    pub status: u32,
+    pub closing_time: u64,
+    pub total_pool: i128,
+    pub participant_count: u32,
+    pub winning_outcome: Option<u32>,
+    pub pools_revealed: bool,
+}
+
+/// Mirror of the oracle's `MarketResolutionState` (decoded from get_market_resolution_state)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResolutionStateSnapshot {
+    pub market_id: BytesN<32>,
+    pub resolution_time: u64,
+    pub tally: (u32, u32),
+    pub consensus_reached: bool,
+    pub consensus_outcome: u32,
+    pub finalized: bool,
+    pub finalized_at: u64,
+    pub overridden: bool,
+    pub challenged: bool,
+    pub archived: bool,
+}
+
+/// Registry record joined with live market and oracle state
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarketOverview {
+    pub market_id: BytesN<32>,
+    /// OR of OVERVIEW_* flags; 0 when every read succeeded
+    pub errors: u32,
+    /// Registry fields; None/0 for ids the factory never registered
+    pub address: Option<Address>,
+    pub creator: Option<Address>,
+    pub category: Option<Symbol>,
+    pub created_at: u64,
+    /// Market fields (see the market's `MarketState`); zero unless the market was read
+    pub status: u32,
+    pub closing_time: u64,
+    pub total_pool: i128,
+    pub participant_count: u32,
+    pub winning_outcome: Option<u32>,
+    pub pools_revealed: bool,
+    /// False when no oracle is set, the oracle does not track the market, or its read failed
+    pub oracle_tracked: bool,
+    /// Oracle fields (see the oracle's `MarketResolutionState`); zero unless oracle_tracked
+    pub tally: (u32, u32),
+    pub consensus_reached: bool,
+    pub consensus_outcome: u32,
+    pub finalized: bool,
+    pub challenged: bool,
+    pub overridden: bool,
+}
+
+/// Mirror of the market contract's `Pools` (decoded from get_pools)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolsSnapshot {
+    pub yes_total: i128,
+    pub no_total: i128,
+    pub participant_count: u32,
+}
+
+/// One market's registry, market, oracle, AMM and treasury state in one read
+///
+/// Each component read is best-effort: a missing or failing component leaves
+/// its fields None rather than failing the whole summary.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarketSummary {
+    pub market_id: BytesN<32>,
+    /// Registry metadata (see get_market_metadata)
+    pub address: Option<Address>,
+    pub creator: Address,
+    pub title: Symbol,
+    pub description: Symbol,
+    pub category: Symbol,
+    pub closing_time: u64,
+    pub resolution_time: u64,
+    /// Market state; None when the market is unlinked or its read failed
+    pub status: Option<u32>,
+    pub total_pool: Option<i128>,
+    pub participant_count: Option<u32>,
+    pub winning_outcome: Option<u32>,
+    /// YES/NO pool totals; also None while the market hides its split
+    pub yes_total: Option<i128>,
+    pub no_total: Option<i128>,
+    /// Oracle attestation counts and consensus; None when no oracle is set or
+    /// it does not track the market
+    pub yes_attestations: Option<u32>,
+    pub no_attestations: Option<u32>,
+    pub consensus_reached: Option<bool>,
+    pub consensus_outcome: Option<u32>,
+    /// AMM spot prices in basis points; None without a live pool
+    pub yes_price: Option<u32>,
+    pub no_price: Option<u32>,
+    /// Cumulative treasury fees for the market, in the factory's token
+    pub fees_collected: Option<i128>,
+}
+
+/// Per-market row of a user's consolidated exposure
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExposureRow {
+    pub market_id: BytesN<32>,
+    /// EXPOSURE_STATUS_* code; position fields are zero unless OK
+    pub status: u32,
+    pub stake: i128,
+    /// 0=NO, 1=YES, 2=none/unrevealed (same encoding as the market)
+    pub side: u32,
+    pub implied_probability_bps: u32,
+    pub claimable: i128,
+}
+
+/// Admin-managed category with its settings, as exported by export_registry
+#[cfg(feature = "staging-tools")]
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CategoryExport {
+    pub category: Symbol,
+    pub reference_depth: Option<u128>,
+    pub resting_floor_bps: u32,
+}
+
+/// Registered operator, as exported by export_registry
+#[cfg(feature = "staging-tools")]
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorExport {
+    pub operator_id: Symbol,
+    pub operator: Operator,
+}
+
+/// Everything the factory stores about one market (not the market contract's own state)
+#[cfg(feature = "staging-tools")]
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarketExport {
+    pub record: MarketRecord,
+    pub title: Symbol,
+    pub description: Symbol,
+    pub closing_time: u64,
+    pub resolution_time: u64,
+    pub region: Option<Symbol>,
+    pub config: MarketConfig,
+    pub operator: Option<Symbol>,
+    pub paused: bool,
+}
+
+/// One row of a registry snapshot; see export_registry for the ordering
+#[cfg(feature = "staging-tools")]
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum RegistryEntryExport {
+    Category(CategoryExport),
+    Operator(OperatorExport),
+    Market(MarketExport),
+}
 
 /// MARKET FACTORY - Handles market creation, fee collection, and market registry
 #[contract]
@@ -42,137 +652,2267 @@ impl MarketFactory {
             panic!("already initialized");
         }
 
-        // Verify admin signature
-        admin.require_auth();
+        // Verify admin signature
+        admin.require_auth();
+
+        // Store admin address
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, ADMIN_KEY), &admin);
+
+        // Store USDC token contract address
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, USDC_KEY), &usdc);
+
+        // Store Treasury contract address
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, TREASURY_KEY), &treasury);
+
+        // Initialize market counter at 0
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, MARKET_COUNT_KEY), &0u32);
+
+        let mut categories = Vec::new(&env);
+        for category in DEFAULT_CATEGORIES {
+            categories.push_back(Symbol::new(&env, category));
+        }
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, CATEGORIES_KEY), &categories);
+
+        // Bind to the network passphrase hash this factory is bootstrapped on
+        env.storage().persistent().set(
+            &Symbol::new(&env, NETWORK_BINDING_KEY),
+            &env.ledger().network_id(),
+        );
+
+        // Emit initialization event
+        FactoryInitializedEvent {
+            admin,
+            usdc,
+            treasury,
+        }
+        .publish(&env);
+    }
+
+    /// Get total markets created
+    pub fn get_market_count(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, MARKET_COUNT_KEY))
+            .unwrap_or(0)
+    }
+
+    /// Get the market created at `index` (creation order, starting at 0)
+    pub fn get_market(env: Env, index: u32) -> MarketRecord {
+        Self::market_record(&env, index).expect("market index out of range")
+    }
+
+    /// List markets in creation order, at most MAX_LIST_MARKETS per page
+    ///
+    /// Offsets past the end return an empty list.
+    pub fn list_markets(env: Env, offset: u32, limit: u32) -> Vec<MarketRecord> {
+        let count = Self::get_market_count(env.clone());
+        let end = offset
+            .saturating_add(limit.min(MAX_LIST_MARKETS))
+            .min(count);
+
+        let mut records = Vec::new(&env);
+        for index in offset..end {
+            if let Some(record) = Self::market_record(&env, index) {
+                records.push_back(record);
+            }
+        }
+        records
+    }
+
+    /// Market: Append an incident to the factory's log
+    ///
+    /// Only the contract registered for `market_id` may report on it.
+    pub fn report_incident(env: Env, market_id: BytesN<32>, kind: Symbol) {
+        let market_address =
+            Self::get_market_address(env.clone(), market_id.clone()).expect("market not deployed");
+        market_address.require_auth();
+        Self::log_incident(&env, market_id, kind);
+    }
+
+    /// Helper: Append an entry to the incident log
+    fn log_incident(env: &Env, market_id: BytesN<32>, kind: Symbol) {
+        let index = Self::get_incident_count(env.clone());
+        let timestamp = env.ledger().timestamp();
+        env.storage().persistent().set(
+            &(Symbol::new(env, INCIDENT_PREFIX), index),
+            &Incident {
+                market_id: market_id.clone(),
+                kind: kind.clone(),
+                timestamp,
+            },
+        );
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(env, INCIDENT_COUNT_KEY), &(index + 1));
+
+        IncidentReportedEvent {
+            index,
+            market_id,
+            kind,
+            timestamp,
+        }
+        .publish(env);
+    }
+
+    /// Number of incidents reported so far
+    pub fn get_incident_count(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, INCIDENT_COUNT_KEY))
+            .unwrap_or(0)
+    }
+
+    /// Page through the incident log in report order (at most MAX_LIST_MARKETS entries)
+    pub fn list_incidents(env: Env, offset: u32, limit: u32) -> Vec<Incident> {
+        let end = offset
+            .saturating_add(limit.min(MAX_LIST_MARKETS))
+            .min(Self::get_incident_count(env.clone()));
+
+        let mut incidents = Vec::new(&env);
+        for index in offset..end {
+            if let Some(incident) = env
+                .storage()
+                .persistent()
+                .get(&(Symbol::new(&env, INCIDENT_PREFIX), index))
+            {
+                incidents.push_back(incident);
+            }
+        }
+        incidents
+    }
+
+    /// List the markets created by `creator`, oldest first, at most MAX_LIST_MARKETS per page
+    pub fn list_markets_by_creator(
+        env: Env,
+        creator: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<MarketRecord> {
+        let key = (Symbol::new(&env, CREATOR_MARKETS_PREFIX), creator);
+        Self::page_index(&env, &key, offset, limit)
+    }
+
+    /// List the markets in `category`, oldest first, at most MAX_LIST_MARKETS per page
+    pub fn list_markets_by_category(
+        env: Env,
+        category: Symbol,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<MarketRecord> {
+        let key = (Symbol::new(&env, CATEGORY_MARKETS_PREFIX), category);
+        Self::page_index(&env, &key, offset, limit)
+    }
+
+    /// Helper: Read one page of a secondary index as registry records
+    fn page_index<K>(env: &Env, key: &K, offset: u32, limit: u32) -> Vec<MarketRecord>
+    where
+        K: IntoVal<Env, Val>,
+    {
+        let market_ids: Vec<BytesN<32>> =
+            env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+        let end = offset
+            .saturating_add(limit.min(MAX_LIST_MARKETS))
+            .min(market_ids.len());
+
+        let mut records = Vec::new(env);
+        for index in offset..end {
+            if let Some(record) = Self::market_record_by_id(env, market_ids.get_unchecked(index)) {
+                records.push_back(record);
+            }
+        }
+        records
+    }
+
+    /// Helper: Append `market_id` to a secondary index
+    fn push_index<K>(env: &Env, key: &K, market_id: &BytesN<32>)
+    where
+        K: IntoVal<Env, Val>,
+    {
+        let mut market_ids: Vec<BytesN<32>> =
+            env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+        market_ids.push_back(market_id.clone());
+        env.storage().persistent().set(key, &market_ids);
+    }
+
+    /// Helper: Assemble the registry record for the market at `index`
+    fn market_record(env: &Env, index: u32) -> Option<MarketRecord> {
+        let market_id: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&(Symbol::new(env, MARKET_INDEX_PREFIX), index))?;
+        Self::market_record_by_id(env, market_id)
+    }
+
+    /// Helper: Assemble the registry record for `market_id`
+    fn market_record_by_id(env: &Env, market_id: BytesN<32>) -> Option<MarketRecord> {
+        let (creator, _, _, category, _, _) =
+            Self::get_market_metadata(env.clone(), market_id.clone())?;
+        let created_at: u64 = env
+            .storage()
+            .persistent()
+            .get(&(
+                Symbol::new(env, MARKET_CREATED_AT_PREFIX),
+                market_id.clone(),
+            ))
+            .unwrap_or(0);
+
+        Some(MarketRecord {
+            address: Self::get_market_address(env.clone(), market_id.clone()),
+            wasm_version: Self::get_market_version(env.clone(), market_id.clone()).unwrap_or(0),
+            market_id,
+            creator,
+            created_at,
+            category,
+        })
+    }
+
+    /// Get treasury address
+    pub fn get_treasury(env: Env) -> Address {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, TREASURY_KEY))
+            .expect("Treasury not set")
+    }
+
+    /// Create a new market instance
+    ///
+    /// When a market template (wasm hash) is set, deploys a market contract
+    /// salted by the market_id, initializes it with the factory's token, oracle
+    /// and platform fee, and records its address in the registry. Without a
+    /// template the market is only registered and linked later through
+    /// `set_market_address`.
+    ///
+    /// Unset fields in `overrides` fall back to the factory's `DefaultParams`.
+    pub fn create_market(
+        env: Env,
+        creator: Address,
+        params: MarketParams,
+        overrides: Option<MarketOverrides>,
+    ) -> BytesN<32> {
+        // Require creator authentication
+        creator.require_auth();
+
+        Self::require_creation_open(&env, &creator);
+        let prepared = Self::prepare_market(&env, params, overrides);
+
+        Self::store_market(&env, &creator, &prepared);
+        // Charge the creation fee and bond before anything is deployed
+        Self::charge_market(&env, &creator, &prepared);
+        let (market_id, _) = Self::launch_market(&env, &creator, prepared);
+        market_id
+    }
+
+    /// Create a series of markets in one call
+    ///
+    /// Every entry is validated (including duplicates within the batch) and
+    /// every creation fee and bond is charged before the first market is
+    /// stored or deployed. Entries use the factory's `DefaultParams`; markets
+    /// that need overrides go through `create_market`. Requires a market
+    /// template, capped at `MAX_BATCH_MARKETS` entries.
+    pub fn create_markets(
+        env: Env,
+        creator: Address,
+        params: Vec<MarketParams>,
+    ) -> Vec<(BytesN<32>, Address)> {
+        creator.require_auth();
+
+        if params.is_empty() {
+            panic!("empty batch");
+        }
+        if params.len() > MAX_BATCH_MARKETS {
+            panic!("too many markets");
+        }
+        Self::require_creation_open(&env, &creator);
+        if Self::get_market_template(env.clone()).is_none() {
+            panic!("market template not set");
+        }
+
+        let mut prepared: Vec<PreparedMarket> = Vec::new(&env);
+        for entry in params.iter() {
+            let market = Self::prepare_market(&env, entry, None);
+            if prepared.iter().any(|p| p.market_id == market.market_id) {
+                panic!("market already exists");
+            }
+            prepared.push_back(market);
+        }
+
+        for market in prepared.iter() {
+            Self::charge_market(&env, &creator, &market);
+        }
+
+        let mut created = Vec::new(&env);
+        for market in prepared {
+            Self::store_market(&env, &creator, &market);
+            let (market_id, market_address) = Self::launch_market(&env, &creator, market);
+            created.push_back((market_id, market_address.unwrap()));
+        }
+
+        BatchCreatedEvent {
+            count: created.len(),
+            creator,
+        }
+        .publish(&env);
+
+        created
+    }
+
+    /// Helper: checks that apply to the factory and creator, not to a market
+    fn require_creation_open(env: &Env, creator: &Address) {
+        if !env.storage().persistent().has(&Symbol::new(env, ADMIN_KEY)) {
+            panic!("factory not initialized");
+        }
+        if Self::is_creation_paused(env.clone()) {
+            panic!("market creation paused");
+        }
+        if Self::is_shut_down(env.clone()) {
+            panic!("factory is shut down");
+        }
+        if Self::is_sunset(env.clone()) {
+            panic!("factory is sunset");
+        }
+        if !Self::is_permissionless(env.clone())
+            && !Self::is_approved_creator(env.clone(), creator.clone())
+        {
+            panic!("creator not approved");
+        }
+        if Self::get_oracle(env.clone()).is_some() && !Self::is_oracle_healthy(env.clone()) {
+            panic!("oracle unhealthy");
+        }
+    }
+
+    /// Helper: validate one market's params and derive its id, writing nothing
+    fn prepare_market(
+        env: &Env,
+        params: MarketParams,
+        overrides: Option<MarketOverrides>,
+    ) -> PreparedMarket {
+        if let Some(operator_id) = &params.operator {
+            if Self::get_operator(env.clone(), operator_id.clone()).is_none() {
+                panic!("operator not found");
+            }
+        }
+        if !Self::is_category(env.clone(), params.category.clone()) {
+            panic!("category not allowed");
+        }
+        if !Self::is_market_allowed(env.clone(), params.region.clone(), params.category.clone()) {
+            panic!("jurisdiction not allowed");
+        }
+
+        // Merge defaults with overrides and validate before anything is stored
+        let config =
+            Self::resolve_config(env, params.closing_time, params.resolution_time, overrides);
+
+        // Validate closing_time > now and < resolution_time
+        let current_time = env.ledger().timestamp();
+        if config.closing_time <= current_time {
+            panic!("invalid timestamps");
+        }
+        if config.closing_time >= params.resolution_time {
+            panic!("invalid timestamps");
+        }
+
+        // Derive the market_id from the question so duplicates are rejected
+        let market_id = Self::derive_market_id(
+            env,
+            &params.title,
+            &params.description,
+            &params.category,
+            params.resolution_time,
+            params.nonce,
+        );
+        if env
+            .storage()
+            .persistent()
+            .has(&(Symbol::new(env, "market"), market_id.clone()))
+        {
+            panic!("market already exists");
+        }
+
+        PreparedMarket {
+            market_id,
+            params,
+            config,
+        }
+    }
+
+    /// Helper: write a validated market into the registry and its indexes
+    fn store_market(env: &Env, creator: &Address, market: &PreparedMarket) {
+        let PreparedMarket {
+            market_id,
+            params,
+            config,
+        } = market;
+
+        let market_count: u32 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, MARKET_COUNT_KEY))
+            .unwrap_or(0);
+
+        // Store market in registry
+        env.storage()
+            .persistent()
+            .set(&(Symbol::new(env, "market"), market_id.clone()), &true);
+
+        // Store market metadata
+        let metadata_key = (Symbol::new(env, "market_meta"), market_id.clone());
+        let metadata = (
+            creator.clone(),
+            params.title.clone(),
+            params.description.clone(),
+            params.category.clone(),
+            config.closing_time,
+            params.resolution_time,
+        );
+        env.storage().persistent().set(&metadata_key, &metadata);
+
+        Self::push_index(
+            env,
+            &(Symbol::new(env, CREATOR_MARKETS_PREFIX), creator.clone()),
+            market_id,
+        );
+        Self::push_index(
+            env,
+            &(
+                Symbol::new(env, CATEGORY_MARKETS_PREFIX),
+                params.category.clone(),
+            ),
+            market_id,
+        );
+        Self::push_index(
+            env,
+            &(
+                Symbol::new(env, REGION_MARKETS_PREFIX),
+                params.region.clone(),
+            ),
+            market_id,
+        );
+        env.storage().persistent().set(
+            &(Symbol::new(env, MARKET_REGION_PREFIX), market_id.clone()),
+            &params.region,
+        );
+
+        env.storage().persistent().set(
+            &(Symbol::new(env, MARKET_CONFIG_PREFIX), market_id.clone()),
+            config,
+        );
+
+        // Tag the market with its operator
+        if let Some(operator_id) = &params.operator {
+            env.storage().persistent().set(
+                &(Symbol::new(env, MARKET_OPERATOR_PREFIX), market_id.clone()),
+                operator_id,
+            );
+            let markets_key = (
+                Symbol::new(env, OPERATOR_MARKETS_PREFIX),
+                operator_id.clone(),
+            );
+            let mut markets: Vec<BytesN<32>> = env
+                .storage()
+                .persistent()
+                .get(&markets_key)
+                .unwrap_or(Vec::new(env));
+            markets.push_back(market_id.clone());
+            env.storage().persistent().set(&markets_key, &markets);
+        }
+
+        // Index the market in creation order
+        env.storage().persistent().set(
+            &(Symbol::new(env, MARKET_INDEX_PREFIX), market_count),
+            market_id,
+        );
+        env.storage().persistent().set(
+            &(
+                Symbol::new(env, MARKET_CREATED_AT_PREFIX),
+                market_id.clone(),
+            ),
+            &env.ledger().timestamp(),
+        );
+
+        // Increment market counter
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(env, MARKET_COUNT_KEY), &(market_count + 1));
+    }
+
+    /// Helper: collect a market's creation fee and creator bond
+    fn charge_market(env: &Env, creator: &Address, market: &PreparedMarket) {
+        Self::charge_creation_fee(env, creator, market.config.creation_fee);
+        if market.config.bond > 0 {
+            let usdc: Address = env
+                .storage()
+                .persistent()
+                .get(&Symbol::new(env, USDC_KEY))
+                .expect("usdc not set");
+            token::Client::new(env, &usdc).transfer(
+                creator,
+                env.current_contract_address(),
+                &market.config.bond,
+            );
+            CreatorBondPostedEvent {
+                market_id: market.market_id.clone(),
+                creator: creator.clone(),
+                amount: market.config.bond,
+            }
+            .publish(env);
+        }
+    }
+
+    /// Helper: register a stored market with the oracle, deploy it when a
+    /// template is set, and emit MarketCreated
+    fn launch_market(
+        env: &Env,
+        creator: &Address,
+        market: PreparedMarket,
+    ) -> (BytesN<32>, Option<Address>) {
+        let PreparedMarket {
+            market_id,
+            params,
+            config,
+        } = market;
+
+        // Register with the oracle so attestations can start at resolution_time
+        // (the factory must be a registrar on the OracleManager)
+        if let Some(oracle) = Self::get_oracle(env.clone()) {
+            env.invoke_contract::<()>(
+                &oracle,
+                &Symbol::new(env, "register_market_as"),
+                (
+                    env.current_contract_address(),
+                    market_id.clone(),
+                    params.resolution_time,
+                    params.category.clone(),
+                )
+                    .into_val(env),
+            );
+        }
+
+        let market_address = Self::get_market_template(env.clone()).map(|wasm_hash| {
+            Self::deploy_market(env, wasm_hash, &market_id, creator, &config, &params)
+        });
+
+        // Emit MarketCreated event
+        MarketCreatedEvent {
+            market_id: market_id.clone(),
+            market_address: market_address.clone(),
+            creator: creator.clone(),
+            closing_time: config.closing_time,
+        }
+        .publish(env);
+
+        (market_id, market_address)
+    }
+
+    /// Compute the market_id `create_market` would assign to `params`
+    ///
+    /// Clients can check `get_market_metadata` for the id before submitting.
+    pub fn compute_market_id(env: Env, params: MarketParams) -> BytesN<32> {
+        Self::derive_market_id(
+            &env,
+            &params.title,
+            &params.description,
+            &params.category,
+            params.resolution_time,
+            params.nonce,
+        )
+    }
+
+    /// Helper: market_id for a question, per `helpers::hashing`'s encoding
+    fn derive_market_id(
+        env: &Env,
+        title: &Symbol,
+        description: &Symbol,
+        category: &Symbol,
+        resolution_time: u64,
+        nonce: u32,
+    ) -> BytesN<32> {
+        let question_hash = hashing::question_hash(env, title, description);
+        hashing::market_id_from_params(
+            env,
+            &question_hash,
+            category,
+            resolution_time,
+            BINARY_OUTCOME_COUNT,
+            nonce,
+        )
+    }
+
+    /// Helper: Move the creation fee from `creator` into the treasury
+    fn charge_creation_fee(env: &Env, creator: &Address, amount: i128) {
+        let fee = CreationFee {
+            amount,
+            ..Self::get_creation_fee(env.clone())
+        };
+        if fee.amount == 0 {
+            return;
+        }
+
+        let treasury_address: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, TREASURY_KEY))
+            .expect("Treasury address not set");
+        let usdc: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, USDC_KEY))
+            .expect("usdc not set");
+
+        if fee.token == usdc {
+            // Booked into the treasury's fee pools
+            env.invoke_contract::<()>(
+                &treasury_address,
+                &Symbol::new(env, "deposit_fees"),
+                (creator.clone(), fee.amount).into_val(env),
+            );
+        } else {
+            token::Client::new(env, &fee.token).transfer(creator, &treasury_address, &fee.amount);
+        }
+
+        let paid_key = (Symbol::new(env, FEES_PAID_PREFIX), creator.clone());
+        let paid: i128 = env.storage().persistent().get(&paid_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&paid_key, &(paid + fee.amount));
+
+        CreationFeeChargedEvent {
+            creator: creator.clone(),
+            amount: fee.amount,
+        }
+        .publish(env);
+    }
+
+    /// Admin: Set the flat fee charged for creating a market (0 disables it)
+    pub fn set_creation_fee(env: Env, admin: Address, token: Address, amount: i128) {
+        if admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
+        }
+        if amount < 0 {
+            panic!("creation fee must not be negative");
+        }
+
+        env.storage().persistent().set(
+            &Symbol::new(&env, CREATION_FEE_KEY),
+            &CreationFee { token, amount },
+        );
+    }
+
+    /// Get the creation fee (defaults to DEFAULT_CREATION_FEE in USDC)
+    pub fn get_creation_fee(env: Env) -> CreationFee {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, CREATION_FEE_KEY))
+            .unwrap_or_else(|| CreationFee {
+                token: env
+                    .storage()
+                    .persistent()
+                    .get(&Symbol::new(&env, USDC_KEY))
+                    .expect("usdc not set"),
+                amount: DEFAULT_CREATION_FEE,
+            })
+    }
+
+    /// Get the cumulative creation fees paid by `creator`
+    pub fn get_fees_paid(env: Env, creator: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, FEES_PAID_PREFIX), creator))
+            .unwrap_or(0)
+    }
+
+    /// Helper: Deploy and initialize a market contract from the template
+    fn deploy_market(
+        env: &Env,
+        wasm_hash: BytesN<32>,
+        market_id: &BytesN<32>,
+        creator: &Address,
+        config: &MarketConfig,
+        params: &MarketParams,
+    ) -> Address {
+        let oracle = Self::get_oracle(env.clone()).expect("oracle not set");
+        let metadata = MarketMetadataInit {
+            question_hash: hashing::question_hash(env, &params.title, &params.description),
+            category: params.category.clone(),
+            creator: creator.clone(),
+            created_at: env.ledger().timestamp(),
+            resolution_time: params.resolution_time,
+            outcome_count: BINARY_OUTCOME_COUNT,
+            oracle: oracle.clone(),
+        };
+
+        let market_address = env
+            .deployer()
+            .with_current_contract(market_id.clone())
+            .deploy_v2(wasm_hash, ());
+
+        env.invoke_contract::<()>(
+            &market_address,
+            &Symbol::new(env, "initialize"),
+            (
+                market_id.clone(),
+                creator.clone(),
+                env.current_contract_address(),
+                config.token.clone(),
+                oracle,
+                config.closing_time,
+                params.resolution_time,
+                config.fee_bps,
+                Some(metadata),
+            )
+                .into_val(env),
+        );
+        // The market cannot call back into the factory while it is being created,
+        // so the remaining merged settings are pushed explicitly
+        if config.min_bet > 0 {
+            env.invoke_contract::<()>(
+                &market_address,
+                &Symbol::new(env, "set_bet_limits"),
+                (creator.clone(), config.min_bet, 0i128).into_val(env),
+            );
+        }
+        env.invoke_contract::<()>(
+            &market_address,
+            &Symbol::new(env, "set_dispute_window"),
+            (creator.clone(), config.dispute_window).into_val(env),
+        );
+
+        env.storage().persistent().set(
+            &(Symbol::new(env, MARKET_ADDRESS_PREFIX), market_id.clone()),
+            &market_address,
+        );
+        env.storage().persistent().set(
+            &(Symbol::new(env, MARKET_VERSION_PREFIX), market_id.clone()),
+            &Self::get_market_wasm_version(env.clone()),
+        );
+        market_address
+    }
+
+    /// Helper: Merge DefaultParams with `overrides`, enforcing the market contract's bounds
+    fn resolve_config(
+        env: &Env,
+        closing_time: u64,
+        resolution_time: u64,
+        overrides: Option<MarketOverrides>,
+    ) -> MarketConfig {
+        let defaults = Self::get_default_params(env.clone());
+        let overrides = overrides.unwrap_or(MarketOverrides {
+            fee_bps: None,
+            min_bet: None,
+            betting_cutoff_offset: None,
+            dispute_window: None,
+            token: None,
+        });
+        // The duration tier replaces the flat creation fee and default fee_bps
+        let tier = Self::get_fee_schedule(env.clone()).map(|schedule| {
+            let duration = resolution_time.saturating_sub(env.ledger().timestamp());
+            Self::duration_tier(schedule, duration)
+        });
+        let (creation_fee, bond, default_fee_bps) = match tier {
+            Some(tier) => (tier.creation_fee, tier.bond, tier.fee_bps),
+            None => (
+                Self::get_creation_fee(env.clone()).amount,
+                0,
+                defaults.fee_bps,
+            ),
+        };
+
+        let fee_bps = overrides.fee_bps.unwrap_or(default_fee_bps);
+        let min_bet = overrides.min_bet.unwrap_or(defaults.min_bet);
+        let dispute_window = overrides.dispute_window.unwrap_or(defaults.dispute_window);
+        Self::validate_params(fee_bps, min_bet, dispute_window);
+
+        let closing_time = if closing_time == 0 {
+            let offset = overrides
+                .betting_cutoff_offset
+                .unwrap_or(defaults.betting_cutoff_offset);
+            resolution_time.saturating_sub(offset)
+        } else {
+            closing_time
+        };
+        if closing_time > resolution_time {
+            panic!("invalid timestamps");
+        }
+
+        MarketConfig {
+            fee_bps,
+            min_bet,
+            closing_time,
+            dispute_window,
+            token: overrides.token.unwrap_or(defaults.token),
+            creation_fee,
+            bond,
+        }
+    }
+
+    /// Helper: Pick the schedule bucket for a market resolving `duration` seconds out
+    fn duration_tier(schedule: FeeSchedule, duration: u64) -> DurationTier {
+        if duration <= DURATION_DAY {
+            schedule.up_to_day
+        } else if duration <= DURATION_WEEK {
+            schedule.up_to_week
+        } else if duration <= DURATION_MONTH {
+            schedule.up_to_month
+        } else {
+            schedule.longer
+        }
+    }
+
+    /// Admin: Queue a duration-based fee schedule, applicable after PARAM_CHANGE_DELAY
+    ///
+    /// Replaces any schedule already waiting in the queue.
+    pub fn propose_fee_schedule(env: Env, admin: Address, schedule: FeeSchedule) {
+        if admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
+        }
+        for tier in [
+            &schedule.up_to_day,
+            &schedule.up_to_week,
+            &schedule.up_to_month,
+            &schedule.longer,
+        ] {
+            if tier.fee_bps > MAX_DEFAULT_FEE_BPS {
+                panic!("fee exceeds maximum");
+            }
+            if tier.creation_fee < 0 || tier.bond < 0 {
+                panic!("tier amounts must not be negative");
+            }
+        }
+
+        let eta = env.ledger().timestamp() + PARAM_CHANGE_DELAY;
+        env.storage().persistent().set(
+            &Symbol::new(&env, PENDING_FEE_SCHEDULE_KEY),
+            &PendingFeeSchedule {
+                schedule: schedule.clone(),
+                eta,
+            },
+        );
+
+        FeeScheduleProposedEvent { schedule, eta }.publish(&env);
+    }
+
+    /// Apply the queued fee schedule once its delay has passed (anyone)
+    ///
+    /// Only markets created afterwards use it.
+    pub fn apply_fee_schedule(env: Env) {
+        let pending = Self::get_pending_fee_schedule(env.clone()).expect("no pending fee schedule");
+        if !time::has_reached(&env, pending.eta) {
+            panic!("fee schedule delay not elapsed");
+        }
+
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, FEE_SCHEDULE_KEY), &pending.schedule);
+        env.storage()
+            .persistent()
+            .remove(&Symbol::new(&env, PENDING_FEE_SCHEDULE_KEY));
+
+        FeeScheduleAppliedEvent {
+            schedule: pending.schedule,
+        }
+        .publish(&env);
+    }
+
+    /// Admin: Drop the queued fee schedule
+    pub fn cancel_fee_schedule(env: Env, admin: Address) {
+        if admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
+        }
+        env.storage()
+            .persistent()
+            .remove(&Symbol::new(&env, PENDING_FEE_SCHEDULE_KEY));
+    }
+
+    /// Get the active fee schedule (None = flat creation fee and default fee_bps)
+    pub fn get_fee_schedule(env: Env) -> Option<FeeSchedule> {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, FEE_SCHEDULE_KEY))
+    }
+
+    /// Get the fee schedule waiting in the parameter-change queue
+    pub fn get_pending_fee_schedule(env: Env) -> Option<PendingFeeSchedule> {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, PENDING_FEE_SCHEDULE_KEY))
+    }
+
+    /// Return a market's creation bond to its creator once the market has settled
+    /// (Resolved, Cancelled or Refunding). Callable by anyone.
+    pub fn release_creator_bond(env: Env, market_id: BytesN<32>) -> i128 {
+        let config =
+            Self::get_market_config(env.clone(), market_id.clone()).expect("market not found");
+        let released_key = (Symbol::new(&env, BOND_RELEASED_PREFIX), market_id.clone());
+        if config.bond == 0 || env.storage().persistent().has(&released_key) {
+            panic!("no bond to release");
+        }
+
+        let market_address =
+            Self::get_market_address(env.clone(), market_id.clone()).expect("market not deployed");
+        let state: Option<u32> = env.invoke_contract(
+            &market_address,
+            &Symbol::new(&env, "get_market_state_value"),
+            Vec::new(&env),
+        );
+        // Resolved = 2, Cancelled = 4, Refunding = 6, Swept = 7
+        if !matches!(state, Some(2) | Some(4) | Some(6) | Some(7)) {
+            panic!("market not settled");
+        }
+
+        let (creator, _, _, _, _, _) =
+            Self::get_market_metadata(env.clone(), market_id.clone()).expect("market not found");
+        let usdc: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, USDC_KEY))
+            .expect("usdc not set");
+        env.storage().persistent().set(&released_key, &true);
+        token::Client::new(&env, &usdc).transfer(
+            &env.current_contract_address(),
+            &creator,
+            &config.bond,
+        );
+
+        CreatorBondReleasedEvent {
+            market_id,
+            creator,
+            amount: config.bond,
+        }
+        .publish(&env);
+        config.bond
+    }
+
+    /// Helper: Bounds shared by DefaultParams and per-market overrides
+    fn validate_params(fee_bps: u32, min_bet: i128, dispute_window: u64) {
+        if fee_bps > MAX_DEFAULT_FEE_BPS {
+            panic!("fee exceeds maximum");
+        }
+        if min_bet < 0 {
+            panic!("min bet must not be negative");
+        }
+        if dispute_window == 0 {
+            panic!("dispute window must be positive");
+        }
+    }
+
+    /// Admin: Set the defaults applied to markets created afterwards
+    pub fn set_default_params(env: Env, admin: Address, params: DefaultParams) {
+        if admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
+        }
+        Self::validate_params(params.fee_bps, params.min_bet, params.dispute_window);
+
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, DEFAULT_PARAMS_KEY), &params);
+    }
+
+    /// Get the defaults applied to new markets
+    pub fn get_default_params(env: Env) -> DefaultParams {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, DEFAULT_PARAMS_KEY))
+            .unwrap_or_else(|| DefaultParams {
+                fee_bps: 0,
+                min_bet: 0,
+                betting_cutoff_offset: DEFAULT_BETTING_CUTOFF_OFFSET,
+                dispute_window: DEFAULT_DISPUTE_WINDOW,
+                token: env
+                    .storage()
+                    .persistent()
+                    .get(&Symbol::new(&env, USDC_KEY))
+                    .expect("usdc not set"),
+            })
+    }
+
+    /// Get the settings a market was created with
+    pub fn get_market_config(env: Env, market_id: BytesN<32>) -> Option<MarketConfig> {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, MARKET_CONFIG_PREFIX), market_id))
+    }
+
+    /// Admin: Allow `creator` to create markets while the factory is permissioned
+    pub fn add_creator(env: Env, admin: Address, creator: Address) {
+        if admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
+        }
+
+        env.storage().persistent().set(
+            &(Symbol::new(&env, APPROVED_CREATOR_PREFIX), creator.clone()),
+            &true,
+        );
+
+        CreatorApprovedEvent { creator }.publish(&env);
+    }
+
+    /// Admin: Revoke `creator`'s approval (their existing markets are unaffected)
+    pub fn remove_creator(env: Env, admin: Address, creator: Address) {
+        if admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&(Symbol::new(&env, APPROVED_CREATOR_PREFIX), creator.clone()));
+
+        CreatorRemovedEvent { creator }.publish(&env);
+    }
+
+    /// Whether `creator` is on the creator allowlist
+    pub fn is_approved_creator(env: Env, creator: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, APPROVED_CREATOR_PREFIX), creator))
+            .unwrap_or(false)
+    }
+
+    /// Admin: Open market creation to anyone (true) or only approved creators (false)
+    pub fn set_permissionless(env: Env, admin: Address, enabled: bool) {
+        if admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
+        }
+
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, PERMISSIONLESS_KEY), &enabled);
+
+        PermissionlessSetEvent { enabled }.publish(&env);
+    }
+
+    /// Whether anyone may create markets (false until the admin opens creation up)
+    pub fn is_permissionless(env: Env) -> bool {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, PERMISSIONLESS_KEY))
+            .unwrap_or(false)
+    }
+
+    /// Super admin: Register a partner operator
+    pub fn register_operator(
+        env: Env,
+        super_admin: Address,
+        operator_id: Symbol,
+        admin: Address,
+        fee_share_bps: u32,
+    ) {
+        if super_admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
+        }
+        if fee_share_bps > 10_000 {
+            panic!("fee share exceeds 100%");
+        }
+        let key = (Symbol::new(&env, OPERATOR_PREFIX), operator_id.clone());
+        if env.storage().persistent().has(&key) {
+            panic!("operator already registered");
+        }
+
+        env.storage().persistent().set(
+            &key,
+            &Operator {
+                admin: admin.clone(),
+                fee_share_bps,
+            },
+        );
+        Self::index_operator(&env, &operator_id);
+
+        OperatorRegisteredEvent {
+            operator_id,
+            admin,
+            fee_share_bps,
+        }
+        .publish(&env);
+    }
+
+    /// Get an operator, if registered
+    pub fn get_operator(env: Env, operator_id: Symbol) -> Option<Operator> {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, OPERATOR_PREFIX), operator_id))
+    }
+
+    /// Helper: Remember `operator_id` so registered operators can be enumerated
+    fn index_operator(env: &Env, operator_id: &Symbol) {
+        let key = Symbol::new(env, OPERATOR_IDS_KEY);
+        let mut operator_ids: Vec<Symbol> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        if !operator_ids.contains(operator_id) {
+            operator_ids.push_back(operator_id.clone());
+            env.storage().persistent().set(&key, &operator_ids);
+        }
+    }
+
+    /// Get the operator a market was created under, if any
+    pub fn get_market_operator(env: Env, market_id: BytesN<32>) -> Option<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, MARKET_OPERATOR_PREFIX), market_id))
+    }
+
+    /// List the markets created under an operator
+    pub fn get_operator_markets(env: Env, operator_id: Symbol) -> Vec<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, OPERATOR_MARKETS_PREFIX), operator_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Operator admin: Pause or resume predictions on one of the operator's markets
+    pub fn set_market_paused(env: Env, caller: Address, market_id: BytesN<32>, paused: bool) {
+        Self::require_market_operator(&env, &caller, &market_id);
+
+        env.storage().persistent().set(
+            &(Symbol::new(&env, MARKET_PAUSED_PREFIX), market_id.clone()),
+            &paused,
+        );
+
+        MarketPausedEvent { market_id, paused }.publish(&env);
+    }
+
+    /// Whether predictions on a market are paused by its operator
+    pub fn is_market_paused(env: Env, market_id: BytesN<32>) -> bool {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, MARKET_PAUSED_PREFIX), market_id))
+            .unwrap_or(false)
+    }
+
+    /// Operator admin: Update the listing metadata of one of the operator's markets
+    pub fn update_market_metadata(
+        env: Env,
+        caller: Address,
+        market_id: BytesN<32>,
+        title: Symbol,
+        description: Symbol,
+        category: Symbol,
+    ) {
+        Self::require_market_operator(&env, &caller, &market_id);
+        if !Self::is_category(env.clone(), category.clone()) {
+            panic!("category not allowed");
+        }
+
+        let metadata_key = (Symbol::new(&env, "market_meta"), market_id.clone());
+        let (creator, _, _, old_category, closing_time, resolution_time): (
+            Address,
+            Symbol,
+            Symbol,
+            Symbol,
+            u64,
+            u64,
+        ) = env
+            .storage()
+            .persistent()
+            .get(&metadata_key)
+            .expect("market not found");
+        env.storage().persistent().set(
+            &metadata_key,
+            &(
+                creator,
+                title,
+                description,
+                category.clone(),
+                closing_time,
+                resolution_time,
+            ),
+        );
+
+        // Move the market between category indices
+        if old_category != category {
+            let old_key = (Symbol::new(&env, CATEGORY_MARKETS_PREFIX), old_category);
+            let mut market_ids: Vec<BytesN<32>> = env
+                .storage()
+                .persistent()
+                .get(&old_key)
+                .unwrap_or(Vec::new(&env));
+            if let Some(position) = market_ids.first_index_of(&market_id) {
+                market_ids.remove(position);
+            }
+            env.storage().persistent().set(&old_key, &market_ids);
+            Self::push_index(
+                &env,
+                &(Symbol::new(&env, CATEGORY_MARKETS_PREFIX), category),
+                &market_id,
+            );
+        }
+    }
+
+    /// Admin: Add a category accepted by create_market
+    pub fn add_category(env: Env, admin: Address, category: Symbol) {
+        if admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
+        }
+        let mut categories = Self::get_categories(env.clone());
+        if categories.contains(&category) {
+            panic!("category already exists");
+        }
+        if categories.len() >= MAX_CATEGORIES {
+            panic!("too many categories");
+        }
+        categories.push_back(category.clone());
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, CATEGORIES_KEY), &categories);
+
+        CategoryAddedEvent { category }.publish(&env);
+    }
+
+    /// Admin: Stop accepting a category for new markets
+    ///
+    /// Markets already in the category stay listed under it.
+    pub fn remove_category(env: Env, admin: Address, category: Symbol) {
+        if admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
+        }
+        let mut categories = Self::get_categories(env.clone());
+        let position = categories
+            .first_index_of(&category)
+            .expect("category not found");
+        categories.remove(position);
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, CATEGORIES_KEY), &categories);
+
+        CategoryRemovedEvent { category }.publish(&env);
+    }
+
+    /// Categories accepted by create_market
+    pub fn get_categories(env: Env) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, CATEGORIES_KEY))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Whether create_market accepts `category`
+    pub fn is_category(env: Env, category: Symbol) -> bool {
+        Self::get_categories(env).contains(&category)
+    }
+
+    /// Admin: Allow or deny markets of `category` in `region`
+    ///
+    /// Either side may be JURISDICTION_ANY. Only creation is gated; live markets
+    /// that the change disallows are recorded in the incident log.
+    pub fn set_jurisdiction_rule(
+        env: Env,
+        admin: Address,
+        region: Symbol,
+        category: Symbol,
+        allowed: bool,
+    ) {
+        if admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
+        }
+
+        // Live markets allowed under the current rules that this deny could affect
+        let mut affected = Vec::new(&env);
+        if !allowed {
+            for market_id in Self::jurisdiction_candidates(&env, &region, &category).iter() {
+                if let Some((market_region, market_category)) =
+                    Self::live_market_jurisdiction(&env, &market_id)
+                {
+                    if Self::is_market_allowed(env.clone(), market_region, market_category) {
+                        affected.push_back(market_id);
+                    }
+                }
+            }
+        }
+
+        env.storage().persistent().set(
+            &(
+                Symbol::new(&env, JURISDICTION_RULE_PREFIX),
+                region.clone(),
+                category.clone(),
+            ),
+            &allowed,
+        );
+
+        for market_id in affected.iter() {
+            let (market_region, market_category) =
+                Self::live_market_jurisdiction(&env, &market_id).expect("market not found");
+            if !Self::is_market_allowed(env.clone(), market_region, market_category) {
+                Self::log_incident(&env, market_id, Symbol::new(&env, JURISDICTION_INCIDENT));
+            }
+        }
+
+        JurisdictionRuleSetEvent {
+            region,
+            category,
+            allowed,
+        }
+        .publish(&env);
+    }
+
+    /// Whether new markets of `category` may be offered in `region`
+    ///
+    /// The most specific rule wins: (region, category), then (region, any),
+    /// then (any, category), then (any, any). Allowed when no rule matches.
+    pub fn is_market_allowed(env: Env, region: Symbol, category: Symbol) -> bool {
+        let any = Symbol::new(&env, JURISDICTION_ANY);
+        for (rule_region, rule_category) in [
+            (region.clone(), category.clone()),
+            (region, any.clone()),
+            (any.clone(), category),
+            (any.clone(), any),
+        ] {
+            if let Some(allowed) = env.storage().persistent().get::<_, bool>(&(
+                Symbol::new(&env, JURISDICTION_RULE_PREFIX),
+                rule_region,
+                rule_category,
+            )) {
+                return allowed;
+            }
+        }
+        true
+    }
+
+    /// Get the region a market was created for
+    pub fn get_market_region(env: Env, market_id: BytesN<32>) -> Option<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, MARKET_REGION_PREFIX), market_id))
+    }
+
+    /// Helper: Markets a rule for (region, category) can apply to
+    fn jurisdiction_candidates(env: &Env, region: &Symbol, category: &Symbol) -> Vec<BytesN<32>> {
+        let any = Symbol::new(env, JURISDICTION_ANY);
+        if *region != any {
+            return env
+                .storage()
+                .persistent()
+                .get(&(Symbol::new(env, REGION_MARKETS_PREFIX), region.clone()))
+                .unwrap_or(Vec::new(env));
+        }
+        if *category != any {
+            return env
+                .storage()
+                .persistent()
+                .get(&(Symbol::new(env, CATEGORY_MARKETS_PREFIX), category.clone()))
+                .unwrap_or(Vec::new(env));
+        }
+        let mut market_ids = Vec::new(env);
+        for index in 0..Self::get_market_count(env.clone()) {
+            if let Some(market_id) = env
+                .storage()
+                .persistent()
+                .get(&(Symbol::new(env, MARKET_INDEX_PREFIX), index))
+            {
+                market_ids.push_back(market_id);
+            }
+        }
+        market_ids
+    }
+
+    /// Helper: (region, category) of a market that has not reached resolution_time
+    fn live_market_jurisdiction(env: &Env, market_id: &BytesN<32>) -> Option<(Symbol, Symbol)> {
+        let (_, _, _, category, _, resolution_time) =
+            Self::get_market_metadata(env.clone(), market_id.clone())?;
+        if time::has_reached(env, resolution_time) {
+            return None;
+        }
+        let region = Self::get_market_region(env.clone(), market_id.clone())?;
+        Some((region, category))
+    }
+
+    /// Admin: Set the pool depth at which a category's markets score full depth
+    pub fn set_reference_depth(env: Env, admin: Address, category: Symbol, depth: u128) {
+        if admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
+        }
+        if depth == 0 {
+            panic!("reference depth must be positive");
+        }
+        env.storage().persistent().set(
+            &(Symbol::new(&env, REFERENCE_DEPTH_PREFIX), category),
+            &depth,
+        );
+    }
+
+    /// Reference pool depth for a category, if configured
+    pub fn get_reference_depth(env: Env, category: Symbol) -> Option<u128> {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, REFERENCE_DEPTH_PREFIX), category))
+    }
+
+    /// Reference pool depth for a market's category (read by the AMM)
+    pub fn get_market_reference_depth(env: Env, market_id: BytesN<32>) -> Option<u128> {
+        let (_, _, _, category, _, _) = Self::get_market_metadata(env.clone(), market_id)?;
+        Self::get_reference_depth(env, category)
+    }
+
+    /// Admin: Set the resting liquidity floor for a category's pools
+    ///
+    /// In basis points of the market's open interest; 0 removes the rule.
+    /// Takes effect on a pool through apply_resting_floor.
+    pub fn set_resting_floor(env: Env, admin: Address, category: Symbol, floor_bps: u32) {
+        if admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
+        }
+        if floor_bps > 10000 {
+            panic!("floor exceeds 100%");
+        }
+        env.storage().persistent().set(
+            &(Symbol::new(&env, RESTING_FLOOR_PREFIX), category),
+            &floor_bps,
+        );
+    }
+
+    /// Resting liquidity floor configured for a category (0 = none)
+    pub fn get_resting_floor(env: Env, category: Symbol) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, RESTING_FLOOR_PREFIX), category))
+            .unwrap_or(0)
+    }
+
+    /// Push a market's category resting floor to its AMM pool (permissionless)
+    pub fn apply_resting_floor(env: Env, market_id: BytesN<32>) {
+        let (_, _, _, category, _, _) =
+            Self::get_market_metadata(env.clone(), market_id.clone()).expect("market not found");
+        let amm = Self::get_amm(env.clone()).expect("amm not set");
+        let floor_bps = Self::get_resting_floor(env.clone(), category);
+        env.invoke_contract::<()>(
+            &amm,
+            &Symbol::new(&env, "set_resting_floor"),
+            (env.current_contract_address(), market_id, floor_bps).into_val(&env),
+        );
+    }
+
+    /// Admin: Set the AMM whose pools back this factory's markets
+    pub fn set_amm(env: Env, admin: Address, amm: Address) {
+        if admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
+        }
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, AMM_KEY), &amm);
+    }
+
+    /// Get the configured AMM, if any
+    pub fn get_amm(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&Symbol::new(&env, AMM_KEY))
+    }
+
+    /// Pool health for up to MAX_HEALTH_BATCH markets, in request order
+    ///
+    /// Scores each pool against its category's reference depth (the AMM's
+    /// default when unset).
+    pub fn get_pools_health(env: Env, market_ids: Vec<BytesN<32>>) -> Vec<PoolHealthSnapshot> {
+        if market_ids.len() > MAX_HEALTH_BATCH {
+            panic!("too many markets");
+        }
+        let amm = Self::get_amm(env.clone()).expect("amm not set");
+
+        let mut rows = Vec::new(&env);
+        for market_id in market_ids.iter() {
+            let depth = Self::get_market_reference_depth(env.clone(), market_id.clone());
+            let health: PoolHealthSnapshot = env.invoke_contract(
+                &amm,
+                &Symbol::new(&env, "get_health_for_depth"),
+                (market_id, depth.unwrap_or(0)).into_val(&env),
+            );
+            rows.push_back(health);
+        }
+        rows
+    }
+
+    /// Get a market's listing metadata:
+    /// (creator, title, description, category, closing_time, resolution_time)
+    pub fn get_market_metadata(
+        env: Env,
+        market_id: BytesN<32>,
+    ) -> Option<(Address, Symbol, Symbol, Symbol, u64, u64)> {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, "market_meta"), market_id))
+    }
+
+    /// Helper: Require `caller` to be the admin of the operator running `market_id`
+    fn require_market_operator(env: &Env, caller: &Address, market_id: &BytesN<32>) {
+        caller.require_auth();
+
+        let operator_id = Self::get_market_operator(env.clone(), market_id.clone())
+            .expect("market has no operator");
+        let operator = Self::get_operator(env.clone(), operator_id).expect("operator not found");
+        if operator.admin != *caller {
+            panic!("Unauthorized: not operator admin");
+        }
+    }
+
+    /// Get the network passphrase hash this factory was bootstrapped on
+    ///
+    /// Destructive admin operations must echo this value back as confirmation.
+    pub fn get_network_binding(env: Env) -> BytesN<32> {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, NETWORK_BINDING_KEY))
+            .expect("Factory not initialized")
+    }
+
+    /// Admin: Halt market creation immediately
+    ///
+    /// Requires the network binding hash as confirmation.
+    pub fn emergency_shutdown(env: Env, network_hash: BytesN<32>) -> Result<(), FactoryError> {
+        let admin = Self::require_admin(&env);
+        Self::require_network(&env, &network_hash)?;
+
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, SHUTDOWN_KEY), &true);
+
+        EmergencyShutdownEvent {
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Whether an emergency shutdown is in effect
+    pub fn is_shut_down(env: Env) -> bool {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, SHUTDOWN_KEY))
+            .unwrap_or(false)
+    }
+
+    /// Admin: Temporarily stop new markets from being created
+    ///
+    /// Existing markets and every lookup keep working; `resume_creation` lifts it.
+    pub fn pause_creation(env: Env, admin: Address) {
+        if admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
+        }
+        if Self::is_creation_paused(env.clone()) {
+            panic!("market creation already paused");
+        }
+
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, CREATION_PAUSED_KEY), &true);
+
+        CreationPausedEvent {
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+    }
+
+    /// Admin: Allow market creation again after `pause_creation`
+    pub fn resume_creation(env: Env, admin: Address) {
+        if admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
+        }
+        if !Self::is_creation_paused(env.clone()) {
+            panic!("market creation not paused");
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&Symbol::new(&env, CREATION_PAUSED_KEY));
 
-        // Store admin address
+        CreationResumedEvent {
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+    }
+
+    /// Whether market creation is paused
+    pub fn is_creation_paused(env: Env) -> bool {
         env.storage()
             .persistent()
-            .set(&Symbol::new(&env, ADMIN_KEY), &admin);
+            .get(&Symbol::new(&env, CREATION_PAUSED_KEY))
+            .unwrap_or(false)
+    }
+
+    /// Admin: Permanently retire the factory (no further markets or template changes)
+    ///
+    /// Requires the network binding hash as confirmation.
+    pub fn sunset(env: Env, network_hash: BytesN<32>) -> Result<(), FactoryError> {
+        let admin = Self::require_admin(&env);
+        Self::require_network(&env, &network_hash)?;
 
-        // Store USDC token contract address
         env.storage()
             .persistent()
-            .set(&Symbol::new(&env, USDC_KEY), &usdc);
+            .set(&Symbol::new(&env, SUNSET_KEY), &true);
 
-        // Store Treasury contract address
+        FactorySunsetEvent {
+            admin,
+            timestamp: env.ledger().timestamp(),
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Whether the factory has been sunset
+    pub fn is_sunset(env: Env) -> bool {
         env.storage()
             .persistent()
-            .set(&Symbol::new(&env, TREASURY_KEY), &treasury);
+            .get(&Symbol::new(&env, SUNSET_KEY))
+            .unwrap_or(false)
+    }
 
-        // Initialize market counter at 0
+    /// Admin: Replace the market contract template (wasm hash) used for new markets
+    ///
+    /// Requires the network binding hash as confirmation. The wasm version is
+    /// bumped by one; use `set_market_wasm_hash` to choose the version number.
+    pub fn set_market_template(
+        env: Env,
+        network_hash: BytesN<32>,
+        wasm_hash: BytesN<32>,
+    ) -> Result<(), FactoryError> {
+        Self::require_admin(&env);
+        Self::require_network(&env, &network_hash)?;
+        let version = Self::get_market_wasm_version(env.clone()) + 1;
+        Self::store_market_wasm(&env, wasm_hash, version);
+        Ok(())
+    }
+
+    /// Admin: Set the market wasm hash for new markets under an explicit version
+    ///
+    /// Requires the network binding hash as confirmation. Versions must strictly
+    /// increase. Existing markets keep running the code they were deployed with
+    /// until `upgrade_market` moves them forward.
+    pub fn set_market_wasm_hash(
+        env: Env,
+        admin: Address,
+        network_hash: BytesN<32>,
+        wasm_hash: BytesN<32>,
+        version: u32,
+    ) -> Result<(), FactoryError> {
+        if admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
+        }
+        Self::require_network(&env, &network_hash)?;
+        if version <= Self::get_market_wasm_version(env.clone()) {
+            panic!("version must increase");
+        }
+        Self::store_market_wasm(&env, wasm_hash, version);
+        Ok(())
+    }
+
+    /// Helper: Record a new market template and its version
+    fn store_market_wasm(env: &Env, wasm_hash: BytesN<32>, version: u32) {
+        if Self::is_sunset(env.clone()) {
+            panic!("factory is sunset");
+        }
+
+        let key = Symbol::new(env, MARKET_TEMPLATE_KEY);
+        let old_template: Option<BytesN<32>> = env.storage().persistent().get(&key);
+        env.storage().persistent().set(&key, &wasm_hash);
         env.storage()
             .persistent()
-            .set(&Symbol::new(&env, MARKET_COUNT_KEY), &0u32);
+            .set(&Symbol::new(env, MARKET_WASM_VERSION_KEY), &version);
 
-        // Emit initialization event
-        FactoryInitializedEvent {
-            admin,
-            usdc,
-            treasury,
+        MarketTemplateUpdatedEvent {
+            old_template,
+            new_template: wasm_hash.clone(),
         }
-        .publish(&env);
+        .publish(env);
+        MarketWasmVersionEvent { wasm_hash, version }.publish(env);
     }
 
-    /// Get total markets created
-    pub fn get_market_count(env: Env) -> u32 {
+    /// Get the version of the current market template (0 when none is set)
+    pub fn get_market_wasm_version(env: Env) -> u32 {
         env.storage()
             .persistent()
-            .get(&Symbol::new(&env, MARKET_COUNT_KEY))
+            .get(&Symbol::new(&env, MARKET_WASM_VERSION_KEY))
             .unwrap_or(0)
     }
 
-    /// Get treasury address
-    pub fn get_treasury(env: Env) -> Address {
+    /// Get the market wasm version a deployed market runs
+    pub fn get_market_version(env: Env, market_id: BytesN<32>) -> Option<u32> {
         env.storage()
             .persistent()
-            .get(&Symbol::new(&env, TREASURY_KEY))
-            .expect("Treasury not set")
+            .get(&(Symbol::new(&env, MARKET_VERSION_PREFIX), market_id))
     }
 
-    /// Create a new market instance
-    pub fn create_market(
+    /// Admin: Move a deployed market onto the current market template
+    ///
+    /// Requires the network binding hash as confirmation. Calls the market's own
+    /// `upgrade` entry point, which only accepts the factory.
+    pub fn upgrade_market(
         env: Env,
-        creator: Address,
-        title: Symbol,
-        description: Symbol,
-        category: Symbol,
-        closing_time: u64,
-        resolution_time: u64,
-    ) -> BytesN<32> {
-        // Require creator authentication
-        creator.require_auth();
+        admin: Address,
+        network_hash: BytesN<32>,
+        market_id: BytesN<32>,
+    ) -> Result<(), FactoryError> {
+        if admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
+        }
+        Self::require_network(&env, &network_hash)?;
+        let market_address =
+            Self::get_market_address(env.clone(), market_id.clone()).expect("market not deployed");
+        let from_version =
+            Self::get_market_version(env.clone(), market_id.clone()).expect("market not deployed");
+        let wasm_hash = Self::get_market_template(env.clone()).expect("market template not set");
+        let to_version = Self::get_market_wasm_version(env.clone());
+        if from_version >= to_version {
+            panic!("market already on latest version");
+        }
 
-        // Validate closing_time > now and < resolution_time
-        let current_time = env.ledger().timestamp();
-        if closing_time <= current_time {
-            panic!("invalid timestamps");
+        env.invoke_contract::<()>(
+            &market_address,
+            &Symbol::new(&env, "upgrade"),
+            (wasm_hash,).into_val(&env),
+        );
+        env.storage().persistent().set(
+            &(Symbol::new(&env, MARKET_VERSION_PREFIX), market_id.clone()),
+            &to_version,
+        );
+
+        MarketUpgradedEvent {
+            market_id,
+            from_version,
+            to_version,
         }
-        if closing_time >= resolution_time {
-            panic!("invalid timestamps");
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Admin: Replace a deployed market's metadata question hash
+    ///
+    /// Only accepted by the market before its first prediction.
+    pub fn update_market_question_hash(
+        env: Env,
+        admin: Address,
+        market_id: BytesN<32>,
+        question_hash: BytesN<32>,
+    ) {
+        if admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
         }
+        let market_address =
+            Self::get_market_address(env.clone(), market_id).expect("market not deployed");
+        env.invoke_contract::<()>(
+            &market_address,
+            &Symbol::new(&env, "update_metadata_hash"),
+            (question_hash,).into_val(&env),
+        );
+    }
 
-        // Get market count and increment
-        let market_count: u32 = env
-            .storage()
+    /// Admin: Freeze or unfreeze a user's claims on a deployed market
+    pub fn set_market_account_frozen(
+        env: Env,
+        admin: Address,
+        market_id: BytesN<32>,
+        user: Address,
+        frozen: bool,
+    ) {
+        if admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
+        }
+        let market_address =
+            Self::get_market_address(env.clone(), market_id).expect("market not deployed");
+        env.invoke_contract::<()>(
+            &market_address,
+            &Symbol::new(&env, "set_account_frozen"),
+            (user, frozen).into_val(&env),
+        );
+    }
+
+    /// Admin: Rebuild a corrupted position on a deployed market from its bet history
+    pub fn rebuild_market_position(
+        env: Env,
+        admin: Address,
+        market_id: BytesN<32>,
+        user: Address,
+        bet_history: Vec<BetRecordSnapshot>,
+    ) {
+        if admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
+        }
+        let market_address =
+            Self::get_market_address(env.clone(), market_id).expect("market not deployed");
+        env.invoke_contract::<()>(
+            &market_address,
+            &Symbol::new(&env, "rebuild_position"),
+            (user, bet_history).into_val(&env),
+        );
+    }
+
+    /// Admin: Push back a deployed market's resolution time (postponed event)
+    pub fn extend_market_resolution_time(
+        env: Env,
+        admin: Address,
+        market_id: BytesN<32>,
+        new_time: u64,
+    ) {
+        if admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
+        }
+        let market_address =
+            Self::get_market_address(env.clone(), market_id).expect("market not deployed");
+        env.invoke_contract::<()>(
+            &market_address,
+            &Symbol::new(&env, "extend_resolution_time"),
+            (new_time,).into_val(&env),
+        );
+    }
+
+    /// Admin: Move a deployed market's betting cutoff earlier
+    pub fn extend_market_betting_cutoff(
+        env: Env,
+        admin: Address,
+        market_id: BytesN<32>,
+        new_cutoff: u64,
+    ) {
+        if admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
+        }
+        let market_address =
+            Self::get_market_address(env.clone(), market_id).expect("market not deployed");
+        env.invoke_contract::<()>(
+            &market_address,
+            &Symbol::new(&env, "extend_betting_cutoff"),
+            (new_cutoff,).into_val(&env),
+        );
+    }
+
+    /// Get the market contract template (wasm hash), if set
+    pub fn get_market_template(env: Env) -> Option<BytesN<32>> {
+        env.storage()
             .persistent()
-            .get(&Symbol::new(&env, MARKET_COUNT_KEY))
-            .unwrap_or(0);
+            .get(&Symbol::new(&env, MARKET_TEMPLATE_KEY))
+    }
 
-        // Generate unique market_id using SHA256
-        let mut hash_input = Bytes::new(&env);
-        hash_input.extend_from_array(&market_count.to_be_bytes());
-        hash_input.extend_from_array(&current_time.to_be_bytes());
+    /// Admin: Set the oracle used by new markets
+    ///
+    /// The oracle must advertise every capability the factory relies on.
+    pub fn set_oracle(env: Env, oracle: Address) {
+        Self::require_admin(&env);
 
-        let hash = env.crypto().sha256(&hash_input);
-        let market_id = BytesN::from_array(&env, &hash.to_array());
+        let capabilities =
+            Self::oracle_capabilities(&env, &oracle).expect("oracle capabilities unavailable");
+        for capability in REQUIRED_ORACLE_CAPABILITIES {
+            if !capabilities.contains(Symbol::new(&env, capability)) {
+                panic!("oracle missing required capability");
+            }
+        }
+
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, ORACLE_KEY), &oracle);
+    }
+
+    /// Get the configured oracle, if any
+    pub fn get_oracle(env: Env) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, ORACLE_KEY))
+    }
+
+    /// Oracle health gate used by create_market
+    ///
+    /// Healthy when an oracle is configured, answers get_capabilities, reports
+    /// every required capability and has enough oracles registered for consensus.
+    pub fn is_oracle_healthy(env: Env) -> bool {
+        let oracle = match Self::get_oracle(env.clone()) {
+            Some(oracle) => oracle,
+            None => return false,
+        };
+        let capabilities = match Self::oracle_capabilities(&env, &oracle) {
+            Some(capabilities) => capabilities,
+            None => return false,
+        };
+        REQUIRED_ORACLE_CAPABILITIES
+            .iter()
+            .chain(["consensus_ready"].iter())
+            .all(|capability| capabilities.contains(Symbol::new(&env, capability)))
+    }
+
+    /// Get the platform fee (bps of winnings profit) applied to new markets
+    pub fn get_fee_bps(env: Env) -> u32 {
+        Self::get_default_params(env).fee_bps
+    }
+
+    /// Admin: Set the platform fee for markets created afterwards
+    ///
+    /// Markets snapshot this rate at initialization, so existing markets keep
+    /// the fee they were created with.
+    pub fn set_fee_bps(env: Env, fee_bps: u32) {
+        Self::require_admin(&env);
+
+        if fee_bps > MAX_DEFAULT_FEE_BPS {
+            panic!("fee exceeds maximum");
+        }
+
+        let mut defaults = Self::get_default_params(env.clone());
+        defaults.fee_bps = fee_bps;
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, DEFAULT_PARAMS_KEY), &defaults);
+    }
+
+    /// Admin: Link a registered market_id to its deployed market contract
+    pub fn set_market_address(env: Env, market_id: BytesN<32>, market_address: Address) {
+        Self::require_admin(&env);
 
-        // Store market in registry
         let market_key = (Symbol::new(&env, "market"), market_id.clone());
-        env.storage().persistent().set(&market_key, &true);
+        if !env.storage().persistent().has(&market_key) {
+            panic!("market not found");
+        }
 
-        // Store market metadata
-        let metadata_key = (Symbol::new(&env, "market_meta"), market_id.clone());
-        let metadata = (
-            creator.clone(),
-            title.clone(),
+        env.storage().persistent().set(
+            &(Symbol::new(&env, MARKET_ADDRESS_PREFIX), market_id),
+            &market_address,
+        );
+    }
+
+    /// Get the deployed contract address for a market, if linked
+    pub fn get_market_address(env: Env, market_id: BytesN<32>) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, MARKET_ADDRESS_PREFIX), market_id))
+    }
+
+    /// Consolidated view of a user's positions across several markets
+    ///
+    /// Reads each market's `get_portfolio(user)` and returns one row per requested
+    /// market_id, in order. Unknown ids (or markets whose read fails) produce a row
+    /// with a non-OK status instead of aborting the whole call.
+    /// Bounded to MAX_EXPOSURE_MARKETS ids per call.
+    pub fn get_user_exposure(
+        env: Env,
+        user: Address,
+        market_ids: Vec<BytesN<32>>,
+    ) -> Vec<ExposureRow> {
+        if market_ids.len() > MAX_EXPOSURE_MARKETS {
+            panic!("too many markets");
+        }
+
+        let mut rows = Vec::new(&env);
+        for market_id in market_ids.iter() {
+            let mut row = ExposureRow {
+                market_id: market_id.clone(),
+                status: EXPOSURE_STATUS_UNKNOWN_MARKET,
+                stake: 0,
+                side: 2,
+                implied_probability_bps: 0,
+                claimable: 0,
+            };
+
+            if let Some(market_address) = Self::get_market_address(env.clone(), market_id) {
+                let result = env.try_invoke_contract::<PortfolioSnapshot, soroban_sdk::Error>(
+                    &market_address,
+                    &Symbol::new(&env, "get_portfolio"),
+                    (user.clone(),).into_val(&env),
+                );
+                match result {
+                    Ok(Ok(portfolio)) => {
+                        row.status = EXPOSURE_STATUS_OK;
+                        row.stake = portfolio.stake;
+                        row.side = portfolio.side;
+                        row.implied_probability_bps = portfolio.implied_probability_bps;
+                        row.claimable = portfolio.claimable;
+                    }
+                    _ => row.status = EXPOSURE_STATUS_READ_FAILED,
+                }
+            }
+
+            rows.push_back(row);
+        }
+
+        rows
+    }
+
+    /// Registry, market and oracle state for several markets in one read
+    ///
+    /// Returns one overview per requested market_id, in order. A failing market
+    /// or oracle read sets the matching OVERVIEW_* flag on that entry instead of
+    /// aborting the call. Bounded to MAX_OVERVIEW_BATCH ids per call.
+    pub fn get_market_overviews(env: Env, market_ids: Vec<BytesN<32>>) -> Vec<MarketOverview> {
+        if market_ids.len() > MAX_OVERVIEW_BATCH {
+            panic!("too many markets");
+        }
+        let oracle = Self::get_oracle(env.clone());
+
+        let mut overviews = Vec::new(&env);
+        for market_id in market_ids.iter() {
+            let mut overview = MarketOverview {
+                market_id: market_id.clone(),
+                errors: 0,
+                address: None,
+                creator: None,
+                category: None,
+                created_at: 0,
+                status: 0,
+                closing_time: 0,
+                total_pool: 0,
+                participant_count: 0,
+                winning_outcome: None,
+                pools_revealed: false,
+                oracle_tracked: false,
+                tally: (0, 0),
+                consensus_reached: false,
+                consensus_outcome: 0,
+                finalized: false,
+                challenged: false,
+                overridden: false,
+            };
+            let record = match Self::market_record_by_id(&env, market_id.clone()) {
+                Some(record) => record,
+                None => {
+                    overview.errors |= OVERVIEW_UNKNOWN_MARKET;
+                    overviews.push_back(overview);
+                    continue;
+                }
+            };
+            overview.creator = Some(record.creator);
+            overview.category = Some(record.category);
+            overview.created_at = record.created_at;
+
+            if let Some(market_address) = record.address {
+                let result = env.try_invoke_contract::<MarketStateSnapshot, soroban_sdk::Error>(
+                    &market_address,
+                    &Symbol::new(&env, "get_market_state"),
+                    (market_id.clone(),).into_val(&env),
+                );
+                match result {
+                    Ok(Ok(state)) => {
+                        overview.status = state.status;
+                        overview.closing_time = state.closing_time;
+                        overview.total_pool = state.total_pool;
+                        overview.participant_count = state.participant_count;
+                        overview.winning_outcome = state.winning_outcome;
+                        overview.pools_revealed = state.pools_revealed;
+                    }
+                    _ => overview.errors |= OVERVIEW_MARKET_READ_FAILED,
+                }
+                overview.address = Some(market_address);
+            }
+
+            if let Some(oracle) = &oracle {
+                let result = env
+                    .try_invoke_contract::<Option<ResolutionStateSnapshot>, soroban_sdk::Error>(
+                        oracle,
+                        &Symbol::new(&env, "get_market_resolution_state"),
+                        (market_id.clone(),).into_val(&env),
+                    );
+                match result {
+                    Ok(Ok(Some(resolution))) => {
+                        overview.oracle_tracked = true;
+                        overview.tally = resolution.tally;
+                        overview.consensus_reached = resolution.consensus_reached;
+                        overview.consensus_outcome = resolution.consensus_outcome;
+                        overview.finalized = resolution.finalized;
+                        overview.challenged = resolution.challenged;
+                        overview.overridden = resolution.overridden;
+                    }
+                    Ok(Ok(None)) => {}
+                    _ => overview.errors |= OVERVIEW_ORACLE_READ_FAILED,
+                }
+            }
+
+            overviews.push_back(overview);
+        }
+
+        overviews
+    }
+
+    /// Registry, market, oracle, AMM and treasury state for one market
+    ///
+    /// Panics only for a market_id the factory never registered; every
+    /// cross-contract read that is unavailable or fails leaves its fields None.
+    pub fn get_market_summary(env: Env, market_id: BytesN<32>) -> MarketSummary {
+        let (creator, title, description, category, closing_time, resolution_time) =
+            Self::get_market_metadata(env.clone(), market_id.clone()).expect("market not found");
+        let mut summary = MarketSummary {
+            market_id: market_id.clone(),
+            address: Self::get_market_address(env.clone(), market_id.clone()),
+            creator,
+            title,
             description,
             category,
             closing_time,
             resolution_time,
-        );
-        env.storage().persistent().set(&metadata_key, &metadata);
+            status: None,
+            total_pool: None,
+            participant_count: None,
+            winning_outcome: None,
+            yes_total: None,
+            no_total: None,
+            yes_attestations: None,
+            no_attestations: None,
+            consensus_reached: None,
+            consensus_outcome: None,
+            yes_price: None,
+            no_price: None,
+            fees_collected: None,
+        };
 
-        // Increment market counter
-        env.storage()
+        if let Some(market_address) = &summary.address {
+            let state = env
+                .try_invoke_contract::<MarketStateSnapshot, soroban_sdk::Error>(
+                    market_address,
+                    &Symbol::new(&env, "get_market_state"),
+                    (market_id.clone(),).into_val(&env),
+                )
+                .ok()
+                .and_then(|result| result.ok());
+            if let Some(state) = state {
+                summary.status = Some(state.status);
+                summary.total_pool = Some(state.total_pool);
+                summary.participant_count = Some(state.participant_count);
+                summary.winning_outcome = state.winning_outcome;
+                let pools = env
+                    .try_invoke_contract::<PoolsSnapshot, soroban_sdk::Error>(
+                        market_address,
+                        &Symbol::new(&env, "get_pools"),
+                        Vec::new(&env),
+                    )
+                    .ok()
+                    .and_then(|result| result.ok());
+                if let Some(pools) = pools.filter(|_| state.pools_revealed) {
+                    summary.yes_total = Some(pools.yes_total);
+                    summary.no_total = Some(pools.no_total);
+                }
+            }
+        }
+
+        if let Some(oracle) = Self::get_oracle(env.clone()) {
+            let resolution = env
+                .try_invoke_contract::<Option<ResolutionStateSnapshot>, soroban_sdk::Error>(
+                    &oracle,
+                    &Symbol::new(&env, "get_market_resolution_state"),
+                    (market_id.clone(),).into_val(&env),
+                )
+                .ok()
+                .and_then(|result| result.ok())
+                .flatten();
+            if let Some(resolution) = resolution {
+                summary.yes_attestations = Some(resolution.tally.0);
+                summary.no_attestations = Some(resolution.tally.1);
+                summary.consensus_reached = Some(resolution.consensus_reached);
+                summary.consensus_outcome = Some(resolution.consensus_outcome);
+            }
+        }
+
+        if let Some(amm) = Self::get_amm(env.clone()) {
+            // get_spot_price panics for a missing, drained or terminal pool
+            let spot_price = |outcome: u32| {
+                env.try_invoke_contract::<u32, soroban_sdk::Error>(
+                    &amm,
+                    &Symbol::new(&env, "get_spot_price"),
+                    (market_id.clone(), outcome).into_val(&env),
+                )
+                .ok()
+                .and_then(|result| result.ok())
+            };
+            summary.yes_price = spot_price(1);
+            summary.no_price = spot_price(0);
+        }
+
+        let treasury: Option<Address> = env
+            .storage()
             .persistent()
-            .set(&Symbol::new(&env, MARKET_COUNT_KEY), &(market_count + 1));
+            .get(&Symbol::new(&env, TREASURY_KEY));
+        let token: Option<Address> = env.storage().persistent().get(&Symbol::new(&env, USDC_KEY));
+        if let (Some(treasury), Some(token)) = (treasury, token) {
+            summary.fees_collected = env
+                .try_invoke_contract::<i128, soroban_sdk::Error>(
+                    &treasury,
+                    &Symbol::new(&env, "get_market_token_fees"),
+                    (market_id, token).into_val(&env),
+                )
+                .ok()
+                .and_then(|result| result.ok());
+        }
 
-        // Charge creation fee (1 USDC = 10^7 stroops, assuming 7 decimals)
-        let creation_fee: i128 = 10_000_000; // 1 USDC
-        let treasury_address: Address = env
+        summary
+    }
+
+    /// Helper: Read an oracle's capability descriptor (None if the call fails)
+    fn oracle_capabilities(env: &Env, oracle: &Address) -> Option<Vec<Symbol>> {
+        env.try_invoke_contract::<Vec<Symbol>, soroban_sdk::Error>(
+            oracle,
+            &Symbol::new(env, "get_capabilities"),
+            Vec::new(env),
+        )
+        .ok()
+        .and_then(|result| result.ok())
+    }
+
+    /// Helper: Load the admin and require its authorization
+    fn require_admin(env: &Env) -> Address {
+        let admin: Address = env
             .storage()
             .persistent()
-            .get(&Symbol::new(&env, TREASURY_KEY))
-            .expect("Treasury address not set");
+            .get(&Symbol::new(env, ADMIN_KEY))
+            .expect("Factory not initialized");
+        admin.require_auth();
+        admin
+    }
 
-        // Cross-contract call to Treasury using contract address
-        // This works because we're calling by address at runtime, not compile-time module reference
-        env.invoke_contract::<()>(
-            &treasury_address,
-            &Symbol::new(&env, "deposit_fees"),
-            (creator.clone(), creation_fee).into_val(&env),
-        );
+    /// Helper: Guard against running a destructive operation on the wrong network
+    fn require_network(env: &Env, network_hash: &BytesN<32>) -> Result<(), FactoryError> {
+        let binding: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, NETWORK_BINDING_KEY))
+            .expect("Factory not initialized");
+        if binding != *network_hash {
+            return Err(FactoryError::NetworkMismatch);
+        }
+        Ok(())
+    }
 
-        // Emit MarketCreated event
-        MarketCreatedEvent {
-            market_id: market_id.clone(),
-            creator,
-            closing_time,
+    /// Claim winnings from several markets in one transaction
+    ///
+    /// Calls each linked market's `claim_winnings(user, market_id)`. Markets that
+    /// are unknown, unresolved, already claimed or otherwise not claimable are
+    /// skipped instead of aborting the batch. Returns `(market_id, amount)` for
+    /// every market actually claimed. Bounded to MAX_CLAIM_BATCH ids per call.
+    pub fn claim_all(
+        env: Env,
+        user: Address,
+        market_ids: Vec<BytesN<32>>,
+    ) -> Vec<(BytesN<32>, i128)> {
+        user.require_auth();
+
+        if market_ids.len() > MAX_CLAIM_BATCH {
+            panic!("too many markets");
+        }
+
+        let mut claimed = Vec::new(&env);
+        let mut total_amount: i128 = 0;
+        for market_id in market_ids.iter() {
+            let market_address = match Self::get_market_address(env.clone(), market_id.clone()) {
+                Some(address) => address,
+                None => continue,
+            };
+            let result = env.try_invoke_contract::<i128, soroban_sdk::Error>(
+                &market_address,
+                &Symbol::new(&env, "claim_winnings"),
+                (user.clone(), market_id.clone()).into_val(&env),
+            );
+            if let Ok(Ok(amount)) = result {
+                total_amount += amount;
+                claimed.push_back((market_id, amount));
+            }
+        }
+
+        BatchClaimedEvent {
+            user,
+            market_count: claimed.len(),
+            total_amount,
         }
         .publish(&env);
 
-        market_id
+        claimed
     }
 
     /// Get market info by market_id
@@ -185,21 +2925,11 @@ impl MarketFactory {
         todo!("See get active markets TODO above")
     }
 
-    /// Get user's created markets
-    pub fn get_creator_markets(_env: Env, _creator: Address) {
-        todo!("See get creator markets TODO above")
-    }
-
     /// Get market resolution
     pub fn get_market_resolution(_env: Env, _market_id: BytesN<32>) -> Symbol {
         todo!("See get market resolution TODO above")
     }
 
-    /// Admin: Pause market creation (emergency)
-    pub fn set_market_creation_pause(_env: Env, _paused: bool) {
-        todo!("See set market creation pause TODO above")
-    }
-
     /// Get factory statistics
     pub fn get_factory_stats(_env: Env) {
         todo!("See get factory stats TODO above")
@@ -215,3 +2945,240 @@ impl MarketFactory {
         todo!("See withdraw fees TODO above")
     }
 }
+
+/// Staging-only registry snapshots, compiled in with the `staging-tools` feature
+///
+/// Lets QA rebuild a redeployed staging factory's registry without replaying
+/// every create_market transaction. Never enable this feature for production builds.
+#[cfg(feature = "staging-tools")]
+#[contractimpl]
+impl MarketFactory {
+    /// Admin: Export one page of the registry, at most MAX_LIST_MARKETS entries
+    ///
+    /// Entries come in a fixed order: categories, then operators, then markets
+    /// in creation order. A page shorter than `limit` is the last one.
+    pub fn export_registry(env: Env, start: u32, limit: u32) -> Vec<RegistryEntryExport> {
+        Self::require_admin(&env);
+
+        let categories = Self::get_categories(env.clone());
+        let operator_ids: Vec<Symbol> = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, OPERATOR_IDS_KEY))
+            .unwrap_or(Vec::new(&env));
+        let market_start = categories.len() + operator_ids.len();
+        let total = market_start + Self::get_market_count(env.clone());
+        let end = start.saturating_add(limit.min(MAX_LIST_MARKETS)).min(total);
+
+        let mut entries = Vec::new(&env);
+        for position in start..end {
+            let entry = if position < categories.len() {
+                let category = categories.get_unchecked(position);
+                RegistryEntryExport::Category(CategoryExport {
+                    reference_depth: Self::get_reference_depth(env.clone(), category.clone()),
+                    resting_floor_bps: Self::get_resting_floor(env.clone(), category.clone()),
+                    category,
+                })
+            } else if position < market_start {
+                let operator_id = operator_ids.get_unchecked(position - categories.len());
+                RegistryEntryExport::Operator(OperatorExport {
+                    operator: Self::get_operator(env.clone(), operator_id.clone())
+                        .expect("operator not found"),
+                    operator_id,
+                })
+            } else {
+                let record =
+                    Self::market_record(&env, position - market_start).expect("market not found");
+                RegistryEntryExport::Market(Self::export_market(&env, record))
+            };
+            entries.push_back(entry);
+        }
+        entries
+    }
+
+    /// Admin: Restore entries produced by export_registry
+    ///
+    /// Only registry bookkeeping is written: no fees, bonds, oracle registration
+    /// or deployments happen. Importing into a registry that already lists
+    /// markets requires `force`; markets already present are then skipped.
+    pub fn import_registry(
+        env: Env,
+        admin: Address,
+        entries: Vec<RegistryEntryExport>,
+        force: bool,
+    ) {
+        if admin != Self::require_admin(&env) {
+            panic!("Unauthorized");
+        }
+        if !force && Self::get_market_count(env.clone()) > 0 {
+            panic!("registry not empty");
+        }
+
+        let mut markets_imported = 0u32;
+        for entry in entries.iter() {
+            match entry {
+                RegistryEntryExport::Category(export) => Self::import_category(&env, export),
+                RegistryEntryExport::Operator(export) => {
+                    env.storage().persistent().set(
+                        &(
+                            Symbol::new(&env, OPERATOR_PREFIX),
+                            export.operator_id.clone(),
+                        ),
+                        &export.operator,
+                    );
+                    Self::index_operator(&env, &export.operator_id);
+                }
+                RegistryEntryExport::Market(export) => {
+                    if Self::import_market(&env, export) {
+                        markets_imported += 1;
+                    }
+                }
+            }
+        }
+
+        RegistryImportedEvent {
+            entries: entries.len(),
+            markets_imported,
+        }
+        .publish(&env);
+    }
+
+    /// Helper: Gather the registry's view of one market
+    fn export_market(env: &Env, record: MarketRecord) -> MarketExport {
+        let market_id = record.market_id.clone();
+        let (_, title, description, _, closing_time, resolution_time) =
+            Self::get_market_metadata(env.clone(), market_id.clone()).expect("market not found");
+        MarketExport {
+            title,
+            description,
+            closing_time,
+            resolution_time,
+            region: Self::get_market_region(env.clone(), market_id.clone()),
+            config: Self::get_market_config(env.clone(), market_id.clone())
+                .expect("market config not found"),
+            operator: Self::get_market_operator(env.clone(), market_id.clone()),
+            paused: Self::is_market_paused(env.clone(), market_id),
+            record,
+        }
+    }
+
+    /// Helper: Accept an exported category and its reference depth
+    fn import_category(env: &Env, export: CategoryExport) {
+        let mut categories = Self::get_categories(env.clone());
+        if !categories.contains(&export.category) {
+            if categories.len() >= MAX_CATEGORIES {
+                panic!("too many categories");
+            }
+            categories.push_back(export.category.clone());
+            env.storage()
+                .persistent()
+                .set(&Symbol::new(env, CATEGORIES_KEY), &categories);
+        }
+        if let Some(depth) = export.reference_depth {
+            env.storage().persistent().set(
+                &(
+                    Symbol::new(env, REFERENCE_DEPTH_PREFIX),
+                    export.category.clone(),
+                ),
+                &depth,
+            );
+        }
+        if export.resting_floor_bps > 0 {
+            env.storage().persistent().set(
+                &(Symbol::new(env, RESTING_FLOOR_PREFIX), export.category),
+                &export.resting_floor_bps,
+            );
+        }
+    }
+
+    /// Helper: Write the same registry entries create_market would; false if already listed
+    fn import_market(env: &Env, export: MarketExport) -> bool {
+        let record = export.record;
+        let market_id = record.market_id;
+        let market_key = (Symbol::new(env, "market"), market_id.clone());
+        if env.storage().persistent().has(&market_key) {
+            return false;
+        }
+        env.storage().persistent().set(&market_key, &true);
+        env.storage().persistent().set(
+            &(Symbol::new(env, "market_meta"), market_id.clone()),
+            &(
+                record.creator.clone(),
+                export.title,
+                export.description,
+                record.category.clone(),
+                export.closing_time,
+                export.resolution_time,
+            ),
+        );
+
+        Self::push_index(
+            env,
+            &(Symbol::new(env, CREATOR_MARKETS_PREFIX), record.creator),
+            &market_id,
+        );
+        Self::push_index(
+            env,
+            &(Symbol::new(env, CATEGORY_MARKETS_PREFIX), record.category),
+            &market_id,
+        );
+        if let Some(region) = export.region {
+            Self::push_index(
+                env,
+                &(Symbol::new(env, REGION_MARKETS_PREFIX), region.clone()),
+                &market_id,
+            );
+            env.storage().persistent().set(
+                &(Symbol::new(env, MARKET_REGION_PREFIX), market_id.clone()),
+                &region,
+            );
+        }
+        env.storage().persistent().set(
+            &(Symbol::new(env, MARKET_CONFIG_PREFIX), market_id.clone()),
+            &export.config,
+        );
+        if let Some(operator_id) = export.operator {
+            env.storage().persistent().set(
+                &(Symbol::new(env, MARKET_OPERATOR_PREFIX), market_id.clone()),
+                &operator_id,
+            );
+            Self::push_index(
+                env,
+                &(Symbol::new(env, OPERATOR_MARKETS_PREFIX), operator_id),
+                &market_id,
+            );
+        }
+        if export.paused {
+            env.storage().persistent().set(
+                &(Symbol::new(env, MARKET_PAUSED_PREFIX), market_id.clone()),
+                &true,
+            );
+        }
+        if let Some(address) = record.address {
+            env.storage().persistent().set(
+                &(Symbol::new(env, MARKET_ADDRESS_PREFIX), market_id.clone()),
+                &address,
+            );
+        }
+        if record.wasm_version != 0 {
+            env.storage().persistent().set(
+                &(Symbol::new(env, MARKET_VERSION_PREFIX), market_id.clone()),
+                &record.wasm_version,
+            );
+        }
+
+        let market_count = Self::get_market_count(env.clone());
+        env.storage().persistent().set(
+            &(Symbol::new(env, MARKET_INDEX_PREFIX), market_count),
+            &market_id,
+        );
+        env.storage().persistent().set(
+            &(Symbol::new(env, MARKET_CREATED_AT_PREFIX), market_id),
+            &record.created_at,
+        );
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(env, MARKET_COUNT_KEY), &(market_count + 1));
+        true
+    }
+}
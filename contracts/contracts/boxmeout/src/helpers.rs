@@ -3,6 +3,22 @@
 use soroban_sdk::{Address, BytesN, Env, Symbol};
 // use crate::helpers::*;
 
+pub mod access;
+pub mod checked;
+pub mod events;
+pub mod fixed;
+pub mod guard;
+pub mod hashing;
+pub mod kyc;
+pub mod math;
+pub mod rand;
+pub mod time;
+
+pub use checked::{
+    safe_add_i128, safe_add_u128, safe_add_u32, safe_add_u64, safe_sub_i128, safe_sub_u128,
+    safe_sub_u32, safe_sub_u64,
+};
+
 #[allow(dead_code)]
 const POOL_YES_RESERVE: &str = "pool_yes_reserve";
 #[allow(dead_code)]
@@ -0,0 +1,188 @@
+// Role membership shared by the contracts
+//
+// Each role keeps its members as an ordered list in the calling contract's
+// persistent storage, so a contract granting roles only ever sees its own
+// members. Lists stay small (signers, registrars), so lookups are linear.
+
+use soroban_sdk::{contractevent, contracttype, Address, Env, Symbol, Vec};
+
+const ROLE_MEMBERS_KEY: &str = "role_members";
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+    /// Administers the contract (the oracle's emergency override signers)
+    Admin,
+    /// Runs markets on behalf of an operator
+    Operator,
+    /// Attests market outcomes
+    Oracle,
+    /// Registers markets on another contract's behalf (e.g. the factory)
+    Registrar,
+}
+
+#[contractevent]
+pub struct RoleGrantedEvent {
+    pub role: Role,
+    pub account: Address,
+}
+
+#[contractevent]
+pub struct RoleRevokedEvent {
+    pub role: Role,
+    pub account: Address,
+}
+
+/// Members of `role`, in grant order
+pub fn role_members(env: &Env, role: Role) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&(Symbol::new(env, ROLE_MEMBERS_KEY), role))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Whether `account` holds `role`
+pub fn has_role(env: &Env, role: Role, account: &Address) -> bool {
+    role_members(env, role).contains(account)
+}
+
+/// Add `account` to `role`; false (and no event) if it already held it
+pub fn grant_role(env: &Env, role: Role, account: &Address) -> bool {
+    let mut members = role_members(env, role);
+    if members.contains(account) {
+        return false;
+    }
+    members.push_back(account.clone());
+    env.storage()
+        .persistent()
+        .set(&(Symbol::new(env, ROLE_MEMBERS_KEY), role), &members);
+
+    RoleGrantedEvent {
+        role,
+        account: account.clone(),
+    }
+    .publish(env);
+    true
+}
+
+/// Remove `account` from `role`; false (and no event) if it did not hold it
+pub fn revoke_role(env: &Env, role: Role, account: &Address) -> bool {
+    let mut members = role_members(env, role);
+    let Some(index) = members.first_index_of(account) else {
+        return false;
+    };
+    members.remove(index);
+    env.storage()
+        .persistent()
+        .set(&(Symbol::new(env, ROLE_MEMBERS_KEY), role), &members);
+
+    RoleRevokedEvent {
+        role,
+        account: account.clone(),
+    }
+    .publish(env);
+    true
+}
+
+/// Require `caller`'s authorization and membership in `role`
+pub fn require_role(env: &Env, role: Role, caller: &Address) {
+    caller.require_auth();
+    if !has_role(env, role, caller) {
+        panic!("Unauthorized");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, AuthorizedFunction};
+    use soroban_sdk::{contract, contractimpl};
+
+    #[contract]
+    struct Guarded;
+
+    #[contractimpl]
+    impl Guarded {
+        pub fn grant(env: Env, role: Role, account: Address) -> bool {
+            grant_role(&env, role, &account)
+        }
+
+        pub fn revoke(env: Env, role: Role, account: Address) -> bool {
+            revoke_role(&env, role, &account)
+        }
+
+        pub fn has(env: Env, role: Role, account: Address) -> bool {
+            has_role(&env, role, &account)
+        }
+
+        pub fn guarded(env: Env, caller: Address) {
+            require_role(&env, Role::Admin, &caller);
+        }
+    }
+
+    #[test]
+    fn test_grant_and_revoke_are_per_role_and_idempotent() {
+        let env = Env::default();
+        let client = GuardedClient::new(&env, &env.register(Guarded, ()));
+        let account = Address::generate(&env);
+
+        assert!(client.grant(&Role::Registrar, &account));
+        assert!(!client.grant(&Role::Registrar, &account));
+        assert!(client.has(&Role::Registrar, &account));
+        assert!(!client.has(&Role::Admin, &account));
+
+        assert!(client.revoke(&Role::Registrar, &account));
+        assert!(!client.revoke(&Role::Registrar, &account));
+        assert!(!client.has(&Role::Registrar, &account));
+    }
+
+    #[test]
+    fn test_role_members_keep_grant_order() {
+        let env = Env::default();
+        let contract_id = env.register(Guarded, ());
+        let client = GuardedClient::new(&env, &contract_id);
+        let first = Address::generate(&env);
+        let second = Address::generate(&env);
+        let third = Address::generate(&env);
+        for account in [&first, &second, &third] {
+            client.grant(&Role::Admin, account);
+        }
+        client.revoke(&Role::Admin, &second);
+
+        let members = env.as_contract(&contract_id, || role_members(&env, Role::Admin));
+        assert_eq!(members, soroban_sdk::vec![&env, first, third]);
+    }
+
+    #[test]
+    fn test_require_role_checks_auth_and_membership() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(Guarded, ());
+        let client = GuardedClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        client.grant(&Role::Admin, &admin);
+
+        client.guarded(&admin);
+        let auths = env.auths();
+        assert_eq!(auths.len(), 1);
+        assert_eq!(auths[0].0, admin);
+        assert!(matches!(
+            &auths[0].1.function,
+            AuthorizedFunction::Contract((address, _, _)) if *address == contract_id
+        ));
+
+        // A member still needs to authorize
+        env.set_auths(&[]);
+        assert!(client.try_guarded(&admin).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Unauthorized")]
+    fn test_require_role_rejects_non_member() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let client = GuardedClient::new(&env, &env.register(Guarded, ()));
+
+        client.guarded(&Address::generate(&env));
+    }
+}
@@ -0,0 +1,77 @@
+// Checked arithmetic for balances and counters
+//
+// Overflow panics with a stable message instead of a bare arithmetic trap, so
+// a failing call names the problem in both debug and release builds.
+
+macro_rules! checked_ops {
+    ($($ty:ty => $add:ident, $sub:ident;)*) => {
+        $(
+            /// `a + b`, panicking on overflow
+            pub fn $add(a: $ty, b: $ty) -> $ty {
+                a.checked_add(b).expect("arithmetic overflow")
+            }
+
+            /// `a - b`, panicking on underflow
+            pub fn $sub(a: $ty, b: $ty) -> $ty {
+                a.checked_sub(b).expect("arithmetic underflow")
+            }
+        )*
+    };
+}
+
+checked_ops! {
+    u32 => safe_add_u32, safe_sub_u32;
+    u64 => safe_add_u64, safe_sub_u64;
+    u128 => safe_add_u128, safe_sub_u128;
+    i128 => safe_add_i128, safe_sub_i128;
+}
+
+/// Panic with a contract error unless `cond` holds
+///
+/// `assert_invariant!(env, cond, error)` surfaces a broken invariant as the
+/// contract's own error code rather than a generic trap.
+#[macro_export]
+macro_rules! assert_invariant {
+    ($env:expr, $cond:expr, $error:expr) => {
+        if !($cond) {
+            soroban_sdk::panic_with_error!($env, $error);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_range_results_pass_through() {
+        assert_eq!(safe_add_u32(u32::MAX - 1, 1), u32::MAX);
+        assert_eq!(safe_sub_u64(5, 5), 0);
+        assert_eq!(safe_add_u128(u128::MAX - 2, 2), u128::MAX);
+        assert_eq!(safe_sub_i128(i128::MIN + 1, 1), i128::MIN);
+    }
+
+    #[test]
+    #[should_panic(expected = "arithmetic overflow")]
+    fn test_u32_counter_overflow() {
+        safe_add_u32(u32::MAX, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "arithmetic underflow")]
+    fn test_u64_timestamp_underflow() {
+        safe_sub_u64(0, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "arithmetic overflow")]
+    fn test_i128_balance_overflow() {
+        safe_add_i128(i128::MAX, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "arithmetic underflow")]
+    fn test_u128_reserve_underflow() {
+        safe_sub_u128(1, 2);
+    }
+}
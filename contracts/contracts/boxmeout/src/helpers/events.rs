@@ -0,0 +1,2224 @@
+// Event schema shared by the contracts
+//
+// Every event published through this module has the same layout:
+// - topic 0: a short event name, listed in the contract's `*_EVENTS` table
+// - topic 1: the entity the event is about, a market id or an address;
+//   contract-wide settings changes have none
+// - further topics narrow it down (e.g. the oracle that attested a market)
+// - data: the remaining fields as a vec, in declaration order
+//
+// Contracts publish through the emit functions, never the structs directly, so
+// a call site cannot drift from the layout. Market, AMM and treasury events
+// that predate the schema (e.g. the market's initialized and closed events)
+// still publish from their contract with the SDK's default layout; new events
+// belong here, with their names in a table.
+
+/// Topic-0 names of every oracle event, in declaration order
+///
+/// An indexer keys on these; renaming or removing one is a breaking change.
+pub const ORACLE_EVENTS: [&str; 28] = [
+    "init",
+    "oracle_reg",
+    "oracle_dereg",
+    "oracle_cats",
+    "market_reg",
+    "market_bind",
+    "res_time",
+    "registrar",
+    "tie_break",
+    "strict_hash",
+    "submitter",
+    "attest",
+    "value",
+    "staking",
+    "bond_cfg",
+    "bond_refund",
+    "bond_forfeit",
+    "finalize",
+    "subscribe",
+    "deliver",
+    "alert_hook",
+    "alert",
+    "archive",
+    "challenge",
+    "challenge_res",
+    "invalidate",
+    "reattest_win",
+    "override",
+];
+
+/// Topic-0 names of the market events published through `market`, in declaration order
+///
+/// An indexer keys on these; renaming or removing one is a breaking change.
+pub const MARKET_EVENTS: [&str; 21] = [
+    "resolve",
+    "claim",
+    "prediction",
+    "position",
+    "pos_xfer",
+    "operator",
+    "pos_rebuild",
+    "state",
+    "dispute_flag",
+    "swept",
+    "cutoff",
+    "guarantee",
+    "guarantee_rel",
+    "void",
+    "oracle_timeout",
+    "notify",
+    "res_time",
+    "anti_snipe",
+    "freeze",
+    "metadata",
+    "loss_refund",
+];
+
+/// Topic-0 names of the AMM events published through `amm`, in declaration order
+///
+/// An indexer keys on these; renaming or removing one is a breaking change.
+pub const AMM_EVENTS: [&str; 9] = [
+    "pool_init",
+    "swap",
+    "pool_pause",
+    "resting_floor",
+    "pool_fee",
+    "fee_flush",
+    "settle",
+    "redeem",
+    "lp_fees",
+];
+
+/// Topic-0 names of the treasury events published through `treasury`, in declaration order
+///
+/// An indexer keys on these; renaming or removing one is a breaking change.
+pub const TREASURY_EVENTS: [&str; 30] = [
+    "fee_deposit",
+    "token",
+    "creator_split",
+    "wd_limit",
+    "creator_reg",
+    "creator_claim",
+    "velocity",
+    "breaker",
+    "unfreeze",
+    "archive",
+    "ins_release",
+    "ins_rebalance",
+    "wd_propose",
+    "wd_approve",
+    "wd_execute",
+    "payee",
+    "creditor",
+    "credit",
+    "claim",
+    "vest_create",
+    "vest_claim",
+    "vest_cancel",
+    "depositor",
+    "return_propose",
+    "return_block",
+    "keeper_bounty",
+    "op_fee",
+    "op_withdraw",
+    "auditor",
+    "return_exec",
+];
+
+#[cfg(any(feature = "oracle", test, feature = "testutils"))]
+pub mod oracle {
+    use crate::oracle::{BondConfig, ResolutionArchive, TieBreakMode};
+    use soroban_sdk::{contractevent, Address, BytesN, Env, Symbol, Vec};
+
+    /// Topics `("init", admin)`, data `(required_consensus)`
+    #[contractevent(topics = ["init"], data_format = "vec")]
+    pub struct OracleInitializedEvent {
+        #[topic]
+        pub admin: Address,
+        pub required_consensus: u32,
+    }
+
+    /// Topics `("oracle_reg", oracle)`, data `(oracle_name, timestamp)`
+    #[contractevent(topics = ["oracle_reg"], data_format = "vec")]
+    pub struct OracleRegisteredEvent {
+        #[topic]
+        pub oracle: Address,
+        pub oracle_name: Symbol,
+        pub timestamp: u64,
+    }
+
+    /// Topics `("oracle_dereg", oracle)`, data `(timestamp)`
+    #[contractevent(topics = ["oracle_dereg"], data_format = "vec")]
+    pub struct OracleDeregisteredEvent {
+        #[topic]
+        pub oracle: Address,
+        pub timestamp: u64,
+    }
+
+    /// Topics `("oracle_cats", oracle)`, data `(categories)`
+    #[contractevent(topics = ["oracle_cats"], data_format = "vec")]
+    pub struct OracleCategoriesUpdatedEvent {
+        #[topic]
+        pub oracle: Address,
+        pub categories: Vec<Symbol>,
+    }
+
+    /// Topics `("market_reg", market_id)`, data `(resolution_time)`
+    #[contractevent(topics = ["market_reg"], data_format = "vec")]
+    pub struct MarketRegisteredEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub resolution_time: u64,
+    }
+
+    /// Topics `("market_bind", market_id)`, data `(market_contract)`
+    #[contractevent(topics = ["market_bind"], data_format = "vec")]
+    pub struct MarketContractBoundEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub market_contract: Address,
+    }
+
+    /// Topics `("res_time", market_id)`, data `(old_time, new_time)`
+    #[contractevent(topics = ["res_time"], data_format = "vec")]
+    pub struct ResolutionTimeUpdatedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub old_time: u64,
+        pub new_time: u64,
+    }
+
+    /// Topics `("registrar", registrar)`, data `(enabled)`
+    #[contractevent(topics = ["registrar"], data_format = "vec")]
+    pub struct RegistrarUpdatedEvent {
+        #[topic]
+        pub registrar: Address,
+        pub enabled: bool,
+    }
+
+    /// Topics `("tie_break")`, data `(mode)`
+    #[contractevent(topics = ["tie_break"], data_format = "vec")]
+    pub struct TieBreakModeUpdatedEvent {
+        pub mode: TieBreakMode,
+    }
+
+    /// Topics `("strict_hash")`, data `(enabled)`
+    #[contractevent(topics = ["strict_hash"], data_format = "vec")]
+    pub struct StrictDataHashUpdatedEvent {
+        pub enabled: bool,
+    }
+
+    /// Topics `("submitter", oracle)`, data `(submitter)`
+    #[contractevent(topics = ["submitter"], data_format = "vec")]
+    pub struct SubmitterUpdatedEvent {
+        #[topic]
+        pub oracle: Address,
+        pub submitter: Option<Address>,
+    }
+
+    /// Topics `("attest", market_id, oracle)`, data `(attestation_result)`
+    #[contractevent(topics = ["attest"], data_format = "vec")]
+    pub struct AttestationSubmittedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        #[topic]
+        pub oracle: Address,
+        pub attestation_result: u32,
+    }
+
+    /// Topics `("value", market_id, oracle)`, data `(value)`
+    #[contractevent(topics = ["value"], data_format = "vec")]
+    pub struct ValueSubmittedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        #[topic]
+        pub oracle: Address,
+        pub value: i128,
+    }
+
+    /// Topics `("staking")`, data `(enabled, min_participation_bps)`
+    #[contractevent(topics = ["staking"], data_format = "vec")]
+    pub struct StakingConfigUpdatedEvent {
+        pub enabled: bool,
+        pub min_participation_bps: u32,
+    }
+
+    /// Topics `("bond_cfg")`, data `(token, treasury, amount)`
+    #[contractevent(topics = ["bond_cfg"], data_format = "vec")]
+    pub struct BondConfigUpdatedEvent {
+        pub token: Address,
+        pub treasury: Address,
+        pub amount: i128,
+    }
+
+    /// Topics `("bond_refund", market_id)`, data `(oracle, amount)`
+    #[contractevent(topics = ["bond_refund"], data_format = "vec")]
+    pub struct BondRefundedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub oracle: Address,
+        pub amount: i128,
+    }
+
+    /// Topics `("bond_forfeit", market_id)`, data `(amount)`
+    #[contractevent(topics = ["bond_forfeit"], data_format = "vec")]
+    pub struct BondsForfeitedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub amount: i128,
+    }
+
+    /// Topics `("finalize", market_id)`, data `(final_outcome, timestamp)`
+    #[contractevent(topics = ["finalize"], data_format = "vec")]
+    pub struct ResolutionFinalizedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub final_outcome: u32,
+        pub timestamp: u64,
+    }
+
+    /// Topics `("subscribe", market_id, subscriber)`, data `()`
+    #[contractevent(topics = ["subscribe"], data_format = "vec")]
+    pub struct ResolutionSubscribedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        #[topic]
+        pub subscriber: Address,
+    }
+
+    /// Topics `("deliver", market_id, subscriber)`, data `(round_id, delivered)`
+    #[contractevent(topics = ["deliver"], data_format = "vec")]
+    pub struct ResolutionDeliveredEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        #[topic]
+        pub subscriber: Address,
+        pub round_id: u32,
+        /// False if the subscriber's on_resolution failed; the round is still readable
+        pub delivered: bool,
+    }
+
+    /// Topics `("alert_hook")`, data `(hook)`
+    #[contractevent(topics = ["alert_hook"], data_format = "vec")]
+    pub struct AlertHookUpdatedEvent {
+        pub hook: Option<Address>,
+    }
+
+    /// Topics `("alert", market_id)`, data `(kind, severity, delivered)`
+    #[contractevent(topics = ["alert"], data_format = "vec")]
+    pub struct AlertRaisedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub kind: Symbol,
+        pub severity: u32,
+        /// False if the hook's on_alert failed; the failure is kept in the alert log
+        pub delivered: bool,
+    }
+
+    /// Topics `("archive", market_id)`, data `(archive)`
+    #[contractevent(topics = ["archive"], data_format = "vec")]
+    pub struct ResolutionArchivedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub archive: ResolutionArchive,
+    }
+
+    /// Topics `("challenge", market_id, oracle)`, data `(challenger, challenge_reason)`
+    #[contractevent(topics = ["challenge"], data_format = "vec")]
+    pub struct AttestationChallengedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        #[topic]
+        pub oracle: Address,
+        pub challenger: Address,
+        pub challenge_reason: Symbol,
+    }
+
+    /// Topics `("challenge_res", oracle)`, data `(challenger, challenge_valid, new_reputation, slashed_amount)`
+    #[contractevent(topics = ["challenge_res"], data_format = "vec")]
+    pub struct ChallengeResolvedEvent {
+        #[topic]
+        pub oracle: Address,
+        pub challenger: Address,
+        pub challenge_valid: bool,
+        pub new_reputation: u32,
+        pub slashed_amount: i128,
+    }
+
+    /// Topics `("invalidate", market_id, oracle)`, data `(outcome, reopened_until)`
+    #[contractevent(topics = ["invalidate"], data_format = "vec")]
+    pub struct AttestationInvalidatedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        #[topic]
+        pub oracle: Address,
+        pub outcome: u32,
+        /// finalize_resolution waits until this timestamp
+        pub reopened_until: u64,
+    }
+
+    /// Topics `("reattest_win")`, data `(window)`
+    #[contractevent(topics = ["reattest_win"], data_format = "vec")]
+    pub struct ReattestWindowUpdatedEvent {
+        pub window: u64,
+    }
+
+    /// Topics `("override", market_id)`, data `(forced_outcome, justification_hash, approvers, timestamp)`
+    #[contractevent(topics = ["override"], data_format = "vec")]
+    pub struct EmergencyOverrideEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub forced_outcome: u32,
+        pub justification_hash: BytesN<32>,
+        pub approvers: Vec<Address>,
+        pub timestamp: u64,
+    }
+
+    pub fn oracle_initialized(env: &Env, admin: Address, required_consensus: u32) {
+        OracleInitializedEvent {
+            admin,
+            required_consensus,
+        }
+        .publish(env);
+    }
+
+    pub fn oracle_registered(env: &Env, oracle: Address, oracle_name: Symbol, timestamp: u64) {
+        OracleRegisteredEvent {
+            oracle,
+            oracle_name,
+            timestamp,
+        }
+        .publish(env);
+    }
+
+    pub fn oracle_deregistered(env: &Env, oracle: Address, timestamp: u64) {
+        OracleDeregisteredEvent { oracle, timestamp }.publish(env);
+    }
+
+    pub fn oracle_categories_updated(env: &Env, oracle: Address, categories: Vec<Symbol>) {
+        OracleCategoriesUpdatedEvent { oracle, categories }.publish(env);
+    }
+
+    pub fn market_registered(env: &Env, market_id: BytesN<32>, resolution_time: u64) {
+        MarketRegisteredEvent {
+            market_id,
+            resolution_time,
+        }
+        .publish(env);
+    }
+
+    pub fn market_contract_bound(env: &Env, market_id: BytesN<32>, market_contract: Address) {
+        MarketContractBoundEvent {
+            market_id,
+            market_contract,
+        }
+        .publish(env);
+    }
+
+    pub fn resolution_time_updated(env: &Env, market_id: BytesN<32>, old_time: u64, new_time: u64) {
+        ResolutionTimeUpdatedEvent {
+            market_id,
+            old_time,
+            new_time,
+        }
+        .publish(env);
+    }
+
+    pub fn registrar_updated(env: &Env, registrar: Address, enabled: bool) {
+        RegistrarUpdatedEvent { registrar, enabled }.publish(env);
+    }
+
+    pub fn tie_break_mode_updated(env: &Env, mode: TieBreakMode) {
+        TieBreakModeUpdatedEvent { mode }.publish(env);
+    }
+
+    pub fn strict_data_hash_updated(env: &Env, enabled: bool) {
+        StrictDataHashUpdatedEvent { enabled }.publish(env);
+    }
+
+    pub fn submitter_updated(env: &Env, oracle: Address, submitter: Option<Address>) {
+        SubmitterUpdatedEvent { oracle, submitter }.publish(env);
+    }
+
+    pub fn attestation_submitted(
+        env: &Env,
+        market_id: BytesN<32>,
+        oracle: Address,
+        attestation_result: u32,
+    ) {
+        AttestationSubmittedEvent {
+            market_id,
+            oracle,
+            attestation_result,
+        }
+        .publish(env);
+    }
+
+    pub fn value_submitted(env: &Env, market_id: BytesN<32>, oracle: Address, value: i128) {
+        ValueSubmittedEvent {
+            market_id,
+            oracle,
+            value,
+        }
+        .publish(env);
+    }
+
+    pub fn staking_config_updated(env: &Env, enabled: bool, min_participation_bps: u32) {
+        StakingConfigUpdatedEvent {
+            enabled,
+            min_participation_bps,
+        }
+        .publish(env);
+    }
+
+    pub fn bond_config_updated(env: &Env, config: BondConfig) {
+        BondConfigUpdatedEvent {
+            token: config.token,
+            treasury: config.treasury,
+            amount: config.amount,
+        }
+        .publish(env);
+    }
+
+    pub fn bond_refunded(env: &Env, market_id: BytesN<32>, oracle: Address, amount: i128) {
+        BondRefundedEvent {
+            market_id,
+            oracle,
+            amount,
+        }
+        .publish(env);
+    }
+
+    pub fn bonds_forfeited(env: &Env, market_id: BytesN<32>, amount: i128) {
+        BondsForfeitedEvent { market_id, amount }.publish(env);
+    }
+
+    pub fn resolution_finalized(
+        env: &Env,
+        market_id: BytesN<32>,
+        final_outcome: u32,
+        timestamp: u64,
+    ) {
+        ResolutionFinalizedEvent {
+            market_id,
+            final_outcome,
+            timestamp,
+        }
+        .publish(env);
+    }
+
+    pub fn resolution_subscribed(env: &Env, market_id: BytesN<32>, subscriber: Address) {
+        ResolutionSubscribedEvent {
+            market_id,
+            subscriber,
+        }
+        .publish(env);
+    }
+
+    pub fn resolution_delivered(
+        env: &Env,
+        market_id: BytesN<32>,
+        subscriber: Address,
+        round_id: u32,
+        delivered: bool,
+    ) {
+        ResolutionDeliveredEvent {
+            market_id,
+            subscriber,
+            round_id,
+            delivered,
+        }
+        .publish(env);
+    }
+
+    pub fn alert_hook_updated(env: &Env, hook: Option<Address>) {
+        AlertHookUpdatedEvent { hook }.publish(env);
+    }
+
+    pub fn alert_raised(
+        env: &Env,
+        market_id: BytesN<32>,
+        kind: Symbol,
+        severity: u32,
+        delivered: bool,
+    ) {
+        AlertRaisedEvent {
+            market_id,
+            kind,
+            severity,
+            delivered,
+        }
+        .publish(env);
+    }
+
+    pub fn resolution_archived(env: &Env, market_id: BytesN<32>, archive: ResolutionArchive) {
+        ResolutionArchivedEvent { market_id, archive }.publish(env);
+    }
+
+    pub fn attestation_challenged(
+        env: &Env,
+        market_id: BytesN<32>,
+        oracle: Address,
+        challenger: Address,
+        challenge_reason: Symbol,
+    ) {
+        AttestationChallengedEvent {
+            market_id,
+            oracle,
+            challenger,
+            challenge_reason,
+        }
+        .publish(env);
+    }
+
+    pub fn challenge_resolved(
+        env: &Env,
+        oracle: Address,
+        challenger: Address,
+        challenge_valid: bool,
+        new_reputation: u32,
+        slashed_amount: i128,
+    ) {
+        ChallengeResolvedEvent {
+            oracle,
+            challenger,
+            challenge_valid,
+            new_reputation,
+            slashed_amount,
+        }
+        .publish(env);
+    }
+
+    pub fn attestation_invalidated(
+        env: &Env,
+        market_id: BytesN<32>,
+        oracle: Address,
+        outcome: u32,
+        reopened_until: u64,
+    ) {
+        AttestationInvalidatedEvent {
+            market_id,
+            oracle,
+            outcome,
+            reopened_until,
+        }
+        .publish(env);
+    }
+
+    pub fn reattest_window_updated(env: &Env, window: u64) {
+        ReattestWindowUpdatedEvent { window }.publish(env);
+    }
+
+    pub fn emergency_override(
+        env: &Env,
+        market_id: BytesN<32>,
+        forced_outcome: u32,
+        justification_hash: BytesN<32>,
+        approvers: Vec<Address>,
+        timestamp: u64,
+    ) {
+        EmergencyOverrideEvent {
+            market_id,
+            forced_outcome,
+            justification_hash,
+            approvers,
+            timestamp,
+        }
+        .publish(env);
+    }
+}
+
+#[cfg(any(feature = "market", test, feature = "testutils"))]
+pub mod market {
+    use crate::market::{MarketStatus, Pools, Position};
+    use soroban_sdk::{contractevent, Address, BytesN, Env};
+
+    /// Topics `("resolve", market_id)`, data `(final_outcome, timestamp)`
+    #[contractevent(topics = ["resolve"], data_format = "vec")]
+    pub struct MarketResolvedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub final_outcome: u32,
+        pub timestamp: u64,
+    }
+
+    /// Topics `("claim", market_id, user)`, data `(amount, fee)`
+    #[contractevent(topics = ["claim"], data_format = "vec")]
+    pub struct WinningsClaimedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        #[topic]
+        pub user: Address,
+        /// Net amount paid to the user
+        pub amount: i128,
+        pub fee: i128,
+    }
+
+    /// Topics `("prediction", market_id, user)`, data `(outcome, amount, timestamp)`
+    #[contractevent(topics = ["prediction"], data_format = "vec")]
+    pub struct PredictionEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        #[topic]
+        pub user: Address,
+        pub outcome: u32,
+        pub amount: i128,
+        pub timestamp: u64,
+    }
+
+    /// Balance-after record of a position mutation
+    ///
+    /// Topics `("position", market_id, user)`, data `(seq, op, outcome, delta, position, pools)`.
+    /// `position` and `pools` are the post-operation values, so the latest event per
+    /// user (and overall) is enough to rebuild storage without summing deltas.
+    #[contractevent(topics = ["position"], data_format = "vec")]
+    pub struct PositionChangedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        #[topic]
+        pub user: Address,
+        /// Per-user operation counter, starting at 1
+        pub seq: u64,
+        /// POSITION_OP_* code
+        pub op: u32,
+        pub outcome: u32,
+        /// Signed change to the user's stake (0 for a claim)
+        pub delta: i128,
+        pub position: Position,
+        pub pools: Pools,
+    }
+
+    /// Topics `("pos_xfer", market_id, from, to)`, data `(outcome, amount)`
+    #[contractevent(topics = ["pos_xfer"], data_format = "vec")]
+    pub struct PositionTransferredEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        #[topic]
+        pub from: Address,
+        #[topic]
+        pub to: Address,
+        pub outcome: u32,
+        pub amount: i128,
+    }
+
+    /// Topics `("operator", owner, operator)`, data `(approved)`
+    #[contractevent(topics = ["operator"], data_format = "vec")]
+    pub struct OperatorApprovalEvent {
+        #[topic]
+        pub owner: Address,
+        #[topic]
+        pub operator: Address,
+        pub approved: bool,
+    }
+
+    /// Topics `("pos_rebuild", market_id, user)`, data `(old_amount, new_amount, record_count)`
+    #[contractevent(topics = ["pos_rebuild"], data_format = "vec")]
+    pub struct PositionRebuiltEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        #[topic]
+        pub user: Address,
+        pub old_amount: i128,
+        pub new_amount: i128,
+        pub record_count: u32,
+    }
+
+    /// Topics `("state", market_id)`, data `(old_state, new_state)`
+    #[contractevent(topics = ["state"], data_format = "vec")]
+    pub struct StateChangedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub old_state: MarketStatus,
+        pub new_state: MarketStatus,
+    }
+
+    /// Topics `("dispute_flag", market_id)`, data `(disputed, state)`
+    #[contractevent(topics = ["dispute_flag"], data_format = "vec")]
+    pub struct DisputeFlagEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub disputed: bool,
+        /// Market state after the flag was applied
+        pub state: MarketStatus,
+    }
+
+    /// Topics `("swept", market_id)`, data `(amount)`
+    #[contractevent(topics = ["swept"], data_format = "vec")]
+    pub struct UnclaimedSweptEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub amount: i128,
+    }
+
+    /// Topics `("cutoff", market_id)`, data `(old_cutoff, new_cutoff)`
+    #[contractevent(topics = ["cutoff"], data_format = "vec")]
+    pub struct BettingCutoffUpdatedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub old_cutoff: u64,
+        pub new_cutoff: u64,
+    }
+
+    /// Topics `("guarantee", funder)`, data `(multiple_bps, escrow)`
+    #[contractevent(topics = ["guarantee"], data_format = "vec")]
+    pub struct GuaranteeFundedEvent {
+        #[topic]
+        pub funder: Address,
+        pub multiple_bps: u32,
+        pub escrow: i128,
+    }
+
+    /// Topics `("guarantee_rel", funder)`, data `(amount)`
+    #[contractevent(topics = ["guarantee_rel"], data_format = "vec")]
+    pub struct GuaranteeReleasedEvent {
+        #[topic]
+        pub funder: Address,
+        pub amount: i128,
+    }
+
+    /// Topics `("void", market_id)`, data `(timestamp)`
+    #[contractevent(topics = ["void"], data_format = "vec")]
+    pub struct MarketVoidedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub timestamp: u64,
+    }
+
+    /// Topics `("oracle_timeout", market_id)`, data `(oracle, oracle_timeout, caller, timestamp)`
+    #[contractevent(topics = ["oracle_timeout"], data_format = "vec")]
+    pub struct OracleTimeoutEscalatedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub oracle: Address,
+        pub oracle_timeout: u64,
+        pub caller: Address,
+        pub timestamp: u64,
+    }
+
+    /// Topics `("notify", market_id, target)`, data `(outcome, delivered)`
+    #[contractevent(topics = ["notify"], data_format = "vec")]
+    pub struct ResolutionNotifiedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        #[topic]
+        pub target: Address,
+        pub outcome: u32,
+        pub delivered: bool,
+    }
+
+    /// Topics `("res_time", market_id)`, data `(old_time, new_time)`
+    #[contractevent(topics = ["res_time"], data_format = "vec")]
+    pub struct ResolutionTimeExtendedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub old_time: u64,
+        pub new_time: u64,
+    }
+
+    /// Topics `("anti_snipe", market_id)`, data `(trigger_amount, old_cutoff, new_cutoff, total_extension)`
+    #[contractevent(topics = ["anti_snipe"], data_format = "vec")]
+    pub struct AntiSnipeExtendedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub trigger_amount: i128,
+        pub old_cutoff: u64,
+        pub new_cutoff: u64,
+        pub total_extension: u64,
+    }
+
+    /// Topics `("freeze", market_id, user)`, data `(frozen, timestamp)`
+    #[contractevent(topics = ["freeze"], data_format = "vec")]
+    pub struct AccountFreezeChangedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        #[topic]
+        pub user: Address,
+        pub frozen: bool,
+        pub timestamp: u64,
+    }
+
+    /// Topics `("metadata", market_id)`, data `(question_hash, timestamp)`
+    #[contractevent(topics = ["metadata"], data_format = "vec")]
+    pub struct MetadataUpdatedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub question_hash: BytesN<32>,
+        pub timestamp: u64,
+    }
+
+    /// Topics `("loss_refund", market_id, user)`, data `(amount, timestamp)`
+    #[contractevent(topics = ["loss_refund"], data_format = "vec")]
+    pub struct LossRefundClaimedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        #[topic]
+        pub user: Address,
+        pub amount: i128,
+        pub timestamp: u64,
+    }
+
+    pub fn market_resolved(env: &Env, market_id: BytesN<32>, final_outcome: u32, timestamp: u64) {
+        MarketResolvedEvent {
+            market_id,
+            final_outcome,
+            timestamp,
+        }
+        .publish(env);
+    }
+
+    pub fn winnings_claimed(
+        env: &Env,
+        market_id: BytesN<32>,
+        user: Address,
+        amount: i128,
+        fee: i128,
+    ) {
+        WinningsClaimedEvent {
+            market_id,
+            user,
+            amount,
+            fee,
+        }
+        .publish(env);
+    }
+
+    pub fn prediction_recorded(
+        env: &Env,
+        market_id: BytesN<32>,
+        user: Address,
+        outcome: u32,
+        amount: i128,
+        timestamp: u64,
+    ) {
+        PredictionEvent {
+            market_id,
+            user,
+            outcome,
+            amount,
+            timestamp,
+        }
+        .publish(env);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn position_changed(
+        env: &Env,
+        market_id: BytesN<32>,
+        user: Address,
+        seq: u64,
+        op: u32,
+        outcome: u32,
+        delta: i128,
+        position: Position,
+        pools: Pools,
+    ) {
+        PositionChangedEvent {
+            market_id,
+            user,
+            seq,
+            op,
+            outcome,
+            delta,
+            position,
+            pools,
+        }
+        .publish(env);
+    }
+
+    pub fn position_transferred(
+        env: &Env,
+        market_id: BytesN<32>,
+        from: Address,
+        to: Address,
+        outcome: u32,
+        amount: i128,
+    ) {
+        PositionTransferredEvent {
+            market_id,
+            from,
+            to,
+            outcome,
+            amount,
+        }
+        .publish(env);
+    }
+
+    pub fn operator_approval(env: &Env, owner: Address, operator: Address, approved: bool) {
+        OperatorApprovalEvent {
+            owner,
+            operator,
+            approved,
+        }
+        .publish(env);
+    }
+
+    pub fn position_rebuilt(
+        env: &Env,
+        market_id: BytesN<32>,
+        user: Address,
+        old_amount: i128,
+        new_amount: i128,
+        record_count: u32,
+    ) {
+        PositionRebuiltEvent {
+            market_id,
+            user,
+            old_amount,
+            new_amount,
+            record_count,
+        }
+        .publish(env);
+    }
+
+    pub fn state_changed(
+        env: &Env,
+        market_id: BytesN<32>,
+        old_state: MarketStatus,
+        new_state: MarketStatus,
+    ) {
+        StateChangedEvent {
+            market_id,
+            old_state,
+            new_state,
+        }
+        .publish(env);
+    }
+
+    pub fn dispute_flag(env: &Env, market_id: BytesN<32>, disputed: bool, state: MarketStatus) {
+        DisputeFlagEvent {
+            market_id,
+            disputed,
+            state,
+        }
+        .publish(env);
+    }
+
+    pub fn unclaimed_swept(env: &Env, market_id: BytesN<32>, amount: i128) {
+        UnclaimedSweptEvent { market_id, amount }.publish(env);
+    }
+
+    pub fn betting_cutoff_updated(
+        env: &Env,
+        market_id: BytesN<32>,
+        old_cutoff: u64,
+        new_cutoff: u64,
+    ) {
+        BettingCutoffUpdatedEvent {
+            market_id,
+            old_cutoff,
+            new_cutoff,
+        }
+        .publish(env);
+    }
+
+    pub fn guarantee_funded(env: &Env, funder: Address, multiple_bps: u32, escrow: i128) {
+        GuaranteeFundedEvent {
+            funder,
+            multiple_bps,
+            escrow,
+        }
+        .publish(env);
+    }
+
+    pub fn guarantee_released(env: &Env, funder: Address, amount: i128) {
+        GuaranteeReleasedEvent { funder, amount }.publish(env);
+    }
+
+    pub fn market_voided(env: &Env, market_id: BytesN<32>, timestamp: u64) {
+        MarketVoidedEvent {
+            market_id,
+            timestamp,
+        }
+        .publish(env);
+    }
+
+    pub fn oracle_timeout_escalated(
+        env: &Env,
+        market_id: BytesN<32>,
+        oracle: Address,
+        oracle_timeout: u64,
+        caller: Address,
+        timestamp: u64,
+    ) {
+        OracleTimeoutEscalatedEvent {
+            market_id,
+            oracle,
+            oracle_timeout,
+            caller,
+            timestamp,
+        }
+        .publish(env);
+    }
+
+    pub fn resolution_notified(
+        env: &Env,
+        market_id: BytesN<32>,
+        target: Address,
+        outcome: u32,
+        delivered: bool,
+    ) {
+        ResolutionNotifiedEvent {
+            market_id,
+            target,
+            outcome,
+            delivered,
+        }
+        .publish(env);
+    }
+
+    pub fn resolution_time_extended(
+        env: &Env,
+        market_id: BytesN<32>,
+        old_time: u64,
+        new_time: u64,
+    ) {
+        ResolutionTimeExtendedEvent {
+            market_id,
+            old_time,
+            new_time,
+        }
+        .publish(env);
+    }
+
+    pub fn anti_snipe_extended(
+        env: &Env,
+        market_id: BytesN<32>,
+        trigger_amount: i128,
+        old_cutoff: u64,
+        new_cutoff: u64,
+        total_extension: u64,
+    ) {
+        AntiSnipeExtendedEvent {
+            market_id,
+            trigger_amount,
+            old_cutoff,
+            new_cutoff,
+            total_extension,
+        }
+        .publish(env);
+    }
+
+    pub fn account_freeze_changed(
+        env: &Env,
+        market_id: BytesN<32>,
+        user: Address,
+        frozen: bool,
+        timestamp: u64,
+    ) {
+        AccountFreezeChangedEvent {
+            market_id,
+            user,
+            frozen,
+            timestamp,
+        }
+        .publish(env);
+    }
+
+    pub fn metadata_updated(
+        env: &Env,
+        market_id: BytesN<32>,
+        question_hash: BytesN<32>,
+        timestamp: u64,
+    ) {
+        MetadataUpdatedEvent {
+            market_id,
+            question_hash,
+            timestamp,
+        }
+        .publish(env);
+    }
+
+    pub fn loss_refund_claimed(
+        env: &Env,
+        market_id: BytesN<32>,
+        user: Address,
+        amount: i128,
+        timestamp: u64,
+    ) {
+        LossRefundClaimedEvent {
+            market_id,
+            user,
+            amount,
+            timestamp,
+        }
+        .publish(env);
+    }
+}
+
+#[cfg(any(feature = "amm", test, feature = "testutils"))]
+pub mod amm {
+    use soroban_sdk::{contractevent, Address, BytesN, Env};
+
+    /// Topics `("pool_init", market_id)`, data `(market, collateral, liquidity)`
+    #[contractevent(topics = ["pool_init"], data_format = "vec")]
+    pub struct PoolInitializedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub market: Address,
+        pub collateral: Address,
+        pub liquidity: u128,
+    }
+
+    /// Topics `("swap", market_id, user)`, data `(from_outcome, to_outcome, shares_in, shares_out, fee_amount)`
+    #[contractevent(topics = ["swap"], data_format = "vec")]
+    pub struct OutcomeSwappedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        #[topic]
+        pub user: Address,
+        pub from_outcome: u32,
+        pub to_outcome: u32,
+        pub shares_in: u128,
+        pub shares_out: u128,
+        /// Fees of both legs together
+        pub fee_amount: u128,
+    }
+
+    /// Topics `("pool_pause", market_id)`, data `(paused)`
+    #[contractevent(topics = ["pool_pause"], data_format = "vec")]
+    pub struct PoolPausedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub paused: bool,
+    }
+
+    /// Topics `("resting_floor", market_id)`, data `(floor_bps)`
+    #[contractevent(topics = ["resting_floor"], data_format = "vec")]
+    pub struct RestingFloorSetEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub floor_bps: u32,
+    }
+
+    /// Topics `("pool_fee", market_id)`, data `(fee_bps)`
+    #[contractevent(topics = ["pool_fee"], data_format = "vec")]
+    pub struct PoolFeeSetEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub fee_bps: u32,
+    }
+
+    /// Topics `("fee_flush", market_id)`, data `(treasury, amount)`
+    #[contractevent(topics = ["fee_flush"], data_format = "vec")]
+    pub struct TreasuryFeesFlushedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub treasury: Address,
+        pub amount: u128,
+    }
+
+    /// Topics `("settle", market_id)`, data `(winning_outcome, yes_rate, no_rate)`
+    #[contractevent(topics = ["settle"], data_format = "vec")]
+    pub struct PoolSettledEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub winning_outcome: Option<u32>,
+        pub yes_rate: u128,
+        pub no_rate: u128,
+    }
+
+    /// Topics `("redeem", market_id, user)`, data `(yes_shares, no_shares, payout)`
+    #[contractevent(topics = ["redeem"], data_format = "vec")]
+    pub struct SharesRedeemedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        #[topic]
+        pub user: Address,
+        pub yes_shares: u128,
+        pub no_shares: u128,
+        pub payout: u128,
+    }
+
+    /// Topics `("lp_fees", market_id, lp_provider)`, data `(amount)`
+    #[contractevent(topics = ["lp_fees"], data_format = "vec")]
+    pub struct LpFeesClaimedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        #[topic]
+        pub lp_provider: Address,
+        pub amount: u128,
+    }
+
+    pub fn pool_initialized(
+        env: &Env,
+        market_id: BytesN<32>,
+        market: Address,
+        collateral: Address,
+        liquidity: u128,
+    ) {
+        PoolInitializedEvent {
+            market_id,
+            market,
+            collateral,
+            liquidity,
+        }
+        .publish(env);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn outcome_swapped(
+        env: &Env,
+        market_id: BytesN<32>,
+        user: Address,
+        from_outcome: u32,
+        to_outcome: u32,
+        shares_in: u128,
+        shares_out: u128,
+        fee_amount: u128,
+    ) {
+        OutcomeSwappedEvent {
+            market_id,
+            user,
+            from_outcome,
+            to_outcome,
+            shares_in,
+            shares_out,
+            fee_amount,
+        }
+        .publish(env);
+    }
+
+    pub fn pool_paused(env: &Env, market_id: BytesN<32>, paused: bool) {
+        PoolPausedEvent { market_id, paused }.publish(env);
+    }
+
+    pub fn resting_floor_set(env: &Env, market_id: BytesN<32>, floor_bps: u32) {
+        RestingFloorSetEvent {
+            market_id,
+            floor_bps,
+        }
+        .publish(env);
+    }
+
+    pub fn pool_fee_set(env: &Env, market_id: BytesN<32>, fee_bps: u32) {
+        PoolFeeSetEvent { market_id, fee_bps }.publish(env);
+    }
+
+    pub fn treasury_fees_flushed(
+        env: &Env,
+        market_id: BytesN<32>,
+        treasury: Address,
+        amount: u128,
+    ) {
+        TreasuryFeesFlushedEvent {
+            market_id,
+            treasury,
+            amount,
+        }
+        .publish(env);
+    }
+
+    pub fn pool_settled(
+        env: &Env,
+        market_id: BytesN<32>,
+        winning_outcome: Option<u32>,
+        yes_rate: u128,
+        no_rate: u128,
+    ) {
+        PoolSettledEvent {
+            market_id,
+            winning_outcome,
+            yes_rate,
+            no_rate,
+        }
+        .publish(env);
+    }
+
+    pub fn shares_redeemed(
+        env: &Env,
+        market_id: BytesN<32>,
+        user: Address,
+        yes_shares: u128,
+        no_shares: u128,
+        payout: u128,
+    ) {
+        SharesRedeemedEvent {
+            market_id,
+            user,
+            yes_shares,
+            no_shares,
+            payout,
+        }
+        .publish(env);
+    }
+
+    pub fn lp_fees_claimed(env: &Env, market_id: BytesN<32>, lp_provider: Address, amount: u128) {
+        LpFeesClaimedEvent {
+            market_id,
+            lp_provider,
+            amount,
+        }
+        .publish(env);
+    }
+}
+
+#[cfg(any(feature = "treasury", test, feature = "testutils"))]
+pub mod treasury {
+    use soroban_sdk::{contractevent, Address, BytesN, Env, Symbol, Vec};
+
+    /// Topics `("fee_deposit", market_id)`, data `(source, token, amount)`
+    #[contractevent(topics = ["fee_deposit"], data_format = "vec")]
+    pub struct FeeDepositedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub source: Address,
+        pub token: Address,
+        pub amount: i128,
+    }
+
+    /// Topics `("token", token)`, data `(supported)`
+    #[contractevent(topics = ["token"], data_format = "vec")]
+    pub struct SupportedTokenUpdatedEvent {
+        #[topic]
+        pub token: Address,
+        pub supported: bool,
+    }
+
+    /// Topics `("creator_split")`, data `(split_bps)`
+    #[contractevent(topics = ["creator_split"], data_format = "vec")]
+    pub struct CreatorSplitUpdatedEvent {
+        pub split_bps: u32,
+    }
+
+    /// Topics `("wd_limit", token)`, data `(limit, effective_at)`
+    #[contractevent(topics = ["wd_limit"], data_format = "vec")]
+    pub struct WithdrawalLimitUpdatedEvent {
+        #[topic]
+        pub token: Address,
+        pub limit: i128,
+        pub effective_at: u64,
+    }
+
+    /// Topics `("creator_reg", market_id)`, data `(creator)`
+    #[contractevent(topics = ["creator_reg"], data_format = "vec")]
+    pub struct MarketCreatorRegisteredEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub creator: Address,
+    }
+
+    /// Topics `("creator_claim", creator)`, data `(amount)`
+    #[contractevent(topics = ["creator_claim"], data_format = "vec")]
+    pub struct CreatorRevenueClaimedEvent {
+        #[topic]
+        pub creator: Address,
+        pub amount: i128,
+    }
+
+    /// Topics `("velocity", token)`, data `(threshold)`
+    #[contractevent(topics = ["velocity"], data_format = "vec")]
+    pub struct VelocityThresholdUpdatedEvent {
+        #[topic]
+        pub token: Address,
+        pub threshold: i128,
+    }
+
+    /// High-severity alert: outflows of `token` tripped the circuit breaker
+    ///
+    /// Topics `("breaker", token)`, data `(outflow, threshold, timestamp)`
+    #[contractevent(topics = ["breaker"], data_format = "vec")]
+    pub struct CircuitBreakerTrippedEvent {
+        #[topic]
+        pub token: Address,
+        pub outflow: i128,
+        pub threshold: i128,
+        pub timestamp: u64,
+    }
+
+    /// Topics `("unfreeze", token)`, data `(approvers)`
+    #[contractevent(topics = ["unfreeze"], data_format = "vec")]
+    pub struct TreasuryUnfrozenEvent {
+        #[topic]
+        pub token: Address,
+        pub approvers: Vec<Address>,
+    }
+
+    /// Topics `("archive")`, data `(from_id, to_id)`
+    #[contractevent(topics = ["archive"], data_format = "vec")]
+    pub struct HistoryArchivedEvent {
+        pub from_id: u64,
+        pub to_id: u64,
+    }
+
+    /// Topics `("ins_release", market_id)`, data `(release_id, recipient, amount)`
+    #[contractevent(topics = ["ins_release"], data_format = "vec")]
+    pub struct InsuranceReleasedEvent {
+        #[topic]
+        pub market_id: BytesN<32>,
+        pub release_id: u32,
+        pub recipient: Address,
+        pub amount: i128,
+    }
+
+    /// Topics `("ins_rebalance", keeper)`, data `(moved, target, insurance_balance)`
+    #[contractevent(topics = ["ins_rebalance"], data_format = "vec")]
+    pub struct InsuranceRebalancedEvent {
+        #[topic]
+        pub keeper: Address,
+        /// Positive when funds moved into insurance, negative when released back
+        pub moved: i128,
+        pub target: i128,
+        pub insurance_balance: i128,
+    }
+
+    /// Topics `("wd_propose", proposer)`, data `(proposal_id, token, recipient, amount, expires_at)`
+    #[contractevent(topics = ["wd_propose"], data_format = "vec")]
+    pub struct WithdrawalProposedEvent {
+        #[topic]
+        pub proposer: Address,
+        pub proposal_id: u32,
+        pub token: Address,
+        pub recipient: Address,
+        pub amount: i128,
+        pub expires_at: u64,
+    }
+
+    /// Topics `("wd_approve", signer)`, data `(proposal_id, approvals)`
+    #[contractevent(topics = ["wd_approve"], data_format = "vec")]
+    pub struct WithdrawalApprovedEvent {
+        #[topic]
+        pub signer: Address,
+        pub proposal_id: u32,
+        pub approvals: u32,
+    }
+
+    /// Topics `("wd_execute", recipient)`, data `(proposal_id, amount, timestamp)`
+    #[contractevent(topics = ["wd_execute"], data_format = "vec")]
+    pub struct WithdrawalExecutedEvent {
+        #[topic]
+        pub recipient: Address,
+        pub proposal_id: u32,
+        pub amount: i128,
+        pub timestamp: u64,
+    }
+
+    /// Topics `("payee", payee)`, data `(enabled)`
+    #[contractevent(topics = ["payee"], data_format = "vec")]
+    pub struct PayeeUpdatedEvent {
+        #[topic]
+        pub payee: Address,
+        pub enabled: bool,
+    }
+
+    /// Topics `("creditor", creditor)`, data `(enabled)`
+    #[contractevent(topics = ["creditor"], data_format = "vec")]
+    pub struct CreditorUpdatedEvent {
+        #[topic]
+        pub creditor: Address,
+        pub enabled: bool,
+    }
+
+    /// Topics `("credit", creditor, recipient)`, data `(token, amount)`
+    #[contractevent(topics = ["credit"], data_format = "vec")]
+    pub struct CreditedEvent {
+        #[topic]
+        pub creditor: Address,
+        #[topic]
+        pub recipient: Address,
+        pub token: Address,
+        pub amount: i128,
+    }
+
+    /// Topics `("claim", recipient)`, data `(token, amount)`
+    #[contractevent(topics = ["claim"], data_format = "vec")]
+    pub struct ClaimedEvent {
+        #[topic]
+        pub recipient: Address,
+        pub token: Address,
+        pub amount: i128,
+    }
+
+    /// Topics `("vest_create", recipient)`, data `(token, total_amount, start_ts, duration, cliff)`
+    #[contractevent(topics = ["vest_create"], data_format = "vec")]
+    pub struct VestingCreatedEvent {
+        #[topic]
+        pub recipient: Address,
+        pub token: Address,
+        pub total_amount: i128,
+        pub start_ts: u64,
+        pub duration: u64,
+        pub cliff: u64,
+    }
+
+    /// Topics `("vest_claim", recipient)`, data `(token, amount)`
+    #[contractevent(topics = ["vest_claim"], data_format = "vec")]
+    pub struct VestingClaimedEvent {
+        #[topic]
+        pub recipient: Address,
+        pub token: Address,
+        pub amount: i128,
+    }
+
+    /// Topics `("vest_cancel", recipient)`, data `(token, released)`
+    #[contractevent(topics = ["vest_cancel"], data_format = "vec")]
+    pub struct VestingCancelledEvent {
+        #[topic]
+        pub recipient: Address,
+        pub token: Address,
+        /// Unvested remainder returned to the free balance
+        pub released: i128,
+    }
+
+    /// Topics `("depositor", depositor)`, data `(enabled)`
+    #[contractevent(topics = ["depositor"], data_format = "vec")]
+    pub struct DepositorUpdatedEvent {
+        #[topic]
+        pub depositor: Address,
+        pub enabled: bool,
+    }
+
+    /// Topics `("return_propose", claimed_sender)`, data `(proposal_id, token, amount, executable_at)`
+    #[contractevent(topics = ["return_propose"], data_format = "vec")]
+    pub struct ReturnProposedEvent {
+        #[topic]
+        pub claimed_sender: Address,
+        pub proposal_id: u32,
+        pub token: Address,
+        pub amount: i128,
+        pub executable_at: u64,
+    }
+
+    /// Topics `("return_block", signer)`, data `(proposal_id, timestamp)`
+    #[contractevent(topics = ["return_block"], data_format = "vec")]
+    pub struct ReturnBlockedEvent {
+        #[topic]
+        pub signer: Address,
+        pub proposal_id: u32,
+        pub timestamp: u64,
+    }
+
+    /// Topics `("keeper_bounty", keeper)`, data `(job_id, proof_seq, amount)`
+    #[contractevent(topics = ["keeper_bounty"], data_format = "vec")]
+    pub struct KeeperBountyPaidEvent {
+        #[topic]
+        pub keeper: Address,
+        pub job_id: Symbol,
+        pub proof_seq: u64,
+        pub amount: i128,
+    }
+
+    /// Topics `("op_fee", source)`, data `(operator_id, protocol_share, operator_share)`
+    #[contractevent(topics = ["op_fee"], data_format = "vec")]
+    pub struct OperatorFeeCollectedEvent {
+        #[topic]
+        pub source: Address,
+        pub operator_id: Symbol,
+        pub protocol_share: i128,
+        pub operator_share: i128,
+    }
+
+    /// Topics `("op_withdraw", recipient)`, data `(operator_id, amount)`
+    #[contractevent(topics = ["op_withdraw"], data_format = "vec")]
+    pub struct OperatorFeesWithdrawnEvent {
+        #[topic]
+        pub recipient: Address,
+        pub operator_id: Symbol,
+        pub amount: i128,
+    }
+
+    /// Topics `("auditor", auditor)`, data `(enabled)`
+    #[contractevent(topics = ["auditor"], data_format = "vec")]
+    pub struct AuditorUpdatedEvent {
+        #[topic]
+        pub auditor: Address,
+        pub enabled: bool,
+    }
+
+    /// Topics `("return_exec", recipient)`, data `(proposal_id, amount, timestamp)`
+    #[contractevent(topics = ["return_exec"], data_format = "vec")]
+    pub struct ReturnExecutedEvent {
+        #[topic]
+        pub recipient: Address,
+        pub proposal_id: u32,
+        pub amount: i128,
+        pub timestamp: u64,
+    }
+
+    pub fn fee_deposited(
+        env: &Env,
+        market_id: BytesN<32>,
+        source: Address,
+        token: Address,
+        amount: i128,
+    ) {
+        FeeDepositedEvent {
+            market_id,
+            source,
+            token,
+            amount,
+        }
+        .publish(env);
+    }
+
+    pub fn supported_token_updated(env: &Env, token: Address, supported: bool) {
+        SupportedTokenUpdatedEvent { token, supported }.publish(env);
+    }
+
+    pub fn creator_split_updated(env: &Env, split_bps: u32) {
+        CreatorSplitUpdatedEvent { split_bps }.publish(env);
+    }
+
+    pub fn withdrawal_limit_updated(env: &Env, token: Address, limit: i128, effective_at: u64) {
+        WithdrawalLimitUpdatedEvent {
+            token,
+            limit,
+            effective_at,
+        }
+        .publish(env);
+    }
+
+    pub fn market_creator_registered(env: &Env, market_id: BytesN<32>, creator: Address) {
+        MarketCreatorRegisteredEvent { market_id, creator }.publish(env);
+    }
+
+    pub fn creator_revenue_claimed(env: &Env, creator: Address, amount: i128) {
+        CreatorRevenueClaimedEvent { creator, amount }.publish(env);
+    }
+
+    pub fn velocity_threshold_updated(env: &Env, token: Address, threshold: i128) {
+        VelocityThresholdUpdatedEvent { token, threshold }.publish(env);
+    }
+
+    pub fn circuit_breaker_tripped(
+        env: &Env,
+        token: Address,
+        outflow: i128,
+        threshold: i128,
+        timestamp: u64,
+    ) {
+        CircuitBreakerTrippedEvent {
+            token,
+            outflow,
+            threshold,
+            timestamp,
+        }
+        .publish(env);
+    }
+
+    pub fn treasury_unfrozen(env: &Env, token: Address, approvers: Vec<Address>) {
+        TreasuryUnfrozenEvent { token, approvers }.publish(env);
+    }
+
+    pub fn history_archived(env: &Env, from_id: u64, to_id: u64) {
+        HistoryArchivedEvent { from_id, to_id }.publish(env);
+    }
+
+    pub fn insurance_released(
+        env: &Env,
+        market_id: BytesN<32>,
+        release_id: u32,
+        recipient: Address,
+        amount: i128,
+    ) {
+        InsuranceReleasedEvent {
+            market_id,
+            release_id,
+            recipient,
+            amount,
+        }
+        .publish(env);
+    }
+
+    pub fn insurance_rebalanced(
+        env: &Env,
+        keeper: Address,
+        moved: i128,
+        target: i128,
+        insurance_balance: i128,
+    ) {
+        InsuranceRebalancedEvent {
+            keeper,
+            moved,
+            target,
+            insurance_balance,
+        }
+        .publish(env);
+    }
+
+    pub fn withdrawal_proposed(
+        env: &Env,
+        proposer: Address,
+        proposal_id: u32,
+        token: Address,
+        recipient: Address,
+        amount: i128,
+        expires_at: u64,
+    ) {
+        WithdrawalProposedEvent {
+            proposer,
+            proposal_id,
+            token,
+            recipient,
+            amount,
+            expires_at,
+        }
+        .publish(env);
+    }
+
+    pub fn withdrawal_approved(env: &Env, signer: Address, proposal_id: u32, approvals: u32) {
+        WithdrawalApprovedEvent {
+            signer,
+            proposal_id,
+            approvals,
+        }
+        .publish(env);
+    }
+
+    pub fn withdrawal_executed(
+        env: &Env,
+        recipient: Address,
+        proposal_id: u32,
+        amount: i128,
+        timestamp: u64,
+    ) {
+        WithdrawalExecutedEvent {
+            recipient,
+            proposal_id,
+            amount,
+            timestamp,
+        }
+        .publish(env);
+    }
+
+    pub fn payee_updated(env: &Env, payee: Address, enabled: bool) {
+        PayeeUpdatedEvent { payee, enabled }.publish(env);
+    }
+
+    pub fn creditor_updated(env: &Env, creditor: Address, enabled: bool) {
+        CreditorUpdatedEvent { creditor, enabled }.publish(env);
+    }
+
+    pub fn credited(
+        env: &Env,
+        creditor: Address,
+        recipient: Address,
+        token: Address,
+        amount: i128,
+    ) {
+        CreditedEvent {
+            creditor,
+            recipient,
+            token,
+            amount,
+        }
+        .publish(env);
+    }
+
+    pub fn claimed(env: &Env, recipient: Address, token: Address, amount: i128) {
+        ClaimedEvent {
+            recipient,
+            token,
+            amount,
+        }
+        .publish(env);
+    }
+
+    pub fn vesting_created(
+        env: &Env,
+        recipient: Address,
+        token: Address,
+        total_amount: i128,
+        start_ts: u64,
+        duration: u64,
+        cliff: u64,
+    ) {
+        VestingCreatedEvent {
+            recipient,
+            token,
+            total_amount,
+            start_ts,
+            duration,
+            cliff,
+        }
+        .publish(env);
+    }
+
+    pub fn vesting_claimed(env: &Env, recipient: Address, token: Address, amount: i128) {
+        VestingClaimedEvent {
+            recipient,
+            token,
+            amount,
+        }
+        .publish(env);
+    }
+
+    pub fn vesting_cancelled(env: &Env, recipient: Address, token: Address, released: i128) {
+        VestingCancelledEvent {
+            recipient,
+            token,
+            released,
+        }
+        .publish(env);
+    }
+
+    pub fn depositor_updated(env: &Env, depositor: Address, enabled: bool) {
+        DepositorUpdatedEvent { depositor, enabled }.publish(env);
+    }
+
+    pub fn return_proposed(
+        env: &Env,
+        claimed_sender: Address,
+        proposal_id: u32,
+        token: Address,
+        amount: i128,
+        executable_at: u64,
+    ) {
+        ReturnProposedEvent {
+            claimed_sender,
+            proposal_id,
+            token,
+            amount,
+            executable_at,
+        }
+        .publish(env);
+    }
+
+    pub fn return_blocked(env: &Env, signer: Address, proposal_id: u32, timestamp: u64) {
+        ReturnBlockedEvent {
+            signer,
+            proposal_id,
+            timestamp,
+        }
+        .publish(env);
+    }
+
+    pub fn keeper_bounty_paid(
+        env: &Env,
+        keeper: Address,
+        job_id: Symbol,
+        proof_seq: u64,
+        amount: i128,
+    ) {
+        KeeperBountyPaidEvent {
+            keeper,
+            job_id,
+            proof_seq,
+            amount,
+        }
+        .publish(env);
+    }
+
+    pub fn operator_fee_collected(
+        env: &Env,
+        source: Address,
+        operator_id: Symbol,
+        protocol_share: i128,
+        operator_share: i128,
+    ) {
+        OperatorFeeCollectedEvent {
+            source,
+            operator_id,
+            protocol_share,
+            operator_share,
+        }
+        .publish(env);
+    }
+
+    pub fn operator_fees_withdrawn(
+        env: &Env,
+        recipient: Address,
+        operator_id: Symbol,
+        amount: i128,
+    ) {
+        OperatorFeesWithdrawnEvent {
+            recipient,
+            operator_id,
+            amount,
+        }
+        .publish(env);
+    }
+
+    pub fn auditor_updated(env: &Env, auditor: Address, enabled: bool) {
+        AuditorUpdatedEvent { auditor, enabled }.publish(env);
+    }
+
+    pub fn return_executed(
+        env: &Env,
+        recipient: Address,
+        proposal_id: u32,
+        amount: i128,
+        timestamp: u64,
+    ) {
+        ReturnExecutedEvent {
+            recipient,
+            proposal_id,
+            amount,
+            timestamp,
+        }
+        .publish(env);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::oracle::*;
+    use super::{amm, market, treasury};
+    use super::{AMM_EVENTS, MARKET_EVENTS, ORACLE_EVENTS, TREASURY_EVENTS};
+    use crate::amm::AMM;
+    use crate::market::{MarketStatus, Pools, Position, PredictionMarket};
+    use crate::oracle::{BondConfig, OracleManager, ResolutionArchive, TieBreakMode};
+    use crate::treasury::Treasury;
+    use soroban_sdk::testutils::{Address as _, Events};
+    use soroban_sdk::{Address, BytesN, Env, Symbol, TryFromVal, Vec};
+
+    /// Assert the events published so far carry `table`'s names, in order
+    fn assert_names_match(env: &Env, table: &[&str]) {
+        let events = env.events().all();
+        assert_eq!(events.len() as usize, table.len());
+        for ((_, topics, _), expected) in events.iter().zip(table) {
+            let name = Symbol::try_from_val(env, &topics.get(0).unwrap()).unwrap();
+            assert_eq!(name, Symbol::new(env, expected));
+        }
+    }
+
+    #[test]
+    fn test_oracle_event_names_match_table() {
+        let env = Env::default();
+        let contract = env.register(OracleManager, ());
+        let market_id = BytesN::from_array(&env, &[1u8; 32]);
+        let address = Address::generate(&env);
+        let name = Symbol::new(&env, "name");
+
+        // One of each, in declaration order
+        env.as_contract(&contract, || {
+            oracle_initialized(&env, address.clone(), 2);
+            oracle_registered(&env, address.clone(), name.clone(), 0);
+            oracle_deregistered(&env, address.clone(), 0);
+            oracle_categories_updated(&env, address.clone(), Vec::new(&env));
+            market_registered(&env, market_id.clone(), 0);
+            market_contract_bound(&env, market_id.clone(), address.clone());
+            resolution_time_updated(&env, market_id.clone(), 0, 1);
+            registrar_updated(&env, address.clone(), true);
+            tie_break_mode_updated(&env, TieBreakMode::Random);
+            strict_data_hash_updated(&env, true);
+            submitter_updated(&env, address.clone(), None);
+            attestation_submitted(&env, market_id.clone(), address.clone(), 1);
+            value_submitted(&env, market_id.clone(), address.clone(), 7);
+            staking_config_updated(&env, true, 100);
+            bond_config_updated(
+                &env,
+                BondConfig {
+                    token: address.clone(),
+                    treasury: address.clone(),
+                    amount: 10,
+                },
+            );
+            bond_refunded(&env, market_id.clone(), address.clone(), 10);
+            bonds_forfeited(&env, market_id.clone(), 10);
+            resolution_finalized(&env, market_id.clone(), 1, 0);
+            resolution_subscribed(&env, market_id.clone(), address.clone());
+            resolution_delivered(&env, market_id.clone(), address.clone(), 1, true);
+            alert_hook_updated(&env, None);
+            alert_raised(&env, market_id.clone(), name.clone(), 1, true);
+            resolution_archived(
+                &env,
+                market_id.clone(),
+                ResolutionArchive {
+                    market_id: market_id.clone(),
+                    final_outcome: 1,
+                    tally: (1, 0),
+                    attestor_digest: market_id.clone(),
+                    finalized_at: 0,
+                    overridden: false,
+                    metadata: Vec::new(&env),
+                },
+            );
+            attestation_challenged(
+                &env,
+                market_id.clone(),
+                address.clone(),
+                address.clone(),
+                name.clone(),
+            );
+            challenge_resolved(&env, address.clone(), address.clone(), true, 50, 10);
+            attestation_invalidated(&env, market_id.clone(), address.clone(), 1, 0);
+            reattest_window_updated(&env, 3600);
+            emergency_override(
+                &env,
+                market_id.clone(),
+                0,
+                market_id.clone(),
+                Vec::new(&env),
+                0,
+            );
+        });
+
+        assert_names_match(&env, &ORACLE_EVENTS);
+    }
+
+    #[test]
+    fn test_market_event_names_match_table() {
+        let env = Env::default();
+        let contract = env.register(PredictionMarket, ());
+        let market_id = BytesN::from_array(&env, &[1u8; 32]);
+        let address = Address::generate(&env);
+        let position = Position {
+            yes_amount: 10,
+            no_amount: 0,
+            claimed: false,
+        };
+        let pools = Pools {
+            yes_total: 10,
+            no_total: 0,
+            participant_count: 1,
+        };
+
+        // One of each, in declaration order
+        env.as_contract(&contract, || {
+            market::market_resolved(&env, market_id.clone(), 1, 0);
+            market::winnings_claimed(&env, market_id.clone(), address.clone(), 10, 10);
+            market::prediction_recorded(&env, market_id.clone(), address.clone(), 1, 10, 0);
+            market::position_changed(
+                &env,
+                market_id.clone(),
+                address.clone(),
+                0,
+                1,
+                1,
+                10,
+                position.clone(),
+                pools.clone(),
+            );
+            market::position_transferred(
+                &env,
+                market_id.clone(),
+                address.clone(),
+                address.clone(),
+                1,
+                10,
+            );
+            market::operator_approval(&env, address.clone(), address.clone(), true);
+            market::position_rebuilt(&env, market_id.clone(), address.clone(), 10, 10, 1);
+            market::state_changed(
+                &env,
+                market_id.clone(),
+                MarketStatus::Open,
+                MarketStatus::Open,
+            );
+            market::dispute_flag(&env, market_id.clone(), true, MarketStatus::Open);
+            market::unclaimed_swept(&env, market_id.clone(), 10);
+            market::betting_cutoff_updated(&env, market_id.clone(), 0, 0);
+            market::guarantee_funded(&env, address.clone(), 1, 10);
+            market::guarantee_released(&env, address.clone(), 10);
+            market::market_voided(&env, market_id.clone(), 0);
+            market::oracle_timeout_escalated(
+                &env,
+                market_id.clone(),
+                address.clone(),
+                0,
+                address.clone(),
+                0,
+            );
+            market::resolution_notified(&env, market_id.clone(), address.clone(), 1, true);
+            market::resolution_time_extended(&env, market_id.clone(), 0, 0);
+            market::anti_snipe_extended(&env, market_id.clone(), 10, 0, 0, 0);
+            market::account_freeze_changed(&env, market_id.clone(), address.clone(), true, 0);
+            market::metadata_updated(&env, market_id.clone(), market_id.clone(), 0);
+            market::loss_refund_claimed(&env, market_id.clone(), address.clone(), 10, 0);
+        });
+
+        assert_names_match(&env, &MARKET_EVENTS);
+    }
+
+    #[test]
+    fn test_amm_event_names_match_table() {
+        let env = Env::default();
+        let contract = env.register(AMM, ());
+        let market_id = BytesN::from_array(&env, &[1u8; 32]);
+        let address = Address::generate(&env);
+
+        // One of each, in declaration order
+        env.as_contract(&contract, || {
+            amm::pool_initialized(
+                &env,
+                market_id.clone(),
+                address.clone(),
+                address.clone(),
+                10,
+            );
+            amm::outcome_swapped(&env, market_id.clone(), address.clone(), 1, 1, 10, 10, 10);
+            amm::pool_paused(&env, market_id.clone(), true);
+            amm::resting_floor_set(&env, market_id.clone(), 1);
+            amm::pool_fee_set(&env, market_id.clone(), 1);
+            amm::treasury_fees_flushed(&env, market_id.clone(), address.clone(), 10);
+            amm::pool_settled(&env, market_id.clone(), Some(1), 10, 10);
+            amm::shares_redeemed(&env, market_id.clone(), address.clone(), 10, 10, 10);
+            amm::lp_fees_claimed(&env, market_id.clone(), address.clone(), 10);
+        });
+
+        assert_names_match(&env, &AMM_EVENTS);
+    }
+
+    #[test]
+    fn test_treasury_event_names_match_table() {
+        let env = Env::default();
+        let contract = env.register(Treasury, ());
+        let market_id = BytesN::from_array(&env, &[1u8; 32]);
+        let address = Address::generate(&env);
+        let name = Symbol::new(&env, "name");
+
+        // One of each, in declaration order
+        env.as_contract(&contract, || {
+            treasury::fee_deposited(
+                &env,
+                market_id.clone(),
+                address.clone(),
+                address.clone(),
+                10,
+            );
+            treasury::supported_token_updated(&env, address.clone(), true);
+            treasury::creator_split_updated(&env, 1);
+            treasury::withdrawal_limit_updated(&env, address.clone(), 10, 0);
+            treasury::market_creator_registered(&env, market_id.clone(), address.clone());
+            treasury::creator_revenue_claimed(&env, address.clone(), 10);
+            treasury::velocity_threshold_updated(&env, address.clone(), 10);
+            treasury::circuit_breaker_tripped(&env, address.clone(), 10, 10, 0);
+            treasury::treasury_unfrozen(&env, address.clone(), Vec::new(&env));
+            treasury::history_archived(&env, 0, 0);
+            treasury::insurance_released(&env, market_id.clone(), 1, address.clone(), 10);
+            treasury::insurance_rebalanced(&env, address.clone(), 10, 10, 10);
+            treasury::withdrawal_proposed(
+                &env,
+                address.clone(),
+                1,
+                address.clone(),
+                address.clone(),
+                10,
+                0,
+            );
+            treasury::withdrawal_approved(&env, address.clone(), 1, 1);
+            treasury::withdrawal_executed(&env, address.clone(), 1, 10, 0);
+            treasury::payee_updated(&env, address.clone(), true);
+            treasury::creditor_updated(&env, address.clone(), true);
+            treasury::credited(&env, address.clone(), address.clone(), address.clone(), 10);
+            treasury::claimed(&env, address.clone(), address.clone(), 10);
+            treasury::vesting_created(&env, address.clone(), address.clone(), 10, 0, 0, 0);
+            treasury::vesting_claimed(&env, address.clone(), address.clone(), 10);
+            treasury::vesting_cancelled(&env, address.clone(), address.clone(), 10);
+            treasury::depositor_updated(&env, address.clone(), true);
+            treasury::return_proposed(&env, address.clone(), 1, address.clone(), 10, 0);
+            treasury::return_blocked(&env, address.clone(), 1, 0);
+            treasury::keeper_bounty_paid(&env, address.clone(), name.clone(), 0, 10);
+            treasury::operator_fee_collected(&env, address.clone(), name.clone(), 10, 10);
+            treasury::operator_fees_withdrawn(&env, address.clone(), name.clone(), 10);
+            treasury::auditor_updated(&env, address.clone(), true);
+            treasury::return_executed(&env, address.clone(), 1, 10, 0);
+        });
+
+        assert_names_match(&env, &TREASURY_EVENTS);
+    }
+
+    #[test]
+    fn test_event_names_are_unique() {
+        for table in [
+            &ORACLE_EVENTS[..],
+            &MARKET_EVENTS[..],
+            &AMM_EVENTS[..],
+            &TREASURY_EVENTS[..],
+        ] {
+            for (i, name) in table.iter().enumerate() {
+                assert!(!table[i + 1..].contains(name), "duplicate {}", name);
+            }
+        }
+    }
+}
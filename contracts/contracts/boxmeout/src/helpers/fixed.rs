@@ -0,0 +1,159 @@
+// Fixed-point exp/ln in 18-decimal (WAD) signed integers
+//
+// Precision:
+// - exp_wad: Taylor series after reducing x to |r| <= ln(2)/2. Relative error
+//   is below 1e-15 for results >= 1; smaller results are floored to the WAD
+//   grid, so their absolute error stays below 1e-15.
+// - ln_wad: atanh series after reducing x to [1, 2). Absolute error is below
+//   1e-15 across the whole domain.
+//
+// Inputs beyond the domain panic rather than saturate, so a caller never
+// silently prices against a clamped value.
+
+/// 1.0 in WAD fixed point
+pub const WAD: i128 = 1_000_000_000_000_000_000;
+
+/// ln(2) in WAD fixed point
+pub const LN2_WAD: i128 = 693_147_180_559_945_309;
+
+/// Largest argument exp_wad accepts (e^46 in WAD still fits an i128)
+pub const EXP_MAX_WAD: i128 = 46 * WAD;
+
+/// Below this argument e^x is under 1e-18 and exp_wad returns 0
+pub const EXP_MIN_WAD: i128 = -42 * WAD;
+
+/// e^x, with `x` and the result in WAD
+pub fn exp_wad(x: i128) -> i128 {
+    if x > EXP_MAX_WAD {
+        panic!("exp overflow");
+    }
+    if x < EXP_MIN_WAD {
+        return 0;
+    }
+
+    // x = k * ln2 + r with |r| <= ln2 / 2, so e^x = 2^k * e^r
+    let k = if x >= 0 {
+        (x + LN2_WAD / 2) / LN2_WAD
+    } else {
+        (x - LN2_WAD / 2) / LN2_WAD
+    };
+    let r = x - k * LN2_WAD;
+
+    let mut term = WAD;
+    let mut sum = WAD;
+    let mut i = 1;
+    while term != 0 {
+        term = term * r / WAD / i;
+        sum += term;
+        i += 1;
+    }
+
+    if k >= 0 {
+        sum << k
+    } else {
+        sum >> -k
+    }
+}
+
+/// ln(x) for `x` > 0, with `x` and the result in WAD
+pub fn ln_wad(x: i128) -> i128 {
+    if x <= 0 {
+        panic!("ln of non-positive value");
+    }
+
+    // x = 2^k * m with m in [1, 2), so ln(x) = k * ln2 + ln(m)
+    let mut m = x;
+    let mut k: i128 = 0;
+    while m >= 2 * WAD {
+        m >>= 1;
+        k += 1;
+    }
+    while m < WAD {
+        m <<= 1;
+        k -= 1;
+    }
+
+    // ln(m) = 2 * atanh(z) = 2 * (z + z^3/3 + z^5/5 + ...), z = (m-1)/(m+1) < 1/3
+    let z = (m - WAD) * WAD / (m + WAD);
+    let z2 = z * z / WAD;
+    let mut power = z;
+    let mut sum = 0;
+    let mut n = 1;
+    while power != 0 {
+        sum += power / n;
+        power = power * z2 / WAD;
+        n += 2;
+    }
+
+    k * LN2_WAD + 2 * sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// e in WAD, to 18 decimals
+    const E_WAD: i128 = 2_718_281_828_459_045_235;
+
+    fn assert_close(actual: i128, expected: i128, tolerance: i128) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{} not within {} of {}",
+            actual,
+            tolerance,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_exp_matches_known_values() {
+        assert_eq!(exp_wad(0), WAD);
+        assert_close(exp_wad(WAD), E_WAD, 100);
+        assert_close(exp_wad(LN2_WAD), 2 * WAD, 100);
+        // e^-1 = 0.367879441171442321...
+        assert_close(exp_wad(-WAD), 367_879_441_171_442_321, 100);
+        // e^20 = 485165195.409790277969...
+        assert_close(
+            exp_wad(20 * WAD),
+            485_165_195_409_790_277_969_106_000,
+            10i128.pow(11),
+        );
+    }
+
+    #[test]
+    fn test_ln_matches_known_values() {
+        assert_eq!(ln_wad(WAD), 0);
+        assert_close(ln_wad(2 * WAD), LN2_WAD, 100);
+        assert_close(ln_wad(E_WAD), WAD, 100);
+        // ln(0.5) = -ln(2)
+        assert_close(ln_wad(WAD / 2), -LN2_WAD, 100);
+        // ln(1e-9) = -20.723265836946411156...
+        assert_close(ln_wad(1_000_000_000), -20_723_265_836_946_411_156, 1000);
+    }
+
+    #[test]
+    fn test_ln_inverts_exp() {
+        // Far below 1, exp results sit on a coarse WAD grid and do not round-trip
+        for x in [-5 * WAD, -7 * WAD / 3, -1, 0, 1, WAD / 7, 5 * WAD, 40 * WAD] {
+            assert_close(ln_wad(exp_wad(x)), x, 1000);
+        }
+    }
+
+    #[test]
+    fn test_exp_domain_edges() {
+        assert_eq!(exp_wad(EXP_MIN_WAD - 1), 0);
+        assert!(exp_wad(EXP_MAX_WAD) > 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "exp overflow")]
+    fn test_exp_rejects_overflowing_argument() {
+        exp_wad(EXP_MAX_WAD + 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "ln of non-positive value")]
+    fn test_ln_rejects_zero() {
+        ln_wad(0);
+    }
+}
@@ -0,0 +1,98 @@
+// Effects-before-interactions guard for token-moving entry points
+//
+// A guarded call enters the guard first, makes all of its storage writes, and
+// only queues its token transfers (`Guard::transfer`). `Guard::commit` runs the
+// queued transfers last, so no external call can observe or interrupt a
+// half-applied update. While the guard is held, every other guarded entry
+// point of the same contract panics instead of running.
+//
+// Soroban already rolls back every write of a failed invocation and rejects a
+// contract re-entering itself; the guard keeps the ordering explicit at each
+// call site and stays correct if a transfer is ever made fallible.
+
+use soroban_sdk::{token, Address, Env, Symbol, Vec};
+
+const GUARD_KEY: &str = "reentrancy_guard";
+
+/// Held for the duration of a guarded call; dropping it clears the in-progress flag
+pub struct Guard<'a> {
+    env: &'a Env,
+    /// (token, from, to, amount), run in order at commit
+    transfers: Vec<(Address, Address, Address, i128)>,
+}
+
+/// Enter a guarded call, panicking if one is already in progress
+pub fn enter(env: &Env) -> Guard<'_> {
+    if is_entered(env) {
+        panic!("Reentrant call");
+    }
+    env.storage()
+        .instance()
+        .set(&Symbol::new(env, GUARD_KEY), &true);
+    Guard {
+        env,
+        transfers: Vec::new(env),
+    }
+}
+
+/// Whether a guarded call is in progress
+pub fn is_entered(env: &Env) -> bool {
+    env.storage().instance().has(&Symbol::new(env, GUARD_KEY))
+}
+
+impl Guard<'_> {
+    /// Queue a token transfer to run at commit, after the call's storage writes
+    pub fn transfer(&mut self, token: &Address, from: &Address, to: &Address, amount: i128) {
+        self.transfers
+            .push_back((token.clone(), from.clone(), to.clone(), amount));
+    }
+
+    /// Run the queued transfers and release the guard
+    pub fn commit(self) {
+        for (token, from, to, amount) in self.transfers.iter() {
+            token::Client::new(self.env, &token).transfer(&from, &to, &amount);
+        }
+    }
+}
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        self.env
+            .storage()
+            .instance()
+            .remove(&Symbol::new(self.env, GUARD_KEY));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::contract;
+
+    #[contract]
+    struct Host;
+
+    #[test]
+    fn test_flag_held_until_guard_drops() {
+        let env = Env::default();
+        let host = env.register(Host, ());
+        env.as_contract(&host, || {
+            assert!(!is_entered(&env));
+            let guard = enter(&env);
+            assert!(is_entered(&env));
+            guard.commit();
+            assert!(!is_entered(&env));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Reentrant call")]
+    fn test_nested_enter_rejected() {
+        let env = Env::default();
+        let host = env.register(Host, ());
+        env.as_contract(&host, || {
+            let _guard = enter(&env);
+            enter(&env);
+        });
+    }
+}
@@ -0,0 +1,176 @@
+// Deterministic ids and digests shared by the contracts
+//
+// Every digest is sha256 over a versioned, length-prefixed preimage so that
+// off-chain tooling can rebuild it byte for byte:
+//
+//   preimage = ENCODING_VERSION (1 byte)
+//              || field(domain) || field(value_1) || ... || field(value_n)
+//   field(x) = len(x) as u32 big-endian || x
+//
+// `domain` is the ASCII tag of the digest kind, so two kinds never share a
+// preimage. Integers are fixed-width big-endian; a Symbol is its XDR ScVal
+// encoding (0x0000000f || len u32 BE || name, zero-padded to 4 bytes).
+// Changing any of this changes every id: bump ENCODING_VERSION and update the
+// vectors in the tests below.
+
+use soroban_sdk::{xdr::ToXdr, Bytes, BytesN, Env, Symbol};
+
+/// Version byte leading every preimage
+pub const ENCODING_VERSION: u8 = 1;
+
+const QUESTION_DOMAIN: &str = "boxmeout:question";
+const MARKET_ID_DOMAIN: &str = "boxmeout:market_id";
+const PAIR_DOMAIN: &str = "boxmeout:pair";
+
+/// Hash of a market question: its title and description
+pub fn question_hash(env: &Env, title: &Symbol, description: &Symbol) -> BytesN<32> {
+    let mut preimage = begin(env, QUESTION_DOMAIN);
+    push_field(&mut preimage, &title.to_xdr(env));
+    push_field(&mut preimage, &description.to_xdr(env));
+    digest(env, &preimage)
+}
+
+/// Deterministic market_id for a question and its listing parameters
+pub fn market_id_from_params(
+    env: &Env,
+    question_hash: &BytesN<32>,
+    category: &Symbol,
+    resolution_time: u64,
+    outcome_count: u32,
+    nonce: u32,
+) -> BytesN<32> {
+    let mut preimage = begin(env, MARKET_ID_DOMAIN);
+    push_field(
+        &mut preimage,
+        &Bytes::from_array(env, &question_hash.to_array()),
+    );
+    push_field(&mut preimage, &category.to_xdr(env));
+    push_field(
+        &mut preimage,
+        &Bytes::from_array(env, &resolution_time.to_be_bytes()),
+    );
+    push_field(
+        &mut preimage,
+        &Bytes::from_array(env, &outcome_count.to_be_bytes()),
+    );
+    push_field(&mut preimage, &Bytes::from_array(env, &nonce.to_be_bytes()));
+    digest(env, &preimage)
+}
+
+/// Ordered hash of two digests (`hash_pair(a, b) != hash_pair(b, a)`)
+pub fn hash_pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let mut preimage = begin(env, PAIR_DOMAIN);
+    push_field(&mut preimage, &Bytes::from_array(env, &a.to_array()));
+    push_field(&mut preimage, &Bytes::from_array(env, &b.to_array()));
+    digest(env, &preimage)
+}
+
+fn begin(env: &Env, domain: &str) -> Bytes {
+    let mut preimage = Bytes::from_array(env, &[ENCODING_VERSION]);
+    push_field(&mut preimage, &Bytes::from_slice(env, domain.as_bytes()));
+    preimage
+}
+
+fn push_field(preimage: &mut Bytes, field: &Bytes) {
+    preimage.extend_from_array(&field.len().to_be_bytes());
+    preimage.append(field);
+}
+
+fn digest(env: &Env, preimage: &Bytes) -> BytesN<32> {
+    env.crypto().sha256(preimage).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Fixed inputs and their expected digests. These pin the encoding: a
+    // change here means every market_id changes with it.
+
+    fn hex32(env: &Env, hex: &str) -> BytesN<32> {
+        let digit = |c: u8| match c {
+            b'0'..=b'9' => c - b'0',
+            b'a'..=b'f' => c - b'a' + 10,
+            _ => panic!("bad hex digit"),
+        };
+        let hex = hex.as_bytes();
+        let mut out = [0u8; 32];
+        for (i, byte) in out.iter_mut().enumerate() {
+            *byte = digit(hex[2 * i]) << 4 | digit(hex[2 * i + 1]);
+        }
+        BytesN::from_array(env, &out)
+    }
+
+    #[test]
+    fn test_question_hash_vector() {
+        let env = Env::default();
+        assert_eq!(
+            question_hash(
+                &env,
+                &Symbol::new(&env, "FightNight"),
+                &Symbol::new(&env, "MainEvent")
+            ),
+            hex32(
+                &env,
+                "0529b2f2d14ce856a8b8ffff4452a469e5fafb97636d4d62e25b3897b09b72b7"
+            )
+        );
+    }
+
+    #[test]
+    fn test_market_id_vectors() {
+        let env = Env::default();
+        let boxing = Symbol::new(&env, "Boxing");
+        let fixed = BytesN::from_array(&env, &[0x11; 32]);
+
+        assert_eq!(
+            market_id_from_params(&env, &fixed, &boxing, 1_700_000_000, 2, 0),
+            hex32(
+                &env,
+                "59c51b42fc29e6141a089424eb618e8bda118c65b2bc0fea48c0424aa0c52a53"
+            )
+        );
+        assert_eq!(
+            market_id_from_params(&env, &fixed, &boxing, 1_700_000_000, 2, 1),
+            hex32(
+                &env,
+                "229c5e9f750b04ffbab0effd29b3554bafda2d7f60682758ce7a483f0d609524"
+            )
+        );
+
+        let question = question_hash(
+            &env,
+            &Symbol::new(&env, "FightNight"),
+            &Symbol::new(&env, "MainEvent"),
+        );
+        assert_eq!(
+            market_id_from_params(&env, &question, &boxing, 1_700_000_000, 2, 0),
+            hex32(
+                &env,
+                "e48e04543979483da4012cb47d4f674061789f54aca5efcc1fa05ee8e44e2326"
+            )
+        );
+    }
+
+    #[test]
+    fn test_hash_pair_vectors_are_ordered() {
+        let env = Env::default();
+        let a = BytesN::from_array(&env, &[0x01; 32]);
+        let b = BytesN::from_array(&env, &[0x02; 32]);
+
+        assert_eq!(
+            hash_pair(&env, &a, &b),
+            hex32(
+                &env,
+                "41be39514d8cb3ecb58fbdbc56fb14941f667818eb77941cfc341ff132a01f4b"
+            )
+        );
+        assert_eq!(
+            hash_pair(&env, &b, &a),
+            hex32(
+                &env,
+                "f079cb609a5844b80ad4fc570e5be56d3b03c775c851e0e38ea26cf775c962a9"
+            )
+        );
+    }
+}
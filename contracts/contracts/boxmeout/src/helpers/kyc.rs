@@ -0,0 +1,33 @@
+// KYC registry reads shared by the contracts
+//
+// A registry is any contract exposing `is_verified(user) -> bool`. Answers are
+// cached in temporary storage for the current ledger, so a user hitting several
+// gated entry points in one ledger costs a single cross-contract read while a
+// revocation still takes effect from the next ledger on.
+
+use soroban_sdk::{Address, Env, IntoVal, Symbol};
+
+const KYC_CACHE_PREFIX: &str = "kyc_cache";
+
+/// Whether `registry` reports `user` as verified (cached per ledger)
+pub fn is_verified(env: &Env, registry: &Address, user: &Address) -> bool {
+    let key = (
+        Symbol::new(env, KYC_CACHE_PREFIX),
+        registry.clone(),
+        user.clone(),
+    );
+    let sequence = env.ledger().sequence();
+    if let Some((cached_at, verified)) = env.storage().temporary().get::<_, (u32, bool)>(&key) {
+        if cached_at == sequence {
+            return verified;
+        }
+    }
+
+    let verified: bool = env.invoke_contract(
+        registry,
+        &Symbol::new(env, "is_verified"),
+        (user.clone(),).into_val(env),
+    );
+    env.storage().temporary().set(&key, &(sequence, verified));
+    verified
+}
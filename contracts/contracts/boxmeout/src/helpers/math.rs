@@ -0,0 +1,223 @@
+// Overflow-free `a * b / c` on i128 with explicit rounding
+//
+// The product is formed in 256 bits, so only a quotient that does not fit an
+// i128 overflows. Rounding is toward negative infinity (floor) or positive
+// infinity (ceil), for negative results too. Division by zero and results
+// outside i128 panic rather than wrap or saturate.
+
+/// Denominator of a basis-point rate
+pub const BPS_DENOMINATOR: i128 = 10_000;
+
+/// `a * b / c`, rounded down
+pub fn mul_div_floor(a: i128, b: i128, c: i128) -> i128 {
+    let (quotient, exact, negative) = mul_div(a, b, c);
+    if negative && !exact {
+        signed(quotient, true, 1)
+    } else {
+        signed(quotient, negative, 0)
+    }
+}
+
+/// `a * b / c`, rounded up
+pub fn mul_div_ceil(a: i128, b: i128, c: i128) -> i128 {
+    let (quotient, exact, negative) = mul_div(a, b, c);
+    if !negative && !exact {
+        signed(quotient, false, 1)
+    } else {
+        signed(quotient, negative, 0)
+    }
+}
+
+/// `basis_points` of `amount`, rounded down
+pub fn bps(amount: i128, basis_points: u32) -> i128 {
+    mul_div_floor(amount, basis_points as i128, BPS_DENOMINATOR)
+}
+
+/// |a * b| / |c| truncated, whether the division was exact, and the result's sign
+fn mul_div(a: i128, b: i128, c: i128) -> (u128, bool, bool) {
+    if c == 0 {
+        panic!("division by zero");
+    }
+    let negative = (a < 0) ^ (b < 0) ^ (c < 0) && a != 0 && b != 0;
+    let (a, b, c) = (a.unsigned_abs(), b.unsigned_abs(), c.unsigned_abs());
+
+    // Fast path: the product already fits 128 bits
+    if let Some(product) = a.checked_mul(b) {
+        return (product / c, product % c == 0, negative);
+    }
+
+    let (hi, lo) = wide_mul(a, b);
+    if hi >= c {
+        panic!("mul_div overflow");
+    }
+    let (quotient, remainder) = wide_div(hi, lo, c);
+    (quotient, remainder == 0, negative)
+}
+
+/// Full 256-bit product of two u128s as (high, low) halves
+fn wide_mul(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+    let (a_hi, a_lo) = (a >> 64, a & MASK);
+    let (b_hi, b_lo) = (b >> 64, b & MASK);
+
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_hi = a_hi * b_hi;
+
+    // Middle column: never exceeds 3 * (2^64 - 1), so it fits
+    let middle = (lo_lo >> 64) + (hi_lo & MASK) + (lo_hi & MASK);
+    let lo = (middle << 64) | (lo_lo & MASK);
+    let hi = hi_hi + (hi_lo >> 64) + (lo_hi >> 64) + (middle >> 64);
+    (hi, lo)
+}
+
+/// (hi, lo) / c by shift-subtract long division; requires hi < c
+fn wide_div(hi: u128, lo: u128, c: u128) -> (u128, u128) {
+    let mut remainder = hi;
+    let mut quotient = 0u128;
+    for i in (0..128).rev() {
+        // remainder < c before the shift, so the shifted value is below 2c and
+        // at most one subtraction is needed; `carry` is its lost top bit
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | ((lo >> i) & 1);
+        quotient <<= 1;
+        if carry == 1 || remainder >= c {
+            remainder = remainder.wrapping_sub(c);
+            quotient |= 1;
+        }
+    }
+    (quotient, remainder)
+}
+
+/// `magnitude + bump` with the given sign, panicking outside i128
+fn signed(magnitude: u128, negative: bool, bump: u128) -> i128 {
+    let magnitude = magnitude.checked_add(bump).expect("mul_div overflow");
+    if negative {
+        // i128::MIN has no positive counterpart, so negate through its magnitude
+        if magnitude > i128::MIN.unsigned_abs() {
+            panic!("mul_div overflow");
+        }
+        (magnitude as i128).wrapping_neg()
+    } else {
+        i128::try_from(magnitude).expect("mul_div overflow")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Floor division on the native product, for inputs whose product fits i128
+    fn native_floor(a: i128, b: i128, c: i128) -> i128 {
+        let product = a * b;
+        let quotient = product / c;
+        if product % c != 0 && ((product < 0) != (c < 0)) {
+            quotient - 1
+        } else {
+            quotient
+        }
+    }
+
+    /// Deterministic pseudo-random positive i128s spread over the whole range
+    fn samples() -> [i128; 64] {
+        let mut state: u128 = 0x9e37_79b9_7f4a_7c15_f39c_c060_5ced_c835;
+        let mut out = [0i128; 64];
+        for (i, slot) in out.iter_mut().enumerate() {
+            state = state
+                .wrapping_mul(0x2360_ed05_1fc6_5da4_4385_df64_9fcc_f645)
+                .wrapping_add(0x5851_f42d_4c95_7f2d_1405_7b7e_f767_814f);
+            // Vary the magnitude so small and near-max values both show up
+            *slot = ((state >> 1) >> (i * 2 % 127)).max(1) as i128;
+        }
+        out
+    }
+
+    #[test]
+    fn test_small_values_match_native_math() {
+        let values = [-1_000_007i128, -37, -1, 0, 1, 2, 3, 10_000, 999_999_937];
+        for a in values {
+            for b in values {
+                for c in values.iter().copied().filter(|c| *c != 0) {
+                    let floor = native_floor(a, b, c);
+                    let exact = (a * b) % c == 0;
+                    assert_eq!(mul_div_floor(a, b, c), floor);
+                    assert_eq!(mul_div_ceil(a, b, c), floor + if exact { 0 } else { 1 });
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_floor_and_ceil_bracket_exact_quotient() {
+        let values = samples();
+        for a in values {
+            for b in values.iter().copied().step_by(7) {
+                for c in values.iter().copied().step_by(5) {
+                    let (hi, lo) = wide_mul(a as u128, b as u128);
+                    if hi >= c as u128 {
+                        continue;
+                    }
+                    let (quotient, _) = wide_div(hi, lo, c as u128);
+                    if quotient >= i128::MAX as u128 {
+                        continue;
+                    }
+                    let floor = mul_div_floor(a, b, c);
+                    let ceil = mul_div_ceil(a, b, c);
+                    // floor * c <= a * b <= ceil * c, compared as 256-bit (hi, lo) pairs
+                    assert!(wide_mul(floor as u128, c as u128) <= (hi, lo));
+                    assert!(wide_mul(ceil as u128, c as u128) >= (hi, lo));
+                    assert!(ceil - floor <= 1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_wide_products_near_i128_max() {
+        assert_eq!(mul_div_floor(i128::MAX, i128::MAX, i128::MAX), i128::MAX);
+        assert_eq!(mul_div_ceil(i128::MAX, 2, 2), i128::MAX);
+        assert_eq!(mul_div_floor(i128::MAX, 3, 6), i128::MAX / 2);
+        assert_eq!(mul_div_ceil(i128::MAX, 3, 6), i128::MAX / 2 + 1);
+        assert_eq!(mul_div_floor(i128::MIN, 1, 1), i128::MIN);
+        assert_eq!(mul_div_floor(i128::MIN, i128::MAX, i128::MAX), i128::MIN);
+
+        // 3 * (u128::MAX / 3) = 2^128 - 1, so the quotient by 2 is MAX + 1/2
+        let third = (u128::MAX / 3) as i128;
+        assert_eq!(mul_div_floor(3, third, 2), i128::MAX);
+        assert_eq!(mul_div_floor(-3, third, 2), i128::MIN);
+        assert_eq!(mul_div_ceil(-3, third, 2), -i128::MAX);
+    }
+
+    #[test]
+    fn test_bps_floors() {
+        assert_eq!(bps(10_000, 250), 250);
+        assert_eq!(bps(999, 250), 24);
+        assert_eq!(bps(-999, 250), -25);
+        assert_eq!(bps(i128::MAX, 10_000), i128::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "mul_div overflow")]
+    fn test_ceil_overflows_past_i128_max() {
+        mul_div_ceil(3, (u128::MAX / 3) as i128, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "mul_div overflow")]
+    fn test_negating_i128_min_overflows() {
+        mul_div_floor(i128::MIN, -1, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "mul_div overflow")]
+    fn test_wide_quotient_overflows() {
+        mul_div_floor(i128::MAX, i128::MAX, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn test_rejects_zero_divisor() {
+        mul_div_floor(1, 1, 0);
+    }
+}
@@ -0,0 +1,91 @@
+// Deterministic pseudo-random choices that every validator agrees on
+//
+// The draw is sha256(ledger sequence || seed parts) reduced modulo `modulus`,
+// so identical inputs in the same ledger always give the same answer and a
+// new ledger gives a fresh draw. Reduction bias is below modulus / 2^128.
+//
+// NOT a source of secrets: anyone can compute the result ahead of time, and a
+// submitter can pick the ledger their transaction lands in. Use it only to
+// break ties fairly where no party gains from predicting the outcome.
+
+use soroban_sdk::{Bytes, Env};
+
+/// Pick a value in `0..modulus` from the current ledger and `seed_parts`
+///
+/// Panics if `modulus` is zero.
+pub fn deterministic_choice(env: &Env, seed_parts: &[Bytes], modulus: u64) -> u64 {
+    if modulus == 0 {
+        panic!("modulus must be positive");
+    }
+
+    let mut input = Bytes::new(env);
+    input.extend_from_array(&env.ledger().sequence().to_be_bytes());
+    for part in seed_parts {
+        input.append(part);
+    }
+    let digest = env.crypto().sha256(&input).to_array();
+
+    let mut high = [0u8; 16];
+    high.copy_from_slice(&digest[..16]);
+    (u128::from_be_bytes(high) % modulus as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Ledger;
+
+    fn seed(env: &Env, n: u32) -> Bytes {
+        Bytes::from_array(env, &n.to_be_bytes())
+    }
+
+    #[test]
+    fn test_identical_inputs_give_identical_choice() {
+        let env = Env::default();
+        env.ledger().set_sequence_number(42);
+        let parts = [seed(&env, 7), seed(&env, 8)];
+
+        let first = deterministic_choice(&env, &parts, 1000);
+        assert_eq!(deterministic_choice(&env, &parts, 1000), first);
+        assert!(first < 1000);
+    }
+
+    #[test]
+    fn test_choice_depends_on_ledger_and_parts() {
+        let env = Env::default();
+        let mut draws = [0u64; 16];
+        for sequence in 0..8 {
+            env.ledger().set_sequence_number(sequence);
+            draws[sequence as usize] = deterministic_choice(&env, &[seed(&env, 1)], u64::MAX);
+        }
+        env.ledger().set_sequence_number(100);
+        for n in 0..8 {
+            draws[8 + n as usize] = deterministic_choice(&env, &[seed(&env, n)], u64::MAX);
+        }
+        // 16 draws from a 64-bit range should all differ
+        for i in 0..draws.len() {
+            for j in i + 1..draws.len() {
+                assert_ne!(draws[i], draws[j]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_distribution_is_roughly_uniform() {
+        let env = Env::default();
+        let mut buckets = [0u32; 4];
+        for n in 0..2000 {
+            buckets[deterministic_choice(&env, &[seed(&env, n)], 4) as usize] += 1;
+        }
+        for count in buckets {
+            assert!((400..=600).contains(&count), "bucket count {}", count);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus must be positive")]
+    fn test_zero_modulus_rejected() {
+        let env = Env::default();
+        deterministic_choice(&env, &[], 0);
+    }
+}
@@ -0,0 +1,59 @@
+// Ledger timestamp comparisons shared by the contracts
+//
+// Convention:
+// - A point in time is reached AT that timestamp (inclusive): an action gated
+//   on `resolution_time` is allowed when `now == resolution_time`.
+// - A window of `duration` seconds starting at `start` is half-open,
+//   `[start, start + duration)`: open at `start`, already closed at
+//   `start + duration`. Cooldowns are windows that block while open.
+//
+// Every ledger closed with the same timestamp therefore sees the same answer,
+// and a window ending exactly when another starts never overlaps it.
+
+use soroban_sdk::Env;
+
+/// True once the ledger timestamp is at or past `at`
+pub fn has_reached(env: &Env, at: u64) -> bool {
+    env.ledger().timestamp() >= at
+}
+
+/// True while the ledger timestamp is inside `[start, start + duration)`
+pub fn window_open(env: &Env, start: u64, duration: u64) -> bool {
+    let now = env.ledger().timestamp();
+    now >= start && now < start.saturating_add(duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Ledger;
+
+    #[test]
+    fn test_has_reached_is_inclusive() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1000);
+        assert!(has_reached(&env, 999));
+        assert!(has_reached(&env, 1000));
+        assert!(!has_reached(&env, 1001));
+    }
+
+    #[test]
+    fn test_window_is_half_open() {
+        let env = Env::default();
+        env.ledger().set_timestamp(999);
+        assert!(!window_open(&env, 1000, 100));
+        env.ledger().set_timestamp(1000);
+        assert!(window_open(&env, 1000, 100));
+        env.ledger().set_timestamp(1099);
+        assert!(window_open(&env, 1000, 100));
+        env.ledger().set_timestamp(1100);
+        assert!(!window_open(&env, 1000, 100));
+    }
+
+    #[test]
+    fn test_unbounded_window_does_not_overflow() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1000);
+        assert!(window_open(&env, 10, u64::MAX));
+    }
+}
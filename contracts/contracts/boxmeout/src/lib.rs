@@ -1,5 +1,24 @@
+//! BoxMeOut Stella contracts
+//!
+//! One crate holds all five contracts; a cargo feature picks which one a WASM
+//! build contains. Each WASM must be built with default features off and
+//! exactly one contract feature:
+//!
+//! ```text
+//! cargo build --target wasm32v1-none --release --no-default-features --features market
+//! cargo build --target wasm32v1-none --release --no-default-features --features oracle
+//! cargo build --target wasm32v1-none --release --no-default-features --features amm
+//! cargo build --target wasm32v1-none --release --no-default-features --features factory
+//! cargo build --target wasm32v1-none --release --no-default-features --features treasury
+//! ```
+//!
+//! Every build writes `target/wasm32v1-none/release/boxmeout.wasm`, so copy it
+//! aside before building the next contract (`build_contracts.sh` does this).
+//!
+//! Native builds see every contract: the default `testutils` feature compiles
+//! all modules, so a plain `cargo test` runs the unit tests and every
+//! cross-contract integration suite.
 #![no_std]
-// lib.rs
 
 #[cfg(any(feature = "amm", test, feature = "testutils"))]
 pub mod amm;
@@ -14,6 +33,23 @@ pub mod treasury;
 
 pub mod helpers;
 
+// A WASM exports every compiled contract, so two would merge into one binary
+#[cfg(target_family = "wasm")]
+const _: () = {
+    let contracts = cfg!(feature = "market") as u32
+        + cfg!(feature = "oracle") as u32
+        + cfg!(feature = "amm") as u32
+        + cfg!(feature = "factory") as u32
+        + cfg!(feature = "treasury") as u32;
+    assert!(
+        contracts == 1,
+        "build exactly one contract per WASM: --no-default-features --features market|oracle|amm|factory|treasury"
+    );
+};
+
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
+
 // Feature-gated exports for WASM builds
 #[cfg(feature = "market")]
 pub use market::*;
@@ -1,9 +1,18 @@
 // contracts/market.rs - Individual Prediction Market Contract
 // Handles predictions, bet commitment/reveal, market resolution, and winnings claims
 
+// `initialize` takes every setting the factory assigns; the client functions
+// #[contractimpl] generates for it do not carry a per-function allow
+#![allow(clippy::too_many_arguments)]
+
+use crate::assert_invariant;
+use crate::helpers::{
+    events, guard, kyc, math, safe_add_i128, safe_add_u32, safe_add_u64, safe_sub_i128, time,
+};
 use soroban_sdk::{
-    contract, contracterror, contractevent, contractimpl, contracttype, token, Address, BytesN,
-    Env, Symbol, Vec,
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contract, contracterror, contractevent, contractimpl, contracttype, panic_with_error, token,
+    Address, BytesN, Env, IntoVal, Symbol, Vec,
 };
 
 #[contractevent]
@@ -29,29 +38,6 @@ pub struct MarketClosedEvent {
     pub timestamp: u64,
 }
 
-#[contractevent]
-pub struct MarketResolvedEvent {
-    pub market_id: BytesN<32>,
-    pub final_outcome: u32,
-    pub timestamp: u64,
-}
-
-#[contractevent]
-pub struct WinningsClaimedEvent {
-    pub user: Address,
-    pub market_id: BytesN<32>,
-    pub net_payout: i128,
-}
-
-#[contractevent]
-pub struct PredictionRevealedEvent {
-    pub user: Address,
-    pub market_id: BytesN<32>,
-    pub outcome: u32,
-    pub amount: i128,
-    pub timestamp: u64,
-}
-
 #[contractevent]
 pub struct MarketDisputedEvent {
     pub user: Address,
@@ -68,6 +54,13 @@ pub struct RefundedEvent {
     pub timestamp: u64,
 }
 
+/// Version of the event schema published by this contract
+///
+/// Version 2 moved prediction, claim, resolve and state events to indexed
+/// topics carrying market_id (and user where relevant). Version 3 gave every
+/// event in `helpers::events::MARKET_EVENTS` that layout.
+pub const EVENT_SCHEMA_VERSION: u32 = 3;
+
 // Storage keys
 const MARKET_ID_KEY: &str = "market_id";
 const CREATOR_KEY: &str = "creator";
@@ -86,16 +79,150 @@ const PARTICIPANTS_KEY: &str = "participants";
 const PREDICTION_PREFIX: &str = "prediction";
 const REVEALED_PARTICIPANTS_KEY: &str = "revealed_participants";
 const REFUNDED_PREFIX: &str = "refunded";
+const PARTICIPANT_PREFIX: &str = "participant";
 const WINNING_OUTCOME_KEY: &str = "winning_outcome";
 const WINNER_SHARES_KEY: &str = "winner_shares";
 const LOSER_SHARES_KEY: &str = "loser_shares";
+const METADATA_KEY: &str = "metadata";
+const BET_HASH_PREFIX: &str = "bet_hash";
+const POSITION_SEQ_PREFIX: &str = "position_seq";
+const OPERATOR_PREFIX: &str = "operator";
+const KYC_REGISTRY_KEY: &str = "kyc_registry";
+const FEE_BPS_KEY: &str = "fee_bps";
+const MIN_BET_KEY: &str = "min_bet";
+const MAX_POSITION_KEY: &str = "max_position";
+const ANTI_SNIPE_KEY: &str = "anti_snipe";
+const ORIGINAL_RES_TIME_KEY: &str = "original_res_time";
+const MAX_RES_EXTENSION_KEY: &str = "max_res_extension";
+
+/// Default cap on how far resolution_time may be pushed past its original value
+pub const DEFAULT_MAX_RESOLUTION_EXTENSION: u64 = 30 * 24 * 60 * 60;
+const SNIPE_EXTENDED_KEY: &str = "snipe_extended";
+const GUARANTEE_KEY: &str = "guarantee";
+const DISPUTE_WINDOW_KEY: &str = "dispute_window";
+
+/// Dispute window after resolution unless the creator sets another (7 days)
+pub const DEFAULT_MARKET_DISPUTE_WINDOW: u64 = 7 * 24 * 60 * 60;
+const GUARANTEE_ESCROW_KEY: &str = "guarantee_escrow";
+const ORACLE_TIMEOUT_KEY: &str = "oracle_timeout";
+
+/// How long after resolution_time the oracle has to resolve before anyone may
+/// escalate the market to Refunding (14 days)
+pub const DEFAULT_ORACLE_TIMEOUT: u64 = 14 * 24 * 60 * 60;
+const NOTIFY_TARGET_KEY: &str = "notify_target";
+const NOTIFY_STATUS_KEY: &str = "notify_status";
+const POOL_REVEAL_KEY: &str = "pool_reveal";
+const LOSS_CAP_KEY: &str = "loss_cap_bps";
+const ORACLE_DISPUTE_KEY: &str = "oracle_dispute";
+const DISPUTE_PRIOR_KEY: &str = "dispute_prior";
+const CLAIM_WINDOW_KEY: &str = "claim_window";
+const SWEPT_AMOUNT_KEY: &str = "swept_amount";
+const RESOLVED_AT_KEY: &str = "resolved_at";
+const FROZEN_PREFIX: &str = "frozen";
+
+/// How long after resolution_time winners have to claim unless the creator sets another (180 days)
+pub const DEFAULT_CLAIM_WINDOW: u64 = 180 * 24 * 60 * 60;
+/// Shortest claim window a creator may set (30 days)
+pub const MIN_CLAIM_WINDOW: u64 = 30 * 24 * 60 * 60;
+
+/// Reported for each side's pool total while the split is hidden (see set_pool_reveal)
+pub const REDACTED_AMOUNT: i128 = -1;
+
+/// Maximum platform fee on winnings profit (5%)
+pub const MAX_FEE_BPS: u32 = 500;
+
+/// Loss cap at which every stake comes back; rejected as a market config
+pub const FULL_REFUND_BPS: u32 = 10_000;
+
+/// Pure pari-mutuel payout math, driven by pool totals so it can be tested
+/// without any contract setup.
+pub mod payout {
+    use crate::helpers::math;
+
+    /// Refund owed on a losing stake of `amount` under the loss cap, floored
+    pub fn loss_refund(amount: i128, loss_cap_bps: u32) -> i128 {
+        math::bps(amount, loss_cap_bps)
+    }
+
+    /// Losing stake left for winners once each loser's capped refund is set aside
+    ///
+    /// Refunds are floored per loser, so they never add up to more than the
+    /// loser pool's floored refund, and winners plus losers never take more
+    /// than the pool.
+    pub fn forfeited_stake(loser_shares: i128, loss_cap_bps: u32) -> i128 {
+        loser_shares - loss_refund(loser_shares, loss_cap_bps)
+    }
+
+    /// Gross payout and platform fee on a winning stake of `amount`
+    ///
+    /// Gross is `amount`'s floored share of the winner pool plus the forfeited
+    /// stake; the fee is `fee_bps` of the profit, rounded up. Fees come out of
+    /// gross, so winners and the treasury together never take more than
+    /// `winner_shares + forfeited`.
+    pub fn winner_payout(
+        amount: i128,
+        winner_shares: i128,
+        forfeited: i128,
+        fee_bps: u32,
+    ) -> (i128, i128) {
+        if winner_shares == 0 {
+            return (0, 0);
+        }
+
+        let gross_payout = math::mul_div_floor(amount, winner_shares + forfeited, winner_shares);
+        let profit = gross_payout - amount;
+        if fee_bps == 0 || profit <= 0 {
+            return (gross_payout, 0);
+        }
+
+        (
+            gross_payout,
+            math::mul_div_ceil(profit, fee_bps as i128, math::BPS_DENOMINATOR),
+        )
+    }
+}
+
+/// Market lifecycle state
+///
+/// Stored as a u32 under `market_state`; the discriminants keep the encoding
+/// used by `MarketState.status` (0=OPEN, 1=CLOSED, 2=RESOLVED, ...).
+/// Allowed transitions:
+/// Created -> Open -> Locked -> Resolved -> Disputed, Open/Locked -> Cancelled,
+/// and Resolved -> Swept once the claim deadline passes.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MarketStatus {
+    /// Not yet initialized
+    Created = 5,
+    /// Accepting predictions until the betting cutoff
+    Open = 0,
+    /// Betting closed, awaiting resolution
+    Locked = 1,
+    /// Outcome settled; winners may claim
+    Resolved = 2,
+    /// Resolution under challenge; claims blocked
+    Disputed = 3,
+    /// Market cancelled; participants may claim refunds
+    Cancelled = 4,
+    /// Resolved as Invalid; participants may claim refunds of principal
+    Refunding = 6,
+    /// Claim deadline passed; unclaimed funds were swept to the treasury
+    Swept = 7,
+}
 
-/// Market states
-const STATE_OPEN: u32 = 0;
-const STATE_CLOSED: u32 = 1;
-const STATE_RESOLVED: u32 = 2;
-const STATE_DISPUTED: u32 = 3;
-const STATE_CANCELLED: u32 = 4;
+/// Outcome delivered by the oracle when resolving a market
+///
+/// Wire-compatible with the u32 outcome codes (0 = NO, 1 = YES).
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ResolutionOutcome {
+    No = 0,
+    Yes = 1,
+    /// Question unanswerable; every participant is refunded
+    Invalid = 2,
+}
 
 /// Error codes following Soroban best practices
 #[contracterror]
@@ -126,6 +253,32 @@ pub enum MarketError {
     InvalidReveal = 11,
     /// User has already revealed their prediction
     DuplicateReveal = 12,
+    /// User already holds a position on the other outcome
+    ConflictingOutcome = 13,
+    /// Outcome must be 0 (NO) or 1 (YES)
+    InvalidOutcome = 14,
+    /// Requested lifecycle transition is not allowed from the current state
+    InvalidStateTransition = 15,
+    /// Amount is below the market's minimum bet
+    BetBelowMinimum = 16,
+    /// Deposit would push the user's position above the per-user maximum
+    PositionLimitExceeded = 17,
+    /// Predictions are paused by the market's operator
+    MarketPaused = 18,
+    /// The market's KYC registry does not verify the user
+    NotVerified = 19,
+    /// Stored balances disagree with what the operation expects
+    InvariantViolation = 20,
+    /// Transfer exceeds the sender's position on that outcome
+    InsufficientPosition = 21,
+    /// Caller is neither the position owner nor an approved operator
+    NotOperator = 22,
+    /// Unclaimed funds were swept to the treasury; claim compensation there
+    UnclaimedSwept = 23,
+    /// The claim deadline has not passed yet
+    ClaimDeadlineNotReached = 24,
+    /// The user's account is frozen on this market
+    AccountFrozen = 25,
 }
 
 /// Commitment record for commit-reveal scheme
@@ -171,6 +324,7 @@ pub const PREDICTION_OUTCOME_NONE: u32 = 2;
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RevealedPredictionItem {
     pub user: Address,
+    /// PREDICTION_OUTCOME_NONE while the pool split is hidden
     pub outcome: u32,
     pub amount: i128,
     pub timestamp: u64,
@@ -212,6 +366,147 @@ pub struct MarketState {
     pub participant_count: u32,
     /// Winning outcome (0=NO, 1=YES), None if not resolved
     pub winning_outcome: Option<u32>,
+    /// False while the YES/NO split is hidden (see set_pool_reveal)
+    pub pools_revealed: bool,
+}
+
+/// Descriptive market metadata so UIs can render a market purely from chain state
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarketMetadata {
+    /// Hash of the full question text (stored off-chain)
+    pub question_hash: BytesN<32>,
+    pub category: Symbol,
+    pub creator: Address,
+    pub created_at: u64,
+    pub resolution_time: u64,
+    /// Number of outcome labels (2 for binary markets)
+    pub outcome_count: u32,
+    /// Oracle contract resolving this market
+    pub oracle: Address,
+}
+
+/// Per-user position by outcome (revealed or directly placed predictions only)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Position {
+    pub yes_amount: i128,
+    pub no_amount: i128,
+    pub claimed: bool,
+}
+
+/// One bet in a user's history, as folded into the rolling bet hash
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BetRecord {
+    pub outcome: u32,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+/// Pool totals and unique participant count
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pools {
+    pub yes_total: i128,
+    pub no_total: i128,
+    pub participant_count: u32,
+}
+
+/// Anti-sniping rule: large late bets push the betting cutoff back
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AntiSnipeConfig {
+    /// A bet above this fraction of the current pool (basis points) is "large"
+    pub snipe_threshold_bps: u32,
+    /// Final seconds before the cutoff in which large bets trigger an extension
+    pub snipe_window: u64,
+    /// Seconds added to the cutoff per trigger
+    pub snipe_extension: u64,
+    /// Upper bound on the cumulative extension
+    pub max_total_extension: u64,
+}
+
+/// Promotional payout floor for winners, backed by an escrow
+///
+/// The escrow is held by the market alongside (but never counted in) the
+/// prediction pools.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Guarantee {
+    /// Guaranteed payout as a multiple of stake (basis points, 12000 = 1.2x)
+    pub multiple_bps: u32,
+    /// Escrow pulled from the funder when the guarantee was set
+    pub escrow: i128,
+    /// Unused escrow can be returned to the funder from this timestamp
+    pub claim_deadline: u64,
+    pub funder: Address,
+}
+
+/// Result of pinging the creator's notification target on resolution
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum NotificationStatus {
+    /// `on_market_resolved` returned normally
+    Delivered = 0,
+    /// The target trapped or does not implement `on_market_resolved`
+    Failed = 1,
+}
+
+/// Position summary for a single user, read by the factory exposure view
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Portfolio {
+    /// Amount staked (committed or revealed), 0 if no position
+    pub stake: i128,
+    /// 0=NO, 1=YES when revealed; PREDICTION_OUTCOME_NONE otherwise
+    pub side: u32,
+    /// Implied probability of `side` from current pools (basis points)
+    pub implied_probability_bps: u32,
+    /// Amount the user could claim right now (winnings or refund)
+    pub claimable: i128,
+}
+
+/// Claim preview status codes
+pub const CLAIM_STATUS_OK: u32 = 0;
+pub const CLAIM_STATUS_NOT_RESOLVED: u32 = 1;
+pub const CLAIM_STATUS_NO_POSITION: u32 = 2;
+pub const CLAIM_STATUS_ALREADY_CLAIMED: u32 = 3;
+pub const CLAIM_STATUS_LOSING_POSITION: u32 = 4;
+pub const CLAIM_STATUS_NO_WINNERS: u32 = 5;
+pub const CLAIM_STATUS_ZERO_PAYOUT: u32 = 6;
+/// Losing position on a capped-loss market; claim_loss_refund pays `net`
+pub const CLAIM_STATUS_LOSS_REFUND: u32 = 7;
+/// The user's account is frozen; nothing is paid until it is unfrozen
+pub const CLAIM_STATUS_ACCOUNT_FROZEN: u32 = 8;
+
+/// Position mutation kinds carried by PositionChangedEvent
+pub const POSITION_OP_BET: u32 = 0;
+pub const POSITION_OP_CLAIM: u32 = 1;
+pub const POSITION_OP_REFUND: u32 = 2;
+pub const POSITION_OP_REBUILD: u32 = 3;
+pub const POSITION_OP_TRANSFER: u32 = 4;
+
+/// Breakdown of what claim_winnings would transfer right now
+///
+/// The platform fee is the only deduction a claim takes: markets carry no
+/// creator fee, insurance levy or rebate.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimPreview {
+    /// CLAIM_STATUS_* code; amounts are zero unless OK or LOSS_REFUND
+    pub status: u32,
+    /// Pro-rata share of the total pool before fees
+    pub gross: i128,
+    /// Platform fee on profit, routed to the treasury
+    pub protocol_fee: i128,
+    /// Top-up from the guarantee escrow (included in net)
+    pub guarantee_topup: i128,
+    /// Amount transferred to the user
+    pub net: i128,
+    /// Stroops lost to rounding the fee up (included in protocol_fee)
+    pub rounding_dust: i128,
 }
 
 /// PREDICTION MARKET - Manages individual market logic
@@ -221,6 +516,12 @@ pub struct PredictionMarket;
 #[contractimpl]
 impl PredictionMarket {
     /// Initialize a single market instance
+    ///
+    /// `closing_time` is the betting cutoff: predictions are accepted strictly
+    /// before it and it must not be later than `resolution_time`. `fee_bps` is
+    /// the platform fee the factory assigned; later factory fee changes only
+    /// affect markets created afterwards. `metadata`, when given, must agree
+    /// with `creator`, `oracle` and `resolution_time`.
     #[allow(clippy::too_many_arguments)]
     pub fn initialize(
         env: Env,
@@ -231,10 +532,25 @@ impl PredictionMarket {
         oracle: Address,
         closing_time: u64,
         resolution_time: u64,
+        fee_bps: u32,
+        metadata: Option<MarketMetadata>,
     ) {
         // Verify creator signature
         creator.require_auth();
 
+        if closing_time > resolution_time {
+            panic!("Betting cutoff after resolution time");
+        }
+        if fee_bps > MAX_FEE_BPS {
+            panic!("Fee exceeds maximum");
+        }
+        if let Some(metadata) = &metadata {
+            Self::check_metadata(&env, metadata, &creator, &oracle, resolution_time);
+            env.storage()
+                .persistent()
+                .set(&Symbol::new(&env, METADATA_KEY), metadata);
+        }
+
         // Store market_id reference
         env.storage()
             .persistent()
@@ -267,10 +583,15 @@ impl PredictionMarket {
         env.storage()
             .persistent()
             .set(&Symbol::new(&env, RESOLUTION_TIME_KEY), &resolution_time);
-
         env.storage()
             .persistent()
-            .set(&Symbol::new(&env, MARKET_STATE_KEY), &STATE_OPEN);
+            .set(&Symbol::new(&env, ORIGINAL_RES_TIME_KEY), &resolution_time);
+        env.storage().persistent().set(
+            &Symbol::new(&env, ORACLE_TIMEOUT_KEY),
+            &DEFAULT_ORACLE_TIMEOUT,
+        );
+
+        Self::transition(&env, MarketStatus::Open);
 
         // Initialize prediction pools
         env.storage()
@@ -291,6 +612,10 @@ impl PredictionMarket {
             .persistent()
             .set(&Symbol::new(&env, PENDING_COUNT_KEY), &0u32);
 
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, FEE_BPS_KEY), &fee_bps);
+
         // Emit initialization event
         MarketInitializedEvent {
             market_id,
@@ -303,164 +628,1370 @@ impl PredictionMarket {
         .publish(&env);
     }
 
-    /// Phase 1: User commits to a prediction (commit-reveal scheme for privacy)
+    /// Get the betting cutoff (the market closing time)
     ///
-    /// - Require user authentication
-    /// - Validate market is in OPEN state
-    /// - Validate current timestamp < closing_time
-    /// - Validate amount > 0
-    /// - Prevent user from committing twice (check existing commits)
-    /// - Transfer amount from user to market escrow
-    /// - Store commit record: { user, commit_hash, amount, timestamp }
-    /// - Emit CommitmentMade(user, market_id, amount)
-    /// - Update pending_predictions count
-    pub fn commit_prediction(
-        env: Env,
-        user: Address,
-        commit_hash: BytesN<32>,
-        amount: i128,
-    ) -> Result<(), MarketError> {
-        // Require user authentication
-        user.require_auth();
-
-        // Validate market is initialized
-        let market_state: u32 = env
-            .storage()
-            .persistent()
-            .get(&Symbol::new(&env, MARKET_STATE_KEY))
-            .ok_or(MarketError::NotInitialized)?;
-
-        // Validate market is in open state
-        if market_state != STATE_OPEN {
-            return Err(MarketError::InvalidMarketState);
-        }
-
-        // Validate current timestamp < closing_time
-        let closing_time: u64 = env
-            .storage()
+    /// Predictions are accepted only while `now < cutoff`; a prediction at
+    /// exactly the cutoff timestamp is rejected with `MarketClosed`.
+    pub fn get_betting_cutoff(env: Env) -> u64 {
+        env.storage()
             .persistent()
             .get(&Symbol::new(&env, CLOSING_TIME_KEY))
-            .ok_or(MarketError::NotInitialized)?;
+            .expect("Market not initialized")
+    }
 
-        let current_time = env.ledger().timestamp();
-        if current_time >= closing_time {
-            return Err(MarketError::MarketClosed);
+    /// Move the betting cutoff earlier (factory only)
+    ///
+    /// The factory admin runs it through `extend_market_betting_cutoff`; a
+    /// creator holding a position cannot close betting on their own market.
+    /// The cutoff can never be pushed later, and can only be changed while the
+    /// current cutoff has not yet passed. The new cutoff must still be in the future.
+    pub fn extend_betting_cutoff(env: Env, new_cutoff: u64) {
+        Self::require_factory(&env);
+
+        let current_cutoff = Self::get_betting_cutoff(env.clone());
+        let now = env.ledger().timestamp();
+
+        if now >= current_cutoff {
+            panic!("Betting cutoff already passed");
         }
-
-        // Validate amount > 0
-        if amount <= 0 {
-            return Err(MarketError::InvalidAmount);
+        if new_cutoff >= current_cutoff {
+            panic!("Cutoff can only move earlier");
         }
-
-        // Check for duplicate commit per user
-        let commit_key = Self::get_commit_key(&env, &user);
-        if env.storage().persistent().has(&commit_key) {
-            return Err(MarketError::DuplicateCommit);
+        if new_cutoff <= now {
+            panic!("Cutoff must be in the future");
         }
 
-        // Get USDC token contract and market_id
-        let usdc_token: Address = env
-            .storage()
+        env.storage()
             .persistent()
-            .get(&Symbol::new(&env, USDC_KEY))
-            .ok_or(MarketError::NotInitialized)?;
+            .set(&Symbol::new(&env, CLOSING_TIME_KEY), &new_cutoff);
 
         let market_id: BytesN<32> = env
             .storage()
             .persistent()
             .get(&Symbol::new(&env, MARKET_ID_KEY))
-            .ok_or(MarketError::NotInitialized)?;
-
-        // Transfer USDC from user to market escrow (this contract)
-        let token_client = token::TokenClient::new(&env, &usdc_token);
-        let contract_address = env.current_contract_address();
-
-        // Transfer tokens - will panic if insufficient balance or approval
-        token_client.transfer(&user, &contract_address, &amount);
-
-        // Create and store commitment record
-        let commitment = Commitment {
-            user: user.clone(),
-            commit_hash: commit_hash.clone(),
-            amount,
-            timestamp: current_time,
-        };
+            .expect("Market not initialized");
+        events::market::betting_cutoff_updated(&env, market_id, current_cutoff, new_cutoff);
+    }
 
-        env.storage().persistent().set(&commit_key, &commitment);
+    /// Get the event schema version, so indexers can pick a decoder
+    pub fn get_event_version(_env: Env) -> u32 {
+        EVENT_SCHEMA_VERSION
+    }
 
-        // Add user to participants (for cancel refunds)
-        let mut participants: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&Symbol::new(&env, PARTICIPANTS_KEY))
-            .unwrap_or_else(|| Vec::new(&env));
-        participants.push_back(user.clone());
+    /// Get the current resolution time
+    pub fn get_resolution_time(env: Env) -> u64 {
         env.storage()
             .persistent()
-            .set(&Symbol::new(&env, PARTICIPANTS_KEY), &participants);
-
-        // Update pending count
-        let pending_count: u32 = env
-            .storage()
-            .persistent()
-            .get(&Symbol::new(&env, PENDING_COUNT_KEY))
-            .unwrap_or(0);
+            .get(&Symbol::new(&env, RESOLUTION_TIME_KEY))
+            .expect("Market not initialized")
+    }
 
+    /// Get the cap on extending resolution_time past its original value (seconds)
+    pub fn get_max_resolution_extension(env: Env) -> u64 {
         env.storage()
             .persistent()
-            .set(&Symbol::new(&env, PENDING_COUNT_KEY), &(pending_count + 1));
+            .get(&Symbol::new(&env, MAX_RES_EXTENSION_KEY))
+            .unwrap_or(DEFAULT_MAX_RESOLUTION_EXTENSION)
+    }
 
-        // Emit CommitmentMade event
-        CommitmentMadeEvent {
-            user,
-            market_id,
-            amount,
-        }
-        .publish(&env);
+    /// Set the resolution extension cap (creator only, before the first prediction)
+    pub fn set_max_resolution_extension(env: Env, creator: Address, max_extension: u64) {
+        Self::require_creator(&env, &creator);
 
-        Ok(())
-    }
+        if Self::has_predictions(&env) {
+            panic!("Extension cap locked after first prediction");
+        }
 
-    /// Helper: Generate storage key for user commitment
-    fn get_commit_key(env: &Env, user: &Address) -> (Symbol, Address) {
-        (Symbol::new(env, COMMIT_PREFIX), user.clone())
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, MAX_RES_EXTENSION_KEY), &max_extension);
     }
 
-    /// Helper: Generate storage key for user prediction
-    fn get_prediction_key(env: &Env, user: &Address) -> (Symbol, Address) {
-        (Symbol::new(env, PREDICTION_PREFIX), user.clone())
-    }
+    /// Push back the resolution time for a postponed event (factory only)
+    ///
+    /// The factory admin runs it through `extend_market_resolution_time`; the
+    /// oracle timeout and claim deadline move with it. Allowed while the market
+    /// is Open or Locked and the oracle holds no attestation for it. The new
+    /// time may be at most the configured cap past the original resolution
+    /// time (a cap reaching past u64::MAX is unbounded). The oracle's copy is
+    /// updated in the same call.
+    pub fn extend_resolution_time(env: Env, new_time: u64) {
+        Self::require_factory(&env);
+
+        let state = Self::get_state(env.clone());
+        if state != MarketStatus::Open && state != MarketStatus::Locked {
+            panic!("Market already resolved or cancelled");
+        }
 
-    /// Helper: Storage key for refunded flag (prevents double-refund)
-    fn get_refunded_key(env: &Env, user: &Address) -> (Symbol, Address) {
-        (Symbol::new(env, REFUNDED_PREFIX), user.clone())
-    }
+        let old_time = Self::get_resolution_time(env.clone());
+        if new_time <= old_time {
+            panic!("Resolution time can only move later");
+        }
+        let original_time: u64 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, ORIGINAL_RES_TIME_KEY))
+            .unwrap_or(old_time);
+        if original_time
+            .checked_add(Self::get_max_resolution_extension(env.clone()))
+            .is_some_and(|latest| new_time > latest)
+        {
+            panic!("Extension exceeds maximum");
+        }
 
-    /// Helper: Get user commitment (for testing and reveal phase)
-    pub fn get_commitment(env: Env, user: Address) -> Option<Commitment> {
-        let commit_key = Self::get_commit_key(&env, &user);
-        env.storage().persistent().get(&commit_key)
-    }
+        let oracle: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, ORACLE_KEY))
+            .expect("Oracle address not found");
+        let market_id: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, MARKET_ID_KEY))
+            .expect("Market not initialized");
+        let (yes_count, no_count): (u32, u32) = env.invoke_contract(
+            &oracle,
+            &Symbol::new(&env, "get_attestation_counts"),
+            (market_id.clone(),).into_val(&env),
+        );
+        if yes_count + no_count > 0 {
+            panic!("Attestation already exists");
+        }
 
-    /// Helper: Get pending commit count
-    pub fn get_pending_count(env: Env) -> u32 {
         env.storage()
             .persistent()
-            .get(&Symbol::new(&env, PENDING_COUNT_KEY))
-            .unwrap_or(0)
+            .set(&Symbol::new(&env, RESOLUTION_TIME_KEY), &new_time);
+        env.invoke_contract::<()>(
+            &oracle,
+            &Symbol::new(&env, "update_resolution_time"),
+            (market_id.clone(), new_time).into_val(&env),
+        );
+
+        events::market::resolution_time_extended(&env, market_id, old_time, new_time);
     }
 
-    /// Helper: Get market state
-    pub fn get_market_state_value(env: Env) -> Option<u32> {
+    /// Configure the anti-sniping rule (creator only, before the first prediction)
+    pub fn set_anti_sniping(env: Env, creator: Address, config: AntiSnipeConfig) {
+        Self::require_creator(&env, &creator);
+
+        if Self::has_predictions(&env) {
+            panic!("Anti-sniping locked after first prediction");
+        }
+        if config.snipe_threshold_bps == 0 || config.snipe_threshold_bps > 10000 {
+            panic!("Invalid snipe threshold");
+        }
+
         env.storage()
             .persistent()
-            .get(&Symbol::new(&env, MARKET_STATE_KEY))
+            .set(&Symbol::new(&env, ANTI_SNIPE_KEY), &config);
     }
 
-    /// Phase 2: User reveals their committed prediction
+    /// Fund a payout floor for winners (creator only, once, before the first prediction)
     ///
-    /// Verifies the commitment hash matches hash(user + market_id + outcome + salt),
-    /// transitions prediction from COMMITTED → REVEALED, updates pools,
+    /// Pulls `escrow` from the creator. Winners whose pool payout falls below
+    /// `multiple_bps` of their stake are topped up from it at claim time; what is
+    /// left returns to the creator via `release_guarantee` after `claim_deadline`.
+    pub fn set_guarantee(
+        env: Env,
+        creator: Address,
+        multiple_bps: u32,
+        escrow: i128,
+        claim_deadline: u64,
+    ) {
+        Self::require_creator(&env, &creator);
+
+        if Self::has_predictions(&env) {
+            panic!("Guarantee locked after first prediction");
+        }
+        if Self::get_guarantee(env.clone()).is_some() {
+            panic!("Guarantee already set");
+        }
+        if multiple_bps <= 10_000 {
+            panic!("Guaranteed multiple must exceed 1x");
+        }
+        if escrow <= 0 {
+            panic!("Escrow must be positive");
+        }
+        let resolution_time: u64 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, RESOLUTION_TIME_KEY))
+            .expect("Resolution time not found");
+        if claim_deadline <= resolution_time {
+            panic!("Claim deadline must be after resolution time");
+        }
+
+        let usdc: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, USDC_KEY))
+            .expect("USDC token not found");
+        let contract_address = env.current_contract_address();
+        token::TokenClient::new(&env, &usdc).transfer(&creator, &contract_address, &escrow);
+
+        env.storage().persistent().set(
+            &Symbol::new(&env, GUARANTEE_KEY),
+            &Guarantee {
+                multiple_bps,
+                escrow,
+                claim_deadline,
+                funder: creator.clone(),
+            },
+        );
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, GUARANTEE_ESCROW_KEY), &escrow);
+
+        events::market::guarantee_funded(&env, creator, multiple_bps, escrow);
+    }
+
+    /// Get the payout guarantee, if funded
+    pub fn get_guarantee(env: Env) -> Option<Guarantee> {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, GUARANTEE_KEY))
+    }
+
+    /// Escrow still available for guarantee top-ups (held outside the pools)
+    pub fn get_guarantee_escrow(env: Env) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, GUARANTEE_ESCROW_KEY))
+            .unwrap_or(0)
+    }
+
+    /// Return unused guarantee escrow to its funder
+    ///
+    /// Allowed after the claim deadline, or at any time once the market is
+    /// cancelled or refunding.
+    pub fn release_guarantee(env: Env) -> i128 {
+        let guarantee = Self::get_guarantee(env.clone()).expect("No guarantee");
+        let state = Self::get_state(env.clone());
+        if env.ledger().timestamp() < guarantee.claim_deadline
+            && state != MarketStatus::Cancelled
+            && state != MarketStatus::Refunding
+        {
+            panic!("Claim deadline not reached");
+        }
+        let amount = Self::get_guarantee_escrow(env.clone());
+        if amount <= 0 {
+            panic!("No escrow to release");
+        }
+
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, GUARANTEE_ESCROW_KEY), &0i128);
+        let usdc: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, USDC_KEY))
+            .expect("USDC token not found");
+        let contract_address = env.current_contract_address();
+        token::TokenClient::new(&env, &usdc).transfer(
+            &contract_address,
+            &guarantee.funder,
+            &amount,
+        );
+
+        events::market::guarantee_released(&env, guarantee.funder, amount);
+
+        amount
+    }
+
+    /// Get the anti-sniping rule, if enabled
+    pub fn get_anti_sniping(env: Env) -> Option<AntiSnipeConfig> {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, ANTI_SNIPE_KEY))
+    }
+
+    /// Total seconds the cutoff has been extended by anti-sniping so far
+    pub fn get_snipe_extension_used(env: Env) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, SNIPE_EXTENDED_KEY))
+            .unwrap_or(0)
+    }
+
+    /// Helper: Extend the cutoff if a large bet lands inside the snipe window
+    ///
+    /// `amount` is compared against the pool before the bet is added. The
+    /// extension is capped by `max_total_extension` and by `resolution_time`.
+    fn apply_anti_sniping(env: &Env, market_id: &BytesN<32>, amount: i128, now: u64, cutoff: u64) {
+        let config = match Self::get_anti_sniping(env.clone()) {
+            Some(config) => config,
+            None => return,
+        };
+        if now < cutoff.saturating_sub(config.snipe_window) {
+            return;
+        }
+
+        let pools = Self::exact_pools(env);
+        let pool = pools.yes_total + pools.no_total;
+        if amount * 10000 <= pool * config.snipe_threshold_bps as i128 {
+            return;
+        }
+
+        let used = Self::get_snipe_extension_used(env.clone());
+        let resolution_time: u64 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, RESOLUTION_TIME_KEY))
+            .expect("Resolution time not found");
+        let extension = config
+            .snipe_extension
+            .min(config.max_total_extension.saturating_sub(used))
+            .min(resolution_time.saturating_sub(cutoff));
+        if extension == 0 {
+            return;
+        }
+
+        let new_cutoff = cutoff + extension;
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(env, CLOSING_TIME_KEY), &new_cutoff);
+        env.storage().persistent().set(
+            &Symbol::new(env, SNIPE_EXTENDED_KEY),
+            &safe_add_u64(used, extension),
+        );
+
+        events::market::anti_snipe_extended(
+            env,
+            market_id.clone(),
+            amount,
+            cutoff,
+            new_cutoff,
+            used + extension,
+        );
+    }
+
+    /// Helper: Validate metadata against the market's own settings
+    fn check_metadata(
+        env: &Env,
+        metadata: &MarketMetadata,
+        creator: &Address,
+        oracle: &Address,
+        resolution_time: u64,
+    ) {
+        if metadata.creator != *creator {
+            panic!("Metadata creator mismatch");
+        }
+        if metadata.oracle != *oracle {
+            panic!("Metadata oracle mismatch");
+        }
+        if metadata.resolution_time != resolution_time {
+            panic!("Metadata resolution time mismatch");
+        }
+        if metadata.resolution_time < env.ledger().timestamp() {
+            panic!("Resolution time in the past");
+        }
+        if metadata.category == Symbol::new(env, "") {
+            panic!("Category must not be empty");
+        }
+    }
+
+    /// Get market metadata, None if it was never set
+    pub fn get_metadata(env: Env) -> Option<MarketMetadata> {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, METADATA_KEY))
+    }
+
+    /// Replace the question hash (factory only, before the first prediction)
+    ///
+    /// The factory admin reaches this through the factory's `update_market_question_hash`.
+    pub fn update_metadata_hash(env: Env, question_hash: BytesN<32>) {
+        Self::require_factory(&env);
+
+        if Self::has_predictions(&env) {
+            panic!("Metadata locked after first prediction");
+        }
+
+        let mut metadata: MarketMetadata = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, METADATA_KEY))
+            .expect("Metadata not set");
+        metadata.question_hash = question_hash.clone();
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, METADATA_KEY), &metadata);
+
+        let market_id: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, MARKET_ID_KEY))
+            .expect("Market not initialized");
+        events::market::metadata_updated(&env, market_id, question_hash, env.ledger().timestamp());
+    }
+
+    /// Helper: Require auth from the factory that created this market
+    fn require_factory(env: &Env) {
+        let factory: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, FACTORY_KEY))
+            .expect("Market not initialized");
+        factory.require_auth();
+    }
+
+    /// Helper: Require auth from the stored market creator
+    fn require_creator(env: &Env, caller: &Address) {
+        caller.require_auth();
+        let creator: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, CREATOR_KEY))
+            .expect("Market not initialized");
+        if *caller != creator {
+            panic!("Unauthorized: only creator");
+        }
+    }
+
+    /// Helper: Whether any prediction (committed or revealed) has been placed
+    fn has_predictions(env: &Env) -> bool {
+        let participants: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, PARTICIPANTS_KEY))
+            .unwrap_or_else(|| Vec::new(env));
+        let revealed: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, REVEALED_PARTICIPANTS_KEY))
+            .unwrap_or_else(|| Vec::new(env));
+        !participants.is_empty() || !revealed.is_empty()
+    }
+
+    /// Get the market lifecycle state (Created until initialized)
+    pub fn get_state(env: Env) -> MarketStatus {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, MARKET_STATE_KEY))
+            .unwrap_or(MarketStatus::Created)
+    }
+
+    /// Move a resolved market into Disputed if the oracle reports an active challenge
+    ///
+    /// Permissionless; returns the (possibly updated) state. Claims are blocked
+    /// while the market is Disputed.
+    pub fn sync_dispute_state(env: Env) -> MarketStatus {
+        let state = Self::get_state(env.clone());
+        if state != MarketStatus::Resolved {
+            return state;
+        }
+
+        let oracle: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, ORACLE_KEY))
+            .expect("Oracle address not found");
+        if !Self::oracle_supports(&env, &oracle, "challenge") {
+            return state;
+        }
+        let market_id: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, MARKET_ID_KEY))
+            .expect("Market not initialized");
+        let challenged = env
+            .try_invoke_contract::<bool, soroban_sdk::Error>(
+                &oracle,
+                &Symbol::new(&env, "has_active_challenge"),
+                (market_id,).into_val(&env),
+            )
+            .ok()
+            .and_then(|result| result.ok())
+            .unwrap_or(false);
+
+        if challenged {
+            env.storage()
+                .persistent()
+                .set(&Symbol::new(&env, ORACLE_DISPUTE_KEY), &true);
+            Self::enter_oracle_dispute(&env);
+            return MarketStatus::Disputed;
+        }
+        state
+    }
+
+    /// Oracle push: raise or clear the attestation-challenge flag
+    ///
+    /// Only the configured oracle may call this. Raising the flag moves a
+    /// Resolved market into Disputed (claims and AMM settlement blocked); a
+    /// market resolved later while the flag is up goes straight to Disputed.
+    /// Clearing it returns a market the flag disputed to Resolved. Disputes
+    /// opened through dispute_market are left alone.
+    pub fn set_dispute_flag(env: Env, market_id: BytesN<32>, disputed: bool) {
+        let oracle: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, ORACLE_KEY))
+            .expect("Oracle address not found");
+        oracle.require_auth();
+
+        let stored_id: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, MARKET_ID_KEY))
+            .expect("Market not initialized");
+        if stored_id != market_id {
+            panic!("Market ID mismatch");
+        }
+
+        let flag_key = Symbol::new(&env, ORACLE_DISPUTE_KEY);
+        if disputed {
+            env.storage().persistent().set(&flag_key, &true);
+            Self::enter_oracle_dispute(&env);
+        } else {
+            env.storage().persistent().remove(&flag_key);
+            let prior_key = Symbol::new(&env, DISPUTE_PRIOR_KEY);
+            let prior: Option<MarketStatus> = env.storage().persistent().get(&prior_key);
+            if let Some(prior) = prior {
+                env.storage().persistent().remove(&prior_key);
+                // Direct write: Disputed -> Resolved is not a general lifecycle move
+                env.storage()
+                    .persistent()
+                    .set(&Symbol::new(&env, MARKET_STATE_KEY), &prior);
+                // Winners could not claim while disputed: restart the claim window
+                env.storage().persistent().set(
+                    &Symbol::new(&env, RESOLVED_AT_KEY),
+                    &env.ledger().timestamp(),
+                );
+                events::market::state_changed(
+                    &env,
+                    market_id.clone(),
+                    MarketStatus::Disputed,
+                    prior,
+                );
+            }
+        }
+
+        events::market::dispute_flag(&env, market_id, disputed, Self::get_state(env.clone()));
+    }
+
+    /// Whether the oracle has flagged an open attestation challenge
+    pub fn is_dispute_flagged(env: Env) -> bool {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, ORACLE_DISPUTE_KEY))
+            .unwrap_or(false)
+    }
+
+    /// Helper: Move a Resolved market into Disputed on the oracle's behalf,
+    /// remembering to restore it when the flag clears
+    fn enter_oracle_dispute(env: &Env) {
+        if Self::get_state(env.clone()) != MarketStatus::Resolved {
+            return;
+        }
+        env.storage().persistent().set(
+            &Symbol::new(env, DISPUTE_PRIOR_KEY),
+            &MarketStatus::Resolved,
+        );
+        Self::transition(env, MarketStatus::Disputed);
+    }
+
+    /// Helper: Whether the oracle advertises `capability` in get_capabilities
+    fn oracle_supports(env: &Env, oracle: &Address, capability: &str) -> bool {
+        env.try_invoke_contract::<Vec<Symbol>, soroban_sdk::Error>(
+            oracle,
+            &Symbol::new(env, "get_capabilities"),
+            Vec::new(env),
+        )
+        .ok()
+        .and_then(|result| result.ok())
+        .map(|capabilities| capabilities.contains(Symbol::new(env, capability)))
+        .unwrap_or(false)
+    }
+
+    /// Helper: Whether the lifecycle allows moving from `from` to `to`
+    fn can_transition(from: MarketStatus, to: MarketStatus) -> bool {
+        matches!(
+            (from, to),
+            (MarketStatus::Created, MarketStatus::Open)
+                | (MarketStatus::Open, MarketStatus::Locked)
+                | (MarketStatus::Open, MarketStatus::Cancelled)
+                | (MarketStatus::Locked, MarketStatus::Resolved)
+                | (MarketStatus::Locked, MarketStatus::Cancelled)
+                | (MarketStatus::Locked, MarketStatus::Refunding)
+                | (MarketStatus::Resolved, MarketStatus::Disputed)
+                | (MarketStatus::Disputed, MarketStatus::Refunding)
+                | (MarketStatus::Resolved, MarketStatus::Swept)
+        )
+    }
+
+    /// Helper: Apply a lifecycle transition and emit StateChanged
+    ///
+    /// Panics with `InvalidStateTransition` if the move is not allowed.
+    fn transition(env: &Env, to: MarketStatus) {
+        let from = Self::get_state(env.clone());
+        if !Self::can_transition(from, to) {
+            panic_with_error!(env, MarketError::InvalidStateTransition);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(env, MARKET_STATE_KEY), &to);
+
+        let market_id: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, MARKET_ID_KEY))
+            .expect("Market not initialized");
+        events::market::state_changed(env, market_id, from, to);
+    }
+
+    /// Get the minimum amount accepted per prediction (0 = no minimum)
+    pub fn get_min_bet(env: Env) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, MIN_BET_KEY))
+            .unwrap_or(0)
+    }
+
+    /// Get the maximum cumulative position per user on one outcome (0 = no limit)
+    pub fn get_max_position_per_user(env: Env) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, MAX_POSITION_KEY))
+            .unwrap_or(0)
+    }
+
+    /// Set bet limits (creator only, before the first prediction)
+    ///
+    /// A `max_position_per_user` of 0 means no limit.
+    pub fn set_bet_limits(env: Env, creator: Address, min_bet: i128, max_position_per_user: i128) {
+        Self::require_creator(&env, &creator);
+
+        if Self::has_predictions(&env) {
+            panic!("Bet limits locked after first prediction");
+        }
+        if min_bet < 0 || max_position_per_user < 0 {
+            panic!("Bet limits must not be negative");
+        }
+        if max_position_per_user > 0 && min_bet > max_position_per_user {
+            panic!("Minimum bet exceeds maximum position");
+        }
+
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, MIN_BET_KEY), &min_bet);
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, MAX_POSITION_KEY), &max_position_per_user);
+    }
+
+    /// Restrict predictions to users verified by `registry` (creator only, before the first prediction)
+    ///
+    /// The registry must expose `is_verified(user) -> bool`. Claims and refunds
+    /// are never gated.
+    pub fn set_kyc_registry(env: Env, creator: Address, registry: Option<Address>) {
+        Self::require_creator(&env, &creator);
+
+        if Self::has_predictions(&env) {
+            panic!("KYC registry locked after first prediction");
+        }
+
+        let key = Symbol::new(&env, KYC_REGISTRY_KEY);
+        match registry {
+            Some(registry) => env.storage().persistent().set(&key, &registry),
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    /// Get the KYC registry gating predictions, if any
+    pub fn get_kyc_registry(env: Env) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, KYC_REGISTRY_KEY))
+    }
+
+    /// Helper: Reject `user` when the market is permissioned and they are not verified
+    fn check_verified(env: &Env, user: &Address) -> Result<(), MarketError> {
+        match Self::get_kyc_registry(env.clone()) {
+            Some(registry) if !kyc::is_verified(env, &registry, user) => {
+                Err(MarketError::NotVerified)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Helper: Enforce min_bet on `amount` and the per-user cap on `new_position`
+    fn check_bet_limits(env: &Env, amount: i128, new_position: i128) -> Result<(), MarketError> {
+        if amount < Self::get_min_bet(env.clone()) {
+            return Err(MarketError::BetBelowMinimum);
+        }
+        let max_position = Self::get_max_position_per_user(env.clone());
+        if max_position > 0 && new_position > max_position {
+            return Err(MarketError::PositionLimitExceeded);
+        }
+        Ok(())
+    }
+
+    /// Phase 1: User commits to a prediction (commit-reveal scheme for privacy)
+    ///
+    /// - Require user authentication
+    /// - Validate market is in OPEN state
+    /// - Validate current timestamp < closing_time
+    /// - Validate amount > 0
+    /// - Prevent user from committing twice (check existing commits)
+    /// - Transfer amount from user to market escrow
+    /// - Store commit record: { user, commit_hash, amount, timestamp }
+    /// - Emit CommitmentMade(user, market_id, amount)
+    /// - Update pending_predictions count
+    pub fn commit_prediction(
+        env: Env,
+        user: Address,
+        commit_hash: BytesN<32>,
+        amount: i128,
+    ) -> Result<(), MarketError> {
+        // Require user authentication
+        user.require_auth();
+
+        // Validate market is initialized
+        let market_state: MarketStatus = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, MARKET_STATE_KEY))
+            .ok_or(MarketError::NotInitialized)?;
+
+        // Validate market is in open state
+        if market_state != MarketStatus::Open {
+            return Err(MarketError::InvalidMarketState);
+        }
+
+        // Validate current timestamp < closing_time
+        let closing_time: u64 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, CLOSING_TIME_KEY))
+            .ok_or(MarketError::NotInitialized)?;
+
+        let current_time = env.ledger().timestamp();
+        if current_time >= closing_time {
+            return Err(MarketError::MarketClosed);
+        }
+
+        if Self::is_paused_by_operator(&env) {
+            return Err(MarketError::MarketPaused);
+        }
+        Self::check_verified(&env, &user)?;
+
+        // Validate amount > 0
+        if amount <= 0 {
+            return Err(MarketError::InvalidAmount);
+        }
+        Self::check_bet_limits(&env, amount, amount)?;
+
+        // Check for duplicate commit per user
+        let commit_key = Self::get_commit_key(&env, &user);
+        if env.storage().persistent().has(&commit_key) {
+            return Err(MarketError::DuplicateCommit);
+        }
+
+        // Get USDC token contract and market_id
+        let usdc_token: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, USDC_KEY))
+            .ok_or(MarketError::NotInitialized)?;
+
+        let market_id: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, MARKET_ID_KEY))
+            .ok_or(MarketError::NotInitialized)?;
+
+        // Transfer USDC from user to market escrow (this contract)
+        let token_client = token::TokenClient::new(&env, &usdc_token);
+        let contract_address = env.current_contract_address();
+
+        // Transfer tokens - will panic if insufficient balance or approval
+        token_client.transfer(&user, &contract_address, &amount);
+        Self::apply_anti_sniping(&env, &market_id, amount, current_time, closing_time);
+
+        // Create and store commitment record
+        let commitment = Commitment {
+            user: user.clone(),
+            commit_hash: commit_hash.clone(),
+            amount,
+            timestamp: current_time,
+        };
+
+        env.storage().persistent().set(&commit_key, &commitment);
+
+        // Add user to participants (for cancel refunds)
+        env.storage().persistent().set(
+            &(Symbol::new(&env, PARTICIPANT_PREFIX), user.clone()),
+            &true,
+        );
+        let mut participants: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, PARTICIPANTS_KEY))
+            .unwrap_or_else(|| Vec::new(&env));
+        participants.push_back(user.clone());
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, PARTICIPANTS_KEY), &participants);
+
+        // Update pending count
+        let pending_count: u32 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, PENDING_COUNT_KEY))
+            .unwrap_or(0);
+
+        env.storage().persistent().set(
+            &Symbol::new(&env, PENDING_COUNT_KEY),
+            &safe_add_u32(pending_count, 1),
+        );
+
+        // Emit CommitmentMade event
+        CommitmentMadeEvent {
+            user,
+            market_id,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Place a public prediction directly (no commit-reveal)
+    ///
+    /// Adds `amount` to the user's position on `outcome`; repeated calls on the
+    /// same outcome accumulate. A user holds a single side per market, so betting
+    /// the other outcome, or placing while a commitment is pending, is rejected.
+    ///
+    /// # Errors
+    /// - `NotInitialized` - Market not initialized
+    /// - `InvalidMarketState` - Market not in OPEN state
+    /// - `MarketClosed` - Current time >= closing time (betting cutoff)
+    /// - `InvalidAmount` - Amount must be positive
+    /// - `InvalidOutcome` - Outcome is not 0 or 1
+    /// - `DuplicateCommit` - User has an unrevealed commitment
+    /// - `ConflictingOutcome` - User already holds the other side
+    /// - `BetBelowMinimum` - Amount is below `min_bet`
+    /// - `PositionLimitExceeded` - Position would exceed `max_position_per_user`
+    pub fn place_prediction(
+        env: Env,
+        user: Address,
+        outcome: u32,
+        amount: i128,
+    ) -> Result<(), MarketError> {
+        user.require_auth();
+
+        let market_state: MarketStatus = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, MARKET_STATE_KEY))
+            .ok_or(MarketError::NotInitialized)?;
+        if market_state != MarketStatus::Open {
+            return Err(MarketError::InvalidMarketState);
+        }
+
+        let closing_time: u64 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, CLOSING_TIME_KEY))
+            .ok_or(MarketError::NotInitialized)?;
+        let current_time = env.ledger().timestamp();
+        if current_time >= closing_time {
+            return Err(MarketError::MarketClosed);
+        }
+
+        if Self::is_paused_by_operator(&env) {
+            return Err(MarketError::MarketPaused);
+        }
+        Self::check_verified(&env, &user)?;
+
+        if amount <= 0 {
+            return Err(MarketError::InvalidAmount);
+        }
+        if outcome > 1 {
+            return Err(MarketError::InvalidOutcome);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&Self::get_commit_key(&env, &user))
+        {
+            return Err(MarketError::DuplicateCommit);
+        }
+
+        let prediction_key = Self::get_prediction_key(&env, &user);
+        let existing: Option<UserPrediction> = env.storage().persistent().get(&prediction_key);
+        let prediction = match existing {
+            Some(mut pred) => {
+                if pred.outcome != outcome {
+                    return Err(MarketError::ConflictingOutcome);
+                }
+                pred.amount = safe_add_i128(pred.amount, amount);
+                pred.timestamp = current_time;
+                pred
+            }
+            None => {
+                // First position for this address, unless it held one before
+                Self::track_participant(&env, &user);
+                UserPrediction {
+                    user: user.clone(),
+                    outcome,
+                    amount,
+                    claimed: false,
+                    timestamp: current_time,
+                }
+            }
+        };
+        Self::check_bet_limits(&env, amount, prediction.amount)?;
+
+        let usdc_token: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, USDC_KEY))
+            .ok_or(MarketError::NotInitialized)?;
+        let market_id: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, MARKET_ID_KEY))
+            .ok_or(MarketError::NotInitialized)?;
+
+        let token_client = token::TokenClient::new(&env, &usdc_token);
+        let contract_address = env.current_contract_address();
+        token_client.transfer(&user, &contract_address, &amount);
+        Self::apply_anti_sniping(&env, &market_id, amount, current_time, closing_time);
+
+        env.storage().persistent().set(&prediction_key, &prediction);
+        Self::add_to_pool(&env, outcome, amount);
+        Self::record_bet(&env, &user, outcome, amount, current_time);
+
+        Self::publish_position_changed(&env, &market_id, &user, POSITION_OP_BET, outcome, amount);
+        events::market::prediction_recorded(&env, market_id, user, outcome, amount, current_time);
+
+        Ok(())
+    }
+
+    /// Helper: Fold a bet into the user's rolling history hash
+    ///
+    /// hash' = sha256(hash || outcome || amount || timestamp), starting from 32 zero bytes.
+    fn record_bet(env: &Env, user: &Address, outcome: u32, amount: i128, timestamp: u64) {
+        let key = (Symbol::new(env, BET_HASH_PREFIX), user.clone());
+        let previous: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| BytesN::from_array(env, &[0u8; 32]));
+        let record = BetRecord {
+            outcome,
+            amount,
+            timestamp,
+        };
+        env.storage()
+            .persistent()
+            .set(&key, &Self::chain_bet_hash(env, &previous, &record));
+    }
+
+    /// Helper: One step of the rolling bet hash
+    fn chain_bet_hash(env: &Env, previous: &BytesN<32>, record: &BetRecord) -> BytesN<32> {
+        let mut preimage = soroban_sdk::Bytes::new(env);
+        preimage.extend_from_array(&previous.to_array());
+        preimage.extend_from_array(&record.outcome.to_be_bytes());
+        preimage.extend_from_array(&record.amount.to_be_bytes());
+        preimage.extend_from_array(&record.timestamp.to_be_bytes());
+        BytesN::from_array(env, &env.crypto().sha256(&preimage).to_array())
+    }
+
+    /// Get the rolling hash of a user's bet history (None if the user never bet)
+    pub fn get_bet_hash(env: Env, user: Address) -> Option<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, BET_HASH_PREFIX), user))
+    }
+
+    /// Recovery tool: rebuild a corrupted position from the user's bet history
+    ///
+    /// The supplied history must fold to the rolling hash stored at bet time;
+    /// the position amount and side are then recomputed from it, keeping the
+    /// claimed flag. Factory only: the factory admin runs it through
+    /// `rebuild_market_position`. Emits PositionRebuilt with old/new amounts.
+    pub fn rebuild_position(env: Env, user: Address, bet_history: Vec<BetRecord>) {
+        Self::require_factory(&env);
+
+        let stored_hash = Self::get_bet_hash(env.clone(), user.clone()).expect("No bet history");
+
+        let mut hash = BytesN::from_array(&env, &[0u8; 32]);
+        let mut amount = 0i128;
+        let mut outcome: Option<u32> = None;
+        let mut last_timestamp = 0u64;
+        for record in bet_history.iter() {
+            hash = Self::chain_bet_hash(&env, &hash, &record);
+            if outcome.is_some() && outcome != Some(record.outcome) {
+                panic!("Inconsistent outcome in history");
+            }
+            outcome = Some(record.outcome);
+            amount = safe_add_i128(amount, record.amount);
+            last_timestamp = record.timestamp;
+        }
+
+        if hash != stored_hash {
+            panic!("History does not match stored hash");
+        }
+
+        let prediction_key = Self::get_prediction_key(&env, &user);
+        let existing: Option<UserPrediction> = env.storage().persistent().get(&prediction_key);
+        let (old_amount, claimed) = match &existing {
+            Some(pred) => (pred.amount, pred.claimed),
+            None => (0, false),
+        };
+
+        let prediction = UserPrediction {
+            user: user.clone(),
+            outcome: outcome.expect("Empty history"),
+            amount,
+            claimed,
+            timestamp: last_timestamp,
+        };
+        env.storage().persistent().set(&prediction_key, &prediction);
+
+        let market_id: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, MARKET_ID_KEY))
+            .expect("Market not initialized");
+        Self::publish_position_changed(
+            &env,
+            &market_id,
+            &user,
+            POSITION_OP_REBUILD,
+            prediction.outcome,
+            amount - old_amount,
+        );
+        events::market::position_rebuilt(
+            &env,
+            market_id,
+            user,
+            old_amount,
+            amount,
+            bet_history.len(),
+        );
+    }
+
+    /// Helper: Bump the user's op sequence and publish their post-operation position and pools
+    fn publish_position_changed(
+        env: &Env,
+        market_id: &BytesN<32>,
+        user: &Address,
+        op: u32,
+        outcome: u32,
+        delta: i128,
+    ) {
+        let seq_key = (Symbol::new(env, POSITION_SEQ_PREFIX), user.clone());
+        let seq: u64 = env.storage().persistent().get(&seq_key).unwrap_or(0) + 1;
+        env.storage().persistent().set(&seq_key, &seq);
+
+        events::market::position_changed(
+            env,
+            market_id.clone(),
+            user.clone(),
+            seq,
+            op,
+            outcome,
+            delta,
+            Self::get_position(env.clone(), user.clone()),
+            Self::get_pools(env.clone()),
+        );
+    }
+
+    /// Get the number of position mutations recorded for `user` (0 if none)
+    pub fn get_position_seq(env: Env, user: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, POSITION_SEQ_PREFIX), user))
+            .unwrap_or(0)
+    }
+
+    /// Let `operator` (e.g. the AMM or an order book) move `owner`'s positions
+    ///
+    /// Owner auth required; `approved = false` revokes.
+    pub fn approve_operator(env: Env, owner: Address, operator: Address, approved: bool) {
+        owner.require_auth();
+        let key = (
+            Symbol::new(&env, OPERATOR_PREFIX),
+            owner.clone(),
+            operator.clone(),
+        );
+        if approved {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        events::market::operator_approval(&env, owner, operator, approved);
+    }
+
+    /// Whether `operator` may transfer `owner`'s positions
+    pub fn is_operator(env: Env, owner: Address, operator: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&(Symbol::new(&env, OPERATOR_PREFIX), owner, operator))
+    }
+
+    /// Move `amount` of `from`'s stake on `outcome` to `to`
+    ///
+    /// Pools are unchanged; only ownership of the stake moves. Allowed only while
+    /// the market is Open or Locked, and never for a claimed position, so a
+    /// paid-out stake cannot be claimed again from another address (e.g. across
+    /// a dispute flag that sends the market back to Resolved). The recipient must pass KYC
+    /// and may not hold the other side or have a pending commitment; the
+    /// per-user cap applies to the recipient's new position.
+    ///
+    /// # Errors
+    /// - `NotInitialized` - Market not initialized
+    /// - `InvalidMarketState` - Market is not Open or Locked
+    /// - `InvalidAmount` - Amount must be positive, and `from != to`
+    /// - `InvalidOutcome` - Outcome is not 0 or 1
+    /// - `NoPrediction` - Sender holds no position
+    /// - `AlreadyClaimed` - Sender's position was already paid out
+    /// - `InsufficientPosition` - Amount exceeds the sender's stake on `outcome`
+    /// - `NotVerified` - Recipient fails the market's KYC check
+    /// - `DuplicateCommit` - Recipient has an unrevealed commitment
+    /// - `ConflictingOutcome` - Recipient already holds the other side
+    /// - `PositionLimitExceeded` - Recipient would exceed `max_position_per_user`
+    pub fn transfer_position(
+        env: Env,
+        from: Address,
+        to: Address,
+        outcome: u32,
+        amount: i128,
+    ) -> Result<(), MarketError> {
+        from.require_auth();
+        Self::move_position(&env, &from, &to, outcome, amount)
+    }
+
+    /// Operator variant of transfer_position: `operator` moves `from`'s stake
+    ///
+    /// # Errors
+    /// - `NotOperator` - `from` has not approved `operator`
+    /// - Otherwise as transfer_position
+    pub fn transfer_position_from(
+        env: Env,
+        operator: Address,
+        from: Address,
+        to: Address,
+        outcome: u32,
+        amount: i128,
+    ) -> Result<(), MarketError> {
+        operator.require_auth();
+        if operator != from && !Self::is_operator(env.clone(), from.clone(), operator) {
+            return Err(MarketError::NotOperator);
+        }
+        Self::move_position(&env, &from, &to, outcome, amount)
+    }
+
+    /// Helper: Shared body of transfer_position / transfer_position_from (auth already checked)
+    fn move_position(
+        env: &Env,
+        from: &Address,
+        to: &Address,
+        outcome: u32,
+        amount: i128,
+    ) -> Result<(), MarketError> {
+        let market_state: MarketStatus = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, MARKET_STATE_KEY))
+            .ok_or(MarketError::NotInitialized)?;
+        if !matches!(market_state, MarketStatus::Open | MarketStatus::Locked) {
+            return Err(MarketError::InvalidMarketState);
+        }
+        if amount <= 0 || from == to {
+            return Err(MarketError::InvalidAmount);
+        }
+        if outcome > 1 {
+            return Err(MarketError::InvalidOutcome);
+        }
+
+        let from_key = Self::get_prediction_key(env, from);
+        let mut sender: UserPrediction = env
+            .storage()
+            .persistent()
+            .get(&from_key)
+            .ok_or(MarketError::NoPrediction)?;
+        if sender.claimed {
+            return Err(MarketError::AlreadyClaimed);
+        }
+        if sender.outcome != outcome || sender.amount < amount {
+            return Err(MarketError::InsufficientPosition);
+        }
+
+        Self::check_verified(env, to)?;
+        if env
+            .storage()
+            .persistent()
+            .has(&Self::get_commit_key(env, to))
+        {
+            return Err(MarketError::DuplicateCommit);
+        }
+        let current_time = env.ledger().timestamp();
+        let to_key = Self::get_prediction_key(env, to);
+        let existing: Option<UserPrediction> = env.storage().persistent().get(&to_key);
+        let recipient = match existing {
+            Some(mut pred) => {
+                if pred.outcome != outcome {
+                    return Err(MarketError::ConflictingOutcome);
+                }
+                pred.amount = safe_add_i128(pred.amount, amount);
+                pred.timestamp = current_time;
+                pred
+            }
+            None => {
+                Self::track_participant(env, to);
+                UserPrediction {
+                    user: to.clone(),
+                    outcome,
+                    amount,
+                    claimed: false,
+                    timestamp: current_time,
+                }
+            }
+        };
+        let max_position = Self::get_max_position_per_user(env.clone());
+        if max_position > 0 && recipient.amount > max_position {
+            return Err(MarketError::PositionLimitExceeded);
+        }
+
+        sender.amount -= amount;
+        if sender.amount == 0 {
+            env.storage().persistent().remove(&from_key);
+        } else {
+            env.storage().persistent().set(&from_key, &sender);
+        }
+        env.storage().persistent().set(&to_key, &recipient);
+        Self::record_bet(env, from, outcome, -amount, current_time);
+        Self::record_bet(env, to, outcome, amount, current_time);
+
+        let market_id: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, MARKET_ID_KEY))
+            .ok_or(MarketError::NotInitialized)?;
+        Self::publish_position_changed(
+            env,
+            &market_id,
+            from,
+            POSITION_OP_TRANSFER,
+            outcome,
+            -amount,
+        );
+        Self::publish_position_changed(env, &market_id, to, POSITION_OP_TRANSFER, outcome, amount);
+        events::market::position_transferred(
+            env,
+            market_id,
+            from.clone(),
+            to.clone(),
+            outcome,
+            amount,
+        );
+        Ok(())
+    }
+
+    /// Helper: Add a stake to the YES/NO pool and total volume
+    fn add_to_pool(env: &Env, outcome: u32, amount: i128) {
+        let pool_key = if outcome == 1 {
+            Symbol::new(env, YES_POOL_KEY)
+        } else {
+            Symbol::new(env, NO_POOL_KEY)
+        };
+        let pool: i128 = env.storage().persistent().get(&pool_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&pool_key, &safe_add_i128(pool, amount));
+
+        let total_volume: i128 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, TOTAL_VOLUME_KEY))
+            .unwrap_or(0);
+        env.storage().persistent().set(
+            &Symbol::new(env, TOTAL_VOLUME_KEY),
+            &safe_add_i128(total_volume, amount),
+        );
+    }
+
+    /// Helper: Generate storage key for user commitment
+    fn get_commit_key(env: &Env, user: &Address) -> (Symbol, Address) {
+        (Symbol::new(env, COMMIT_PREFIX), user.clone())
+    }
+
+    /// Helper: Generate storage key for user prediction
+    fn get_prediction_key(env: &Env, user: &Address) -> (Symbol, Address) {
+        (Symbol::new(env, PREDICTION_PREFIX), user.clone())
+    }
+
+    /// Helper: Add `user` to the participant lists the first time it holds a position
+    ///
+    /// Membership is tracked per address, not inferred from the prediction
+    /// record: a sender that transfers out fully loses its record but stays listed.
+    fn track_participant(env: &Env, user: &Address) {
+        let seen_key = (Symbol::new(env, PARTICIPANT_PREFIX), user.clone());
+        if env.storage().persistent().has(&seen_key) {
+            return;
+        }
+        env.storage().persistent().set(&seen_key, &true);
+        for key in [PARTICIPANTS_KEY, REVEALED_PARTICIPANTS_KEY] {
+            let mut list: Vec<Address> = env
+                .storage()
+                .persistent()
+                .get(&Symbol::new(env, key))
+                .unwrap_or_else(|| Vec::new(env));
+            list.push_back(user.clone());
+            env.storage()
+                .persistent()
+                .set(&Symbol::new(env, key), &list);
+        }
+    }
+
+    /// Helper: Storage key for refunded flag (prevents double-refund)
+    fn get_refunded_key(env: &Env, user: &Address) -> (Symbol, Address) {
+        (Symbol::new(env, REFUNDED_PREFIX), user.clone())
+    }
+
+    /// Helper: Get user commitment (for testing and reveal phase)
+    pub fn get_commitment(env: Env, user: Address) -> Option<Commitment> {
+        let commit_key = Self::get_commit_key(&env, &user);
+        env.storage().persistent().get(&commit_key)
+    }
+
+    /// Helper: Get pending commit count
+    pub fn get_pending_count(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, PENDING_COUNT_KEY))
+            .unwrap_or(0)
+    }
+
+    /// Helper: Get market state
+    pub fn get_market_state_value(env: Env) -> Option<u32> {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, MARKET_STATE_KEY))
+    }
+
+    /// Winning outcome (0=NO, 1=YES), None until the market is Resolved (or Swept)
+    pub fn get_winning_outcome(env: Env) -> Option<u32> {
+        if !matches!(
+            Self::get_state(env.clone()),
+            MarketStatus::Resolved | MarketStatus::Swept
+        ) {
+            return None;
+        }
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, WINNING_OUTCOME_KEY))
+    }
+
+    /// Phase 2: User reveals their committed prediction
+    ///
+    /// Verifies the commitment hash matches hash(user + market_id + outcome + salt),
+    /// transitions prediction from COMMITTED → REVEALED, updates pools,
     /// and emits a PredictionRevealed event.
     ///
     /// # Errors
@@ -483,13 +2014,13 @@ impl PredictionMarket {
         user.require_auth();
 
         // 2. Validate market is initialized and in OPEN state
-        let market_state: u32 = env
+        let market_state: MarketStatus = env
             .storage()
             .persistent()
             .get(&Symbol::new(&env, MARKET_STATE_KEY))
             .ok_or(MarketError::NotInitialized)?;
 
-        if market_state != STATE_OPEN {
+        if market_state != MarketStatus::Open {
             return Err(MarketError::InvalidMarketState);
         }
 
@@ -561,39 +2092,9 @@ impl PredictionMarket {
             .persistent()
             .set(&Symbol::new(&env, REVEALED_PARTICIPANTS_KEY), &revealed);
 
-        // 10. Update prediction pools
-        if outcome == 1 {
-            // YES outcome
-            let yes_pool: i128 = env
-                .storage()
-                .persistent()
-                .get(&Symbol::new(&env, YES_POOL_KEY))
-                .unwrap_or(0);
-            env.storage()
-                .persistent()
-                .set(&Symbol::new(&env, YES_POOL_KEY), &(yes_pool + amount));
-        } else {
-            // NO outcome
-            let no_pool: i128 = env
-                .storage()
-                .persistent()
-                .get(&Symbol::new(&env, NO_POOL_KEY))
-                .unwrap_or(0);
-            env.storage()
-                .persistent()
-                .set(&Symbol::new(&env, NO_POOL_KEY), &(no_pool + amount));
-        }
-
-        // 11. Update total volume
-        let total_volume: i128 = env
-            .storage()
-            .persistent()
-            .get(&Symbol::new(&env, TOTAL_VOLUME_KEY))
-            .unwrap_or(0);
-        env.storage().persistent().set(
-            &Symbol::new(&env, TOTAL_VOLUME_KEY),
-            &(total_volume + amount),
-        );
+        // 10-11. Update prediction pools and total volume
+        Self::add_to_pool(&env, outcome, amount);
+        Self::record_bet(&env, &user, outcome, amount, current_time);
 
         // 12. Decrement pending count
         let pending_count: u32 = env
@@ -614,14 +2115,8 @@ impl PredictionMarket {
         env.storage().persistent().remove(&commit_key);
 
         // 14. Emit PredictionRevealed event with anonymized data
-        PredictionRevealedEvent {
-            user,
-            market_id,
-            outcome,
-            amount,
-            timestamp: current_time,
-        }
-        .publish(&env);
+        Self::publish_position_changed(&env, &market_id, &user, POSITION_OP_BET, outcome, amount);
+        events::market::prediction_recorded(&env, market_id, user, outcome, amount, current_time);
 
         Ok(())
     }
@@ -644,21 +2139,19 @@ impl PredictionMarket {
         }
 
         // Load current state
-        let current_state: u32 = env
+        let current_state: MarketStatus = env
             .storage()
             .persistent()
             .get(&Symbol::new(&env, MARKET_STATE_KEY))
             .expect("Market state not found");
 
         // Validate market state is OPEN
-        if current_state != STATE_OPEN {
+        if current_state != MarketStatus::Open {
             panic!("Market not in OPEN state");
         }
 
         // Change market state to CLOSED
-        env.storage()
-            .persistent()
-            .set(&Symbol::new(&env, MARKET_STATE_KEY), &STATE_CLOSED);
+        Self::transition(&env, MarketStatus::Locked);
 
         // Emit MarketClosed Event
         MarketClosedEvent {
@@ -696,18 +2189,18 @@ impl PredictionMarket {
         }
 
         // Load current market state
-        let current_state: u32 = env
+        let current_state: MarketStatus = env
             .storage()
             .persistent()
             .get(&Symbol::new(&env, MARKET_STATE_KEY))
             .expect("Market state not found");
 
         // Validate: market state is CLOSED (not OPEN or already RESOLVED)
-        if current_state == STATE_OPEN {
+        if current_state == MarketStatus::Open {
             panic!("Cannot resolve market that is still OPEN");
         }
 
-        if current_state == STATE_RESOLVED {
+        if current_state == MarketStatus::Resolved {
             panic!("Market already resolved");
         }
 
@@ -727,31 +2220,67 @@ impl PredictionMarket {
         //     panic!("Oracle consensus not reached");
         // }
 
-        // TEMPORARY: Simulate oracle consensus for testing (outcome = 1 for YES)
-        let _consensus_reached = true;
-        let final_outcome = 1u32;
+        // TEMPORARY: Simulate oracle consensus for testing (outcome = 1 for YES)
+        let _consensus_reached = true;
+        let final_outcome = 1u32;
+
+        // Validate outcome is binary (0 or 1)
+        if final_outcome > 1 {
+            panic!("Invalid oracle outcome");
+        }
+
+        Self::settle_outcome(&env, market_id, final_outcome, current_time);
+    }
+
+    /// Resolve with an explicit outcome delivered by the oracle contract
+    ///
+    /// Only the configured oracle may call this (its consensus or emergency
+    /// override path). YES/NO settle like `resolve_market`; `Invalid` moves the
+    /// market to Refunding, where every participant recovers their principal
+    /// through `claim_refund` and `claim_winnings` is blocked.
+    pub fn resolve_with_outcome(env: Env, market_id: BytesN<32>, outcome: ResolutionOutcome) {
+        let oracle: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, ORACLE_KEY))
+            .expect("Oracle address not found");
+        oracle.require_auth();
+
+        let current_time = env.ledger().timestamp();
+        if current_time < Self::get_resolution_time(env.clone()) {
+            panic!("Cannot resolve market before resolution time");
+        }
 
-        // Validate outcome is binary (0 or 1)
-        if final_outcome > 1 {
-            panic!("Invalid oracle outcome");
+        match outcome {
+            ResolutionOutcome::Invalid => {
+                Self::transition(&env, MarketStatus::Refunding);
+                events::market::market_voided(&env, market_id.clone(), current_time);
+                Self::notify_resolution(&env, &market_id, ResolutionOutcome::Invalid as u32);
+            }
+            ResolutionOutcome::No | ResolutionOutcome::Yes => {
+                Self::settle_outcome(&env, market_id, outcome as u32, current_time);
+            }
         }
+    }
 
+    /// Helper: Record a binary winning outcome and move the market to Resolved
+    fn settle_outcome(env: &Env, market_id: BytesN<32>, final_outcome: u32, timestamp: u64) {
         // Store winning outcome
         env.storage()
             .persistent()
-            .set(&Symbol::new(&env, WINNING_OUTCOME_KEY), &final_outcome);
+            .set(&Symbol::new(env, WINNING_OUTCOME_KEY), &final_outcome);
 
         // Load pool sizes
         let yes_pool: i128 = env
             .storage()
             .persistent()
-            .get(&Symbol::new(&env, YES_POOL_KEY))
+            .get(&Symbol::new(env, YES_POOL_KEY))
             .unwrap_or(0);
 
         let no_pool: i128 = env
             .storage()
             .persistent()
-            .get(&Symbol::new(&env, NO_POOL_KEY))
+            .get(&Symbol::new(env, NO_POOL_KEY))
             .unwrap_or(0);
 
         // Calculate winner and loser shares
@@ -766,31 +2295,332 @@ impl PredictionMarket {
         // Store winner and loser shares for payout calculations
         env.storage()
             .persistent()
-            .set(&Symbol::new(&env, WINNER_SHARES_KEY), &winner_shares);
+            .set(&Symbol::new(env, WINNER_SHARES_KEY), &winner_shares);
 
         env.storage()
             .persistent()
-            .set(&Symbol::new(&env, LOSER_SHARES_KEY), &loser_shares);
+            .set(&Symbol::new(env, LOSER_SHARES_KEY), &loser_shares);
 
-        // Update market state to RESOLVED
+        // Update market state to RESOLVED; the claim window runs from here
+        Self::transition(env, MarketStatus::Resolved);
         env.storage()
             .persistent()
-            .set(&Symbol::new(&env, MARKET_STATE_KEY), &STATE_RESOLVED);
+            .set(&Symbol::new(env, RESOLVED_AT_KEY), &timestamp);
 
         // Emit MarketResolved event
-        MarketResolvedEvent {
-            market_id,
-            final_outcome,
-            timestamp: current_time,
+        events::market::market_resolved(env, market_id.clone(), final_outcome, timestamp);
+
+        if Self::is_dispute_flagged(env.clone()) {
+            Self::enter_oracle_dispute(env);
         }
-        .publish(&env);
+        Self::notify_resolution(env, &market_id, final_outcome);
+    }
+
+    /// Set the dispute window after resolution (creator only, before the first prediction)
+    pub fn set_dispute_window(env: Env, creator: Address, dispute_window: u64) {
+        Self::require_creator(&env, &creator);
+
+        if Self::has_predictions(&env) {
+            panic!("Dispute window locked after first prediction");
+        }
+        if dispute_window == 0 {
+            panic!("Dispute window must be positive");
+        }
+
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, DISPUTE_WINDOW_KEY), &dispute_window);
+    }
+
+    /// Get the dispute window after resolution (seconds)
+    pub fn get_dispute_window(env: Env) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, DISPUTE_WINDOW_KEY))
+            .unwrap_or(DEFAULT_MARKET_DISPUTE_WINDOW)
+    }
+
+    /// Set how long after resolution_time the oracle has before escalation
+    /// (creator only, before the first prediction)
+    pub fn set_oracle_timeout(env: Env, creator: Address, timeout: u64) {
+        Self::require_creator(&env, &creator);
+
+        if Self::has_predictions(&env) {
+            panic!("Oracle timeout locked after first prediction");
+        }
+        if timeout == 0 {
+            panic!("Oracle timeout must be positive");
+        }
+
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, ORACLE_TIMEOUT_KEY), &timeout);
+    }
+
+    /// Get the timestamp from which `escalate_unresolved` is allowed
+    ///
+    /// Follows resolution_time when it is extended.
+    pub fn get_oracle_timeout(env: Env) -> u64 {
+        let timeout: u64 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, ORACLE_TIMEOUT_KEY))
+            .unwrap_or(DEFAULT_ORACLE_TIMEOUT);
+        Self::get_resolution_time(env).saturating_add(timeout)
+    }
+
+    /// Set how long after resolution winners have to claim
+    /// (creator only, before the first prediction, at least `MIN_CLAIM_WINDOW`)
+    pub fn set_claim_window(env: Env, creator: Address, window: u64) {
+        Self::require_creator(&env, &creator);
+
+        if Self::has_predictions(&env) {
+            panic!("Claim window locked after first prediction");
+        }
+        if window < MIN_CLAIM_WINDOW {
+            panic!("Claim window below minimum");
+        }
+
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, CLAIM_WINDOW_KEY), &window);
+    }
+
+    /// Get the timestamp from which `sweep_unclaimed` is allowed
+    ///
+    /// Counted from when the market actually resolved, restarted when an
+    /// oracle dispute flag clears. Before resolution it is projected from
+    /// resolution_time (and follows it when extended).
+    pub fn get_claim_deadline(env: Env) -> u64 {
+        let window: u64 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, CLAIM_WINDOW_KEY))
+            .unwrap_or(DEFAULT_CLAIM_WINDOW);
+        let resolved_at: u64 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, RESOLVED_AT_KEY))
+            .unwrap_or_else(|| Self::get_resolution_time(env.clone()));
+        resolved_at.saturating_add(window)
+    }
+
+    /// Send whatever winners left unclaimed to the treasury (permissionless)
+    ///
+    /// Allowed on a Resolved market from the claim deadline on. Moves the whole
+    /// token balance except unused guarantee escrow, which stays for
+    /// `release_guarantee`, and leaves the market in the terminal Swept state.
+    /// Later claims fail with `UnclaimedSwept`; the swept funds sit in the
+    /// treasury's unaccounted balance, from which its signers can return them.
+    ///
+    /// # Errors
+    /// - `MarketNotResolved` - Market is not Resolved (including Disputed)
+    /// - `ClaimDeadlineNotReached` - Current time < claim deadline
+    pub fn sweep_unclaimed(env: Env, caller: Address) -> Result<i128, MarketError> {
+        caller.require_auth();
+        let mut guard = guard::enter(&env);
+
+        if Self::get_state(env.clone()) != MarketStatus::Resolved {
+            return Err(MarketError::MarketNotResolved);
+        }
+        if !time::has_reached(&env, Self::get_claim_deadline(env.clone())) {
+            return Err(MarketError::ClaimDeadlineNotReached);
+        }
+
+        let usdc: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, USDC_KEY))
+            .ok_or(MarketError::NotInitialized)?;
+        let factory: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, FACTORY_KEY))
+            .ok_or(MarketError::NotInitialized)?;
+        let market_id: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, MARKET_ID_KEY))
+            .ok_or(MarketError::NotInitialized)?;
+        let treasury: Address =
+            env.invoke_contract(&factory, &Symbol::new(&env, "get_treasury"), Vec::new(&env));
+
+        let contract_address = env.current_contract_address();
+        let balance = token::TokenClient::new(&env, &usdc).balance(&contract_address);
+        let amount = (balance - Self::get_guarantee_escrow(env.clone())).max(0);
+
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, SWEPT_AMOUNT_KEY), &amount);
+        Self::transition(&env, MarketStatus::Swept);
+        if amount > 0 {
+            guard.transfer(&usdc, &contract_address, &treasury, amount);
+        }
+        guard.commit();
+
+        events::market::unclaimed_swept(&env, market_id, amount);
+        Ok(amount)
+    }
+
+    /// Amount sent to the treasury by `sweep_unclaimed` (0 until swept)
+    pub fn get_swept_amount(env: Env) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, SWEPT_AMOUNT_KEY))
+            .unwrap_or(0)
+    }
+
+    /// Set or clear the contract pinged when this market resolves (creator only)
+    ///
+    /// On resolution or voiding the market calls
+    /// `on_market_resolved(market_id, outcome)` on the target, with outcome
+    /// 0 = NO, 1 = YES, 2 = voided. Can be changed until the market settles.
+    pub fn set_notification_target(env: Env, creator: Address, target: Option<Address>) {
+        Self::require_creator(&env, &creator);
+
+        match Self::get_state(env.clone()) {
+            MarketStatus::Open | MarketStatus::Locked => {}
+            _ => panic!("Market already settled"),
+        }
+
+        let key = Symbol::new(&env, NOTIFY_TARGET_KEY);
+        match target {
+            Some(target) => env.storage().persistent().set(&key, &target),
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    /// Get the contract pinged on resolution, if any
+    pub fn get_notification_target(env: Env) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, NOTIFY_TARGET_KEY))
+    }
+
+    /// Get whether the resolution notification was delivered
+    ///
+    /// None until the market settles with a target registered.
+    pub fn get_notification_status(env: Env) -> Option<NotificationStatus> {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, NOTIFY_STATUS_KEY))
+    }
+
+    /// Helper: Best-effort ping of the notification target; never blocks resolution
+    fn notify_resolution(env: &Env, market_id: &BytesN<32>, outcome: u32) {
+        let Some(target) = Self::get_notification_target(env.clone()) else {
+            return;
+        };
+
+        let delivered = env
+            .try_invoke_contract::<(), soroban_sdk::Error>(
+                &target,
+                &Symbol::new(env, "on_market_resolved"),
+                (market_id.clone(), outcome).into_val(env),
+            )
+            .is_ok_and(|result| result.is_ok());
+        let status = if delivered {
+            NotificationStatus::Delivered
+        } else {
+            NotificationStatus::Failed
+        };
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(env, NOTIFY_STATUS_KEY), &status);
+
+        events::market::resolution_notified(env, market_id.clone(), target, outcome, delivered);
+    }
+
+    /// Void a market the oracle never resolved (anyone, after the oracle timeout)
+    ///
+    /// Cross-checks the oracle first: if it has reached consensus the market is
+    /// only waiting on finalization and escalation is refused. Otherwise the
+    /// market moves to Refunding, so every participant recovers their principal
+    /// through `claim_refund`, and the incident is reported to the factory.
+    /// A later resolution attempt from the oracle fails on the terminal state.
+    pub fn escalate_unresolved(env: Env, caller: Address) {
+        caller.require_auth();
+
+        if Self::get_state(env.clone()) != MarketStatus::Locked {
+            panic!("Market not awaiting resolution");
+        }
+        let oracle_timeout = Self::get_oracle_timeout(env.clone());
+        if !time::has_reached(&env, oracle_timeout) {
+            panic!("Oracle timeout not reached");
+        }
+
+        let market_id: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, MARKET_ID_KEY))
+            .expect("Market not initialized");
+        let oracle: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, ORACLE_KEY))
+            .expect("Oracle address not found");
+
+        // An oracle that cannot answer counts as stuck
+        let (consensus_reached, _) = env
+            .try_invoke_contract::<(bool, u32), soroban_sdk::Error>(
+                &oracle,
+                &Symbol::new(&env, "check_consensus"),
+                (market_id.clone(),).into_val(&env),
+            )
+            .ok()
+            .and_then(|result| result.ok())
+            .unwrap_or((false, 0));
+        if consensus_reached {
+            panic!("Oracle has reached consensus");
+        }
+
+        Self::transition(&env, MarketStatus::Refunding);
+
+        let timestamp = env.ledger().timestamp();
+        events::market::oracle_timeout_escalated(
+            &env,
+            market_id.clone(),
+            oracle,
+            oracle_timeout,
+            caller,
+            timestamp,
+        );
+        events::market::market_voided(&env, market_id.clone(), timestamp);
+        Self::notify_resolution(&env, &market_id, ResolutionOutcome::Invalid as u32);
+
+        // Best effort: markets outside the factory have no incident log
+        if let Some(factory) = env
+            .storage()
+            .persistent()
+            .get::<_, Address>(&Symbol::new(&env, FACTORY_KEY))
+        {
+            let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+                &factory,
+                &Symbol::new(&env, "report_incident"),
+                (market_id, Symbol::new(&env, "oracle_timeout")).into_val(&env),
+            );
+        }
+    }
+
+    /// Factory: Replace this market's code with `new_wasm_hash`
+    ///
+    /// Storage is kept as-is, so the new code must read the same layout.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        let factory: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, FACTORY_KEY))
+            .expect("Market not initialized");
+        factory.require_auth();
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
     }
 
-    /// Dispute market resolution within 7-day window
+    /// Dispute market resolution within the dispute window (7 days by default)
     ///
     /// - Require user authentication
     /// - Validate market state is RESOLVED
-    /// - Validate current timestamp < resolution_time + 7 days
+    /// - Validate current timestamp < resolution_time + dispute window
     /// - Require minimum stake (1000 tokens)
     /// - Store dispute record: { user, reason, evidence, timestamp }
     /// - Change market state to DISPUTED
@@ -805,13 +2635,13 @@ impl PredictionMarket {
     ) {
         user.require_auth();
 
-        let state: u32 = env
+        let state: MarketStatus = env
             .storage()
             .persistent()
             .get(&Symbol::new(&env, MARKET_STATE_KEY))
             .expect("Market not initialized");
 
-        if state != STATE_RESOLVED {
+        if state != MarketStatus::Resolved {
             panic!("Market not resolved");
         }
 
@@ -822,8 +2652,7 @@ impl PredictionMarket {
             .expect("Resolution time not found");
 
         let current_time = env.ledger().timestamp();
-        // 7 days = 604800 seconds
-        if current_time >= resolution_time + 604800 {
+        if current_time >= resolution_time + Self::get_dispute_window(env.clone()) {
             panic!("Dispute window has closed");
         }
 
@@ -841,9 +2670,7 @@ impl PredictionMarket {
         token_client.transfer(&user, &contract_address, &dispute_stake_amount);
 
         // Transition market status to DISPUTED
-        env.storage()
-            .persistent()
-            .set(&Symbol::new(&env, MARKET_STATE_KEY), &STATE_DISPUTED);
+        Self::transition(&env, MarketStatus::Disputed);
 
         // Store dispute record
         let dispute = DisputeRecord {
@@ -876,7 +2703,10 @@ impl PredictionMarket {
     ///
     /// # Payout Calculation
     /// - Payout = (user_amount / winner_shares) * total_pool
-    /// - 10% protocol fee is deducted from the gross payout
+    /// - On a capped-loss market (`set_loss_cap`) the losers' refunds are set
+    ///   aside first, and total_pool counts only the forfeited losing stake
+    /// - Platform fee (`fee_bps`) is deducted from the profit portion only and
+    ///   deposited into the treasury
     ///
     /// # Events
     /// - Emits WinningsClaimed(user, market_id, amount)
@@ -889,126 +2719,274 @@ impl PredictionMarket {
     pub fn claim_winnings(env: Env, user: Address, market_id: BytesN<32>) -> i128 {
         // Require user authentication
         user.require_auth();
+        let mut guard = guard::enter(&env);
+        Self::require_not_swept(&env);
+        if !Self::claims_guaranteed(&env, &user) {
+            panic!("Market not resolved");
+        }
 
-        // 1. Validate market state is RESOLVED
-        let state: u32 = env
+        // 1. Validate and price the claim (shared with simulate_claim)
+        let preview = Self::preview_claim(&env, &user);
+        match preview.status {
+            CLAIM_STATUS_NOT_RESOLVED => panic!("Market not resolved"),
+            CLAIM_STATUS_NO_POSITION => panic!("No prediction found for user"),
+            CLAIM_STATUS_ALREADY_CLAIMED => panic!("Winnings already claimed"),
+            CLAIM_STATUS_LOSING_POSITION | CLAIM_STATUS_LOSS_REFUND => {
+                panic!("User did not predict winning outcome")
+            }
+            CLAIM_STATUS_NO_WINNERS => panic!("No winners to claim"),
+            CLAIM_STATUS_ZERO_PAYOUT => panic!("Payout amount is zero"),
+            CLAIM_STATUS_ACCOUNT_FROZEN => panic_with_error!(env, MarketError::AccountFrozen),
+            _ => {}
+        }
+        let net_payout = preview.net;
+        let fee = preview.protocol_fee;
+        if preview.guarantee_topup > 0 {
+            let escrow = Self::get_guarantee_escrow(env.clone());
+            assert_invariant!(
+                env,
+                preview.guarantee_topup <= escrow,
+                MarketError::InvariantViolation
+            );
+            env.storage().persistent().set(
+                &Symbol::new(&env, GUARANTEE_ESCROW_KEY),
+                &safe_sub_i128(escrow, preview.guarantee_topup),
+            );
+        }
+
+        // 2. Mark as claimed (idempotent - prevents double-claim)
+        let prediction_key = (Symbol::new(&env, PREDICTION_PREFIX), user.clone());
+        let mut prediction: UserPrediction = env
             .storage()
             .persistent()
-            .get(&Symbol::new(&env, MARKET_STATE_KEY))
-            .expect("Market not initialized");
+            .get(&prediction_key)
+            .expect("No prediction found for user");
+        prediction.claimed = true;
+        env.storage().persistent().set(&prediction_key, &prediction);
+
+        // 3. Transfer Payout from market escrow to user
+        let usdc_token: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, USDC_KEY))
+            .expect("USDC token not found");
+        guard.transfer(
+            &usdc_token,
+            &env.current_contract_address(),
+            &user,
+            net_payout,
+        );
+
+        // 4. Route Fee to Treasury (treasury address resolved through the factory)
+        if fee > 0 {
+            Self::deposit_fee_to_treasury(&env, &usdc_token, fee);
+        }
+        guard.commit();
+
+        // 5. Emit WinningsClaimed Event
+        Self::publish_position_changed(
+            &env,
+            &market_id,
+            &user,
+            POSITION_OP_CLAIM,
+            prediction.outcome,
+            0,
+        );
+        events::market::winnings_claimed(&env, market_id.clone(), user, net_payout, fee);
+
+        net_payout
+    }
 
-        if state != STATE_RESOLVED {
+    /// Claim the capped-loss refund on a losing stake
+    ///
+    /// Pays `loss_cap_bps` of the stake once the market is Resolved against
+    /// the user. Cancelled and voided markets refund in full via claim_refund
+    /// instead. Returns the amount paid.
+    pub fn claim_loss_refund(env: Env, user: Address, market_id: BytesN<32>) -> i128 {
+        user.require_auth();
+        let mut guard = guard::enter(&env);
+        Self::require_not_swept(&env);
+        if !Self::claims_guaranteed(&env, &user) {
             panic!("Market not resolved");
         }
 
-        // 2. Get User Prediction
+        let preview = Self::preview_claim(&env, &user);
+        match preview.status {
+            CLAIM_STATUS_LOSS_REFUND => {}
+            CLAIM_STATUS_NOT_RESOLVED => panic!("Market not resolved"),
+            CLAIM_STATUS_NO_POSITION => panic!("No prediction found for user"),
+            CLAIM_STATUS_ALREADY_CLAIMED => panic!("Loss refund already claimed"),
+            CLAIM_STATUS_LOSING_POSITION => panic!("No loss refund on this market"),
+            CLAIM_STATUS_ACCOUNT_FROZEN => panic_with_error!(env, MarketError::AccountFrozen),
+            _ => panic!("User predicted winning outcome"),
+        }
+
         let prediction_key = (Symbol::new(&env, PREDICTION_PREFIX), user.clone());
         let mut prediction: UserPrediction = env
             .storage()
             .persistent()
             .get(&prediction_key)
             .expect("No prediction found for user");
+        prediction.claimed = true;
+        env.storage().persistent().set(&prediction_key, &prediction);
+
+        let usdc_token: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, USDC_KEY))
+            .expect("USDC token not found");
+        guard.transfer(
+            &usdc_token,
+            &env.current_contract_address(),
+            &user,
+            preview.net,
+        );
+        guard.commit();
+
+        Self::publish_position_changed(
+            &env,
+            &market_id,
+            &user,
+            POSITION_OP_REFUND,
+            prediction.outcome,
+            0,
+        );
+        events::market::loss_refund_claimed(
+            &env,
+            market_id,
+            user,
+            preview.net,
+            env.ledger().timestamp(),
+        );
 
-        // 3. Check if already claimed (idempotent - return early if already claimed)
+        preview.net
+    }
+
+    /// Dry-run claim_winnings for `user`: same payout math, no state change or transfer
+    pub fn simulate_claim(env: Env, user: Address) -> ClaimPreview {
+        Self::preview_claim(&env, &user)
+    }
+
+    /// Helper: Payout breakdown shared by claim_winnings and simulate_claim
+    fn preview_claim(env: &Env, user: &Address) -> ClaimPreview {
+        let mut preview = ClaimPreview {
+            status: CLAIM_STATUS_OK,
+            gross: 0,
+            protocol_fee: 0,
+            guarantee_topup: 0,
+            net: 0,
+            rounding_dust: 0,
+        };
+
+        if Self::get_state(env.clone()) != MarketStatus::Resolved {
+            preview.status = CLAIM_STATUS_NOT_RESOLVED;
+            return preview;
+        }
+        if Self::is_account_frozen(env.clone(), user.clone()) {
+            preview.status = CLAIM_STATUS_ACCOUNT_FROZEN;
+            return preview;
+        }
+
+        let prediction: UserPrediction = match env
+            .storage()
+            .persistent()
+            .get(&(Symbol::new(env, PREDICTION_PREFIX), user.clone()))
+        {
+            Some(prediction) => prediction,
+            None => {
+                preview.status = CLAIM_STATUS_NO_POSITION;
+                return preview;
+            }
+        };
         if prediction.claimed {
-            panic!("Winnings already claimed");
+            preview.status = CLAIM_STATUS_ALREADY_CLAIMED;
+            return preview;
         }
 
-        // 4. Validate outcome matches winning outcome
         let winning_outcome: u32 = env
             .storage()
             .persistent()
-            .get(&Symbol::new(&env, WINNING_OUTCOME_KEY))
+            .get(&Symbol::new(env, WINNING_OUTCOME_KEY))
             .expect("Winning outcome not found");
-
         if prediction.outcome != winning_outcome {
-            panic!("User did not predict winning outcome");
+            let refund = Self::loss_refund(env, prediction.amount);
+            if refund == 0 {
+                preview.status = CLAIM_STATUS_LOSING_POSITION;
+            } else {
+                preview.status = CLAIM_STATUS_LOSS_REFUND;
+                preview.gross = refund;
+                preview.net = refund;
+            }
+            return preview;
         }
 
-        // 5. Calculate Payout
-        // Payout = (UserAmount / WinnerPool) * TotalPool
-        // Apply 10% Protocol Fee
         let winner_shares: i128 = env
             .storage()
             .persistent()
-            .get(&Symbol::new(&env, WINNER_SHARES_KEY))
+            .get(&Symbol::new(env, WINNER_SHARES_KEY))
             .expect("Winner shares not found");
-
-        let loser_shares: i128 = env
-            .storage()
-            .persistent()
-            .get(&Symbol::new(&env, LOSER_SHARES_KEY))
-            .unwrap_or(0);
-
-        let total_pool = winner_shares + loser_shares;
-
         if winner_shares == 0 {
-            panic!("No winners to claim");
+            preview.status = CLAIM_STATUS_NO_WINNERS;
+            return preview;
         }
 
-        // Calculate gross payout using integer arithmetic
-        // (amount * total_pool) / winner_shares
-        let gross_payout = prediction
-            .amount
-            .checked_mul(total_pool)
-            .expect("Overflow in payout calculation")
-            .checked_div(winner_shares)
-            .expect("Division by zero in payout calculation");
-
-        // 10% Fee
-        let fee = gross_payout / 10;
-        let net_payout = gross_payout - fee;
-
-        if net_payout == 0 {
-            panic!("Payout amount is zero");
+        // Payout = (UserAmount / WinnerPool) * TotalPool, minus the platform fee on profit
+        let (gross_payout, fee) = Self::calculate_payout(env, prediction.amount);
+        if gross_payout - fee == 0 {
+            preview.status = CLAIM_STATUS_ZERO_PAYOUT;
+            return preview;
         }
 
-        // 6. Transfer Payout from market escrow to user
-        let usdc_token: Address = env
+        let fee_bps: u32 = env
             .storage()
             .persistent()
-            .get(&Symbol::new(&env, USDC_KEY))
-            .expect("USDC token not found");
-
-        let token_client = token::TokenClient::new(&env, &usdc_token);
-        let contract_address = env.current_contract_address();
-
-        token_client.transfer(&contract_address, &user, &net_payout);
-
-        // 7. Route Fee to Treasury
-        // TODO: Cross-contract call to Factory and Treasury - requires those contracts to be deployed
-        // For now, fees are kept in the market contract escrow
-        // Uncomment when Factory and Treasury are deployed
-        // if fee > 0 {
-        //     let factory_address: Address = env
-        //         .storage()
-        //         .persistent()
-        //         .get(&Symbol::new(&env, FACTORY_KEY))
-        //         .expect("Factory address not set");
-        //
-        //     let factory_client = crate::factory::MarketFactoryClient::new(&env, &factory_address);
-        //     let treasury_address = factory_client.get_treasury();
-        //
-        //     let treasury_client = crate::treasury::TreasuryClient::new(&env, &treasury_address);
-        //     treasury_client.deposit_fees(&contract_address, &fee);
-        // }
-
-        // TEMPORARY: Fees remain in market contract until Treasury is deployed
-        // In production, fees would be routed to Treasury contract
-
-        // 8. Mark as claimed (idempotent - prevents double-claim)
-        prediction.claimed = true;
-        env.storage().persistent().set(&prediction_key, &prediction);
+            .get(&Symbol::new(env, FEE_BPS_KEY))
+            .unwrap_or(0);
+        let profit = gross_payout - prediction.amount;
+        if fee > 0 {
+            preview.rounding_dust = fee - math::bps(profit, fee_bps);
+        }
+        preview.gross = gross_payout;
+        preview.protocol_fee = fee;
+        preview.guarantee_topup = Self::guarantee_topup(env, prediction.amount, gross_payout - fee);
+        preview.net = gross_payout - fee + preview.guarantee_topup;
+        preview
+    }
 
-        // 9. Emit WinningsClaimed Event
-        WinningsClaimedEvent {
-            user,
-            market_id: market_id.clone(),
-            net_payout,
+    /// Helper: Escrow top-up owed to a winner with `stake` whose pool payout is `net`
+    ///
+    /// If the escrow cannot cover every winner's shortfall, each winner receives
+    /// the same fraction of theirs, independent of claim order.
+    fn guarantee_topup(env: &Env, stake: i128, net: i128) -> i128 {
+        let guarantee = match Self::get_guarantee(env.clone()) {
+            Some(guarantee) => guarantee,
+            None => return 0,
+        };
+        let shortfall = math::bps(stake, guarantee.multiple_bps) - net;
+        if shortfall <= 0 {
+            return 0;
         }
-        .publish(&env);
 
-        net_payout
+        let winner_shares: i128 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, WINNER_SHARES_KEY))
+            .unwrap_or(0);
+        let forfeited = Self::forfeited_stake(env);
+        let fee_bps: u32 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, FEE_BPS_KEY))
+            .unwrap_or(0);
+        let total_net = winner_shares + forfeited - math::bps(forfeited, fee_bps);
+        let total_shortfall = math::bps(winner_shares, guarantee.multiple_bps) - total_net;
+
+        let topup = if total_shortfall > guarantee.escrow {
+            math::mul_div_floor(shortfall, guarantee.escrow, total_shortfall)
+        } else {
+            shortfall
+        };
+        topup.min(Self::get_guarantee_escrow(env.clone()))
     }
 
     /// Refund users if their prediction failed (optional opt-in)
@@ -1040,11 +3018,11 @@ impl PredictionMarket {
     /// - winning_outcome: Final outcome if resolved (0=NO, 1=YES), None otherwise
     pub fn get_market_state(env: Env, _market_id: BytesN<32>) -> MarketState {
         // Get market status
-        let status: u32 = env
+        let status: MarketStatus = env
             .storage()
             .persistent()
             .get(&Symbol::new(&env, MARKET_STATE_KEY))
-            .unwrap_or(STATE_OPEN);
+            .unwrap_or(MarketStatus::Open);
 
         // Get closing time
         let closing_time: u64 = env
@@ -1076,7 +3054,7 @@ impl PredictionMarket {
             .unwrap_or(0);
 
         // Get winning outcome if market is resolved
-        let winning_outcome: Option<u32> = if status == STATE_RESOLVED {
+        let winning_outcome: Option<u32> = if status == MarketStatus::Resolved {
             env.storage()
                 .persistent()
                 .get(&Symbol::new(&env, WINNING_OUTCOME_KEY))
@@ -1085,11 +3063,12 @@ impl PredictionMarket {
         };
 
         MarketState {
-            status,
+            status: status as u32,
             closing_time,
             total_pool,
             participant_count,
             winning_outcome,
+            pools_revealed: Self::pools_revealed(env.clone()),
         }
     }
 
@@ -1131,6 +3110,430 @@ impl PredictionMarket {
         None
     }
 
+    /// Get a user's position by outcome (no auth)
+    ///
+    /// Unrevealed commitments are not included (their side is private); users who
+    /// never bet, or whose stake was refunded, get a zeroed position.
+    pub fn get_position(env: Env, user: Address) -> Position {
+        match env
+            .storage()
+            .persistent()
+            .get::<_, UserPrediction>(&Self::get_prediction_key(&env, &user))
+        {
+            Some(pred) => Position {
+                yes_amount: if pred.outcome == 1 { pred.amount } else { 0 },
+                no_amount: if pred.outcome == 1 { 0 } else { pred.amount },
+                claimed: pred.claimed,
+            },
+            None => Position {
+                yes_amount: 0,
+                no_amount: 0,
+                claimed: false,
+            },
+        }
+    }
+
+    /// Get YES/NO pool totals and the number of unique participants (no auth)
+    ///
+    /// Each total reads REDACTED_AMOUNT while the split is hidden (see set_pool_reveal).
+    pub fn get_pools(env: Env) -> Pools {
+        let pools = Self::exact_pools(&env);
+        if Self::pools_revealed(env.clone()) {
+            return pools;
+        }
+        Pools {
+            yes_total: REDACTED_AMOUNT,
+            no_total: REDACTED_AMOUNT,
+            participant_count: pools.participant_count,
+        }
+    }
+
+    /// Get the total YES + NO stake (no auth)
+    ///
+    /// Never redacted: the total says nothing about which side is larger, and
+    /// the AMM sizes its resting liquidity floor from it.
+    pub fn get_open_interest(env: Env) -> i128 {
+        let pools = Self::exact_pools(&env);
+        pools.yes_total + pools.no_total
+    }
+
+    /// Hide the YES/NO split until `before_cutoff` seconds ahead of the betting cutoff
+    /// (creator only, before the first prediction; None turns the mode off)
+    ///
+    /// While hidden, get_pools and PositionChangedEvent report REDACTED_AMOUNT
+    /// per side, get_market_liquidity reports empty reserves at 50/50 odds,
+    /// get_portfolio reports a 50% implied probability and
+    /// get_paginated_predictions omits each item's outcome. The reveal time
+    /// follows the current cutoff. Accounting, claims and get_open_interest
+    /// always see exact pools.
+    pub fn set_pool_reveal(env: Env, creator: Address, before_cutoff: Option<u64>) {
+        Self::require_creator(&env, &creator);
+
+        if Self::has_predictions(&env) {
+            panic!("Pool reveal locked after first prediction");
+        }
+
+        let key = Symbol::new(&env, POOL_REVEAL_KEY);
+        match before_cutoff {
+            Some(before_cutoff) => env.storage().persistent().set(&key, &before_cutoff),
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    /// Get the time the YES/NO split becomes public, None when it is never hidden
+    pub fn get_pool_reveal_time(env: Env) -> Option<u64> {
+        let before_cutoff: u64 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, POOL_REVEAL_KEY))?;
+        Some(Self::get_betting_cutoff(env).saturating_sub(before_cutoff))
+    }
+
+    /// Whether read paths report the exact YES/NO split
+    ///
+    /// True without a reveal time, from the reveal time on, and once the
+    /// market has left Open.
+    pub fn pools_revealed(env: Env) -> bool {
+        let Some(reveal_time) = Self::get_pool_reveal_time(env.clone()) else {
+            return true;
+        };
+        time::has_reached(&env, reveal_time)
+            || Self::get_market_state_value(env) != Some(MarketStatus::Open as u32)
+    }
+
+    /// Helper: Unredacted pool totals for internal accounting
+    fn exact_pools(env: &Env) -> Pools {
+        let participants: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, PARTICIPANTS_KEY))
+            .unwrap_or_else(|| Vec::new(env));
+        Pools {
+            yes_total: env
+                .storage()
+                .persistent()
+                .get(&Symbol::new(env, YES_POOL_KEY))
+                .unwrap_or(0),
+            no_total: env
+                .storage()
+                .persistent()
+                .get(&Symbol::new(env, NO_POOL_KEY))
+                .unwrap_or(0),
+            participant_count: participants.len(),
+        }
+    }
+
+    /// Get a user's position summary: stake, side, implied probability and claimable value
+    ///
+    /// Read-only; returns a zeroed portfolio (side = PREDICTION_OUTCOME_NONE) when
+    /// the user has no position in this market. On a Resolved market `claimable`
+    /// is simulate_claim's net (guarantee top-up included, 0 while frozen); on a
+    /// Cancelled or Refunding market it is the unrefunded stake.
+    pub fn get_portfolio(env: Env, user: Address) -> Portfolio {
+        let mut portfolio = Portfolio {
+            stake: 0,
+            side: PREDICTION_OUTCOME_NONE,
+            implied_probability_bps: 0,
+            claimable: 0,
+        };
+
+        let state: MarketStatus = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, MARKET_STATE_KEY))
+            .unwrap_or(MarketStatus::Open);
+        let refundable = matches!(state, MarketStatus::Cancelled | MarketStatus::Refunding)
+            && !env
+                .storage()
+                .persistent()
+                .has(&Self::get_refunded_key(&env, &user))
+            && !Self::is_account_frozen(env.clone(), user.clone());
+
+        // Unrevealed commitment: side stays hidden
+        if let Some(commitment) = Self::get_commitment(env.clone(), user.clone()) {
+            portfolio.stake = commitment.amount;
+            if refundable {
+                portfolio.claimable = commitment.amount;
+            }
+            return portfolio;
+        }
+
+        let pred: UserPrediction = match env
+            .storage()
+            .persistent()
+            .get(&Self::get_prediction_key(&env, &user))
+        {
+            Some(pred) => pred,
+            None => return portfolio,
+        };
+
+        portfolio.stake = pred.amount;
+        portfolio.side = pred.outcome;
+
+        let yes_pool: i128 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, YES_POOL_KEY))
+            .unwrap_or(0);
+        let no_pool: i128 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, NO_POOL_KEY))
+            .unwrap_or(0);
+        let total_pool = yes_pool + no_pool;
+        let side_pool = if pred.outcome == 1 { yes_pool } else { no_pool };
+        portfolio.implied_probability_bps = if total_pool == 0 || !Self::pools_revealed(env.clone())
+        {
+            5000
+        } else {
+            ((side_pool * 10000) / total_pool) as u32
+        };
+
+        if state == MarketStatus::Resolved {
+            portfolio.claimable = Self::preview_claim(&env, &user).net;
+        } else if refundable {
+            portfolio.claimable = pred.amount;
+        }
+
+        portfolio
+    }
+
+    /// Helper: Net payout for a winning stake after the platform fee (0 if no winners)
+    fn calculate_net_payout(env: &Env, amount: i128) -> i128 {
+        let (gross_payout, fee) = Self::calculate_payout(env, amount);
+        gross_payout - fee
+    }
+
+    /// Helper: (gross payout, platform fee) for a winning stake
+    ///
+    /// The fee applies to profit (gross - stake) only and rounds up, so it favours
+    /// the platform by at most 1 stroop. A zero fee rate short-circuits.
+    fn calculate_payout(env: &Env, amount: i128) -> (i128, i128) {
+        let winner_shares: i128 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, WINNER_SHARES_KEY))
+            .unwrap_or(0);
+        let fee_bps: u32 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, FEE_BPS_KEY))
+            .unwrap_or(0);
+
+        payout::winner_payout(amount, winner_shares, Self::forfeited_stake(env), fee_bps)
+    }
+
+    /// Helper: Refund owed on a losing stake of `amount` under the loss cap, floored
+    fn loss_refund(env: &Env, amount: i128) -> i128 {
+        payout::loss_refund(amount, Self::get_loss_cap_bps(env.clone()))
+    }
+
+    /// Helper: Losing stake left for winners once loss refunds are set aside
+    fn forfeited_stake(env: &Env) -> i128 {
+        let loser_shares: i128 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, LOSER_SHARES_KEY))
+            .unwrap_or(0);
+        payout::forfeited_stake(loser_shares, Self::get_loss_cap_bps(env.clone()))
+    }
+
+    /// Helper: Move `amount` of the market's token into the treasury's fee pools
+    ///
+    /// The treasury pulls the tokens via `deposit_fees(market, amount)`, so the
+    /// nested token transfer is pre-authorized for this contract.
+    fn deposit_fee_to_treasury(env: &Env, token_address: &Address, amount: i128) {
+        let factory: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, FACTORY_KEY))
+            .expect("Factory address not set");
+        let treasury: Address =
+            env.invoke_contract(&factory, &Symbol::new(env, "get_treasury"), Vec::new(env));
+        let contract_address = env.current_contract_address();
+        // Fees on operator-run markets are booked against the operator
+        let operator = Self::factory_market_operator(env, &factory);
+
+        env.authorize_as_current_contract(soroban_sdk::vec![
+            env,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: token_address.clone(),
+                    fn_name: Symbol::new(env, "transfer"),
+                    args: (contract_address.clone(), treasury.clone(), amount).into_val(env),
+                },
+                sub_invocations: Vec::new(env),
+            }),
+        ]);
+
+        match operator {
+            Some(operator_id) => env.invoke_contract::<()>(
+                &treasury,
+                &Symbol::new(env, "deposit_operator_fees"),
+                (contract_address, operator_id, amount).into_val(env),
+            ),
+            None => env.invoke_contract::<()>(
+                &treasury,
+                &Symbol::new(env, "deposit_fees"),
+                (contract_address, amount).into_val(env),
+            ),
+        }
+    }
+
+    /// Helper: The operator this market was created under (None if untagged or unknown)
+    fn factory_market_operator(env: &Env, factory: &Address) -> Option<Symbol> {
+        let market_id: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, MARKET_ID_KEY))?;
+        env.try_invoke_contract::<Option<Symbol>, soroban_sdk::Error>(
+            factory,
+            &Symbol::new(env, "get_market_operator"),
+            (market_id,).into_val(env),
+        )
+        .ok()
+        .and_then(|result| result.ok())
+        .flatten()
+    }
+
+    /// Factory: Freeze or unfreeze a user's claims on this market
+    ///
+    /// A frozen user's claim_winnings, claim_loss_refund and claim_refund fail
+    /// with `AccountFrozen`, and simulate_claim reports CLAIM_STATUS_ACCOUNT_FROZEN.
+    pub fn set_account_frozen(env: Env, user: Address, frozen: bool) {
+        Self::require_factory(&env);
+
+        let key = (Symbol::new(&env, FROZEN_PREFIX), user.clone());
+        if frozen {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        let market_id: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, MARKET_ID_KEY))
+            .expect("Market not initialized");
+        events::market::account_freeze_changed(
+            &env,
+            market_id,
+            user,
+            frozen,
+            env.ledger().timestamp(),
+        );
+    }
+
+    /// Whether `user`'s claims are frozen on this market
+    pub fn is_account_frozen(env: Env, user: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&(Symbol::new(&env, FROZEN_PREFIX), user))
+    }
+
+    /// Helper: The only gate policy claim_winnings and claim_refund consult
+    ///
+    /// Once a market is cleanly settled, getting money out must always work.
+    /// Claims honor the dispute gate (a Disputed market is not settled) and
+    /// the per-user freeze (a frozen `user` fails with `AccountFrozen`), and
+    /// deliberately ignore every other switch:
+    /// - the operator's betting pause (`is_paused_by_operator`)
+    /// - factory pause, shutdown and sunset flags
+    /// - the KYC registry (`check_verified`)
+    ///
+    /// A new gate that must stop claims goes here, and into the claim gate
+    /// matrix in tests/market_test.rs, on purpose.
+    fn claims_guaranteed(env: &Env, user: &Address) -> bool {
+        if Self::is_account_frozen(env.clone(), user.clone()) {
+            panic_with_error!(env, MarketError::AccountFrozen);
+        }
+        Self::get_state(env.clone()) != MarketStatus::Disputed
+    }
+
+    /// Helper: Fail a claim on a swept market with `UnclaimedSwept`
+    fn require_not_swept(env: &Env) {
+        if Self::get_state(env.clone()) == MarketStatus::Swept {
+            panic_with_error!(env, MarketError::UnclaimedSwept);
+        }
+    }
+
+    /// Helper: Whether the market's operator has paused predictions on the factory
+    fn is_paused_by_operator(env: &Env) -> bool {
+        let factory: Option<Address> = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, FACTORY_KEY));
+        let market_id: Option<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, MARKET_ID_KEY));
+        let (Some(factory), Some(market_id)) = (factory, market_id) else {
+            return false;
+        };
+        env.try_invoke_contract::<bool, soroban_sdk::Error>(
+            &factory,
+            &Symbol::new(env, "is_market_paused"),
+            (market_id,).into_val(env),
+        )
+        .ok()
+        .and_then(|result| result.ok())
+        .unwrap_or(false)
+    }
+
+    /// Get the platform fee rate on winnings profit (basis points)
+    pub fn get_fee_bps(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, FEE_BPS_KEY))
+            .unwrap_or(0)
+    }
+
+    /// Set the platform fee rate (factory only, before the first prediction)
+    ///
+    /// The rate is fixed once trading starts. Capped at MAX_FEE_BPS.
+    pub fn set_fee_bps(env: Env, fee_bps: u32) {
+        Self::require_factory(&env);
+
+        if Self::has_predictions(&env) {
+            panic!("Fee locked after first prediction");
+        }
+        if fee_bps > MAX_FEE_BPS {
+            panic!("Fee exceeds maximum");
+        }
+
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, FEE_BPS_KEY), &fee_bps);
+    }
+
+    /// Share of a losing stake returned on resolution (basis points, 0 = classic)
+    pub fn get_loss_cap_bps(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, LOSS_CAP_KEY))
+            .unwrap_or(0)
+    }
+
+    /// Make this a capped-loss market (creator only, before the first prediction)
+    ///
+    /// Losers get `loss_cap_bps` of their stake back through claim_loss_refund,
+    /// and winners split only the forfeited remainder. FULL_REFUND_BPS would
+    /// leave nothing to win and is rejected.
+    pub fn set_loss_cap(env: Env, creator: Address, loss_cap_bps: u32) {
+        Self::require_creator(&env, &creator);
+
+        if Self::has_predictions(&env) {
+            panic!("Loss cap locked after first prediction");
+        }
+        if loss_cap_bps >= FULL_REFUND_BPS {
+            panic!("Loss cap must leave losers at risk");
+        }
+
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, LOSS_CAP_KEY), &loss_cap_bps);
+    }
+
     /// Return paginated list of all revealed predictions for this market.
     ///
     /// Only includes predictions that have been revealed (commit-phase privacy preserved).
@@ -1163,6 +3566,7 @@ impl PredictionMarket {
 
         let start = offset.min(total);
         let end = (start + limit).min(total);
+        let revealed_split = Self::pools_revealed(env.clone());
 
         for i in start..end {
             let user = revealed.get(i).unwrap();
@@ -1174,7 +3578,11 @@ impl PredictionMarket {
             {
                 items.push_back(RevealedPredictionItem {
                     user: pred.user,
-                    outcome: pred.outcome,
+                    outcome: if revealed_split {
+                        pred.outcome
+                    } else {
+                        PREDICTION_OUTCOME_NONE
+                    },
                     amount: pred.amount,
                     timestamp: pred.timestamp,
                 });
@@ -1216,13 +3624,13 @@ impl PredictionMarket {
         limit: u32,
     ) -> Vec<(Address, i128)> {
         // 1. Validate market state is RESOLVED
-        let state: u32 = env
+        let state: MarketStatus = env
             .storage()
             .persistent()
             .get(&Symbol::new(&env, MARKET_STATE_KEY))
             .expect("Market not initialized");
 
-        if state != STATE_RESOLVED {
+        if state != MarketStatus::Resolved {
             panic!("Market not resolved");
         }
 
@@ -1311,6 +3719,8 @@ impl PredictionMarket {
     /// - k_constant: CPMM invariant (yes_reserve * no_reserve)
     /// - yes_odds: Implied probability for YES outcome (basis points, 5000 = 50%)
     /// - no_odds: Implied probability for NO outcome (basis points, 5000 = 50%)
+    ///
+    /// Reads (0, 0, 0, 5000, 5000) while the pool split is hidden.
     pub fn get_market_liquidity(env: Env, market_id: BytesN<32>) -> (u128, u128, u128, u32, u32) {
         // Get AMM contract address from factory
         let factory: Address = env
@@ -1347,18 +3757,15 @@ impl PredictionMarket {
         // let amm_client = AMMClient::new(&env, &amm_address);
         // amm_client.get_pool_state(&market_id)
 
-        // For now, read from local storage (assuming AMM data is synced)
-        let yes_reserve: u128 = env
-            .storage()
-            .persistent()
-            .get(&Symbol::new(&env, YES_POOL_KEY))
-            .unwrap_or(0);
+        // A hidden split reads like an empty pool
+        if !Self::pools_revealed(env.clone()) {
+            return (0, 0, 0, 5000, 5000);
+        }
 
-        let no_reserve: u128 = env
-            .storage()
-            .persistent()
-            .get(&Symbol::new(&env, NO_POOL_KEY))
-            .unwrap_or(0);
+        // For now, read from local storage (assuming AMM data is synced)
+        let pools = Self::exact_pools(&env);
+        let yes_reserve = pools.yes_total.max(0) as u128;
+        let no_reserve = pools.no_total.max(0) as u128;
 
         let total_liquidity = yes_reserve + no_reserve;
 
@@ -1409,23 +3816,21 @@ impl PredictionMarket {
             panic!("Unauthorized: only creator can cancel");
         }
 
-        let state: u32 = env
+        let state: MarketStatus = env
             .storage()
             .persistent()
             .get(&Symbol::new(&env, MARKET_STATE_KEY))
             .expect("Market state not found");
 
-        if state == STATE_RESOLVED {
+        if state == MarketStatus::Resolved {
             panic!("Cannot cancel resolved market");
         }
-        if state == STATE_CANCELLED {
+        if state == MarketStatus::Cancelled {
             panic!("Market already cancelled");
         }
 
         // Set state to CANCELLED; participants claim refunds via claim_refund (only callable when CANCELLED)
-        env.storage()
-            .persistent()
-            .set(&Symbol::new(&env, MARKET_STATE_KEY), &STATE_CANCELLED);
+        Self::transition(&env, MarketStatus::Cancelled);
 
         let timestamp = env.ledger().timestamp();
 
@@ -1444,22 +3849,27 @@ impl PredictionMarket {
         .publish(&env);
     }
 
-    /// Refund committed USDC to a participant. Only callable when market is CANCELLED.
+    /// Refund committed USDC to a participant. Only callable when market is CANCELLED
+    /// or REFUNDING (resolved as Invalid). No fee is charged on refunds.
     ///
-    /// - Requires market state is CANCELLED
+    /// - Requires market state is CANCELLED or REFUNDING
     /// - Refunds exact committed/revealed amount (from commitment or prediction)
     /// - Tracks refund status to prevent double-refunds
     /// - Emits RefundedEvent
     pub fn claim_refund(env: Env, user: Address, market_id: BytesN<32>) {
         user.require_auth();
+        let mut guard = guard::enter(&env);
+        if !Self::claims_guaranteed(&env, &user) {
+            panic!("Refunds only available for cancelled markets");
+        }
 
-        let state: u32 = env
+        let state: MarketStatus = env
             .storage()
             .persistent()
             .get(&Symbol::new(&env, MARKET_STATE_KEY))
             .expect("Market not initialized");
 
-        if state != STATE_CANCELLED {
+        if state != MarketStatus::Cancelled && state != MarketStatus::Refunding {
             panic!("Refunds only available for cancelled markets");
         }
 
@@ -1473,9 +3883,9 @@ impl PredictionMarket {
             .persistent()
             .get(&Symbol::new(&env, USDC_KEY))
             .expect("USDC token not found");
-        let token_client = token::TokenClient::new(&env, &usdc);
-        let contract = env.current_contract_address();
 
+        // Only revealed stakes show up in get_position; a refunded commitment leaves no trace
+        let mut revealed_outcome = None;
         let amount = if let Some(commitment) = Self::get_commitment(env.clone(), user.clone()) {
             env.storage()
                 .persistent()
@@ -1484,6 +3894,7 @@ impl PredictionMarket {
         } else if let Some(pred) = Self::test_get_prediction(env.clone(), user.clone()) {
             let pred_key = Self::get_prediction_key(&env, &user);
             env.storage().persistent().remove(&pred_key);
+            revealed_outcome = Some(pred.outcome);
             pred.amount
         } else {
             panic!("No commitment or prediction found for user");
@@ -1493,10 +3904,20 @@ impl PredictionMarket {
             panic!("No amount to refund");
         }
 
-        token_client.transfer(&contract, &user, &amount);
-
         env.storage().persistent().set(&refunded_key, &true);
-
+        guard.transfer(&usdc, &env.current_contract_address(), &user, amount);
+        guard.commit();
+
+        if let Some(outcome) = revealed_outcome {
+            Self::publish_position_changed(
+                &env,
+                &market_id,
+                &user,
+                POSITION_OP_REFUND,
+                outcome,
+                -amount,
+            );
+        }
         RefundedEvent {
             user: user.clone(),
             market_id,
@@ -1553,9 +3974,10 @@ impl PredictionMarket {
         winner_shares: i128,
         loser_shares: i128,
     ) {
-        env.storage()
-            .persistent()
-            .set(&Symbol::new(&env, MARKET_STATE_KEY), &STATE_RESOLVED);
+        env.storage().persistent().set(
+            &Symbol::new(&env, MARKET_STATE_KEY),
+            &MarketStatus::Resolved,
+        );
         env.storage()
             .persistent()
             .set(&Symbol::new(&env, WINNING_OUTCOME_KEY), &outcome);
@@ -1589,13 +4011,13 @@ impl PredictionMarket {
         users: Vec<Address>,
     ) -> Vec<(Address, i128)> {
         // Validate market state is RESOLVED
-        let state: u32 = env
+        let state: MarketStatus = env
             .storage()
             .persistent()
             .get(&Symbol::new(&env, MARKET_STATE_KEY))
             .expect("Market not initialized");
 
-        if state != STATE_RESOLVED {
+        if state != MarketStatus::Resolved {
             panic!("Market not resolved");
         }
 
@@ -1615,14 +4037,6 @@ impl PredictionMarket {
             .get(&Symbol::new(&env, WINNER_SHARES_KEY))
             .expect("Winner shares not found");
 
-        let loser_shares: i128 = env
-            .storage()
-            .persistent()
-            .get(&Symbol::new(&env, LOSER_SHARES_KEY))
-            .unwrap_or(0);
-
-        let total_pool = winner_shares + loser_shares;
-
         if winner_shares == 0 {
             return Vec::new(&env);
         }
@@ -1640,14 +4054,7 @@ impl PredictionMarket {
                 .get::<_, UserPrediction>(&prediction_key)
             {
                 if prediction.outcome == winning_outcome {
-                    let gross_payout = prediction
-                        .amount
-                        .checked_mul(total_pool)
-                        .expect("Overflow in payout calculation")
-                        .checked_div(winner_shares)
-                        .expect("Division by zero in payout calculation");
-                    let fee = gross_payout / 10;
-                    let net_payout = gross_payout - fee;
+                    let net_payout = Self::calculate_net_payout(&env, prediction.amount);
                     winners.push_back((user, net_payout));
                 }
             }
@@ -1770,6 +4177,8 @@ mod tests {
             &oracle_contract_id,
             &2000,
             &3000,
+            &0,
+            &None,
         );
 
         // Mint USDC to contract to simulate pot
@@ -1792,11 +4201,11 @@ mod tests {
         // Claim
         let payout = market_client.claim_winnings(&user, &market_id_bytes);
 
-        // Expect 900 (1000 - 10% fee)
-        assert_eq!(payout, 900);
+        // Expect 1000 (no profit, so no platform fee)
+        assert_eq!(payout, 1000);
 
         // Verify transfer happened
-        assert_eq!(usdc_client.balance(&user), 900);
+        assert_eq!(usdc_client.balance(&user), 1000);
     }
 
     #[test]
@@ -1820,6 +4229,8 @@ mod tests {
             &oracle_contract_id,
             &2000,
             &3000,
+            &0,
+            &None,
         );
 
         market_client.test_setup_resolution(&market_id_bytes, &1u32, &1000, &1000);
@@ -1852,6 +4263,8 @@ mod tests {
             &oracle_contract_id,
             &2000,
             &3000,
+            &0,
+            &None,
         );
 
         let user = Address::generate(&env);
@@ -1882,6 +4295,8 @@ mod tests {
             &oracle_contract_id,
             &2000,
             &3000,
+            &0,
+            &None,
         );
         usdc_client.mint(&market_contract_id, &2000);
 
@@ -1914,12 +4329,14 @@ mod tests {
             &oracle_contract_id,
             &2000,
             &3000,
+            &0,
+            &None,
         );
 
         // Total pool: 1000 (winners) + 500 (losers) = 1500
         // User has 500 of 1000 winner shares
         // Gross payout = (500 / 1000) * 1500 = 750
-        // Net payout (after 10% fee) = 750 - 75 = 675
+        // No platform fee configured, so net payout = 750
         usdc_client.mint(&market_contract_id, &1500);
 
         market_client.test_setup_resolution(&market_id_bytes, &1u32, &1000, &500);
@@ -1928,8 +4345,8 @@ mod tests {
         market_client.test_set_prediction(&user, &1u32, &500);
 
         let payout = market_client.claim_winnings(&user, &market_id_bytes);
-        assert_eq!(payout, 675);
-        assert_eq!(usdc_client.balance(&user), 675);
+        assert_eq!(payout, 750);
+        assert_eq!(usdc_client.balance(&user), 750);
     }
 
     #[test]
@@ -1952,6 +4369,8 @@ mod tests {
             &oracle_contract_id,
             &2000,
             &3000,
+            &0,
+            &None,
         );
 
         // Total pool: 1000 (winners) + 1000 (losers) = 2000
@@ -1965,13 +4384,13 @@ mod tests {
         market_client.test_set_prediction(&user1, &1u32, &600);
         market_client.test_set_prediction(&user2, &1u32, &400);
 
-        // User1: (600 / 1000) * 2000 = 1200, minus 10% = 1080
+        // User1: (600 / 1000) * 2000 = 1200, no fee configured
         let payout1 = market_client.claim_winnings(&user1, &market_id_bytes);
-        assert_eq!(payout1, 1080);
+        assert_eq!(payout1, 1200);
 
-        // User2: (400 / 1000) * 2000 = 800, minus 10% = 720
+        // User2: (400 / 1000) * 2000 = 800, no fee configured
         let payout2 = market_client.claim_winnings(&user2, &market_id_bytes);
-        assert_eq!(payout2, 720);
+        assert_eq!(payout2, 800);
     }
 
     #[test]
@@ -1995,6 +4414,8 @@ mod tests {
             &oracle_contract_id,
             &2000,
             &3000,
+            &0,
+            &None,
         );
 
         market_client.test_setup_resolution(&market_id_bytes, &1u32, &1000, &0);
@@ -2042,6 +4463,8 @@ mod tests {
             &oracle_contract_id,
             &closing_time,
             &resolution_time,
+            &0,
+            &None,
         );
 
         // Advance time to closing
@@ -2081,6 +4504,8 @@ mod tests {
             &oracle_contract_id,
             &2000,
             &3000,
+            &0,
+            &None,
         );
 
         env.ledger().with_mut(|li| {
@@ -2120,6 +4545,8 @@ mod tests {
             &oracle_contract_id,
             &2000,
             &resolution_time,
+            &0,
+            &None,
         );
 
         // Advance time but NOT enough
@@ -2187,6 +4614,8 @@ mod tests {
             &oracle_contract_id,
             &closing_time,
             &resolution_time,
+            &0,
+            &None,
         );
 
         let user = Address::generate(&env);
@@ -2533,8 +4962,8 @@ mod tests {
 
         // Step 5: Claim winnings
         let payout = market_client.claim_winnings(&user, &market_id);
-        // 1000 total pool, user has all 1000 winner shares, gross 1000, net 900 (10% fee)
-        assert_eq!(payout, 900);
+        // 1000 total pool, user has all 1000 winner shares, gross 1000, no profit so no fee
+        assert_eq!(payout, 1000);
     }
 
     #[test]
@@ -2605,6 +5034,8 @@ mod tests {
             &oracle_contract_id,
             &2000,
             &3000,
+            &0,
+            &None,
         );
 
         let user = Address::generate(&env);
@@ -2632,6 +5063,8 @@ mod tests {
             &oracle_contract_id,
             &2000,
             &3000,
+            &0,
+            &None,
         );
 
         let user = Address::generate(&env);
@@ -2671,6 +5104,8 @@ mod tests {
             &oracle_contract_id,
             &2000,
             &3000,
+            &0,
+            &None,
         );
 
         let user = Address::generate(&env);
@@ -2708,6 +5143,8 @@ mod tests {
             &oracle_contract_id,
             &2000,
             &3000,
+            &0,
+            &None,
         );
 
         let user = Address::generate(&env);
@@ -2744,6 +5181,8 @@ mod tests {
             &oracle_contract_id,
             &2000,
             &3000,
+            &0,
+            &None,
         );
 
         let user = Address::generate(&env);
@@ -2788,6 +5227,8 @@ mod tests {
             &oracle_contract_id,
             &2000,
             &3000,
+            &0,
+            &None,
         );
 
         let user = Address::generate(&env);
@@ -2796,6 +5237,49 @@ mod tests {
         // Market is OPEN, not RESOLVED
         market_client.dispute_market(&user, &market_id, &dispute_reason, &None);
     }
+
+    // ============================================================================
+    // PORTFOLIO TESTS
+    // ============================================================================
+
+    #[test]
+    fn test_get_portfolio_hides_side_of_commitment_and_refunds_on_cancel() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let market_id = BytesN::from_array(&env, &[3; 32]);
+        let market_contract_id = env.register(PredictionMarket, ());
+        let market_client = PredictionMarketClient::new(&env, &market_contract_id);
+        let usdc_client = create_token_contract(&env, &Address::generate(&env));
+        let creator = Address::generate(&env);
+
+        market_client.initialize(
+            &market_id,
+            &creator,
+            &Address::generate(&env),
+            &usdc_client.address,
+            &Address::generate(&env),
+            &2000,
+            &3000,
+            &0,
+            &None,
+        );
+
+        let user = Address::generate(&env);
+        usdc_client.mint(&user, &500);
+        market_client.commit_prediction(&user, &BytesN::from_array(&env, &[9; 32]), &500);
+
+        let portfolio = market_client.get_portfolio(&user);
+        assert_eq!(portfolio.stake, 500);
+        assert_eq!(portfolio.side, PREDICTION_OUTCOME_NONE);
+        assert_eq!(portfolio.claimable, 0);
+
+        market_client.cancel_market(&creator, &market_id);
+        assert_eq!(market_client.get_portfolio(&user).claimable, 500);
+
+        market_client.claim_refund(&user, &market_id);
+        assert_eq!(market_client.get_portfolio(&user).claimable, 0);
+    }
 }
 
 // ============================================================================
@@ -2835,6 +5319,8 @@ mod market_leaderboard_tests {
             &oracle_contract_id,
             &2000,
             &3000,
+            &0,
+            &None,
         );
 
         // Setup: 3 winners with different payouts
@@ -2845,11 +5331,11 @@ mod market_leaderboard_tests {
         let user2 = Address::generate(&env);
         let user3 = Address::generate(&env);
 
-        // User1: 500 shares -> (500/1000)*1500 = 750, minus 10% = 675
+        // User1: 500 shares -> (500/1000)*1500 = 750, no fee configured
         market_client.test_set_prediction(&user1, &1u32, &500);
-        // User2: 300 shares -> (300/1000)*1500 = 450, minus 10% = 405
+        // User2: 300 shares -> (300/1000)*1500 = 450, no fee configured
         market_client.test_set_prediction(&user2, &1u32, &300);
-        // User3: 200 shares -> (200/1000)*1500 = 300, minus 10% = 270
+        // User3: 200 shares -> (200/1000)*1500 = 300, no fee configured
         market_client.test_set_prediction(&user3, &1u32, &200);
 
         let mut users = Vec::new(&env);
@@ -2867,11 +5353,11 @@ mod market_leaderboard_tests {
         let winner3 = winners.get(2).unwrap();
 
         assert_eq!(winner1.0, user1);
-        assert_eq!(winner1.1, 675);
+        assert_eq!(winner1.1, 750);
         assert_eq!(winner2.0, user2);
-        assert_eq!(winner2.1, 405);
+        assert_eq!(winner2.1, 450);
         assert_eq!(winner3.0, user3);
-        assert_eq!(winner3.1, 270);
+        assert_eq!(winner3.1, 300);
     }
 
     #[test]
@@ -2895,6 +5381,8 @@ mod market_leaderboard_tests {
             &oracle_contract_id,
             &2000,
             &3000,
+            &0,
+            &None,
         );
 
         market_client.test_setup_resolution(&market_id_bytes, &1u32, &1000, &500);
@@ -2921,9 +5409,9 @@ mod market_leaderboard_tests {
         let winner2 = winners.get(1).unwrap();
 
         assert_eq!(winner1.0, user1);
-        assert_eq!(winner1.1, 675);
+        assert_eq!(winner1.1, 750);
         assert_eq!(winner2.0, user2);
-        assert_eq!(winner2.1, 405);
+        assert_eq!(winner2.1, 450);
     }
 
     #[test]
@@ -2947,6 +5435,8 @@ mod market_leaderboard_tests {
             &oracle_contract_id,
             &2000,
             &3000,
+            &0,
+            &None,
         );
 
         market_client.test_setup_resolution(&market_id_bytes, &1u32, &1000, &500);
@@ -2978,6 +5468,8 @@ mod market_leaderboard_tests {
             &oracle_contract_id,
             &2000,
             &3000,
+            &0,
+            &None,
         );
 
         // No winner shares (edge case)
@@ -3011,6 +5503,8 @@ mod market_leaderboard_tests {
             &oracle_contract_id,
             &2000,
             &3000,
+            &0,
+            &None,
         );
 
         // Market is still OPEN (not resolved)
@@ -3039,6 +5533,8 @@ mod market_leaderboard_tests {
             &oracle_contract_id,
             &2000,
             &3000,
+            &0,
+            &None,
         );
 
         // Winning outcome is YES (1)
@@ -3090,6 +5586,8 @@ mod market_leaderboard_tests {
             &oracle_contract_id,
             &2000,
             &3000,
+            &0,
+            &None,
         );
 
         market_client.test_setup_resolution(&market_id_bytes, &1u32, &1000, &500);
@@ -3117,11 +5615,11 @@ mod market_leaderboard_tests {
         let w2 = winners.get(1).unwrap();
         let w3 = winners.get(2).unwrap();
 
-        // Both user1 and user2 should have payout of 540
-        // (400/1000)*1500 = 600, minus 10% = 540
-        assert_eq!(w1.1, 540);
-        assert_eq!(w2.1, 540);
-        assert_eq!(w3.1, 270);
+        // Both user1 and user2 should have payout of 600
+        // (400/1000)*1500 = 600, no fee configured
+        assert_eq!(w1.1, 600);
+        assert_eq!(w2.1, 600);
+        assert_eq!(w3.1, 300);
     }
 
     #[test]
@@ -3145,6 +5643,8 @@ mod market_leaderboard_tests {
             &oracle_contract_id,
             &2000,
             &3000,
+            &0,
+            &None,
         );
 
         market_client.test_setup_resolution(&market_id_bytes, &1u32, &1000, &500);
@@ -3165,3 +5665,66 @@ mod market_leaderboard_tests {
         assert_eq!(winners.len(), 2);
     }
 }
+
+#[cfg(test)]
+mod payout_proptests {
+    extern crate std;
+
+    use super::payout::{forfeited_stake, loss_refund, winner_payout};
+    use super::{FULL_REFUND_BPS, MAX_FEE_BPS};
+    use proptest::prelude::*;
+    use std::vec::Vec;
+
+    fn stakes(min_len: usize) -> impl Strategy<Value = Vec<i128>> {
+        proptest::collection::vec(1i128..=1_000_000_000_000, min_len..=20)
+    }
+
+    proptest! {
+        #[test]
+        fn prop_claims_and_fees_never_exceed_pool(
+            winners in stakes(1),
+            losers in stakes(0),
+            fee_bps in 0..=MAX_FEE_BPS,
+            loss_cap_bps in 0..FULL_REFUND_BPS,
+        ) {
+            let winner_shares: i128 = winners.iter().sum();
+            let loser_shares: i128 = losers.iter().sum();
+            let forfeited = forfeited_stake(loser_shares, loss_cap_bps);
+
+            let mut paid = 0i128;
+            for stake in &winners {
+                let (gross, fee) = winner_payout(*stake, winner_shares, forfeited, fee_bps);
+                // The fee comes out of profit only, so a winner never gets back less than their stake
+                prop_assert!(fee >= 0 && gross - fee >= *stake);
+                paid += gross;
+            }
+            for stake in &losers {
+                paid += loss_refund(*stake, loss_cap_bps);
+            }
+
+            let pool = winner_shares + loser_shares;
+            let claimers = (winners.len() + losers.len()) as i128;
+            prop_assert!(paid <= pool, "paid {} out of a pool of {}", paid, pool);
+            prop_assert!(pool - paid <= claimers, "dust {} over {} claimers", pool - paid, claimers);
+        }
+
+        #[test]
+        fn prop_fee_is_ceiled_bps_of_profit(
+            stake in 1i128..=1_000_000_000_000,
+            others in 0i128..=1_000_000_000_000,
+            forfeited in 0i128..=1_000_000_000_000,
+            fee_bps in 0..=MAX_FEE_BPS,
+        ) {
+            let (gross, fee) = winner_payout(stake, stake + others, forfeited, fee_bps);
+            let profit = gross - stake;
+            prop_assert!(profit >= 0);
+            prop_assert!(fee * 10_000 >= profit * fee_bps as i128);
+            prop_assert!(fee * 10_000 < profit * fee_bps as i128 + 10_000);
+        }
+    }
+
+    #[test]
+    fn test_no_winners_pays_nothing() {
+        assert_eq!(winner_payout(100, 0, 500, MAX_FEE_BPS), (0, 0));
+    }
+}
@@ -1,87 +1,443 @@
 // contract/src/oracle.rs - Oracle & Market Resolution Contract Implementation
 // Handles multi-source oracle consensus for market resolution
+// Timestamp checks go through helpers::time: deadlines are inclusive, windows
+// and cooldowns are half-open [start, start + duration).
 
+use crate::assert_invariant;
+use crate::helpers::access::{self, Role};
+use crate::helpers::{
+    events, guard, hashing, rand, safe_add_i128, safe_add_u32, safe_add_u64, safe_sub_u32, time,
+};
+use soroban_sdk::xdr::ToXdr;
 use soroban_sdk::{
-    contract, contractevent, contractimpl, contracttype, Address, BytesN, Env, Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, panic_with_error, token, Address, Bytes,
+    BytesN, Env, IntoVal, Symbol, Vec,
 };
 
-#[contractevent]
-pub struct OracleInitializedEvent {
-    pub admin: Address,
-    pub required_consensus: u32,
+const CHALLENGE_STAKE_AMOUNT: i128 = 1000; // Minimum stake required to challenge
+
+/// Storage keys of the override audit log
+///
+/// Kept apart from DataKey, which is close to the 50-variant limit of a contract type.
+#[contracttype]
+#[derive(Clone)]
+pub enum AuditKey {
+    /// Number of entries in the log
+    OverrideCount,
+    /// Log entry, by id
+    OverrideLog(u32),
 }
 
-#[contractevent]
-pub struct OracleRegisteredEvent {
-    pub oracle: Address,
-    pub oracle_name: Symbol,
-    pub timestamp: u64,
+/// Storage keys
+///
+/// Typed keys replace the former string `Symbol` keys (and `(Symbol, ..)`
+/// tuples), so a typo or a wrong argument order is a compile error instead of
+/// a silent read of an empty slot. This changes the on-chain storage layout:
+/// state written under the old keys is not readable through these, which is
+/// fine only because no oracle has been deployed to mainnet yet.
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    RequiredConsensus,
+    OracleCount,
+    /// Every oracle ever registered (active flag kept separately)
+    OracleList,
+    /// Active flag of a registered oracle
+    Oracle(Address),
+    OracleName(Address),
+    /// Market categories an oracle may attest on
+    OracleCategories(Address),
+    /// Active oracles covering a category
+    CategoryOracleCount(Symbol),
+    OracleTimestamp(Address),
+    OracleAccuracy(Address),
+    /// Oracle's staked amount
+    OracleStake(Address),
+    OracleReward(Address),
+    ChallengerReward(Address),
+    /// Delegated hot key submitting for an oracle
+    Submitter(Address),
+    /// Required signatures for multi-sig
+    RequiredSignatures,
+    /// Timestamp of last emergency override
+    LastOverrideTime,
+    /// Cooldown period in seconds (default 86400 = 24h)
+    OverrideCooldown,
+    /// TieBreakMode applied by check_consensus
+    TieBreak,
+    /// Weigh numeric consensus by oracle stake
+    StakingEnabled,
+    /// Participating stake floor (bps of active stake)
+    MinParticipation,
+    /// data_hash must commit to the result and metadata
+    StrictHash,
+    /// Contract paged on high-severity occurrences
+    AlertHook,
+    /// Recent AlertDelivery records, oldest first
+    AlertLog,
+    /// Seconds a voided attestation reopens a market for
+    ReattestWindow,
+    /// Market resolution time
+    MarketResTime(BytesN<32>),
+    /// Market contract bound to a market_id
+    MarketContract(BytesN<32>),
+    /// Admin or registrar that registered a market (kept for audit)
+    MarketRegistrar(BytesN<32>),
+    /// Category a market was registered under
+    MarketCategory(BytesN<32>),
+    /// Attestation count for YES outcome
+    AttestYes(BytesN<32>),
+    /// Attestation count for NO outcome
+    AttestNo(BytesN<32>),
+    /// Oracles that attested a market
+    Voters(BytesN<32>),
+    Vote(BytesN<32>, Address),
+    Attestation(BytesN<32>, Address),
+    /// Numeric attestation per (market, oracle)
+    Value(BytesN<32>, Address),
+    /// Metadata blob of a numeric attestation
+    ValueMetadata(BytesN<32>, Address),
+    /// Oracles that submitted a numeric value
+    ValueVoters(BytesN<32>),
+    ConsensusResult(BytesN<32>),
+    /// Timestamp a market's outcome was locked in
+    FinalizedAt(BytesN<32>),
+    /// ResolutionArchive kept after cleanup
+    Archive(BytesN<32>),
+    MarketChallenged(BytesN<32>),
+    Challenge(BytesN<32>, Address),
+    OverrideRecord(BytesN<32>),
+    ManualOverride(BytesN<32>),
+    /// Latest RoundData published for a market
+    Round(BytesN<32>),
+    /// Per-attestation bond settings
+    BondConfig,
+    /// Bond posted with an oracle's attestation on a market
+    Bond(BytesN<32>, Address),
+    /// Oracles with an outstanding bond on a market
+    Bonded(BytesN<32>),
+    /// Contracts called back on each new round
+    Subscribers(BytesN<32>),
+    /// Finalization blocked until this timestamp
+    ReopenedUntil(BytesN<32>),
+    /// Oracle whose attestation on a market was invalidated
+    Voided(BytesN<32>, Address),
 }
 
-#[contractevent]
-pub struct OracleDeregisteredEvent {
-    pub oracle: Address,
-    pub timestamp: u64,
+/// Maximum number of active oracles
+const MAX_ORACLES: u32 = 10;
+
+/// Participating stake must exceed this share of active stake unless configured (50%)
+pub const DEFAULT_MIN_PARTICIPATION_BPS: u32 = 5000;
+
+/// Seconds a market stays open for re-attestation after a vote is voided, unless configured (1 day)
+pub const DEFAULT_REATTEST_WINDOW: u64 = 86400;
+
+/// Market category open to any registered oracle
+pub const CATEGORY_ANY: &str = "";
+
+/// Maximum number of records per list_overrides page
+pub const MAX_OVERRIDE_PAGE: u32 = 25;
+
+/// Maximum number of markets per get_resolution_states call
+pub const MAX_RESOLUTION_BATCH: u32 = 25;
+
+/// Maximum size of an attestation's metadata blob, in bytes
+pub const MAX_METADATA_LEN: u32 = 128;
+
+/// Maximum contracts notified of one market's resolution
+pub const MAX_RESOLUTION_SUBSCRIBERS: u32 = 10;
+
+/// Alert deliveries kept by get_alert_log; older ones are dropped
+pub const MAX_ALERT_LOG: u32 = 20;
+
+// Alert kinds passed to the hook's on_alert
+pub const ALERT_OVERRIDE: &str = "override"; // Emergency override executed
+pub const ALERT_INVARIANT: &str = "invariant"; // Stored tallies disagree at finalization
+pub const ALERT_ANOMALY: &str = "anomaly"; // Challenge upheld against an attestation
+pub const ALERT_VOID: &str = "void"; // Market escalated to OUTCOME_INVALID
+
+// Alert severities
+pub const SEVERITY_HIGH: u32 = 2;
+pub const SEVERITY_CRITICAL: u32 = 3;
+
+/// Capability flags reported by get_capabilities
+pub const CAP_BINARY: &str = "binary"; // YES/NO attestations
+pub const CAP_CHALLENGE: &str = "challenge"; // Attestation challenges (has_active_challenge)
+pub const CAP_OVERRIDE: &str = "override"; // Multi-sig emergency override
+pub const CAP_SCALAR: &str = "scalar"; // Numeric attestations (submit_value)
+pub const CAP_SUBSCRIBE: &str = "subscribe"; // subscribe_resolution callbacks and latest_round_data
+
+/// Consensus result recorded for markets declared unanswerable
+pub const OUTCOME_INVALID: u32 = 2;
+/// `MarketStatus::Cancelled` as returned by the market contract's get_state
+const MARKET_STATUS_CANCELLED: u32 = 4;
+pub const CAP_CONSENSUS_READY: &str = "consensus_ready"; // Enough oracles registered
+
+/// Parse a decimal version component at compile time
+const fn parse_version_part(part: &str) -> u32 {
+    let bytes = part.as_bytes();
+    let mut value = 0u32;
+    let mut i = 0;
+    while i < bytes.len() {
+        value = value * 10 + (bytes[i] - b'0') as u32;
+        i += 1;
+    }
+    value
 }
 
-#[contractevent]
-pub struct MarketRegisteredEvent {
-    pub market_id: BytesN<32>,
-    pub resolution_time: u64,
+const VERSION: (u32, u32, u32) = (
+    parse_version_part(env!("CARGO_PKG_VERSION_MAJOR")),
+    parse_version_part(env!("CARGO_PKG_VERSION_MINOR")),
+    parse_version_part(env!("CARGO_PKG_VERSION_PATCH")),
+);
+
+/// Pure consensus decision logic, driven by vote tallies so it can be tested
+/// (and compared across modes) without any contract setup.
+pub mod consensus {
+    /// Vote counts and summed weights per binary outcome
+    #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+    pub struct Tally {
+        pub yes_count: u32,
+        pub no_count: u32,
+        pub yes_weight: u128,
+        pub no_weight: u128,
+    }
+
+    impl Tally {
+        /// Tally of unit-weight votes with the given counts
+        pub fn from_counts(yes_count: u32, no_count: u32) -> Self {
+            Tally {
+                yes_count,
+                no_count,
+                yes_weight: yes_count as u128,
+                no_weight: no_count as u128,
+            }
+        }
+
+        /// Record one vote (1 = YES, anything else = NO) with the given weight
+        pub fn add_vote(&mut self, outcome: u32, weight: u128) {
+            if outcome == 1 {
+                self.yes_count += 1;
+                self.yes_weight += weight;
+            } else {
+                self.no_count += 1;
+                self.no_weight += weight;
+            }
+        }
+
+        pub fn total_count(&self) -> u32 {
+            self.yes_count + self.no_count
+        }
+
+        pub fn total_weight(&self) -> u128 {
+            self.yes_weight + self.no_weight
+        }
+    }
+
+    /// Count-based consensus: an outcome wins once it has at least `threshold`
+    /// votes and strictly more votes than the other outcome. Ties never resolve.
+    pub fn count_based(tally: &Tally, threshold: u32) -> Option<u32> {
+        if tally.total_count() < threshold {
+            return None;
+        }
+        if tally.yes_count >= threshold && tally.yes_count > tally.no_count {
+            Some(1)
+        } else if tally.no_count >= threshold && tally.no_count > tally.yes_count {
+            Some(0)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the votes are evenly split with enough of them to otherwise decide
+    pub fn is_tie(tally: &Tally, threshold: u32) -> bool {
+        tally.yes_count > 0 && tally.yes_count == tally.no_count && tally.total_count() >= threshold
+    }
+
+    /// Reputation-weighted consensus: an outcome wins when its weight strictly
+    /// exceeds the other outcome's and it holds at least `threshold` votes' worth
+    /// of the average weight (`win_weight * count >= threshold * total_weight`).
+    ///
+    /// With equal weights this reduces exactly to `count_based`.
+    pub fn weighted(tally: &Tally, threshold: u32) -> Option<u32> {
+        let count = tally.total_count() as u128;
+        let total_weight = tally.total_weight();
+        if count == 0 || total_weight == 0 {
+            return None;
+        }
+
+        let meets_threshold = |weight: u128| weight * count >= threshold as u128 * total_weight;
+        if tally.yes_weight > tally.no_weight && meets_threshold(tally.yes_weight) {
+            Some(1)
+        } else if tally.no_weight > tally.yes_weight && meets_threshold(tally.no_weight) {
+            Some(0)
+        } else {
+            None
+        }
+    }
+
+    /// Lower weighted median: the smallest value whose cumulative weight reaches
+    /// half of the total weight. Sorts `entries` by value in place.
+    ///
+    /// With equal weights this is the plain lower median.
+    pub fn weighted_median(entries: &mut [(i128, u128)]) -> Option<i128> {
+        let total_weight: u128 = entries.iter().map(|(_, weight)| weight).sum();
+        if total_weight == 0 {
+            return None;
+        }
+
+        entries.sort_unstable_by_key(|(value, _)| *value);
+        let mut cumulative = 0u128;
+        for (value, weight) in entries.iter() {
+            cumulative += weight;
+            if cumulative * 2 >= total_weight {
+                return Some(*value);
+            }
+        }
+        None
+    }
+
+    /// Whether `participating` stake strictly exceeds `min_bps` of `total` stake
+    pub fn meets_participation(participating: u128, total: u128, min_bps: u32) -> bool {
+        total > 0 && participating * 10_000 > total * min_bps as u128
+    }
 }
 
-#[contractevent]
-pub struct AttestationSubmittedEvent {
-    pub market_id: BytesN<32>,
-    pub oracle: Address,
-    pub attestation_result: u32,
+/// How check_consensus treats an evenly split vote
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TieBreakMode {
+    /// Ties never resolve; the market waits for more votes or an override
+    None,
+    /// Ties resolve to a deterministic pseudo-random outcome (helpers::rand)
+    Random,
+}
+
+/// Typed errors raised by attestation submission
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum OracleError {
+    /// Metadata is longer than MAX_METADATA_LEN bytes
+    MetadataTooLarge = 1,
+    /// Strict mode is on and data_hash does not commit to the result and metadata
+    DataHashMismatch = 2,
+    /// Stored tallies disagree with the recorded votes
+    InvariantViolation = 3,
+}
+
+/// Attestation record for market resolution
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attestation {
+    pub attestor: Address,
+    /// Key that signed the submission (the attestor itself or its delegated submitter)
+    pub submitter: Address,
+    pub outcome: u32,
+    pub timestamp: u64,
+    pub data_hash: BytesN<32>,
+    /// Structured result payload, e.g. the final score behind the outcome
+    pub metadata: Option<Bytes>,
 }
 
-#[contractevent]
-pub struct ResolutionFinalizedEvent {
+/// Verifiable summary of how a market was resolved
+///
+/// `attestor_digest` is the sha256 of the XDR-encoded attestations sorted by
+/// attestor address, so any off-chain copy of the detailed records can be
+/// checked against it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResolutionProof {
     pub market_id: BytesN<32>,
     pub final_outcome: u32,
-    pub timestamp: u64,
+    /// (yes_count, no_count)
+    pub tally: (u32, u32),
+    pub attestor_digest: BytesN<32>,
 }
 
-#[contractevent]
-pub struct AttestationChallengedEvent {
-    pub oracle: Address,
-    pub challenger: Address,
+/// One attempt to page the alert hook
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AlertDelivery {
+    pub kind: Symbol,
     pub market_id: BytesN<32>,
-    pub challenge_reason: Symbol,
+    pub severity: u32,
+    pub hook: Address,
+    pub timestamp: u64,
+    pub delivered: bool,
 }
 
-#[contractevent]
-pub struct ChallengeResolvedEvent {
-    pub oracle: Address,
-    pub challenger: Address,
-    pub challenge_valid: bool,
-    pub new_reputation: u32,
-    pub slashed_amount: i128,
+/// A market's published result, as delivered to subscribers and served by latest_round_data
+///
+/// `round_id` starts at 1 on finalization and increases each time an emergency
+/// override republishes the result. `attestor_digest` matches the
+/// ResolutionProof of the same round.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundData {
+    pub market_id: BytesN<32>,
+    pub round_id: u32,
+    pub outcome: u32,
+    pub attestor_digest: BytesN<32>,
+    pub updated_at: u64,
+    pub overridden: bool,
 }
 
-// Storage keys
-const ADMIN_KEY: &str = "admin";
-const REQUIRED_CONSENSUS_KEY: &str = "required_consensus";
-const ORACLE_COUNT_KEY: &str = "oracle_count";
-const MARKET_RES_TIME_KEY: &str = "mkt_res_time"; // Market resolution time storage
-const ATTEST_COUNT_YES_KEY: &str = "attest_yes"; // Attestation count for YES outcome
-const ATTEST_COUNT_NO_KEY: &str = "attest_no"; // Attestation count for NO outcome
-const ADMIN_SIGNERS_KEY: &str = "admin_signers"; // Multi-sig admin addresses
-const REQUIRED_SIGNATURES_KEY: &str = "required_sigs"; // Required signatures for multi-sig
-const LAST_OVERRIDE_TIME_KEY: &str = "last_override"; // Timestamp of last emergency override
-const OVERRIDE_COOLDOWN_KEY: &str = "override_cooldown"; // Cooldown period in seconds (default 86400 = 24h)
-const CHALLENGE_STAKE_AMOUNT: i128 = 1000; // Minimum stake required to challenge
-const ORACLE_STAKE_KEY: &str = "oracle_stake"; // Oracle's staked amount
+/// Bond posted with every binary attestation while `amount` is non-zero
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BondConfig {
+    pub token: Address,
+    /// Receives forfeited bonds
+    pub treasury: Address,
+    pub amount: i128,
+}
 
-/// Attestation record for market resolution
+/// A bond held by the oracle contract until its market's outcome is recorded
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct Attestation {
-    pub attestor: Address,
+pub struct AttestationBond {
+    /// Account the bond was taken from and is refunded to (the oracle or its submitter)
+    pub payer: Address,
+    pub token: Address,
+    pub amount: i128,
     pub outcome: u32,
-    pub timestamp: u64,
+}
+
+/// Permanent record kept after a market's detailed attestation data is cleaned up
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResolutionArchive {
+    pub market_id: BytesN<32>,
+    pub final_outcome: u32,
+    /// (yes_count, no_count)
+    pub tally: (u32, u32),
+    pub attestor_digest: BytesN<32>,
+    pub finalized_at: u64,
+    pub overridden: bool,
+    /// Metadata blobs of the attestations that carried one, by attestor
+    pub metadata: Vec<(Address, Bytes)>,
+}
+
+/// Where a market stands in the resolution flow, as served to portfolio views
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarketResolutionState {
+    pub market_id: BytesN<32>,
+    pub resolution_time: u64,
+    /// (yes_count, no_count), served from the archive once cleaned up
+    pub tally: (u32, u32),
+    pub consensus_reached: bool,
+    /// Consensus outcome; 0 unless consensus_reached
+    pub consensus_outcome: u32,
+    pub finalized: bool,
+    /// 0 until finalized
+    pub finalized_at: u64,
+    pub overridden: bool,
+    pub challenged: bool,
+    pub archived: bool,
 }
 
 /// Emergency override approval record
@@ -96,9 +452,14 @@ pub struct OverrideApproval {
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct EmergencyOverrideRecord {
+    /// Position in the override audit log (see list_overrides)
+    pub id: u32,
     pub market_id: BytesN<32>,
     pub forced_outcome: u32,
     pub justification_hash: BytesN<32>,
+    /// `hashing::hash_pair(market_id, justification_hash)`: the justification
+    /// bound to this market, distinct even when a document is reused
+    pub justification_id: BytesN<32>,
     pub approvers: Vec<Address>,
     pub timestamp: u64,
 }
@@ -128,75 +489,63 @@ impl OracleManager {
         admin.require_auth();
 
         // Store admin
-        env.storage()
-            .persistent()
-            .set(&Symbol::new(&env, ADMIN_KEY), &admin);
+        env.storage().persistent().set(&DataKey::Admin, &admin);
 
         // Store required consensus threshold
-        env.storage().persistent().set(
-            &Symbol::new(&env, REQUIRED_CONSENSUS_KEY),
-            &required_consensus,
-        );
-
-        // Initialize oracle counter
         env.storage()
             .persistent()
-            .set(&Symbol::new(&env, ORACLE_COUNT_KEY), &0u32);
+            .set(&DataKey::RequiredConsensus, &required_consensus);
+
+        // Initialize oracle counter
+        env.storage().persistent().set(&DataKey::OracleCount, &0u32);
 
         // Initialize multi-sig with single admin (can be updated later)
-        let mut admin_signers = Vec::new(&env);
-        admin_signers.push_back(admin.clone());
-        env.storage()
-            .persistent()
-            .set(&Symbol::new(&env, ADMIN_SIGNERS_KEY), &admin_signers);
+        access::grant_role(&env, Role::Admin, &admin);
 
         // Default: require 2 of 3 signatures for emergency override
         env.storage()
             .persistent()
-            .set(&Symbol::new(&env, REQUIRED_SIGNATURES_KEY), &2u32);
+            .set(&DataKey::RequiredSignatures, &2u32);
 
         // Default cooldown: 24 hours (86400 seconds)
         env.storage()
             .persistent()
-            .set(&Symbol::new(&env, OVERRIDE_COOLDOWN_KEY), &86400u64);
+            .set(&DataKey::OverrideCooldown, &86400u64);
 
         // Initialize last override time to 0
         env.storage()
             .persistent()
-            .set(&Symbol::new(&env, LAST_OVERRIDE_TIME_KEY), &0u64);
+            .set(&DataKey::LastOverrideTime, &0u64);
 
         // Emit initialization event
-        OracleInitializedEvent {
-            admin,
-            required_consensus,
-        }
-        .publish(&env);
+        events::oracle::oracle_initialized(&env, admin, required_consensus);
     }
 
-    /// Register a new oracle node
-    pub fn register_oracle(env: Env, oracle: Address, oracle_name: Symbol) {
+    /// Register a new oracle node, assigned to the market `categories` it covers
+    pub fn register_oracle(
+        env: Env,
+        oracle: Address,
+        oracle_name: Symbol,
+        categories: Vec<Symbol>,
+    ) {
         // Require admin authentication
-        let admin: Address = env
-            .storage()
-            .persistent()
-            .get(&Symbol::new(&env, ADMIN_KEY))
-            .unwrap();
+        let admin: Address = env.storage().persistent().get(&DataKey::Admin).unwrap();
         admin.require_auth();
 
         // Get current oracle count
         let oracle_count: u32 = env
             .storage()
             .persistent()
-            .get(&Symbol::new(&env, ORACLE_COUNT_KEY))
+            .get(&DataKey::OracleCount)
             .unwrap_or(0);
 
         // Validate total_oracles < max_oracles (max 10 oracles)
-        if oracle_count >= 10 {
+        if oracle_count >= MAX_ORACLES {
             panic!("Maximum oracle limit reached");
         }
 
         // Create storage key for this oracle using the oracle address
-        let oracle_key = (Symbol::new(&env, "oracle"), oracle.clone());
+        let oracle_key = DataKey::Oracle(oracle.clone());
 
         // Check if oracle already registered
         let is_registered: bool = env.storage().persistent().has(&oracle_key);
@@ -208,24 +557,37 @@ impl OracleManager {
         // Store oracle metadata
         env.storage().persistent().set(&oracle_key, &true);
 
+        let list_key = DataKey::OracleList;
+        let mut oracles: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&list_key)
+            .unwrap_or(Vec::new(&env));
+        oracles.push_back(oracle.clone());
+        env.storage().persistent().set(&list_key, &oracles);
+
         // Store oracle name
-        let oracle_name_key = (Symbol::new(&env, "oracle_name"), oracle.clone());
+        let oracle_name_key = DataKey::OracleName(oracle.clone());
         env.storage()
             .persistent()
             .set(&oracle_name_key, &oracle_name);
+        env.storage()
+            .persistent()
+            .set(&DataKey::OracleCategories(oracle.clone()), &categories);
+        Self::adjust_category_counts(&env, &categories, true);
 
         // Initialize oracle's accuracy score at 100%
-        let accuracy_key = (Symbol::new(&env, "oracle_accuracy"), oracle.clone());
+        let accuracy_key = DataKey::OracleAccuracy(oracle.clone());
         env.storage().persistent().set(&accuracy_key, &100u32);
 
         // Initialize oracle's stake (required for slashing)
-        let stake_key = (Symbol::new(&env, ORACLE_STAKE_KEY), oracle.clone());
+        let stake_key = DataKey::OracleStake(oracle.clone());
         env.storage()
             .persistent()
             .set(&stake_key, &(CHALLENGE_STAKE_AMOUNT * 10)); // 10x challenge stake
 
         // Store registration timestamp
-        let timestamp_key = (Symbol::new(&env, "oracle_timestamp"), oracle.clone());
+        let timestamp_key = DataKey::OracleTimestamp(oracle.clone());
         env.storage()
             .persistent()
             .set(&timestamp_key, &env.ledger().timestamp());
@@ -233,15 +595,43 @@ impl OracleManager {
         // Increment oracle counter
         env.storage()
             .persistent()
-            .set(&Symbol::new(&env, ORACLE_COUNT_KEY), &(oracle_count + 1));
+            .set(&DataKey::OracleCount, &safe_add_u32(oracle_count, 1));
 
         // Emit OracleRegistered event
-        OracleRegisteredEvent {
-            oracle,
-            oracle_name,
-            timestamp: env.ledger().timestamp(),
+        events::oracle::oracle_registered(&env, oracle, oracle_name, env.ledger().timestamp());
+    }
+
+    /// Admin: Replace the market categories a registered oracle covers
+    pub fn set_oracle_categories(env: Env, oracle: Address, categories: Vec<Symbol>) {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .expect("Oracle not initialized");
+        admin.require_auth();
+
+        let oracle_key = DataKey::Oracle(oracle.clone());
+        if !env.storage().persistent().has(&oracle_key) {
+            panic!("Oracle not registered");
         }
-        .publish(&env);
+        if env.storage().persistent().get(&oracle_key).unwrap_or(false) {
+            let previous = Self::get_oracle_categories(env.clone(), oracle.clone());
+            Self::adjust_category_counts(&env, &previous, false);
+            Self::adjust_category_counts(&env, &categories, true);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::OracleCategories(oracle.clone()), &categories);
+
+        events::oracle::oracle_categories_updated(&env, oracle, categories);
+    }
+
+    /// Get the market categories an oracle covers
+    pub fn get_oracle_categories(env: Env, oracle: Address) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OracleCategories(oracle))
+            .unwrap_or(Vec::new(&env))
     }
 
     /// Deregister an oracle node
@@ -254,12 +644,12 @@ impl OracleManager {
         let admin: Address = env
             .storage()
             .persistent()
-            .get(&Symbol::new(&env, ADMIN_KEY))
+            .get(&DataKey::Admin)
             .expect("Contract not initialized");
         admin.require_auth();
 
         // 2. Validate oracle is currently registered and active
-        let oracle_key = (Symbol::new(&env, "oracle"), oracle.clone());
+        let oracle_key = DataKey::Oracle(oracle.clone());
         let is_active: bool = env.storage().persistent().get(&oracle_key).unwrap_or(false);
 
         if !is_active {
@@ -268,146 +658,473 @@ impl OracleManager {
 
         // 3. Mark oracle as inactive (don't delete, keep for history)
         env.storage().persistent().set(&oracle_key, &false);
+        let categories = Self::get_oracle_categories(env.clone(), oracle.clone());
+        Self::adjust_category_counts(&env, &categories, false);
 
         // 4. Decrement oracle count
         let oracle_count: u32 = env
             .storage()
             .persistent()
-            .get(&Symbol::new(&env, ORACLE_COUNT_KEY))
+            .get(&DataKey::OracleCount)
             .unwrap_or(0);
         if oracle_count > 0 {
             let new_count = oracle_count - 1;
             env.storage()
                 .persistent()
-                .set(&Symbol::new(&env, ORACLE_COUNT_KEY), &new_count);
+                .set(&DataKey::OracleCount, &new_count);
 
             // 5. Recalculate consensus threshold
             // Threshold should not exceed the number of active oracles
             let current_threshold: u32 = env
                 .storage()
                 .persistent()
-                .get(&Symbol::new(&env, REQUIRED_CONSENSUS_KEY))
+                .get(&DataKey::RequiredConsensus)
                 .unwrap_or(0);
             if current_threshold > new_count {
                 env.storage()
                     .persistent()
-                    .set(&Symbol::new(&env, REQUIRED_CONSENSUS_KEY), &new_count);
+                    .set(&DataKey::RequiredConsensus, &new_count);
             }
         }
 
         // 6. Emit OracleDeregistered event
-        OracleDeregisteredEvent {
-            oracle,
-            timestamp: env.ledger().timestamp(),
-        }
-        .publish(&env);
+        events::oracle::oracle_deregistered(&env, oracle, env.ledger().timestamp());
     }
 
     /// Register a market with its resolution time for attestation validation
     /// Must be called before oracles can submit attestations for this market.
-    pub fn register_market(env: Env, market_id: BytesN<32>, resolution_time: u64) {
+    ///
+    /// Only oracles assigned to `category` may attest on the market. With
+    /// CATEGORY_ANY, or a category no active oracle covers, any registered
+    /// oracle may.
+    pub fn register_market(
+        env: Env,
+        market_id: BytesN<32>,
+        resolution_time: u64,
+        category: Symbol,
+    ) {
         // Require admin authentication
         let admin: Address = env
             .storage()
             .persistent()
-            .get(&Symbol::new(&env, ADMIN_KEY))
+            .get(&DataKey::Admin)
             .expect("Oracle not initialized");
         admin.require_auth();
 
+        Self::store_market(&env, &admin, market_id, resolution_time, category);
+    }
+
+    /// Register a market on behalf of an authorized registrar (e.g. the factory)
+    ///
+    /// The admin is accepted here too. Unlike register_market, this path cannot
+    /// re-register an existing market.
+    pub fn register_market_as(
+        env: Env,
+        registrar: Address,
+        market_id: BytesN<32>,
+        resolution_time: u64,
+        category: Symbol,
+    ) {
+        registrar.require_auth();
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .expect("Oracle not initialized");
+        if registrar != admin && !Self::is_registrar(env.clone(), registrar.clone()) {
+            panic!("Not an authorized registrar");
+        }
+
+        let market_key = DataKey::MarketResTime(market_id.clone());
+        if env.storage().persistent().has(&market_key) {
+            panic!("Market already registered");
+        }
+
+        Self::store_market(&env, &registrar, market_id, resolution_time, category);
+    }
+
+    /// Get the category a market was registered under (CATEGORY_ANY if unregistered)
+    pub fn get_market_category(env: Env, market_id: BytesN<32>) -> Symbol {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MarketCategory(market_id))
+            .unwrap_or(Symbol::new(&env, CATEGORY_ANY))
+    }
+
+    /// Get the number of active oracles covering `category`
+    pub fn get_category_oracle_count(env: Env, category: Symbol) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CategoryOracleCount(category))
+            .unwrap_or(0)
+    }
+
+    /// Helper: Move the active-oracle count of each distinct category by one
+    fn adjust_category_counts(env: &Env, categories: &Vec<Symbol>, increment: bool) {
+        for (i, category) in categories.iter().enumerate() {
+            if categories.first_index_of(&category) != Some(i as u32) {
+                continue;
+            }
+            let count = Self::get_category_oracle_count(env.clone(), category.clone());
+            let count = if increment {
+                safe_add_u32(count, 1)
+            } else {
+                count.saturating_sub(1)
+            };
+            env.storage()
+                .persistent()
+                .set(&DataKey::CategoryOracleCount(category), &count);
+        }
+    }
+
+    /// Helper: Market category restricting who may attest, with its active-oracle count
+    ///
+    /// None when any registered oracle may attest: the market has no category,
+    /// or no active oracle covers it.
+    fn restricted_category(env: &Env, market_id: &BytesN<32>) -> Option<(Symbol, u32)> {
+        let category = Self::get_market_category(env.clone(), market_id.clone());
+        if category == Symbol::new(env, CATEGORY_ANY) {
+            return None;
+        }
+        match Self::get_category_oracle_count(env.clone(), category.clone()) {
+            0 => None,
+            count => Some((category, count)),
+        }
+    }
+
+    /// Helper: Panic unless `oracle` may attest on the market's category
+    fn require_category_oracle(env: &Env, market_id: &BytesN<32>, oracle: &Address) {
+        if let Some((category, _)) = Self::restricted_category(env, market_id) {
+            let active: bool = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Oracle(oracle.clone()))
+                .unwrap_or(false);
+            if !active
+                || !Self::get_oracle_categories(env.clone(), oracle.clone()).contains(&category)
+            {
+                panic!("Oracle not assigned to market category");
+            }
+        }
+    }
+
+    /// Helper: Votes needed for consensus on a market
+    ///
+    /// The configured count, capped at the number of oracles eligible for the
+    /// market's category.
+    fn consensus_threshold(env: &Env, market_id: &BytesN<32>) -> u32 {
+        let required: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RequiredConsensus)
+            .unwrap_or(0);
+        match Self::restricted_category(env, market_id) {
+            Some((_, eligible)) => required.min(eligible),
+            None => required,
+        }
+    }
+
+    /// Helper: Record a market's resolution time, registrar and category, and reset its attestation counts
+    fn store_market(
+        env: &Env,
+        registrar: &Address,
+        market_id: BytesN<32>,
+        resolution_time: u64,
+        category: Symbol,
+    ) {
         // Store market resolution time
-        let market_key = (Symbol::new(&env, MARKET_RES_TIME_KEY), market_id.clone());
+        let market_key = DataKey::MarketResTime(market_id.clone());
         env.storage()
             .persistent()
             .set(&market_key, &resolution_time);
+        env.storage()
+            .persistent()
+            .set(&DataKey::MarketRegistrar(market_id.clone()), registrar);
+        env.storage()
+            .persistent()
+            .set(&DataKey::MarketCategory(market_id.clone()), &category);
 
         // Initialize attestation counts for this market
-        let yes_count_key = (Symbol::new(&env, ATTEST_COUNT_YES_KEY), market_id.clone());
-        let no_count_key = (Symbol::new(&env, ATTEST_COUNT_NO_KEY), market_id.clone());
+        let yes_count_key = DataKey::AttestYes(market_id.clone());
+        let no_count_key = DataKey::AttestNo(market_id.clone());
         env.storage().persistent().set(&yes_count_key, &0u32);
         env.storage().persistent().set(&no_count_key, &0u32);
 
         // Emit market registered event
-        MarketRegisteredEvent {
-            market_id,
-            resolution_time,
-        }
-        .publish(&env);
+        events::oracle::market_registered(env, market_id, resolution_time);
     }
 
-    /// Get market resolution time (helper function)
-    pub fn get_market_resolution_time(env: Env, market_id: BytesN<32>) -> Option<u64> {
-        let market_key = (Symbol::new(&env, MARKET_RES_TIME_KEY), market_id);
-        env.storage().persistent().get(&market_key)
+    /// Admin: Allow `registrar` to register markets via register_market_as
+    pub fn add_registrar(env: Env, admin: Address, registrar: Address) {
+        Self::set_registrar(&env, &admin, registrar, true);
     }
 
-    /// Get attestation counts for a market
-    pub fn get_attestation_counts(env: Env, market_id: BytesN<32>) -> (u32, u32) {
-        let yes_count_key = (Symbol::new(&env, ATTEST_COUNT_YES_KEY), market_id.clone());
-        let no_count_key = (Symbol::new(&env, ATTEST_COUNT_NO_KEY), market_id);
+    /// Admin: Revoke a registrar
+    pub fn remove_registrar(env: Env, admin: Address, registrar: Address) {
+        Self::set_registrar(&env, &admin, registrar, false);
+    }
 
-        let yes_count: u32 = env.storage().persistent().get(&yes_count_key).unwrap_or(0);
-        let no_count: u32 = env.storage().persistent().get(&no_count_key).unwrap_or(0);
+    /// Whether `registrar` may register markets
+    pub fn is_registrar(env: Env, registrar: Address) -> bool {
+        access::has_role(&env, Role::Registrar, &registrar)
+    }
 
-        (yes_count, no_count)
+    /// Get the admin or registrar that last registered `market_id`
+    ///
+    /// Unaffected by later registrar revocations.
+    pub fn get_market_registrar(env: Env, market_id: BytesN<32>) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MarketRegistrar(market_id))
     }
 
-    /// Get attestation record for an oracle on a market
-    pub fn get_attestation(
-        env: Env,
-        market_id: BytesN<32>,
-        oracle: Address,
-    ) -> Option<Attestation> {
-        let attestation_key = (Symbol::new(&env, "attestation"), market_id, oracle);
-        env.storage().persistent().get(&attestation_key)
+    fn set_registrar(env: &Env, admin: &Address, registrar: Address, enabled: bool) {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .expect("Oracle not initialized");
+        if *admin != stored_admin {
+            panic!("Unauthorized");
+        }
+
+        if enabled {
+            access::grant_role(env, Role::Registrar, &registrar);
+        } else {
+            access::revoke_role(env, Role::Registrar, &registrar);
+        }
+
+        events::oracle::registrar_updated(env, registrar, enabled);
     }
 
-    /// Submit oracle attestation for market result
+    /// Admin: Bind the deployed market contract for a registered market_id
     ///
-    /// Validates:
-    /// - Caller is a trusted attestor (registered oracle)
-    /// - Market is past resolution_time
-    /// - Outcome is valid (0=NO, 1=YES)
-    /// - Oracle hasn't already attested
-    pub fn submit_attestation(
+    /// The bound contract may then push resolution-time updates for its market.
+    pub fn set_market_contract(env: Env, market_id: BytesN<32>, market_contract: Address) {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .expect("Oracle not initialized");
+        admin.require_auth();
+
+        let market_key = DataKey::MarketResTime(market_id.clone());
+        if !env.storage().persistent().has(&market_key) {
+            panic!("Market not registered");
+        }
+
+        env.storage().persistent().set(
+            &DataKey::MarketContract(market_id.clone()),
+            &market_contract,
+        );
+
+        events::oracle::market_contract_bound(&env, market_id, market_contract);
+    }
+
+    /// Get the market contract bound to a market_id, if any
+    pub fn get_market_contract(env: Env, market_id: BytesN<32>) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MarketContract(market_id))
+    }
+
+    /// Push back a market's resolution time (called by the bound market contract)
+    ///
+    /// Rejected once any attestation exists, so attestations are always validated
+    /// against the time they were submitted under.
+    pub fn update_resolution_time(env: Env, market_id: BytesN<32>, new_time: u64) {
+        let market_contract: Address = Self::get_market_contract(env.clone(), market_id.clone())
+            .expect("Market contract not bound");
+        market_contract.require_auth();
+
+        let market_key = DataKey::MarketResTime(market_id.clone());
+        let old_time: u64 = env
+            .storage()
+            .persistent()
+            .get(&market_key)
+            .expect("Market not registered");
+
+        let (yes_count, no_count) = Self::get_attestation_counts(env.clone(), market_id.clone());
+        if yes_count + no_count > 0 {
+            panic!("Attestations already submitted");
+        }
+        if new_time <= old_time {
+            panic!("Resolution time can only move later");
+        }
+
+        env.storage().persistent().set(&market_key, &new_time);
+
+        events::oracle::resolution_time_updated(&env, market_id, old_time, new_time);
+    }
+
+    /// Get market resolution time (helper function)
+    pub fn get_market_resolution_time(env: Env, market_id: BytesN<32>) -> Option<u64> {
+        let market_key = DataKey::MarketResTime(market_id);
+        env.storage().persistent().get(&market_key)
+    }
+
+    /// Get attestation counts for a market
+    pub fn get_attestation_counts(env: Env, market_id: BytesN<32>) -> (u32, u32) {
+        let yes_count_key = DataKey::AttestYes(market_id.clone());
+        let no_count_key = DataKey::AttestNo(market_id);
+
+        let yes_count: u32 = env.storage().persistent().get(&yes_count_key).unwrap_or(0);
+        let no_count: u32 = env.storage().persistent().get(&no_count_key).unwrap_or(0);
+
+        (yes_count, no_count)
+    }
+
+    /// Get attestation record for an oracle on a market
+    pub fn get_attestation(
+        env: Env,
+        market_id: BytesN<32>,
+        oracle: Address,
+    ) -> Option<Attestation> {
+        let attestation_key = DataKey::Attestation(market_id, oracle);
+        env.storage().persistent().get(&attestation_key)
+    }
+
+    /// Oracle: Delegate attestation submission to a hot key (replaces any previous one)
+    pub fn set_submitter(env: Env, oracle: Address, submitter: Address) {
+        oracle.require_auth();
+
+        let oracle_key = DataKey::Oracle(oracle.clone());
+        if !env.storage().persistent().get(&oracle_key).unwrap_or(false) {
+            panic!("Oracle not registered");
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Submitter(oracle.clone()), &submitter);
+
+        events::oracle::submitter_updated(&env, oracle, Some(submitter));
+    }
+
+    /// Oracle: Revoke the delegated submitter key
+    pub fn revoke_submitter(env: Env, oracle: Address) {
+        oracle.require_auth();
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Submitter(oracle.clone()));
+
+        events::oracle::submitter_updated(&env, oracle, None);
+    }
+
+    /// Get the delegated submitter key for an oracle, if any
+    pub fn get_submitter(env: Env, oracle: Address) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Submitter(oracle))
+    }
+
+    /// Submit oracle attestation for market result
+    ///
+    /// Validates:
+    /// - Caller is a trusted attestor (registered oracle)
+    /// - Market is past resolution_time
+    /// - Outcome is valid (0=NO, 1=YES)
+    /// - Oracle hasn't already attested
+    /// - `metadata` fits in MAX_METADATA_LEN bytes and, in strict mode,
+    ///   `data_hash` is sha256(result as u32 BE || metadata)
+    pub fn submit_attestation(
         env: Env,
         oracle: Address,
         market_id: BytesN<32>,
         attestation_result: u32,
-        _data_hash: BytesN<32>,
+        data_hash: BytesN<32>,
+        metadata: Option<Bytes>,
     ) {
         // 1. Require oracle authentication
         oracle.require_auth();
 
+        Self::record_attestation(
+            &env,
+            oracle.clone(),
+            oracle,
+            market_id,
+            attestation_result,
+            data_hash,
+            metadata,
+        );
+    }
+
+    /// Submit an attestation on behalf of `oracle` from its delegated submitter key
+    ///
+    /// The attestation, accuracy, rewards and challenges all attach to `oracle`;
+    /// `submitter` is only recorded alongside it.
+    pub fn submit_attestation_as(
+        env: Env,
+        submitter: Address,
+        oracle: Address,
+        market_id: BytesN<32>,
+        attestation_result: u32,
+        data_hash: BytesN<32>,
+        metadata: Option<Bytes>,
+    ) {
+        submitter.require_auth();
+        if Self::get_submitter(env.clone(), oracle.clone()) != Some(submitter.clone()) {
+            panic!("Not the oracle's submitter");
+        }
+
+        Self::record_attestation(
+            &env,
+            oracle,
+            submitter,
+            market_id,
+            attestation_result,
+            data_hash,
+            metadata,
+        );
+    }
+
+    /// Helper: Validate and store an attestation for an authenticated oracle
+    fn record_attestation(
+        env: &Env,
+        oracle: Address,
+        submitter: Address,
+        market_id: BytesN<32>,
+        attestation_result: u32,
+        data_hash: BytesN<32>,
+        metadata: Option<Bytes>,
+    ) {
         // 2. Validate oracle is registered (trusted attestor)
-        let oracle_key = (Symbol::new(&env, "oracle"), oracle.clone());
+        let oracle_key = DataKey::Oracle(oracle.clone());
         let is_registered: bool = env.storage().persistent().get(&oracle_key).unwrap_or(false);
         if !is_registered {
             panic!("Oracle not registered");
         }
 
         // 3. Validate market is registered and past resolution_time
-        let market_key = (Symbol::new(&env, MARKET_RES_TIME_KEY), market_id.clone());
+        let market_key = DataKey::MarketResTime(market_id.clone());
         let resolution_time: u64 = env
             .storage()
             .persistent()
             .get(&market_key)
             .expect("Market not registered");
 
-        let current_time = env.ledger().timestamp();
-        if current_time < resolution_time {
+        if !time::has_reached(env, resolution_time) {
             panic!("Cannot attest before resolution time");
         }
+        Self::require_category_oracle(env, &market_id, &oracle);
 
         // 4. Validate result is binary (0 or 1)
         if attestation_result > 1 {
             panic!("Invalid attestation result");
         }
 
-        // 5. Check if oracle already attested
-        let vote_key = (Symbol::new(&env, "vote"), market_id.clone(), oracle.clone());
+        let mut payload = Bytes::new(env);
+        payload.extend_from_array(&attestation_result.to_be_bytes());
+        check_payload(env, payload, &data_hash, &metadata);
+
+        // 5. Check if oracle already attested (a voided attestation still counts)
+        let vote_key = DataKey::Vote(market_id.clone(), oracle.clone());
         if env.storage().persistent().has(&vote_key) {
             panic!("Oracle already attested");
         }
+        let voided_key = DataKey::Voided(market_id.clone(), oracle.clone());
+        if env.storage().persistent().has(&voided_key) {
+            panic!("Attestation was invalidated");
+        }
 
         // 6. Store vote for consensus
         env.storage()
@@ -417,107 +1134,570 @@ impl OracleManager {
         // 7. Store attestation with timestamp
         let attestation = Attestation {
             attestor: oracle.clone(),
+            submitter: submitter.clone(),
             outcome: attestation_result,
-            timestamp: current_time,
+            timestamp: env.ledger().timestamp(),
+            data_hash,
+            metadata,
         };
-        let attestation_key = (
-            Symbol::new(&env, "attestation"),
-            market_id.clone(),
-            oracle.clone(),
-        );
+        let attestation_key = DataKey::Attestation(market_id.clone(), oracle.clone());
         env.storage()
             .persistent()
             .set(&attestation_key, &attestation);
 
         // 8. Track oracle in market's voter list
-        let voters_key = (Symbol::new(&env, "voters"), market_id.clone());
+        let voters_key = DataKey::Voters(market_id.clone());
         let mut voters: Vec<Address> = env
             .storage()
             .persistent()
             .get(&voters_key)
-            .unwrap_or(Vec::new(&env));
+            .unwrap_or(Vec::new(env));
 
         voters.push_back(oracle.clone());
         env.storage().persistent().set(&voters_key, &voters);
 
-        // 9. Update attestation count per outcome
+        // 9. Hold the attestation bond, if bonding is enabled
+        Self::post_bond(env, &market_id, &oracle, &submitter, attestation_result);
+
+        // 10. Update attestation count per outcome
         if attestation_result == 1 {
-            let yes_count_key = (Symbol::new(&env, ATTEST_COUNT_YES_KEY), market_id.clone());
+            let yes_count_key = DataKey::AttestYes(market_id.clone());
             let current_count: u32 = env.storage().persistent().get(&yes_count_key).unwrap_or(0);
             env.storage()
                 .persistent()
-                .set(&yes_count_key, &(current_count + 1));
+                .set(&yes_count_key, &safe_add_u32(current_count, 1));
         } else {
-            let no_count_key = (Symbol::new(&env, ATTEST_COUNT_NO_KEY), market_id.clone());
+            let no_count_key = DataKey::AttestNo(market_id.clone());
             let current_count: u32 = env.storage().persistent().get(&no_count_key).unwrap_or(0);
             env.storage()
                 .persistent()
-                .set(&no_count_key, &(current_count + 1));
+                .set(&no_count_key, &safe_add_u32(current_count, 1));
         }
 
-        // 10. Emit AttestationSubmitted(market_id, attestor, outcome)
-        AttestationSubmittedEvent {
-            market_id,
-            oracle,
-            attestation_result,
-        }
-        .publish(&env);
+        // 11. Emit AttestationSubmitted(market_id, attestor, outcome)
+        events::oracle::attestation_submitted(env, market_id, oracle, attestation_result);
     }
 
     /// Check if consensus has been reached for market
+    ///
+    /// Reads the per-outcome counts kept by `submit_attestation`, so the cost
+    /// does not grow with the number of voters.
     pub fn check_consensus(env: Env, market_id: BytesN<32>) -> (bool, u32) {
-        // 1. Query attestations for market_id
-        let voters_key = (Symbol::new(&env, "voters"), market_id.clone());
+        // 1. Load the running attestation counts
+        let (yes_count, no_count) = Self::get_attestation_counts(env.clone(), market_id.clone());
+        let tally = consensus::Tally::from_counts(yes_count, no_count);
+        debug_assert_eq!(tally, Self::tally_votes(&env, &market_id));
+
+        // 2. Get required threshold for the market's category
+        let threshold = Self::consensus_threshold(&env, &market_id);
+
+        if tally.total_count() < threshold {
+            return (false, 0);
+        }
+
+        // 3. Compare counts against threshold
+        match consensus::count_based(&tally, threshold) {
+            Some(outcome) => (true, outcome),
+            None if Self::get_tie_break_mode(env.clone()) == TieBreakMode::Random
+                && consensus::is_tie(&tally, threshold) =>
+            {
+                let seed = Bytes::from_array(&env, &market_id.to_array());
+                (true, rand::deterministic_choice(&env, &[seed], 2) as u32)
+            }
+            None => (false, 0),
+        }
+    }
+
+    /// Helper: Tally recomputed from the individual votes, for checking the running counts
+    fn tally_votes(env: &Env, market_id: &BytesN<32>) -> consensus::Tally {
         let voters: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Voters(market_id.clone()))
+            .unwrap_or(Vec::new(env));
+
+        let mut tally = consensus::Tally::default();
+        for oracle in voters.iter() {
+            let vote_key = DataKey::Vote(market_id.clone(), oracle);
+            let vote: u32 = env.storage().persistent().get(&vote_key).unwrap_or(0);
+            tally.add_vote(vote, 1);
+        }
+        tally
+    }
+
+    /// Submit a numeric attestation for a scalar market
+    ///
+    /// Same eligibility and metadata rules as `submit_attestation` (the strict
+    /// hash covers `value` as i128 BE); values are kept apart from binary
+    /// votes and settled by `check_value_consensus`.
+    pub fn submit_value(
+        env: Env,
+        oracle: Address,
+        market_id: BytesN<32>,
+        value: i128,
+        data_hash: BytesN<32>,
+        metadata: Option<Bytes>,
+    ) {
+        oracle.require_auth();
+
+        let oracle_key = DataKey::Oracle(oracle.clone());
+        if !env.storage().persistent().get(&oracle_key).unwrap_or(false) {
+            panic!("Oracle not registered");
+        }
+
+        let resolution_time: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MarketResTime(market_id.clone()))
+            .expect("Market not registered");
+        if !time::has_reached(&env, resolution_time) {
+            panic!("Cannot attest before resolution time");
+        }
+        Self::require_category_oracle(&env, &market_id, &oracle);
+
+        let mut payload = Bytes::new(&env);
+        payload.extend_from_array(&value.to_be_bytes());
+        check_payload(&env, payload, &data_hash, &metadata);
+
+        let value_key = DataKey::Value(market_id.clone(), oracle.clone());
+        if env.storage().persistent().has(&value_key) {
+            panic!("Oracle already attested");
+        }
+
+        let voters_key = DataKey::ValueVoters(market_id.clone());
+        let mut voters: Vec<Address> = env
             .storage()
             .persistent()
             .get(&voters_key)
             .unwrap_or(Vec::new(&env));
+        if voters.len() >= MAX_ORACLES {
+            panic!("Too many values submitted");
+        }
+        voters.push_back(oracle.clone());
+
+        env.storage().persistent().set(&value_key, &value);
+        env.storage().persistent().set(&voters_key, &voters);
+        if let Some(metadata) = metadata {
+            env.storage().persistent().set(
+                &DataKey::ValueMetadata(market_id.clone(), oracle.clone()),
+                &metadata,
+            );
+        }
+
+        events::oracle::value_submitted(&env, market_id, oracle, value);
+    }
+
+    /// Get an oracle's numeric attestation for a market
+    pub fn get_value(env: Env, market_id: BytesN<32>, oracle: Address) -> Option<i128> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Value(market_id, oracle))
+    }
+
+    /// Get the metadata blob submitted with an oracle's numeric attestation
+    pub fn get_value_metadata(env: Env, market_id: BytesN<32>, oracle: Address) -> Option<Bytes> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ValueMetadata(market_id, oracle))
+    }
+
+    /// Check whether the numeric attestations for a market agree on a value
+    ///
+    /// Needs at least the required consensus count of values. With staking
+    /// enabled the value is the stake-weighted median and the participating
+    /// stake must exceed the minimum participation share of active stake;
+    /// otherwise it is the plain median.
+    pub fn check_value_consensus(env: Env, market_id: BytesN<32>) -> (bool, i128) {
+        let voters = Self::value_voters(&env, &market_id);
+        let threshold = Self::consensus_threshold(&env, &market_id);
+        if voters.is_empty() || voters.len() < threshold {
+            return (false, 0);
+        }
+
+        let staking = Self::is_staking_enabled(env.clone());
+        if staking {
+            let (participating, total) = Self::participating_stake(&env, &voters);
+            let min_bps = Self::get_min_participation_bps(env.clone());
+            if !consensus::meets_participation(participating, total, min_bps) {
+                return (false, 0);
+            }
+        }
+
+        let mut entries = [(0i128, 0u128); MAX_ORACLES as usize];
+        for (i, oracle) in voters.iter().enumerate() {
+            let value: i128 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Value(market_id.clone(), oracle.clone()))
+                .unwrap_or(0);
+            let weight = if staking {
+                Self::active_stake(&env, &oracle)
+            } else {
+                1
+            };
+            entries[i] = (value, weight);
+        }
+
+        match consensus::weighted_median(&mut entries[..voters.len() as usize]) {
+            Some(value) => (true, value),
+            None => (false, 0),
+        }
+    }
+
+    /// Share of active oracle stake that submitted a value for the market (bps)
+    pub fn get_participation_bps(env: Env, market_id: BytesN<32>) -> u32 {
+        let voters = Self::value_voters(&env, &market_id);
+        let (participating, total) = Self::participating_stake(&env, &voters);
+        if total == 0 {
+            return 0;
+        }
+        (participating * 10_000 / total) as u32
+    }
+
+    /// Admin: Weigh numeric consensus by stake and set the participation floor
+    pub fn set_staking_config(env: Env, enabled: bool, min_participation_bps: u32) {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .expect("Oracle not initialized");
+        admin.require_auth();
+
+        if min_participation_bps > 10_000 {
+            panic!("Participation must be at most 10000 bps");
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::StakingEnabled, &enabled);
+        env.storage()
+            .persistent()
+            .set(&DataKey::MinParticipation, &min_participation_bps);
+
+        events::oracle::staking_config_updated(&env, enabled, min_participation_bps);
+    }
+
+    /// Whether numeric consensus is weighted by oracle stake
+    pub fn is_staking_enabled(env: Env) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::StakingEnabled)
+            .unwrap_or(false)
+    }
+
+    /// Participating stake must exceed this share of active stake (bps)
+    pub fn get_min_participation_bps(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MinParticipation)
+            .unwrap_or(DEFAULT_MIN_PARTICIPATION_BPS)
+    }
+
+    /// Admin: Require a bond of `amount` in `token` with every binary attestation
+    ///
+    /// Bonds on correct attestations are refunded through reclaim_bond; the
+    /// rest go to `treasury` via settle_bonds. An amount of zero disables
+    /// bonding; bonds already posted are settled as usual.
+    pub fn set_attestation_bond(env: Env, token: Address, treasury: Address, amount: i128) {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .expect("Oracle not initialized");
+        admin.require_auth();
+
+        if amount < 0 {
+            panic!("Bond amount must not be negative");
+        }
+
+        let config = BondConfig {
+            token,
+            treasury,
+            amount,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::BondConfig, &config);
+
+        events::oracle::bond_config_updated(&env, config);
+    }
+
+    /// Get the bond settings (None until set_attestation_bond is called)
+    pub fn get_attestation_bond(env: Env) -> Option<BondConfig> {
+        env.storage().persistent().get(&DataKey::BondConfig)
+    }
+
+    /// Get the bond an oracle posted on a market, while it is held
+    pub fn get_bond(env: Env, market_id: BytesN<32>, oracle: Address) -> Option<AttestationBond> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Bond(market_id, oracle))
+    }
+
+    /// Refund an oracle's bond once its market's outcome is recorded
+    ///
+    /// Refundable when the attestation matched the final outcome, or for every
+    /// attestation on a market resolved as OUTCOME_INVALID. A bound market
+    /// cancelled before finalization settles as OUTCOME_INVALID. Invalidated
+    /// attestations are never refunded. Returns the amount paid to the bond's payer.
+    pub fn reclaim_bond(env: Env, oracle: Address, market_id: BytesN<32>) -> i128 {
+        oracle.require_auth();
+
+        let final_outcome = Self::recorded_outcome(&env, &market_id);
+        let bond =
+            Self::get_bond(env.clone(), market_id.clone(), oracle.clone()).expect("No bond held");
+        if !Self::bond_refundable(&env, &market_id, &oracle, &bond, final_outcome) {
+            panic!("Bond forfeited");
+        }
+
+        let mut guard = guard::enter(&env);
+        Self::release_bond(&env, &market_id, &oracle);
+        guard.transfer(
+            &bond.token,
+            &env.current_contract_address(),
+            &bond.payer,
+            bond.amount,
+        );
+        guard.commit();
+
+        events::oracle::bond_refunded(&env, market_id, oracle, bond.amount);
+        bond.amount
+    }
+
+    /// Forfeit the bonds of incorrect attestations to the treasury (permissionless)
+    ///
+    /// Refundable bonds stay held for reclaim_bond. Returns the total forfeited.
+    pub fn settle_bonds(env: Env, market_id: BytesN<32>) -> i128 {
+        let final_outcome = Self::recorded_outcome(&env, &market_id);
+        let treasury = Self::get_attestation_bond(env.clone())
+            .expect("Bonds not configured")
+            .treasury;
+
+        let mut guard = guard::enter(&env);
+        let mut forfeited: i128 = 0;
+        let bonded: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Bonded(market_id.clone()))
+            .unwrap_or(Vec::new(&env));
+        for oracle in bonded.iter() {
+            let Some(bond) = Self::get_bond(env.clone(), market_id.clone(), oracle.clone()) else {
+                continue;
+            };
+            if Self::bond_refundable(&env, &market_id, &oracle, &bond, final_outcome) {
+                continue;
+            }
+            Self::release_bond(&env, &market_id, &oracle);
+            guard.transfer(
+                &bond.token,
+                &env.current_contract_address(),
+                &treasury,
+                bond.amount,
+            );
+            forfeited = safe_add_i128(forfeited, bond.amount);
+        }
+        guard.commit();
+
+        events::oracle::bonds_forfeited(&env, market_id, forfeited);
+        forfeited
+    }
+
+    /// Helper: Take the configured bond from `payer` for `oracle`'s attestation
+    fn post_bond(
+        env: &Env,
+        market_id: &BytesN<32>,
+        oracle: &Address,
+        payer: &Address,
+        outcome: u32,
+    ) {
+        let Some(config) = Self::get_attestation_bond(env.clone()) else {
+            return;
+        };
+        if config.amount == 0 {
+            return;
+        }
+
+        let bond = AttestationBond {
+            payer: payer.clone(),
+            token: config.token.clone(),
+            amount: config.amount,
+            outcome,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Bond(market_id.clone(), oracle.clone()), &bond);
+        let bonded_key = DataKey::Bonded(market_id.clone());
+        let mut bonded: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&bonded_key)
+            .unwrap_or(Vec::new(env));
+        bonded.push_back(oracle.clone());
+        env.storage().persistent().set(&bonded_key, &bonded);
+
+        token::Client::new(env, &config.token).transfer(
+            payer,
+            env.current_contract_address(),
+            &config.amount,
+        );
+    }
+
+    /// Helper: Drop a bond record once it is refunded or forfeited
+    fn release_bond(env: &Env, market_id: &BytesN<32>, oracle: &Address) {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Bond(market_id.clone(), oracle.clone()));
+        let bonded_key = DataKey::Bonded(market_id.clone());
+        let mut bonded: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&bonded_key)
+            .unwrap_or(Vec::new(env));
+        if let Some(index) = bonded.first_index_of(oracle) {
+            bonded.remove(index);
+        }
+        env.storage().persistent().set(&bonded_key, &bonded);
+    }
+
+    /// Helper: Whether a held bond goes back to its payer
+    fn bond_refundable(
+        env: &Env,
+        market_id: &BytesN<32>,
+        oracle: &Address,
+        bond: &AttestationBond,
+        final_outcome: u32,
+    ) -> bool {
+        let voided = env
+            .storage()
+            .persistent()
+            .has(&DataKey::Voided(market_id.clone(), oracle.clone()));
+        !voided && (final_outcome == OUTCOME_INVALID || bond.outcome == final_outcome)
+    }
+
+    /// Helper: The final outcome recorded by finalization or an override
+    ///
+    /// OUTCOME_INVALID when nothing was recorded but the bound market contract
+    /// was cancelled, so its bonds are not stranded.
+    fn recorded_outcome(env: &Env, market_id: &BytesN<32>) -> u32 {
+        if let Some(outcome) = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ConsensusResult(market_id.clone()))
+        {
+            return outcome;
+        }
+        if Self::market_cancelled(env, market_id) {
+            return OUTCOME_INVALID;
+        }
+        panic!("Outcome not recorded")
+    }
+
+    /// Helper: Whether the market contract bound to `market_id` reports Cancelled
+    fn market_cancelled(env: &Env, market_id: &BytesN<32>) -> bool {
+        let Some(market_address) = Self::get_market_contract(env.clone(), market_id.clone()) else {
+            return false;
+        };
+        env.try_invoke_contract::<u32, soroban_sdk::Error>(
+            &market_address,
+            &Symbol::new(env, "get_state"),
+            ().into_val(env),
+        )
+        .ok()
+        .and_then(|result| result.ok())
+            == Some(MARKET_STATUS_CANCELLED)
+    }
+
+    fn value_voters(env: &Env, market_id: &BytesN<32>) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ValueVoters(market_id.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Helper: Stake of an oracle, or 0 once it is deregistered
+    fn active_stake(env: &Env, oracle: &Address) -> u128 {
+        let oracle_key = DataKey::Oracle(oracle.clone());
+        if !env.storage().persistent().get(&oracle_key).unwrap_or(false) {
+            return 0;
+        }
+        let stake: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OracleStake(oracle.clone()))
+            .unwrap_or(0);
+        stake.max(0) as u128
+    }
+
+    /// Helper: (stake of `voters`, stake of every active oracle)
+    fn participating_stake(env: &Env, voters: &Vec<Address>) -> (u128, u128) {
+        let participating = voters
+            .iter()
+            .map(|oracle| Self::active_stake(env, &oracle))
+            .sum();
+        let oracles: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OracleList)
+            .unwrap_or(Vec::new(env));
+        let total = oracles
+            .iter()
+            .map(|oracle| Self::active_stake(env, &oracle))
+            .sum();
+        (participating, total)
+    }
+
+    /// Admin: Choose how evenly split votes are handled
+    ///
+    /// In Random mode the drawn outcome depends on the ledger, so it can differ
+    /// between ledgers until finalize_resolution stores it.
+    pub fn set_tie_break_mode(env: Env, mode: TieBreakMode) {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .expect("Oracle not initialized");
+        admin.require_auth();
+
+        env.storage().persistent().set(&DataKey::TieBreak, &mode);
+
+        events::oracle::tie_break_mode_updated(&env, mode);
+    }
 
-        // 2. Get required threshold
-        let threshold: u32 = env
+    /// Admin: Require each attestation's data_hash to commit to its result and metadata
+    pub fn set_strict_data_hash(env: Env, enabled: bool) {
+        let admin: Address = env
             .storage()
             .persistent()
-            .get(&Symbol::new(&env, REQUIRED_CONSENSUS_KEY))
-            .unwrap_or(0);
+            .get(&DataKey::Admin)
+            .expect("Oracle not initialized");
+        admin.require_auth();
 
-        if voters.len() < threshold {
-            return (false, 0);
-        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::StrictHash, &enabled);
 
-        // 3. Count votes for each outcome
-        let mut yes_votes = 0;
-        let mut no_votes = 0;
+        events::oracle::strict_data_hash_updated(&env, enabled);
+    }
 
-        for oracle in voters.iter() {
-            let vote_key = (Symbol::new(&env, "vote"), market_id.clone(), oracle);
-            let vote: u32 = env.storage().persistent().get(&vote_key).unwrap_or(0);
-            if vote == 1 {
-                yes_votes += 1;
-            } else {
-                no_votes += 1;
-            }
-        }
+    /// Whether strict data_hash checking is on (off by default)
+    pub fn is_strict_data_hash(env: Env) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::StrictHash)
+            .unwrap_or(false)
+    }
 
-        // 4. Compare counts against threshold
-        // Winner is the one that reached the threshold first
-        // If both reach threshold (possible if threshold is low), we favor the one with more votes
-        // If tied and both >= threshold, return false (no clear winner yet)
-        if yes_votes >= threshold && yes_votes > no_votes {
-            (true, 1)
-        } else if no_votes >= threshold && no_votes > yes_votes {
-            (true, 0)
-        } else if yes_votes >= threshold && no_votes >= threshold && yes_votes == no_votes {
-            // Tie scenario appropriately handled: no consensus if tied but threshold met
-            (false, 0)
-        } else {
-            (false, 0)
-        }
+    /// Get the tie-break mode (None unless the admin opted in)
+    pub fn get_tie_break_mode(env: Env) -> TieBreakMode {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TieBreak)
+            .unwrap_or(TieBreakMode::None)
     }
 
     /// Get the consensus result for a market
     pub fn get_consensus_result(env: Env, market_id: BytesN<32>) -> u32 {
-        let result_key = (Symbol::new(&env, "consensus_result"), market_id.clone());
+        let result_key = DataKey::ConsensusResult(market_id.clone());
         env.storage()
             .persistent()
             .get(&result_key)
@@ -527,11 +1707,11 @@ impl OracleManager {
     /// Finalize market resolution after consensus and dispute period
     ///
     /// Called after consensus reached and dispute period elapsed.
-    /// Makes cross-contract call to Market.resolve_market().
-    /// Locks in final outcome permanently.
-    pub fn finalize_resolution(env: Env, market_id: BytesN<32>, _market_address: Address) {
+    /// Delivers the outcome to the market through Market.resolve_with_outcome()
+    /// and locks it in permanently.
+    pub fn finalize_resolution(env: Env, market_id: BytesN<32>, market_address: Address) {
         // 1. Validate market is registered
-        let market_key = (Symbol::new(&env, MARKET_RES_TIME_KEY), market_id.clone());
+        let market_key = DataKey::MarketResTime(market_id.clone());
         let resolution_time: u64 = env
             .storage()
             .persistent()
@@ -548,29 +1728,307 @@ impl OracleManager {
         // 3. Validate dispute period elapsed (7 days = 604800 seconds)
         let current_time = env.ledger().timestamp();
         let dispute_period = 604800u64;
-        if current_time < resolution_time + dispute_period {
+        if !time::has_reached(&env, resolution_time.saturating_add(dispute_period)) {
             panic!("Dispute period not elapsed");
         }
+        let reopened_until: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ReopenedUntil(market_id.clone()))
+            .unwrap_or(0);
+        if !time::has_reached(&env, reopened_until) {
+            panic!("Re-attestation window open");
+        }
+
+        // The running per-outcome counts must cover exactly the recorded voters;
+        // the votes themselves stay authoritative, so a mismatch pages rather than blocks
+        let (yes_count, no_count) = Self::get_attestation_counts(env.clone(), market_id.clone());
+        let voters: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Voters(market_id.clone()))
+            .unwrap_or(Vec::new(&env));
+        if yes_count + no_count != voters.len() {
+            raise_alert(&env, ALERT_INVARIANT, &market_id, SEVERITY_CRITICAL);
+        }
 
         // 4. Store consensus result permanently
-        let result_key = (Symbol::new(&env, "consensus_result"), market_id.clone());
+        let result_key = DataKey::ConsensusResult(market_id.clone());
         env.storage().persistent().set(&result_key, &final_outcome);
+        env.storage()
+            .persistent()
+            .set(&DataKey::FinalizedAt(market_id.clone()), &current_time);
 
-        // 5. Cross-contract call to Market.resolve_market()
-        #[cfg(feature = "market")]
-        {
-            use crate::market::PredictionMarketClient;
-            let market_client = PredictionMarketClient::new(&env, &_market_address);
-            market_client.resolve_market(&market_id);
-        }
+        // 5. Cross-contract call to Market.resolve_with_outcome()
+        env.invoke_contract::<()>(
+            &market_address,
+            &Symbol::new(&env, "resolve_with_outcome"),
+            (market_id.clone(), final_outcome).into_val(&env),
+        );
 
         // 6. Emit ResolutionFinalized event
-        ResolutionFinalizedEvent {
-            market_id,
+        events::oracle::resolution_finalized(&env, market_id.clone(), final_outcome, current_time);
+
+        // 7. Notify subscribed consumer contracts
+        publish_round(&env, &market_id, final_outcome, false);
+    }
+
+    /// Subscribe a contract to a market's resolution
+    ///
+    /// Each time a result is published (finalization, then any emergency
+    /// override) the oracle calls `on_resolution(round: RoundData)` on every
+    /// subscriber, authorizing as itself. A failing callback never blocks
+    /// resolution; the subscriber can read latest_round_data instead.
+    pub fn subscribe_resolution(env: Env, subscriber: Address, market_id: BytesN<32>) {
+        subscriber.require_auth();
+
+        let key = DataKey::Subscribers(market_id.clone());
+        let mut subscribers: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        if subscribers.contains(&subscriber) {
+            panic!("Already subscribed");
+        }
+        if subscribers.len() >= MAX_RESOLUTION_SUBSCRIBERS {
+            panic!("Too many subscribers");
+        }
+        subscribers.push_back(subscriber.clone());
+        env.storage().persistent().set(&key, &subscribers);
+
+        events::oracle::resolution_subscribed(&env, market_id, subscriber);
+    }
+
+    /// Stop calling back `subscriber` for a market
+    pub fn unsubscribe_resolution(env: Env, subscriber: Address, market_id: BytesN<32>) {
+        subscriber.require_auth();
+
+        let key = DataKey::Subscribers(market_id);
+        let mut subscribers: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        let index = subscribers
+            .first_index_of(&subscriber)
+            .expect("Not subscribed");
+        subscribers.remove(index);
+        env.storage().persistent().set(&key, &subscribers);
+    }
+
+    /// Contracts subscribed to a market's resolution
+    pub fn get_resolution_subscribers(env: Env, market_id: BytesN<32>) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Subscribers(market_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Admin: Set or clear the contract paged on high-severity occurrences
+    ///
+    /// The oracle calls `on_alert(kind: Symbol, market_id: BytesN<32>, severity: u32)`
+    /// on the hook, authorizing as itself, after an override, a void escalation,
+    /// an upheld challenge or a tally mismatch at finalization. Delivery is
+    /// best-effort: a failing hook never blocks the operation and is recorded
+    /// in the alert log instead.
+    pub fn set_alert_hook(env: Env, hook: Option<Address>) {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .expect("Oracle not initialized");
+        admin.require_auth();
+
+        let key = DataKey::AlertHook;
+        match &hook {
+            Some(hook) => env.storage().persistent().set(&key, hook),
+            None => env.storage().persistent().remove(&key),
+        }
+        events::oracle::alert_hook_updated(&env, hook);
+    }
+
+    /// Get the configured alert hook
+    pub fn get_alert_hook(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::AlertHook)
+    }
+
+    /// Recent alert deliveries and failures, oldest first (at most MAX_ALERT_LOG)
+    pub fn get_alert_log(env: Env) -> Vec<AlertDelivery> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AlertLog)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Latest published result for a market, if any
+    pub fn latest_round_data(env: Env, market_id: BytesN<32>) -> Option<RoundData> {
+        env.storage().persistent().get(&DataKey::Round(market_id))
+    }
+
+    /// Get the resolution proof for a finalized market
+    ///
+    /// Served from the archive once the market has been cleaned up.
+    pub fn get_resolution_proof(env: Env, market_id: BytesN<32>) -> ResolutionProof {
+        if let Some(archive) = Self::get_resolution_archive(env.clone(), market_id.clone()) {
+            return ResolutionProof {
+                market_id,
+                final_outcome: archive.final_outcome,
+                tally: archive.tally,
+                attestor_digest: archive.attestor_digest,
+            };
+        }
+
+        let result_key = DataKey::ConsensusResult(market_id.clone());
+        let final_outcome: u32 = env
+            .storage()
+            .persistent()
+            .get(&result_key)
+            .expect("Market not finalized");
+
+        ResolutionProof {
+            market_id: market_id.clone(),
             final_outcome,
-            timestamp: current_time,
+            tally: Self::get_attestation_counts(env.clone(), market_id.clone()),
+            attestor_digest: attestor_digest(&env, &market_id),
+        }
+    }
+
+    /// Admin: Archive a finalized market's resolution and delete its detailed records
+    ///
+    /// Stores and emits a ResolutionArchive, then removes per-oracle votes and
+    /// attestations, the voter list and the attestation counts.
+    pub fn cleanup_market(env: Env, market_id: BytesN<32>) -> ResolutionArchive {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .expect("Oracle not initialized");
+        admin.require_auth();
+
+        if Self::get_resolution_archive(env.clone(), market_id.clone()).is_some() {
+            panic!("Market already archived");
+        }
+        if Self::has_active_challenge(env.clone(), market_id.clone()) {
+            panic!("Market has an active challenge");
+        }
+
+        let proof = Self::get_resolution_proof(env.clone(), market_id.clone());
+        let voters_key = DataKey::Voters(market_id.clone());
+        let voters: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&voters_key)
+            .unwrap_or(Vec::new(&env));
+        let mut metadata = Vec::new(&env);
+        for oracle in voters.iter() {
+            if let Some(blob) =
+                Self::get_attestation(env.clone(), market_id.clone(), oracle.clone())
+                    .and_then(|attestation| attestation.metadata)
+            {
+                metadata.push_back((oracle, blob));
+            }
+        }
+        let finalized_at: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::FinalizedAt(market_id.clone()))
+            .unwrap_or(0);
+        let archive = ResolutionArchive {
+            market_id: market_id.clone(),
+            final_outcome: proof.final_outcome,
+            tally: proof.tally,
+            attestor_digest: proof.attestor_digest,
+            finalized_at,
+            overridden: Self::is_manual_override(env.clone(), market_id.clone()),
+            metadata,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Archive(market_id.clone()), &archive);
+
+        events::oracle::resolution_archived(&env, market_id.clone(), archive.clone());
+
+        for oracle in voters.iter() {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Vote(market_id.clone(), oracle.clone()));
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Attestation(market_id.clone(), oracle));
+        }
+        env.storage().persistent().remove(&voters_key);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::AttestYes(market_id.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::AttestNo(market_id));
+
+        archive
+    }
+
+    /// Get the archived resolution of a cleaned-up market
+    pub fn get_resolution_archive(env: Env, market_id: BytesN<32>) -> Option<ResolutionArchive> {
+        env.storage().persistent().get(&DataKey::Archive(market_id))
+    }
+
+    /// Get a market's resolution state (None if the market is not registered)
+    pub fn get_market_resolution_state(
+        env: Env,
+        market_id: BytesN<32>,
+    ) -> Option<MarketResolutionState> {
+        Self::resolution_state(&env, &market_id)
+    }
+
+    /// Batched get_market_resolution_state, in input order (at most MAX_RESOLUTION_BATCH ids)
+    pub fn get_resolution_states(
+        env: Env,
+        market_ids: Vec<BytesN<32>>,
+    ) -> Vec<Option<MarketResolutionState>> {
+        if market_ids.len() > MAX_RESOLUTION_BATCH {
+            panic!("Too many markets");
         }
-        .publish(&env);
+        let mut states = Vec::new(&env);
+        for market_id in market_ids.iter() {
+            states.push_back(Self::resolution_state(&env, &market_id));
+        }
+        states
+    }
+
+    /// Helper: Assemble the resolution snapshot shared by the single and batched getters
+    fn resolution_state(env: &Env, market_id: &BytesN<32>) -> Option<MarketResolutionState> {
+        let resolution_time = Self::get_market_resolution_time(env.clone(), market_id.clone())?;
+        let archive = Self::get_resolution_archive(env.clone(), market_id.clone());
+        let final_outcome: Option<u32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ConsensusResult(market_id.clone()));
+
+        // Once finalized the stored result is authoritative (votes may be cleaned up)
+        let (consensus_reached, consensus_outcome) = match final_outcome {
+            Some(outcome) => (true, outcome),
+            None => Self::check_consensus(env.clone(), market_id.clone()),
+        };
+        Some(MarketResolutionState {
+            market_id: market_id.clone(),
+            resolution_time,
+            tally: match &archive {
+                Some(archive) => archive.tally,
+                None => Self::get_attestation_counts(env.clone(), market_id.clone()),
+            },
+            consensus_reached,
+            consensus_outcome,
+            finalized: final_outcome.is_some(),
+            finalized_at: env
+                .storage()
+                .persistent()
+                .get(&DataKey::FinalizedAt(market_id.clone()))
+                .unwrap_or(0),
+            overridden: Self::is_manual_override(env.clone(), market_id.clone()),
+            challenged: Self::has_active_challenge(env.clone(), market_id.clone()),
+            archived: archive.is_some(),
+        })
     }
 
     /// Challenge an attestation (dispute oracle honesty)
@@ -588,29 +2046,21 @@ impl OracleManager {
         challenger.require_auth();
 
         // 2. Validate oracle is registered
-        let oracle_key = (Symbol::new(&env, "oracle"), oracle.clone());
+        let oracle_key = DataKey::Oracle(oracle.clone());
         let is_registered: bool = env.storage().persistent().get(&oracle_key).unwrap_or(false);
         if !is_registered {
             panic!("Oracle not registered");
         }
 
         // 3. Validate attestation exists
-        let attestation_key = (
-            Symbol::new(&env, "attestation"),
-            market_id.clone(),
-            oracle.clone(),
-        );
+        let attestation_key = DataKey::Attestation(market_id.clone(), oracle.clone());
         let attestation: Option<Attestation> = env.storage().persistent().get(&attestation_key);
         if attestation.is_none() {
             panic!("Attestation not found");
         }
 
         // 4. Check if challenge already exists for this oracle/market
-        let challenge_key = (
-            Symbol::new(&env, "challenge"),
-            market_id.clone(),
-            oracle.clone(),
-        );
+        let challenge_key = DataKey::Challenge(market_id.clone(), oracle.clone());
         if env.storage().persistent().has(&challenge_key) {
             panic!("Challenge already exists");
         }
@@ -630,17 +2080,18 @@ impl OracleManager {
         env.storage().persistent().set(&challenge_key, &challenge);
 
         // 7. Mark market as having active challenge (pause finalization)
-        let market_challenge_key = (Symbol::new(&env, "market_challenged"), market_id.clone());
+        let market_challenge_key = DataKey::MarketChallenged(market_id.clone());
         env.storage().persistent().set(&market_challenge_key, &true);
+        push_dispute_flag(&env, &market_id, true);
 
         // 8. Emit AttestationChallenged event
-        AttestationChallengedEvent {
+        events::oracle::attestation_challenged(
+            &env,
+            market_id,
             oracle,
             challenger,
-            market_id,
             challenge_reason,
-        }
-        .publish(&env);
+        );
     }
 
     /// Resolve a challenge and update oracle reputation
@@ -657,16 +2108,12 @@ impl OracleManager {
         let admin: Address = env
             .storage()
             .persistent()
-            .get(&Symbol::new(&env, ADMIN_KEY))
+            .get(&DataKey::Admin)
             .expect("Oracle not initialized");
         admin.require_auth();
 
         // 2. Query challenge record
-        let challenge_key = (
-            Symbol::new(&env, "challenge"),
-            market_id.clone(),
-            oracle.clone(),
-        );
+        let challenge_key = DataKey::Challenge(market_id.clone(), oracle.clone());
         let mut challenge: Challenge = env
             .storage()
             .persistent()
@@ -679,11 +2126,11 @@ impl OracleManager {
         }
 
         // 4. Get oracle's current accuracy score
-        let accuracy_key = (Symbol::new(&env, "oracle_accuracy"), oracle.clone());
+        let accuracy_key = DataKey::OracleAccuracy(oracle.clone());
         let mut accuracy: u32 = env.storage().persistent().get(&accuracy_key).unwrap_or(100);
 
         // 5. Get oracle's stake
-        let stake_key = (Symbol::new(&env, ORACLE_STAKE_KEY), oracle.clone());
+        let stake_key = DataKey::OracleStake(oracle.clone());
         let oracle_stake: i128 = env.storage().persistent().get(&stake_key).unwrap_or(0);
 
         let new_reputation: u32;
@@ -702,87 +2149,212 @@ impl OracleManager {
             env.storage().persistent().set(&stake_key, &remaining_stake);
 
             // 6c. Reward challenger with slashed amount
-            let challenger_reward_key = (
-                Symbol::new(&env, "challenger_reward"),
-                challenge.challenger.clone(),
-            );
+            let challenger_reward_key = DataKey::ChallengerReward(challenge.challenger.clone());
             let current_rewards: i128 = env
                 .storage()
                 .persistent()
                 .get(&challenger_reward_key)
                 .unwrap_or(0);
-            env.storage()
-                .persistent()
-                .set(&challenger_reward_key, &(current_rewards + slashed_amount));
+            env.storage().persistent().set(
+                &challenger_reward_key,
+                &safe_add_i128(current_rewards, slashed_amount),
+            );
+
+            // 6d. Discard the dishonest vote so consensus is re-established without it
+            if !Self::is_finalized(&env, &market_id) {
+                Self::void_attestation(&env, &market_id, &oracle);
+            }
 
-            // 6d. If accuracy drops below threshold (50%), deregister oracle
+            // 6e. If accuracy drops below threshold (50%), deregister oracle
             if accuracy < 50 {
-                let oracle_key = (Symbol::new(&env, "oracle"), oracle.clone());
+                let oracle_key = DataKey::Oracle(oracle.clone());
+                if env.storage().persistent().get(&oracle_key).unwrap_or(false) {
+                    let categories = Self::get_oracle_categories(env.clone(), oracle.clone());
+                    Self::adjust_category_counts(&env, &categories, false);
+                }
                 env.storage().persistent().set(&oracle_key, &false);
 
                 // Decrement oracle count
                 let oracle_count: u32 = env
                     .storage()
                     .persistent()
-                    .get(&Symbol::new(&env, ORACLE_COUNT_KEY))
+                    .get(&DataKey::OracleCount)
                     .unwrap_or(0);
                 if oracle_count > 0 {
                     env.storage()
                         .persistent()
-                        .set(&Symbol::new(&env, ORACLE_COUNT_KEY), &(oracle_count - 1));
+                        .set(&DataKey::OracleCount, &(oracle_count - 1));
                 }
 
-                // Emit OracleDeregistered event
-                OracleDeregisteredEvent {
-                    oracle: oracle.clone(),
-                    timestamp: env.ledger().timestamp(),
-                }
-                .publish(&env);
-            }
+                // Emit OracleDeregistered event
+                events::oracle::oracle_deregistered(&env, oracle.clone(), env.ledger().timestamp());
+            }
+        } else {
+            // Challenge is invalid - oracle was honest
+
+            // 7a. Increase oracle's reputation (increase by 5%)
+            accuracy = if accuracy <= 95 { accuracy + 5 } else { 100 };
+            new_reputation = accuracy;
+            slashed_amount = 0;
+
+            // 7b. Penalize false challenger (forfeit their stake)
+            // Challenger's stake goes to oracle
+            let oracle_reward_key = DataKey::OracleReward(oracle.clone());
+            let current_rewards: i128 = env
+                .storage()
+                .persistent()
+                .get(&oracle_reward_key)
+                .unwrap_or(0);
+            env.storage().persistent().set(
+                &oracle_reward_key,
+                &safe_add_i128(current_rewards, CHALLENGE_STAKE_AMOUNT),
+            );
+        }
+
+        // 8. Update oracle's accuracy score
+        env.storage()
+            .persistent()
+            .set(&accuracy_key, &new_reputation);
+
+        // 9. Mark challenge as resolved
+        challenge.resolved = true;
+        env.storage().persistent().set(&challenge_key, &challenge);
+
+        // 10. Remove market challenge flag (allow finalization)
+        let market_challenge_key = DataKey::MarketChallenged(market_id.clone());
+        env.storage().persistent().remove(&market_challenge_key);
+        push_dispute_flag(&env, &market_id, false);
+
+        if challenge_valid {
+            raise_alert(&env, ALERT_ANOMALY, &market_id, SEVERITY_HIGH);
+        }
+
+        // 11. Emit ChallengeResolved event
+        events::oracle::challenge_resolved(
+            &env,
+            oracle,
+            challenge.challenger,
+            challenge_valid,
+            new_reputation,
+            slashed_amount,
+        );
+    }
+
+    /// Admin: Discard an oracle's counted attestation on an unfinalized market
+    ///
+    /// Emergency counterpart of an upheld challenge: the vote leaves the tally
+    /// and voter list, consensus is re-derived without it, and finalization waits
+    /// out the re-attestation window so remaining or standby oracles can attest.
+    /// The voided oracle cannot attest on the market again.
+    pub fn invalidate_attestation(env: Env, market_id: BytesN<32>, oracle: Address) {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .expect("Oracle not initialized");
+        admin.require_auth();
+
+        if Self::is_finalized(&env, &market_id) {
+            panic!("Market already finalized");
+        }
+        Self::void_attestation(&env, &market_id, &oracle);
+    }
+
+    /// Admin: Set how long a voided attestation reopens its market for (seconds)
+    pub fn set_reattestation_window(env: Env, window: u64) {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .expect("Oracle not initialized");
+        admin.require_auth();
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::ReattestWindow, &window);
+
+        events::oracle::reattest_window_updated(&env, window);
+    }
+
+    /// Seconds a voided attestation reopens its market for
+    pub fn get_reattestation_window(env: Env) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ReattestWindow)
+            .unwrap_or(DEFAULT_REATTEST_WINDOW)
+    }
+
+    /// Timestamp finalize_resolution waits for after a voided attestation (0 if none)
+    pub fn get_reopened_until(env: Env, market_id: BytesN<32>) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ReopenedUntil(market_id))
+            .unwrap_or(0)
+    }
+
+    /// Helper: Whether finalize_resolution (or an override) has locked in the outcome
+    fn is_finalized(env: &Env, market_id: &BytesN<32>) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::ConsensusResult(market_id.clone()))
+    }
+
+    /// Helper: Remove `oracle`'s vote from the market's tally and reopen attestation
+    ///
+    /// The vote, its attestation record, the voter list entry and the outcome
+    /// count are removed together, so yes + no still equals the voter count.
+    /// Consensus is derived from the remaining votes; nothing else is cached
+    /// before finalization.
+    fn void_attestation(env: &Env, market_id: &BytesN<32>, oracle: &Address) {
+        let vote_key = DataKey::Vote(market_id.clone(), oracle.clone());
+        let outcome: u32 = env
+            .storage()
+            .persistent()
+            .get(&vote_key)
+            .expect("Attestation not found");
+
+        let count_key = if outcome == 1 {
+            DataKey::AttestYes(market_id.clone())
         } else {
-            // Challenge is invalid - oracle was honest
-
-            // 7a. Increase oracle's reputation (increase by 5%)
-            accuracy = if accuracy <= 95 { accuracy + 5 } else { 100 };
-            new_reputation = accuracy;
-            slashed_amount = 0;
+            DataKey::AttestNo(market_id.clone())
+        };
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        assert_invariant!(env, count > 0, OracleError::InvariantViolation);
+        let count = safe_sub_u32(count, 1);
 
-            // 7b. Penalize false challenger (forfeit their stake)
-            // Challenger's stake goes to oracle
-            let oracle_reward_key = (Symbol::new(&env, "oracle_reward"), oracle.clone());
-            let current_rewards: i128 = env
-                .storage()
-                .persistent()
-                .get(&oracle_reward_key)
-                .unwrap_or(0);
-            env.storage().persistent().set(
-                &oracle_reward_key,
-                &(current_rewards + CHALLENGE_STAKE_AMOUNT),
-            );
-        }
+        let voters_key = DataKey::Voters(market_id.clone());
+        let mut voters: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&voters_key)
+            .unwrap_or(Vec::new(env));
+        let index = voters.first_index_of(oracle);
+        assert_invariant!(env, index.is_some(), OracleError::InvariantViolation);
+        voters.remove(index.unwrap());
 
-        // 8. Update oracle's accuracy score
+        env.storage().persistent().set(&count_key, &count);
+        env.storage().persistent().set(&voters_key, &voters);
+        env.storage().persistent().remove(&vote_key);
         env.storage()
             .persistent()
-            .set(&accuracy_key, &new_reputation);
-
-        // 9. Mark challenge as resolved
-        challenge.resolved = true;
-        env.storage().persistent().set(&challenge_key, &challenge);
+            .remove(&DataKey::Attestation(market_id.clone(), oracle.clone()));
+        env.storage()
+            .persistent()
+            .set(&DataKey::Voided(market_id.clone(), oracle.clone()), &true);
 
-        // 10. Remove market challenge flag (allow finalization)
-        let market_challenge_key = (Symbol::new(&env, "market_challenged"), market_id.clone());
-        env.storage().persistent().remove(&market_challenge_key);
+        let window = Self::get_reattestation_window(env.clone());
+        let reopened_until = env.ledger().timestamp().saturating_add(window);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ReopenedUntil(market_id.clone()), &reopened_until);
 
-        // 11. Emit ChallengeResolved event
-        ChallengeResolvedEvent {
-            oracle,
-            challenger: challenge.challenger,
-            challenge_valid,
-            new_reputation,
-            slashed_amount,
-        }
-        .publish(&env);
+        events::oracle::attestation_invalidated(
+            env,
+            market_id.clone(),
+            oracle.clone(),
+            outcome,
+            reopened_until,
+        );
     }
 
     /// Get all attestations for a market
@@ -844,13 +2416,13 @@ impl OracleManager {
 
     /// Get challenge information for a specific oracle and market
     pub fn get_challenge(env: Env, oracle: Address, market_id: BytesN<32>) -> Option<Challenge> {
-        let challenge_key = (Symbol::new(&env, "challenge"), market_id, oracle);
+        let challenge_key = DataKey::Challenge(market_id, oracle);
         env.storage().persistent().get(&challenge_key)
     }
 
     /// Check if a market has an active (unresolved) challenge
     pub fn has_active_challenge(env: Env, market_id: BytesN<32>) -> bool {
-        let market_challenge_key = (Symbol::new(&env, "market_challenged"), market_id);
+        let market_challenge_key = DataKey::MarketChallenged(market_id);
         env.storage()
             .persistent()
             .get(&market_challenge_key)
@@ -859,13 +2431,48 @@ impl OracleManager {
 
     /// Get oracle's current stake
     pub fn get_oracle_stake(env: Env, oracle: Address) -> i128 {
-        let stake_key = (Symbol::new(&env, ORACLE_STAKE_KEY), oracle);
+        let stake_key = DataKey::OracleStake(oracle);
         env.storage().persistent().get(&stake_key).unwrap_or(0)
     }
 
+    /// Feature flags supported by this oracle instance
+    ///
+    /// Built-in entry points are always listed; `consensus_ready` is reported
+    /// once at least `required_consensus` oracles are registered. Clients should
+    /// check this descriptor instead of probing optional entry points.
+    pub fn get_capabilities(env: Env) -> Vec<Symbol> {
+        let mut capabilities = Vec::new(&env);
+        capabilities.push_back(Symbol::new(&env, CAP_BINARY));
+        capabilities.push_back(Symbol::new(&env, CAP_CHALLENGE));
+        capabilities.push_back(Symbol::new(&env, CAP_OVERRIDE));
+        capabilities.push_back(Symbol::new(&env, CAP_SCALAR));
+        capabilities.push_back(Symbol::new(&env, CAP_SUBSCRIBE));
+
+        let required: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RequiredConsensus)
+            .unwrap_or(0);
+        let oracle_count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OracleCount)
+            .unwrap_or(0);
+        if required > 0 && oracle_count >= required {
+            capabilities.push_back(Symbol::new(&env, CAP_CONSENSUS_READY));
+        }
+
+        capabilities
+    }
+
+    /// Crate version (major, minor, patch) baked in at build time
+    pub fn get_version(_env: Env) -> (u32, u32, u32) {
+        VERSION
+    }
+
     /// Get oracle's accuracy score
     pub fn get_oracle_accuracy(env: Env, oracle: Address) -> u32 {
-        let accuracy_key = (Symbol::new(&env, "oracle_accuracy"), oracle);
+        let accuracy_key = DataKey::OracleAccuracy(oracle);
         env.storage().persistent().get(&accuracy_key).unwrap_or(0)
     }
 
@@ -877,6 +2484,7 @@ impl OracleManager {
     /// - Justification hash for audit trail
     /// - Complete override record stored permanently
     /// - EmergencyOverride event with all details
+    /// - Resolves the market contract bound via set_market_contract, if any
     ///
     /// Parameters:
     /// - approvers: Vec of admin addresses approving this override
@@ -895,17 +2503,67 @@ impl OracleManager {
             panic!("Invalid outcome: must be 0 or 1");
         }
 
-        // 2. Get admin signers and required signatures
-        let admin_signers: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&Symbol::new(&env, ADMIN_SIGNERS_KEY))
-            .expect("Oracle not initialized");
+        Self::apply_override(
+            &env,
+            approvers,
+            market_id.clone(),
+            forced_outcome,
+            justification_hash,
+        );
+
+        if let Some(market_address) = Self::get_market_contract(env.clone(), market_id.clone()) {
+            env.invoke_contract::<()>(
+                &market_address,
+                &Symbol::new(&env, "resolve_with_outcome"),
+                (market_id, forced_outcome).into_val(&env),
+            );
+        }
+    }
+
+    /// Emergency: Declare a market unanswerable (e.g. match abandoned)
+    ///
+    /// Same multi-sig and cooldown rules as `emergency_override`. Records
+    /// OUTCOME_INVALID as the consensus result and resolves the market contract
+    /// as Invalid, which opens refunds of principal to every participant.
+    pub fn mark_unresolvable(
+        env: Env,
+        approvers: Vec<Address>,
+        market_id: BytesN<32>,
+        justification_hash: BytesN<32>,
+        market_address: Address,
+    ) {
+        Self::apply_override(
+            &env,
+            approvers,
+            market_id.clone(),
+            OUTCOME_INVALID,
+            justification_hash,
+        );
+
+        env.invoke_contract::<()>(
+            &market_address,
+            &Symbol::new(&env, "resolve_with_outcome"),
+            (market_id, OUTCOME_INVALID).into_val(&env),
+        );
+    }
+
+    /// Helper: Verify override approvals and record the forced outcome
+    fn apply_override(
+        env: &Env,
+        approvers: Vec<Address>,
+        market_id: BytesN<32>,
+        forced_outcome: u32,
+        justification_hash: BytesN<32>,
+    ) {
+        // 2. Get required signatures (admin signers hold Role::Admin)
+        if !env.storage().persistent().has(&DataKey::Admin) {
+            panic!("Oracle not initialized");
+        }
 
         let required_sigs: u32 = env
             .storage()
             .persistent()
-            .get(&Symbol::new(&env, REQUIRED_SIGNATURES_KEY))
+            .get(&DataKey::RequiredSignatures)
             .unwrap_or(2);
 
         // 3. Validate we have enough approvers
@@ -919,16 +2577,8 @@ impl OracleManager {
             // Require authentication from each approver
             approver.require_auth();
 
-            // Verify approver is in admin_signers list
-            let mut is_valid_admin = false;
-            for admin in admin_signers.iter() {
-                if admin == approver {
-                    is_valid_admin = true;
-                    break;
-                }
-            }
-
-            if !is_valid_admin {
+            // Verify approver is an admin signer
+            if !access::has_role(env, Role::Admin, &approver) {
                 panic!("Invalid approver: not an admin");
             }
 
@@ -944,102 +2594,157 @@ impl OracleManager {
         let last_override_time: u64 = env
             .storage()
             .persistent()
-            .get(&Symbol::new(&env, LAST_OVERRIDE_TIME_KEY))
+            .get(&DataKey::LastOverrideTime)
             .unwrap_or(0);
 
         let cooldown_period: u64 = env
             .storage()
             .persistent()
-            .get(&Symbol::new(&env, OVERRIDE_COOLDOWN_KEY))
+            .get(&DataKey::OverrideCooldown)
             .unwrap_or(86400);
 
         let current_time = env.ledger().timestamp();
 
-        if last_override_time > 0 && (current_time - last_override_time) < cooldown_period {
+        // A ledger clock behind the last override counts as still cooling down
+        let cooldown_ends = safe_add_u64(last_override_time, cooldown_period);
+        if last_override_time > 0 && !time::has_reached(env, cooldown_ends) {
             panic!("Cooldown period not elapsed");
         }
 
         // 7. Verify market exists
-        let market_key = (Symbol::new(&env, MARKET_RES_TIME_KEY), market_id.clone());
+        let market_key = DataKey::MarketResTime(market_id.clone());
         if !env.storage().persistent().has(&market_key) {
             panic!("Market not registered");
         }
 
         // 8. Store consensus result (override any existing consensus)
-        let result_key = (Symbol::new(&env, "consensus_result"), market_id.clone());
+        let result_key = DataKey::ConsensusResult(market_id.clone());
         env.storage().persistent().set(&result_key, &forced_outcome);
 
+        env.storage()
+            .persistent()
+            .set(&DataKey::FinalizedAt(market_id.clone()), &current_time);
+
         // 9. Mark market as manually overridden for audit purposes
-        let override_flag_key = (Symbol::new(&env, "manual_override"), market_id.clone());
+        let override_flag_key = DataKey::ManualOverride(market_id.clone());
         env.storage().persistent().set(&override_flag_key, &true);
 
-        // 10. Create and store complete override record
+        // 10. Create and store complete override record, appended to the audit log
+        let id = Self::get_override_count(env.clone());
         let override_record = EmergencyOverrideRecord {
+            id,
             market_id: market_id.clone(),
             forced_outcome,
             justification_hash: justification_hash.clone(),
+            justification_id: hashing::hash_pair(env, &market_id, &justification_hash),
             approvers: approvers.clone(),
             timestamp: current_time,
         };
 
-        let override_record_key = (Symbol::new(&env, "override_record"), market_id.clone());
+        let override_record_key = DataKey::OverrideRecord(market_id.clone());
         env.storage()
             .persistent()
             .set(&override_record_key, &override_record);
+        env.storage()
+            .persistent()
+            .set(&AuditKey::OverrideLog(id), &override_record);
+        env.storage()
+            .persistent()
+            .set(&AuditKey::OverrideCount, &safe_add_u32(id, 1));
 
         // 11. Update last override timestamp
         env.storage()
             .persistent()
-            .set(&Symbol::new(&env, LAST_OVERRIDE_TIME_KEY), &current_time);
+            .set(&DataKey::LastOverrideTime, &current_time);
+        publish_round(env, &market_id, forced_outcome, true);
 
         // 12. Emit EmergencyOverride event with all details
-        #[contractevent]
-        pub struct EmergencyOverrideEvent {
-            pub market_id: BytesN<32>,
-            pub forced_outcome: u32,
-            pub justification_hash: BytesN<32>,
-            pub approvers: Vec<Address>,
-            pub timestamp: u64,
-        }
-
-        EmergencyOverrideEvent {
-            market_id,
+        events::oracle::emergency_override(
+            env,
+            market_id.clone(),
             forced_outcome,
             justification_hash,
             approvers,
-            timestamp: current_time,
+            current_time,
+        );
+
+        if forced_outcome == OUTCOME_INVALID {
+            raise_alert(env, ALERT_VOID, &market_id, SEVERITY_CRITICAL);
+        } else {
+            raise_alert(env, ALERT_OVERRIDE, &market_id, SEVERITY_CRITICAL);
         }
-        .publish(&env);
     }
 
     /// Get emergency override record for a market (for audit purposes)
     pub fn get_override_record(env: Env, market_id: BytesN<32>) -> Option<EmergencyOverrideRecord> {
-        let override_record_key = (Symbol::new(&env, "override_record"), market_id);
+        let override_record_key = DataKey::OverrideRecord(market_id);
         env.storage().persistent().get(&override_record_key)
     }
 
+    /// Number of overrides recorded across all markets
+    pub fn get_override_count(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&AuditKey::OverrideCount)
+            .unwrap_or(0)
+    }
+
+    /// Get an override audit log entry by id
+    pub fn get_override_by_id(env: Env, id: u32) -> Option<EmergencyOverrideRecord> {
+        env.storage().persistent().get(&AuditKey::OverrideLog(id))
+    }
+
+    /// Page through every override in the order applied (at most MAX_OVERRIDE_PAGE records)
+    ///
+    /// Unlike get_override_record, earlier overrides of a market stay listed
+    /// after it is overridden again.
+    pub fn list_overrides(env: Env, offset: u32, limit: u32) -> Vec<EmergencyOverrideRecord> {
+        let end = offset
+            .saturating_add(limit.min(MAX_OVERRIDE_PAGE))
+            .min(Self::get_override_count(env.clone()));
+
+        let mut records = Vec::new(&env);
+        for id in offset..end {
+            if let Some(record) = Self::get_override_by_id(env.clone(), id) {
+                records.push_back(record);
+            }
+        }
+        records
+    }
+
     /// Check if market was manually overridden
     pub fn is_manual_override(env: Env, market_id: BytesN<32>) -> bool {
-        let override_flag_key = (Symbol::new(&env, "manual_override"), market_id);
+        let override_flag_key = DataKey::ManualOverride(market_id);
         env.storage()
             .persistent()
             .get(&override_flag_key)
             .unwrap_or(false)
     }
 
+    /// Admin: Add a signer to the emergency override multi-sig
+    pub fn add_admin_signer(env: Env, signer: Address) {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .expect("Oracle not initialized");
+        admin.require_auth();
+
+        if !access::grant_role(&env, Role::Admin, &signer) {
+            panic!("Signer already registered");
+        }
+    }
+
     /// Get admin signers list
     pub fn get_admin_signers(env: Env) -> Vec<Address> {
-        env.storage()
-            .persistent()
-            .get(&Symbol::new(&env, ADMIN_SIGNERS_KEY))
-            .unwrap_or(Vec::new(&env))
+        access::role_members(&env, Role::Admin)
     }
 
     /// Get required signatures for emergency override
     pub fn get_required_signatures(env: Env) -> u32 {
         env.storage()
             .persistent()
-            .get(&Symbol::new(&env, REQUIRED_SIGNATURES_KEY))
+            .get(&DataKey::RequiredSignatures)
             .unwrap_or(2)
     }
 
@@ -1047,7 +2752,7 @@ impl OracleManager {
     pub fn get_override_cooldown(env: Env) -> u64 {
         env.storage()
             .persistent()
-            .get(&Symbol::new(&env, OVERRIDE_COOLDOWN_KEY))
+            .get(&DataKey::OverrideCooldown)
             .unwrap_or(86400)
     }
 
@@ -1055,11 +2760,132 @@ impl OracleManager {
     pub fn get_last_override_time(env: Env) -> u64 {
         env.storage()
             .persistent()
-            .get(&Symbol::new(&env, LAST_OVERRIDE_TIME_KEY))
+            .get(&DataKey::LastOverrideTime)
             .unwrap_or(0)
     }
 }
 
+/// Bound the metadata size and, in strict mode, check that data_hash is
+/// sha256(payload || metadata)
+fn check_payload(env: &Env, mut payload: Bytes, data_hash: &BytesN<32>, metadata: &Option<Bytes>) {
+    if let Some(metadata) = metadata {
+        if metadata.len() > MAX_METADATA_LEN {
+            panic_with_error!(env, OracleError::MetadataTooLarge);
+        }
+        payload.append(metadata);
+    }
+    if OracleManager::is_strict_data_hash(env.clone())
+        && BytesN::from(env.crypto().sha256(&payload)) != *data_hash
+    {
+        panic_with_error!(env, OracleError::DataHashMismatch);
+    }
+}
+
+/// Record a new round for a market and call every subscriber's on_resolution
+fn publish_round(env: &Env, market_id: &BytesN<32>, outcome: u32, overridden: bool) {
+    let round_id = OracleManager::latest_round_data(env.clone(), market_id.clone())
+        .map_or(1, |round| safe_add_u32(round.round_id, 1));
+    let round = RoundData {
+        market_id: market_id.clone(),
+        round_id,
+        outcome,
+        attestor_digest: attestor_digest(env, market_id),
+        updated_at: env.ledger().timestamp(),
+        overridden,
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::Round(market_id.clone()), &round);
+
+    for subscriber in OracleManager::get_resolution_subscribers(env.clone(), market_id.clone()) {
+        let delivered = env
+            .try_invoke_contract::<(), soroban_sdk::Error>(
+                &subscriber,
+                &Symbol::new(env, "on_resolution"),
+                (round.clone(),).into_val(env),
+            )
+            .is_ok_and(|result| result.is_ok());
+        events::oracle::resolution_delivered(
+            env,
+            market_id.clone(),
+            subscriber,
+            round_id,
+            delivered,
+        );
+    }
+}
+
+/// Tell the bound market contract, if any, that a challenge opened or closed
+///
+/// Best effort: a market that cannot take the push still sees the challenge
+/// through its own sync_dispute_state.
+fn push_dispute_flag(env: &Env, market_id: &BytesN<32>, disputed: bool) {
+    let Some(market) = OracleManager::get_market_contract(env.clone(), market_id.clone()) else {
+        return;
+    };
+    let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+        &market,
+        &Symbol::new(env, "set_dispute_flag"),
+        (market_id.clone(), disputed).into_val(env),
+    );
+}
+
+/// Page the alert hook, if one is set, and log the attempt
+fn raise_alert(env: &Env, kind: &str, market_id: &BytesN<32>, severity: u32) {
+    let Some(hook) = OracleManager::get_alert_hook(env.clone()) else {
+        return;
+    };
+    let kind = Symbol::new(env, kind);
+    let delivered = env
+        .try_invoke_contract::<(), soroban_sdk::Error>(
+            &hook,
+            &Symbol::new(env, "on_alert"),
+            (kind.clone(), market_id.clone(), severity).into_val(env),
+        )
+        .is_ok_and(|result| result.is_ok());
+
+    let mut log = OracleManager::get_alert_log(env.clone());
+    if log.len() >= MAX_ALERT_LOG {
+        log.pop_front();
+    }
+    log.push_back(AlertDelivery {
+        kind: kind.clone(),
+        market_id: market_id.clone(),
+        severity,
+        hook,
+        timestamp: env.ledger().timestamp(),
+        delivered,
+    });
+    env.storage().persistent().set(&DataKey::AlertLog, &log);
+
+    events::oracle::alert_raised(env, market_id.clone(), kind, severity, delivered);
+}
+
+/// sha256 over the XDR of a market's attestations, sorted by attestor address
+fn attestor_digest(env: &Env, market_id: &BytesN<32>) -> BytesN<32> {
+    let voters: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Voters(market_id.clone()))
+        .unwrap_or(Vec::new(env));
+
+    let mut sorted: Vec<Attestation> = Vec::new(env);
+    for oracle in voters.iter() {
+        let attestation: Attestation = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Attestation(market_id.clone(), oracle))
+            .expect("Attestation not found");
+        let mut index = sorted.len();
+        while index > 0 && sorted.get_unchecked(index - 1).attestor > attestation.attestor {
+            index -= 1;
+        }
+        sorted.insert(index, attestation);
+    }
+
+    env.crypto().sha256(&sorted.to_xdr(env)).into()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1087,8 +2913,8 @@ mod tests {
         oracle1: &Address,
         oracle2: &Address,
     ) {
-        oracle_client.register_oracle(oracle1, &Symbol::new(env, "Oracle1"));
-        oracle_client.register_oracle(oracle2, &Symbol::new(env, "Oracle2"));
+        oracle_client.register_oracle(oracle1, &Symbol::new(env, "Oracle1"), &Vec::new(env));
+        oracle_client.register_oracle(oracle2, &Symbol::new(env, "Oracle2"), &Vec::new(env));
     }
 
     fn create_market_id(env: &Env) -> BytesN<32> {
@@ -1107,7 +2933,11 @@ mod tests {
         let resolution_time = env.ledger().timestamp() + 100;
 
         // Register market
-        oracle_client.register_market(&market_id, &resolution_time);
+        oracle_client.register_market(
+            &market_id,
+            &resolution_time,
+            &Symbol::new(&env, CATEGORY_ANY),
+        );
 
         // Move time forward past resolution
         env.ledger()
@@ -1115,7 +2945,7 @@ mod tests {
 
         // Oracle submits attestation
         let data_hash = BytesN::from_array(&env, &[2u8; 32]);
-        oracle_client.submit_attestation(&oracle1, &market_id, &1, &data_hash);
+        oracle_client.submit_attestation(&oracle1, &market_id, &1, &data_hash, &None);
 
         // Challenger challenges the attestation
         let challenger = Address::generate(&env);
@@ -1168,12 +2998,16 @@ mod tests {
         let market_id = create_market_id(&env);
         let resolution_time = env.ledger().timestamp() + 100;
 
-        oracle_client.register_market(&market_id, &resolution_time);
+        oracle_client.register_market(
+            &market_id,
+            &resolution_time,
+            &Symbol::new(&env, CATEGORY_ANY),
+        );
         env.ledger()
             .with_mut(|li| li.timestamp = resolution_time + 1);
 
         let data_hash = BytesN::from_array(&env, &[2u8; 32]);
-        oracle_client.submit_attestation(&oracle1, &market_id, &1, &data_hash);
+        oracle_client.submit_attestation(&oracle1, &market_id, &1, &data_hash, &None);
 
         let challenger = Address::generate(&env);
         let reason = Symbol::new(&env, "fraud");
@@ -1196,12 +3030,16 @@ mod tests {
         let market_id = create_market_id(&env);
         let resolution_time = env.ledger().timestamp() + 100;
 
-        oracle_client.register_market(&market_id, &resolution_time);
+        oracle_client.register_market(
+            &market_id,
+            &resolution_time,
+            &Symbol::new(&env, CATEGORY_ANY),
+        );
         env.ledger()
             .with_mut(|li| li.timestamp = resolution_time + 1);
 
         let data_hash = BytesN::from_array(&env, &[2u8; 32]);
-        oracle_client.submit_attestation(&oracle1, &market_id, &1, &data_hash);
+        oracle_client.submit_attestation(&oracle1, &market_id, &1, &data_hash, &None);
 
         // Get initial oracle stake and accuracy
         let initial_stake = oracle_client.get_oracle_stake(&oracle1);
@@ -1243,12 +3081,16 @@ mod tests {
         let market_id = create_market_id(&env);
         let resolution_time = env.ledger().timestamp() + 100;
 
-        oracle_client.register_market(&market_id, &resolution_time);
+        oracle_client.register_market(
+            &market_id,
+            &resolution_time,
+            &Symbol::new(&env, CATEGORY_ANY),
+        );
         env.ledger()
             .with_mut(|li| li.timestamp = resolution_time + 1);
 
         let data_hash = BytesN::from_array(&env, &[2u8; 32]);
-        oracle_client.submit_attestation(&oracle1, &market_id, &1, &data_hash);
+        oracle_client.submit_attestation(&oracle1, &market_id, &1, &data_hash, &None);
 
         let initial_stake = oracle_client.get_oracle_stake(&oracle1);
         let _initial_accuracy = oracle_client.get_oracle_accuracy(&oracle1);
@@ -1286,7 +3128,7 @@ mod tests {
         register_test_oracles(&env, &oracle_client, &oracle1, &oracle2);
 
         // Manually set oracle accuracy to 60% (just above threshold)
-        let accuracy_key = (Symbol::new(&env, "oracle_accuracy"), oracle1.clone());
+        let accuracy_key = DataKey::OracleAccuracy(oracle1.clone());
         env.as_contract(&oracle_client.address, || {
             env.storage().persistent().set(&accuracy_key, &60u32);
         });
@@ -1294,12 +3136,16 @@ mod tests {
         let market_id = create_market_id(&env);
         let resolution_time = env.ledger().timestamp() + 100;
 
-        oracle_client.register_market(&market_id, &resolution_time);
+        oracle_client.register_market(
+            &market_id,
+            &resolution_time,
+            &Symbol::new(&env, CATEGORY_ANY),
+        );
         env.ledger()
             .with_mut(|li| li.timestamp = resolution_time + 1);
 
         let data_hash = BytesN::from_array(&env, &[2u8; 32]);
-        oracle_client.submit_attestation(&oracle1, &market_id, &1, &data_hash);
+        oracle_client.submit_attestation(&oracle1, &market_id, &1, &data_hash, &None);
 
         let challenger = Address::generate(&env);
         let reason = Symbol::new(&env, "fraud");
@@ -1314,7 +3160,7 @@ mod tests {
         assert_eq!(new_accuracy, 40);
 
         // Verify oracle was deregistered (marked as inactive)
-        let oracle_key = (Symbol::new(&env, "oracle"), oracle1.clone());
+        let oracle_key = DataKey::Oracle(oracle1.clone());
         let is_active: bool = env
             .as_contract(&oracle_client.address, || {
                 env.storage().persistent().get(&oracle_key)
@@ -1350,12 +3196,16 @@ mod tests {
         let market_id = create_market_id(&env);
         let resolution_time = env.ledger().timestamp() + 100;
 
-        oracle_client.register_market(&market_id, &resolution_time);
+        oracle_client.register_market(
+            &market_id,
+            &resolution_time,
+            &Symbol::new(&env, CATEGORY_ANY),
+        );
         env.ledger()
             .with_mut(|li| li.timestamp = resolution_time + 1);
 
         let data_hash = BytesN::from_array(&env, &[2u8; 32]);
-        oracle_client.submit_attestation(&oracle1, &market_id, &1, &data_hash);
+        oracle_client.submit_attestation(&oracle1, &market_id, &1, &data_hash, &None);
 
         let challenger = Address::generate(&env);
         let reason = Symbol::new(&env, "fraud");
@@ -1377,7 +3227,7 @@ mod tests {
         let (oracle_client, _admin, oracle1, _oracle2) = setup_oracle(&env);
 
         // Register oracle
-        oracle_client.register_oracle(&oracle1, &Symbol::new(&env, "Oracle1"));
+        oracle_client.register_oracle(&oracle1, &Symbol::new(&env, "Oracle1"), &Vec::new(&env));
 
         // Verify stake was initialized
         let stake = oracle_client.get_oracle_stake(&oracle1);
@@ -1418,15 +3268,19 @@ mod tests {
         let market_id = create_market_id(&env);
         let resolution_time = env.ledger().timestamp() + 100;
 
-        oracle_client.register_market(&market_id, &resolution_time);
+        oracle_client.register_market(
+            &market_id,
+            &resolution_time,
+            &Symbol::new(&env, CATEGORY_ANY),
+        );
         env.ledger()
             .with_mut(|li| li.timestamp = resolution_time + 1);
 
         let data_hash = BytesN::from_array(&env, &[2u8; 32]);
 
         // Both oracles submit attestations
-        oracle_client.submit_attestation(&oracle1, &market_id, &1, &data_hash);
-        oracle_client.submit_attestation(&oracle2, &market_id, &0, &data_hash);
+        oracle_client.submit_attestation(&oracle1, &market_id, &1, &data_hash, &None);
+        oracle_client.submit_attestation(&oracle2, &market_id, &0, &data_hash, &None);
 
         let challenger = Address::generate(&env);
         let reason = Symbol::new(&env, "fraud");
@@ -1439,4 +3293,282 @@ mod tests {
         assert!(oracle_client.get_challenge(&oracle1, &market_id).is_some());
         assert!(oracle_client.get_challenge(&oracle2, &market_id).is_some());
     }
+
+    #[test]
+    fn test_check_consensus_catches_counter_drift() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (oracle_client, _admin, oracle1, oracle2) = setup_oracle(&env);
+        register_test_oracles(&env, &oracle_client, &oracle1, &oracle2);
+
+        let market_id = create_market_id(&env);
+        let resolution_time = env.ledger().timestamp() + 100;
+        oracle_client.register_market(
+            &market_id,
+            &resolution_time,
+            &Symbol::new(&env, CATEGORY_ANY),
+        );
+        env.ledger()
+            .with_mut(|li| li.timestamp = resolution_time + 1);
+
+        let data_hash = BytesN::from_array(&env, &[2u8; 32]);
+        oracle_client.submit_attestation(&oracle1, &market_id, &1, &data_hash, &None);
+        oracle_client.submit_attestation(&oracle2, &market_id, &1, &data_hash, &None);
+        assert_eq!(oracle_client.check_consensus(&market_id), (true, 1));
+
+        // A YES count that no longer matches the recorded votes
+        env.as_contract(&oracle_client.address, || {
+            let counted = || {
+                let (yes, no) =
+                    OracleManager::get_attestation_counts(env.clone(), market_id.clone());
+                consensus::Tally::from_counts(yes, no)
+            };
+            assert_eq!(counted(), OracleManager::tally_votes(&env, &market_id));
+            env.storage()
+                .persistent()
+                .set(&DataKey::AttestYes(market_id.clone()), &3u32);
+            assert_ne!(counted(), OracleManager::tally_votes(&env, &market_id));
+        });
+
+        // Debug builds also refuse to report consensus from the drifted counts
+        #[cfg(debug_assertions)]
+        assert!(oracle_client.try_check_consensus(&market_id).is_err());
+    }
+}
+
+#[cfg(test)]
+mod consensus_differential_tests {
+    use super::consensus::{count_based, meets_participation, weighted, weighted_median, Tally};
+
+    /// Small deterministic xorshift PRNG so scenarios are reproducible
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: u64) -> u64 {
+            self.next() % n
+        }
+    }
+
+    const SCENARIOS: u32 = 500;
+    const MAX_ORACLES: u64 = 10;
+
+    /// Random votes (1 or 0) for up to MAX_ORACLES oracles
+    fn random_votes(rng: &mut Rng, votes: &mut [u32; MAX_ORACLES as usize]) -> usize {
+        let n = 1 + rng.below(MAX_ORACLES) as usize;
+        for vote in votes.iter_mut().take(n) {
+            *vote = rng.below(2) as u32;
+        }
+        n
+    }
+
+    #[test]
+    fn test_equal_weights_match_count_based() {
+        let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+        let mut votes = [0u32; MAX_ORACLES as usize];
+
+        for _ in 0..SCENARIOS {
+            let n = random_votes(&mut rng, &mut votes);
+            let threshold = 1 + rng.below(n as u64) as u32;
+            let weight = 1 + rng.below(1_000) as u128;
+
+            let mut counted = Tally::default();
+            let mut weighed = Tally::default();
+            for vote in votes.iter().take(n) {
+                counted.add_vote(*vote, 1);
+                weighed.add_vote(*vote, weight);
+            }
+
+            assert_eq!(
+                count_based(&counted, threshold),
+                weighted(&weighed, threshold),
+                "votes={:?} threshold={} weight={}",
+                &votes[..n],
+                threshold,
+                weight
+            );
+        }
+    }
+
+    #[test]
+    fn test_weighted_is_monotonic_in_winner_weight() {
+        let mut rng = Rng(0xD1B5_4A32_D192_ED03);
+        let mut votes = [0u32; MAX_ORACLES as usize];
+        let mut weights = [0u128; MAX_ORACLES as usize];
+
+        for _ in 0..SCENARIOS {
+            let n = random_votes(&mut rng, &mut votes);
+            let threshold = 1 + rng.below(n as u64) as u32;
+            for weight in weights.iter_mut().take(n) {
+                *weight = 1 + rng.below(500) as u128;
+            }
+
+            let tally = |weights: &[u128]| {
+                let mut tally = Tally::default();
+                for i in 0..n {
+                    tally.add_vote(votes[i], weights[i]);
+                }
+                tally
+            };
+
+            let winner = match weighted(&tally(&weights), threshold) {
+                Some(winner) => winner,
+                None => continue,
+            };
+
+            // Boost a random oracle that voted for the winner
+            let winners: usize = votes.iter().take(n).filter(|v| **v == winner).count();
+            let pick = rng.below(winners as u64) as usize;
+            let idx = (0..n).filter(|i| votes[*i] == winner).nth(pick).unwrap();
+            weights[idx] += 1 + rng.below(1_000) as u128;
+
+            assert_eq!(weighted(&tally(&weights), threshold), Some(winner));
+        }
+    }
+
+    #[test]
+    fn test_count_based_edge_cases() {
+        let mut tally = Tally::default();
+        assert_eq!(count_based(&tally, 1), None);
+
+        tally.add_vote(1, 1);
+        tally.add_vote(0, 1);
+        // Tie never resolves even when both sides meet the threshold
+        assert_eq!(count_based(&tally, 1), None);
+        assert_eq!(weighted(&tally, 1), None);
+
+        tally.add_vote(1, 1);
+        assert_eq!(count_based(&tally, 2), Some(1));
+        assert_eq!(count_based(&tally, 3), None);
+    }
+
+    #[test]
+    fn test_weighted_heavy_minority_can_win() {
+        let mut tally = Tally::default();
+        tally.add_vote(0, 1_000);
+        tally.add_vote(1, 10);
+        tally.add_vote(1, 10);
+
+        // Count mode: YES 2-1; weighted: NO carries more reputation
+        assert_eq!(count_based(&tally, 1), Some(1));
+        assert_eq!(weighted(&tally, 1), Some(0));
+    }
+
+    #[test]
+    fn test_weighted_median_equal_weights_is_lower_median() {
+        assert_eq!(weighted_median(&mut [(30, 1), (10, 1), (20, 1)]), Some(20));
+        assert_eq!(
+            weighted_median(&mut [(40, 7), (10, 7), (30, 7), (20, 7)]),
+            Some(20)
+        );
+        assert_eq!(weighted_median(&mut []), None);
+        assert_eq!(weighted_median(&mut [(5, 0)]), None);
+    }
+
+    #[test]
+    fn test_weighted_median_follows_stake() {
+        // Plain median is 20; the single heavy staker outweighs both others
+        let mut entries = [(10, 100), (20, 100), (300, 1_000)];
+        assert_eq!(weighted_median(&mut entries), Some(300));
+
+        // One light outlier cannot drag the weighted median
+        let mut entries = [(100, 1_000), (101, 1_000), (1_000_000, 10)];
+        assert_eq!(weighted_median(&mut entries), Some(101));
+    }
+
+    #[test]
+    fn test_participation_must_strictly_exceed_floor() {
+        assert!(!meets_participation(5_000, 10_000, 5_000));
+        assert!(meets_participation(5_001, 10_000, 5_000));
+        assert!(!meets_participation(0, 0, 0));
+        assert!(meets_participation(1, 10_000, 0));
+    }
+}
+
+#[cfg(test)]
+mod consensus_proptests {
+    extern crate std;
+
+    use super::consensus::{count_based, Tally};
+    use super::MAX_ORACLES;
+    use crate::testutils::OracleFixture;
+    use proptest::prelude::*;
+    use soroban_sdk::{testutils::Ledger, Env};
+    use std::vec::Vec;
+
+    /// The outcome the votes should settle on: strictly more votes than the
+    /// other side, and at least `threshold` of them
+    fn expected(votes: &[bool], threshold: u32) -> Option<u32> {
+        let yes = votes.iter().filter(|vote| **vote).count() as u32;
+        let no = votes.len() as u32 - yes;
+        if yes > no && yes >= threshold {
+            Some(1)
+        } else if no > yes && no >= threshold {
+            Some(0)
+        } else {
+            None
+        }
+    }
+
+    fn votes() -> impl Strategy<Value = Vec<bool>> {
+        proptest::collection::vec(any::<bool>(), 0..=MAX_ORACLES as usize)
+    }
+
+    proptest! {
+        #[test]
+        fn prop_count_based_matches_model(votes in votes(), threshold in 0..=MAX_ORACLES + 2) {
+            let mut tally = Tally::default();
+            for vote in &votes {
+                tally.add_vote(*vote as u32, 1);
+            }
+
+            prop_assert_eq!(tally.total_count(), votes.len() as u32);
+            prop_assert_eq!(
+                tally.yes_count,
+                votes.iter().filter(|vote| **vote).count() as u32
+            );
+            prop_assert_eq!(count_based(&tally, threshold), expected(&votes, threshold));
+        }
+    }
+
+    proptest! {
+        // Each case deploys an oracle contract, so keep the case count modest
+        #![proptest_config(ProptestConfig::with_cases(48))]
+
+        #[test]
+        fn prop_check_consensus_matches_model(
+            votes in votes().prop_filter("needs a voter", |votes| !votes.is_empty()),
+            threshold in 1..=MAX_ORACLES,
+        ) {
+            let env = Env::default();
+            let fixture = OracleFixture::new(&env)
+                .with_oracles(votes.len() as u32)
+                .with_threshold(threshold)
+                .with_market([1u8; 32], 100);
+            let market_id = fixture.market(0);
+            env.ledger().set_timestamp(100);
+
+            for (i, vote) in votes.iter().enumerate() {
+                fixture.attest(&fixture.oracle(i as u32), &market_id, *vote as u32);
+            }
+
+            let yes = votes.iter().filter(|vote| **vote).count() as u32;
+            prop_assert_eq!(
+                fixture.client.get_attestation_counts(&market_id),
+                (yes, votes.len() as u32 - yes)
+            );
+            let reached = expected(&votes, threshold);
+            prop_assert_eq!(
+                fixture.client.check_consensus(&market_id),
+                (reached.is_some(), reached.unwrap_or(0))
+            );
+        }
+    }
 }
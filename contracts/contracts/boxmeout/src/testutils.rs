@@ -0,0 +1,220 @@
+// Fixtures shared by the contract test suites
+//
+// Builders deploy and wire contracts the way most tests need them and expose
+// the generated addresses and clients, so a test only spells out what it is
+// actually about. Every auth is mocked. Compiled for unit tests and under the
+// `testutils` feature only.
+
+use crate::oracle::{DataKey, OracleManager, OracleManagerClient, CATEGORY_ANY};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{contract, contractimpl, token, Address, BytesN, Env, MuxedAddress, Symbol, Vec};
+
+/// Consensus threshold of a fresh `OracleFixture`
+pub const DEFAULT_THRESHOLD: u32 = 2;
+
+/// Names given to fixture oracles, by registration order (the last one repeats)
+const ORACLE_NAMES: [&str; 10] = [
+    "Oracle1", "Oracle2", "Oracle3", "Oracle4", "Oracle5", "Oracle6", "Oracle7", "Oracle8",
+    "Oracle9", "Oracle10",
+];
+
+/// An initialized oracle manager with registered oracles and markets
+///
+/// ```ignore
+/// let fixture = OracleFixture::new(&env)
+///     .with_oracles(3)
+///     .with_threshold(2)
+///     .with_market([1u8; 32], 1000);
+/// ```
+pub struct OracleFixture {
+    pub env: Env,
+    pub admin: Address,
+    pub client: OracleManagerClient<'static>,
+    /// Registered oracles, in registration order
+    pub oracles: Vec<Address>,
+    /// Registered market ids, in registration order
+    pub markets: Vec<BytesN<32>>,
+}
+
+impl OracleFixture {
+    /// Deploy an oracle manager initialized with `DEFAULT_THRESHOLD` and no oracles
+    pub fn new(env: &Env) -> Self {
+        env.mock_all_auths();
+        let admin = Address::generate(env);
+        let client = OracleManagerClient::new(env, &env.register(OracleManager, ()));
+        client.initialize(&admin, &DEFAULT_THRESHOLD);
+
+        OracleFixture {
+            env: env.clone(),
+            admin,
+            client,
+            oracles: Vec::new(env),
+            markets: Vec::new(env),
+        }
+    }
+
+    /// Register `count` more generated oracles
+    pub fn with_oracles(mut self, count: u32) -> Self {
+        for _ in 0..count {
+            let oracle = Address::generate(&self.env);
+            let index = (self.oracles.len() as usize).min(ORACLE_NAMES.len() - 1);
+            self.client.register_oracle(
+                &oracle,
+                &Symbol::new(&self.env, ORACLE_NAMES[index]),
+                &Vec::new(&self.env),
+            );
+            self.oracles.push_back(oracle);
+        }
+        self
+    }
+
+    /// Set the required consensus
+    ///
+    /// `set_consensus_threshold` is not implemented yet, so this writes the
+    /// value `initialize` would have stored.
+    pub fn with_threshold(self, threshold: u32) -> Self {
+        self.env.as_contract(&self.client.address, || {
+            self.env
+                .storage()
+                .persistent()
+                .set(&DataKey::RequiredConsensus, &threshold);
+        });
+        self
+    }
+
+    /// Register a market resolving at `resolution_time`
+    pub fn with_market(mut self, id: [u8; 32], resolution_time: u64) -> Self {
+        let market_id = BytesN::from_array(&self.env, &id);
+        self.client.register_market(
+            &market_id,
+            &resolution_time,
+            &Symbol::new(&self.env, CATEGORY_ANY),
+        );
+        self.markets.push_back(market_id);
+        self
+    }
+
+    /// The `index`-th registered oracle
+    pub fn oracle(&self, index: u32) -> Address {
+        self.oracles.get(index).expect("no such fixture oracle")
+    }
+
+    /// The `index`-th registered market id
+    pub fn market(&self, index: u32) -> BytesN<32> {
+        self.markets.get(index).expect("no such fixture market")
+    }
+
+    /// Attest `outcome` on `market_id` from `oracle`, with a zero data hash and no metadata
+    pub fn attest(&self, oracle: &Address, market_id: &BytesN<32>, outcome: u32) {
+        let data_hash = BytesN::from_array(&self.env, &[0u8; 32]);
+        self.client
+            .submit_attestation(oracle, market_id, &outcome, &data_hash, &None);
+    }
+}
+
+/// A Stellar asset contract issued by a generated address
+///
+/// Minting needs the issuer's auth, so pair it with `mock_all_auths`.
+pub fn deploy_token(env: &Env) -> token::StellarAssetClient<'static> {
+    let token = env.register_stellar_asset_contract_v2(Address::generate(env));
+    token::StellarAssetClient::new(env, &token.address())
+}
+
+/// Mint `amount` of `token` to each of `accounts`
+pub fn fund(token: &token::StellarAssetClient, accounts: &[&Address], amount: i128) {
+    for account in accounts {
+        token.mint(account, &amount);
+    }
+}
+
+/// A minimal token whose `transfer` panics while failing is switched on
+///
+/// Stands in for a misbehaving collateral token: a call that pays out through
+/// it fails as a whole, and the test checks that nothing was used up.
+#[contract]
+pub struct FlakyToken;
+
+#[contractimpl]
+impl FlakyToken {
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage().persistent().set(&to, &(balance + amount));
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage().persistent().get(&id).unwrap_or(0)
+    }
+
+    /// Make every following `transfer` panic until switched off again
+    pub fn set_failing(env: Env, failing: bool) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "failing"), &failing);
+    }
+
+    pub fn transfer(env: Env, from: Address, to: MuxedAddress, amount: i128) {
+        from.require_auth();
+        if env
+            .storage()
+            .instance()
+            .get(&Symbol::new(&env, "failing"))
+            .unwrap_or(false)
+        {
+            panic!("transfer failed");
+        }
+        let balance = Self::balance(env.clone(), from.clone());
+        if balance < amount {
+            panic!("insufficient balance");
+        }
+        env.storage().persistent().set(&from, &(balance - amount));
+        Self::mint(env, to.address(), amount);
+    }
+}
+
+/// A fresh `FlakyToken`, transferring normally until `set_failing(&true)`
+pub fn deploy_flaky_token(env: &Env) -> FlakyTokenClient<'static> {
+    FlakyTokenClient::new(env, &env.register(FlakyToken, ()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Ledger;
+
+    #[test]
+    fn test_oracle_fixture_builds_a_resolvable_setup() {
+        let env = Env::default();
+        let fixture = OracleFixture::new(&env)
+            .with_oracles(3)
+            .with_threshold(3)
+            .with_market([1u8; 32], 1000);
+        assert_eq!(fixture.oracles.len(), 3);
+        assert_eq!(
+            fixture
+                .client
+                .get_market_resolution_time(&fixture.market(0)),
+            Some(1000)
+        );
+
+        env.ledger().set_timestamp(1000);
+        let market_id = fixture.market(0);
+        fixture.attest(&fixture.oracle(0), &market_id, 1);
+        fixture.attest(&fixture.oracle(1), &market_id, 1);
+        assert_eq!(fixture.client.check_consensus(&market_id), (false, 0));
+        fixture.attest(&fixture.oracle(2), &market_id, 1);
+        assert_eq!(fixture.client.check_consensus(&market_id), (true, 1));
+    }
+
+    #[test]
+    fn test_fund_mints_to_every_account() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let token = deploy_token(&env);
+        let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+        fund(&token, &[&alice, &bob], 500);
+
+        let balances = token::TokenClient::new(&env, &token.address);
+        assert_eq!(balances.balance(&alice), 500);
+        assert_eq!(balances.balance(&bob), 500);
+    }
+}
@@ -0,0 +1,1386 @@
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    token, Address, BytesN, Env,
+};
+
+use boxmeout::amm::{
+    AMMClient, FeeConfig, PricingMode, AMM, MAX_OBSERVATIONS, MAX_POOL_FEE_BPS,
+    SETTLEMENT_RATE_PRECISION,
+};
+use boxmeout::factory::{MarketFactory, MarketFactoryClient};
+use boxmeout::market::{PredictionMarket, PredictionMarketClient};
+use boxmeout::testutils::{deploy_flaky_token, deploy_token};
+use boxmeout::treasury::{Treasury, TreasuryClient};
+
+/// Helper: an initialized AMM whose admin holds 10_000_000 of a separate collateral token
+fn setup_amm(env: &Env) -> (AMMClient<'_>, Address, token::StellarAssetClient<'_>) {
+    env.mock_all_auths();
+    let admin = Address::generate(env);
+    let usdc = deploy_token(env);
+    let amm = AMMClient::new(env, &env.register(AMM, ()));
+    amm.initialize(
+        &admin,
+        &Address::generate(env),
+        &usdc.address,
+        &1_000_000_000u128,
+    );
+
+    let collateral = deploy_token(env);
+    collateral.mint(&admin, &10_000_000);
+    (amm, admin, collateral)
+}
+
+#[test]
+fn test_initialize_pool_pulls_collateral_and_mints_reserves() {
+    let env = Env::default();
+    let (amm, admin, collateral) = setup_amm(&env);
+    let market_id = BytesN::from_array(&env, &[1u8; 32]);
+    let market = Address::generate(&env);
+
+    amm.initialize_pool(
+        &admin,
+        &market_id,
+        &market,
+        &collateral.address,
+        &1_000_000u128,
+        &20u32,
+        &PricingMode::Cpmm,
+    );
+    assert!(!env.events().all().is_empty());
+
+    assert_eq!(amm.get_reserves(&market_id), (500_000, 500_000));
+    assert_eq!(amm.get_collateral_balance(&market_id), 1_000_000);
+    assert_eq!(amm.get_pool_market(&market_id), Some(market));
+    assert_eq!(
+        amm.get_pool_collateral_token(&market_id),
+        collateral.address
+    );
+    assert_eq!(amm.get_pool_fee_bps(&market_id), 20);
+
+    let collateral_client = token::Client::new(&env, &collateral.address);
+    assert_eq!(collateral_client.balance(&admin), 9_000_000);
+    assert_eq!(collateral_client.balance(&amm.address), 1_000_000);
+}
+
+#[test]
+fn test_initialized_pool_trades_in_its_collateral() {
+    let env = Env::default();
+    let (amm, admin, collateral) = setup_amm(&env);
+    let market_id = BytesN::from_array(&env, &[1u8; 32]);
+    amm.initialize_pool(
+        &admin,
+        &market_id,
+        &Address::generate(&env),
+        &collateral.address,
+        &1_000_000u128,
+        &20u32,
+        &PricingMode::Cpmm,
+    );
+
+    let trader = Address::generate(&env);
+    collateral.mint(&trader, &10_000);
+    let shares = amm.buy_shares(&trader, &market_id, &1, &10_000u128, &0u128);
+    assert_eq!(amm.get_collateral_balance(&market_id), 1_010_000);
+
+    let payout = amm.sell_shares(&trader, &market_id, &1, &shares, &0u128);
+    assert_eq!(
+        amm.get_collateral_balance(&market_id),
+        1_010_000 - payout as i128
+    );
+    assert_eq!(
+        token::Client::new(&env, &collateral.address).balance(&trader),
+        payout as i128
+    );
+}
+
+#[test]
+fn test_failed_sell_payout_keeps_shares() {
+    let env = Env::default();
+    let (amm, admin, _) = setup_amm(&env);
+    let collateral = deploy_flaky_token(&env);
+    collateral.mint(&admin, &1_000_000);
+    let market_id = BytesN::from_array(&env, &[1u8; 32]);
+    amm.initialize_pool(
+        &admin,
+        &market_id,
+        &Address::generate(&env),
+        &collateral.address,
+        &1_000_000u128,
+        &20u32,
+        &PricingMode::Cpmm,
+    );
+    let trader = Address::generate(&env);
+    collateral.mint(&trader, &10_000);
+    let shares = amm.buy_shares(&trader, &market_id, &1, &10_000u128, &0u128);
+
+    collateral.set_failing(&true);
+    assert!(amm
+        .try_sell_shares(&trader, &market_id, &1, &shares, &0u128)
+        .is_err());
+    assert_eq!(amm.get_user_shares(&market_id, &trader, &1), shares);
+    assert_eq!(amm.get_collateral_balance(&market_id), 1_010_000);
+
+    collateral.set_failing(&false);
+    let payout = amm.sell_shares(&trader, &market_id, &1, &shares, &0u128);
+    assert_eq!(amm.get_user_shares(&market_id, &trader, &1), 0);
+    assert_eq!(collateral.balance(&trader), payout as i128);
+}
+
+#[test]
+fn test_failed_lp_exit_keeps_lp_tokens_and_fees() {
+    let env = Env::default();
+    let (amm, admin, _) = setup_amm(&env);
+    let collateral = deploy_flaky_token(&env);
+    collateral.mint(&admin, &1_000_000);
+    let market_id = BytesN::from_array(&env, &[1u8; 32]);
+    amm.initialize_pool(
+        &admin,
+        &market_id,
+        &Address::generate(&env),
+        &collateral.address,
+        &1_000_000u128,
+        &20u32,
+        &PricingMode::Cpmm,
+    );
+    let lp = Address::generate(&env);
+    collateral.mint(&lp, &500_000);
+    let minted = amm.add_liquidity(&lp, &market_id, &500_000u128, &0u128);
+    let trader = Address::generate(&env);
+    collateral.mint(&trader, &100_000);
+    amm.buy_shares(&trader, &market_id, &1, &100_000u128, &0u128);
+    let pool_balance = amm.get_collateral_balance(&market_id);
+    let statement = amm.get_lp_statement(&lp);
+
+    collateral.set_failing(&true);
+    assert!(amm
+        .try_remove_liquidity(&lp, &market_id, &minted, &0u128)
+        .is_err());
+    assert!(amm.try_claim_lp_fees(&lp, &market_id).is_err());
+    assert_eq!(amm.get_lp_balance(&market_id, &lp), minted);
+    assert_eq!(amm.get_collateral_balance(&market_id), pool_balance);
+    assert_eq!(amm.get_lp_statement(&lp), statement);
+
+    collateral.set_failing(&false);
+    let fees = amm.claim_lp_fees(&lp, &market_id);
+    assert!(fees > 0);
+    let (yes, no) = amm.remove_liquidity(&lp, &market_id, &minted, &0u128);
+    assert_eq!(amm.get_lp_balance(&market_id, &lp), 0);
+    assert_eq!(collateral.balance(&lp), (fees + yes + no) as i128);
+}
+
+#[test]
+#[should_panic(expected = "pool already exists")]
+fn test_initialize_pool_twice_rejected() {
+    let env = Env::default();
+    let (amm, admin, collateral) = setup_amm(&env);
+    let market_id = BytesN::from_array(&env, &[1u8; 32]);
+    let market = Address::generate(&env);
+
+    amm.initialize_pool(
+        &admin,
+        &market_id,
+        &market,
+        &collateral.address,
+        &1_000u128,
+        &20u32,
+        &PricingMode::Cpmm,
+    );
+    amm.initialize_pool(
+        &admin,
+        &market_id,
+        &market,
+        &collateral.address,
+        &1_000u128,
+        &20u32,
+        &PricingMode::Cpmm,
+    );
+}
+
+#[test]
+#[should_panic(expected = "initial liquidity must be greater than 0")]
+fn test_initialize_pool_zero_liquidity_rejected() {
+    let env = Env::default();
+    let (amm, admin, collateral) = setup_amm(&env);
+
+    amm.initialize_pool(
+        &admin,
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &Address::generate(&env),
+        &collateral.address,
+        &0u128,
+        &20u32,
+        &PricingMode::Cpmm,
+    );
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_initialize_pool_requires_admin() {
+    let env = Env::default();
+    let (amm, _, collateral) = setup_amm(&env);
+
+    amm.initialize_pool(
+        &Address::generate(&env),
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &Address::generate(&env),
+        &collateral.address,
+        &1_000u128,
+        &20u32,
+        &PricingMode::Cpmm,
+    );
+}
+
+/// Helper: a pool of 1_000_000 liquidity per side pinned to a market address
+fn setup_pool<'a>(
+    env: &'a Env,
+    market: &Address,
+) -> (AMMClient<'a>, BytesN<32>, token::StellarAssetClient<'a>) {
+    let (amm, admin, collateral) = setup_amm(env);
+    let market_id = BytesN::from_array(env, &[1u8; 32]);
+    amm.initialize_pool(
+        &admin,
+        &market_id,
+        market,
+        &collateral.address,
+        &2_000_000u128,
+        &20u32,
+        &PricingMode::Cpmm,
+    );
+    (amm, market_id, collateral)
+}
+
+/// Helper: an open prediction market whose betting cutoff is at 2000
+fn setup_open_market<'a>(env: &'a Env, collateral: &Address) -> PredictionMarketClient<'a> {
+    setup_market_by(env, collateral, &Address::generate(env))
+}
+
+fn setup_market_by<'a>(
+    env: &'a Env,
+    collateral: &Address,
+    creator: &Address,
+) -> PredictionMarketClient<'a> {
+    env.ledger().set_timestamp(1000);
+    let market = PredictionMarketClient::new(env, &env.register(PredictionMarket, ()));
+    market.initialize(
+        &BytesN::from_array(env, &[1u8; 32]),
+        creator,
+        &Address::generate(env),
+        collateral,
+        &Address::generate(env),
+        &2000,
+        &3000,
+        &0,
+        &None,
+    );
+    market
+}
+
+#[test]
+fn test_buy_shares_bigger_buy_gets_worse_average_price() {
+    let env = Env::default();
+    let mut last_avg_price = 0u128;
+    for amount in [1_000u128, 10_000, 100_000, 500_000] {
+        let (amm, market_id, collateral) = setup_pool(&env, &Address::generate(&env));
+        let trader = Address::generate(&env);
+        collateral.mint(&trader, &(amount as i128));
+
+        let shares = amm.buy_shares(&trader, &market_id, &1, &amount, &0u128);
+        assert_eq!(amm.get_user_shares(&market_id, &trader, &1), shares);
+
+        // Average price in basis points of collateral per share
+        let avg_price = amount * 10000 / shares;
+        assert!(avg_price > last_avg_price);
+        last_avg_price = avg_price;
+    }
+}
+
+#[test]
+fn test_buy_shares_moves_price_and_reserves() {
+    let env = Env::default();
+    let (amm, market_id, collateral) = setup_pool(&env, &Address::generate(&env));
+    let trader = Address::generate(&env);
+    collateral.mint(&trader, &100_000);
+
+    let shares = amm.buy_shares(&trader, &market_id, &0, &100_000u128, &0u128);
+
+    // 0.2% fee: 99_800 enters the YES reserve, NO shares floored
+    assert_eq!(shares, 99_800 * 1_000_000 / 1_099_800);
+    assert_eq!(
+        amm.get_reserves(&market_id),
+        (1_099_800, 1_000_000 - shares as i128)
+    );
+    assert_eq!(amm.get_user_shares(&market_id, &trader, &0), shares);
+    assert_eq!(amm.get_user_shares(&market_id, &trader, &1), 0);
+    let (yes_price, no_price) = amm.get_current_prices(&market_id);
+    assert!(no_price > yes_price);
+}
+
+#[test]
+#[should_panic(expected = "Slippage exceeded")]
+fn test_buy_shares_reverts_below_min_shares_out() {
+    let env = Env::default();
+    let (amm, market_id, collateral) = setup_pool(&env, &Address::generate(&env));
+    let trader = Address::generate(&env);
+    collateral.mint(&trader, &100_000);
+
+    // Price impact means 100_000 in can never return 100_000 shares
+    amm.buy_shares(&trader, &market_id, &1, &100_000u128, &100_000u128);
+}
+
+#[test]
+#[should_panic(expected = "amount must be greater than 0")]
+fn test_buy_shares_zero_amount_rejected() {
+    let env = Env::default();
+    let (amm, market_id, _) = setup_pool(&env, &Address::generate(&env));
+    amm.buy_shares(&Address::generate(&env), &market_id, &1, &0u128, &0u128);
+}
+
+#[test]
+#[should_panic(expected = "outcome must be 0 (NO) or 1 (YES)")]
+fn test_buy_shares_invalid_outcome_rejected() {
+    let env = Env::default();
+    let (amm, market_id, _) = setup_pool(&env, &Address::generate(&env));
+    amm.buy_shares(&Address::generate(&env), &market_id, &2, &1_000u128, &0u128);
+}
+
+#[test]
+fn test_buy_shares_allowed_before_market_cutoff() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let collateral = deploy_token(&env);
+    let market = setup_open_market(&env, &collateral.address);
+    let (amm, market_id, collateral) = setup_pool(&env, &market.address);
+    let trader = Address::generate(&env);
+    collateral.mint(&trader, &1_000);
+
+    env.ledger().set_timestamp(1999);
+    assert!(amm.buy_shares(&trader, &market_id, &1, &1_000u128, &0u128) > 0);
+}
+
+#[test]
+#[should_panic(expected = "market closed")]
+fn test_buy_shares_rejected_at_market_cutoff() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let collateral = deploy_token(&env);
+    let market = setup_open_market(&env, &collateral.address);
+    let (amm, market_id, collateral) = setup_pool(&env, &market.address);
+    let trader = Address::generate(&env);
+    collateral.mint(&trader, &1_000);
+
+    env.ledger().set_timestamp(2000);
+    amm.buy_shares(&trader, &market_id, &1, &1_000u128, &0u128);
+}
+
+#[test]
+#[should_panic(expected = "market closed")]
+fn test_buy_shares_rejected_after_market_resolution() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let collateral = deploy_token(&env);
+    let market = setup_open_market(&env, &collateral.address);
+    let (amm, market_id, collateral) = setup_pool(&env, &market.address);
+    let market_key = BytesN::from_array(&env, &[1u8; 32]);
+    env.ledger().set_timestamp(2000);
+    market.close_market(&market_key);
+    env.ledger().set_timestamp(3000);
+    market.resolve_market(&market_key);
+
+    // Even with the clock wound back, a resolved market takes no more bets
+    env.ledger().set_timestamp(1500);
+    let trader = Address::generate(&env);
+    collateral.mint(&trader, &1_000);
+    amm.buy_shares(&trader, &market_id, &1, &1_000u128, &0u128);
+}
+
+struct RestingPool<'a> {
+    amm: AMMClient<'a>,
+    admin: Address,
+    market: PredictionMarketClient<'a>,
+    market_id: BytesN<32>,
+    factory: Address,
+    lp: Address,
+}
+
+/// Helper: a 2_000_000 pool (admin and `lp` hold half each) on a live market
+/// with 3_000_000 of open interest, and a 50% resting floor (1_500_000)
+fn setup_resting_pool(env: &Env) -> RestingPool<'_> {
+    resting_pool(env, None)
+}
+
+/// Helper: setup_resting_pool, with the market optionally hiding its pool split
+fn resting_pool(env: &Env, pool_reveal: Option<u64>) -> RestingPool<'_> {
+    env.mock_all_auths();
+    let admin = Address::generate(env);
+    let factory = Address::generate(env);
+    let usdc = deploy_token(env);
+    let amm = AMMClient::new(env, &env.register(AMM, ()));
+    amm.initialize(&admin, &factory, &usdc.address, &1_000_000_000u128);
+
+    let creator = Address::generate(env);
+    let market = setup_market_by(env, &usdc.address, &creator);
+    market.set_pool_reveal(&creator, &pool_reveal);
+    for (outcome, stake) in [(1u32, 2_000_000i128), (0u32, 1_000_000i128)] {
+        let bettor = Address::generate(env);
+        usdc.mint(&bettor, &stake);
+        market.place_prediction(&bettor, &outcome, &stake);
+    }
+
+    let market_id = BytesN::from_array(env, &[1u8; 32]);
+    usdc.mint(&admin, &1_000_000);
+    amm.initialize_pool(
+        &admin,
+        &market_id,
+        &market.address,
+        &usdc.address,
+        &1_000_000u128,
+        &20u32,
+        &PricingMode::Cpmm,
+    );
+    let lp = Address::generate(env);
+    usdc.mint(&lp, &1_000_000);
+    amm.add_liquidity(&lp, &market_id, &1_000_000u128, &0u128);
+
+    amm.set_resting_floor(&factory, &market_id, &5000);
+    RestingPool {
+        amm,
+        admin,
+        market,
+        market_id,
+        factory,
+        lp,
+    }
+}
+
+#[test]
+fn test_max_withdrawable_clamped_by_resting_floor() {
+    let env = Env::default();
+    let pool = setup_resting_pool(&env);
+
+    assert_eq!(pool.amm.get_resting_floor(&pool.market_id), 5000);
+    // Share is 1_000_000 but only 2_000_000 - 1_500_000 may leave
+    assert_eq!(
+        pool.amm.get_max_withdrawable(&pool.market_id, &pool.lp),
+        500_000
+    );
+
+    let (yes, no) = pool
+        .amm
+        .remove_liquidity(&pool.lp, &pool.market_id, &500_000u128, &0u128);
+    assert_eq!(yes + no, 500_000);
+    assert_eq!(pool.amm.get_reserves(&pool.market_id), (750_000, 750_000));
+    assert_eq!(pool.amm.get_max_withdrawable(&pool.market_id, &pool.lp), 0);
+}
+
+#[test]
+#[should_panic(expected = "withdrawal breaches resting liquidity floor")]
+fn test_remove_liquidity_below_resting_floor_rejected() {
+    let env = Env::default();
+    let pool = setup_resting_pool(&env);
+
+    pool.amm
+        .remove_liquidity(&pool.lp, &pool.market_id, &1_000_000u128, &0u128);
+}
+
+#[test]
+fn test_resting_floor_ignores_hidden_pool_split() {
+    let env = Env::default();
+    let pool = resting_pool(&env, Some(500));
+    assert!(!pool.market.pools_revealed());
+
+    assert_eq!(
+        pool.amm.get_max_withdrawable(&pool.market_id, &pool.lp),
+        500_000
+    );
+}
+
+#[test]
+fn test_resting_floor_still_applies_while_market_locked() {
+    let env = Env::default();
+    let pool = setup_resting_pool(&env);
+    env.ledger().set_timestamp(2000);
+    pool.market.close_market(&pool.market_id);
+
+    assert_eq!(
+        pool.amm.get_max_withdrawable(&pool.market_id, &pool.lp),
+        500_000
+    );
+}
+
+#[test]
+#[should_panic(expected = "pool not settled")]
+fn test_withdrawal_after_resolution_waits_for_settlement() {
+    let env = Env::default();
+    let pool = setup_resting_pool(&env);
+    env.ledger().set_timestamp(2000);
+    pool.market.close_market(&pool.market_id);
+    env.ledger().set_timestamp(3000);
+    pool.market.resolve_market(&pool.market_id);
+
+    assert!(!pool.amm.is_pool_settled(&pool.market_id));
+    assert_eq!(pool.amm.get_max_withdrawable(&pool.market_id, &pool.lp), 0);
+    pool.amm
+        .remove_liquidity(&pool.lp, &pool.market_id, &1_000_000u128, &0u128);
+}
+
+#[test]
+fn test_resting_floor_zero_removes_rule() {
+    let env = Env::default();
+    let pool = setup_resting_pool(&env);
+    pool.amm
+        .set_resting_floor(&pool.factory, &pool.market_id, &0);
+
+    assert_eq!(
+        pool.amm.get_max_withdrawable(&pool.market_id, &pool.lp),
+        1_000_000
+    );
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_set_resting_floor_requires_factory() {
+    let env = Env::default();
+    let pool = setup_resting_pool(&env);
+    pool.amm
+        .set_resting_floor(&Address::generate(&env), &pool.market_id, &1000);
+}
+
+#[test]
+fn test_second_lp_joins_after_price_move_and_exits_without_profit() {
+    let env = Env::default();
+    let (amm, market_id, collateral) = setup_pool(&env, &Address::generate(&env));
+    let trader = Address::generate(&env);
+    collateral.mint(&trader, &400_000);
+    amm.buy_shares(&trader, &market_id, &1, &300_000u128, &0u128);
+    let (yes_price, no_price) = amm.get_current_prices(&market_id);
+    assert!(yes_price > no_price);
+
+    let lp = Address::generate(&env);
+    collateral.mint(&lp, &1_000_000);
+    let supply_before = amm.get_lp_total_supply(&market_id);
+    let minted = amm.add_liquidity(&lp, &market_id, &1_000_000u128, &0u128);
+    assert_eq!(amm.get_lp_balance(&market_id, &lp), minted);
+    assert_eq!(amm.get_lp_total_supply(&market_id), supply_before + minted);
+    // Prices are unchanged by a proportional deposit
+    assert_eq!(amm.get_current_prices(&market_id), (yes_price, no_price));
+
+    let (yes, no) = amm.remove_liquidity(&lp, &market_id, &minted, &0u128);
+    assert!(yes + no <= 1_000_000);
+    assert!(1_000_000 - (yes + no) <= 2);
+    assert_eq!(amm.get_lp_balance(&market_id, &lp), 0);
+    assert_eq!(amm.get_lp_total_supply(&market_id), supply_before);
+    assert_eq!(
+        token::Client::new(&env, &collateral.address).balance(&lp),
+        (yes + no) as i128
+    );
+}
+
+#[test]
+#[should_panic(expected = "Slippage exceeded")]
+fn test_add_liquidity_reverts_below_min_lp_out() {
+    let env = Env::default();
+    let (amm, market_id, collateral) = setup_pool(&env, &Address::generate(&env));
+    let lp = Address::generate(&env);
+    collateral.mint(&lp, &1_000);
+
+    // The first provider minted 1 token per unit, so 1_000 mints at most 1_000
+    amm.add_liquidity(&lp, &market_id, &1_000u128, &1_001u128);
+}
+
+#[test]
+#[should_panic(expected = "Slippage exceeded")]
+fn test_remove_liquidity_reverts_below_min_collateral_out() {
+    let env = Env::default();
+    let (amm, market_id, collateral) = setup_pool(&env, &Address::generate(&env));
+    let lp = Address::generate(&env);
+    collateral.mint(&lp, &100_000);
+    let minted = amm.add_liquidity(&lp, &market_id, &100_000u128, &0u128);
+
+    amm.remove_liquidity(&lp, &market_id, &minted, &100_001u128);
+}
+
+// ===== QUOTES =====
+
+#[test]
+fn test_quotes_match_executed_trades() {
+    let env = Env::default();
+    let (amm, market_id, collateral) = setup_pool(&env, &Address::generate(&env));
+    let trader = Address::generate(&env);
+    collateral.mint(&trader, &400_000);
+
+    for (outcome, amount) in [(1u32, 100_000u128), (0, 250_000), (1, 7)] {
+        let quote = amm.quote_buy(&market_id, &outcome, &amount);
+        let shares = amm.buy_shares(&trader, &market_id, &outcome, &amount, &quote.shares_out);
+        assert_eq!(shares, quote.shares_out);
+        assert_eq!(quote.collateral_out, 0);
+        let (yes, no) = amm.get_reserves(&market_id);
+        let opposite = if outcome == 1 { no } else { yes };
+        assert_eq!(quote.new_price as i128, opposite * 10000 / (yes + no));
+    }
+
+    let held = amm.get_user_shares(&market_id, &trader, &1);
+    let quote = amm.quote_sell(&market_id, &1, &held);
+    let payout = amm.sell_shares(&trader, &market_id, &1, &held, &quote.collateral_out);
+    assert_eq!(payout, quote.collateral_out);
+    assert_eq!(quote.shares_out, 0);
+    assert!(quote.fee > 0);
+}
+
+#[test]
+fn test_spot_prices_sum_to_one_minus_spread() {
+    let env = Env::default();
+    let (amm, market_id, collateral) = setup_pool(&env, &Address::generate(&env));
+    let fee_bps = amm.get_pool_fee_bps(&market_id);
+    let spread = |amm: &AMMClient| {
+        10000 - amm.get_spot_price(&market_id, &1) - amm.get_spot_price(&market_id, &0)
+    };
+    assert_eq!(amm.get_spot_price(&market_id, &1), (10000 - fee_bps) / 2);
+    assert!((fee_bps..=fee_bps + 2).contains(&spread(&amm)));
+
+    let trader = Address::generate(&env);
+    collateral.mint(&trader, &500_000);
+    amm.buy_shares(&trader, &market_id, &1, &500_000u128, &0u128);
+    assert!(amm.get_spot_price(&market_id, &1) > amm.get_spot_price(&market_id, &0));
+    assert!((fee_bps..=fee_bps + 2).contains(&spread(&amm)));
+}
+
+#[test]
+fn test_quote_price_impact_grows_with_size() {
+    let env = Env::default();
+    let (amm, market_id, _) = setup_pool(&env, &Address::generate(&env));
+
+    let small = amm.quote_buy(&market_id, &1, &1_000u128);
+    let large = amm.quote_buy(&market_id, &1, &1_000_000u128);
+    assert!(small.price_impact_bps < large.price_impact_bps);
+    assert!(large.new_price > 5000);
+    // Quoting is a pure read
+    assert_eq!(amm.get_reserves(&market_id), (1_000_000, 1_000_000));
+}
+
+#[test]
+#[should_panic(expected = "pool does not exist")]
+fn test_quote_against_missing_pool_fails() {
+    let env = Env::default();
+    let (amm, _, _) = setup_amm(&env);
+    amm.quote_buy(&BytesN::from_array(&env, &[9u8; 32]), &1, &1_000u128);
+}
+
+#[test]
+#[should_panic(expected = "market closed")]
+fn test_quote_sell_against_resolved_market_fails() {
+    let env = Env::default();
+    let pool = setup_resting_pool(&env);
+    env.ledger().set_timestamp(2000);
+    pool.market.close_market(&pool.market_id);
+    env.ledger().set_timestamp(3000);
+    pool.market.resolve_market(&pool.market_id);
+
+    pool.amm.quote_sell(&pool.market_id, &1, &1_000u128);
+}
+
+// ===== FEE SPLIT =====
+
+struct FeePool<'a> {
+    amm: AMMClient<'a>,
+    admin: Address,
+    treasury: TreasuryClient<'a>,
+    usdc: token::StellarAssetClient<'a>,
+    market_id: BytesN<32>,
+}
+
+/// Helper: a 2_000_000 pool charging 100 bps, a quarter of which goes to a real treasury
+fn setup_fee_pool(env: &Env) -> FeePool<'_> {
+    env.mock_all_auths();
+    let admin = Address::generate(env);
+    let usdc = deploy_token(env);
+    let factory = MarketFactoryClient::new(env, &env.register(MarketFactory, ()));
+    let treasury = TreasuryClient::new(env, &env.register(Treasury, ()));
+    factory.initialize(&admin, &usdc.address, &treasury.address);
+    treasury.initialize(&admin, &usdc.address, &factory.address);
+
+    let amm = AMMClient::new(env, &env.register(AMM, ()));
+    amm.initialize(&admin, &factory.address, &usdc.address, &1_000_000_000u128);
+    treasury.add_depositor(&admin, &amm.address);
+    amm.set_treasury_fee_share(&admin, &2500);
+
+    let market_id = BytesN::from_array(env, &[4u8; 32]);
+    usdc.mint(&admin, &2_000_000);
+    amm.initialize_pool(
+        &admin,
+        &market_id,
+        &Address::generate(env),
+        &usdc.address,
+        &2_000_000u128,
+        &100u32,
+        &PricingMode::Cpmm,
+    );
+    FeePool {
+        amm,
+        admin,
+        treasury,
+        usdc,
+        market_id,
+    }
+}
+
+#[test]
+fn test_fee_split_grows_lp_value_and_flushes_to_treasury() {
+    let env = Env::default();
+    let pool = setup_fee_pool(&env);
+    assert_eq!(
+        pool.amm.get_fee_config(&pool.market_id),
+        FeeConfig {
+            fee_bps: 100,
+            lp_share_bps: 7500,
+            treasury_share_bps: 2500,
+        }
+    );
+
+    let trader = Address::generate(&env);
+    pool.usdc.mint(&trader, &1_000_000);
+    let mut total_fees = 0u128;
+    let mut treasury_fees = 0u128;
+    for (outcome, amount) in [(1u32, 100_000u128), (0, 33_333), (1, 250_001)] {
+        let fee = pool.amm.quote_buy(&pool.market_id, &outcome, &amount).fee;
+        pool.amm
+            .buy_shares(&trader, &pool.market_id, &outcome, &amount, &0u128);
+        total_fees += fee;
+        treasury_fees += fee * 2500 / 10000;
+    }
+    let held = pool.amm.get_user_shares(&pool.market_id, &trader, &1);
+    let sell = pool.amm.quote_sell(&pool.market_id, &1, &held);
+    pool.amm
+        .sell_shares(&trader, &pool.market_id, &1, &held, &0u128);
+    total_fees += sell.fee;
+    treasury_fees += sell.fee * 2500 / 10000;
+
+    // The two halves of every fee add up exactly
+    let lp_fees = pool.amm.get_pool_lp_fees(&pool.market_id);
+    let pending = pool.amm.get_pending_treasury_fees(&pool.market_id);
+    assert_eq!(pending, treasury_fees);
+    assert_eq!(lp_fees + pending, total_fees);
+    // The seed LP's fee earnings grow by the LP half (less accumulator rounding)
+    let earned = pool.amm.get_lp_statement(&pool.admin).fees_earned;
+    assert!(earned > 0 && earned <= lp_fees);
+
+    let collateral_before = pool.amm.get_collateral_balance(&pool.market_id);
+    assert_eq!(pool.amm.flush_fees_to_treasury(&pool.market_id), pending);
+    assert_eq!(pool.amm.get_pending_treasury_fees(&pool.market_id), 0);
+    assert_eq!(
+        pool.treasury
+            .get_market_token_fees(&pool.market_id, &pool.usdc.address),
+        pending as i128
+    );
+    assert_eq!(
+        pool.amm.get_collateral_balance(&pool.market_id),
+        collateral_before - pending as i128
+    );
+    // Nothing left to send
+    assert_eq!(pool.amm.flush_fees_to_treasury(&pool.market_id), 0);
+}
+
+#[test]
+fn test_trade_whose_fee_rounds_to_zero_still_executes() {
+    let env = Env::default();
+    let pool = setup_fee_pool(&env);
+    let trader = Address::generate(&env);
+    pool.usdc.mint(&trader, &99);
+
+    let shares = pool
+        .amm
+        .buy_shares(&trader, &pool.market_id, &1, &99u128, &0u128);
+    assert!(shares > 0);
+    assert_eq!(pool.amm.get_pool_lp_fees(&pool.market_id), 0);
+    assert_eq!(pool.amm.get_pending_treasury_fees(&pool.market_id), 0);
+}
+
+#[test]
+fn test_pool_fee_adjustable_until_outside_liquidity_arrives() {
+    let env = Env::default();
+    let pool = setup_fee_pool(&env);
+
+    pool.amm
+        .set_pool_fee(&pool.admin, &pool.market_id, &MAX_POOL_FEE_BPS);
+    assert_eq!(
+        pool.amm.get_fee_config(&pool.market_id).fee_bps,
+        MAX_POOL_FEE_BPS
+    );
+
+    let lp = Address::generate(&env);
+    pool.usdc.mint(&lp, &100_000);
+    pool.amm
+        .add_liquidity(&lp, &pool.market_id, &100_000u128, &0u128);
+    assert!(pool
+        .amm
+        .try_set_pool_fee(&pool.admin, &pool.market_id, &50)
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "fee exceeds maximum")]
+fn test_initialize_pool_rejects_fee_above_cap() {
+    let env = Env::default();
+    let (amm, admin, collateral) = setup_amm(&env);
+    amm.initialize_pool(
+        &admin,
+        &BytesN::from_array(&env, &[5u8; 32]),
+        &Address::generate(&env),
+        &collateral.address,
+        &1_000_000u128,
+        &(MAX_POOL_FEE_BPS + 1),
+        &PricingMode::Cpmm,
+    );
+}
+
+/// Helper: an LMSR pool with b = 1_000_000 and no fee, seeded with 1_000_000
+fn setup_lmsr_pool(env: &Env) -> (AMMClient<'_>, BytesN<32>, token::StellarAssetClient<'_>) {
+    let (amm, admin, collateral) = setup_amm(env);
+    let market_id = BytesN::from_array(env, &[6u8; 32]);
+    amm.initialize_pool(
+        &admin,
+        &market_id,
+        &Address::generate(env),
+        &collateral.address,
+        &1_000_000u128,
+        &0u32,
+        &PricingMode::Lmsr(1_000_000),
+    );
+    (amm, market_id, collateral)
+}
+
+#[test]
+fn test_lmsr_symmetric_state_prices_even() {
+    let env = Env::default();
+    let (amm, market_id, _) = setup_lmsr_pool(&env);
+
+    assert_eq!(
+        amm.get_pricing_mode(&market_id),
+        PricingMode::Lmsr(1_000_000)
+    );
+    assert_eq!(amm.get_odds(&market_id), (5000, 5000));
+    assert_eq!(amm.get_spot_price(&market_id, &1), 5000);
+    assert_eq!(amm.get_spot_price(&market_id, &0), 5000);
+    assert_eq!(
+        amm.quote_buy(&market_id, &1, &250_000u128),
+        amm.quote_buy(&market_id, &0, &250_000u128)
+    );
+}
+
+#[test]
+fn test_lmsr_loss_bounded_by_b_ln2() {
+    let env = Env::default();
+    let (amm, market_id, collateral) = setup_lmsr_pool(&env);
+    let trader = Address::generate(&env);
+    let amount = 50_000_000u128;
+    collateral.mint(&trader, &(amount as i128));
+
+    // Buying far past certainty: if YES wins the pool pays every share and
+    // keeps only the collateral, losing b * ln(2) = 693_147.18 at most
+    let shares = amm.buy_shares(&trader, &market_id, &1, &amount, &0u128);
+    let loss = shares - amount;
+    assert!(loss <= 693_148, "loss {} above b * ln(2)", loss);
+    assert!(loss >= 693_140, "loss {} far below b * ln(2)", loss);
+    assert!(amm.get_collateral_balance(&market_id) as u128 >= shares);
+    assert_eq!(amm.get_lmsr_shares(&market_id), (shares, 0));
+}
+
+#[test]
+fn test_lmsr_buys_move_price_toward_outcome() {
+    let env = Env::default();
+    let (amm, market_id, collateral) = setup_lmsr_pool(&env);
+    let trader = Address::generate(&env);
+    collateral.mint(&trader, &1_000_000);
+
+    let quote = amm.quote_buy(&market_id, &1, &200_000u128);
+    let shares = amm.buy_shares(&trader, &market_id, &1, &200_000u128, &0u128);
+    assert_eq!(shares, quote.shares_out);
+    let yes_after_buy = amm.get_spot_price(&market_id, &1);
+    assert!(yes_after_buy > 5000);
+    assert_eq!(yes_after_buy, quote.new_price);
+    assert!(amm.get_spot_price(&market_id, &0) < 5000);
+    // A buy costs no more than the shares it returns pay out on a win
+    assert!(shares > 200_000);
+
+    amm.buy_shares(&trader, &market_id, &0, &100_000u128, &0u128);
+    assert!(amm.get_spot_price(&market_id, &1) < yes_after_buy);
+
+    // Selling the YES shares back cannot return more than was paid
+    let payout = amm.sell_shares(&trader, &market_id, &1, &shares, &0u128);
+    assert!(payout <= 200_000);
+    assert!(amm.get_spot_price(&market_id, &1) < 5000);
+}
+
+#[test]
+#[should_panic(expected = "liquidity below lmsr max loss")]
+fn test_lmsr_pool_must_cover_max_loss() {
+    let env = Env::default();
+    let (amm, admin, collateral) = setup_amm(&env);
+    amm.initialize_pool(
+        &admin,
+        &BytesN::from_array(&env, &[6u8; 32]),
+        &Address::generate(&env),
+        &collateral.address,
+        &693_147u128,
+        &0u32,
+        &PricingMode::Lmsr(1_000_000),
+    );
+}
+
+#[test]
+#[should_panic(expected = "lmsr pool liquidity is fixed")]
+fn test_lmsr_pool_rejects_added_liquidity() {
+    let env = Env::default();
+    let (amm, market_id, collateral) = setup_lmsr_pool(&env);
+    let lp = Address::generate(&env);
+    collateral.mint(&lp, &100_000);
+    amm.add_liquidity(&lp, &market_id, &100_000u128, &0u128);
+}
+
+struct SettlingPool<'a> {
+    amm: AMMClient<'a>,
+    market: PredictionMarketClient<'a>,
+    market_id: BytesN<32>,
+    collateral: token::StellarAssetClient<'a>,
+    creator: Address,
+    lp: Address,
+    yes_trader: Address,
+    no_trader: Address,
+}
+
+/// Helper: a 2_000_000 pool seeded by `lp` on a real market, with 100_000 bought on YES
+/// and 50_000 on NO before the market's cutoff
+fn setup_settling_pool(env: &Env) -> SettlingPool<'_> {
+    env.mock_all_auths();
+    let creator = Address::generate(env);
+    let market_token = deploy_token(env);
+    let market = setup_market_by(env, &market_token.address, &creator);
+    let (amm, lp, collateral) = setup_amm(env);
+    let market_id = BytesN::from_array(env, &[1u8; 32]);
+    amm.initialize_pool(
+        &lp,
+        &market_id,
+        &market.address,
+        &collateral.address,
+        &2_000_000u128,
+        &20u32,
+        &PricingMode::Cpmm,
+    );
+
+    let yes_trader = Address::generate(env);
+    let no_trader = Address::generate(env);
+    collateral.mint(&yes_trader, &100_000);
+    collateral.mint(&no_trader, &50_000);
+    amm.buy_shares(&yes_trader, &market_id, &1, &100_000u128, &0u128);
+    amm.buy_shares(&no_trader, &market_id, &0, &50_000u128, &0u128);
+    SettlingPool {
+        amm,
+        market,
+        market_id,
+        collateral,
+        creator,
+        lp,
+        yes_trader,
+        no_trader,
+    }
+}
+
+/// Helper: close the pool's market and resolve it (the market settles YES)
+fn resolve_market(env: &Env, pool: &SettlingPool) {
+    env.ledger().set_timestamp(2000);
+    pool.market.close_market(&pool.market_id);
+    env.ledger().set_timestamp(3000);
+    pool.market.resolve_market(&pool.market_id);
+}
+
+#[test]
+fn test_settled_pool_redeems_winning_shares_one_to_one() {
+    let env = Env::default();
+    let pool = setup_settling_pool(&env);
+    let yes_shares = pool
+        .amm
+        .get_user_shares(&pool.market_id, &pool.yes_trader, &1);
+    resolve_market(&env, &pool);
+
+    let settlement = pool.amm.settle_pool(&pool.market_id);
+    assert!(pool.amm.is_pool_settled(&pool.market_id));
+    assert_eq!(settlement.winning_outcome, Some(1));
+    assert_eq!(settlement.yes_rate, SETTLEMENT_RATE_PRECISION);
+    assert_eq!(settlement.no_rate, 0);
+    assert_eq!(settlement.redemption_reserve, yes_shares);
+    assert_eq!(
+        pool.amm.get_pool_settlement(&pool.market_id),
+        Some(settlement)
+    );
+
+    let token = token::Client::new(&env, &pool.collateral.address);
+    assert_eq!(
+        pool.amm.redeem_shares(&pool.yes_trader, &pool.market_id),
+        yes_shares
+    );
+    assert_eq!(token.balance(&pool.yes_trader), yes_shares as i128);
+    assert_eq!(
+        pool.amm
+            .get_user_shares(&pool.market_id, &pool.yes_trader, &1),
+        0
+    );
+
+    // Losing shares burn for nothing
+    assert_eq!(pool.amm.redeem_shares(&pool.no_trader, &pool.market_id), 0);
+    assert_eq!(
+        pool.amm
+            .get_user_shares(&pool.market_id, &pool.no_trader, &0),
+        0
+    );
+    assert_eq!(
+        pool.amm
+            .get_pool_settlement(&pool.market_id)
+            .unwrap()
+            .redemption_reserve,
+        0
+    );
+}
+
+#[test]
+fn test_cancelled_market_refunds_both_sides_at_pool_prices() {
+    let env = Env::default();
+    let pool = setup_settling_pool(&env);
+    // Marginal prices in basis points, before fees
+    let (yes_reserve, no_reserve) = pool.amm.get_reserves(&pool.market_id);
+    let yes_price = no_reserve * 10000 / (yes_reserve + no_reserve);
+    let no_price = yes_reserve * 10000 / (yes_reserve + no_reserve);
+    pool.market.cancel_market(&pool.creator, &pool.market_id);
+
+    let settlement = pool.amm.settle_pool(&pool.market_id);
+    assert_eq!(settlement.winning_outcome, None);
+    assert_eq!(
+        settlement.yes_rate,
+        yes_price as u128 * SETTLEMENT_RATE_PRECISION / 10000
+    );
+    assert_eq!(
+        settlement.no_rate,
+        no_price as u128 * SETTLEMENT_RATE_PRECISION / 10000
+    );
+
+    let no_shares = pool
+        .amm
+        .get_user_shares(&pool.market_id, &pool.no_trader, &0);
+    let payout = pool.amm.redeem_shares(&pool.no_trader, &pool.market_id);
+    assert_eq!(
+        payout,
+        no_shares * settlement.no_rate / SETTLEMENT_RATE_PRECISION
+    );
+    assert!(payout > 0);
+    assert!(pool.amm.redeem_shares(&pool.yes_trader, &pool.market_id) > 0);
+}
+
+#[test]
+fn test_lp_withdraws_everything_left_after_settlement() {
+    let env = Env::default();
+    let pool = setup_settling_pool(&env);
+    let yes_shares = pool
+        .amm
+        .get_user_shares(&pool.market_id, &pool.yes_trader, &1);
+    resolve_market(&env, &pool);
+    pool.amm.settle_pool(&pool.market_id);
+
+    // The only LP drains the pool before anyone redeems; winners stay covered
+    let lp_tokens = pool.amm.get_lp_balance(&pool.market_id, &pool.lp);
+    let fees = pool.amm.get_pool_lp_fees(&pool.market_id);
+    let residual = pool.amm.get_collateral_balance(&pool.market_id) as u128 - yes_shares - fees;
+    assert_eq!(
+        pool.amm.get_max_withdrawable(&pool.market_id, &pool.lp),
+        residual
+    );
+    let (yes, no) = pool
+        .amm
+        .remove_liquidity(&pool.lp, &pool.market_id, &lp_tokens, &residual);
+    assert_eq!(yes + no, residual);
+    assert_eq!(pool.amm.get_lp_total_supply(&pool.market_id), 0);
+    assert_eq!(pool.amm.get_reserves(&pool.market_id), (0, 0));
+
+    assert_eq!(
+        pool.amm.redeem_shares(&pool.yes_trader, &pool.market_id),
+        yes_shares
+    );
+    assert_eq!(pool.amm.claim_lp_fees(&pool.lp, &pool.market_id), fees);
+    assert_eq!(pool.amm.get_collateral_balance(&pool.market_id), 0);
+}
+
+#[test]
+#[should_panic(expected = "pool already settled")]
+fn test_settle_pool_twice_rejected() {
+    let env = Env::default();
+    let pool = setup_settling_pool(&env);
+    resolve_market(&env, &pool);
+    pool.amm.settle_pool(&pool.market_id);
+    pool.amm.settle_pool(&pool.market_id);
+}
+
+#[test]
+#[should_panic(expected = "market not finished")]
+fn test_settle_pool_waits_for_market_outcome() {
+    let env = Env::default();
+    let pool = setup_settling_pool(&env);
+    env.ledger().set_timestamp(2000);
+    pool.market.close_market(&pool.market_id);
+    pool.amm.settle_pool(&pool.market_id);
+}
+
+#[test]
+fn test_twap_resists_single_large_trade() {
+    let env = Env::default();
+    env.ledger().set_timestamp(10_000);
+    let (amm, market_id, collateral) = setup_pool(&env, &Address::generate(&env));
+    let whale = Address::generate(&env);
+    collateral.mint(&whale, &2_000_000);
+
+    // An hour at 50/50, then a whale pushes YES far up
+    env.ledger().set_timestamp(13_600);
+    amm.buy_shares(&whale, &market_id, &1, &2_000_000u128, &0u128);
+    let spot = amm.get_twap(&market_id, &0);
+    assert!(spot > 8000);
+
+    env.ledger().set_timestamp(13_660);
+    let twap = amm.get_twap(&market_id, &3600);
+    // (3540 * 5000 + 60 * spot) / 3600
+    assert_eq!(twap, ((3540 * 5000 + 60 * spot as u128) / 3600) as u32);
+    assert!(twap < 5100);
+
+    // Held for a full hour, the manipulated price is all the TWAP sees
+    env.ledger().set_timestamp(17_200);
+    amm.poke(&market_id);
+    assert_eq!(amm.get_twap(&market_id, &3600), spot);
+}
+
+#[test]
+fn test_twap_keeps_one_observation_per_second() {
+    let env = Env::default();
+    env.ledger().set_timestamp(10_000);
+    let (amm, market_id, collateral) = setup_pool(&env, &Address::generate(&env));
+    let trader = Address::generate(&env);
+    collateral.mint(&trader, &200_000);
+
+    env.ledger().set_timestamp(10_100);
+    amm.buy_shares(&trader, &market_id, &1, &100_000u128, &0u128);
+    amm.buy_shares(&trader, &market_id, &0, &100_000u128, &0u128);
+    let observations = amm.get_observations(&market_id);
+    assert_eq!(observations.len(), 2);
+    let last = observations.last().unwrap();
+    assert_eq!(last.timestamp, 10_100);
+    assert_eq!(last.cumulative, 5000 * 100);
+    assert_eq!(last.price, amm.get_twap(&market_id, &0));
+
+    // Only the second trade's price held after that second
+    env.ledger().set_timestamp(10_150);
+    assert_eq!(amm.get_twap(&market_id, &50), last.price);
+}
+
+#[test]
+fn test_twap_ring_keeps_latest_observations() {
+    let env = Env::default();
+    env.ledger().set_timestamp(10_000);
+    let (amm, market_id, _) = setup_pool(&env, &Address::generate(&env));
+    for second in 1..=70u64 {
+        env.ledger().set_timestamp(10_000 + second);
+        amm.poke(&market_id);
+    }
+
+    let observations = amm.get_observations(&market_id);
+    assert_eq!(observations.len(), MAX_OBSERVATIONS);
+    assert_eq!(observations.get(0).unwrap().timestamp, 10_007);
+    assert_eq!(amm.get_twap(&market_id, &63), 5000);
+}
+
+#[test]
+#[should_panic(expected = "twap window exceeds history")]
+fn test_twap_window_longer_than_history_rejected() {
+    let env = Env::default();
+    env.ledger().set_timestamp(10_000);
+    let (amm, market_id, _) = setup_pool(&env, &Address::generate(&env));
+    env.ledger().set_timestamp(10_500);
+    amm.get_twap(&market_id, &501);
+}
+
+// ===== EMERGENCY PAUSE =====
+
+#[test]
+fn test_paused_pool_blocks_trading_until_unpaused() {
+    let env = Env::default();
+    let pool = setup_settling_pool(&env);
+    let trader = Address::generate(&env);
+    pool.collateral.mint(&trader, &20_000);
+
+    pool.amm.pause_pool(&pool.lp, &pool.market_id);
+    assert!(pool.amm.is_pool_paused(&pool.market_id));
+    assert!(pool
+        .amm
+        .try_buy_shares(&trader, &pool.market_id, &1, &10_000u128, &0u128)
+        .is_err());
+    assert!(pool
+        .amm
+        .try_sell_shares(&pool.yes_trader, &pool.market_id, &1, &1_000u128, &0u128)
+        .is_err());
+    assert!(pool
+        .amm
+        .try_quote_buy(&pool.market_id, &1, &10_000u128)
+        .is_err());
+    assert!(pool
+        .amm
+        .try_add_liquidity(&trader, &pool.market_id, &10_000u128, &0u128)
+        .is_err());
+
+    pool.amm.unpause_pool(&pool.lp, &pool.market_id);
+    assert!(!pool.amm.is_pool_paused(&pool.market_id));
+    assert!(
+        pool.amm
+            .buy_shares(&trader, &pool.market_id, &1, &10_000u128, &0u128)
+            > 0
+    );
+}
+
+#[test]
+fn test_lp_exits_paused_pool_past_resting_floor() {
+    let env = Env::default();
+    let pool = setup_resting_pool(&env);
+
+    pool.amm.pause_pool(&pool.admin, &pool.market_id);
+    let (yes, no) = pool
+        .amm
+        .remove_liquidity(&pool.lp, &pool.market_id, &1_000_000u128, &0u128);
+    assert_eq!(yes + no, 1_000_000);
+    assert_eq!(pool.amm.get_lp_balance(&pool.market_id, &pool.lp), 0);
+}
+
+#[test]
+fn test_paused_pool_still_settles_and_redeems() {
+    let env = Env::default();
+    let pool = setup_settling_pool(&env);
+    let yes_shares = pool
+        .amm
+        .get_user_shares(&pool.market_id, &pool.yes_trader, &1);
+    resolve_market(&env, &pool);
+
+    pool.amm.pause_pool(&pool.lp, &pool.market_id);
+    pool.amm.settle_pool(&pool.market_id);
+    assert_eq!(
+        pool.amm.redeem_shares(&pool.yes_trader, &pool.market_id),
+        yes_shares
+    );
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_pause_pool_requires_admin() {
+    let env = Env::default();
+    let pool = setup_settling_pool(&env);
+
+    pool.amm
+        .pause_pool(&Address::generate(&env), &pool.market_id);
+}
+
+// ===== OUTCOME SWAP =====
+
+#[test]
+fn test_swap_outcome_matches_two_step_route() {
+    let atomic_env = Env::default();
+    let atomic = setup_settling_pool(&atomic_env);
+    let manual_env = Env::default();
+    let manual = setup_settling_pool(&manual_env);
+    let shares_in = atomic
+        .amm
+        .get_user_shares(&atomic.market_id, &atomic.yes_trader, &1);
+
+    let quote = atomic
+        .amm
+        .quote_swap_outcome(&atomic.market_id, &1, &0, &shares_in);
+    let swapped = atomic.amm.swap_outcome(
+        &atomic.yes_trader,
+        &atomic.market_id,
+        &1,
+        &0,
+        &shares_in,
+        &quote.shares_out,
+    );
+    assert_eq!(swapped, quote.shares_out);
+
+    let payout = manual.amm.sell_shares(
+        &manual.yes_trader,
+        &manual.market_id,
+        &1,
+        &shares_in,
+        &0u128,
+    );
+    assert_eq!(payout, quote.collateral_routed);
+    let bought = manual
+        .amm
+        .buy_shares(&manual.yes_trader, &manual.market_id, &0, &payout, &0u128);
+    assert_eq!(swapped, bought);
+
+    assert_eq!(
+        atomic.amm.get_reserves(&atomic.market_id),
+        manual.amm.get_reserves(&manual.market_id)
+    );
+    assert_eq!(
+        atomic.amm.get_pool_lp_fees(&atomic.market_id),
+        manual.amm.get_pool_lp_fees(&manual.market_id)
+    );
+    for outcome in [0u32, 1] {
+        assert_eq!(
+            atomic
+                .amm
+                .get_user_shares(&atomic.market_id, &atomic.yes_trader, &outcome),
+            manual
+                .amm
+                .get_user_shares(&manual.market_id, &manual.yes_trader, &outcome)
+        );
+    }
+    assert_eq!(
+        atomic.amm.get_spot_price(&atomic.market_id, &0),
+        manual.amm.get_spot_price(&manual.market_id, &0)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Slippage exceeded")]
+fn test_swap_outcome_reverts_below_min_shares_out() {
+    let env = Env::default();
+    let pool = setup_settling_pool(&env);
+    let shares_in = pool
+        .amm
+        .get_user_shares(&pool.market_id, &pool.yes_trader, &1);
+    let quote = pool
+        .amm
+        .quote_swap_outcome(&pool.market_id, &1, &0, &shares_in);
+
+    pool.amm.swap_outcome(
+        &pool.yes_trader,
+        &pool.market_id,
+        &1,
+        &0,
+        &shares_in,
+        &(quote.shares_out + 1),
+    );
+}
+
+#[test]
+fn test_swap_outcome_rejected_on_paused_or_resolved_pool() {
+    let env = Env::default();
+    let pool = setup_settling_pool(&env);
+
+    pool.amm.pause_pool(&pool.lp, &pool.market_id);
+    assert!(pool
+        .amm
+        .try_swap_outcome(&pool.no_trader, &pool.market_id, &0, &1, &1_000u128, &0u128)
+        .is_err());
+    pool.amm.unpause_pool(&pool.lp, &pool.market_id);
+
+    resolve_market(&env, &pool);
+    assert!(pool
+        .amm
+        .try_swap_outcome(&pool.no_trader, &pool.market_id, &0, &1, &1_000u128, &0u128)
+        .is_err());
+    assert!(pool
+        .amm
+        .try_quote_swap_outcome(&pool.market_id, &0, &1, &1_000u128)
+        .is_err());
+}
@@ -253,10 +253,24 @@ fn test_update_treasury_address() {
 }
 */
 
-use soroban_sdk::{testutils::Address as _, Address, Env, Symbol};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, Bytes, BytesN, Env, Symbol, Vec,
+};
 
 // Import the Factory contract
-use boxmeout::factory::{MarketFactory, MarketFactoryClient};
+use boxmeout::amm::{AMMClient, AMM};
+use boxmeout::factory::{
+    BetRecordSnapshot, DefaultParams, DurationTier, FactoryError, FeeSchedule, MarketFactory,
+    MarketFactoryClient, MarketOverrides, MarketParams, DURATION_DAY, DURATION_MONTH,
+    DURATION_WEEK, EXPOSURE_STATUS_OK, EXPOSURE_STATUS_UNKNOWN_MARKET, JURISDICTION_ANY,
+    MAX_BATCH_MARKETS, MAX_CLAIM_BATCH, MAX_DEFAULT_FEE_BPS, MAX_HEALTH_BATCH, MAX_LIST_MARKETS,
+    MAX_OVERVIEW_BATCH, OVERVIEW_MARKET_READ_FAILED, OVERVIEW_UNKNOWN_MARKET, PARAM_CHANGE_DELAY,
+};
+use boxmeout::helpers::hashing;
+use boxmeout::market::{MarketError, PredictionMarket, PredictionMarketClient, ResolutionOutcome};
+use boxmeout::oracle::{OracleManager, OracleManagerClient, CATEGORY_ANY};
+use boxmeout::treasury::{Treasury, TreasuryClient};
 // Helper function to create test environment
 fn create_test_env() -> Env {
     Env::default()
@@ -326,6 +340,7 @@ fn test_create_market_invalid_timestamps() {
     let treasury = Address::generate(&env);
     env.mock_all_auths();
     client.initialize(&admin, &usdc, &treasury);
+    client.set_permissionless(&admin, &true);
 
     // Try to create market with closing_time > resolution_time
     let creator = Address::generate(&env);
@@ -337,11 +352,17 @@ fn test_create_market_invalid_timestamps() {
 
     client.create_market(
         &creator,
-        &title,
-        &description,
-        &category,
-        &closing_time,
-        &resolution_time,
+        &MarketParams {
+            title,
+            description,
+            category,
+            closing_time,
+            resolution_time,
+            operator: None,
+            nonce: 0,
+            region: Symbol::new(&env, "global"),
+        },
+        &None,
     );
 }
 
@@ -358,6 +379,7 @@ fn test_create_market_closing_time_in_past() {
     let treasury = Address::generate(&env);
     env.mock_all_auths();
     client.initialize(&admin, &usdc, &treasury);
+    client.set_permissionless(&admin, &true);
 
     // Try to create market with closing_time in the past
     let creator = Address::generate(&env);
@@ -369,11 +391,17 @@ fn test_create_market_closing_time_in_past() {
 
     client.create_market(
         &creator,
-        &title,
-        &description,
-        &category,
-        &closing_time,
-        &resolution_time,
+        &MarketParams {
+            title,
+            description,
+            category,
+            closing_time,
+            resolution_time,
+            operator: None,
+            nonce: 0,
+            region: Symbol::new(&env, "global"),
+        },
+        &None,
     );
 }
 
@@ -397,3 +425,2436 @@ fn test_update_treasury_address() {
     // Test admin can update treasury address
     // Test non-admin cannot update
 }
+
+// ============================================================================
+// USER EXPOSURE TESTS
+// ============================================================================
+
+/// Helper: factory wired to a real treasury and USDC token so create_market succeeds
+fn setup_factory_with_treasury(
+    env: &Env,
+) -> (
+    MarketFactoryClient<'_>,
+    token::StellarAssetClient<'_>,
+    Address,
+) {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let usdc_address = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let usdc = token::StellarAssetClient::new(env, &usdc_address);
+
+    let factory_id = register_factory(env);
+    let treasury_id = env.register(Treasury, ());
+    TreasuryClient::new(env, &treasury_id).initialize(&admin, &usdc_address, &factory_id);
+
+    let client = MarketFactoryClient::new(env, &factory_id);
+    client.initialize(&admin, &usdc_address, &treasury_id);
+    client.set_permissionless(&admin, &true);
+
+    (client, usdc, admin)
+}
+
+/// Helper: create a market through the factory and link a deployed market contract to it
+fn create_linked_market<'a>(
+    env: &'a Env,
+    factory: &MarketFactoryClient<'a>,
+    usdc: &token::StellarAssetClient<'a>,
+    title: &str,
+) -> (BytesN<32>, PredictionMarketClient<'a>) {
+    create_operator_market(env, factory, usdc, title, None)
+}
+
+/// Helper: like `create_linked_market`, tagged with `operator`
+fn create_operator_market<'a>(
+    env: &'a Env,
+    factory: &MarketFactoryClient<'a>,
+    usdc: &token::StellarAssetClient<'a>,
+    title: &str,
+    operator: Option<Symbol>,
+) -> (BytesN<32>, PredictionMarketClient<'a>) {
+    let creator = Address::generate(env);
+    usdc.mint(&creator, &10_000_000);
+
+    let closing_time = env.ledger().timestamp() + 86400;
+    let resolution_time = closing_time + 3600;
+    let market_id = factory.create_market(
+        &creator,
+        &MarketParams {
+            title: Symbol::new(env, title),
+            description: Symbol::new(env, "Desc"),
+            category: Symbol::new(env, "Boxing"),
+            closing_time,
+            resolution_time,
+            operator,
+            nonce: 0,
+            region: Symbol::new(env, "global"),
+        },
+        &None,
+    );
+
+    let market_address = env.register(PredictionMarket, ());
+    let market = PredictionMarketClient::new(env, &market_address);
+    market.initialize(
+        &market_id,
+        &creator,
+        &factory.address,
+        &usdc.address,
+        &Address::generate(env),
+        &closing_time,
+        &resolution_time,
+        &factory.get_fee_bps(),
+        &None,
+    );
+    factory.set_market_address(&market_id, &market_address);
+
+    (market_id, market)
+}
+
+/// Helper: commit and reveal a prediction for `user`
+fn place_revealed_prediction(
+    env: &Env,
+    market: &PredictionMarketClient,
+    usdc: &token::StellarAssetClient,
+    market_id: &BytesN<32>,
+    user: &Address,
+    outcome: u32,
+    amount: i128,
+) {
+    usdc.mint(user, &amount);
+    let salt = BytesN::from_array(env, &[7u8; 32]);
+    let mut preimage = Bytes::new(env);
+    preimage.extend_from_array(&market_id.to_array());
+    preimage.extend_from_array(&outcome.to_be_bytes());
+    preimage.extend_from_array(&salt.to_array());
+    let commit_hash = BytesN::from_array(env, &env.crypto().sha256(&preimage).to_array());
+
+    market.commit_prediction(user, &commit_hash, &amount);
+    market.reveal_prediction(user, market_id, &outcome, &amount, &salt);
+}
+
+#[test]
+fn test_get_user_exposure_across_markets() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+
+    let (market_a_id, market_a) = create_linked_market(&env, &factory, &usdc, "MarketA");
+    let (market_b_id, market_b) = create_linked_market(&env, &factory, &usdc, "MarketB");
+    let (market_c_id, _market_c) = create_linked_market(&env, &factory, &usdc, "MarketC");
+
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    // Market A: user YES 300 vs other NO 100 -> 75% implied for YES
+    place_revealed_prediction(&env, &market_a, &usdc, &market_a_id, &user, 1, 300);
+    place_revealed_prediction(&env, &market_a, &usdc, &market_a_id, &other, 0, 100);
+    // Market B: offsetting NO position, alone in the pool
+    place_revealed_prediction(&env, &market_b, &usdc, &market_b_id, &user, 0, 200);
+
+    let unknown_id = BytesN::from_array(&env, &[0xAB; 32]);
+    let mut ids = Vec::new(&env);
+    ids.push_back(market_a_id.clone());
+    ids.push_back(market_b_id.clone());
+    ids.push_back(market_c_id.clone());
+    ids.push_back(unknown_id.clone());
+
+    let rows = factory.get_user_exposure(&user, &ids);
+    assert_eq!(rows.len(), 4);
+
+    let row_a = rows.get(0).unwrap();
+    assert_eq!(row_a.market_id, market_a_id);
+    assert_eq!(row_a.status, EXPOSURE_STATUS_OK);
+    assert_eq!(row_a.stake, 300);
+    assert_eq!(row_a.side, 1);
+    assert_eq!(row_a.implied_probability_bps, 7500);
+    assert_eq!(row_a.claimable, 0);
+
+    let row_b = rows.get(1).unwrap();
+    assert_eq!(row_b.status, EXPOSURE_STATUS_OK);
+    assert_eq!(row_b.stake, 200);
+    assert_eq!(row_b.side, 0);
+    assert_eq!(row_b.implied_probability_bps, 10000);
+
+    // No position: OK row with zeroed values
+    let row_c = rows.get(2).unwrap();
+    assert_eq!(row_c.status, EXPOSURE_STATUS_OK);
+    assert_eq!(row_c.stake, 0);
+    assert_eq!(row_c.side, 2);
+
+    // Unknown id: explicit error row, call does not abort
+    let row_unknown = rows.get(3).unwrap();
+    assert_eq!(row_unknown.market_id, unknown_id);
+    assert_eq!(row_unknown.status, EXPOSURE_STATUS_UNKNOWN_MARKET);
+    assert_eq!(row_unknown.stake, 0);
+}
+
+#[test]
+fn test_get_user_exposure_reports_claimable_after_resolution() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    let (market_id, market) = create_linked_market(&env, &factory, &usdc, "MarketA");
+
+    let user = Address::generate(&env);
+    market.test_set_prediction(&user, &1u32, &1000);
+    market.test_setup_resolution(&market_id, &1u32, &1000, &500);
+
+    let mut ids = Vec::new(&env);
+    ids.push_back(market_id);
+    let row = factory.get_user_exposure(&user, &ids).get(0).unwrap();
+
+    // Sole winner collects the whole 1500 pool (no platform fee configured)
+    assert_eq!(row.claimable, 1500);
+}
+
+#[test]
+fn test_get_user_exposure_reports_refund_on_invalid_market() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    let (market_id, market) = create_linked_market(&env, &factory, &usdc, "MarketA");
+
+    let user = Address::generate(&env);
+    usdc.mint(&user, &1000);
+    market.place_prediction(&user, &1u32, &1000);
+    env.ledger().set_timestamp(market.get_betting_cutoff());
+    market.close_market(&market_id);
+    env.ledger().set_timestamp(market.get_resolution_time());
+    market.resolve_with_outcome(&market_id, &ResolutionOutcome::Invalid);
+
+    let row = factory
+        .get_user_exposure(&user, &vec![&env, market_id])
+        .get(0)
+        .unwrap();
+    assert_eq!(row.claimable, 1000);
+}
+
+#[test]
+#[should_panic(expected = "too many markets")]
+fn test_get_user_exposure_rejects_oversized_batch() {
+    let env = create_test_env();
+    let (factory, _usdc, _admin) = setup_factory_with_treasury(&env);
+
+    let mut ids = Vec::new(&env);
+    for i in 0..21u8 {
+        ids.push_back(BytesN::from_array(&env, &[i; 32]));
+    }
+    factory.get_user_exposure(&Address::generate(&env), &ids);
+}
+
+#[test]
+#[should_panic(expected = "market not found")]
+fn test_set_market_address_requires_registered_market() {
+    let env = create_test_env();
+    let (factory, _usdc, _admin) = setup_factory_with_treasury(&env);
+
+    factory.set_market_address(
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &Address::generate(&env),
+    );
+}
+
+// ============================================================================
+// NETWORK BINDING TESTS
+// ============================================================================
+
+/// Helper: a hash that differs from the factory's network binding
+fn wrong_network_hash(env: &Env, factory: &MarketFactoryClient) -> BytesN<32> {
+    let mut bytes = factory.get_network_binding().to_array();
+    bytes[0] ^= 0xff;
+    BytesN::from_array(env, &bytes)
+}
+
+#[test]
+fn test_network_binding_matches_ledger_network() {
+    let env = create_test_env();
+    let (factory, _usdc, _admin) = setup_factory_with_treasury(&env);
+
+    assert_eq!(factory.get_network_binding(), env.ledger().network_id());
+}
+
+#[test]
+fn test_emergency_shutdown_rejects_wrong_network() {
+    let env = create_test_env();
+    let (factory, _usdc, _admin) = setup_factory_with_treasury(&env);
+
+    let result = factory.try_emergency_shutdown(&wrong_network_hash(&env, &factory));
+    assert_eq!(result, Err(Ok(FactoryError::NetworkMismatch)));
+    assert!(!factory.is_shut_down());
+}
+
+#[test]
+#[should_panic(expected = "factory is shut down")]
+fn test_emergency_shutdown_with_binding_blocks_creation() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+
+    factory.emergency_shutdown(&factory.get_network_binding());
+    assert!(factory.is_shut_down());
+
+    create_linked_market(&env, &factory, &usdc, "after_shutdown");
+}
+
+#[test]
+fn test_sunset_rejects_wrong_network() {
+    let env = create_test_env();
+    let (factory, _usdc, _admin) = setup_factory_with_treasury(&env);
+
+    let result = factory.try_sunset(&wrong_network_hash(&env, &factory));
+    assert_eq!(result, Err(Ok(FactoryError::NetworkMismatch)));
+    assert!(!factory.is_sunset());
+
+    factory.sunset(&factory.get_network_binding());
+    assert!(factory.is_sunset());
+}
+
+#[test]
+fn test_set_market_template_requires_network_binding() {
+    let env = create_test_env();
+    let (factory, _usdc, _admin) = setup_factory_with_treasury(&env);
+    let template = BytesN::from_array(&env, &[9u8; 32]);
+
+    let result = factory.try_set_market_template(&wrong_network_hash(&env, &factory), &template);
+    assert_eq!(result, Err(Ok(FactoryError::NetworkMismatch)));
+    assert_eq!(factory.get_market_template(), None);
+
+    factory.set_market_template(&factory.get_network_binding(), &template);
+    assert_eq!(factory.get_market_template(), Some(template));
+}
+
+#[test]
+#[should_panic(expected = "factory is sunset")]
+fn test_set_market_template_rejected_after_sunset() {
+    let env = create_test_env();
+    let (factory, _usdc, _admin) = setup_factory_with_treasury(&env);
+    let binding = factory.get_network_binding();
+
+    factory.sunset(&binding);
+    factory.set_market_template(&binding, &BytesN::from_array(&env, &[9u8; 32]));
+}
+
+// ============================================================================
+// BATCH CLAIM TESTS
+// ============================================================================
+
+#[test]
+fn test_claim_all_skips_unclaimable_markets() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    let user = Address::generate(&env);
+
+    // Claimable: resolved YES, user is the sole winner of a 1000 + 500 pool
+    let (claimable_id, claimable) = create_linked_market(&env, &factory, &usdc, "Claimable");
+    usdc.mint(&claimable.address, &1500);
+    claimable.test_set_prediction(&user, &1u32, &1000);
+    claimable.test_setup_resolution(&claimable_id, &1u32, &1000, &500);
+
+    // Already claimed beforehand
+    let (claimed_id, already_claimed) = create_linked_market(&env, &factory, &usdc, "Claimed");
+    usdc.mint(&already_claimed.address, &400);
+    already_claimed.test_set_prediction(&user, &1u32, &400);
+    already_claimed.test_setup_resolution(&claimed_id, &1u32, &400, &0);
+    already_claimed.claim_winnings(&user, &claimed_id);
+
+    // Still open: nothing to claim yet
+    let (open_id, open_market) = create_linked_market(&env, &factory, &usdc, "Open");
+    place_revealed_prediction(&env, &open_market, &usdc, &open_id, &user, 1, 300);
+
+    let unknown_id = BytesN::from_array(&env, &[0xCD; 32]);
+
+    let mut ids = Vec::new(&env);
+    ids.push_back(claimed_id.clone());
+    ids.push_back(open_id.clone());
+    ids.push_back(claimable_id.clone());
+    ids.push_back(unknown_id);
+
+    let balance_before = usdc.balance(&user);
+    let results = factory.claim_all(&user, &ids);
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results.get(0).unwrap(), (claimable_id.clone(), 1500));
+    assert_eq!(usdc.balance(&user) - balance_before, 1500);
+
+    // A second batch finds nothing left to claim
+    assert_eq!(factory.claim_all(&user, &ids).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "too many markets")]
+fn test_claim_all_rejects_oversized_batch() {
+    let env = create_test_env();
+    let (factory, _usdc, _admin) = setup_factory_with_treasury(&env);
+
+    let mut ids = Vec::new(&env);
+    for i in 0..(MAX_CLAIM_BATCH + 1) {
+        ids.push_back(BytesN::from_array(&env, &[i as u8; 32]));
+    }
+    factory.claim_all(&Address::generate(&env), &ids);
+}
+
+// ============================================================================
+// ORACLE HEALTH GATE TESTS
+// ============================================================================
+
+/// Helper: initialized oracle with `registered` attestors and a threshold of 2
+/// Helper: oracle manager with `registered` attestors and `factory` as a market registrar
+fn setup_oracle(env: &Env, factory: &Address, registered: u32) -> Address {
+    let oracle_id = env.register(OracleManager, ());
+    let oracle = OracleManagerClient::new(env, &oracle_id);
+    let oracle_admin = Address::generate(env);
+    oracle.initialize(&oracle_admin, &2u32);
+    oracle.add_registrar(&oracle_admin, factory);
+    for _ in 0..registered {
+        oracle.register_oracle(
+            &Address::generate(env),
+            &Symbol::new(env, "node"),
+            &Vec::new(env),
+        );
+    }
+    oracle_id
+}
+
+#[test]
+fn test_set_oracle_accepts_capable_oracle() {
+    let env = create_test_env();
+    let (factory, _usdc, _admin) = setup_factory_with_treasury(&env);
+    let oracle_id = setup_oracle(&env, &factory.address, 2);
+
+    assert!(!factory.is_oracle_healthy());
+    factory.set_oracle(&oracle_id);
+    assert_eq!(factory.get_oracle(), Some(oracle_id));
+    assert!(factory.is_oracle_healthy());
+}
+
+#[test]
+#[should_panic(expected = "oracle capabilities unavailable")]
+fn test_set_oracle_rejects_contract_without_capabilities() {
+    let env = create_test_env();
+    let (factory, _usdc, _admin) = setup_factory_with_treasury(&env);
+
+    // A market contract does not expose get_capabilities
+    factory.set_oracle(&env.register(PredictionMarket, ()));
+}
+
+#[test]
+#[should_panic(expected = "oracle unhealthy")]
+fn test_create_market_blocked_when_oracle_not_consensus_ready() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+
+    // Capable oracle, but only one of two required attestors registered
+    factory.set_oracle(&setup_oracle(&env, &factory.address, 1));
+    assert!(!factory.is_oracle_healthy());
+
+    create_linked_market(&env, &factory, &usdc, "Gated");
+}
+
+// ===== MARKET DEPLOYMENT =====
+
+/// Market contract wasm used as the factory template. Regenerate with
+/// `cargo build -p boxmeout --target wasm32v1-none --release --no-default-features --features market`
+/// and copy `target/wasm32v1-none/release/boxmeout.wasm` here.
+const MARKET_WASM: &[u8] = include_bytes!("fixtures/market.wasm");
+
+/// Helper: upload the market template; parsing and deploying the full market
+/// wasm does not fit the default test budget
+fn upload_market_wasm(env: &Env) -> BytesN<32> {
+    env.cost_estimate().budget().reset_unlimited();
+    env.deployer().upload_contract_wasm(MARKET_WASM)
+}
+
+fn market_params(env: &Env, title: &str) -> MarketParams {
+    let closing_time = env.ledger().timestamp() + 86400;
+    MarketParams {
+        title: Symbol::new(env, title),
+        description: Symbol::new(env, "Desc"),
+        category: Symbol::new(env, "Boxing"),
+        closing_time,
+        resolution_time: closing_time + 3600,
+        operator: None,
+        nonce: 0,
+        region: Symbol::new(env, "global"),
+    }
+}
+
+#[test]
+fn test_create_market_deploys_market_contracts() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    let oracle_id = setup_oracle(&env, &factory.address, 2);
+    factory.set_oracle(&oracle_id);
+    factory.set_fee_bps(&150);
+    let wasm_hash = upload_market_wasm(&env);
+    factory.set_market_template(&factory.get_network_binding(), &wasm_hash);
+
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &100_000_000);
+    let params = market_params(&env, "FightOne");
+    let market_id1 = factory.create_market(&creator, &params, &None);
+    let market_id2 = factory.create_market(&creator, &market_params(&env, "FightTwo"), &None);
+
+    assert_ne!(market_id1, market_id2);
+    assert_eq!(factory.get_market_count(), 2);
+    let address1 = factory.get_market_address(&market_id1).unwrap();
+    let address2 = factory.get_market_address(&market_id2).unwrap();
+    assert_ne!(address1, address2);
+
+    for (market_id, address) in [(&market_id1, &address1), (&market_id2, &address2)] {
+        let market = PredictionMarketClient::new(&env, address);
+        let state = market.get_market_state(market_id);
+        assert_eq!(state.status, 0); // Open
+        assert_eq!(market.get_betting_cutoff(), params.closing_time);
+        assert_eq!(market.get_resolution_time(), params.resolution_time);
+        assert_eq!(market.get_fee_bps(), 150);
+    }
+}
+
+#[test]
+fn test_deployed_market_takes_factory_fee() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    factory.set_oracle(&setup_oracle(&env, &factory.address, 2));
+    factory.set_fee_bps(&275);
+    let wasm_hash = upload_market_wasm(&env);
+    factory.set_market_template(&factory.get_network_binding(), &wasm_hash);
+
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &10_000_000);
+    let market_id = factory.create_market(&creator, &market_params(&env, "FeeBout"), &None);
+    let market =
+        PredictionMarketClient::new(&env, &factory.get_market_address(&market_id).unwrap());
+    assert_eq!(market.get_fee_bps(), 275);
+
+    // Later factory changes leave the deployed market's fee alone
+    factory.set_fee_bps(&100);
+    assert_eq!(market.get_fee_bps(), 275);
+}
+
+#[test]
+fn test_deployed_market_carries_metadata() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    let oracle = setup_oracle(&env, &factory.address, 2);
+    factory.set_oracle(&oracle);
+    let wasm_hash = upload_market_wasm(&env);
+    factory.set_market_template(&factory.get_network_binding(), &wasm_hash);
+
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &10_000_000);
+    let params = market_params(&env, "MetaBout");
+    let market_id = factory.create_market(&creator, &params, &None);
+    let market =
+        PredictionMarketClient::new(&env, &factory.get_market_address(&market_id).unwrap());
+
+    let metadata = market.get_metadata().unwrap();
+    assert_eq!(
+        metadata.question_hash,
+        hashing::question_hash(&env, &params.title, &params.description)
+    );
+    assert_eq!(metadata.category, params.category);
+    assert_eq!(metadata.creator, creator);
+    assert_eq!(metadata.oracle, oracle);
+    assert_eq!(metadata.resolution_time, params.resolution_time);
+    assert_eq!(metadata.outcome_count, 2);
+
+    // The factory admin can still correct the hash before the first prediction
+    let new_hash = BytesN::from_array(&env, &[9u8; 32]);
+    factory.update_market_question_hash(&admin, &market_id, &new_hash);
+    assert_eq!(market.get_metadata().unwrap().question_hash, new_hash);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_update_market_question_hash_non_admin() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    factory.set_oracle(&setup_oracle(&env, &factory.address, 2));
+    let wasm_hash = upload_market_wasm(&env);
+    factory.set_market_template(&factory.get_network_binding(), &wasm_hash);
+
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &10_000_000);
+    let market_id = factory.create_market(&creator, &market_params(&env, "MetaBout"), &None);
+    factory.update_market_question_hash(
+        &creator,
+        &market_id,
+        &BytesN::from_array(&env, &[9u8; 32]),
+    );
+}
+
+#[test]
+fn test_set_market_account_frozen() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    factory.set_oracle(&setup_oracle(&env, &factory.address, 2));
+    let wasm_hash = upload_market_wasm(&env);
+    factory.set_market_template(&factory.get_network_binding(), &wasm_hash);
+
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &10_000_000);
+    let market_id = factory.create_market(&creator, &market_params(&env, "FreezeBout"), &None);
+    let market =
+        PredictionMarketClient::new(&env, &factory.get_market_address(&market_id).unwrap());
+    let user = Address::generate(&env);
+
+    factory.set_market_account_frozen(&admin, &market_id, &user, &true);
+    assert!(market.is_account_frozen(&user));
+    factory.set_market_account_frozen(&admin, &market_id, &user, &false);
+    assert!(!market.is_account_frozen(&user));
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_set_market_account_frozen_non_admin() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    factory.set_oracle(&setup_oracle(&env, &factory.address, 2));
+    let wasm_hash = upload_market_wasm(&env);
+    factory.set_market_template(&factory.get_network_binding(), &wasm_hash);
+
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &10_000_000);
+    let market_id = factory.create_market(&creator, &market_params(&env, "FreezeBout"), &None);
+    factory.set_market_account_frozen(&creator, &market_id, &creator, &true);
+}
+
+#[test]
+fn test_rebuild_market_position() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    let (market_id, market) = create_linked_market(&env, &factory, &usdc, "RebuildBout");
+
+    let user = Address::generate(&env);
+    usdc.mint(&user, &1_000_000);
+    market.place_prediction(&user, &1u32, &1_000_000);
+    let history = vec![
+        &env,
+        BetRecordSnapshot {
+            outcome: 1,
+            amount: 1_000_000,
+            timestamp: env.ledger().timestamp(),
+        },
+    ];
+
+    market.test_set_prediction(&user, &1u32, &5);
+    factory.rebuild_market_position(&admin, &market_id, &user, &history);
+    assert_eq!(market.get_position(&user).yes_amount, 1_000_000);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_rebuild_market_position_non_admin() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    let (market_id, _market) = create_linked_market(&env, &factory, &usdc, "RebuildBout");
+
+    let user = Address::generate(&env);
+    factory.rebuild_market_position(&user, &market_id, &user, &Vec::new(&env));
+}
+
+#[test]
+fn test_extend_market_betting_cutoff() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    let (market_id, market) = create_linked_market(&env, &factory, &usdc, "CutoffBout");
+
+    let new_cutoff = env.ledger().timestamp() + 100;
+    factory.extend_market_betting_cutoff(&admin, &market_id, &new_cutoff);
+    assert_eq!(market.get_betting_cutoff(), new_cutoff);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_extend_market_betting_cutoff_non_admin() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    let (market_id, _market) = create_linked_market(&env, &factory, &usdc, "CutoffBout");
+
+    factory.extend_market_betting_cutoff(
+        &Address::generate(&env),
+        &market_id,
+        &(env.ledger().timestamp() + 100),
+    );
+}
+
+/// Helper: factory-registered market whose oracle is bound to it, so the
+/// market can push resolution time changes through to the oracle
+fn create_oracle_bound_market<'a>(
+    env: &'a Env,
+    factory: &MarketFactoryClient<'a>,
+    usdc: &token::StellarAssetClient<'a>,
+) -> (BytesN<32>, PredictionMarketClient<'a>, u64) {
+    let oracle_id = env.register(OracleManager, ());
+    let oracle = OracleManagerClient::new(env, &oracle_id);
+    oracle.initialize(&Address::generate(env), &1u32);
+
+    let creator = Address::generate(env);
+    usdc.mint(&creator, &10_000_000);
+    let closing_time = env.ledger().timestamp() + 86400;
+    let resolution_time = closing_time + 3600;
+    let market_id = factory.create_market(
+        &creator,
+        &MarketParams {
+            title: Symbol::new(env, "PostponedBout"),
+            description: Symbol::new(env, "Desc"),
+            category: Symbol::new(env, "Boxing"),
+            closing_time,
+            resolution_time,
+            operator: None,
+            nonce: 0,
+            region: Symbol::new(env, "global"),
+        },
+        &None,
+    );
+    oracle.register_market(
+        &market_id,
+        &resolution_time,
+        &Symbol::new(env, CATEGORY_ANY),
+    );
+
+    let market_address = env.register(PredictionMarket, ());
+    let market = PredictionMarketClient::new(env, &market_address);
+    market.initialize(
+        &market_id,
+        &creator,
+        &factory.address,
+        &usdc.address,
+        &oracle_id,
+        &closing_time,
+        &resolution_time,
+        &0,
+        &None,
+    );
+    oracle.set_market_contract(&market_id, &market_address);
+    factory.set_market_address(&market_id, &market_address);
+
+    (market_id, market, resolution_time)
+}
+
+#[test]
+fn test_extend_market_resolution_time() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    let (market_id, market, resolution_time) = create_oracle_bound_market(&env, &factory, &usdc);
+
+    factory.extend_market_resolution_time(&admin, &market_id, &(resolution_time + 86400));
+    assert_eq!(market.get_resolution_time(), resolution_time + 86400);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_extend_market_resolution_time_non_admin() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    let (market_id, _market, resolution_time) = create_oracle_bound_market(&env, &factory, &usdc);
+
+    factory.extend_market_resolution_time(
+        &Address::generate(&env),
+        &market_id,
+        &(resolution_time + 86400),
+    );
+}
+
+#[test]
+#[should_panic(expected = "factory not initialized")]
+fn test_create_market_before_initialize() {
+    let env = create_test_env();
+    env.mock_all_auths();
+    let factory = MarketFactoryClient::new(&env, &register_factory(&env));
+
+    factory.create_market(
+        &Address::generate(&env),
+        &market_params(&env, "Early"),
+        &None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "invalid timestamps")]
+fn test_create_market_resolution_in_past() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    env.ledger().set_timestamp(100_000);
+
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &100_000_000);
+    let mut params = market_params(&env, "Late");
+    params.resolution_time = 50_000;
+
+    factory.create_market(&creator, &params, &None);
+}
+
+#[test]
+fn test_create_markets_deploys_series() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    factory.set_oracle(&setup_oracle(&env, &factory.address, 2));
+    let wasm_hash = upload_market_wasm(&env);
+    factory.set_market_template(&factory.get_network_binding(), &wasm_hash);
+
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &100_000_000);
+    let fee = factory.get_creation_fee().amount;
+    let series = vec![
+        &env,
+        market_params(&env, "BoutOne"),
+        market_params(&env, "BoutTwo"),
+        market_params(&env, "BoutThree"),
+    ];
+
+    let created = factory.create_markets(&creator, &series);
+
+    assert_eq!(created.len(), 3);
+    assert_eq!(factory.get_market_count(), 3);
+    for (i, (market_id, address)) in created.iter().enumerate() {
+        assert_eq!(
+            market_id,
+            factory.compute_market_id(&series.get_unchecked(i as u32))
+        );
+        assert_eq!(
+            factory.get_market_address(&market_id),
+            Some(address.clone())
+        );
+        let market = PredictionMarketClient::new(&env, &address);
+        assert_eq!(market.get_market_state(&market_id).status, 0); // Open
+    }
+    assert_eq!(
+        token::Client::new(&env, &usdc.address).balance(&creator),
+        100_000_000 - 3 * fee
+    );
+}
+
+#[test]
+fn test_create_markets_rejects_whole_batch_on_invalid_entry() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    factory.set_oracle(&setup_oracle(&env, &factory.address, 2));
+    let wasm_hash = upload_market_wasm(&env);
+    factory.set_market_template(&factory.get_network_binding(), &wasm_hash);
+
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &100_000_000);
+    let mut late = market_params(&env, "BoutThree");
+    late.resolution_time = late.closing_time;
+    let series = vec![
+        &env,
+        market_params(&env, "BoutOne"),
+        market_params(&env, "BoutTwo"),
+        late,
+    ];
+
+    assert!(factory.try_create_markets(&creator, &series).is_err());
+    assert_eq!(factory.get_market_count(), 0);
+    assert_eq!(
+        token::Client::new(&env, &usdc.address).balance(&creator),
+        100_000_000
+    );
+}
+
+#[test]
+#[should_panic(expected = "too many markets")]
+fn test_create_markets_rejects_oversized_batch() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &100_000_000);
+
+    let mut series = Vec::new(&env);
+    for i in 0..=MAX_BATCH_MARKETS {
+        let mut params = market_params(&env, "Bout");
+        params.nonce = i;
+        series.push_back(params);
+    }
+
+    factory.create_markets(&creator, &series);
+}
+
+// ============================================================================
+// OPERATOR TESTS
+// ============================================================================
+
+/// Helper: register operators "alpha" (50% fee share) and "beta" (20%)
+fn register_operators(
+    env: &Env,
+    factory: &MarketFactoryClient,
+    admin: &Address,
+) -> (Address, Address) {
+    let alpha_admin = Address::generate(env);
+    let beta_admin = Address::generate(env);
+    factory.register_operator(admin, &Symbol::new(env, "alpha"), &alpha_admin, &5000);
+    factory.register_operator(admin, &Symbol::new(env, "beta"), &beta_admin, &2000);
+    (alpha_admin, beta_admin)
+}
+
+/// Helper: resolve `market` YES with `user` as sole winner of a 1000 + 500 pool and claim
+fn resolve_and_claim(
+    env: &Env,
+    market: &PredictionMarketClient,
+    usdc: &token::StellarAssetClient,
+    market_id: &BytesN<32>,
+) -> i128 {
+    let user = Address::generate(env);
+    usdc.mint(&market.address, &1500);
+    market.test_set_prediction(&user, &1u32, &1000);
+    market.test_setup_resolution(market_id, &1u32, &1000, &500);
+    let fee = market.simulate_claim(&user).protocol_fee;
+    market.claim_winnings(&user, market_id);
+    fee
+}
+
+#[test]
+fn test_operator_fees_segregated_per_operator() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    factory.set_fee_bps(&500);
+    register_operators(&env, &factory, &admin);
+    let alpha = Symbol::new(&env, "alpha");
+    let beta = Symbol::new(&env, "beta");
+
+    let (alpha_id, alpha_market) =
+        create_operator_market(&env, &factory, &usdc, "AlphaMarket", Some(alpha.clone()));
+    let (beta_id, beta_market) =
+        create_operator_market(&env, &factory, &usdc, "BetaMarket", Some(beta.clone()));
+    let (plain_id, plain_market) = create_linked_market(&env, &factory, &usdc, "PlainMarket");
+
+    assert_eq!(factory.get_market_operator(&alpha_id), Some(alpha.clone()));
+    assert_eq!(factory.get_market_operator(&plain_id), None);
+    assert_eq!(
+        factory.get_operator_markets(&alpha),
+        vec![&env, alpha_id.clone()]
+    );
+    assert_eq!(
+        factory.get_operator_markets(&beta),
+        vec![&env, beta_id.clone()]
+    );
+
+    let treasury = TreasuryClient::new(&env, &factory.get_treasury());
+    let fees_before = treasury.get_total_fees();
+
+    let alpha_fee = resolve_and_claim(&env, &alpha_market, &usdc, &alpha_id);
+    let beta_fee = resolve_and_claim(&env, &beta_market, &usdc, &beta_id);
+    let plain_fee = resolve_and_claim(&env, &plain_market, &usdc, &plain_id);
+    assert!(alpha_fee > 0);
+
+    let alpha_revenue = treasury.get_operator_revenue(&alpha);
+    assert_eq!(alpha_revenue.operator_share, alpha_fee * 5000 / 10_000);
+    assert_eq!(
+        alpha_revenue.protocol_share,
+        alpha_fee - alpha_revenue.operator_share
+    );
+    let beta_revenue = treasury.get_operator_revenue(&beta);
+    assert_eq!(beta_revenue.operator_share, beta_fee * 2000 / 10_000);
+    assert_eq!(
+        beta_revenue.protocol_share,
+        beta_fee - beta_revenue.operator_share
+    );
+
+    // Only protocol shares reach the fee pools; untagged markets book in full
+    assert_eq!(
+        treasury.get_total_fees() - fees_before,
+        alpha_revenue.protocol_share + beta_revenue.protocol_share + plain_fee
+    );
+}
+
+#[test]
+fn test_operator_withdraws_only_own_share() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    factory.set_fee_bps(&500);
+    let (alpha_admin, _beta_admin) = register_operators(&env, &factory, &admin);
+    let alpha = Symbol::new(&env, "alpha");
+    let beta = Symbol::new(&env, "beta");
+
+    let (alpha_id, alpha_market) =
+        create_operator_market(&env, &factory, &usdc, "AlphaMarket", Some(alpha.clone()));
+    let (beta_id, beta_market) =
+        create_operator_market(&env, &factory, &usdc, "BetaMarket", Some(beta.clone()));
+    resolve_and_claim(&env, &alpha_market, &usdc, &alpha_id);
+    resolve_and_claim(&env, &beta_market, &usdc, &beta_id);
+
+    let treasury = TreasuryClient::new(&env, &factory.get_treasury());
+    let paid = treasury.withdraw_operator_fees(&alpha, &alpha_admin);
+    assert_eq!(paid, treasury.get_operator_revenue(&alpha).operator_share);
+    assert_eq!(usdc.balance(&alpha_admin), paid);
+
+    let revenue = treasury.get_operator_revenue(&alpha);
+    assert_eq!(revenue.withdrawn, revenue.operator_share);
+    assert_eq!(treasury.get_operator_revenue(&beta).withdrawn, 0);
+}
+
+#[test]
+fn test_operator_pauses_own_market() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    let (alpha_admin, _beta_admin) = register_operators(&env, &factory, &admin);
+
+    let (alpha_id, alpha_market) = create_operator_market(
+        &env,
+        &factory,
+        &usdc,
+        "AlphaMarket",
+        Some(Symbol::new(&env, "alpha")),
+    );
+
+    factory.set_market_paused(&alpha_admin, &alpha_id, &true);
+    assert!(factory.is_market_paused(&alpha_id));
+
+    let user = Address::generate(&env);
+    usdc.mint(&user, &1000);
+    let result = alpha_market.try_place_prediction(&user, &1u32, &1000);
+    assert_eq!(result, Err(Ok(MarketError::MarketPaused)));
+
+    factory.set_market_paused(&alpha_admin, &alpha_id, &false);
+    alpha_market.place_prediction(&user, &1u32, &1000);
+
+    factory.update_market_metadata(
+        &alpha_admin,
+        &alpha_id,
+        &Symbol::new(&env, "Renamed"),
+        &Symbol::new(&env, "NewDesc"),
+        &Symbol::new(&env, "Boxing"),
+    );
+    let metadata = factory.get_market_metadata(&alpha_id).unwrap();
+    assert_eq!(metadata.1, Symbol::new(&env, "Renamed"));
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: not operator admin")]
+fn test_operator_cannot_pause_other_operators_market() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    let (_alpha_admin, beta_admin) = register_operators(&env, &factory, &admin);
+
+    let (alpha_id, _alpha_market) = create_operator_market(
+        &env,
+        &factory,
+        &usdc,
+        "AlphaMarket",
+        Some(Symbol::new(&env, "alpha")),
+    );
+
+    factory.set_market_paused(&beta_admin, &alpha_id, &true);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: not operator admin")]
+fn test_operator_cannot_edit_other_operators_metadata() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    let (_alpha_admin, beta_admin) = register_operators(&env, &factory, &admin);
+
+    let (alpha_id, _alpha_market) = create_operator_market(
+        &env,
+        &factory,
+        &usdc,
+        "AlphaMarket",
+        Some(Symbol::new(&env, "alpha")),
+    );
+
+    factory.update_market_metadata(
+        &beta_admin,
+        &alpha_id,
+        &Symbol::new(&env, "Hijack"),
+        &Symbol::new(&env, "Desc"),
+        &Symbol::new(&env, "Boxing"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "operator not found")]
+fn test_create_market_with_unknown_operator() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    create_operator_market(
+        &env,
+        &factory,
+        &usdc,
+        "Ghost",
+        Some(Symbol::new(&env, "ghost")),
+    );
+}
+
+#[test]
+#[should_panic(expected = "operator already registered")]
+fn test_register_operator_twice() {
+    let env = create_test_env();
+    let (factory, _usdc, admin) = setup_factory_with_treasury(&env);
+    register_operators(&env, &factory, &admin);
+    factory.register_operator(&admin, &Symbol::new(&env, "alpha"), &admin, &0);
+}
+
+// ============================================================================
+// MARKET REGISTRY TESTS
+// ============================================================================
+
+/// Helper: create `count` markets, advancing the ledger clock between them
+fn create_many_markets(
+    env: &Env,
+    factory: &MarketFactoryClient,
+    usdc: &token::StellarAssetClient,
+    count: u32,
+) -> (Address, std::vec::Vec<BytesN<32>>) {
+    let creator = Address::generate(env);
+    usdc.mint(&creator, &(count as i128 * 10_000_000));
+
+    let mut ids = std::vec::Vec::new();
+    for _ in 0..count {
+        ids.push(factory.create_market(&creator, &market_params(env, "Paged"), &None));
+        env.ledger().with_mut(|li| li.timestamp += 10);
+    }
+    (creator, ids)
+}
+
+#[test]
+fn test_list_markets_pages_in_creation_order() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    let start = env.ledger().timestamp();
+    let (creator, ids) = create_many_markets(&env, &factory, &usdc, 60);
+    assert_eq!(factory.get_market_count(), 60);
+
+    let first = factory.list_markets(&0, &MAX_LIST_MARKETS);
+    assert_eq!(first.len(), MAX_LIST_MARKETS);
+    let second = factory.list_markets(&MAX_LIST_MARKETS, &MAX_LIST_MARKETS);
+    assert_eq!(second.len(), 10);
+
+    for (i, record) in first.iter().chain(second.iter()).enumerate() {
+        assert_eq!(record.market_id, ids[i]);
+        assert_eq!(record.creator, creator);
+        assert_eq!(record.created_at, start + 10 * i as u64);
+        assert_eq!(record.category, Symbol::new(&env, "Boxing"));
+        assert_eq!(record.address, None);
+    }
+
+    // Middle page straddling the end
+    let tail = factory.list_markets(&55, &20);
+    assert_eq!(tail.len(), 5);
+    assert_eq!(tail.get(0).unwrap().market_id, ids[55]);
+
+    assert_eq!(factory.get_market(&59).market_id, ids[59]);
+}
+
+#[test]
+fn test_list_markets_caps_limit_and_handles_out_of_range() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    create_many_markets(&env, &factory, &usdc, 60);
+
+    assert_eq!(factory.list_markets(&0, &1000).len(), MAX_LIST_MARKETS);
+    assert_eq!(factory.list_markets(&0, &0).len(), 0);
+    assert_eq!(factory.list_markets(&60, &10).len(), 0);
+    assert_eq!(factory.list_markets(&u32::MAX, &u32::MAX).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "market index out of range")]
+fn test_get_market_out_of_range() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    create_many_markets(&env, &factory, &usdc, 2);
+    factory.get_market(&2);
+}
+
+#[test]
+fn test_market_record_survives_resolution() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    let (market_id, market) = create_linked_market(&env, &factory, &usdc, "Resolved");
+
+    let user = Address::generate(&env);
+    market.test_set_prediction(&user, &1u32, &1000);
+    market.test_setup_resolution(&market_id, &1u32, &1000, &0);
+
+    let record = factory.get_market(&0);
+    assert_eq!(record.market_id, market_id);
+    assert_eq!(record.address, Some(market.address.clone()));
+    assert_eq!(factory.get_market_address(&market_id), Some(market.address));
+}
+
+// ============================================================================
+// CREATOR ALLOWLIST TESTS
+// ============================================================================
+
+/// Helper: factory wired to a treasury with creation still restricted to approved creators
+fn setup_permissioned_factory(
+    env: &Env,
+) -> (
+    MarketFactoryClient<'_>,
+    token::StellarAssetClient<'_>,
+    Address,
+) {
+    let (factory, usdc, admin) = setup_factory_with_treasury(env);
+    factory.set_permissionless(&admin, &false);
+    (factory, usdc, admin)
+}
+
+#[test]
+fn test_new_factory_is_permissioned() {
+    let env = create_test_env();
+    let factory_id = register_factory(&env);
+    let client = MarketFactoryClient::new(&env, &factory_id);
+    env.mock_all_auths();
+    client.initialize(
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &Address::generate(&env),
+    );
+
+    assert!(!client.is_permissionless());
+    assert!(!client.is_approved_creator(&Address::generate(&env)));
+}
+
+#[test]
+fn test_approved_creator_can_create_market() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_permissioned_factory(&env);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &10_000_000);
+
+    factory.add_creator(&admin, &creator);
+    assert!(factory.is_approved_creator(&creator));
+
+    factory.create_market(&creator, &market_params(&env, "Vetted"), &None);
+    assert_eq!(factory.get_market_count(), 1);
+}
+
+#[test]
+#[should_panic(expected = "creator not approved")]
+fn test_unapproved_creator_rejected() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_permissioned_factory(&env);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &10_000_000);
+
+    factory.create_market(&creator, &market_params(&env, "Unvetted"), &None);
+}
+
+#[test]
+fn test_removed_creator_keeps_existing_markets() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_permissioned_factory(&env);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &20_000_000);
+
+    factory.add_creator(&admin, &creator);
+    let market_id = factory.create_market(&creator, &market_params(&env, "Before"), &None);
+    factory.remove_creator(&admin, &creator);
+
+    assert!(!factory.is_approved_creator(&creator));
+    assert_eq!(factory.get_market(&0).market_id, market_id);
+    assert!(factory.get_market_metadata(&market_id).is_some());
+
+    let result = factory.try_create_market(&creator, &market_params(&env, "After"), &None);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_permissionless_mode_allows_anyone() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_permissioned_factory(&env);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &10_000_000);
+
+    factory.set_permissionless(&admin, &true);
+    assert!(factory.is_permissionless());
+    assert!(!factory.is_approved_creator(&creator));
+
+    factory.create_market(&creator, &market_params(&env, "Open"), &None);
+    assert_eq!(factory.get_market_count(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_add_creator_requires_admin() {
+    let env = create_test_env();
+    let (factory, _usdc, _admin) = setup_permissioned_factory(&env);
+    let outsider = Address::generate(&env);
+    factory.add_creator(&outsider, &outsider);
+}
+
+// ============================================================================
+// CREATION FEE TESTS
+// ============================================================================
+
+#[test]
+fn test_creation_fee_paid_into_treasury() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    factory.set_creation_fee(&admin, &usdc.address, &25_000_000);
+    assert_eq!(factory.get_creation_fee().amount, 25_000_000);
+
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &60_000_000);
+    let token = token::Client::new(&env, &usdc.address);
+    let treasury = factory.get_treasury();
+
+    factory.create_market(&creator, &market_params(&env, "FeeOne"), &None);
+    factory.create_market(&creator, &market_params(&env, "FeeTwo"), &None);
+
+    assert_eq!(token.balance(&treasury), 50_000_000);
+    assert_eq!(token.balance(&creator), 10_000_000);
+    assert_eq!(factory.get_fees_paid(&creator), 50_000_000);
+    assert_eq!(
+        TreasuryClient::new(&env, &treasury).get_total_fees(),
+        50_000_000
+    );
+}
+
+#[test]
+fn test_creation_fee_in_other_token() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    let fee_token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    factory.set_creation_fee(&admin, &fee_token, &500);
+
+    let creator = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &fee_token).mint(&creator, &500);
+    factory.create_market(&creator, &market_params(&env, "OtherToken"), &None);
+
+    let treasury = factory.get_treasury();
+    assert_eq!(token::Client::new(&env, &fee_token).balance(&treasury), 500);
+    assert_eq!(
+        token::Client::new(&env, &usdc.address).balance(&treasury),
+        0
+    );
+    assert_eq!(factory.get_fees_paid(&creator), 500);
+}
+
+#[test]
+fn test_creation_fails_with_insufficient_balance() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    factory.set_creation_fee(&admin, &usdc.address, &25_000_000);
+
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &24_999_999);
+
+    let result = factory.try_create_market(&creator, &market_params(&env, "Broke"), &None);
+    assert!(result.is_err());
+    assert_eq!(factory.get_market_count(), 0);
+    assert_eq!(factory.get_fees_paid(&creator), 0);
+}
+
+#[test]
+fn test_zero_creation_fee_skips_transfer() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    factory.set_creation_fee(&admin, &usdc.address, &0);
+
+    // Creator holds no funds at all
+    let creator = Address::generate(&env);
+    factory.create_market(&creator, &market_params(&env, "Free"), &None);
+
+    assert_eq!(factory.get_market_count(), 1);
+    assert_eq!(factory.get_fees_paid(&creator), 0);
+    assert_eq!(
+        token::Client::new(&env, &usdc.address).balance(&factory.get_treasury()),
+        0
+    );
+}
+
+// ============================================================================
+// ORACLE AUTO-REGISTRATION TESTS
+// ============================================================================
+
+#[test]
+fn test_create_market_registers_with_oracle() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    let oracle_id = setup_oracle(&env, &factory.address, 2);
+    factory.set_oracle(&oracle_id);
+
+    let (market_id, _market) = create_linked_market(&env, &factory, &usdc, "AutoReg");
+
+    // The oracle knows the market: a second registration by the factory is rejected
+    let oracle = OracleManagerClient::new(&env, &oracle_id);
+    let result = oracle.try_register_market_as(
+        &factory.address,
+        &market_id,
+        &0,
+        &Symbol::new(&env, CATEGORY_ANY),
+    );
+    assert!(result.is_err());
+    assert_eq!(
+        oracle.get_market_registrar(&market_id),
+        Some(factory.address.clone())
+    );
+}
+
+#[test]
+#[should_panic(expected = "Not an authorized registrar")]
+fn test_create_market_requires_factory_registrar() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    let oracle_id = setup_oracle(&env, &factory.address, 2);
+    factory.set_oracle(&oracle_id);
+
+    // Registrar approval revoked before creation
+    let oracle = OracleManagerClient::new(&env, &oracle_id);
+    let signers = oracle.get_admin_signers();
+    oracle.remove_registrar(&signers.get(0).unwrap(), &factory.address);
+
+    create_linked_market(&env, &factory, &usdc, "NoRegistrar");
+}
+
+// ============================================================================
+// DEFAULT PARAMS TESTS
+// ============================================================================
+
+/// Helper: factory deploying from the market template with custom defaults
+fn setup_factory_with_defaults(
+    env: &Env,
+) -> (
+    MarketFactoryClient<'_>,
+    token::StellarAssetClient<'_>,
+    Address,
+) {
+    let (factory, usdc, admin) = setup_factory_with_treasury(env);
+    factory.set_oracle(&setup_oracle(env, &factory.address, 2));
+    let wasm_hash = upload_market_wasm(env);
+    factory.set_market_template(&factory.get_network_binding(), &wasm_hash);
+    factory.set_default_params(
+        &admin,
+        &DefaultParams {
+            fee_bps: 200,
+            min_bet: 100,
+            betting_cutoff_offset: 7200,
+            dispute_window: 3 * 86400,
+            token: usdc.address.clone(),
+        },
+    );
+    (factory, usdc, admin)
+}
+
+/// Helper: params with the cutoff derived from the defaults
+fn derived_params(env: &Env, title: &str) -> MarketParams {
+    MarketParams {
+        closing_time: 0,
+        ..market_params(env, title)
+    }
+}
+
+fn no_overrides() -> MarketOverrides {
+    MarketOverrides {
+        fee_bps: None,
+        min_bet: None,
+        betting_cutoff_offset: None,
+        dispute_window: None,
+        token: None,
+    }
+}
+
+#[test]
+fn test_create_market_with_default_params() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_defaults(&env);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &10_000_000);
+
+    let params = derived_params(&env, "Defaults");
+    let market_id = factory.create_market(&creator, &params, &None);
+
+    let market =
+        PredictionMarketClient::new(&env, &factory.get_market_address(&market_id).unwrap());
+    assert_eq!(market.get_fee_bps(), 200);
+    assert_eq!(market.get_min_bet(), 100);
+    assert_eq!(market.get_betting_cutoff(), params.resolution_time - 7200);
+    assert_eq!(market.get_dispute_window(), 3 * 86400);
+
+    let config = factory.get_market_config(&market_id).unwrap();
+    assert_eq!(config.closing_time, params.resolution_time - 7200);
+    assert_eq!(config.token, usdc.address);
+}
+
+#[test]
+fn test_create_market_with_partial_overrides() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_defaults(&env);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &10_000_000);
+
+    let overrides = MarketOverrides {
+        fee_bps: Some(50),
+        betting_cutoff_offset: Some(600),
+        ..no_overrides()
+    };
+    let params = derived_params(&env, "Partial");
+    let market_id = factory.create_market(&creator, &params, &Some(overrides));
+
+    let market =
+        PredictionMarketClient::new(&env, &factory.get_market_address(&market_id).unwrap());
+    assert_eq!(market.get_fee_bps(), 50);
+    assert_eq!(market.get_betting_cutoff(), params.resolution_time - 600);
+    // Unset fields fall back to the defaults
+    assert_eq!(market.get_min_bet(), 100);
+    assert_eq!(market.get_dispute_window(), 3 * 86400);
+}
+
+#[test]
+fn test_changing_defaults_only_affects_new_markets() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_defaults(&env);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &20_000_000);
+
+    let first = factory.create_market(&creator, &derived_params(&env, "First"), &None);
+    let mut defaults = factory.get_default_params();
+    defaults.fee_bps = 400;
+    defaults.min_bet = 0;
+    factory.set_default_params(&admin, &defaults);
+    let second = factory.create_market(&creator, &derived_params(&env, "Second"), &None);
+
+    let first_market =
+        PredictionMarketClient::new(&env, &factory.get_market_address(&first).unwrap());
+    let second_market =
+        PredictionMarketClient::new(&env, &factory.get_market_address(&second).unwrap());
+    assert_eq!(first_market.get_fee_bps(), 200);
+    assert_eq!(first_market.get_min_bet(), 100);
+    assert_eq!(second_market.get_fee_bps(), 400);
+    assert_eq!(second_market.get_min_bet(), 0);
+    assert_eq!(factory.get_market_config(&first).unwrap().fee_bps, 200);
+}
+
+#[test]
+#[should_panic(expected = "fee exceeds maximum")]
+fn test_create_market_rejects_invalid_fee_override() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_defaults(&env);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &10_000_000);
+
+    let overrides = MarketOverrides {
+        fee_bps: Some(MAX_DEFAULT_FEE_BPS + 1),
+        ..no_overrides()
+    };
+    factory.create_market(&creator, &derived_params(&env, "BadFee"), &Some(overrides));
+}
+
+#[test]
+fn test_create_market_rejects_cutoff_override_in_past() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_defaults(&env);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &10_000_000);
+
+    // Resolution is ~1 day + 1 hour away; a 2-day offset puts the cutoff in the past
+    let overrides = MarketOverrides {
+        betting_cutoff_offset: Some(2 * 86400),
+        ..no_overrides()
+    };
+    let result = factory.try_create_market(
+        &creator,
+        &derived_params(&env, "BadCutoff"),
+        &Some(overrides),
+    );
+    assert!(result.is_err());
+    assert_eq!(factory.get_market_count(), 0);
+}
+
+// ============================================================================
+// MARKET WASM VERSION TESTS
+// ============================================================================
+
+/// Helper: factory with an oracle and market wasm v1 installed
+fn setup_versioned_factory(
+    env: &Env,
+) -> (
+    MarketFactoryClient<'_>,
+    token::StellarAssetClient<'_>,
+    Address,
+    BytesN<32>,
+) {
+    let (factory, usdc, admin) = setup_factory_with_treasury(env);
+    factory.set_oracle(&setup_oracle(env, &factory.address, 2));
+    let wasm_hash = upload_market_wasm(env);
+    factory.set_market_wasm_hash(&admin, &factory.get_network_binding(), &wasm_hash, &1);
+    (factory, usdc, admin, wasm_hash)
+}
+
+#[test]
+fn test_markets_record_wasm_version_they_were_deployed_with() {
+    let env = create_test_env();
+    let (factory, usdc, admin, wasm_hash) = setup_versioned_factory(&env);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &20_000_000);
+
+    let v1_market = factory.create_market(&creator, &market_params(&env, "OnV1"), &None);
+    factory.set_market_wasm_hash(&admin, &factory.get_network_binding(), &wasm_hash, &2);
+    let v2_market = factory.create_market(&creator, &market_params(&env, "OnV2"), &None);
+
+    assert_eq!(factory.get_market_wasm_version(), 2);
+    assert_eq!(factory.get_market_version(&v1_market), Some(1));
+    assert_eq!(factory.get_market_version(&v2_market), Some(2));
+    assert_eq!(factory.get_market(&0).wasm_version, 1);
+    assert_eq!(factory.get_market(&1).wasm_version, 2);
+}
+
+#[test]
+#[should_panic(expected = "version must increase")]
+fn test_set_market_wasm_hash_rejects_non_increasing_version() {
+    let env = create_test_env();
+    let (factory, _usdc, admin, wasm_hash) = setup_versioned_factory(&env);
+
+    factory.set_market_wasm_hash(&admin, &factory.get_network_binding(), &wasm_hash, &1);
+}
+
+#[test]
+fn test_set_market_template_bumps_wasm_version() {
+    let env = create_test_env();
+    let (factory, _usdc, _admin, wasm_hash) = setup_versioned_factory(&env);
+
+    factory.set_market_template(&factory.get_network_binding(), &wasm_hash);
+    assert_eq!(factory.get_market_wasm_version(), 2);
+}
+
+#[test]
+fn test_upgrade_market_moves_market_to_latest_version() {
+    let env = create_test_env();
+    let (factory, usdc, admin, wasm_hash) = setup_versioned_factory(&env);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &10_000_000);
+
+    let market_id = factory.create_market(&creator, &market_params(&env, "Upgrade"), &None);
+    factory.set_market_wasm_hash(&admin, &factory.get_network_binding(), &wasm_hash, &3);
+    factory.upgrade_market(&admin, &factory.get_network_binding(), &market_id);
+
+    assert_eq!(factory.get_market_version(&market_id), Some(3));
+    // Storage survives the code swap
+    let market =
+        PredictionMarketClient::new(&env, &factory.get_market_address(&market_id).unwrap());
+    assert_eq!(market.get_market_state(&market_id).status, 0);
+
+    let result = factory.try_upgrade_market(&admin, &factory.get_network_binding(), &market_id);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_set_market_wasm_hash_rejects_wrong_network() {
+    let env = create_test_env();
+    let (factory, _usdc, admin, wasm_hash) = setup_versioned_factory(&env);
+
+    let result = factory.try_set_market_wasm_hash(
+        &admin,
+        &wrong_network_hash(&env, &factory),
+        &wasm_hash,
+        &2,
+    );
+    assert_eq!(result, Err(Ok(FactoryError::NetworkMismatch)));
+    assert_eq!(factory.get_market_wasm_version(), 1);
+}
+
+#[test]
+fn test_upgrade_market_rejects_wrong_network() {
+    let env = create_test_env();
+    let (factory, usdc, admin, wasm_hash) = setup_versioned_factory(&env);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &10_000_000);
+    let market_id = factory.create_market(&creator, &market_params(&env, "Upgrade"), &None);
+    factory.set_market_wasm_hash(&admin, &factory.get_network_binding(), &wasm_hash, &2);
+
+    let result =
+        factory.try_upgrade_market(&admin, &wrong_network_hash(&env, &factory), &market_id);
+    assert_eq!(result, Err(Ok(FactoryError::NetworkMismatch)));
+    assert_eq!(factory.get_market_version(&market_id), Some(1));
+}
+
+// ============================================================================
+// CREATOR / CATEGORY INDEX TESTS
+// ============================================================================
+
+/// Helper: create a market in `category` and return its id
+fn create_in_category(
+    env: &Env,
+    factory: &MarketFactoryClient,
+    creator: &Address,
+    title: &str,
+    category: &str,
+) -> BytesN<32> {
+    let params = MarketParams {
+        category: Symbol::new(env, category),
+        ..market_params(env, title)
+    };
+    factory.create_market(creator, &params, &None)
+}
+
+#[test]
+fn test_creator_markets_across_categories() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    usdc.mint(&alice, &30_000_000);
+    usdc.mint(&bob, &10_000_000);
+
+    let a1 = create_in_category(&env, &factory, &alice, "A1", "Boxing");
+    let a2 = create_in_category(&env, &factory, &alice, "A2", "MMA");
+    let a3 = create_in_category(&env, &factory, &alice, "A3", "Boxing");
+    let b1 = create_in_category(&env, &factory, &bob, "B1", "Boxing");
+
+    let by_alice = factory.list_markets_by_creator(&alice, &0, &10);
+    assert_eq!(by_alice.len(), 3);
+    assert_eq!(by_alice.get(0).unwrap().market_id, a1);
+    assert_eq!(by_alice.get(1).unwrap().market_id, a2);
+    assert_eq!(by_alice.get(1).unwrap().category, Symbol::new(&env, "MMA"));
+    assert_eq!(by_alice.get(2).unwrap().market_id, a3);
+
+    let boxing = factory.list_markets_by_category(&Symbol::new(&env, "Boxing"), &0, &10);
+    assert_eq!(boxing.len(), 3);
+    assert_eq!(boxing.get(2).unwrap().market_id, b1);
+    assert_eq!(boxing.get(2).unwrap().creator, bob);
+
+    let mma = factory.list_markets_by_category(&Symbol::new(&env, "MMA"), &0, &10);
+    assert_eq!(mma.len(), 1);
+    assert_eq!(mma.get(0).unwrap().market_id, a2);
+}
+
+#[test]
+fn test_index_listing_is_paginated() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    let (creator, ids) = create_many_markets(&env, &factory, &usdc, 60);
+
+    let first = factory.list_markets_by_creator(&creator, &0, &1000);
+    assert_eq!(first.len(), MAX_LIST_MARKETS);
+    let rest = factory.list_markets_by_creator(&creator, &MAX_LIST_MARKETS, &1000);
+    assert_eq!(rest.len(), 10);
+    assert_eq!(rest.get(9).unwrap().market_id, ids[59]);
+
+    let boxing = Symbol::new(&env, "Boxing");
+    assert_eq!(factory.list_markets_by_category(&boxing, &55, &3).len(), 3);
+    assert_eq!(factory.list_markets_by_category(&boxing, &60, &3).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "category not allowed")]
+fn test_create_market_rejects_unknown_category() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &10_000_000);
+
+    create_in_category(&env, &factory, &creator, "Chess", "Chess");
+}
+
+#[test]
+fn test_admin_manages_category_set() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &20_000_000);
+    let kickboxing = Symbol::new(&env, "Kickboxing");
+
+    factory.add_category(&admin, &kickboxing);
+    assert!(factory.is_category(&kickboxing));
+    let market_id = create_in_category(&env, &factory, &creator, "Kick", "Kickboxing");
+
+    // Removing a category blocks new markets but keeps existing ones listed
+    factory.remove_category(&admin, &kickboxing);
+    assert!(!factory.is_category(&kickboxing));
+    let listed = factory.list_markets_by_category(&kickboxing, &0, &10);
+    assert_eq!(listed.get(0).unwrap().market_id, market_id);
+
+    let params = MarketParams {
+        category: kickboxing.clone(),
+        ..market_params(&env, "KickTwo")
+    };
+    assert!(factory.try_create_market(&creator, &params, &None).is_err());
+}
+
+#[test]
+fn test_metadata_update_moves_market_between_categories() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    let operator_admin = Address::generate(&env);
+    let operator_id = Symbol::new(&env, "brand");
+    factory.register_operator(&admin, &operator_id, &operator_admin, &1000);
+    let (market_id, _market) =
+        create_operator_market(&env, &factory, &usdc, "Recat", Some(operator_id));
+
+    factory.update_market_metadata(
+        &operator_admin,
+        &market_id,
+        &Symbol::new(&env, "Recat"),
+        &Symbol::new(&env, "Desc"),
+        &Symbol::new(&env, "MMA"),
+    );
+
+    let boxing = factory.list_markets_by_category(&Symbol::new(&env, "Boxing"), &0, &10);
+    let mma = factory.list_markets_by_category(&Symbol::new(&env, "MMA"), &0, &10);
+    assert_eq!(boxing.len(), 0);
+    assert_eq!(mma.get(0).unwrap().market_id, market_id);
+}
+
+// ============================================================================
+// DURATION FEE SCHEDULE TESTS
+// ============================================================================
+
+fn tier(creation_fee: i128, bond: i128, fee_bps: u32) -> DurationTier {
+    DurationTier {
+        creation_fee,
+        bond,
+        fee_bps,
+    }
+}
+
+fn sample_schedule() -> FeeSchedule {
+    FeeSchedule {
+        up_to_day: tier(1_000_000, 0, 100),
+        up_to_week: tier(2_000_000, 5_000_000, 200),
+        up_to_month: tier(3_000_000, 10_000_000, 300),
+        longer: tier(4_000_000, 20_000_000, 400),
+    }
+}
+
+/// Helper: propose the sample schedule and apply it once the delay has passed
+fn apply_sample_schedule(env: &Env, factory: &MarketFactoryClient, admin: &Address) {
+    factory.propose_fee_schedule(admin, &sample_schedule());
+    env.ledger()
+        .with_mut(|li| li.timestamp += PARAM_CHANGE_DELAY);
+    factory.apply_fee_schedule();
+}
+
+/// Helper: params resolving `duration` seconds from now, closing an hour earlier
+fn params_resolving_in(env: &Env, title: &str, duration: u64) -> MarketParams {
+    let resolution_time = env.ledger().timestamp() + duration;
+    MarketParams {
+        closing_time: resolution_time - 3600,
+        resolution_time,
+        ..market_params(env, title)
+    }
+}
+
+#[test]
+fn test_fee_schedule_applies_per_duration_bucket() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    apply_sample_schedule(&env, &factory, &admin);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &100_000_000);
+    let balance = token::TokenClient::new(&env, &usdc.address);
+
+    let cases = [
+        ("Day", 12 * 3600, sample_schedule().up_to_day),
+        ("Week", 3 * DURATION_DAY, sample_schedule().up_to_week),
+        ("Month", 20 * DURATION_DAY, sample_schedule().up_to_month),
+        ("Longer", 90 * DURATION_DAY, sample_schedule().longer),
+    ];
+    for (title, duration, expected) in cases {
+        let before = balance.balance(&creator);
+        let market_id =
+            factory.create_market(&creator, &params_resolving_in(&env, title, duration), &None);
+
+        let config = factory.get_market_config(&market_id).unwrap();
+        assert_eq!(config.fee_bps, expected.fee_bps);
+        assert_eq!(config.creation_fee, expected.creation_fee);
+        assert_eq!(config.bond, expected.bond);
+        assert_eq!(
+            before - balance.balance(&creator),
+            expected.creation_fee + expected.bond
+        );
+    }
+    assert_eq!(
+        balance.balance(&factory.address),
+        5_000_000 + 10_000_000 + 20_000_000
+    );
+}
+
+#[test]
+fn test_fee_schedule_bucket_edge_is_inclusive() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    apply_sample_schedule(&env, &factory, &admin);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &100_000_000);
+
+    let on_edge = factory.create_market(
+        &creator,
+        &params_resolving_in(&env, "OnEdge", DURATION_DAY),
+        &None,
+    );
+    let past_edge = factory.create_market(
+        &creator,
+        &params_resolving_in(&env, "PastEdge", DURATION_DAY + 1),
+        &None,
+    );
+    let week_edge = factory.create_market(
+        &creator,
+        &params_resolving_in(&env, "WeekEdge", DURATION_WEEK),
+        &None,
+    );
+    let month_edge = factory.create_market(
+        &creator,
+        &params_resolving_in(&env, "MonthEdge", DURATION_MONTH),
+        &None,
+    );
+
+    assert_eq!(factory.get_market_config(&on_edge).unwrap().fee_bps, 100);
+    assert_eq!(factory.get_market_config(&past_edge).unwrap().fee_bps, 200);
+    assert_eq!(factory.get_market_config(&week_edge).unwrap().fee_bps, 200);
+    assert_eq!(factory.get_market_config(&month_edge).unwrap().fee_bps, 300);
+}
+
+#[test]
+fn test_fee_schedule_waits_in_queue() {
+    let env = create_test_env();
+    let (factory, _usdc, admin) = setup_factory_with_treasury(&env);
+
+    factory.propose_fee_schedule(&admin, &sample_schedule());
+    assert_eq!(factory.get_fee_schedule(), None);
+    let pending = factory.get_pending_fee_schedule().unwrap();
+    assert_eq!(pending.eta, env.ledger().timestamp() + PARAM_CHANGE_DELAY);
+
+    env.ledger().set_timestamp(pending.eta - 1);
+    assert!(factory.try_apply_fee_schedule().is_err());
+
+    env.ledger().set_timestamp(pending.eta);
+    factory.apply_fee_schedule();
+    assert_eq!(factory.get_fee_schedule(), Some(sample_schedule()));
+    assert_eq!(factory.get_pending_fee_schedule(), None);
+}
+
+#[test]
+#[should_panic(expected = "fee exceeds maximum")]
+fn test_fee_schedule_rejects_fee_above_cap() {
+    let env = create_test_env();
+    let (factory, _usdc, admin) = setup_factory_with_treasury(&env);
+
+    let schedule = FeeSchedule {
+        longer: tier(0, 0, MAX_DEFAULT_FEE_BPS + 1),
+        ..sample_schedule()
+    };
+    factory.propose_fee_schedule(&admin, &schedule);
+}
+
+#[test]
+fn test_creator_bond_released_after_market_settles() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    apply_sample_schedule(&env, &factory, &admin);
+    let (market_id, market) = create_linked_market(&env, &factory, &usdc, "Bonded");
+    let creator = factory.get_market_metadata(&market_id).unwrap().0;
+    let bond = factory.get_market_config(&market_id).unwrap().bond;
+    assert_eq!(bond, 5_000_000);
+
+    // Still open: the bond stays with the factory
+    assert!(factory.try_release_creator_bond(&market_id).is_err());
+
+    market.cancel_market(&creator, &market_id);
+    let balance = token::TokenClient::new(&env, &usdc.address);
+    let before = balance.balance(&creator);
+    assert_eq!(factory.release_creator_bond(&market_id), bond);
+    assert_eq!(balance.balance(&creator) - before, bond);
+    assert!(factory.try_release_creator_bond(&market_id).is_err());
+}
+
+// ============================================================================
+// CREATION PAUSE TESTS
+// ============================================================================
+
+#[test]
+fn test_paused_creation_blocks_new_markets_only() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    let (market_id, market) = create_linked_market(&env, &factory, &usdc, "Before");
+
+    factory.pause_creation(&admin);
+    assert!(factory.is_creation_paused());
+
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &10_000_000);
+    let result = factory.try_create_market(&creator, &market_params(&env, "During"), &None);
+    assert!(result.is_err());
+
+    // Lookups and existing markets are unaffected
+    assert_eq!(factory.get_market_count(), 1);
+    assert_eq!(factory.list_markets(&0, &10).len(), 1);
+    assert_eq!(
+        factory.get_market_address(&market_id),
+        Some(market.address.clone())
+    );
+    market.place_prediction(&creator, &1u32, &1_000_000);
+}
+
+#[test]
+fn test_resumed_creation_accepts_new_markets() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &10_000_000);
+
+    factory.pause_creation(&admin);
+    factory.resume_creation(&admin);
+
+    assert!(!factory.is_creation_paused());
+    factory.create_market(&creator, &market_params(&env, "After"), &None);
+    assert_eq!(factory.get_market_count(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_non_admin_cannot_pause_creation() {
+    let env = create_test_env();
+    let (factory, _usdc, _admin) = setup_factory_with_treasury(&env);
+
+    factory.pause_creation(&Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "market already exists")]
+fn test_duplicate_market_rejected() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &20_000_000);
+
+    let params = market_params(&env, "Rematch");
+    factory.create_market(&creator, &params, &None);
+
+    // Same question from a different creator, later on, still collides
+    env.ledger().with_mut(|li| li.timestamp += 10);
+    let other = Address::generate(&env);
+    usdc.mint(&other, &10_000_000);
+    factory.create_market(&other, &params, &None);
+}
+
+#[test]
+fn test_nonce_bump_allows_relisting() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &20_000_000);
+
+    let params = market_params(&env, "Rematch");
+    let first = factory.create_market(&creator, &params, &None);
+
+    let relisted = MarketParams { nonce: 1, ..params };
+    let second = factory.create_market(&creator, &relisted, &None);
+
+    assert_ne!(first, second);
+    assert_eq!(factory.get_market_count(), 2);
+}
+
+#[test]
+fn test_compute_market_id_matches_created_id() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &10_000_000);
+
+    let params = market_params(&env, "Predicted");
+    let expected = factory.compute_market_id(&params);
+    assert!(factory.get_market_metadata(&expected).is_none());
+
+    let market_id = factory.create_market(&creator, &params, &None);
+    assert_eq!(market_id, expected);
+    assert!(factory.get_market_metadata(&expected).is_some());
+
+    // Only the question fields feed the id, not who creates it or when
+    let mut other = params.clone();
+    other.category = Symbol::new(&env, "MMA");
+    assert_ne!(factory.compute_market_id(&other), expected);
+}
+
+#[test]
+fn test_pools_health_batch_uses_category_reference_depth() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    let amm = AMMClient::new(&env, &env.register(AMM, ()));
+    amm.initialize(
+        &admin,
+        &factory.address,
+        &usdc.address,
+        &1_000_000_000_000u128,
+    );
+    factory.set_amm(&admin, &amm.address);
+
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &20_000_000);
+    let boxing = factory.create_market(&creator, &market_params(&env, "Deep"), &None);
+    let mut mma_params = market_params(&env, "Thin");
+    mma_params.category = Symbol::new(&env, "MMA");
+    let mma = factory.create_market(&creator, &mma_params, &None);
+
+    // Same 100 USDC pools; Boxing expects 100 USDC of depth, MMA 10,000 USDC
+    factory.set_reference_depth(&admin, &Symbol::new(&env, "Boxing"), &1_000_000_000u128);
+    factory.set_reference_depth(&admin, &Symbol::new(&env, "MMA"), &100_000_000_000u128);
+    let lp = Address::generate(&env);
+    usdc.mint(&lp, &2_000_000_000);
+    amm.create_pool(&lp, &boxing, &1_000_000_000u128);
+    amm.create_pool(&lp, &mma, &1_000_000_000u128);
+
+    let mut ids = Vec::new(&env);
+    ids.push_back(boxing);
+    ids.push_back(mma);
+    ids.push_back(BytesN::from_array(&env, &[0u8; 32]));
+    let rows = factory.get_pools_health(&ids);
+
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows.get(0).unwrap().depth_score, 100);
+    assert_eq!(rows.get(1).unwrap().depth_score, 1);
+    assert!(rows.get(0).unwrap().score > rows.get(1).unwrap().score);
+    assert!(rows.get(2).unwrap().frozen);
+
+    // The AMM's own getter reads the same depth from the factory
+    let boxing_health = amm.get_health(&ids.get(0).unwrap());
+    assert_eq!(boxing_health.score, rows.get(0).unwrap().score);
+    assert_eq!(boxing_health.depth_score, 100);
+}
+
+#[test]
+fn test_apply_resting_floor_pushes_category_rule_to_pool() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    let amm = AMMClient::new(&env, &env.register(AMM, ()));
+    amm.initialize(
+        &admin,
+        &factory.address,
+        &usdc.address,
+        &1_000_000_000_000u128,
+    );
+    factory.set_amm(&admin, &amm.address);
+
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &1_010_000_000);
+    let market_id = factory.create_market(&creator, &market_params(&env, "Floored"), &None);
+    amm.create_pool(&creator, &market_id, &1_000_000_000u128);
+
+    factory.set_resting_floor(&admin, &Symbol::new(&env, "Boxing"), &2500);
+    assert_eq!(
+        factory.get_resting_floor(&Symbol::new(&env, "Boxing")),
+        2500
+    );
+    assert_eq!(factory.get_resting_floor(&Symbol::new(&env, "MMA")), 0);
+
+    factory.apply_resting_floor(&market_id);
+    assert_eq!(amm.get_resting_floor(&market_id), 2500);
+}
+
+#[test]
+#[should_panic(expected = "too many markets")]
+fn test_pools_health_batch_is_bounded() {
+    let env = create_test_env();
+    let (factory, _usdc, admin) = setup_factory_with_treasury(&env);
+    factory.set_amm(&admin, &Address::generate(&env));
+
+    let mut ids = Vec::new(&env);
+    for i in 0..=MAX_HEALTH_BATCH {
+        ids.push_back(BytesN::from_array(&env, &[i as u8; 32]));
+    }
+    factory.get_pools_health(&ids);
+}
+
+#[test]
+fn test_jurisdiction_rule_precedence() {
+    let env = create_test_env();
+    let (factory, _usdc, admin) = setup_factory_with_treasury(&env);
+    let any = Symbol::new(&env, JURISDICTION_ANY);
+    let boxing = Symbol::new(&env, "Boxing");
+    let mma = Symbol::new(&env, "MMA");
+    let (us, uk, fr) = (
+        Symbol::new(&env, "us"),
+        Symbol::new(&env, "uk"),
+        Symbol::new(&env, "fr"),
+    );
+    assert!(factory.is_market_allowed(&us, &boxing));
+
+    // A specific deny beats a general allow
+    factory.set_jurisdiction_rule(&admin, &any, &boxing, &true);
+    factory.set_jurisdiction_rule(&admin, &us, &boxing, &false);
+    assert!(!factory.is_market_allowed(&us, &boxing));
+    assert!(factory.is_market_allowed(&uk, &boxing));
+
+    // A region rule beats a category-wide one, and a global deny covers the rest
+    factory.set_jurisdiction_rule(&admin, &any, &any, &false);
+    factory.set_jurisdiction_rule(&admin, &uk, &any, &true);
+    assert!(factory.is_market_allowed(&uk, &mma));
+    assert!(!factory.is_market_allowed(&fr, &mma));
+    assert!(factory.is_market_allowed(&fr, &boxing));
+}
+
+#[test]
+#[should_panic(expected = "jurisdiction not allowed")]
+fn test_create_market_rejected_in_denied_jurisdiction() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &10_000_000);
+    factory.set_jurisdiction_rule(
+        &admin,
+        &Symbol::new(&env, "us"),
+        &Symbol::new(&env, "Boxing"),
+        &false,
+    );
+
+    let params = MarketParams {
+        region: Symbol::new(&env, "us"),
+        ..market_params(&env, "Blocked")
+    };
+    factory.create_market(&creator, &params, &None);
+}
+
+#[test]
+fn test_jurisdiction_deny_logs_live_markets() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &20_000_000);
+    let us = Symbol::new(&env, "us");
+    let boxing = Symbol::new(&env, "Boxing");
+
+    let us_market = factory.create_market(
+        &creator,
+        &MarketParams {
+            region: us.clone(),
+            ..market_params(&env, "UsFight")
+        },
+        &None,
+    );
+    factory.create_market(&creator, &market_params(&env, "GlobalFight"), &None);
+    assert_eq!(factory.get_market_region(&us_market), Some(us.clone()));
+
+    factory.set_jurisdiction_rule(&admin, &us, &boxing, &false);
+    assert_eq!(factory.get_incident_count(), 1);
+    let incident = factory.list_incidents(&0, &10).get(0).unwrap();
+    assert_eq!(incident.market_id, us_market);
+    assert_eq!(incident.kind, Symbol::new(&env, "jurisdiction_blocked"));
+
+    // Already disallowed: a broader deny logs nothing new
+    factory.set_jurisdiction_rule(&admin, &us, &Symbol::new(&env, JURISDICTION_ANY), &false);
+    assert_eq!(factory.get_incident_count(), 1);
+
+    // Markets past resolution_time are no longer live
+    env.ledger().with_mut(|li| li.timestamp += 2 * 86400);
+    factory.set_jurisdiction_rule(&admin, &Symbol::new(&env, "global"), &boxing, &false);
+    assert_eq!(factory.get_incident_count(), 1);
+    assert!(factory.get_market_metadata(&us_market).is_some());
+}
+
+// ============================================================================
+// MARKET OVERVIEW TESTS
+// ============================================================================
+
+#[test]
+fn test_market_overview_joins_registry_market_and_oracle() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    factory.set_oracle(&setup_oracle(&env, &factory.address, 2));
+    let (market_id, market) = create_linked_market(&env, &factory, &usdc, "Overview");
+
+    let overviews = factory.get_market_overviews(&vec![&env, market_id.clone()]);
+    assert_eq!(overviews.len(), 1);
+    let overview = overviews.get(0).unwrap();
+    assert_eq!(overview.errors, 0);
+    assert_eq!(overview.address, Some(market.address.clone()));
+    assert_eq!(overview.category, Some(Symbol::new(&env, "Boxing")));
+
+    let state = market.get_market_state(&market_id);
+    assert_eq!(overview.status, state.status);
+    assert_eq!(overview.closing_time, state.closing_time);
+    assert_eq!(overview.total_pool, state.total_pool);
+
+    assert!(overview.oracle_tracked);
+    assert_eq!(overview.tally, (0, 0));
+    assert!(!overview.finalized);
+}
+
+#[test]
+fn test_market_overview_flags_failed_market_read() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    let (healthy_id, _) = create_linked_market(&env, &factory, &usdc, "Healthy");
+    let (broken_id, _) = create_linked_market(&env, &factory, &usdc, "Broken");
+    // Relink to a contract that has no get_market_state
+    factory.set_market_address(&broken_id, &env.register(OracleManager, ()));
+
+    let overviews = factory.get_market_overviews(&vec![&env, broken_id, healthy_id]);
+    let broken = overviews.get(0).unwrap();
+    assert_eq!(broken.errors, OVERVIEW_MARKET_READ_FAILED);
+    assert!(broken.creator.is_some());
+    assert_eq!(broken.closing_time, 0);
+    // No oracle configured: nothing to join, nothing flagged
+    assert!(!broken.oracle_tracked);
+
+    let healthy = overviews.get(1).unwrap();
+    assert_eq!(healthy.errors, 0);
+    assert!(healthy.closing_time > 0);
+}
+
+#[test]
+fn test_market_overviews_preserve_request_order() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    let (first, _) = create_linked_market(&env, &factory, &usdc, "First");
+    let (second, _) = create_linked_market(&env, &factory, &usdc, "Second");
+    let unknown = BytesN::from_array(&env, &[9u8; 32]);
+
+    let ids = vec![&env, second.clone(), unknown.clone(), first.clone()];
+    let overviews = factory.get_market_overviews(&ids);
+    assert_eq!(overviews.len(), 3);
+    for (i, id) in ids.iter().enumerate() {
+        assert_eq!(overviews.get(i as u32).unwrap().market_id, id);
+    }
+
+    let missing = overviews.get(1).unwrap();
+    assert_eq!(missing.errors, OVERVIEW_UNKNOWN_MARKET);
+    assert!(missing.creator.is_none() && missing.address.is_none());
+}
+
+#[test]
+#[should_panic(expected = "too many markets")]
+fn test_market_overviews_reject_oversized_batch() {
+    let env = create_test_env();
+    let (factory, _, _) = setup_factory_with_treasury(&env);
+
+    let mut ids = Vec::new(&env);
+    for i in 0..=MAX_OVERVIEW_BATCH {
+        ids.push_back(BytesN::from_array(&env, &[i as u8; 32]));
+    }
+    factory.get_market_overviews(&ids);
+}
+
+#[test]
+fn test_full_overview_batch_fits_budget() {
+    let env = create_test_env();
+    let (factory, usdc, _admin) = setup_factory_with_treasury(&env);
+    factory.set_oracle(&setup_oracle(&env, &factory.address, 2));
+
+    env.cost_estimate().budget().reset_unlimited();
+    let mut ids = Vec::new(&env);
+    for i in 0..MAX_OVERVIEW_BATCH {
+        let title = ["M0", "M1", "M2", "M3", "M4", "M5", "M6", "M7", "M8", "M9"][i as usize];
+        ids.push_back(create_linked_market(&env, &factory, &usdc, title).0);
+    }
+
+    env.cost_estimate().budget().reset_default();
+    let overviews = factory.get_market_overviews(&ids);
+    assert_eq!(overviews.len(), MAX_OVERVIEW_BATCH);
+    assert!(overviews.iter().all(|overview| overview.errors == 0));
+    // Well under the 100M instruction simulation limit
+    assert!(env.cost_estimate().budget().cpu_instruction_cost() < 50_000_000);
+}
+
+// ============================================================================
+// MARKET SUMMARY TESTS
+// ============================================================================
+
+/// Helper: AMM initialized against `factory` and registered with it
+fn setup_factory_amm<'a>(
+    env: &'a Env,
+    factory: &MarketFactoryClient<'a>,
+    usdc: &token::StellarAssetClient<'a>,
+    admin: &Address,
+) -> AMMClient<'a> {
+    let amm = AMMClient::new(env, &env.register(AMM, ()));
+    amm.initialize(
+        admin,
+        &factory.address,
+        &usdc.address,
+        &1_000_000_000_000u128,
+    );
+    factory.set_amm(admin, &amm.address);
+    amm
+}
+
+#[test]
+fn test_market_summary_joins_every_component() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    factory.set_oracle(&setup_oracle(&env, &factory.address, 2));
+    let amm = setup_factory_amm(&env, &factory, &usdc, &admin);
+    let (market_id, market) = create_linked_market(&env, &factory, &usdc, "Wired");
+
+    let lp = Address::generate(&env);
+    usdc.mint(&lp, &1_000_000_000);
+    amm.create_pool(&lp, &market_id, &1_000_000_000u128);
+    let user = Address::generate(&env);
+    place_revealed_prediction(&env, &market, &usdc, &market_id, &user, 1, 300);
+
+    let summary = factory.get_market_summary(&market_id);
+    assert_eq!(summary.address, Some(market.address.clone()));
+    assert_eq!(summary.title, Symbol::new(&env, "Wired"));
+    assert_eq!(summary.category, Symbol::new(&env, "Boxing"));
+
+    let state = market.get_market_state(&market_id);
+    assert_eq!(summary.status, Some(state.status));
+    assert_eq!(summary.total_pool, Some(300));
+    assert_eq!(summary.participant_count, Some(state.participant_count));
+    assert_eq!(summary.winning_outcome, None);
+    assert_eq!(summary.yes_total, Some(300));
+    assert_eq!(summary.no_total, Some(0));
+
+    assert_eq!(summary.yes_attestations, Some(0));
+    assert_eq!(summary.no_attestations, Some(0));
+    assert_eq!(summary.consensus_reached, Some(false));
+
+    assert_eq!(summary.yes_price, Some(amm.get_spot_price(&market_id, &1)));
+    assert_eq!(summary.no_price, Some(amm.get_spot_price(&market_id, &0)));
+
+    let treasury = TreasuryClient::new(&env, &factory.get_treasury());
+    assert_eq!(
+        summary.fees_collected,
+        Some(treasury.get_market_token_fees(&market_id, &usdc.address))
+    );
+}
+
+#[test]
+fn test_market_summary_of_bare_market() {
+    let env = create_test_env();
+    let (factory, usdc, admin) = setup_factory_with_treasury(&env);
+    // AMM configured, but no pool for this market; no oracle at all
+    setup_factory_amm(&env, &factory, &usdc, &admin);
+    let (market_id, market) = create_linked_market(&env, &factory, &usdc, "Bare");
+    let (creator, ..) = factory.get_market_metadata(&market_id).unwrap();
+    market.set_pool_reveal(&creator, &Some(env.ledger().timestamp() + 3600));
+
+    let summary = factory.get_market_summary(&market_id);
+    assert_eq!(summary.creator, creator);
+    assert!(summary.status.is_some());
+    assert_eq!(summary.total_pool, Some(0));
+    // Hidden split stays hidden through the factory
+    assert_eq!(summary.yes_total, None);
+    assert_eq!(summary.no_total, None);
+    assert_eq!(summary.yes_attestations, None);
+    assert_eq!(summary.consensus_reached, None);
+    assert_eq!(summary.yes_price, None);
+    assert_eq!(summary.no_price, None);
+    assert_eq!(summary.fees_collected, Some(0));
+
+    // Unlinked registry entry: metadata only
+    factory.set_market_address(&market_id, &env.register(OracleManager, ()));
+    let summary = factory.get_market_summary(&market_id);
+    assert_eq!(summary.status, None);
+    assert_eq!(summary.total_pool, None);
+}
+
+#[test]
+#[should_panic(expected = "market not found")]
+fn test_market_summary_rejects_unknown_market() {
+    let env = create_test_env();
+    let (factory, _, _) = setup_factory_with_treasury(&env);
+    factory.get_market_summary(&BytesN::from_array(&env, &[9u8; 32]));
+}
+
+// ============================================================================
+// STAGING TOOLS FEATURE GATE
+// ============================================================================
+
+/// Registry export/import only exists in builds with the `staging-tools` feature
+#[cfg(not(feature = "staging-tools"))]
+#[test]
+fn test_staging_tools_excluded_from_default_build() {
+    use soroban_sdk::IntoVal;
+
+    let env = create_test_env();
+    let (factory, _, _) = setup_factory_with_treasury(&env);
+
+    let args: soroban_sdk::Vec<soroban_sdk::Val> = (0u32, 10u32).into_val(&env);
+    let exported = env.try_invoke_contract::<soroban_sdk::Val, soroban_sdk::Error>(
+        &factory.address,
+        &Symbol::new(&env, "export_registry"),
+        args,
+    );
+    assert!(exported.is_err());
+}
@@ -12,117 +12,426 @@ use boxmeout::{AMMClient, MarketFactory, MarketFactoryClient, OracleManager, Ora
 // ...rest of the file...
 */
 
-use soroban_sdk::{testutils::Address as _, Address, Env, Symbol};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger},
+    token, vec, Address, BytesN, Env, Symbol, Vec,
+};
 
 use boxmeout::{
-    amm::{AMMClient, AMM},
-    factory::{MarketFactory, MarketFactoryClient},
+    amm::{AMMClient, PricingMode, AMM},
+    factory::{
+        MarketFactory, MarketFactoryClient, MarketOverrides, MarketParams, DEFAULT_CREATION_FEE,
+    },
+    market::{
+        MarketError, MarketStatus, NotificationStatus, PredictionMarket, PredictionMarketClient,
+    },
     oracle::{OracleManager, OracleManagerClient},
+    testutils::{deploy_token, OracleFixture},
     treasury::{Treasury, TreasuryClient},
 };
 
-/// Integration test: Complete user flow from market creation to resolution
-#[test]
-fn test_complete_prediction_flow() {
-    let env = Env::default();
+const MARKET_WASM: &[u8] = include_bytes!("fixtures/market.wasm");
+/// Helper: all five contracts in one Env, wired together, with a factory market
+/// (2% fee) open for bets and an AMM pool on it
+///
+/// Bets close at `closing_time`; the oracle's three fixture oracles attest from
+/// `resolution_time`, and the dispute period ends `DISPUTE_PERIOD` later.
+struct Lifecycle<'a> {
+    usdc: token::StellarAssetClient<'a>,
+    balances: token::TokenClient<'a>,
+    factory: MarketFactoryClient<'a>,
+    treasury: TreasuryClient<'a>,
+    amm: AMMClient<'a>,
+    oracle: OracleFixture,
+    market: PredictionMarketClient<'a>,
+    market_id: BytesN<32>,
+    creator: Address,
+    closing_time: u64,
+    resolution_time: u64,
+}
+
+const DISPUTE_PERIOD: u64 = 604800;
+const MARKET_FEE_BPS: u32 = 200;
+const AMM_TREASURY_SHARE_BPS: u32 = 2500;
+const CREATOR_SPLIT_BPS: u32 = 2000;
+
+fn setup_lifecycle(env: &Env) -> Lifecycle<'_> {
     env.mock_all_auths();
+    // Parsing and deploying the full market wasm does not fit the default test budget
+    env.cost_estimate().budget().reset_unlimited();
+
+    let admin = Address::generate(env);
+    let usdc = deploy_token(env);
+    let factory = MarketFactoryClient::new(env, &env.register(MarketFactory, ()));
+    let treasury = TreasuryClient::new(env, &env.register(Treasury, ()));
+    let amm = AMMClient::new(env, &env.register(AMM, ()));
+    let oracle = OracleFixture::new(env).with_oracles(3);
+
+    factory.initialize(&admin, &usdc.address, &treasury.address);
+    treasury.initialize(&admin, &usdc.address, &factory.address);
+    amm.initialize(&admin, &factory.address, &usdc.address, &1_000_000_000u128);
+
+    // Factory registers its markets with the oracle and deploys them from the wasm
+    oracle.client.add_registrar(&oracle.admin, &factory.address);
+    factory.set_oracle(&oracle.client.address);
+    factory.set_permissionless(&admin, &true);
+    let wasm_hash = env.deployer().upload_contract_wasm(MARKET_WASM);
+    factory.set_market_wasm_hash(&admin, &factory.get_network_binding(), &wasm_hash, &1);
+
+    // AMM fees reach the treasury, which passes the creator's cut on
+    treasury.add_depositor(&admin, &amm.address);
+    amm.set_treasury_fee_share(&admin, &AMM_TREASURY_SHARE_BPS);
+    treasury.set_creator_split(&admin, &CREATOR_SPLIT_BPS);
+
+    let creator = Address::generate(env);
+    usdc.mint(&creator, &DEFAULT_CREATION_FEE);
+    let closing_time = env.ledger().timestamp() + 86400;
+    let resolution_time = closing_time + 3600;
+    let market_id = factory.create_market(
+        &creator,
+        &MarketParams {
+            title: Symbol::new(env, "Mayweather"),
+            description: Symbol::new(env, "MayweatherWins"),
+            category: Symbol::new(env, "Boxing"),
+            closing_time,
+            resolution_time,
+            operator: None,
+            nonce: 0,
+            region: Symbol::new(env, "global"),
+        },
+        &Some(MarketOverrides {
+            fee_bps: Some(MARKET_FEE_BPS),
+            min_bet: None,
+            betting_cutoff_offset: None,
+            dispute_window: None,
+            token: None,
+        }),
+    );
+    let market_address = factory.get_market_address(&market_id).unwrap();
+    let market = PredictionMarketClient::new(env, &market_address);
+
+    // Admin wiring the factory does not do itself
+    oracle
+        .client
+        .set_market_contract(&market_id, &market_address);
+    treasury.register_market_creator(&admin, &market_id, &creator);
+    usdc.mint(&admin, &2_000_000);
+    amm.initialize_pool(
+        &admin,
+        &market_id,
+        &market_address,
+        &usdc.address,
+        &2_000_000u128,
+        &100u32,
+        &PricingMode::Cpmm,
+    );
+
+    Lifecycle {
+        balances: token::TokenClient::new(env, &usdc.address),
+        usdc,
+        factory,
+        treasury,
+        amm,
+        oracle,
+        market,
+        market_id,
+        creator,
+        closing_time,
+        resolution_time,
+    }
+}
 
-    // Step 1: Deploy all contracts
-    let factory_id = env.register(MarketFactory, ());
-    let treasury_id = env.register(Treasury, ());
-    let oracle_id = env.register(OracleManager, ());
-    let amm_id = env.register(AMM, ());
+/// Helper: `amount` minted to a new address and bet on `outcome` directly
+fn bet(env: &Env, lifecycle: &Lifecycle, outcome: u32, amount: i128) -> Address {
+    let user = Address::generate(env);
+    lifecycle.usdc.mint(&user, &amount);
+    lifecycle.market.place_prediction(&user, &outcome, &amount);
+    user
+}
 
-    let factory_client = MarketFactoryClient::new(&env, &factory_id);
-    let treasury_client = TreasuryClient::new(&env, &treasury_id);
-    let oracle_client = OracleManagerClient::new(&env, &oracle_id);
-    let amm_client = AMMClient::new(&env, &amm_id);
+/// Helper: `amount` minted to a new address and spent on `outcome` shares in the pool
+fn buy(env: &Env, lifecycle: &Lifecycle, outcome: u32, amount: i128) -> (Address, u128) {
+    let user = Address::generate(env);
+    lifecycle.usdc.mint(&user, &amount);
+    let shares =
+        lifecycle
+            .amm
+            .buy_shares(&user, &lifecycle.market_id, &outcome, &(amount as u128), &0);
+    (user, shares)
+}
 
-    // Create addresses
-    let admin = Address::generate(&env);
-    let usdc_token = Address::generate(&env);
-    let _creator = Address::generate(&env);
-    let _user1 = Address::generate(&env);
-    let _user2 = Address::generate(&env);
+/// Helper: close betting and have the fixture oracles attest `outcomes` in order
+fn close_and_attest(env: &Env, lifecycle: &Lifecycle, outcomes: &[u32]) {
+    env.ledger().set_timestamp(lifecycle.closing_time);
+    lifecycle.market.close_market(&lifecycle.market_id);
+    assert_eq!(lifecycle.market.get_state(), MarketStatus::Locked);
+
+    env.ledger().set_timestamp(lifecycle.resolution_time);
+    for (i, outcome) in outcomes.iter().enumerate() {
+        lifecycle.oracle.attest(
+            &lifecycle.oracle.oracle(i as u32),
+            &lifecycle.market_id,
+            *outcome,
+        );
+    }
+}
 
-    // Step 2: Initialize all contracts
-    factory_client.initialize(&admin, &usdc_token, &treasury_id);
-    treasury_client.initialize(&admin, &usdc_token, &factory_id);
-    oracle_client.initialize(&admin, &2u32);
-    amm_client.initialize(&admin, &factory_id, &usdc_token, &100_000_000_000u128);
+/// Integration test: market created, bet on, attested, finalized and paid out across all contracts
+#[test]
+fn test_complete_prediction_flow() {
+    let env = Env::default();
+    let lifecycle = setup_lifecycle(&env);
+    let (market_id, market) = (&lifecycle.market_id, &lifecycle.market);
+
+    // Creation: fee booked in the treasury, market registered with the oracle
+    assert_eq!(
+        lifecycle.balances.balance(&lifecycle.treasury.address),
+        DEFAULT_CREATION_FEE
+    );
+    assert_eq!(lifecycle.treasury.get_total_fees(), DEFAULT_CREATION_FEE);
+    assert_eq!(lifecycle.balances.balance(&lifecycle.creator), 0);
+    assert_eq!(market.get_state(), MarketStatus::Open);
+    assert_eq!(
+        lifecycle
+            .oracle
+            .client
+            .get_market_resolution_time(market_id),
+        Some(lifecycle.resolution_time)
+    );
+    assert_eq!(
+        lifecycle.factory.get_market_address(market_id),
+        Some(market.address.clone())
+    );
+
+    // Predictions, directly and through the pool
+    let yes_bettor = bet(&env, &lifecycle, 1, 3_000_000);
+    let no_bettor = bet(&env, &lifecycle, 0, 1_000_000);
+    let (yes_trader, yes_shares) = buy(&env, &lifecycle, 1, 500_000);
+    assert!(yes_shares > 0);
+    let pools = market.get_pools();
+    assert_eq!((pools.yes_total, pools.no_total), (3_000_000, 1_000_000));
+    assert_eq!(lifecycle.balances.balance(&market.address), 4_000_000);
+    assert_eq!(
+        lifecycle.balances.balance(&lifecycle.amm.address),
+        2_500_000
+    );
+
+    // Cutoff passes: no more bets; oracles attest 2 YES / 1 NO
+    close_and_attest(&env, &lifecycle, &[1, 1, 0]);
+    let late = Address::generate(&env);
+    lifecycle.usdc.mint(&late, &1_000);
+    assert!(market.try_place_prediction(&late, &1, &1_000).is_err());
+    assert_eq!(
+        lifecycle.oracle.client.check_consensus(market_id),
+        (true, 1)
+    );
+
+    // Finalization waits for the dispute period, then resolves the market contract
+    assert!(lifecycle
+        .oracle
+        .client
+        .try_finalize_resolution(market_id, &market.address)
+        .is_err());
+    env.ledger()
+        .set_timestamp(lifecycle.resolution_time + DISPUTE_PERIOD);
+    lifecycle
+        .oracle
+        .client
+        .finalize_resolution(market_id, &market.address);
+    assert_eq!(market.get_state(), MarketStatus::Resolved);
+    assert_eq!(market.get_winning_outcome(), Some(1));
+    assert_eq!(lifecycle.oracle.client.get_consensus_result(market_id), 1);
+
+    // The winner is paid net of fees; the protocol fee lands in the treasury
+    let preview = market.simulate_claim(&yes_bettor);
+    assert_eq!(preview.gross, 4_000_000);
+    assert!(preview.protocol_fee > 0);
+    let treasury_before = lifecycle.balances.balance(&lifecycle.treasury.address);
+    assert_eq!(market.claim_winnings(&yes_bettor, market_id), preview.net);
+    assert_eq!(lifecycle.balances.balance(&yes_bettor), preview.net);
+    assert_eq!(
+        lifecycle.balances.balance(&lifecycle.treasury.address),
+        treasury_before + preview.protocol_fee
+    );
+    assert_eq!(
+        lifecycle.treasury.get_total_fees(),
+        DEFAULT_CREATION_FEE + preview.protocol_fee
+    );
+    assert!(market.try_claim_winnings(&yes_bettor, market_id).is_err());
+    assert!(market.try_claim_winnings(&no_bettor, market_id).is_err());
+
+    // The pool settles on the market's outcome; winning shares redeem one to one
+    let settlement = lifecycle.amm.settle_pool(market_id);
+    assert_eq!(settlement.winning_outcome, Some(1));
+    assert_eq!(
+        lifecycle.amm.redeem_shares(&yes_trader, market_id),
+        yes_shares
+    );
+    assert_eq!(lifecycle.balances.balance(&yes_trader), yes_shares as i128);
+
+    // AMM fees reach the treasury, and the creator claims their split of them
+    let pending = lifecycle.amm.get_pending_treasury_fees(market_id);
+    assert!(pending > 0);
+    assert_eq!(lifecycle.amm.flush_fees_to_treasury(market_id), pending);
+    let creator_cut = pending as i128 * CREATOR_SPLIT_BPS as i128 / 10_000;
+    assert_eq!(
+        lifecycle.treasury.get_creator_claimable(&lifecycle.creator),
+        creator_cut
+    );
+    assert_eq!(
+        lifecycle.treasury.claim_creator_revenue(&lifecycle.creator),
+        creator_cut
+    );
+    assert_eq!(lifecycle.balances.balance(&lifecycle.creator), creator_cut);
+    assert_eq!(
+        lifecycle.treasury.get_creator_claimable(&lifecycle.creator),
+        0
+    );
+}
 
-    // Step 3: Register oracles
-    let oracle1 = Address::generate(&env);
-    let oracle2 = Address::generate(&env);
-    let oracle3 = Address::generate(&env);
+/// Integration test: an emergency override resolves the market against the attested outcome
+#[test]
+fn test_emergency_override_resolves_market() {
+    let env = Env::default();
+    let lifecycle = setup_lifecycle(&env);
+    let (market_id, market) = (&lifecycle.market_id, &lifecycle.market);
+    let yes_bettor = bet(&env, &lifecycle, 1, 3_000_000);
+    let no_bettor = bet(&env, &lifecycle, 0, 1_000_000);
+
+    // The oracles agree on YES, but the admins force NO before finalization
+    close_and_attest(&env, &lifecycle, &[1, 1]);
+    assert_eq!(
+        lifecycle.oracle.client.check_consensus(market_id),
+        (true, 1)
+    );
+    let signer = Address::generate(&env);
+    lifecycle.oracle.client.add_admin_signer(&signer);
+    lifecycle.oracle.client.emergency_override(
+        &vec![&env, lifecycle.oracle.admin.clone(), signer],
+        market_id,
+        &0u32,
+        &BytesN::from_array(&env, &[7u8; 32]),
+    );
+
+    assert!(lifecycle.oracle.client.is_manual_override(market_id));
+    assert_eq!(lifecycle.oracle.client.get_consensus_result(market_id), 0);
+    assert_eq!(market.get_state(), MarketStatus::Resolved);
+    assert_eq!(market.get_winning_outcome(), Some(0));
+
+    // The override is final: the attested consensus can no longer resolve the market
+    env.ledger()
+        .set_timestamp(lifecycle.resolution_time + DISPUTE_PERIOD);
+    assert!(lifecycle
+        .oracle
+        .client
+        .try_finalize_resolution(market_id, &market.address)
+        .is_err());
+
+    let preview = market.simulate_claim(&no_bettor);
+    assert_eq!(preview.gross, 4_000_000);
+    assert_eq!(market.claim_winnings(&no_bettor, market_id), preview.net);
+    assert_eq!(lifecycle.balances.balance(&no_bettor), preview.net);
+    assert!(market.try_claim_winnings(&yes_bettor, market_id).is_err());
+    assert_eq!(
+        lifecycle.amm.settle_pool(market_id).winning_outcome,
+        Some(0)
+    );
+}
 
-    oracle_client.register_oracle(&oracle1, &Symbol::new(&env, "Oracle1"));
-    oracle_client.register_oracle(&oracle2, &Symbol::new(&env, "Oracle2"));
-    oracle_client.register_oracle(&oracle3, &Symbol::new(&env, "Oracle3"));
-
-    // TODO: Complete integration test when functions are implemented
-
-    // Step 4: Create market
-    // let closing_time = env.ledger().timestamp() + 86400; // +1 day
-    // let resolution_time = closing_time + 3600; // +1 hour
-    // let market_id = factory_client.create_market(
-    //     &creator,
-    //     &Symbol::new(&env, "Mayweather"),
-    //     &Symbol::new(&env, "MayweatherWins"),
-    //     &Symbol::new(&env, "Boxing"),
-    //     &closing_time,
-    //     &resolution_time,
-    // );
-
-    // Step 5: Create AMM pool
-    // amm_client.create_pool(&market_id, &10_000_000_000u128);
-
-    // Step 6: User1 commits prediction
-    // let commit_hash_1 = BytesN::from_array(&env, &[1u8; 32]);
-    // market_client.commit_prediction(&user1, &market_id, &commit_hash_1, &100_000_000);
-
-    // Step 7: User2 commits prediction
-    // let commit_hash_2 = BytesN::from_array(&env, &[2u8; 32]);
-    // market_client.commit_prediction(&user2, &market_id, &commit_hash_2, &200_000_000);
-
-    // Step 8: User1 reveals prediction (YES)
-    // let salt_1 = BytesN::from_array(&env, &[10u8; 32]);
-    // market_client.reveal_prediction(&user1, &market_id, &1u32, &100_000_000, &salt_1);
-
-    // Step 9: User2 reveals prediction (NO)
-    // let salt_2 = BytesN::from_array(&env, &[20u8; 32]);
-    // market_client.reveal_prediction(&user2, &market_id, &0u32, &200_000_000, &salt_2);
-
-    // Step 10: Advance time past closing
-    // env.ledger().set(LedgerInfo {
-    //     timestamp: closing_time + 10,
-    //     protocol_version: 20,
-    //     sequence_number: 10,
-    //     network_id: Default::default(),
-    //     base_reserve: 10,
-    //     min_temp_entry_ttl: 10,
-    //     min_persistent_entry_ttl: 10,
-    //     max_entry_ttl: 3110400,
-    // });
-
-    // Step 11: Oracles submit attestations
-    // oracle_client.submit_attestation(&oracle1, &market_id, &1u32); // YES
-    // oracle_client.submit_attestation(&oracle2, &market_id, &1u32); // YES
-    // oracle_client.submit_attestation(&oracle3, &market_id, &0u32); // NO
-
-    // Step 12: Resolve market (2 of 3 oracles voted YES)
-    // market_client.resolve_market(&market_id);
-
-    // Step 13: Winners claim rewards
-    // market_client.claim_winnings(&user1, &market_id);
-
-    // Step 14: Verify treasury fees collected
-    // let platform_fees = treasury_client.get_platform_fees();
-    // assert!(platform_fees > 0);
-
-    // Verify complete flow succeeded - test passes if no panics occurred
+/// Integration test: an attestation challenge on the oracle freezes a resolved market until it is resolved
+#[test]
+fn test_oracle_challenge_blocks_claims_until_resolved() {
+    let env = Env::default();
+    let lifecycle = setup_lifecycle(&env);
+    let (market_id, market) = (&lifecycle.market_id, &lifecycle.market);
+    let yes_bettor = bet(&env, &lifecycle, 1, 3_000_000);
+    bet(&env, &lifecycle, 0, 1_000_000);
+
+    close_and_attest(&env, &lifecycle, &[1, 1]);
+    env.ledger()
+        .set_timestamp(lifecycle.resolution_time + DISPUTE_PERIOD);
+    lifecycle
+        .oracle
+        .client
+        .finalize_resolution(market_id, &market.address);
+    assert_eq!(market.get_state(), MarketStatus::Resolved);
+
+    // The challenge reaches the market: claims and pool settlement stop
+    let challenged = lifecycle.oracle.oracle(0);
+    lifecycle.oracle.client.challenge_attestation(
+        &Address::generate(&env),
+        &challenged,
+        market_id,
+        &Symbol::new(&env, "bad_source"),
+    );
+    assert!(market.is_dispute_flagged());
+    assert_eq!(market.get_state(), MarketStatus::Disputed);
+    assert!(market.try_claim_winnings(&yes_bettor, market_id).is_err());
+    assert!(lifecycle.amm.try_settle_pool(market_id).is_err());
+
+    // Rejecting the challenge clears the flag and re-enables claims
+    lifecycle
+        .oracle
+        .client
+        .resolve_challenge(&challenged, market_id, &false);
+    assert!(!market.is_dispute_flagged());
+    assert_eq!(market.get_state(), MarketStatus::Resolved);
+    let preview = market.simulate_claim(&yes_bettor);
+    assert_eq!(market.claim_winnings(&yes_bettor, market_id), preview.net);
+    assert_eq!(
+        lifecycle.amm.settle_pool(market_id).winning_outcome,
+        Some(1)
+    );
 }
 
+/// Integration test: a cancelled market refunds every bettor in full and the pool at its prices
+#[test]
+fn test_cancelled_market_refunds_everyone() {
+    let env = Env::default();
+    let lifecycle = setup_lifecycle(&env);
+    let (market_id, market) = (&lifecycle.market_id, &lifecycle.market);
+    let yes_bettor = bet(&env, &lifecycle, 1, 3_000_000);
+    let no_bettor = bet(&env, &lifecycle, 0, 1_000_000);
+    let (no_trader, _) = buy(&env, &lifecycle, 0, 400_000);
+
+    market.cancel_market(&lifecycle.creator, market_id);
+    assert_eq!(market.get_state(), MarketStatus::Cancelled);
+
+    market.claim_refund(&yes_bettor, market_id);
+    market.claim_refund(&no_bettor, market_id);
+    assert_eq!(lifecycle.balances.balance(&yes_bettor), 3_000_000);
+    assert_eq!(lifecycle.balances.balance(&no_bettor), 1_000_000);
+    assert_eq!(lifecycle.balances.balance(&market.address), 0);
+    assert!(market.try_claim_refund(&yes_bettor, market_id).is_err());
+    assert!(market.try_claim_winnings(&yes_bettor, market_id).is_err());
+
+    // The pool refunds both sides instead of picking a winner
+    let settlement = lifecycle.amm.settle_pool(market_id);
+    assert_eq!(settlement.winning_outcome, None);
+    let refund = lifecycle.amm.redeem_shares(&no_trader, market_id);
+    assert!(refund > 0 && refund < 400_000);
+    assert_eq!(lifecycle.balances.balance(&no_trader), refund as i128);
+
+    // A cancelled market cannot be resolved afterwards, even with consensus
+    env.ledger().set_timestamp(lifecycle.resolution_time);
+    lifecycle
+        .oracle
+        .attest(&lifecycle.oracle.oracle(0), market_id, 1);
+    lifecycle
+        .oracle
+        .attest(&lifecycle.oracle.oracle(1), market_id, 1);
+    env.ledger()
+        .set_timestamp(lifecycle.resolution_time + DISPUTE_PERIOD);
+    assert!(lifecycle
+        .oracle
+        .client
+        .try_finalize_resolution(market_id, &market.address)
+        .is_err());
+
+    // Only the creation fee reached the treasury
+    assert_eq!(lifecycle.treasury.get_total_fees(), DEFAULT_CREATION_FEE);
+}
 /// Integration test: Market creation and AMM trading flow
 #[test]
 fn test_market_creation_and_trading() {
@@ -170,9 +479,9 @@ fn test_oracle_consensus_flow() {
     let oracle2 = Address::generate(&env);
     let oracle3 = Address::generate(&env);
 
-    oracle_client.register_oracle(&oracle1, &Symbol::new(&env, "Oracle1"));
-    oracle_client.register_oracle(&oracle2, &Symbol::new(&env, "Oracle2"));
-    oracle_client.register_oracle(&oracle3, &Symbol::new(&env, "Oracle3"));
+    oracle_client.register_oracle(&oracle1, &Symbol::new(&env, "Oracle1"), &Vec::new(&env));
+    oracle_client.register_oracle(&oracle2, &Symbol::new(&env, "Oracle2"), &Vec::new(&env));
+    oracle_client.register_oracle(&oracle3, &Symbol::new(&env, "Oracle3"), &Vec::new(&env));
 
     // TODO: Test consensus
     // Submit attestations from oracles
@@ -224,3 +533,489 @@ fn test_error_scenarios() {
     // Unauthorized actions
     // Insufficient balances
 }
+
+/// Integration test: markets created by the factory are attestable without manual oracle registration
+#[test]
+fn test_factory_market_attestable_without_manual_registration() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let usdc_token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let factory_id = env.register(MarketFactory, ());
+    let treasury_id = env.register(Treasury, ());
+    let oracle_id = env.register(OracleManager, ());
+
+    let factory_client = MarketFactoryClient::new(&env, &factory_id);
+    let oracle_client = OracleManagerClient::new(&env, &oracle_id);
+    factory_client.initialize(&admin, &usdc_token, &treasury_id);
+    TreasuryClient::new(&env, &treasury_id).initialize(&admin, &usdc_token, &factory_id);
+    oracle_client.initialize(&admin, &2u32);
+
+    let oracle1 = Address::generate(&env);
+    let oracle2 = Address::generate(&env);
+    oracle_client.register_oracle(&oracle1, &Symbol::new(&env, "Oracle1"), &Vec::new(&env));
+    oracle_client.register_oracle(&oracle2, &Symbol::new(&env, "Oracle2"), &Vec::new(&env));
+
+    // Wire the factory to the oracle as a registrar (not an oracle admin)
+    oracle_client.add_registrar(&admin, &factory_id);
+    factory_client.set_oracle(&oracle_id);
+    factory_client.set_permissionless(&admin, &true);
+
+    let creator = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_token).mint(&creator, &10_000_000);
+    let closing_time = env.ledger().timestamp() + 86400;
+    let resolution_time = closing_time + 3600;
+    let market_id = factory_client.create_market(
+        &creator,
+        &MarketParams {
+            title: Symbol::new(&env, "Mayweather"),
+            description: Symbol::new(&env, "MayweatherWins"),
+            category: Symbol::new(&env, "Boxing"),
+            closing_time,
+            resolution_time,
+            operator: None,
+            nonce: 0,
+            region: Symbol::new(&env, "global"),
+        },
+        &None,
+    );
+
+    // No register_market call: oracles attest straight away after resolution time
+    env.ledger().set_timestamp(resolution_time);
+    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
+    oracle_client.submit_attestation(&oracle1, &market_id, &1u32, &data_hash, &None);
+    oracle_client.submit_attestation(&oracle2, &market_id, &1u32, &data_hash, &None);
+
+    assert_eq!(oracle_client.check_consensus(&market_id), (true, 1));
+}
+
+/// Helper: factory-deployed market wired to a 2-of-2 oracle, with one YES bet placed
+struct StuckMarket<'a> {
+    factory: MarketFactoryClient<'a>,
+    oracle: OracleManagerClient<'a>,
+    market: PredictionMarketClient<'a>,
+    market_id: BytesN<32>,
+    oracles: (Address, Address),
+    creator: Address,
+    bettor: Address,
+    usdc: token::TokenClient<'a>,
+}
+
+fn setup_stuck_market(env: &Env) -> StuckMarket<'_> {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let usdc_token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let factory_id = env.register(MarketFactory, ());
+    let treasury_id = env.register(Treasury, ());
+    let oracle_id = env.register(OracleManager, ());
+
+    let factory = MarketFactoryClient::new(env, &factory_id);
+    let oracle = OracleManagerClient::new(env, &oracle_id);
+    factory.initialize(&admin, &usdc_token, &treasury_id);
+    TreasuryClient::new(env, &treasury_id).initialize(&admin, &usdc_token, &factory_id);
+    oracle.initialize(&admin, &2u32);
+
+    let oracle1 = Address::generate(env);
+    let oracle2 = Address::generate(env);
+    oracle.register_oracle(&oracle1, &Symbol::new(env, "Oracle1"), &Vec::new(env));
+    oracle.register_oracle(&oracle2, &Symbol::new(env, "Oracle2"), &Vec::new(env));
+    oracle.add_registrar(&admin, &factory_id);
+    factory.set_oracle(&oracle_id);
+    factory.set_permissionless(&admin, &true);
+    // Parsing and deploying the full market wasm does not fit the default test budget
+    env.cost_estimate().budget().reset_unlimited();
+    let wasm_hash = env.deployer().upload_contract_wasm(MARKET_WASM);
+    factory.set_market_wasm_hash(&admin, &factory.get_network_binding(), &wasm_hash, &1);
+
+    let minter = token::StellarAssetClient::new(env, &usdc_token);
+    let creator = Address::generate(env);
+    minter.mint(&creator, &10_000_000);
+    let closing_time = env.ledger().timestamp() + 86400;
+    let market_id = factory.create_market(
+        &creator,
+        &MarketParams {
+            title: Symbol::new(env, "Stuck"),
+            description: Symbol::new(env, "OracleNeverAnswers"),
+            category: Symbol::new(env, "Boxing"),
+            closing_time,
+            resolution_time: closing_time + 3600,
+            operator: None,
+            nonce: 0,
+            region: Symbol::new(env, "global"),
+        },
+        &None,
+    );
+    let market = PredictionMarketClient::new(env, &factory.get_market_address(&market_id).unwrap());
+
+    let bettor = Address::generate(env);
+    minter.mint(&bettor, &5_000_000);
+    market.place_prediction(&bettor, &1u32, &5_000_000);
+
+    env.ledger().set_timestamp(closing_time);
+    market.close_market(&market_id);
+
+    StuckMarket {
+        factory,
+        oracle,
+        market,
+        market_id,
+        oracles: (oracle1, oracle2),
+        creator,
+        bettor,
+        usdc: token::TokenClient::new(env, &usdc_token),
+    }
+}
+
+/// Integration test: a market the oracle never resolves is voided after the timeout
+#[test]
+fn test_unresolved_market_escalates_to_refunding_after_oracle_timeout() {
+    let env = Env::default();
+    let stuck = setup_stuck_market(&env);
+
+    env.ledger()
+        .set_timestamp(stuck.market.get_oracle_timeout());
+    stuck.market.escalate_unresolved(&Address::generate(&env));
+
+    assert_eq!(stuck.market.get_state(), MarketStatus::Refunding);
+    let incidents = stuck.factory.list_incidents(&0, &10);
+    assert_eq!(incidents.len(), 1);
+    let incident = incidents.get(0).unwrap();
+    assert_eq!(incident.market_id, stuck.market_id);
+    assert_eq!(incident.kind, Symbol::new(&env, "oracle_timeout"));
+
+    // Refunds open for participants
+    stuck.market.claim_refund(&stuck.bettor, &stuck.market_id);
+    assert_eq!(stuck.usdc.balance(&stuck.bettor), 5_000_000);
+}
+
+/// Integration test: escalation is refused before the oracle timeout
+#[test]
+fn test_escalation_rejected_before_oracle_timeout() {
+    let env = Env::default();
+    let stuck = setup_stuck_market(&env);
+
+    env.ledger()
+        .set_timestamp(stuck.market.get_oracle_timeout() - 1);
+    let result = stuck
+        .market
+        .try_escalate_unresolved(&Address::generate(&env));
+
+    assert!(result.is_err());
+    assert_eq!(stuck.market.get_state(), MarketStatus::Locked);
+    assert_eq!(stuck.factory.get_incident_count(), 0);
+}
+
+/// Integration test: escalation is refused while the oracle has an answer waiting
+#[test]
+fn test_escalation_rejected_when_oracle_has_consensus() {
+    let env = Env::default();
+    let stuck = setup_stuck_market(&env);
+    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
+    env.ledger()
+        .set_timestamp(stuck.market.get_resolution_time());
+    stuck
+        .oracle
+        .submit_attestation(&stuck.oracles.0, &stuck.market_id, &1u32, &data_hash, &None);
+    stuck
+        .oracle
+        .submit_attestation(&stuck.oracles.1, &stuck.market_id, &1u32, &data_hash, &None);
+
+    env.ledger()
+        .set_timestamp(stuck.market.get_oracle_timeout());
+    let result = stuck
+        .market
+        .try_escalate_unresolved(&Address::generate(&env));
+
+    assert!(result.is_err());
+    assert_eq!(stuck.market.get_state(), MarketStatus::Locked);
+}
+
+/// Integration test: the oracle resolving after escalation is refused by the voided market
+#[test]
+fn test_late_oracle_resolution_rejected_after_escalation() {
+    let env = Env::default();
+    let stuck = setup_stuck_market(&env);
+    env.ledger()
+        .set_timestamp(stuck.market.get_oracle_timeout());
+    stuck.market.escalate_unresolved(&Address::generate(&env));
+
+    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
+    stuck
+        .oracle
+        .submit_attestation(&stuck.oracles.0, &stuck.market_id, &1u32, &data_hash, &None);
+    stuck
+        .oracle
+        .submit_attestation(&stuck.oracles.1, &stuck.market_id, &1u32, &data_hash, &None);
+    let result = stuck
+        .oracle
+        .try_finalize_resolution(&stuck.market_id, &stuck.market.address);
+
+    assert!(result.is_err());
+    assert_eq!(stuck.market.get_state(), MarketStatus::Refunding);
+}
+
+/// Creator-side contract that records the resolution it was notified of
+#[contract]
+struct RecordingTarget;
+
+#[contractimpl]
+impl RecordingTarget {
+    pub fn on_market_resolved(env: Env, market_id: BytesN<32>, outcome: u32) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("last"), &(market_id, outcome));
+    }
+
+    pub fn last(env: Env) -> Option<(BytesN<32>, u32)> {
+        env.storage().instance().get(&symbol_short!("last"))
+    }
+}
+
+/// Creator-side contract that traps on every notification
+#[contract]
+struct TrappingTarget;
+
+#[contractimpl]
+impl TrappingTarget {
+    pub fn on_market_resolved(_env: Env, _market_id: BytesN<32>, _outcome: u32) {
+        panic!("target trapped");
+    }
+}
+
+/// Integration test: a resolved market pings the creator's target
+#[test]
+fn test_resolution_notifies_creator_target() {
+    let env = Env::default();
+    let stuck = setup_stuck_market(&env);
+    let target_id = env.register(RecordingTarget, ());
+    stuck
+        .market
+        .set_notification_target(&stuck.creator, &Some(target_id.clone()));
+
+    env.ledger()
+        .set_timestamp(stuck.market.get_resolution_time());
+    stuck.market.resolve_market(&stuck.market_id);
+
+    assert_eq!(stuck.market.get_state(), MarketStatus::Resolved);
+    assert_eq!(
+        stuck.market.get_notification_status(),
+        Some(NotificationStatus::Delivered)
+    );
+    let target = RecordingTargetClient::new(&env, &target_id);
+    assert_eq!(target.last(), Some((stuck.market_id.clone(), 1)));
+}
+
+/// Integration test: a trapping target does not block resolution
+#[test]
+fn test_trapping_notification_target_does_not_block_resolution() {
+    let env = Env::default();
+    let stuck = setup_stuck_market(&env);
+    let target_id = env.register(TrappingTarget, ());
+    stuck
+        .market
+        .set_notification_target(&stuck.creator, &Some(target_id));
+
+    env.ledger()
+        .set_timestamp(stuck.market.get_resolution_time());
+    stuck.market.resolve_market(&stuck.market_id);
+
+    assert_eq!(stuck.market.get_state(), MarketStatus::Resolved);
+    assert_eq!(
+        stuck.market.get_notification_status(),
+        Some(NotificationStatus::Failed)
+    );
+}
+
+/// Integration test: voiding notifies with outcome 2, and a cleared target is skipped
+#[test]
+fn test_void_notifies_target_until_cleared() {
+    let env = Env::default();
+    let stuck = setup_stuck_market(&env);
+    let target_id = env.register(RecordingTarget, ());
+    stuck
+        .market
+        .set_notification_target(&stuck.creator, &Some(target_id.clone()));
+
+    env.ledger()
+        .set_timestamp(stuck.market.get_oracle_timeout());
+    stuck.market.escalate_unresolved(&Address::generate(&env));
+
+    let target = RecordingTargetClient::new(&env, &target_id);
+    assert_eq!(target.last(), Some((stuck.market_id.clone(), 2)));
+    assert_eq!(
+        stuck.market.get_notification_status(),
+        Some(NotificationStatus::Delivered)
+    );
+
+    // Settled markets keep their target
+    let result = stuck
+        .market
+        .try_set_notification_target(&stuck.creator, &None);
+    assert!(result.is_err());
+
+    let other = setup_stuck_market(&env);
+    other
+        .market
+        .set_notification_target(&other.creator, &Some(target_id));
+    other.market.set_notification_target(&other.creator, &None);
+    env.ledger()
+        .set_timestamp(other.market.get_oracle_timeout());
+    other.market.escalate_unresolved(&Address::generate(&env));
+    assert_eq!(other.market.get_notification_status(), None);
+}
+
+/// KYC registry stub that counts how often it is consulted
+#[contract]
+struct MockKycRegistry;
+
+#[contractimpl]
+impl MockKycRegistry {
+    pub fn set_verified(env: Env, user: Address, verified: bool) {
+        env.storage().instance().set(&user, &verified);
+    }
+
+    pub fn is_verified(env: Env, user: Address) -> bool {
+        let calls: u32 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("calls"))
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("calls"), &(calls + 1));
+        env.storage().instance().get(&user).unwrap_or(false)
+    }
+
+    pub fn calls(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("calls"))
+            .unwrap_or(0)
+    }
+}
+
+struct KycMarket<'a> {
+    env: Env,
+    market: PredictionMarketClient<'a>,
+    registry: MockKycRegistryClient<'a>,
+    usdc: token::StellarAssetClient<'a>,
+    market_id: BytesN<32>,
+}
+
+/// Market gated by a mock registry; bets close at 2000, resolution at 3000
+fn setup_kyc_market(env: &Env) -> KycMarket<'_> {
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+    let usdc = token::StellarAssetClient::new(
+        env,
+        &env.register_stellar_asset_contract_v2(Address::generate(env))
+            .address(),
+    );
+    let registry = MockKycRegistryClient::new(env, &env.register(MockKycRegistry, ()));
+
+    let market = PredictionMarketClient::new(env, &env.register(PredictionMarket, ()));
+    let market_id = BytesN::from_array(env, &[12u8; 32]);
+    let creator = Address::generate(env);
+    market.initialize(
+        &market_id,
+        &creator,
+        &Address::generate(env),
+        &usdc.address,
+        &Address::generate(env),
+        &2000,
+        &3000,
+        &0,
+        &None,
+    );
+    market.set_kyc_registry(&creator, &Some(registry.address.clone()));
+
+    KycMarket {
+        env: env.clone(),
+        market,
+        registry,
+        usdc,
+        market_id,
+    }
+}
+
+/// Integration test: verified users bet, with one registry read per ledger
+#[test]
+fn test_kyc_verified_user_can_bet() {
+    let env = Env::default();
+    let kyc = setup_kyc_market(&env);
+    let user = Address::generate(&env);
+    kyc.usdc.mint(&user, &1_000);
+    kyc.registry.set_verified(&user, &true);
+
+    kyc.market.place_prediction(&user, &1, &300);
+    kyc.market.place_prediction(&user, &1, &200);
+    assert_eq!(kyc.registry.calls(), 1);
+
+    kyc.env.ledger().with_mut(|li| li.sequence_number += 1);
+    kyc.market.place_prediction(&user, &1, &100);
+    assert_eq!(kyc.registry.calls(), 2);
+    assert_eq!(kyc.market.get_position(&user).yes_amount, 600);
+}
+
+/// Integration test: unverified users are rejected by the market and the AMM
+#[test]
+fn test_kyc_unverified_user_rejected() {
+    let env = Env::default();
+    let kyc = setup_kyc_market(&env);
+    let user = Address::generate(&env);
+    kyc.usdc.mint(&user, &1_000);
+
+    assert_eq!(
+        kyc.market.try_place_prediction(&user, &1, &300),
+        Err(Ok(MarketError::NotVerified))
+    );
+
+    let admin = Address::generate(&env);
+    let amm = AMMClient::new(&env, &env.register(AMM, ()));
+    amm.initialize(
+        &admin,
+        &Address::generate(&env),
+        &kyc.usdc.address,
+        &1_000_000_000u128,
+    );
+    let lp = Address::generate(&env);
+    kyc.usdc.mint(&lp, &1_000_000);
+    amm.create_pool(&lp, &kyc.market_id, &1_000_000u128);
+    amm.set_pool_kyc_registry(&admin, &kyc.market_id, &Some(kyc.registry.address.clone()));
+
+    assert!(amm
+        .try_buy_shares(&user, &kyc.market_id, &1, &100u128, &0u128)
+        .is_err());
+    kyc.registry.set_verified(&user, &true);
+    kyc.env.ledger().with_mut(|li| li.sequence_number += 1);
+    assert!(amm.buy_shares(&user, &kyc.market_id, &1, &100u128, &0u128) > 0);
+}
+
+/// Integration test: losing verification never blocks a claim
+#[test]
+fn test_kyc_claim_is_never_gated() {
+    let env = Env::default();
+    let kyc = setup_kyc_market(&env);
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    for user in [&winner, &loser] {
+        kyc.usdc.mint(user, &500);
+        kyc.registry.set_verified(user, &true);
+    }
+    kyc.market.place_prediction(&winner, &1, &500);
+    kyc.market.place_prediction(&loser, &0, &500);
+
+    kyc.registry.set_verified(&winner, &false);
+    kyc.env.ledger().set_timestamp(2000);
+    kyc.market.close_market(&kyc.market_id);
+    kyc.env.ledger().set_timestamp(3000);
+    kyc.market.resolve_market(&kyc.market_id);
+
+    assert_eq!(kyc.market.claim_winnings(&winner, &kyc.market_id), 1_000);
+}
@@ -0,0 +1,336 @@
+#![cfg(test)]
+
+//! Asserts the indexed event schema published by the market contract.
+
+use boxmeout::market::{
+    MarketStatus, Pools, Position, PredictionMarket, PredictionMarketClient, EVENT_SCHEMA_VERSION,
+    POSITION_OP_BET, POSITION_OP_CLAIM, POSITION_OP_REFUND, REDACTED_AMOUNT,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    token, Address, Bytes, BytesN, Env, IntoVal, Map, Symbol, TryFromVal, Val, Vec,
+};
+
+struct EventsTest<'a> {
+    env: Env,
+    client: PredictionMarketClient<'a>,
+    token: token::StellarAssetClient<'a>,
+    market_id: BytesN<32>,
+    creator: Address,
+}
+
+fn setup<'a>() -> EventsTest<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000);
+
+    let token_admin = Address::generate(&env);
+    let usdc = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let token = token::StellarAssetClient::new(&env, &usdc);
+
+    let client = PredictionMarketClient::new(&env, &env.register(PredictionMarket, ()));
+    let market_id = BytesN::from_array(&env, &[1u8; 32]);
+    let creator = Address::generate(&env);
+    client.initialize(
+        &market_id,
+        &creator,
+        &Address::generate(&env),
+        &usdc,
+        &Address::generate(&env),
+        &2000,
+        &3000,
+        &0,
+        &None,
+    );
+
+    EventsTest {
+        env,
+        client,
+        token,
+        market_id,
+        creator,
+    }
+}
+
+/// Find the single market event from the last invocation whose first topic is `name`
+fn market_event(t: &EventsTest, name: &str) -> (Vec<Val>, Val) {
+    let mut found = None;
+    for (contract, topics, data) in t.env.events().all().iter() {
+        if contract != t.client.address {
+            continue;
+        }
+        let first = Symbol::try_from_val(&t.env, &topics.get(0).unwrap()).ok();
+        if first == Some(Symbol::new(&t.env, name)) {
+            assert!(found.is_none(), "duplicate {} event", name);
+            found = Some((topics, data));
+        }
+    }
+    found.unwrap_or_else(|| panic!("no {} event", name))
+}
+
+#[test]
+fn test_event_schema_version() {
+    let t = setup();
+    assert_eq!(t.client.get_event_version(), EVENT_SCHEMA_VERSION);
+    assert_eq!(EVENT_SCHEMA_VERSION, 3);
+}
+
+#[test]
+fn test_prediction_event() {
+    let t = setup();
+    let user = Address::generate(&t.env);
+    t.token.mint(&user, &500);
+    t.client.place_prediction(&user, &1, &500);
+
+    let (topics, data) = market_event(&t, "prediction");
+    let expected: Vec<Val> =
+        (Symbol::new(&t.env, "prediction"), t.market_id.clone(), user).into_val(&t.env);
+    assert_eq!(topics, expected);
+    assert_eq!(
+        <(u32, i128, u64)>::try_from_val(&t.env, &data).unwrap(),
+        (1, 500, 1000)
+    );
+}
+
+#[test]
+fn test_state_event() {
+    let t = setup();
+    t.env.ledger().set_timestamp(2000);
+    t.client.close_market(&t.market_id);
+
+    let (topics, data) = market_event(&t, "state");
+    let expected: Vec<Val> = (Symbol::new(&t.env, "state"), t.market_id.clone()).into_val(&t.env);
+    assert_eq!(topics, expected);
+    assert_eq!(
+        <(MarketStatus, MarketStatus)>::try_from_val(&t.env, &data).unwrap(),
+        (MarketStatus::Open, MarketStatus::Locked)
+    );
+}
+
+#[test]
+fn test_resolve_event() {
+    let t = setup();
+    t.env.ledger().set_timestamp(2000);
+    t.client.close_market(&t.market_id);
+    t.env.ledger().set_timestamp(3000);
+    t.client.resolve_market(&t.market_id);
+
+    let (topics, data) = market_event(&t, "resolve");
+    let expected: Vec<Val> = (Symbol::new(&t.env, "resolve"), t.market_id.clone()).into_val(&t.env);
+    assert_eq!(topics, expected);
+    assert_eq!(
+        <(u32, u64)>::try_from_val(&t.env, &data).unwrap(),
+        (1, 3000)
+    );
+
+    // The transition to Resolved is published alongside
+    let (_, data) = market_event(&t, "state");
+    assert_eq!(
+        <(MarketStatus, MarketStatus)>::try_from_val(&t.env, &data).unwrap(),
+        (MarketStatus::Locked, MarketStatus::Resolved)
+    );
+}
+
+#[test]
+fn test_claim_event() {
+    let t = setup();
+    let winner = Address::generate(&t.env);
+    let loser = Address::generate(&t.env);
+    t.token.mint(&winner, &500);
+    t.token.mint(&loser, &500);
+    t.client.place_prediction(&winner, &1, &500);
+    t.client.place_prediction(&loser, &0, &500);
+
+    t.env.ledger().set_timestamp(2000);
+    t.client.close_market(&t.market_id);
+    t.env.ledger().set_timestamp(3000);
+    t.client.resolve_market(&t.market_id);
+    let payout = t.client.claim_winnings(&winner, &t.market_id);
+
+    let (topics, data) = market_event(&t, "claim");
+    let expected: Vec<Val> =
+        (Symbol::new(&t.env, "claim"), t.market_id.clone(), winner).into_val(&t.env);
+    assert_eq!(topics, expected);
+    assert_eq!(
+        <(i128, i128)>::try_from_val(&t.env, &data).unwrap(),
+        (payout, 0)
+    );
+    assert_eq!(payout, 1000);
+}
+
+type PositionData = (u64, u32, u32, i128, Position, Pools);
+/// Replayed per-user state: (last seq, summed deltas, last position)
+type ReplayedPosition = (u64, i128, Position);
+
+/// Append every position event from the last invocation to `log`
+fn collect_positions(t: &EventsTest, log: &mut std::vec::Vec<(Address, PositionData)>) {
+    for (contract, topics, data) in t.env.events().all().iter() {
+        let first = Symbol::try_from_val(&t.env, &topics.get(0).unwrap()).ok();
+        if contract != t.client.address || first != Some(Symbol::new(&t.env, "position")) {
+            continue;
+        }
+        assert_eq!(
+            BytesN::<32>::try_from_val(&t.env, &topics.get(1).unwrap()).unwrap(),
+            t.market_id
+        );
+        let user = Address::try_from_val(&t.env, &topics.get(2).unwrap()).unwrap();
+        log.push((user, PositionData::try_from_val(&t.env, &data).unwrap()));
+    }
+}
+
+/// Rebuild per-user positions and market pools from the event log alone
+fn replay(
+    t: &EventsTest,
+    log: &[(Address, PositionData)],
+) -> (Map<Address, ReplayedPosition>, Option<Pools>) {
+    let mut positions: Map<Address, ReplayedPosition> = Map::new(&t.env);
+    let mut pools = None;
+    for (user, (seq, _op, _outcome, delta, position, after)) in log.iter() {
+        let (last_seq, stake, _) = positions.get(user.clone()).unwrap_or((
+            0,
+            0,
+            Position {
+                yes_amount: 0,
+                no_amount: 0,
+                claimed: false,
+            },
+        ));
+        assert_eq!(*seq, last_seq + 1, "sequence gap");
+        // Deltas and balance-after fields must agree
+        assert_eq!(stake + delta, position.yes_amount + position.no_amount);
+        positions.set(user.clone(), (*seq, stake + delta, position.clone()));
+        pools = Some(after.clone());
+    }
+    (positions, pools)
+}
+
+fn assert_replay_matches(t: &EventsTest, log: &[(Address, PositionData)], users: &[&Address]) {
+    let (positions, pools) = replay(t, log);
+    assert_eq!(pools.unwrap(), t.client.get_pools());
+    for user in users {
+        let (seq, _, position) = positions.get((*user).clone()).unwrap();
+        assert_eq!(position, t.client.get_position(user));
+        assert_eq!(seq, t.client.get_position_seq(user));
+    }
+}
+
+#[test]
+fn test_position_events_replay_bets_and_claim() {
+    let t = setup();
+    let alice = Address::generate(&t.env);
+    let bob = Address::generate(&t.env);
+    let carol = Address::generate(&t.env);
+    for user in [&alice, &bob, &carol] {
+        t.token.mint(user, &1000);
+    }
+    let mut log = std::vec::Vec::new();
+
+    t.client.place_prediction(&alice, &1, &300);
+    collect_positions(&t, &mut log);
+    t.client.place_prediction(&bob, &0, &200);
+    collect_positions(&t, &mut log);
+    t.client.place_prediction(&alice, &1, &100);
+    collect_positions(&t, &mut log);
+
+    // Commit is private; only the reveal mutates the visible position
+    let salt = BytesN::from_array(&t.env, &[7u8; 32]);
+    let mut preimage = Bytes::new(&t.env);
+    preimage.extend_from_array(&t.market_id.to_array());
+    preimage.extend_from_array(&0u32.to_be_bytes());
+    preimage.extend_from_array(&salt.to_array());
+    let commit_hash = BytesN::from_array(&t.env, &t.env.crypto().sha256(&preimage).to_array());
+    t.client.commit_prediction(&carol, &commit_hash, &250);
+    collect_positions(&t, &mut log);
+    assert_eq!(log.len(), 3);
+    t.client
+        .reveal_prediction(&carol, &t.market_id, &0, &250, &salt);
+    collect_positions(&t, &mut log);
+
+    t.env.ledger().set_timestamp(2000);
+    t.client.close_market(&t.market_id);
+    t.env.ledger().set_timestamp(3000);
+    t.client.resolve_market(&t.market_id);
+    t.client.claim_winnings(&alice, &t.market_id);
+    collect_positions(&t, &mut log);
+
+    let ops: std::vec::Vec<u32> = log.iter().map(|(_, d)| d.1).collect();
+    assert_eq!(
+        ops,
+        [
+            POSITION_OP_BET,
+            POSITION_OP_BET,
+            POSITION_OP_BET,
+            POSITION_OP_BET,
+            POSITION_OP_CLAIM
+        ]
+    );
+    assert_replay_matches(&t, &log, &[&alice, &bob, &carol]);
+    assert!(t.client.get_position(&alice).claimed);
+    assert_eq!(t.client.get_position_seq(&alice), 3);
+}
+
+#[test]
+fn test_position_events_replay_refunds() {
+    let t = setup();
+    let alice = Address::generate(&t.env);
+    let bob = Address::generate(&t.env);
+    t.token.mint(&alice, &1000);
+    t.token.mint(&bob, &1000);
+    let mut log = std::vec::Vec::new();
+
+    t.client.place_prediction(&alice, &1, &400);
+    collect_positions(&t, &mut log);
+    t.client.place_prediction(&bob, &0, &150);
+    collect_positions(&t, &mut log);
+
+    t.client.cancel_market(&t.creator, &t.market_id);
+    t.client.claim_refund(&alice, &t.market_id);
+    collect_positions(&t, &mut log);
+    t.client.claim_refund(&bob, &t.market_id);
+    collect_positions(&t, &mut log);
+
+    let (_, (_, op, outcome, delta, position, _)) = log.last().unwrap().clone();
+    assert_eq!((op, outcome, delta), (POSITION_OP_REFUND, 0, -150));
+    assert_eq!(position.yes_amount + position.no_amount, 0);
+    assert_replay_matches(&t, &log, &[&alice, &bob]);
+}
+
+#[test]
+fn test_position_events_redact_pools_until_reveal() {
+    let t = setup();
+    // Cutoff is at 2000, so the split is public from 1500
+    t.client.set_pool_reveal(&t.creator, &Some(500));
+    let alice = Address::generate(&t.env);
+    let bob = Address::generate(&t.env);
+    t.token.mint(&alice, &1000);
+    t.token.mint(&bob, &1000);
+
+    let mut log = std::vec::Vec::new();
+    t.client.place_prediction(&alice, &1, &300);
+    collect_positions(&t, &mut log);
+    let (_, (_, _, _, _, position, pools)) = log.pop().unwrap();
+    assert_eq!(position.yes_amount, 300);
+    assert_eq!(
+        pools,
+        Pools {
+            yes_total: REDACTED_AMOUNT,
+            no_total: REDACTED_AMOUNT,
+            participant_count: 1
+        }
+    );
+
+    t.env.ledger().set_timestamp(1500);
+    t.client.place_prediction(&bob, &0, &200);
+    collect_positions(&t, &mut log);
+    let (_, (_, _, _, _, _, pools)) = log.pop().unwrap();
+    assert_eq!(
+        pools,
+        Pools {
+            yes_total: 300,
+            no_total: 200,
+            participant_count: 2
+        }
+    );
+}
@@ -1,9 +1,18 @@
 #![cfg(test)]
 
-use boxmeout::market::{MarketError, PredictionMarketClient};
+use boxmeout::market::{
+    AntiSnipeConfig, BetRecord, ClaimPreview, MarketError, MarketMetadata, MarketStatus, Pools,
+    Position, PredictionMarketClient, ResolutionOutcome, CLAIM_STATUS_ACCOUNT_FROZEN,
+    CLAIM_STATUS_ALREADY_CLAIMED, CLAIM_STATUS_LOSING_POSITION, CLAIM_STATUS_LOSS_REFUND,
+    CLAIM_STATUS_NOT_RESOLVED, CLAIM_STATUS_NO_POSITION, CLAIM_STATUS_OK,
+    DEFAULT_MAX_RESOLUTION_EXTENSION, DEFAULT_ORACLE_TIMEOUT, PREDICTION_OUTCOME_NONE,
+    REDACTED_AMOUNT,
+};
+use boxmeout::testutils::{deploy_flaky_token, FlakyTokenClient};
 use soroban_sdk::{
-    testutils::{Address as _, Ledger, LedgerInfo},
-    token, Address, BytesN, Env, Symbol,
+    contract, contractimpl,
+    testutils::{Address as _, Ledger, LedgerInfo, MockAuth, MockAuthInvoke},
+    token, Address, BytesN, Env, IntoVal, Symbol, Vec,
 };
 
 // ...rest of the file...
@@ -80,6 +89,8 @@ fn setup_test_market(
         &oracle,
         &closing_time,
         &resolution_time,
+        &0,
+        &None,
     );
 
     (
@@ -125,6 +136,8 @@ fn setup_market_for_claims(
         &oracle,
         &closing_time,
         &resolution_time,
+        &0,
+        &None,
     );
 
     (client, market_id, token_client, market_contract)
@@ -365,14 +378,14 @@ fn test_claim_winnings_happy_path() {
     // Claim winnings
     let payout = client.claim_winnings(&user, &market_id);
 
-    // Expect 900 (1000 - 10% fee = 900)
-    assert_eq!(payout, 900);
+    // No platform fee configured: winner receives the full 1000
+    assert_eq!(payout, 1000);
 
     // Verify transfer happened
-    assert_eq!(token_client.balance(&user), 900);
+    assert_eq!(token_client.balance(&user), 1000);
 
     // Verify contract balance decreased
-    assert_eq!(token_client.balance(&market_contract), 100); // Fee remains
+    assert_eq!(token_client.balance(&market_contract), 0);
 }
 
 #[test]
@@ -426,7 +439,7 @@ fn test_cannot_double_claim() {
 
     // First claim succeeds
     let payout = client.claim_winnings(&user, &market_id);
-    assert_eq!(payout, 900);
+    assert_eq!(payout, 1000);
 
     // Second claim should panic with "Winnings already claimed"
     client.claim_winnings(&user, &market_id);
@@ -442,15 +455,15 @@ fn test_correct_payout_calculation_with_losers() {
     // Total pool: 1000 (winners) + 500 (losers) = 1500
     // User has 500 of 1000 winner shares (50%)
     // Gross payout = (500 / 1000) * 1500 = 750
-    // Net payout (after 10% fee) = 750 - 75 = 675
+    // No platform fee configured, so net payout = 750
     token_client.mint(&market_contract, &1500);
 
     client.test_setup_resolution(&market_id, &1u32, &1000, &500);
     client.test_set_prediction(&user, &1u32, &500);
 
     let payout = client.claim_winnings(&user, &market_id);
-    assert_eq!(payout, 675);
-    assert_eq!(token_client.balance(&user), 675);
+    assert_eq!(payout, 750);
+    assert_eq!(token_client.balance(&user), 750);
 }
 
 #[test]
@@ -469,17 +482,17 @@ fn test_multiple_winners_correct_proportional_payout() {
     client.test_set_prediction(&user1, &1u32, &600);
     client.test_set_prediction(&user2, &1u32, &400);
 
-    // User1: (600 / 1000) * 2000 = 1200, minus 10% = 1080
+    // User1: (600 / 1000) * 2000 = 1200
     let payout1 = client.claim_winnings(&user1, &market_id);
-    assert_eq!(payout1, 1080);
+    assert_eq!(payout1, 1200);
 
-    // User2: (400 / 1000) * 2000 = 800, minus 10% = 720
+    // User2: (400 / 1000) * 2000 = 800
     let payout2 = client.claim_winnings(&user2, &market_id);
-    assert_eq!(payout2, 720);
+    assert_eq!(payout2, 800);
 
     // Verify balances
-    assert_eq!(token_client.balance(&user1), 1080);
-    assert_eq!(token_client.balance(&user2), 720);
+    assert_eq!(token_client.balance(&user1), 1200);
+    assert_eq!(token_client.balance(&user2), 800);
 }
 
 #[test]
@@ -496,7 +509,7 @@ fn test_winner_no_outcome_also_works() {
     client.test_set_prediction(&user, &0u32, &1000); // User voted NO
 
     let payout = client.claim_winnings(&user, &market_id);
-    assert_eq!(payout, 900); // 1000 - 10% fee
+    assert_eq!(payout, 1000);
 }
 
 #[test]
@@ -555,7 +568,7 @@ fn test_small_payout_amounts() {
     client.test_set_prediction(&user, &1u32, &100);
 
     let payout = client.claim_winnings(&user, &market_id);
-    assert_eq!(payout, 90); // 100 - 10% fee = 90
+    assert_eq!(payout, 100);
 }
 
 #[test]
@@ -573,7 +586,7 @@ fn test_large_payout_amounts() {
     client.test_set_prediction(&user, &1u32, &large_amount);
 
     let payout = client.claim_winnings(&user, &market_id);
-    assert_eq!(payout, 900_000_000); // 1B - 10% = 900M
+    assert_eq!(payout, 1_000_000_000);
 }
 
 #[test]
@@ -585,15 +598,143 @@ fn test_uneven_split_payout() {
 
     // User has 333 of 1000 winner shares with 1500 total pool
     // (333 / 1000) * 1500 = 499 (integer division)
-    // 499 - 10% = 449 (approximately)
     token_client.mint(&market_contract, &1500);
 
     client.test_setup_resolution(&market_id, &1u32, &1000, &500);
     client.test_set_prediction(&user, &1u32, &333);
 
     let payout = client.claim_winnings(&user, &market_id);
-    // (333 * 1500) / 1000 = 499, fee = 49, net = 450
-    assert_eq!(payout, 450);
+    assert_eq!(payout, 499);
+}
+
+// ============================================================================
+// FAILED TRANSFER TESTS
+// ============================================================================
+
+/// Helper: market whose collateral token can be made to fail, with its creator
+fn setup_market_with_flaky_token(
+    env: &Env,
+) -> (
+    PredictionMarketClient<'_>,
+    BytesN<32>,
+    FlakyTokenClient<'static>,
+    Address,
+) {
+    env.mock_all_auths();
+    let client = PredictionMarketClient::new(env, &register_market(env));
+    let market_id = BytesN::from_array(env, &[1u8; 32]);
+    let creator = Address::generate(env);
+    let token = deploy_flaky_token(env);
+    let closing_time = env.ledger().timestamp() + 86400;
+
+    client.initialize(
+        &market_id,
+        &creator,
+        &Address::generate(env),
+        &token.address,
+        &Address::generate(env),
+        &closing_time,
+        &(closing_time + 3600),
+        &0,
+        &None,
+    );
+    (client, market_id, token, creator)
+}
+
+#[test]
+fn test_failed_payout_leaves_winnings_claimable() {
+    let env = create_test_env();
+    let (client, market_id, token, _creator) = setup_market_with_flaky_token(&env);
+    let user = Address::generate(&env);
+    token.mint(&client.address, &1000);
+    client.test_setup_resolution(&market_id, &1u32, &1000, &0);
+    client.test_set_prediction(&user, &1u32, &1000);
+
+    token.set_failing(&true);
+    assert!(client.try_claim_winnings(&user, &market_id).is_err());
+
+    // Nothing was marked claimed or paid out
+    let preview = client.simulate_claim(&user);
+    assert_eq!(preview.status, CLAIM_STATUS_OK);
+    assert_eq!(preview.net, 1000);
+    assert!(!client.test_get_prediction(&user).unwrap().claimed);
+    assert_eq!(token.balance(&client.address), 1000);
+
+    token.set_failing(&false);
+    assert_eq!(client.claim_winnings(&user, &market_id), 1000);
+    assert_eq!(token.balance(&user), 1000);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+fn test_failed_refund_leaves_refund_claimable() {
+    let env = create_test_env();
+    let (client, market_id, token, creator) = setup_market_with_flaky_token(&env);
+    let user = Address::generate(&env);
+    token.mint(&client.address, &400);
+    client.test_set_prediction(&user, &0u32, &400);
+    client.cancel_market(&creator, &market_id);
+
+    token.set_failing(&true);
+    assert!(client.try_claim_refund(&user, &market_id).is_err());
+    assert_eq!(client.test_get_prediction(&user).unwrap().amount, 400);
+    assert_eq!(token.balance(&client.address), 400);
+
+    token.set_failing(&false);
+    client.claim_refund(&user, &market_id);
+    assert_eq!(token.balance(&user), 400);
+    assert!(client.try_claim_refund(&user, &market_id).is_err());
+}
+
+/// Token whose transfer calls back into the market's claim_winnings for the recipient
+#[contract]
+struct ReentrantToken;
+
+#[contractimpl]
+impl ReentrantToken {
+    pub fn set_market(env: Env, market: Address, market_id: BytesN<32>) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "market"), &(market, market_id));
+    }
+
+    pub fn transfer(env: Env, _from: Address, to: Address, _amount: i128) {
+        let (market, market_id): (Address, BytesN<32>) = env
+            .storage()
+            .instance()
+            .get(&Symbol::new(&env, "market"))
+            .unwrap();
+        PredictionMarketClient::new(&env, &market).claim_winnings(&to, &market_id);
+    }
+}
+
+#[test]
+fn test_reentrant_token_cannot_claim_twice() {
+    let env = create_test_env();
+    env.mock_all_auths();
+    let client = PredictionMarketClient::new(&env, &register_market(&env));
+    let market_id = BytesN::from_array(&env, &[1u8; 32]);
+    let token = env.register(ReentrantToken, ());
+    ReentrantTokenClient::new(&env, &token).set_market(&client.address, &market_id);
+    let closing_time = env.ledger().timestamp() + 86400;
+    client.initialize(
+        &market_id,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &token,
+        &Address::generate(&env),
+        &closing_time,
+        &(closing_time + 3600),
+        &0,
+        &None,
+    );
+    let user = Address::generate(&env);
+    client.test_setup_resolution(&market_id, &1u32, &1000, &0);
+    client.test_set_prediction(&user, &1u32, &1000);
+
+    assert!(client.try_claim_winnings(&user, &market_id).is_err());
+    assert!(!client.test_get_prediction(&user).unwrap().claimed);
+    assert_eq!(client.simulate_claim(&user).status, CLAIM_STATUS_OK);
 }
 
 // ============================================================================
@@ -638,13 +779,13 @@ fn test_all_winners_no_losers() {
     client.test_set_prediction(&user1, &1u32, &600);
     client.test_set_prediction(&user2, &1u32, &400);
 
-    // User1: (600 / 1000) * 1000 = 600, minus 10% = 540
+    // User1: (600 / 1000) * 1000 = 600
     let payout1 = client.claim_winnings(&user1, &market_id);
-    assert_eq!(payout1, 540);
+    assert_eq!(payout1, 600);
 
-    // User2: (400 / 1000) * 1000 = 400, minus 10% = 360
+    // User2: (400 / 1000) * 1000 = 400
     let payout2 = client.claim_winnings(&user2, &market_id);
-    assert_eq!(payout2, 360);
+    assert_eq!(payout2, 400);
 }
 
 #[test]
@@ -660,9 +801,9 @@ fn test_single_winner_gets_all() {
     client.test_setup_resolution(&market_id, &1u32, &200, &800);
     client.test_set_prediction(&winner, &1u32, &200);
 
-    // Winner: (200 / 200) * 1000 = 1000, minus 10% = 900
+    // Winner: (200 / 200) * 1000 = 1000
     let payout = client.claim_winnings(&winner, &market_id);
-    assert_eq!(payout, 900);
+    assert_eq!(payout, 1000);
 }
 
 // ============================================================================
@@ -1041,3 +1182,2407 @@ fn test_claim_refund_revealed_prediction_exact_amount() {
     client.claim_refund(&user, &market_id);
     assert_eq!(token.balance(&user), 750);
 }
+
+// ============================================================================
+// METADATA TESTS
+// ============================================================================
+
+fn sample_metadata(env: &Env, creator: &Address, oracle: &Address) -> MarketMetadata {
+    MarketMetadata {
+        question_hash: BytesN::from_array(env, &[5u8; 32]),
+        category: Symbol::new(env, "Boxing"),
+        creator: creator.clone(),
+        created_at: env.ledger().timestamp(),
+        resolution_time: env.ledger().timestamp() + 86400 + 3600,
+        outcome_count: 2,
+        oracle: oracle.clone(),
+    }
+}
+
+/// Initialize a market with metadata, letting the test alter it first
+fn setup_metadata_market(
+    env: &Env,
+    alter: impl FnOnce(&mut MarketMetadata),
+) -> (PredictionMarketClient<'_>, Address, Address, Address) {
+    let client = PredictionMarketClient::new(env, &register_market(env));
+    let creator = Address::generate(env);
+    let factory = Address::generate(env);
+    let oracle = Address::generate(env);
+    let (_token, usdc_address) = create_usdc_token(env, &Address::generate(env));
+
+    let closing_time = env.ledger().timestamp() + 86400;
+    let resolution_time = closing_time + 3600;
+    let mut metadata = sample_metadata(env, &creator, &oracle);
+    alter(&mut metadata);
+
+    env.mock_all_auths();
+    client.initialize(
+        &BytesN::from_array(env, &[1u8; 32]),
+        &creator,
+        &factory,
+        &usdc_address,
+        &oracle,
+        &closing_time,
+        &resolution_time,
+        &0,
+        &Some(metadata),
+    );
+    (client, creator, factory, usdc_address)
+}
+
+#[test]
+fn test_initialize_stores_metadata() {
+    let env = create_test_env();
+    let (client, creator, _factory, _usdc_address) = setup_metadata_market(&env, |_| {});
+
+    let metadata = client.get_metadata().unwrap();
+    assert_eq!(metadata.creator, creator);
+    assert_eq!(metadata.category, Symbol::new(&env, "Boxing"));
+}
+
+#[test]
+fn test_initialize_without_metadata() {
+    let env = create_test_env();
+    let (client, _market_id, _creator, _admin, _usdc_address, _market_contract) =
+        setup_test_market(&env);
+
+    assert_eq!(client.get_metadata(), None);
+}
+
+#[test]
+#[should_panic(expected = "Category must not be empty")]
+fn test_initialize_metadata_rejects_empty_category() {
+    let env = create_test_env();
+    setup_metadata_market(&env, |metadata| {
+        metadata.category = Symbol::new(&env, "");
+    });
+}
+
+#[test]
+#[should_panic(expected = "Metadata resolution time mismatch")]
+fn test_initialize_metadata_rejects_resolution_time_mismatch() {
+    let env = create_test_env();
+    setup_metadata_market(&env, |metadata| metadata.resolution_time += 1);
+}
+
+#[test]
+#[should_panic(expected = "Metadata oracle mismatch")]
+fn test_initialize_metadata_rejects_oracle_mismatch() {
+    let env = create_test_env();
+    setup_metadata_market(&env, |metadata| {
+        metadata.oracle = Address::generate(&env);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Metadata creator mismatch")]
+fn test_initialize_metadata_rejects_creator_mismatch() {
+    let env = create_test_env();
+    setup_metadata_market(&env, |metadata| {
+        metadata.creator = Address::generate(&env);
+    });
+}
+
+#[test]
+fn test_update_metadata_hash_before_first_prediction() {
+    let env = create_test_env();
+    let (client, _creator, _factory, _usdc_address) = setup_metadata_market(&env, |_| {});
+
+    let new_hash = BytesN::from_array(&env, &[6u8; 32]);
+    client.update_metadata_hash(&new_hash);
+    assert_eq!(client.get_metadata().unwrap().question_hash, new_hash);
+}
+
+#[test]
+#[should_panic(expected = "Metadata locked after first prediction")]
+fn test_update_metadata_hash_rejected_after_first_prediction() {
+    let env = create_test_env();
+    let (client, _creator, _factory, usdc_address) = setup_metadata_market(&env, |_| {});
+
+    let user = Address::generate(&env);
+    let token = token::StellarAssetClient::new(&env, &usdc_address);
+    token.mint(&user, &1000);
+    client.commit_prediction(&user, &BytesN::from_array(&env, &[2u8; 32]), &1000);
+
+    client.update_metadata_hash(&BytesN::from_array(&env, &[6u8; 32]));
+}
+
+#[test]
+fn test_update_metadata_hash_rejects_creator() {
+    let env = create_test_env();
+    let (client, creator, _factory, _usdc_address) = setup_metadata_market(&env, |_| {});
+
+    let new_hash = BytesN::from_array(&env, &[6u8; 32]);
+    env.mock_auths(&[MockAuth {
+        address: &creator,
+        invoke: &MockAuthInvoke {
+            contract: &client.address,
+            fn_name: "update_metadata_hash",
+            args: (new_hash.clone(),).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_update_metadata_hash(&new_hash).is_err());
+}
+
+// ============================================================================
+// BETTING CUTOFF TESTS
+// ============================================================================
+
+#[test]
+fn test_get_betting_cutoff_is_closing_time() {
+    let env = create_test_env();
+    let (client, _market_id, _creator, _admin, _usdc_address, _market_contract) =
+        setup_test_market(&env);
+
+    assert_eq!(
+        client.get_betting_cutoff(),
+        env.ledger().timestamp() + 86400
+    );
+}
+
+#[test]
+fn test_commit_exactly_at_cutoff_rejected() {
+    let env = create_test_env();
+    let (client, _market_id, _creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(&env);
+
+    let user = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_address).mint(&user, &1000);
+
+    // Strictly-before semantics: one second before is fine...
+    let cutoff = client.get_betting_cutoff();
+    env.ledger().set_timestamp(cutoff - 1);
+    let early = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_address).mint(&early, &1000);
+    assert!(client
+        .try_commit_prediction(&early, &BytesN::from_array(&env, &[1u8; 32]), &1000)
+        .is_ok());
+
+    // ...but the cutoff timestamp itself is rejected
+    env.ledger().set_timestamp(cutoff);
+    let result = client.try_commit_prediction(&user, &BytesN::from_array(&env, &[2u8; 32]), &1000);
+    assert_eq!(result, Err(Ok(MarketError::MarketClosed)));
+}
+
+#[test]
+fn test_extend_betting_cutoff_moves_earlier() {
+    let env = create_test_env();
+    let (client, _market_id, _creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(&env);
+
+    let new_cutoff = env.ledger().timestamp() + 100;
+    client.extend_betting_cutoff(&new_cutoff);
+    assert_eq!(client.get_betting_cutoff(), new_cutoff);
+
+    let user = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_address).mint(&user, &1000);
+    env.ledger().set_timestamp(new_cutoff);
+    let result = client.try_commit_prediction(&user, &BytesN::from_array(&env, &[2u8; 32]), &1000);
+    assert_eq!(result, Err(Ok(MarketError::MarketClosed)));
+}
+
+#[test]
+#[should_panic(expected = "Cutoff can only move earlier")]
+fn test_extend_betting_cutoff_cannot_move_later() {
+    let env = create_test_env();
+    let (client, _market_id, _creator, _admin, _usdc_address, _market_contract) =
+        setup_test_market(&env);
+
+    let later = client.get_betting_cutoff() + 1;
+    client.extend_betting_cutoff(&later);
+}
+
+#[test]
+#[should_panic(expected = "Betting cutoff already passed")]
+fn test_extend_betting_cutoff_after_cutoff_rejected() {
+    let env = create_test_env();
+    let (client, _market_id, _creator, _admin, _usdc_address, _market_contract) =
+        setup_test_market(&env);
+
+    let cutoff = client.get_betting_cutoff();
+    env.ledger().set_timestamp(cutoff);
+    client.extend_betting_cutoff(&(cutoff - 10));
+}
+
+#[test]
+fn test_extend_betting_cutoff_rejects_creator() {
+    let env = create_test_env();
+    let (client, _market_id, creator, _admin, _usdc_address, _market_contract) =
+        setup_test_market(&env);
+
+    let cutoff = client.get_betting_cutoff();
+    let new_cutoff = env.ledger().timestamp() + 100;
+    env.mock_auths(&[MockAuth {
+        address: &creator,
+        invoke: &MockAuthInvoke {
+            contract: &client.address,
+            fn_name: "extend_betting_cutoff",
+            args: (new_cutoff,).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_extend_betting_cutoff(&new_cutoff).is_err());
+    assert_eq!(client.get_betting_cutoff(), cutoff);
+}
+
+#[test]
+#[should_panic(expected = "Betting cutoff after resolution time")]
+fn test_initialize_rejects_cutoff_after_resolution() {
+    let env = create_test_env();
+    env.mock_all_auths();
+    let client = PredictionMarketClient::new(&env, &register_market(&env));
+
+    client.initialize(
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &5000,
+        &4000,
+        &0,
+        &None,
+    );
+}
+
+// ============================================================================
+// PLACE PREDICTION / POSITION GETTER TESTS
+// ============================================================================
+
+#[test]
+fn test_position_and_pool_getters_through_lifecycle() {
+    let env = create_test_env();
+    let (client, market_id, _creator, _admin, usdc_address, market_contract) =
+        setup_test_market(&env);
+    let token = token::StellarAssetClient::new(&env, &usdc_address);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let never_bet = Address::generate(&env);
+    token.mint(&alice, &1000);
+    token.mint(&bob, &500);
+
+    // Alice bets YES twice, Bob bets NO once
+    client.place_prediction(&alice, &1, &600);
+    client.place_prediction(&alice, &1, &400);
+    client.place_prediction(&bob, &0, &500);
+
+    assert_eq!(
+        client.get_position(&alice),
+        Position {
+            yes_amount: 1000,
+            no_amount: 0,
+            claimed: false
+        }
+    );
+    assert_eq!(
+        client.get_position(&never_bet),
+        Position {
+            yes_amount: 0,
+            no_amount: 0,
+            claimed: false
+        }
+    );
+    // Alice counted once despite two bets
+    assert_eq!(
+        client.get_pools(),
+        Pools {
+            yes_total: 1000,
+            no_total: 500,
+            participant_count: 2
+        }
+    );
+    assert_eq!(token.balance(&market_contract), 1500);
+
+    // Close and resolve (resolution currently settles YES)
+    let state = client.get_market_state(&market_id);
+    env.ledger().set_timestamp(state.closing_time);
+    client.close_market(&market_id);
+    env.ledger().set_timestamp(state.closing_time + 3600);
+    client.resolve_market(&market_id);
+
+    let payout = client.claim_winnings(&alice, &market_id);
+    assert_eq!(payout, 1500);
+    assert!(client.get_position(&alice).claimed);
+    assert_eq!(client.get_position(&alice).yes_amount, 1000);
+    assert_eq!(client.get_pools().yes_total, 1000);
+}
+
+#[test]
+fn test_place_prediction_rejects_opposite_side() {
+    let env = create_test_env();
+    let (client, _market_id, _creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(&env);
+    let user = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_address).mint(&user, &1000);
+
+    client.place_prediction(&user, &1, &100);
+    let result = client.try_place_prediction(&user, &0, &100);
+    assert_eq!(result, Err(Ok(MarketError::ConflictingOutcome)));
+}
+
+#[test]
+fn test_place_prediction_rejects_invalid_input() {
+    let env = create_test_env();
+    let (client, _market_id, _creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(&env);
+    let user = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_address).mint(&user, &1000);
+
+    assert_eq!(
+        client.try_place_prediction(&user, &2, &100),
+        Err(Ok(MarketError::InvalidOutcome))
+    );
+    assert_eq!(
+        client.try_place_prediction(&user, &1, &0),
+        Err(Ok(MarketError::InvalidAmount))
+    );
+
+    env.ledger().set_timestamp(client.get_betting_cutoff());
+    assert_eq!(
+        client.try_place_prediction(&user, &1, &100),
+        Err(Ok(MarketError::MarketClosed))
+    );
+}
+
+#[test]
+fn test_position_zeroed_after_refund() {
+    let env = create_test_env();
+    let (client, market_id, creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(&env);
+    let user = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_address).mint(&user, &300);
+
+    client.place_prediction(&user, &0, &300);
+    assert_eq!(client.get_position(&user).no_amount, 300);
+
+    client.cancel_market(&creator, &market_id);
+    client.claim_refund(&user, &market_id);
+    assert_eq!(client.get_position(&user).no_amount, 0);
+}
+
+// ============================================================================
+// POSITION TRANSFER TESTS
+// ============================================================================
+
+#[test]
+fn test_transfer_position_moves_stake() {
+    let env = create_test_env();
+    let (client, _market_id, _creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_address).mint(&alice, &1000);
+    client.place_prediction(&alice, &1, &1000);
+
+    client.transfer_position(&alice, &bob, &1, &400);
+    assert_eq!(client.get_position(&alice).yes_amount, 600);
+    assert_eq!(client.get_position(&bob).yes_amount, 400);
+    // Ownership moved; pools did not
+    assert_eq!(
+        client.get_pools(),
+        Pools {
+            yes_total: 1000,
+            no_total: 0,
+            participant_count: 2
+        }
+    );
+
+    assert_eq!(
+        client.try_transfer_position(&alice, &bob, &1, &601),
+        Err(Ok(MarketError::InsufficientPosition))
+    );
+    assert_eq!(
+        client.try_transfer_position(&alice, &bob, &0, &100),
+        Err(Ok(MarketError::InsufficientPosition))
+    );
+
+    // Moving the rest empties the sender
+    client.transfer_position(&alice, &bob, &1, &600);
+    assert_eq!(client.get_position(&alice).yes_amount, 0);
+    assert_eq!(client.get_position(&bob).yes_amount, 1000);
+    assert_eq!(
+        client.try_transfer_position(&alice, &bob, &1, &1),
+        Err(Ok(MarketError::NoPrediction))
+    );
+}
+
+#[test]
+fn test_transfer_position_rejects_conflicting_recipient() {
+    let env = create_test_env();
+    let (client, _market_id, _creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(&env);
+    let token = token::StellarAssetClient::new(&env, &usdc_address);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token.mint(&alice, &500);
+    token.mint(&bob, &500);
+    client.place_prediction(&alice, &1, &500);
+    client.place_prediction(&bob, &0, &500);
+
+    assert_eq!(
+        client.try_transfer_position(&alice, &bob, &1, &100),
+        Err(Ok(MarketError::ConflictingOutcome))
+    );
+}
+
+#[test]
+fn test_operator_transfer_requires_approval() {
+    let env = create_test_env();
+    let (client, _market_id, _creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let amm = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_address).mint(&alice, &1000);
+    client.place_prediction(&alice, &0, &1000);
+
+    assert!(!client.is_operator(&alice, &amm));
+    assert_eq!(
+        client.try_transfer_position_from(&amm, &alice, &bob, &0, &300),
+        Err(Ok(MarketError::NotOperator))
+    );
+
+    client.approve_operator(&alice, &amm, &true);
+    assert!(client.is_operator(&alice, &amm));
+    client.transfer_position_from(&amm, &alice, &bob, &0, &300);
+    assert_eq!(client.get_position(&alice).no_amount, 700);
+    assert_eq!(client.get_position(&bob).no_amount, 300);
+
+    client.approve_operator(&alice, &amm, &false);
+    assert_eq!(
+        client.try_transfer_position_from(&amm, &alice, &bob, &0, &300),
+        Err(Ok(MarketError::NotOperator))
+    );
+}
+
+#[test]
+fn test_transfer_position_blocked_after_resolution() {
+    let env = create_test_env();
+    let (client, market_id, _creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_address).mint(&alice, &1000);
+    client.place_prediction(&alice, &1, &1000);
+
+    let state = client.get_market_state(&market_id);
+    env.ledger().set_timestamp(state.closing_time);
+    client.close_market(&market_id);
+    // Still transferable while awaiting resolution
+    client.transfer_position(&alice, &bob, &1, &100);
+
+    env.ledger().set_timestamp(state.closing_time + 3600);
+    client.resolve_market(&market_id);
+    assert_eq!(
+        client.try_transfer_position(&alice, &bob, &1, &100),
+        Err(Ok(MarketError::InvalidMarketState))
+    );
+}
+
+#[test]
+fn test_transfer_position_cannot_replay_claim_across_dispute_flag() {
+    let env = create_test_env();
+    let (client, market_id, _creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(&env);
+    let token = token::StellarAssetClient::new(&env, &usdc_address);
+    let (alice, bob, carol, dave) = (
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    );
+    for (user, outcome) in [(&alice, 1u32), (&bob, 1u32), (&carol, 0u32)] {
+        token.mint(user, &500);
+        client.place_prediction(user, &outcome, &500);
+    }
+    close_and_resolve(&env, &client, &market_id);
+    assert_eq!(client.claim_winnings(&alice, &market_id), 750);
+
+    // The dispute flag must not reopen transfers of the claimed position
+    client.set_dispute_flag(&market_id, &true);
+    assert_eq!(client.get_state(), MarketStatus::Disputed);
+    assert_eq!(
+        client.try_transfer_position(&alice, &dave, &1, &500),
+        Err(Ok(MarketError::InvalidMarketState))
+    );
+
+    client.set_dispute_flag(&market_id, &false);
+    assert_eq!(client.get_state(), MarketStatus::Resolved);
+    assert!(client.try_claim_winnings(&dave, &market_id).is_err());
+    assert_eq!(client.claim_winnings(&bob, &market_id), 750);
+}
+
+#[test]
+fn test_transfer_position_keeps_participants_unique() {
+    let env = create_test_env();
+    let (client, _market_id, _creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(&env);
+    let token = token::StellarAssetClient::new(&env, &usdc_address);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    token.mint(&alice, &1000);
+
+    // Alice leaves fully, then comes back by betting and by receiving
+    client.place_prediction(&alice, &1, &500);
+    client.transfer_position(&alice, &bob, &1, &500);
+    client.place_prediction(&alice, &1, &500);
+    client.transfer_position(&bob, &carol, &1, &500);
+    client.transfer_position(&alice, &bob, &1, &200);
+    client.transfer_position(&carol, &alice, &1, &500);
+
+    assert_eq!(client.get_pools().participant_count, 3);
+    assert_eq!(
+        client
+            .get_paginated_predictions(&BytesN::from_array(&env, &[1u8; 32]), &0, &10)
+            .total,
+        3
+    );
+}
+
+// ============================================================================
+// POSITION REBUILD TESTS
+// ============================================================================
+
+/// Helper: two YES bets by one user at distinct timestamps, returning the true history
+fn place_two_bets(
+    env: &Env,
+    client: &PredictionMarketClient,
+    usdc_address: &Address,
+    user: &Address,
+) -> soroban_sdk::Vec<BetRecord> {
+    token::StellarAssetClient::new(env, usdc_address).mint(user, &1000);
+
+    let mut history = soroban_sdk::Vec::new(env);
+    client.place_prediction(user, &1, &600);
+    history.push_back(BetRecord {
+        outcome: 1,
+        amount: 600,
+        timestamp: env.ledger().timestamp(),
+    });
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 60);
+    client.place_prediction(user, &1, &400);
+    history.push_back(BetRecord {
+        outcome: 1,
+        amount: 400,
+        timestamp: env.ledger().timestamp(),
+    });
+    history
+}
+
+#[test]
+fn test_rebuild_position_restores_corrupted_entry() {
+    let env = create_test_env();
+    let (client, market_id, _creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(&env);
+    let user = Address::generate(&env);
+    let history = place_two_bets(&env, &client, &usdc_address, &user);
+
+    // Corrupt the stored position
+    client.test_set_prediction(&user, &1, &5);
+    assert_eq!(client.get_position(&user).yes_amount, 5);
+
+    client.rebuild_position(&user, &history);
+    assert_eq!(client.get_position(&user).yes_amount, 1000);
+
+    // Claim uses the corrected amount: sole winner takes the 1000 pool
+    client.test_setup_resolution(&market_id, &1u32, &1000, &0);
+    assert_eq!(client.claim_winnings(&user, &market_id), 1000);
+}
+
+#[test]
+#[should_panic(expected = "History does not match stored hash")]
+fn test_rebuild_position_rejects_tampered_history() {
+    let env = create_test_env();
+    let (client, _market_id, _creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(&env);
+    let user = Address::generate(&env);
+    let mut history = place_two_bets(&env, &client, &usdc_address, &user);
+
+    let mut inflated = history.get(1).unwrap();
+    inflated.amount = 4000;
+    history.set(1, inflated);
+
+    client.rebuild_position(&user, &history);
+}
+
+#[test]
+fn test_rebuild_position_rejects_creator() {
+    let env = create_test_env();
+    let (client, _market_id, creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(&env);
+    let user = Address::generate(&env);
+    let history = place_two_bets(&env, &client, &usdc_address, &user);
+    client.test_set_prediction(&user, &1, &5);
+
+    env.mock_auths(&[MockAuth {
+        address: &creator,
+        invoke: &MockAuthInvoke {
+            contract: &client.address,
+            fn_name: "rebuild_position",
+            args: (user.clone(), history.clone()).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_rebuild_position(&user, &history).is_err());
+    assert_eq!(client.get_position(&user).yes_amount, 5);
+}
+
+// ============================================================================
+// PLATFORM FEE TESTS
+// ============================================================================
+
+#[test]
+fn test_platform_fee_on_profit_routed_to_treasury() {
+    let env = create_test_env();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let (token, usdc_address) = create_usdc_token(&env, &admin);
+
+    let factory_address = env.register(boxmeout::factory::MarketFactory, ());
+    let treasury_address = env.register(boxmeout::treasury::Treasury, ());
+    let factory = boxmeout::factory::MarketFactoryClient::new(&env, &factory_address);
+    let treasury = boxmeout::treasury::TreasuryClient::new(&env, &treasury_address);
+    factory.initialize(&admin, &usdc_address, &treasury_address);
+    treasury.initialize(&admin, &usdc_address, &factory_address);
+
+    // 2% default fee, assigned to the market at initialization
+    factory.set_fee_bps(&200);
+
+    let market_contract = register_market(&env);
+    let client = PredictionMarketClient::new(&env, &market_contract);
+    let market_id = BytesN::from_array(&env, &[7u8; 32]);
+    let closing_time = env.ledger().timestamp() + 86400;
+    client.initialize(
+        &market_id,
+        &creator,
+        &factory_address,
+        &usdc_address,
+        &oracle,
+        &closing_time,
+        &(closing_time + 3600),
+        &factory.get_fee_bps(),
+        &None,
+    );
+    assert_eq!(client.get_fee_bps(), 200);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token.mint(&alice, &600);
+    token.mint(&bob, &400);
+    client.place_prediction(&alice, &1, &600);
+    client.place_prediction(&bob, &0, &400);
+
+    env.ledger().set_timestamp(closing_time);
+    client.close_market(&market_id);
+    env.ledger().set_timestamp(closing_time + 3600);
+    client.resolve_market(&market_id);
+
+    // Gross 1000, profit 400, fee = 2% of 400 = 8, net = 992
+    let treasury_before = token.balance(&treasury_address);
+    let payout = client.claim_winnings(&alice, &market_id);
+    assert_eq!(payout, 992);
+    assert_eq!(token.balance(&alice), 992);
+    assert_eq!(token.balance(&treasury_address) - treasury_before, 8);
+    assert_eq!(token.balance(&market_contract), 0);
+}
+
+#[test]
+fn test_platform_fee_rounds_up_by_at_most_one_stroop() {
+    let env = create_test_env();
+    let (client, market_id, _creator, _admin, _usdc_address, _market_contract) =
+        setup_test_market(&env);
+    client.set_fee_bps(&200);
+
+    let user = Address::generate(&env);
+    client.test_setup_resolution(&market_id, &1u32, &1000, &500);
+    client.test_set_prediction(&user, &1u32, &333);
+
+    // Gross 499, profit 166, exact fee 3.32 rounds up to 4
+    assert_eq!(client.get_portfolio(&user).claimable, 495);
+}
+
+#[test]
+fn test_factory_fee_change_does_not_affect_existing_market() {
+    let env = create_test_env();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (_token, usdc_address) = create_usdc_token(&env, &admin);
+    let factory_address = env.register(boxmeout::factory::MarketFactory, ());
+    let factory = boxmeout::factory::MarketFactoryClient::new(&env, &factory_address);
+    factory.initialize(&admin, &usdc_address, &Address::generate(&env));
+    factory.set_fee_bps(&100);
+
+    let client = PredictionMarketClient::new(&env, &register_market(&env));
+    let closing_time = env.ledger().timestamp() + 86400;
+    client.initialize(
+        &BytesN::from_array(&env, &[8u8; 32]),
+        &Address::generate(&env),
+        &factory_address,
+        &usdc_address,
+        &Address::generate(&env),
+        &closing_time,
+        &(closing_time + 3600),
+        &factory.get_fee_bps(),
+        &None,
+    );
+
+    factory.set_fee_bps(&300);
+    assert_eq!(factory.get_fee_bps(), 300);
+    assert_eq!(client.get_fee_bps(), 100);
+}
+
+#[test]
+#[should_panic(expected = "Fee exceeds maximum")]
+fn test_set_fee_bps_rejects_above_cap() {
+    let env = create_test_env();
+    let (client, _market_id, _creator, _admin, _usdc_address, _market_contract) =
+        setup_test_market(&env);
+
+    client.set_fee_bps(&501);
+}
+
+#[test]
+#[should_panic(expected = "Fee locked after first prediction")]
+fn test_set_fee_bps_locked_after_first_prediction() {
+    let env = create_test_env();
+    let (client, _market_id, _creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(&env);
+    let user = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_address).mint(&user, &100);
+    client.place_prediction(&user, &1, &100);
+
+    client.set_fee_bps(&100);
+}
+
+#[test]
+fn test_set_fee_bps_rejects_creator() {
+    let env = create_test_env();
+    let (client, _market_id, creator, _admin, _usdc_address, _market_contract) =
+        setup_test_market(&env);
+
+    env.mock_auths(&[MockAuth {
+        address: &creator,
+        invoke: &MockAuthInvoke {
+            contract: &client.address,
+            fn_name: "set_fee_bps",
+            args: (0u32,).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_set_fee_bps(&0).is_err());
+}
+
+// ============================================================================
+// STATE MACHINE TESTS
+// ============================================================================
+
+#[test]
+fn test_state_machine_full_lifecycle() {
+    let env = create_test_env();
+    let uninitialized = PredictionMarketClient::new(&env, &register_market(&env));
+    assert_eq!(uninitialized.get_state(), MarketStatus::Created);
+
+    let (client, market_id, _creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(&env);
+    assert_eq!(client.get_state(), MarketStatus::Open);
+
+    let user = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_address).mint(&user, &500);
+    client.place_prediction(&user, &1, &500);
+
+    let closing_time = client.get_betting_cutoff();
+    env.ledger().set_timestamp(closing_time);
+    client.close_market(&market_id);
+    assert_eq!(client.get_state(), MarketStatus::Locked);
+
+    // Predictions only in Open
+    let late = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_address).mint(&late, &100);
+    assert_eq!(
+        client.try_place_prediction(&late, &1, &100),
+        Err(Ok(MarketError::InvalidMarketState))
+    );
+
+    env.ledger().set_timestamp(closing_time + 3600);
+    client.resolve_market(&market_id);
+    assert_eq!(client.get_state(), MarketStatus::Resolved);
+
+    // No challenge reported by the (mock) oracle: state is unchanged
+    assert_eq!(client.sync_dispute_state(), MarketStatus::Resolved);
+    assert_eq!(client.claim_winnings(&user, &market_id), 500);
+}
+
+#[test]
+fn test_resolve_cancelled_market_is_invalid_transition() {
+    let env = create_test_env();
+    let (client, market_id, creator, _admin, _usdc_address, _market_contract) =
+        setup_test_market(&env);
+
+    client.cancel_market(&creator, &market_id);
+    assert_eq!(client.get_state(), MarketStatus::Cancelled);
+
+    let resolution_time = client.get_betting_cutoff() + 3600;
+    env.ledger().set_timestamp(resolution_time);
+    assert_eq!(
+        client.try_resolve_market(&market_id),
+        Err(Ok(MarketError::InvalidStateTransition.into()))
+    );
+}
+
+/// Helper: resolved market (via close + resolve) that has then been disputed
+fn disputed_market(env: &Env) -> (PredictionMarketClient<'_>, BytesN<32>, Address) {
+    let (client, market_id, creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(env);
+    let closing_time = client.get_betting_cutoff();
+    env.ledger().set_timestamp(closing_time);
+    client.close_market(&market_id);
+    env.ledger().set_timestamp(closing_time + 3600);
+    client.resolve_market(&market_id);
+
+    let disputer = Address::generate(env);
+    token::StellarAssetClient::new(env, &usdc_address).mint(&disputer, &1000);
+    client.dispute_market(&disputer, &market_id, &Symbol::new(env, "wrong"), &None);
+    assert_eq!(client.get_state(), MarketStatus::Disputed);
+
+    (client, market_id, creator)
+}
+
+#[test]
+fn test_cancel_disputed_market_is_invalid_transition() {
+    let env = create_test_env();
+    let (client, market_id, creator) = disputed_market(&env);
+
+    assert_eq!(
+        client.try_cancel_market(&creator, &market_id),
+        Err(Ok(MarketError::InvalidStateTransition.into()))
+    );
+}
+
+#[test]
+fn test_resolve_disputed_market_via_resolve_market_rejected() {
+    let env = create_test_env();
+    let (client, market_id, _creator) = disputed_market(&env);
+
+    assert_eq!(
+        client.try_resolve_market(&market_id),
+        Err(Ok(MarketError::InvalidStateTransition.into()))
+    );
+}
+
+#[test]
+fn test_oracle_challenge_moves_resolved_market_to_disputed() {
+    let env = create_test_env();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let (token, usdc_address) = create_usdc_token(&env, &admin);
+
+    let oracle_id = env.register(boxmeout::oracle::OracleManager, ());
+    let oracle = boxmeout::oracle::OracleManagerClient::new(&env, &oracle_id);
+    oracle.initialize(&admin, &1);
+    let attestor = Address::generate(&env);
+    oracle.register_oracle(&attestor, &Symbol::new(&env, "attestor"), &Vec::new(&env));
+
+    let market_id = BytesN::from_array(&env, &[3u8; 32]);
+    let closing_time = env.ledger().timestamp() + 86400;
+    let resolution_time = closing_time + 3600;
+    oracle.register_market(
+        &market_id,
+        &resolution_time,
+        &Symbol::new(&env, boxmeout::oracle::CATEGORY_ANY),
+    );
+
+    let client = PredictionMarketClient::new(&env, &register_market(&env));
+    client.initialize(
+        &market_id,
+        &creator,
+        &Address::generate(&env),
+        &usdc_address,
+        &oracle_id,
+        &closing_time,
+        &resolution_time,
+        &0,
+        &None,
+    );
+    let user = Address::generate(&env);
+    token.mint(&user, &500);
+    client.place_prediction(&user, &1, &500);
+
+    env.ledger().set_timestamp(closing_time);
+    client.close_market(&market_id);
+    env.ledger().set_timestamp(resolution_time);
+    client.resolve_market(&market_id);
+    oracle.submit_attestation(
+        &attestor,
+        &market_id,
+        &1,
+        &BytesN::from_array(&env, &[0u8; 32]),
+        &None,
+    );
+    oracle.challenge_attestation(
+        &Address::generate(&env),
+        &attestor,
+        &market_id,
+        &Symbol::new(&env, "bad_source"),
+    );
+
+    assert_eq!(client.sync_dispute_state(), MarketStatus::Disputed);
+    assert_eq!(client.get_state(), MarketStatus::Disputed);
+}
+
+#[test]
+#[should_panic(expected = "Market not resolved")]
+fn test_claims_blocked_while_disputed() {
+    let env = create_test_env();
+    let (client, market_id, _creator) = disputed_market(&env);
+
+    client.claim_winnings(&Address::generate(&env), &market_id);
+}
+
+#[test]
+fn test_oracle_dispute_flag_blocks_and_restores_claims() {
+    let env = create_test_env();
+    let (client, market_id, _creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(&env);
+    let user = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_address).mint(&user, &500);
+    client.place_prediction(&user, &1, &500);
+
+    // Raised before resolution: the market lands in Disputed when it resolves
+    client.set_dispute_flag(&market_id, &true);
+    assert!(client.is_dispute_flagged());
+    assert_eq!(client.get_state(), MarketStatus::Open);
+    let closing_time = client.get_betting_cutoff();
+    env.ledger().set_timestamp(closing_time);
+    client.close_market(&market_id);
+    env.ledger().set_timestamp(closing_time + 3600);
+    client.resolve_market(&market_id);
+    assert_eq!(client.get_state(), MarketStatus::Disputed);
+    assert!(client.try_claim_winnings(&user, &market_id).is_err());
+
+    client.set_dispute_flag(&market_id, &false);
+    assert!(!client.is_dispute_flagged());
+    assert_eq!(client.get_state(), MarketStatus::Resolved);
+    assert_eq!(client.claim_winnings(&user, &market_id), 500);
+}
+
+#[test]
+fn test_clearing_dispute_flag_keeps_user_dispute() {
+    let env = create_test_env();
+    let (client, market_id, _creator) = disputed_market(&env);
+
+    client.set_dispute_flag(&market_id, &true);
+    client.set_dispute_flag(&market_id, &false);
+    assert_eq!(client.get_state(), MarketStatus::Disputed);
+}
+
+// ============================================================================
+// BET LIMIT TESTS
+// ============================================================================
+
+#[test]
+fn test_bet_limits_exactly_at_limit_allowed() {
+    let env = create_test_env();
+    let (client, _market_id, creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(&env);
+    client.set_bet_limits(&creator, &100, &1000);
+    assert_eq!(client.get_min_bet(), 100);
+    assert_eq!(client.get_max_position_per_user(), 1000);
+
+    let user = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_address).mint(&user, &1000);
+
+    // Exactly min_bet, then top up to exactly the maximum
+    client.place_prediction(&user, &1, &100);
+    client.place_prediction(&user, &1, &900);
+    assert_eq!(client.get_position(&user).yes_amount, 1000);
+}
+
+#[test]
+fn test_bet_limits_one_over_rejected() {
+    let env = create_test_env();
+    let (client, _market_id, creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(&env);
+    client.set_bet_limits(&creator, &100, &1000);
+
+    let user = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_address).mint(&user, &2000);
+
+    assert_eq!(
+        client.try_place_prediction(&user, &1, &99),
+        Err(Ok(MarketError::BetBelowMinimum))
+    );
+    assert_eq!(
+        client.try_place_prediction(&user, &1, &1001),
+        Err(Ok(MarketError::PositionLimitExceeded))
+    );
+
+    // Cumulative position counts toward the cap
+    client.place_prediction(&user, &1, &600);
+    assert_eq!(
+        client.try_place_prediction(&user, &1, &401),
+        Err(Ok(MarketError::PositionLimitExceeded))
+    );
+    assert_eq!(
+        client.try_commit_prediction(
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[1u8; 32]),
+            &50
+        ),
+        Err(Ok(MarketError::BetBelowMinimum))
+    );
+}
+
+#[test]
+fn test_zero_max_position_means_no_limit() {
+    let env = create_test_env();
+    let (client, _market_id, creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(&env);
+    client.set_bet_limits(&creator, &0, &0);
+
+    let user = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_address).mint(&user, &1_000_000_000);
+    client.place_prediction(&user, &0, &1_000_000_000);
+    assert_eq!(client.get_position(&user).no_amount, 1_000_000_000);
+}
+
+#[test]
+#[should_panic(expected = "Bet limits locked after first prediction")]
+fn test_set_bet_limits_locked_after_first_prediction() {
+    let env = create_test_env();
+    let (client, _market_id, creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(&env);
+    let user = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_address).mint(&user, &100);
+    client.place_prediction(&user, &1, &100);
+
+    client.set_bet_limits(&creator, &10, &500);
+}
+
+// ============================================================================
+// ANTI-SNIPING TESTS
+// ============================================================================
+
+/// Helper: market with anti-sniping enabled and a 1000 YES base pool placed early
+fn setup_anti_snipe_market(
+    env: &Env,
+    snipe_extension: u64,
+    max_total_extension: u64,
+) -> (
+    PredictionMarketClient<'_>,
+    token::StellarAssetClient<'_>,
+    u64,
+) {
+    let (client, _market_id, creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(env);
+    client.set_anti_sniping(
+        &creator,
+        &AntiSnipeConfig {
+            snipe_threshold_bps: 2000, // 20% of the pool
+            snipe_window: 600,
+            snipe_extension,
+            max_total_extension,
+        },
+    );
+    let token = token::StellarAssetClient::new(env, &usdc_address);
+
+    // Large but early: outside the window, no extension
+    let early = Address::generate(env);
+    token.mint(&early, &1000);
+    client.place_prediction(&early, &1, &1000);
+
+    let cutoff = client.get_betting_cutoff();
+    assert_eq!(client.get_snipe_extension_used(), 0);
+    (client, token, cutoff)
+}
+
+#[test]
+fn test_anti_snipe_large_late_bet_extends_cutoff() {
+    let env = create_test_env();
+    let (client, token, cutoff) = setup_anti_snipe_market(&env, 300, 900);
+
+    env.ledger().set_timestamp(cutoff - 100);
+    let sniper = Address::generate(&env);
+    token.mint(&sniper, &500);
+    client.place_prediction(&sniper, &0, &500);
+
+    assert_eq!(client.get_betting_cutoff(), cutoff + 300);
+    assert_eq!(client.get_snipe_extension_used(), 300);
+}
+
+#[test]
+fn test_anti_snipe_small_late_bet_does_not_extend() {
+    let env = create_test_env();
+    let (client, token, cutoff) = setup_anti_snipe_market(&env, 300, 900);
+
+    env.ledger().set_timestamp(cutoff - 100);
+    let user = Address::generate(&env);
+    token.mint(&user, &200);
+    // Exactly 20% of the pool is not above the threshold
+    client.place_prediction(&user, &0, &200);
+
+    assert_eq!(client.get_betting_cutoff(), cutoff);
+    assert_eq!(client.get_snipe_extension_used(), 0);
+}
+
+#[test]
+fn test_anti_snipe_cumulative_extension_is_bounded() {
+    let env = create_test_env();
+    let (client, token, cutoff) = setup_anti_snipe_market(&env, 300, 500);
+
+    for _ in 0..3 {
+        let now = client.get_betting_cutoff() - 10;
+        env.ledger().set_timestamp(now);
+        let sniper = Address::generate(&env);
+        token.mint(&sniper, &100_000);
+        client.place_prediction(&sniper, &0, &100_000);
+    }
+
+    // 300 + 200 (capped), then nothing more
+    assert_eq!(client.get_snipe_extension_used(), 500);
+    assert_eq!(client.get_betting_cutoff(), cutoff + 500);
+}
+
+#[test]
+fn test_anti_snipe_never_extends_past_resolution_time() {
+    let env = create_test_env();
+    let (client, token, cutoff) = setup_anti_snipe_market(&env, 5000, 10_000);
+    let resolution_time = cutoff + 3600;
+
+    env.ledger().set_timestamp(cutoff - 1);
+    let sniper = Address::generate(&env);
+    token.mint(&sniper, &5000);
+    client.place_prediction(&sniper, &0, &5000);
+
+    assert_eq!(client.get_betting_cutoff(), resolution_time);
+    assert_eq!(client.get_snipe_extension_used(), 3600);
+}
+
+fn setup_extendable_market(
+    env: &Env,
+) -> (
+    PredictionMarketClient<'_>,
+    boxmeout::oracle::OracleManagerClient<'_>,
+    BytesN<32>,
+    Address,
+    u64,
+) {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let creator = Address::generate(env);
+    let (_token, usdc_address) = create_usdc_token(env, &admin);
+
+    let oracle_id = env.register(boxmeout::oracle::OracleManager, ());
+    let oracle = boxmeout::oracle::OracleManagerClient::new(env, &oracle_id);
+    oracle.initialize(&admin, &1);
+
+    let market_id = BytesN::from_array(env, &[4u8; 32]);
+    let closing_time = env.ledger().timestamp() + 86400;
+    let resolution_time = closing_time + 3600;
+    oracle.register_market(
+        &market_id,
+        &resolution_time,
+        &Symbol::new(env, boxmeout::oracle::CATEGORY_ANY),
+    );
+
+    let market_contract = register_market(env);
+    let client = PredictionMarketClient::new(env, &market_contract);
+    client.initialize(
+        &market_id,
+        &creator,
+        &Address::generate(env),
+        &usdc_address,
+        &oracle_id,
+        &closing_time,
+        &resolution_time,
+        &0,
+        &None,
+    );
+    oracle.set_market_contract(&market_id, &market_contract);
+
+    (client, oracle, market_id, creator, resolution_time)
+}
+
+#[test]
+fn test_extend_resolution_time_syncs_oracle() {
+    let env = create_test_env();
+    let (client, oracle, market_id, _creator, resolution_time) = setup_extendable_market(&env);
+
+    let new_time = resolution_time + 7 * 86400;
+    client.extend_resolution_time(&new_time);
+
+    assert_eq!(client.get_resolution_time(), new_time);
+    assert_eq!(
+        oracle.get_market_resolution_time(&market_id),
+        Some(new_time)
+    );
+}
+
+#[test]
+fn test_oracle_timeout_follows_resolution_time() {
+    let env = create_test_env();
+    let (client, _oracle, _market_id, creator, resolution_time) = setup_extendable_market(&env);
+    assert_eq!(
+        client.get_oracle_timeout(),
+        resolution_time + DEFAULT_ORACLE_TIMEOUT
+    );
+
+    client.set_oracle_timeout(&creator, &86400);
+    client.extend_resolution_time(&(resolution_time + 3600));
+    assert_eq!(client.get_oracle_timeout(), resolution_time + 3600 + 86400);
+}
+
+#[test]
+fn test_extend_resolution_time_cap() {
+    let env = create_test_env();
+    let (client, _oracle, _market_id, _creator, resolution_time) = setup_extendable_market(&env);
+
+    // Exactly at the default cap is allowed, even across several extensions
+    client.extend_resolution_time(&(resolution_time + 86400));
+    client.extend_resolution_time(&(resolution_time + DEFAULT_MAX_RESOLUTION_EXTENSION));
+
+    let result = client
+        .try_extend_resolution_time(&(resolution_time + DEFAULT_MAX_RESOLUTION_EXTENSION + 1));
+    assert!(result.is_err());
+}
+
+#[test]
+#[should_panic(expected = "Extension exceeds maximum")]
+fn test_extend_resolution_time_custom_cap() {
+    let env = create_test_env();
+    let (client, _oracle, _market_id, creator, resolution_time) = setup_extendable_market(&env);
+
+    client.set_max_resolution_extension(&creator, &3600);
+    assert_eq!(client.get_max_resolution_extension(), 3600);
+
+    client.extend_resolution_time(&(resolution_time + 3601));
+}
+
+#[test]
+#[should_panic(expected = "Attestation already exists")]
+fn test_extend_resolution_time_rejected_after_attestation() {
+    let env = create_test_env();
+    let (client, oracle, market_id, _creator, resolution_time) = setup_extendable_market(&env);
+
+    let attestor = Address::generate(&env);
+    oracle.register_oracle(&attestor, &Symbol::new(&env, "attestor"), &Vec::new(&env));
+    env.ledger().set_timestamp(resolution_time);
+    oracle.submit_attestation(
+        &attestor,
+        &market_id,
+        &1,
+        &BytesN::from_array(&env, &[0u8; 32]),
+        &None,
+    );
+
+    client.extend_resolution_time(&(resolution_time + 86400));
+}
+
+#[test]
+fn test_extend_resolution_time_rejects_creator() {
+    let env = create_test_env();
+    let (client, _oracle, _market_id, creator, resolution_time) = setup_extendable_market(&env);
+
+    let new_time = resolution_time + 86400;
+    env.mock_auths(&[MockAuth {
+        address: &creator,
+        invoke: &MockAuthInvoke {
+            contract: &client.address,
+            fn_name: "extend_resolution_time",
+            args: (new_time,).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_extend_resolution_time(&new_time).is_err());
+    assert_eq!(client.get_resolution_time(), resolution_time);
+}
+
+#[test]
+fn test_extend_resolution_time_unbounded_cap_does_not_overflow() {
+    let env = create_test_env();
+    let (client, _oracle, _market_id, creator, resolution_time) = setup_extendable_market(&env);
+
+    client.set_max_resolution_extension(&creator, &u64::MAX);
+    let new_time = resolution_time + 400 * 86400;
+    client.extend_resolution_time(&new_time);
+    assert_eq!(client.get_resolution_time(), new_time);
+}
+
+fn setup_voidable_market(
+    env: &Env,
+) -> (
+    PredictionMarketClient<'_>,
+    boxmeout::oracle::OracleManagerClient<'_>,
+    token::StellarAssetClient<'_>,
+    BytesN<32>,
+    Address,
+    Address,
+) {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let creator = Address::generate(env);
+    let (token, usdc_address) = create_usdc_token(env, &admin);
+
+    let oracle_id = env.register(boxmeout::oracle::OracleManager, ());
+    let oracle = boxmeout::oracle::OracleManagerClient::new(env, &oracle_id);
+    oracle.initialize(&admin, &1);
+    oracle.add_admin_signer(&Address::generate(env));
+
+    let market_id = BytesN::from_array(env, &[5u8; 32]);
+    let closing_time = env.ledger().timestamp() + 86400;
+    let resolution_time = closing_time + 3600;
+    oracle.register_market(
+        &market_id,
+        &resolution_time,
+        &Symbol::new(env, boxmeout::oracle::CATEGORY_ANY),
+    );
+
+    let market_contract = register_market(env);
+    let client = PredictionMarketClient::new(env, &market_contract);
+    client.initialize(
+        &market_id,
+        &creator,
+        &Address::generate(env),
+        &usdc_address,
+        &oracle_id,
+        &closing_time,
+        &resolution_time,
+        &0,
+        &None,
+    );
+
+    (client, oracle, token, market_id, market_contract, creator)
+}
+
+#[test]
+fn test_invalid_resolution_refunds_both_sides() {
+    let env = create_test_env();
+    let (client, oracle, token, market_id, market_contract, _creator) = setup_voidable_market(&env);
+    client.set_fee_bps(&200);
+
+    let yes_user = Address::generate(&env);
+    let no_user = Address::generate(&env);
+    token.mint(&yes_user, &700);
+    token.mint(&no_user, &300);
+    client.place_prediction(&yes_user, &1, &700);
+    client.place_prediction(&no_user, &0, &300);
+
+    env.ledger().set_timestamp(client.get_betting_cutoff());
+    client.close_market(&market_id);
+    env.ledger().set_timestamp(client.get_resolution_time());
+
+    oracle.mark_unresolvable(
+        &oracle.get_admin_signers(),
+        &market_id,
+        &BytesN::from_array(&env, &[9u8; 32]),
+        &market_contract,
+    );
+    assert_eq!(client.get_state(), MarketStatus::Refunding);
+    assert_eq!(
+        oracle.get_consensus_result(&market_id),
+        boxmeout::oracle::OUTCOME_INVALID
+    );
+
+    assert_eq!(client.get_portfolio(&yes_user).claimable, 700);
+    assert_eq!(client.get_portfolio(&no_user).claimable, 300);
+
+    let balance = token::TokenClient::new(&env, &token.address);
+    client.claim_refund(&yes_user, &market_id);
+    client.claim_refund(&no_user, &market_id);
+    assert_eq!(client.get_portfolio(&yes_user).claimable, 0);
+    assert_eq!(balance.balance(&yes_user), 700);
+    assert_eq!(balance.balance(&no_user), 300);
+    assert_eq!(balance.balance(&market_contract), 0);
+}
+
+#[test]
+#[should_panic(expected = "Market not resolved")]
+fn test_invalid_resolution_blocks_claim_winnings() {
+    let env = create_test_env();
+    let (client, _oracle, token, market_id, _market_contract, _creator) =
+        setup_voidable_market(&env);
+
+    let user = Address::generate(&env);
+    token.mint(&user, &500);
+    client.place_prediction(&user, &1, &500);
+
+    env.ledger().set_timestamp(client.get_betting_cutoff());
+    client.close_market(&market_id);
+    env.ledger().set_timestamp(client.get_resolution_time());
+    client.resolve_with_outcome(&market_id, &ResolutionOutcome::Invalid);
+
+    client.claim_winnings(&user, &market_id);
+}
+
+#[test]
+fn test_resolve_with_binary_outcome_from_oracle() {
+    let env = create_test_env();
+    let (client, _oracle, token, market_id, _market_contract, _creator) =
+        setup_voidable_market(&env);
+
+    let user = Address::generate(&env);
+    token.mint(&user, &500);
+    client.place_prediction(&user, &0, &500);
+
+    env.ledger().set_timestamp(client.get_betting_cutoff());
+    client.close_market(&market_id);
+    env.ledger().set_timestamp(client.get_resolution_time());
+    client.resolve_with_outcome(&market_id, &ResolutionOutcome::No);
+
+    assert_eq!(client.get_state(), MarketStatus::Resolved);
+    assert_eq!(client.test_get_winning_outcome(), Some(0));
+}
+
+#[test]
+#[should_panic]
+fn test_resolve_with_outcome_requires_oracle_auth() {
+    let env = create_test_env();
+    let (client, _oracle, _token, market_id, _market_contract, _creator) =
+        setup_voidable_market(&env);
+
+    env.ledger().set_timestamp(client.get_betting_cutoff());
+    client.close_market(&market_id);
+    env.ledger().set_timestamp(client.get_resolution_time());
+
+    env.set_auths(&[]);
+    client.resolve_with_outcome(&market_id, &ResolutionOutcome::Invalid);
+}
+
+// ============================================================================
+// CLAIM SIMULATION TESTS
+// ============================================================================
+
+/// Market wired to a real factory and treasury so fees are actually transferred
+fn setup_fee_market(
+    env: &Env,
+    fee_bps: u32,
+) -> (
+    PredictionMarketClient<'_>,
+    BytesN<32>,
+    token::StellarAssetClient<'_>,
+    Address,
+) {
+    let (client, market_id, token, treasury, _creator) =
+        setup_fee_market_with_creator(env, fee_bps);
+    (client, market_id, token, treasury)
+}
+
+fn setup_fee_market_with_creator(
+    env: &Env,
+    fee_bps: u32,
+) -> (
+    PredictionMarketClient<'_>,
+    BytesN<32>,
+    token::StellarAssetClient<'_>,
+    Address,
+    Address,
+) {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let (token, usdc_address) = create_usdc_token(env, &admin);
+    let factory_address = env.register(boxmeout::factory::MarketFactory, ());
+    let treasury_address = env.register(boxmeout::treasury::Treasury, ());
+    boxmeout::factory::MarketFactoryClient::new(env, &factory_address).initialize(
+        &admin,
+        &usdc_address,
+        &treasury_address,
+    );
+    boxmeout::treasury::TreasuryClient::new(env, &treasury_address).initialize(
+        &admin,
+        &usdc_address,
+        &factory_address,
+    );
+
+    let creator = Address::generate(env);
+    let client = PredictionMarketClient::new(env, &register_market(env));
+    let market_id = BytesN::from_array(env, &[8u8; 32]);
+    let closing_time = env.ledger().timestamp() + 86400;
+    client.initialize(
+        &market_id,
+        &creator,
+        &factory_address,
+        &usdc_address,
+        &Address::generate(env),
+        &closing_time,
+        &(closing_time + 3600),
+        &fee_bps,
+        &None,
+    );
+
+    (client, market_id, token, treasury_address, creator)
+}
+
+fn bet(
+    env: &Env,
+    client: &PredictionMarketClient,
+    token: &token::StellarAssetClient,
+    outcome: u32,
+    amount: i128,
+) -> Address {
+    let user = Address::generate(env);
+    token.mint(&user, &amount);
+    client.place_prediction(&user, &outcome, &amount);
+    user
+}
+
+fn close_and_resolve(env: &Env, client: &PredictionMarketClient, market_id: &BytesN<32>) {
+    env.ledger().set_timestamp(client.get_betting_cutoff());
+    client.close_market(market_id);
+    env.ledger().set_timestamp(client.get_resolution_time());
+    client.resolve_market(market_id);
+}
+
+/// Execute a claim right after previewing it and check both paths agree exactly
+fn assert_claim_matches_preview(
+    env: &Env,
+    client: &PredictionMarketClient,
+    market_id: &BytesN<32>,
+    token: &token::StellarAssetClient,
+    treasury: &Address,
+    user: &Address,
+) -> ClaimPreview {
+    let balances = token::TokenClient::new(env, &token.address);
+    let preview = client.simulate_claim(user);
+    assert_eq!(client.simulate_claim(user), preview);
+    assert_eq!(preview.status, CLAIM_STATUS_OK);
+
+    let user_before = balances.balance(user);
+    let treasury_before = balances.balance(treasury);
+    let paid = client.claim_winnings(user, market_id);
+
+    assert_eq!(paid, preview.net);
+    assert_eq!(balances.balance(user) - user_before, preview.net);
+    assert_eq!(
+        balances.balance(treasury) - treasury_before,
+        preview.protocol_fee
+    );
+    assert_eq!(
+        preview.gross + preview.guarantee_topup,
+        preview.net + preview.protocol_fee
+    );
+    preview
+}
+
+#[test]
+fn test_simulate_claim_matches_claim_with_fee() {
+    let env = create_test_env();
+    let (client, market_id, token, treasury) = setup_fee_market(&env, 200);
+    let alice = bet(&env, &client, &token, 1, 600);
+    let carol = bet(&env, &client, &token, 1, 333);
+    bet(&env, &client, &token, 0, 400);
+    close_and_resolve(&env, &client, &market_id);
+
+    // Gross 600 * 1333 / 933 = 857, profit 257, fee ceil(5.14) = 6
+    let preview =
+        assert_claim_matches_preview(&env, &client, &market_id, &token, &treasury, &alice);
+    assert_eq!(preview.gross, 857);
+    assert_eq!(preview.protocol_fee, 6);
+    assert_eq!(preview.rounding_dust, 1);
+
+    // Gross 333 * 1333 / 933 = 475, profit 142, fee ceil(2.84) = 3
+    let preview =
+        assert_claim_matches_preview(&env, &client, &market_id, &token, &treasury, &carol);
+    assert_eq!(preview.gross, 475);
+    assert_eq!(preview.protocol_fee, 3);
+}
+
+#[test]
+fn test_simulate_claim_matches_claim_without_fee() {
+    let env = create_test_env();
+    let (client, market_id, token, treasury) = setup_fee_market(&env, 0);
+    let alice = bet(&env, &client, &token, 1, 250);
+    bet(&env, &client, &token, 0, 750);
+    close_and_resolve(&env, &client, &market_id);
+
+    let preview =
+        assert_claim_matches_preview(&env, &client, &market_id, &token, &treasury, &alice);
+    assert_eq!(preview.net, 1000);
+    assert_eq!(preview.protocol_fee, 0);
+    assert_eq!(preview.rounding_dust, 0);
+}
+
+#[test]
+fn test_simulate_claim_reports_unclaimable_reasons() {
+    let env = create_test_env();
+    let (client, market_id, token, treasury) = setup_fee_market(&env, 200);
+    let winner = bet(&env, &client, &token, 1, 500);
+    let loser = bet(&env, &client, &token, 0, 500);
+
+    assert_eq!(
+        client.simulate_claim(&winner).status,
+        CLAIM_STATUS_NOT_RESOLVED
+    );
+    close_and_resolve(&env, &client, &market_id);
+
+    let preview = client.simulate_claim(&loser);
+    assert_eq!(preview.status, CLAIM_STATUS_LOSING_POSITION);
+    assert_eq!(preview.net, 0);
+    assert!(client.try_claim_winnings(&loser, &market_id).is_err());
+
+    assert_eq!(
+        client.simulate_claim(&Address::generate(&env)).status,
+        CLAIM_STATUS_NO_POSITION
+    );
+
+    assert_claim_matches_preview(&env, &client, &market_id, &token, &treasury, &winner);
+    let preview = client.simulate_claim(&winner);
+    assert_eq!(preview.status, CLAIM_STATUS_ALREADY_CLAIMED);
+    assert_eq!(preview.net, 0);
+}
+
+// ===== PAYOUT GUARANTEE TESTS =====
+
+/// Helper: fee-free market whose creator funds a 1.2x guarantee with `escrow`
+fn setup_guaranteed_market(
+    env: &Env,
+    escrow: i128,
+) -> (
+    PredictionMarketClient<'_>,
+    BytesN<32>,
+    token::StellarAssetClient<'_>,
+    Address,
+    Address,
+) {
+    let (client, market_id, token, treasury, creator) = setup_fee_market_with_creator(env, 0);
+    token.mint(&creator, &escrow);
+    let claim_deadline = client.get_resolution_time() + 7 * 86400;
+    client.set_guarantee(&creator, &12_000, &escrow, &claim_deadline);
+    (client, market_id, token, treasury, creator)
+}
+
+#[test]
+fn test_guarantee_tops_up_lopsided_pool() {
+    let env = create_test_env();
+    let (client, market_id, token, treasury, creator) = setup_guaranteed_market(&env, 1000);
+    let alice = bet(&env, &client, &token, 1, 600);
+    let bob = bet(&env, &client, &token, 1, 300);
+    bet(&env, &client, &token, 0, 100);
+    close_and_resolve(&env, &client, &market_id);
+
+    // Pool math pays 666 and 333; the floor is 720 and 360
+    assert_eq!(client.get_portfolio(&alice).claimable, 720);
+    let preview =
+        assert_claim_matches_preview(&env, &client, &market_id, &token, &treasury, &alice);
+    assert_eq!(preview.guarantee_topup, 54);
+    assert_eq!(preview.net, 720);
+    let preview = assert_claim_matches_preview(&env, &client, &market_id, &token, &treasury, &bob);
+    assert_eq!(preview.guarantee_topup, 27);
+    assert_eq!(preview.net, 360);
+    assert_eq!(client.get_guarantee_escrow(), 919);
+
+    // The remainder goes back to the funder after the claim deadline
+    env.ledger()
+        .set_timestamp(client.get_guarantee().unwrap().claim_deadline);
+    assert_eq!(client.release_guarantee(), 919);
+    let balances = token::TokenClient::new(&env, &token.address);
+    assert_eq!(balances.balance(&creator), 919);
+    assert_eq!(client.get_guarantee_escrow(), 0);
+}
+
+#[test]
+fn test_guarantee_untouched_for_balanced_pool() {
+    let env = create_test_env();
+    let (client, market_id, token, treasury, creator) = setup_guaranteed_market(&env, 1000);
+    let alice = bet(&env, &client, &token, 1, 500);
+    bet(&env, &client, &token, 0, 500);
+    close_and_resolve(&env, &client, &market_id);
+
+    let preview =
+        assert_claim_matches_preview(&env, &client, &market_id, &token, &treasury, &alice);
+    assert_eq!(preview.guarantee_topup, 0);
+    assert_eq!(preview.net, 1000);
+    assert_eq!(client.get_guarantee_escrow(), 1000);
+
+    env.ledger()
+        .set_timestamp(client.get_guarantee().unwrap().claim_deadline);
+    client.release_guarantee();
+    assert_eq!(
+        token::TokenClient::new(&env, &token.address).balance(&creator),
+        1000
+    );
+}
+
+#[test]
+fn test_exhausted_guarantee_is_shared_pro_rata() {
+    let env = create_test_env();
+    let (client, market_id, token, treasury, _creator) = setup_guaranteed_market(&env, 50);
+    let alice = bet(&env, &client, &token, 1, 600);
+    let bob = bet(&env, &client, &token, 1, 400);
+    close_and_resolve(&env, &client, &market_id);
+
+    // Shortfalls of 120 and 80 share the 50 escrow: 30 and 20, whoever claims first
+    let preview = assert_claim_matches_preview(&env, &client, &market_id, &token, &treasury, &bob);
+    assert_eq!(preview.guarantee_topup, 20);
+    let preview =
+        assert_claim_matches_preview(&env, &client, &market_id, &token, &treasury, &alice);
+    assert_eq!(preview.guarantee_topup, 30);
+    assert_eq!(client.get_guarantee_escrow(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Claim deadline not reached")]
+fn test_guarantee_release_waits_for_claim_deadline() {
+    let env = create_test_env();
+    let (client, market_id, token, _treasury, _creator) = setup_guaranteed_market(&env, 1000);
+    bet(&env, &client, &token, 1, 500);
+    bet(&env, &client, &token, 0, 500);
+    close_and_resolve(&env, &client, &market_id);
+
+    client.release_guarantee();
+}
+
+#[test]
+#[should_panic(expected = "Guarantee locked after first prediction")]
+fn test_guarantee_locked_after_first_prediction() {
+    let env = create_test_env();
+    let (client, _market_id, token, _treasury, creator) = setup_fee_market_with_creator(&env, 0);
+    bet(&env, &client, &token, 1, 500);
+
+    token.mint(&creator, &1000);
+    let claim_deadline = client.get_resolution_time() + 86400;
+    client.set_guarantee(&creator, &12_000, &1000, &claim_deadline);
+}
+
+// ============================================================================
+// CAPPED-LOSS MARKET TESTS
+// ============================================================================
+
+#[test]
+fn test_capped_loss_claims_conserve_pool() {
+    for loss_cap_bps in [0u32, 2500, 5000, 9999] {
+        for fee_bps in [0u32, 200] {
+            let env = create_test_env();
+            let (client, market_id, token, treasury, creator) =
+                setup_fee_market_with_creator(&env, fee_bps);
+            client.set_loss_cap(&creator, &loss_cap_bps);
+            let winners = [600i128, 333, 1001].map(|amount| bet(&env, &client, &token, 1, amount));
+            let losers =
+                [400i128, 777, 5].map(|amount| (bet(&env, &client, &token, 0, amount), amount));
+            close_and_resolve(&env, &client, &market_id);
+
+            let mut outflow = 0;
+            for winner in winners.iter() {
+                let preview = assert_claim_matches_preview(
+                    &env, &client, &market_id, &token, &treasury, winner,
+                );
+                outflow += preview.net + preview.protocol_fee;
+            }
+            for (loser, stake) in losers.iter() {
+                let preview = client.simulate_claim(loser);
+                if loss_cap_bps == 0 {
+                    assert_eq!(preview.status, CLAIM_STATUS_LOSING_POSITION);
+                    continue;
+                }
+                assert_eq!(preview.status, CLAIM_STATUS_LOSS_REFUND);
+                assert_eq!(preview.net, stake * loss_cap_bps as i128 / 10_000);
+                assert_eq!(client.claim_loss_refund(loser, &market_id), preview.net);
+                outflow += preview.net;
+            }
+
+            // Never more than the pool; at most a stroop of rounding per claim stays behind
+            let pool = 600 + 333 + 1001 + 400 + 777 + 5;
+            let balances = token::TokenClient::new(&env, &token.address);
+            let left = balances.balance(&client.address);
+            assert_eq!(left, pool - outflow);
+            assert!(
+                (0..=6).contains(&left),
+                "{left} left with cap {loss_cap_bps} and fee {fee_bps}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_capped_loss_shrinks_winner_upside() {
+    let env = create_test_env();
+    let (client, market_id, token, _treasury, creator) = setup_fee_market_with_creator(&env, 0);
+    client.set_loss_cap(&creator, &5000);
+    assert_eq!(client.get_loss_cap_bps(), 5000);
+    let winner = bet(&env, &client, &token, 1, 500);
+    let loser = bet(&env, &client, &token, 0, 500);
+    close_and_resolve(&env, &client, &market_id);
+
+    // Half the losing stake comes back, the other half goes to the winner
+    assert_eq!(client.get_portfolio(&loser).claimable, 250);
+    assert_eq!(client.claim_winnings(&winner, &market_id), 750);
+    assert_eq!(client.claim_loss_refund(&loser, &market_id), 250);
+    assert_eq!(
+        client.simulate_claim(&loser).status,
+        CLAIM_STATUS_ALREADY_CLAIMED
+    );
+}
+
+#[test]
+fn test_cancelled_capped_loss_market_refunds_in_full() {
+    let env = create_test_env();
+    let (client, market_id, token, _treasury, creator) = setup_fee_market_with_creator(&env, 0);
+    client.set_loss_cap(&creator, &5000);
+    let user = bet(&env, &client, &token, 0, 800);
+    client.cancel_market(&creator, &market_id);
+
+    client.claim_refund(&user, &market_id);
+    let balances = token::TokenClient::new(&env, &token.address);
+    assert_eq!(balances.balance(&user), 800);
+}
+
+#[test]
+#[should_panic(expected = "Loss cap must leave losers at risk")]
+fn test_loss_cap_rejects_full_refund() {
+    let env = create_test_env();
+    let (client, _market_id, _token, _treasury, creator) = setup_fee_market_with_creator(&env, 0);
+    client.set_loss_cap(&creator, &10_000);
+}
+
+#[test]
+#[should_panic(expected = "Loss cap locked after first prediction")]
+fn test_loss_cap_locked_after_first_prediction() {
+    let env = create_test_env();
+    let (client, _market_id, token, _treasury, creator) = setup_fee_market_with_creator(&env, 0);
+    bet(&env, &client, &token, 1, 100);
+    client.set_loss_cap(&creator, &5000);
+}
+
+#[test]
+#[should_panic(expected = "User did not predict winning outcome")]
+fn test_claim_winnings_rejects_capped_loser() {
+    let env = create_test_env();
+    let (client, market_id, token, _treasury, creator) = setup_fee_market_with_creator(&env, 0);
+    client.set_loss_cap(&creator, &5000);
+    bet(&env, &client, &token, 1, 100);
+    let loser = bet(&env, &client, &token, 0, 100);
+    close_and_resolve(&env, &client, &market_id);
+    client.claim_winnings(&loser, &market_id);
+}
+
+// ============================================================================
+// CLAIM GATE MATRIX
+// ============================================================================
+
+/// Factory stub whose pause, shutdown and sunset switches are set directly
+#[contract]
+struct GateFactory;
+
+#[contractimpl]
+impl GateFactory {
+    pub fn set_flags(env: Env, flags: u32) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "flags"), &flags);
+    }
+
+    pub fn is_market_paused(env: Env, _market_id: BytesN<32>) -> bool {
+        Self::flag(&env, GATE_FACTORY_PAUSED)
+    }
+
+    pub fn is_shut_down(env: Env) -> bool {
+        Self::flag(&env, GATE_FACTORY_SHUT_DOWN)
+    }
+
+    pub fn is_sunset(env: Env) -> bool {
+        Self::flag(&env, GATE_FACTORY_SUNSET)
+    }
+}
+
+impl GateFactory {
+    fn flag(env: &Env, bit: u32) -> bool {
+        let flags: u32 = env
+            .storage()
+            .instance()
+            .get(&Symbol::new(env, "flags"))
+            .unwrap_or(0);
+        flags & bit != 0
+    }
+}
+
+/// KYC registry stub with a per-user switch
+#[contract]
+struct GateKycRegistry;
+
+#[contractimpl]
+impl GateKycRegistry {
+    pub fn set_verified(env: Env, user: Address, verified: bool) {
+        env.storage().instance().set(&user, &verified);
+    }
+
+    pub fn is_verified(env: Env, user: Address) -> bool {
+        env.storage().instance().get(&user).unwrap_or(false)
+    }
+}
+
+const GATE_FACTORY_PAUSED: u32 = 1 << 0;
+const GATE_FACTORY_SHUT_DOWN: u32 = 1 << 1;
+const GATE_FACTORY_SUNSET: u32 = 1 << 2;
+const GATE_KYC_REVOKED: u32 = 1 << 3;
+/// Number of gate combinations; every gate claims ignore must have a bit
+/// above, kept in sync with the list on `claims_guaranteed`
+const GATE_COMBINATIONS: u32 = 1 << 4;
+
+struct GatedMarket<'a> {
+    client: PredictionMarketClient<'a>,
+    market_id: BytesN<32>,
+    creator: Address,
+    token: token::StellarAssetClient<'a>,
+    factory: GateFactoryClient<'a>,
+    registry: GateKycRegistryClient<'a>,
+    winner: Address,
+    loser: Address,
+}
+
+/// Market wired to the gate stubs, with one verified bettor on each side
+fn setup_gated_market(env: &Env) -> GatedMarket<'_> {
+    env.mock_all_auths();
+    let (token, usdc_address) = create_usdc_token(env, &Address::generate(env));
+    let factory = GateFactoryClient::new(env, &env.register(GateFactory, ()));
+    let registry = GateKycRegistryClient::new(env, &env.register(GateKycRegistry, ()));
+
+    let client = PredictionMarketClient::new(env, &register_market(env));
+    let market_id = BytesN::from_array(env, &[9u8; 32]);
+    let creator = Address::generate(env);
+    let closing_time = env.ledger().timestamp() + 86400;
+    client.initialize(
+        &market_id,
+        &creator,
+        &factory.address,
+        &usdc_address,
+        &Address::generate(env),
+        &closing_time,
+        &(closing_time + 3600),
+        &0,
+        &None,
+    );
+    client.set_kyc_registry(&creator, &Some(registry.address.clone()));
+
+    let winner = Address::generate(env);
+    let loser = Address::generate(env);
+    for (user, outcome) in [(&winner, 1u32), (&loser, 0u32)] {
+        registry.set_verified(user, &true);
+        token.mint(user, &500);
+        client.place_prediction(user, &outcome, &500);
+    }
+
+    GatedMarket {
+        client,
+        market_id,
+        creator,
+        token,
+        factory,
+        registry,
+        winner,
+        loser,
+    }
+}
+
+/// Flip every gate selected in `flags`, then move to a fresh ledger so the
+/// cached KYC answer is re-read
+fn engage_gates(env: &Env, market: &GatedMarket, flags: u32) {
+    market.factory.set_flags(&flags);
+    if flags & GATE_KYC_REVOKED != 0 {
+        market.registry.set_verified(&market.winner, &false);
+        market.registry.set_verified(&market.loser, &false);
+    }
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+}
+
+#[test]
+fn test_claim_winnings_ignores_every_unrelated_gate() {
+    for flags in 0..GATE_COMBINATIONS {
+        let env = create_test_env();
+        let market = setup_gated_market(&env);
+        engage_gates(&env, &market, flags);
+        close_and_resolve(&env, &market.client, &market.market_id);
+
+        let paid = market
+            .client
+            .claim_winnings(&market.winner, &market.market_id);
+        assert_eq!(paid, 1000, "claim blocked with gate flags {flags:#06b}");
+        let balances = token::TokenClient::new(&env, &market.token.address);
+        assert_eq!(balances.balance(&market.winner), 1000);
+    }
+}
+
+#[test]
+fn test_claim_refund_ignores_every_unrelated_gate() {
+    for flags in 0..GATE_COMBINATIONS {
+        let env = create_test_env();
+        let market = setup_gated_market(&env);
+        engage_gates(&env, &market, flags);
+        market
+            .client
+            .cancel_market(&market.creator, &market.market_id);
+
+        let balances = token::TokenClient::new(&env, &market.token.address);
+        for user in [&market.winner, &market.loser] {
+            market.client.claim_refund(user, &market.market_id);
+            assert_eq!(
+                balances.balance(user),
+                500,
+                "refund blocked with gate flags {flags:#06b}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_frozen_account_blocks_claims_under_every_gate() {
+    for flags in 0..GATE_COMBINATIONS {
+        let env = create_test_env();
+        let market = setup_gated_market(&env);
+        engage_gates(&env, &market, flags);
+        close_and_resolve(&env, &market.client, &market.market_id);
+        market.client.set_account_frozen(&market.winner, &true);
+
+        let preview = market.client.simulate_claim(&market.winner);
+        assert_eq!(preview.status, CLAIM_STATUS_ACCOUNT_FROZEN);
+        assert_eq!(preview.net, 0);
+        assert_eq!(market.client.get_portfolio(&market.winner).claimable, 0);
+        assert_eq!(
+            market
+                .client
+                .try_claim_winnings(&market.winner, &market.market_id),
+            Err(Ok(MarketError::AccountFrozen.into())),
+            "frozen claim paid with gate flags {flags:#06b}"
+        );
+
+        market.client.set_account_frozen(&market.winner, &false);
+        let paid = market
+            .client
+            .claim_winnings(&market.winner, &market.market_id);
+        assert_eq!(paid, 1000);
+    }
+}
+
+#[test]
+fn test_frozen_account_blocks_refund_only_for_that_user() {
+    let env = create_test_env();
+    let market = setup_gated_market(&env);
+    market
+        .client
+        .cancel_market(&market.creator, &market.market_id);
+    market.client.set_account_frozen(&market.loser, &true);
+    assert!(market.client.is_account_frozen(&market.loser));
+
+    assert_eq!(
+        market
+            .client
+            .try_claim_refund(&market.loser, &market.market_id),
+        Err(Ok(MarketError::AccountFrozen.into()))
+    );
+    market
+        .client
+        .claim_refund(&market.winner, &market.market_id);
+    let balances = token::TokenClient::new(&env, &market.token.address);
+    assert_eq!(balances.balance(&market.winner), 500);
+}
+
+#[test]
+fn test_set_account_frozen_rejects_creator() {
+    let env = create_test_env();
+    let market = setup_gated_market(&env);
+
+    env.mock_auths(&[MockAuth {
+        address: &market.creator,
+        invoke: &MockAuthInvoke {
+            contract: &market.client.address,
+            fn_name: "set_account_frozen",
+            args: (market.winner.clone(), true).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(market
+        .client
+        .try_set_account_frozen(&market.winner, &true)
+        .is_err());
+    assert!(!market.client.is_account_frozen(&market.winner));
+}
+
+// ============================================================================
+// POOL REVEAL TESTS
+// ============================================================================
+
+struct SplitMarket<'a> {
+    client: PredictionMarketClient<'a>,
+    market_id: BytesN<32>,
+    yes_bettor: Address,
+    no_bettor: Address,
+}
+
+/// Helper: YES 3000 / NO 1000, optionally hiding the split until an hour before the cutoff
+fn split_market(env: &Env, hide: bool) -> SplitMarket<'_> {
+    let (client, market_id, creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(env);
+    if hide {
+        client.set_pool_reveal(&creator, &Some(3600));
+    }
+    let token = token::StellarAssetClient::new(env, &usdc_address);
+    let yes_bettor = Address::generate(env);
+    let no_bettor = Address::generate(env);
+    token.mint(&yes_bettor, &3000);
+    token.mint(&no_bettor, &1000);
+    client.place_prediction(&yes_bettor, &1, &3000);
+    client.place_prediction(&no_bettor, &0, &1000);
+    SplitMarket {
+        client,
+        market_id,
+        yes_bettor,
+        no_bettor,
+    }
+}
+
+#[test]
+fn test_pool_split_hidden_until_reveal_time() {
+    let env = create_test_env();
+    let market = split_market(&env, true);
+    let client = &market.client;
+    let reveal_time = client.get_betting_cutoff() - 3600;
+    assert_eq!(client.get_pool_reveal_time(), Some(reveal_time));
+
+    env.ledger().set_timestamp(reveal_time - 1);
+    assert!(!client.pools_revealed());
+    assert_eq!(
+        client.get_pools(),
+        Pools {
+            yes_total: REDACTED_AMOUNT,
+            no_total: REDACTED_AMOUNT,
+            participant_count: 2
+        }
+    );
+    let state = client.get_market_state(&market.market_id);
+    assert!(!state.pools_revealed);
+    assert_eq!(state.total_pool, 4000);
+    assert_eq!(client.get_open_interest(), 4000);
+    assert_eq!(
+        client.get_market_liquidity(&market.market_id),
+        (0, 0, 0, 5000, 5000)
+    );
+    assert_eq!(
+        client
+            .get_portfolio(&market.yes_bettor)
+            .implied_probability_bps,
+        5000
+    );
+    let feed = client.get_paginated_predictions(&market.market_id, &0, &10);
+    assert_eq!(feed.total, 2);
+    assert!(feed
+        .items
+        .iter()
+        .all(|item| item.outcome == PREDICTION_OUTCOME_NONE));
+    // The bettor's own position is not an aggregate
+    assert_eq!(client.get_position(&market.yes_bettor).yes_amount, 3000);
+
+    env.ledger().set_timestamp(reveal_time);
+    assert!(client.pools_revealed());
+    assert_eq!(
+        client.get_pools(),
+        Pools {
+            yes_total: 3000,
+            no_total: 1000,
+            participant_count: 2
+        }
+    );
+    assert!(client.get_market_state(&market.market_id).pools_revealed);
+    assert_eq!(
+        client.get_market_liquidity(&market.market_id),
+        (3000, 1000, 3000 * 1000, 2500, 7500)
+    );
+    assert_eq!(
+        client
+            .get_portfolio(&market.yes_bettor)
+            .implied_probability_bps,
+        7500
+    );
+    assert_eq!(
+        client
+            .get_portfolio(&market.no_bettor)
+            .implied_probability_bps,
+        2500
+    );
+    let feed = client.get_paginated_predictions(&market.market_id, &0, &10);
+    assert_eq!(feed.items.get_unchecked(0).outcome, 1);
+    assert_eq!(feed.items.get_unchecked(1).outcome, 0);
+}
+
+#[test]
+fn test_hidden_split_leaves_resolution_untouched() {
+    let mut payouts = [0i128; 2];
+    for (hide, payout) in [false, true].into_iter().zip(payouts.iter_mut()) {
+        let env = create_test_env();
+        let market = split_market(&env, hide);
+        let cutoff = market.client.get_betting_cutoff();
+        env.ledger().set_timestamp(cutoff);
+        market.client.close_market(&market.market_id);
+        env.ledger().set_timestamp(cutoff + 3600);
+        market.client.resolve_market(&market.market_id);
+
+        assert!(market.client.pools_revealed());
+        *payout = market
+            .client
+            .claim_winnings(&market.yes_bettor, &market.market_id);
+        assert_eq!(
+            market.client.get_pools().yes_total + market.client.get_pools().no_total,
+            4000
+        );
+    }
+    assert_eq!(payouts[0], payouts[1]);
+    assert!(payouts[1] > 3000);
+}
+
+#[test]
+fn test_hidden_split_revealed_when_market_cancelled() {
+    let env = create_test_env();
+    let (client, market_id, creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(&env);
+    client.set_pool_reveal(&creator, &Some(3600));
+    let bettor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_address).mint(&bettor, &100);
+    client.place_prediction(&bettor, &1, &100);
+    assert!(!client.pools_revealed());
+
+    client.cancel_market(&creator, &market_id);
+    assert!(client.pools_revealed());
+    assert_eq!(client.get_pools().yes_total, 100);
+}
+
+#[test]
+#[should_panic(expected = "Pool reveal locked after first prediction")]
+fn test_set_pool_reveal_locked_after_first_prediction() {
+    let env = create_test_env();
+    let (client, _market_id, creator, _admin, usdc_address, _market_contract) =
+        setup_test_market(&env);
+    let bettor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_address).mint(&bettor, &100);
+    client.place_prediction(&bettor, &1, &100);
+
+    client.set_pool_reveal(&creator, &Some(3600));
+}
+
+// ============================================================================
+// CLAIM DEADLINE TESTS
+// ============================================================================
+
+#[test]
+fn test_sweep_unclaimed_respects_claim_deadline() {
+    let env = create_test_env();
+    let (client, market_id, token, treasury) = setup_fee_market(&env, 0);
+    let early = bet(&env, &client, &token, 1, 600);
+    let late = bet(&env, &client, &token, 1, 200);
+    bet(&env, &client, &token, 0, 200);
+    let sweeper = Address::generate(&env);
+
+    assert_eq!(
+        client.try_sweep_unclaimed(&sweeper),
+        Err(Ok(MarketError::MarketNotResolved))
+    );
+    close_and_resolve(&env, &client, &market_id);
+    assert_eq!(client.claim_winnings(&early, &market_id), 750);
+
+    let deadline = client.get_claim_deadline();
+    assert_eq!(
+        deadline,
+        client.get_resolution_time() + boxmeout::market::DEFAULT_CLAIM_WINDOW
+    );
+    env.ledger().set_timestamp(deadline - 1);
+    assert_eq!(
+        client.try_sweep_unclaimed(&sweeper),
+        Err(Ok(MarketError::ClaimDeadlineNotReached))
+    );
+
+    env.ledger().set_timestamp(deadline);
+    let balances = token::TokenClient::new(&env, &token.address);
+    let treasury_before = balances.balance(&treasury);
+    assert_eq!(client.sweep_unclaimed(&sweeper), 250);
+    assert_eq!(client.get_swept_amount(), 250);
+    assert_eq!(balances.balance(&treasury) - treasury_before, 250);
+    assert_eq!(balances.balance(&client.address), 0);
+    assert_eq!(client.get_state(), MarketStatus::Swept);
+    assert_eq!(client.get_winning_outcome(), Some(1));
+
+    assert_eq!(
+        client.try_claim_winnings(&late, &market_id),
+        Err(Ok(MarketError::UnclaimedSwept.into()))
+    );
+    assert_eq!(
+        client.try_sweep_unclaimed(&sweeper),
+        Err(Ok(MarketError::MarketNotResolved))
+    );
+}
+
+#[test]
+fn test_claim_deadline_counts_from_late_resolution() {
+    let env = create_test_env();
+    let (client, market_id, token, _treasury) = setup_fee_market(&env, 0);
+    bet(&env, &client, &token, 1, 600);
+    bet(&env, &client, &token, 0, 200);
+    let sweeper = Address::generate(&env);
+
+    env.ledger().set_timestamp(client.get_betting_cutoff());
+    client.close_market(&market_id);
+    let resolved_at = client.get_resolution_time() + 30 * 86400;
+    env.ledger().set_timestamp(resolved_at);
+    client.resolve_market(&market_id);
+
+    let deadline = client.get_claim_deadline();
+    assert_eq!(
+        deadline,
+        resolved_at + boxmeout::market::DEFAULT_CLAIM_WINDOW
+    );
+    env.ledger().set_timestamp(deadline - 1);
+    assert_eq!(
+        client.try_sweep_unclaimed(&sweeper),
+        Err(Ok(MarketError::ClaimDeadlineNotReached))
+    );
+}
+
+#[test]
+fn test_claim_deadline_restarts_when_dispute_flag_clears() {
+    let env = create_test_env();
+    let (client, market_id, token, _treasury) = setup_fee_market(&env, 0);
+    let winner = bet(&env, &client, &token, 1, 600);
+    bet(&env, &client, &token, 0, 200);
+    let sweeper = Address::generate(&env);
+    close_and_resolve(&env, &client, &market_id);
+
+    // Disputed past the original deadline
+    client.set_dispute_flag(&market_id, &true);
+    let cleared_at = client.get_claim_deadline() + 86400;
+    env.ledger().set_timestamp(cleared_at);
+    client.set_dispute_flag(&market_id, &false);
+
+    assert_eq!(
+        client.get_claim_deadline(),
+        cleared_at + boxmeout::market::DEFAULT_CLAIM_WINDOW
+    );
+    assert_eq!(
+        client.try_sweep_unclaimed(&sweeper),
+        Err(Ok(MarketError::ClaimDeadlineNotReached))
+    );
+    assert_eq!(client.claim_winnings(&winner, &market_id), 800);
+}
+
+#[test]
+fn test_claim_window_configurable_above_minimum() {
+    let env = create_test_env();
+    let (client, _market_id, creator, _admin, _usdc_address, _market_contract) =
+        setup_test_market(&env);
+    let window = boxmeout::market::MIN_CLAIM_WINDOW;
+
+    client.set_claim_window(&creator, &window);
+    assert_eq!(
+        client.get_claim_deadline(),
+        client.get_resolution_time() + window
+    );
+    assert!(client
+        .try_set_claim_window(&creator, &(window - 1))
+        .is_err());
+}
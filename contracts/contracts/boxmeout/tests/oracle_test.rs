@@ -13,12 +13,21 @@ use boxmeout::{OracleManager, OracleManagerClient};
 */
 
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    Address, BytesN, Env, Symbol,
+    contract, contractimpl,
+    testutils::{Address as _, Events, Ledger},
+    token, vec, Address, Bytes, BytesN, Env, IntoVal, Symbol, TryFromVal, Val, Vec,
 };
 
+use boxmeout::helpers::events::ORACLE_EVENTS;
+use boxmeout::helpers::{hashing, rand};
 use boxmeout::market::PredictionMarket;
-use boxmeout::oracle::{OracleManager, OracleManagerClient};
+use boxmeout::oracle::{
+    OracleError, OracleManager, OracleManagerClient, TieBreakMode, CAP_BINARY, CAP_CHALLENGE,
+    CAP_CONSENSUS_READY, CAP_OVERRIDE, CAP_SCALAR, CAP_SUBSCRIBE, CATEGORY_ANY, MAX_METADATA_LEN,
+    MAX_OVERRIDE_PAGE, MAX_RESOLUTION_BATCH, MAX_RESOLUTION_SUBSCRIBERS, OUTCOME_INVALID,
+    SEVERITY_CRITICAL,
+};
+use boxmeout::testutils::{deploy_token, fund, OracleFixture};
 
 fn create_test_env() -> Env {
     Env::default()
@@ -47,20 +56,15 @@ fn test_oracle_initialize() {
 #[test]
 fn test_register_oracle() {
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let client = OracleManagerClient::new(&env, &oracle_id);
-
-    let admin = Address::generate(&env);
-    let required_consensus = 2u32;
-    client.initialize(&admin, &required_consensus);
+    let fixture = OracleFixture::new(&env);
 
     // Register oracle
     let oracle1 = Address::generate(&env);
     let oracle_name = Symbol::new(&env, "Oracle1");
 
-    client.register_oracle(&oracle1, &oracle_name);
+    fixture
+        .client
+        .register_oracle(&oracle1, &oracle_name, &Vec::new(&env));
 
     // TODO: Add getter to verify oracle registered
     // Verify oracle count incremented
@@ -69,22 +73,10 @@ fn test_register_oracle() {
 #[test]
 fn test_register_multiple_oracles() {
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let client = OracleManagerClient::new(&env, &oracle_id);
-
-    let admin = Address::generate(&env);
-    client.initialize(&admin, &2u32);
 
     // Register 3 oracles
-    let oracle1 = Address::generate(&env);
-    let oracle2 = Address::generate(&env);
-    let oracle3 = Address::generate(&env);
-
-    client.register_oracle(&oracle1, &Symbol::new(&env, "Oracle1"));
-    client.register_oracle(&oracle2, &Symbol::new(&env, "Oracle2"));
-    client.register_oracle(&oracle3, &Symbol::new(&env, "Oracle3"));
+    let fixture = OracleFixture::new(&env).with_oracles(3);
+    assert_eq!(fixture.oracles.len(), 3);
 
     // TODO: Verify 3 oracles registered
 }
@@ -93,74 +85,40 @@ fn test_register_multiple_oracles() {
 #[should_panic(expected = "Maximum oracle limit reached")]
 fn test_register_oracle_exceeds_limit() {
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let client = OracleManagerClient::new(&env, &oracle_id);
-
-    let admin = Address::generate(&env);
-    client.initialize(&admin, &2u32);
 
     // Register 11 oracles (limit is 10)
-    for _ in 0..11 {
-        let oracle = Address::generate(&env);
-        let name = Symbol::new(&env, "Oracle");
-        client.register_oracle(&oracle, &name);
-    }
+    OracleFixture::new(&env).with_oracles(11);
 }
 
 #[test]
 #[should_panic(expected = "Oracle already registered")]
 fn test_register_duplicate_oracle() {
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let client = OracleManagerClient::new(&env, &oracle_id);
-
-    let admin = Address::generate(&env);
-    client.initialize(&admin, &2u32);
-
-    let oracle1 = Address::generate(&env);
-    let name = Symbol::new(&env, "Oracle1");
-
-    // Register once
-    client.register_oracle(&oracle1, &name);
+    let fixture = OracleFixture::new(&env).with_oracles(1);
 
     // Try to register same oracle again
-    client.register_oracle(&oracle1, &name);
+    fixture.client.register_oracle(
+        &fixture.oracle(0),
+        &Symbol::new(&env, "Oracle1"),
+        &Vec::new(&env),
+    );
 }
 
 #[test]
 fn test_submit_attestation() {
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let client = OracleManagerClient::new(&env, &oracle_id);
-
-    let admin = Address::generate(&env);
-    client.initialize(&admin, &2u32);
-
-    let oracle1 = Address::generate(&env);
-    client.register_oracle(&oracle1, &Symbol::new(&env, "Oracle1"));
-
-    let market_id = BytesN::from_array(&env, &[1u8; 32]);
-    let resolution_time = 1000u64;
-
-    // Register market with resolution time
-    client.register_market(&market_id, &resolution_time);
+    let fixture = OracleFixture::new(&env)
+        .with_oracles(1)
+        .with_market([1u8; 32], 1000);
+    let market_id = fixture.market(0);
 
     // Set ledger time past resolution time
     env.ledger().set_timestamp(1001);
 
-    let result = 1u32; // YES
-    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
-
-    client.submit_attestation(&oracle1, &market_id, &result, &data_hash);
+    fixture.attest(&fixture.oracle(0), &market_id, 1); // YES
 
     // Verify consensus is still false (need 2 votes)
-    let (reached, outcome) = client.check_consensus(&market_id);
+    let (reached, outcome) = fixture.client.check_consensus(&market_id);
     assert!(!reached);
     assert_eq!(outcome, 0);
 }
@@ -168,37 +126,18 @@ fn test_submit_attestation() {
 #[test]
 fn test_check_consensus_reached() {
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let client = OracleManagerClient::new(&env, &oracle_id);
-
-    let admin = Address::generate(&env);
-    client.initialize(&admin, &2u32);
-
-    let oracle1 = Address::generate(&env);
-    let oracle2 = Address::generate(&env);
-    let oracle3 = Address::generate(&env);
-
-    client.register_oracle(&oracle1, &Symbol::new(&env, "Oracle1"));
-    client.register_oracle(&oracle2, &Symbol::new(&env, "Oracle2"));
-    client.register_oracle(&oracle3, &Symbol::new(&env, "Oracle3"));
-
-    let market_id = BytesN::from_array(&env, &[1u8; 32]);
-    let resolution_time = 1000u64;
-
-    // Register market and set timestamp past resolution time
-    client.register_market(&market_id, &resolution_time);
+    let fixture = OracleFixture::new(&env)
+        .with_oracles(3)
+        .with_market([1u8; 32], 1000);
+    let market_id = fixture.market(0);
     env.ledger().set_timestamp(1001);
 
-    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
-
     // 2 oracles submit YES (1)
-    client.submit_attestation(&oracle1, &market_id, &1u32, &data_hash);
-    client.submit_attestation(&oracle2, &market_id, &1u32, &data_hash);
+    fixture.attest(&fixture.oracle(0), &market_id, 1);
+    fixture.attest(&fixture.oracle(1), &market_id, 1);
 
     // Verify consensus reached YES
-    let (reached, outcome) = client.check_consensus(&market_id);
+    let (reached, outcome) = fixture.client.check_consensus(&market_id);
     assert!(reached);
     assert_eq!(outcome, 1);
 }
@@ -206,33 +145,18 @@ fn test_check_consensus_reached() {
 #[test]
 fn test_check_consensus_not_reached() {
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let client = OracleManagerClient::new(&env, &oracle_id);
-
-    let admin = Address::generate(&env);
-    client.initialize(&admin, &3u32); // Need 3 oracles
-
-    let oracle1 = Address::generate(&env);
-    let oracle2 = Address::generate(&env);
-    client.register_oracle(&oracle1, &Symbol::new(&env, "Oracle1"));
-    client.register_oracle(&oracle2, &Symbol::new(&env, "Oracle2"));
-
-    let market_id = BytesN::from_array(&env, &[1u8; 32]);
-    let resolution_time = 1000u64;
-
-    // Register market and set timestamp past resolution time
-    client.register_market(&market_id, &resolution_time);
+    let fixture = OracleFixture::new(&env)
+        .with_oracles(2)
+        .with_threshold(3) // Need 3 oracles
+        .with_market([1u8; 32], 1000);
+    let market_id = fixture.market(0);
     env.ledger().set_timestamp(1001);
 
-    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
-
-    client.submit_attestation(&oracle1, &market_id, &1u32, &data_hash);
-    client.submit_attestation(&oracle2, &market_id, &1u32, &data_hash);
+    fixture.attest(&fixture.oracle(0), &market_id, 1);
+    fixture.attest(&fixture.oracle(1), &market_id, 1);
 
     // Only 2 of 3 votes, consensus not reached
-    let (reached, _) = client.check_consensus(&market_id);
+    let (reached, _) = fixture.client.check_consensus(&market_id);
     assert!(!reached);
 }
 
@@ -249,41 +173,19 @@ fn test_resolve_market_without_consensus() {
 #[test]
 fn test_check_consensus_tie_handling() {
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let client = OracleManagerClient::new(&env, &oracle_id);
-
-    let admin = Address::generate(&env);
-    client.initialize(&admin, &2u32); // threshold 2
-
-    let oracle1 = Address::generate(&env);
-    let oracle2 = Address::generate(&env);
-    let oracle3 = Address::generate(&env);
-    let oracle4 = Address::generate(&env);
-
-    client.register_oracle(&oracle1, &Symbol::new(&env, "O1"));
-    client.register_oracle(&oracle2, &Symbol::new(&env, "O2"));
-    client.register_oracle(&oracle3, &Symbol::new(&env, "O3"));
-    client.register_oracle(&oracle4, &Symbol::new(&env, "O4"));
-
-    let market_id = BytesN::from_array(&env, &[1u8; 32]);
-    let resolution_time = 1000u64;
-
-    // Register market and set timestamp past resolution time
-    client.register_market(&market_id, &resolution_time);
+    let fixture = OracleFixture::new(&env)
+        .with_oracles(4)
+        .with_market([1u8; 32], 1000);
+    let market_id = fixture.market(0);
     env.ledger().set_timestamp(1001);
 
-    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
-
     // 2 vote YES, 2 vote NO
-    client.submit_attestation(&oracle1, &market_id, &1u32, &data_hash);
-    client.submit_attestation(&oracle2, &market_id, &1u32, &data_hash);
-    client.submit_attestation(&oracle3, &market_id, &0u32, &data_hash);
-    client.submit_attestation(&oracle4, &market_id, &0u32, &data_hash);
+    for (i, outcome) in [1u32, 1, 0, 0].into_iter().enumerate() {
+        fixture.attest(&fixture.oracle(i as u32), &market_id, outcome);
+    }
 
     // Both reached threshold 2, but it's a tie
-    let (reached, _) = client.check_consensus(&market_id);
+    let (reached, _) = fixture.client.check_consensus(&market_id);
     assert!(!reached);
 }
 
@@ -293,32 +195,19 @@ fn test_check_consensus_tie_handling() {
 #[test]
 fn test_deregister_oracle_success() {
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let client = OracleManagerClient::new(&env, &oracle_id);
-
-    let admin = Address::generate(&env);
-    client.initialize(&admin, &2u32);
-
-    // Register an oracle
-    let oracle1 = Address::generate(&env);
-    client.register_oracle(&oracle1, &Symbol::new(&env, "Oracle1"));
+    let fixture = OracleFixture::new(&env).with_oracles(1);
+    let oracle1 = fixture.oracle(0);
 
     // Deregister the oracle
-    client.deregister_oracle(&oracle1);
+    fixture.client.deregister_oracle(&oracle1);
 
     // Oracle should be inactive - submitting attestation should fail
-    let market_id = BytesN::from_array(&env, &[1u8; 32]);
-    let resolution_time = 1000u64;
-    client.register_market(&market_id, &resolution_time);
+    let fixture = fixture.with_market([1u8; 32], 1000);
     env.ledger().set_timestamp(1500);
 
-    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
-
     // This should panic because oracle is no longer active
     let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-        client.submit_attestation(&oracle1, &market_id, &1u32, &data_hash);
+        fixture.attest(&oracle1, &fixture.market(0), 1);
     }));
     assert!(result.is_err());
 }
@@ -328,17 +217,10 @@ fn test_deregister_oracle_success() {
 #[should_panic(expected = "Oracle not registered or already inactive")]
 fn test_deregister_oracle_not_registered() {
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let client = OracleManagerClient::new(&env, &oracle_id);
-
-    let admin = Address::generate(&env);
-    client.initialize(&admin, &2u32);
+    let fixture = OracleFixture::new(&env);
 
     // Try to deregister an oracle that was never registered
-    let oracle1 = Address::generate(&env);
-    client.deregister_oracle(&oracle1);
+    fixture.client.deregister_oracle(&Address::generate(&env));
 }
 
 /// Test deregistering an already deregistered oracle
@@ -346,60 +228,34 @@ fn test_deregister_oracle_not_registered() {
 #[should_panic(expected = "Oracle not registered or already inactive")]
 fn test_deregister_oracle_already_inactive() {
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let client = OracleManagerClient::new(&env, &oracle_id);
-
-    let admin = Address::generate(&env);
-    client.initialize(&admin, &2u32);
-
-    let oracle1 = Address::generate(&env);
-    client.register_oracle(&oracle1, &Symbol::new(&env, "Oracle1"));
+    let fixture = OracleFixture::new(&env).with_oracles(1);
 
     // Deregister once
-    client.deregister_oracle(&oracle1);
+    fixture.client.deregister_oracle(&fixture.oracle(0));
 
     // Try to deregister again - should fail
-    client.deregister_oracle(&oracle1);
+    fixture.client.deregister_oracle(&fixture.oracle(0));
 }
 
 /// Test that consensus threshold is recalculated after deregistration
 #[test]
 fn test_deregister_oracle_recalculates_threshold() {
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let client = OracleManagerClient::new(&env, &oracle_id);
-
-    let admin = Address::generate(&env);
-    // Set threshold to 3
-    client.initialize(&admin, &3u32);
-
-    // Register 3 oracles
-    let oracle1 = Address::generate(&env);
-    let oracle2 = Address::generate(&env);
-    let oracle3 = Address::generate(&env);
-    client.register_oracle(&oracle1, &Symbol::new(&env, "O1"));
-    client.register_oracle(&oracle2, &Symbol::new(&env, "O2"));
-    client.register_oracle(&oracle3, &Symbol::new(&env, "O3"));
-
-    let market_id = BytesN::from_array(&env, &[1u8; 32]);
-    let resolution_time = 1000u64;
-    client.register_market(&market_id, &resolution_time);
+    let fixture = OracleFixture::new(&env)
+        .with_oracles(3)
+        .with_threshold(3)
+        .with_market([1u8; 32], 1000);
+    let market_id = fixture.market(0);
     env.ledger().set_timestamp(1500);
 
-    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
-
     // Deregister one oracle (count goes from 3 to 2, threshold adjusted from 3 to 2)
-    client.deregister_oracle(&oracle3);
+    fixture.client.deregister_oracle(&fixture.oracle(2));
 
     // Now 2 votes should be enough for consensus (threshold adjusted to 2)
-    client.submit_attestation(&oracle1, &market_id, &1u32, &data_hash);
-    client.submit_attestation(&oracle2, &market_id, &1u32, &data_hash);
+    fixture.attest(&fixture.oracle(0), &market_id, 1);
+    fixture.attest(&fixture.oracle(1), &market_id, 1);
 
-    let (reached, outcome) = client.check_consensus(&market_id);
+    let (reached, outcome) = fixture.client.check_consensus(&market_id);
     assert!(reached);
     assert_eq!(outcome, 1);
 }
@@ -408,36 +264,20 @@ fn test_deregister_oracle_recalculates_threshold() {
 #[test]
 fn test_deregister_multiple_oracles() {
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let client = OracleManagerClient::new(&env, &oracle_id);
-
-    let admin = Address::generate(&env);
-    client.initialize(&admin, &2u32);
-
-    let oracle1 = Address::generate(&env);
-    let oracle2 = Address::generate(&env);
-    let oracle3 = Address::generate(&env);
-    client.register_oracle(&oracle1, &Symbol::new(&env, "O1"));
-    client.register_oracle(&oracle2, &Symbol::new(&env, "O2"));
-    client.register_oracle(&oracle3, &Symbol::new(&env, "O3"));
+    let fixture = OracleFixture::new(&env).with_oracles(3);
 
     // Deregister two oracles
-    client.deregister_oracle(&oracle1);
-    client.deregister_oracle(&oracle2);
+    fixture.client.deregister_oracle(&fixture.oracle(0));
+    fixture.client.deregister_oracle(&fixture.oracle(1));
 
     // Remaining oracle can still submit attestations
-    let market_id = BytesN::from_array(&env, &[1u8; 32]);
-    let resolution_time = 1000u64;
-    client.register_market(&market_id, &resolution_time);
+    let fixture = fixture.with_market([1u8; 32], 1000);
+    let market_id = fixture.market(0);
     env.ledger().set_timestamp(1500);
-
-    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
-    client.submit_attestation(&oracle3, &market_id, &1u32, &data_hash);
+    fixture.attest(&fixture.oracle(2), &market_id, 1);
 
     // Consensus should be reached with 1 vote (threshold adjusted to 1)
-    let (reached, outcome) = client.check_consensus(&market_id);
+    let (reached, outcome) = fixture.client.check_consensus(&market_id);
     assert!(reached);
     assert_eq!(outcome, 1);
 }
@@ -446,40 +286,26 @@ fn test_deregister_multiple_oracles() {
 #[test]
 fn test_deregister_oracle_preserves_existing_attestations() {
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let client = OracleManagerClient::new(&env, &oracle_id);
-
-    let admin = Address::generate(&env);
-    client.initialize(&admin, &2u32);
-
-    let oracle1 = Address::generate(&env);
-    let oracle2 = Address::generate(&env);
-    client.register_oracle(&oracle1, &Symbol::new(&env, "O1"));
-    client.register_oracle(&oracle2, &Symbol::new(&env, "O2"));
-
-    let market_id = BytesN::from_array(&env, &[1u8; 32]);
-    let resolution_time = 1000u64;
-    client.register_market(&market_id, &resolution_time);
+    let fixture = OracleFixture::new(&env)
+        .with_oracles(2)
+        .with_market([1u8; 32], 1000);
+    let (oracle1, market_id) = (fixture.oracle(0), fixture.market(0));
     env.ledger().set_timestamp(1500);
 
-    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
-
     // Submit attestation before deregistration
-    client.submit_attestation(&oracle1, &market_id, &1u32, &data_hash);
-    client.submit_attestation(&oracle2, &market_id, &1u32, &data_hash);
+    fixture.attest(&oracle1, &market_id, 1);
+    fixture.attest(&fixture.oracle(1), &market_id, 1);
 
     // Deregister oracle1 after attestation
-    client.deregister_oracle(&oracle1);
+    fixture.client.deregister_oracle(&oracle1);
 
     // Existing attestation should still be accessible
-    let attestation = client.get_attestation(&market_id, &oracle1);
+    let attestation = fixture.client.get_attestation(&market_id, &oracle1);
     assert!(attestation.is_some());
     assert_eq!(attestation.unwrap().outcome, 1);
 
     // Consensus should still hold with existing votes
-    let (reached, outcome) = client.check_consensus(&market_id);
+    let (reached, outcome) = fixture.client.check_consensus(&market_id);
     assert!(reached);
     assert_eq!(outcome, 1);
 }
@@ -497,33 +323,18 @@ fn test_update_oracle_accuracy() {
 #[test]
 fn test_submit_attestation_stores_attestation() {
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let client = OracleManagerClient::new(&env, &oracle_id);
-
-    let admin = Address::generate(&env);
-    client.initialize(&admin, &2u32);
-
-    let oracle1 = Address::generate(&env);
-    client.register_oracle(&oracle1, &Symbol::new(&env, "Oracle1"));
-
-    let market_id = BytesN::from_array(&env, &[2u8; 32]);
-    let resolution_time = 1000u64;
-
-    // Register market with resolution time
-    client.register_market(&market_id, &resolution_time);
+    let fixture = OracleFixture::new(&env)
+        .with_oracles(1)
+        .with_market([2u8; 32], 1000);
+    let (oracle1, market_id) = (fixture.oracle(0), fixture.market(0));
 
     // Set ledger time past resolution time
     env.ledger().set_timestamp(1500);
 
-    let result = 1u32; // YES
-    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
-
-    client.submit_attestation(&oracle1, &market_id, &result, &data_hash);
+    fixture.attest(&oracle1, &market_id, 1); // YES
 
     // Verify attestation is stored correctly
-    let attestation = client.get_attestation(&market_id, &oracle1);
+    let attestation = fixture.client.get_attestation(&market_id, &oracle1);
     assert!(attestation.is_some());
     let attestation = attestation.unwrap();
     assert_eq!(attestation.attestor, oracle1);
@@ -531,7 +342,7 @@ fn test_submit_attestation_stores_attestation() {
     assert_eq!(attestation.timestamp, 1500);
 
     // Verify attestation counts are updated
-    let (yes_count, no_count) = client.get_attestation_counts(&market_id);
+    let (yes_count, no_count) = fixture.client.get_attestation_counts(&market_id);
     assert_eq!(yes_count, 1);
     assert_eq!(no_count, 0);
 }
@@ -541,30 +352,13 @@ fn test_submit_attestation_stores_attestation() {
 #[should_panic(expected = "Oracle not registered")]
 fn test_submit_attestation_non_attestor_rejected() {
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let client = OracleManagerClient::new(&env, &oracle_id);
-
-    let admin = Address::generate(&env);
-    client.initialize(&admin, &2u32);
-
-    // Note: we do NOT register unregistered_oracle as an oracle
-    let unregistered_oracle = Address::generate(&env);
-
-    let market_id = BytesN::from_array(&env, &[3u8; 32]);
-    let resolution_time = 1000u64;
-
-    // Register market
-    client.register_market(&market_id, &resolution_time);
+    let fixture = OracleFixture::new(&env).with_market([3u8; 32], 1000);
 
     // Set ledger time past resolution time
     env.ledger().set_timestamp(1500);
 
-    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
-
     // This should panic because oracle is not registered
-    client.submit_attestation(&unregistered_oracle, &market_id, &1u32, &data_hash);
+    fixture.attest(&Address::generate(&env), &fixture.market(0), 1);
 }
 
 /// Cannot attest before resolution_time
@@ -572,30 +366,15 @@ fn test_submit_attestation_non_attestor_rejected() {
 #[should_panic(expected = "Cannot attest before resolution time")]
 fn test_submit_attestation_before_resolution_time() {
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let client = OracleManagerClient::new(&env, &oracle_id);
-
-    let admin = Address::generate(&env);
-    client.initialize(&admin, &2u32);
-
-    let oracle1 = Address::generate(&env);
-    client.register_oracle(&oracle1, &Symbol::new(&env, "Oracle1"));
-
-    let market_id = BytesN::from_array(&env, &[4u8; 32]);
-    let resolution_time = 2000u64;
-
-    // Register market with resolution time of 2000
-    client.register_market(&market_id, &resolution_time);
+    let fixture = OracleFixture::new(&env)
+        .with_oracles(1)
+        .with_market([4u8; 32], 2000);
 
     // Set ledger time BEFORE resolution time
     env.ledger().set_timestamp(1500);
 
-    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
-
     // This should panic because we're before resolution time
-    client.submit_attestation(&oracle1, &market_id, &1u32, &data_hash);
+    fixture.attest(&fixture.oracle(0), &fixture.market(0), 1);
 }
 
 /// Invalid outcome (not 0 or 1) is rejected
@@ -603,98 +382,48 @@ fn test_submit_attestation_before_resolution_time() {
 #[should_panic(expected = "Invalid attestation result")]
 fn test_submit_attestation_invalid_outcome_rejected() {
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let client = OracleManagerClient::new(&env, &oracle_id);
-
-    let admin = Address::generate(&env);
-    client.initialize(&admin, &2u32);
-
-    let oracle1 = Address::generate(&env);
-    client.register_oracle(&oracle1, &Symbol::new(&env, "Oracle1"));
-
-    let market_id = BytesN::from_array(&env, &[5u8; 32]);
-    let resolution_time = 1000u64;
-
-    // Register market
-    client.register_market(&market_id, &resolution_time);
-
-    // Set ledger time past resolution time
+    let fixture = OracleFixture::new(&env)
+        .with_oracles(1)
+        .with_market([5u8; 32], 1000);
     env.ledger().set_timestamp(1500);
 
-    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
-
     // This should panic because outcome 2 is invalid (only 0 or 1 allowed)
-    client.submit_attestation(&oracle1, &market_id, &2u32, &data_hash);
+    fixture.attest(&fixture.oracle(0), &fixture.market(0), 2);
 }
 
 /// Verify AttestationSubmitted event is emitted correctly
 #[test]
 fn test_submit_attestation_event_emitted() {
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let client = OracleManagerClient::new(&env, &oracle_id);
-
-    let admin = Address::generate(&env);
-    client.initialize(&admin, &2u32);
-
-    let oracle1 = Address::generate(&env);
-    client.register_oracle(&oracle1, &Symbol::new(&env, "Oracle1"));
-
-    let market_id = BytesN::from_array(&env, &[6u8; 32]);
-    let resolution_time = 1000u64;
-
-    // Register market
-    client.register_market(&market_id, &resolution_time);
-
-    // Set ledger time past resolution time
+    let fixture = OracleFixture::new(&env)
+        .with_oracles(1)
+        .with_market([6u8; 32], 1000);
+    let (oracle1, market_id) = (fixture.oracle(0), fixture.market(0));
     env.ledger().set_timestamp(1500);
 
-    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
-
-    client.submit_attestation(&oracle1, &market_id, &1u32, &data_hash);
+    fixture.attest(&oracle1, &market_id, 1);
 
-    // Verify event was emitted
-    // The event system stores events that can be queried
-    // In test environment, we verify by checking the attestation was stored
-    // and the counts were updated (both happen only if function completes successfully)
-    let attestation = client.get_attestation(&market_id, &oracle1);
-    assert!(attestation.is_some());
-
-    // Verify attestation counts
-    let (yes_count, no_count) = client.get_attestation_counts(&market_id);
-    assert_eq!(yes_count, 1);
-    assert_eq!(no_count, 0);
+    let (topics, data) = oracle_event(&env, &fixture.client.address, "attest");
+    let expected: Vec<Val> =
+        (Symbol::new(&env, "attest"), market_id.clone(), oracle1).into_val(&env);
+    assert_eq!(topics, expected);
+    assert_eq!(<(u32,)>::try_from_val(&env, &data).unwrap(), (1,));
 }
 
 /// Test register_market function
 #[test]
 fn test_register_market() {
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let client = OracleManagerClient::new(&env, &oracle_id);
-
-    let admin = Address::generate(&env);
-    client.initialize(&admin, &2u32);
-
-    let market_id = BytesN::from_array(&env, &[7u8; 32]);
-    let resolution_time = 3000u64;
-
-    // Register market
-    client.register_market(&market_id, &resolution_time);
+    let fixture = OracleFixture::new(&env).with_market([7u8; 32], 3000);
+    let market_id = fixture.market(0);
 
     // Verify resolution time is stored
-    let stored_time = client.get_market_resolution_time(&market_id);
+    let stored_time = fixture.client.get_market_resolution_time(&market_id);
     assert!(stored_time.is_some());
     assert_eq!(stored_time.unwrap(), 3000);
 
     // Verify attestation counts are initialized to 0
-    let (yes_count, no_count) = client.get_attestation_counts(&market_id);
+    let (yes_count, no_count) = fixture.client.get_attestation_counts(&market_id);
     assert_eq!(yes_count, 0);
     assert_eq!(no_count, 0);
 }
@@ -703,37 +432,19 @@ fn test_register_market() {
 #[test]
 fn test_attestation_count_tracking() {
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let client = OracleManagerClient::new(&env, &oracle_id);
-
-    let admin = Address::generate(&env);
-    client.initialize(&admin, &2u32);
-
-    let oracle1 = Address::generate(&env);
-    let oracle2 = Address::generate(&env);
-    let oracle3 = Address::generate(&env);
-    client.register_oracle(&oracle1, &Symbol::new(&env, "O1"));
-    client.register_oracle(&oracle2, &Symbol::new(&env, "O2"));
-    client.register_oracle(&oracle3, &Symbol::new(&env, "O3"));
-
-    let market_id = BytesN::from_array(&env, &[8u8; 32]);
-    let resolution_time = 1000u64;
-
-    // Register market
-    client.register_market(&market_id, &resolution_time);
+    let fixture = OracleFixture::new(&env)
+        .with_oracles(3)
+        .with_market([8u8; 32], 1000);
+    let market_id = fixture.market(0);
     env.ledger().set_timestamp(1500);
 
-    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
-
     // 2 vote YES, 1 vote NO
-    client.submit_attestation(&oracle1, &market_id, &1u32, &data_hash);
-    client.submit_attestation(&oracle2, &market_id, &1u32, &data_hash);
-    client.submit_attestation(&oracle3, &market_id, &0u32, &data_hash);
+    fixture.attest(&fixture.oracle(0), &market_id, 1);
+    fixture.attest(&fixture.oracle(1), &market_id, 1);
+    fixture.attest(&fixture.oracle(2), &market_id, 0);
 
     // Verify counts
-    let (yes_count, no_count) = client.get_attestation_counts(&market_id);
+    let (yes_count, no_count) = fixture.client.get_attestation_counts(&market_id);
     assert_eq!(yes_count, 2);
     assert_eq!(no_count, 1);
 }
@@ -746,38 +457,25 @@ fn test_finalize_resolution_integration() {
     use boxmeout::market::{PredictionMarket, PredictionMarketClient};
 
     let env = create_test_env();
-    env.mock_all_auths();
 
-    // Register Oracle contract
-    let oracle_id = register_oracle(&env);
-    let oracle_client = OracleManagerClient::new(&env, &oracle_id);
+    // Setup timing
+    let resolution_time = 1000u64;
+    let closing_time = 500u64;
+
+    // Oracle with 2 of 3 consensus, market registered
+    let fixture = OracleFixture::new(&env)
+        .with_oracles(3)
+        .with_market([9u8; 32], resolution_time);
+    let oracle_client = &fixture.client;
+    let oracle_id = oracle_client.address.clone();
+    let market_id_bytes = fixture.market(0);
 
     // Register Market contract
-    let market_id_bytes = BytesN::from_array(&env, &[9u8; 32]);
     let market_contract_id = env.register(PredictionMarket, ());
     let market_client = PredictionMarketClient::new(&env, &market_contract_id);
 
     // Setup token
-    let token_admin = Address::generate(&env);
-    let usdc_address = env
-        .register_stellar_asset_contract_v2(token_admin.clone())
-        .address();
-
-    // Initialize oracle with 2 of 3 consensus
-    let admin = Address::generate(&env);
-    oracle_client.initialize(&admin, &2u32);
-
-    // Register 3 oracles
-    let oracle1 = Address::generate(&env);
-    let oracle2 = Address::generate(&env);
-    let oracle3 = Address::generate(&env);
-    oracle_client.register_oracle(&oracle1, &Symbol::new(&env, "O1"));
-    oracle_client.register_oracle(&oracle2, &Symbol::new(&env, "O2"));
-    oracle_client.register_oracle(&oracle3, &Symbol::new(&env, "O3"));
-
-    // Setup timing
-    let resolution_time = 1000u64;
-    let closing_time = 500u64;
+    let usdc_address = deploy_token(&env).address;
 
     // Initialize market
     let creator = Address::generate(&env);
@@ -789,11 +487,10 @@ fn test_finalize_resolution_integration() {
         &oracle_id,
         &closing_time,
         &resolution_time,
+        &0,
+        &None,
     );
 
-    // Register market in oracle
-    oracle_client.register_market(&market_id_bytes, &resolution_time);
-
     // Advance time past resolution
     env.ledger().set_timestamp(resolution_time + 10);
 
@@ -805,9 +502,8 @@ fn test_finalize_resolution_integration() {
     env.ledger().set_timestamp(resolution_time + 10);
 
     // Submit attestations to reach consensus (2 YES, 1 NO)
-    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
-    oracle_client.submit_attestation(&oracle1, &market_id_bytes, &1u32, &data_hash);
-    oracle_client.submit_attestation(&oracle2, &market_id_bytes, &1u32, &data_hash);
+    fixture.attest(&fixture.oracle(0), &market_id_bytes, 1);
+    fixture.attest(&fixture.oracle(1), &market_id_bytes, 1);
 
     // Verify consensus reached
     let (reached, outcome) = oracle_client.check_consensus(&market_id_bytes);
@@ -837,33 +533,24 @@ fn test_finalize_resolution_no_consensus() {
     use boxmeout::market::PredictionMarket;
 
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let oracle_client = OracleManagerClient::new(&env, &oracle_id);
-
-    let market_contract_id = env.register(PredictionMarket, ());
-    let market_id_bytes = BytesN::from_array(&env, &[10u8; 32]);
-
-    let admin = Address::generate(&env);
-    oracle_client.initialize(&admin, &3u32); // Need 3 votes
-
-    let oracle1 = Address::generate(&env);
-    oracle_client.register_oracle(&oracle1, &Symbol::new(&env, "O1"));
-
     let resolution_time = 1000u64;
-    oracle_client.register_market(&market_id_bytes, &resolution_time);
+    let fixture = OracleFixture::new(&env)
+        .with_oracles(1)
+        .with_threshold(3) // Need 3 votes
+        .with_market([10u8; 32], resolution_time);
+    let market_contract_id = env.register(PredictionMarket, ());
 
     // Only 1 attestation (not enough for consensus)
     env.ledger().set_timestamp(resolution_time + 10);
-    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
-    oracle_client.submit_attestation(&oracle1, &market_id_bytes, &1u32, &data_hash);
+    fixture.attest(&fixture.oracle(0), &fixture.market(0), 1);
 
     // Advance past dispute period
     env.ledger().set_timestamp(resolution_time + 604800 + 10);
 
     // Should panic: consensus not reached
-    oracle_client.finalize_resolution(&market_id_bytes, &market_contract_id);
+    fixture
+        .client
+        .finalize_resolution(&fixture.market(0), &market_contract_id);
 }
 
 /// Test finalize_resolution fails if dispute period not elapsed
@@ -873,36 +560,24 @@ fn test_finalize_resolution_dispute_period_not_elapsed() {
     use boxmeout::market::PredictionMarket;
 
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let oracle_client = OracleManagerClient::new(&env, &oracle_id);
-
-    let market_contract_id = env.register(PredictionMarket, ());
-    let market_id_bytes = BytesN::from_array(&env, &[11u8; 32]);
-
-    let admin = Address::generate(&env);
-    oracle_client.initialize(&admin, &2u32);
-
-    let oracle1 = Address::generate(&env);
-    let oracle2 = Address::generate(&env);
-    oracle_client.register_oracle(&oracle1, &Symbol::new(&env, "O1"));
-    oracle_client.register_oracle(&oracle2, &Symbol::new(&env, "O2"));
-
     let resolution_time = 1000u64;
-    oracle_client.register_market(&market_id_bytes, &resolution_time);
+    let fixture = OracleFixture::new(&env)
+        .with_oracles(2)
+        .with_market([11u8; 32], resolution_time);
+    let market_contract_id = env.register(PredictionMarket, ());
 
     // Submit attestations to reach consensus
     env.ledger().set_timestamp(resolution_time + 10);
-    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
-    oracle_client.submit_attestation(&oracle1, &market_id_bytes, &1u32, &data_hash);
-    oracle_client.submit_attestation(&oracle2, &market_id_bytes, &1u32, &data_hash);
+    fixture.attest(&fixture.oracle(0), &fixture.market(0), 1);
+    fixture.attest(&fixture.oracle(1), &fixture.market(0), 1);
 
     // Try to finalize before dispute period (only 100 seconds after resolution)
     env.ledger().set_timestamp(resolution_time + 100);
 
     // Should panic: dispute period not elapsed
-    oracle_client.finalize_resolution(&market_id_bytes, &market_contract_id);
+    fixture
+        .client
+        .finalize_resolution(&fixture.market(0), &market_contract_id);
 }
 
 /// Test finalize_resolution fails if market not registered
@@ -910,17 +585,1815 @@ fn test_finalize_resolution_dispute_period_not_elapsed() {
 #[should_panic(expected = "Market not registered")]
 fn test_finalize_resolution_market_not_registered() {
     let env = create_test_env();
-    env.mock_all_auths();
-
-    let oracle_id = register_oracle(&env);
-    let oracle_client = OracleManagerClient::new(&env, &oracle_id);
-
+    let fixture = OracleFixture::new(&env);
     let market_contract_id = env.register(PredictionMarket, ());
     let market_id_bytes = BytesN::from_array(&env, &[12u8; 32]);
 
-    let admin = Address::generate(&env);
-    oracle_client.initialize(&admin, &2u32);
-
     // Market not registered - should panic
-    oracle_client.finalize_resolution(&market_id_bytes, &market_contract_id);
+    fixture
+        .client
+        .finalize_resolution(&market_id_bytes, &market_contract_id);
+}
+
+// ===== CAPABILITY DESCRIPTOR TESTS =====
+
+#[test]
+fn test_get_capabilities_reflects_configuration() {
+    let env = create_test_env();
+    let oracle_client = OracleFixture::new(&env).client;
+
+    let capabilities = oracle_client.get_capabilities();
+    assert!(capabilities.contains(Symbol::new(&env, CAP_BINARY)));
+    assert!(capabilities.contains(Symbol::new(&env, CAP_CHALLENGE)));
+    assert!(capabilities.contains(Symbol::new(&env, CAP_OVERRIDE)));
+    assert!(capabilities.contains(Symbol::new(&env, CAP_SCALAR)));
+    assert!(capabilities.contains(Symbol::new(&env, CAP_SUBSCRIBE)));
+    // Only one of two required oracles registered: not ready yet
+    oracle_client.register_oracle(
+        &Address::generate(&env),
+        &Symbol::new(&env, "one"),
+        &Vec::new(&env),
+    );
+    assert!(!oracle_client
+        .get_capabilities()
+        .contains(Symbol::new(&env, CAP_CONSENSUS_READY)));
+
+    oracle_client.register_oracle(
+        &Address::generate(&env),
+        &Symbol::new(&env, "two"),
+        &Vec::new(&env),
+    );
+    assert!(oracle_client
+        .get_capabilities()
+        .contains(Symbol::new(&env, CAP_CONSENSUS_READY)));
+}
+
+#[test]
+fn test_get_version_matches_crate_version() {
+    let env = create_test_env();
+    let oracle_client = OracleManagerClient::new(&env, &register_oracle(&env));
+
+    let expected = (
+        env!("CARGO_PKG_VERSION_MAJOR").parse::<u32>().unwrap(),
+        env!("CARGO_PKG_VERSION_MINOR").parse::<u32>().unwrap(),
+        env!("CARGO_PKG_VERSION_PATCH").parse::<u32>().unwrap(),
+    );
+    assert_eq!(oracle_client.get_version(), expected);
+}
+
+#[test]
+#[should_panic(expected = "Market contract not bound")]
+fn test_update_resolution_time_requires_bound_market() {
+    let env = create_test_env();
+    let fixture = OracleFixture::new(&env).with_market([7u8; 32], 3000);
+
+    fixture
+        .client
+        .update_resolution_time(&fixture.market(0), &4000u64);
+}
+
+#[test]
+fn test_update_resolution_time_by_bound_market() {
+    let env = create_test_env();
+    let fixture = OracleFixture::new(&env).with_market([7u8; 32], 3000);
+    let (client, market_id) = (&fixture.client, fixture.market(0));
+
+    let market_contract = Address::generate(&env);
+    client.set_market_contract(&market_id, &market_contract);
+    assert_eq!(
+        client.get_market_contract(&market_id),
+        Some(market_contract)
+    );
+
+    client.update_resolution_time(&market_id, &4000u64);
+    assert_eq!(client.get_market_resolution_time(&market_id), Some(4000));
+
+    // Moving the time earlier is rejected
+    assert!(client
+        .try_update_resolution_time(&market_id, &3500u64)
+        .is_err());
+}
+
+// ===== RESOLUTION ARCHIVE =====
+
+/// Finalize a market with attestations YES, YES, NO; returns (oracle, market_id, attestors)
+fn finalized_market(
+    env: &Env,
+) -> (
+    OracleManagerClient<'_>,
+    BytesN<32>,
+    soroban_sdk::Vec<Address>,
+) {
+    finalized_market_with_metadata(env, &None)
+}
+
+/// Helper: `finalized_market` where every attestation carries `metadata`
+fn finalized_market_with_metadata<'a>(
+    env: &'a Env,
+    metadata: &Option<Bytes>,
+) -> (
+    OracleManagerClient<'a>,
+    BytesN<32>,
+    soroban_sdk::Vec<Address>,
+) {
+    let fixture = OracleFixture::new(env)
+        .with_oracles(3)
+        .with_market([9u8; 32], 1000);
+    let market_id = fixture.market(0);
+
+    let market_client =
+        boxmeout::market::PredictionMarketClient::new(env, &env.register(PredictionMarket, ()));
+    market_client.initialize(
+        &market_id,
+        &Address::generate(env),
+        &Address::generate(env),
+        &deploy_token(env).address,
+        &fixture.client.address,
+        &500,
+        &1000,
+        &0,
+        &None,
+    );
+    env.ledger().set_timestamp(510);
+    market_client.close_market(&market_id);
+
+    env.ledger().set_timestamp(1010);
+    let data_hash = BytesN::from_array(env, &[0u8; 32]);
+    for (attestor, outcome) in fixture.oracles.iter().zip([1u32, 1, 0]) {
+        fixture
+            .client
+            .submit_attestation(&attestor, &market_id, &outcome, &data_hash, metadata);
+    }
+
+    env.ledger().set_timestamp(1000 + 604800 + 10);
+    fixture
+        .client
+        .finalize_resolution(&market_id, &market_client.address);
+
+    (fixture.client, market_id, fixture.oracles)
+}
+
+#[test]
+fn test_cleanup_market_archives_resolution() {
+    use soroban_sdk::xdr::ToXdr;
+
+    let env = create_test_env();
+    let (oracle_client, market_id, attestors) = finalized_market(&env);
+    let proof = oracle_client.get_resolution_proof(&market_id);
+
+    // Recompute the digest from an off-chain copy of the detailed records
+    let mut records: std::vec::Vec<_> = attestors
+        .iter()
+        .map(|attestor| {
+            oracle_client
+                .get_attestation(&market_id, &attestor)
+                .unwrap()
+        })
+        .collect();
+    records.sort_by(|a, b| a.attestor.cmp(&b.attestor));
+    let sorted = soroban_sdk::Vec::from_slice(&env, &records);
+    let expected_digest: BytesN<32> = env.crypto().sha256(&sorted.to_xdr(&env)).into();
+    assert_eq!(proof.attestor_digest, expected_digest);
+
+    let archive = oracle_client.cleanup_market(&market_id);
+    assert_eq!(archive.market_id, market_id);
+    assert_eq!(archive.final_outcome, 1);
+    assert_eq!(archive.tally, (2, 1));
+    assert_eq!(archive.attestor_digest, proof.attestor_digest);
+    assert_eq!(archive.finalized_at, 1000 + 604800 + 10);
+    assert!(!archive.overridden);
+    assert_eq!(
+        oracle_client.get_resolution_archive(&market_id),
+        Some(archive)
+    );
+}
+
+#[test]
+fn test_cleanup_market_removes_detailed_records() {
+    let env = create_test_env();
+    let (oracle_client, market_id, attestors) = finalized_market(&env);
+    let proof = oracle_client.get_resolution_proof(&market_id);
+
+    oracle_client.cleanup_market(&market_id);
+
+    for attestor in attestors.iter() {
+        assert_eq!(oracle_client.get_attestation(&market_id, &attestor), None);
+    }
+    assert_eq!(oracle_client.get_attestation_counts(&market_id), (0, 0));
+
+    // The archive persists and still backs the proof
+    env.ledger().set_timestamp(10_000_000);
+    assert!(oracle_client.get_resolution_archive(&market_id).is_some());
+    assert_eq!(oracle_client.get_resolution_proof(&market_id), proof);
+}
+
+#[test]
+#[should_panic(expected = "Market not finalized")]
+fn test_cleanup_market_requires_finalized_market() {
+    let env = create_test_env();
+    let fixture = OracleFixture::new(&env).with_market([9u8; 32], 1000);
+
+    fixture.client.cleanup_market(&fixture.market(0));
+}
+
+// ===== DELEGATED SUBMITTER TESTS =====
+
+/// Helper: oracle manager with one registered oracle and a market past resolution time
+fn delegated_setup(env: &Env) -> (OracleManagerClient<'_>, Address, BytesN<32>) {
+    let fixture = OracleFixture::new(env)
+        .with_oracles(1)
+        .with_threshold(1)
+        .with_market([42u8; 32], 1000);
+    env.ledger().set_timestamp(1001);
+
+    let (identity, market_id) = (fixture.oracle(0), fixture.market(0));
+    (fixture.client, identity, market_id)
+}
+
+#[test]
+fn test_submit_attestation_via_delegated_submitter() {
+    let env = create_test_env();
+    let (client, identity, market_id) = delegated_setup(&env);
+    let hot_key = Address::generate(&env);
+    client.set_submitter(&identity, &hot_key);
+    assert_eq!(client.get_submitter(&identity), Some(hot_key.clone()));
+
+    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
+    client.submit_attestation_as(&hot_key, &identity, &market_id, &1u32, &data_hash, &None);
+
+    // Only the hot key signed
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, hot_key);
+
+    // The attestation belongs to the identity, with the submitter recorded alongside
+    let attestation = client.get_attestation(&market_id, &identity).unwrap();
+    assert_eq!(attestation.attestor, identity);
+    assert_eq!(attestation.submitter, hot_key);
+    assert_eq!(client.check_consensus(&market_id), (true, 1));
+}
+
+#[test]
+#[should_panic(expected = "Not the oracle's submitter")]
+fn test_revoked_submitter_cannot_attest() {
+    let env = create_test_env();
+    let (client, identity, market_id) = delegated_setup(&env);
+    let hot_key = Address::generate(&env);
+    client.set_submitter(&identity, &hot_key);
+    client.revoke_submitter(&identity);
+    assert_eq!(client.get_submitter(&identity), None);
+
+    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
+    client.submit_attestation_as(&hot_key, &identity, &market_id, &1u32, &data_hash, &None);
+}
+
+#[test]
+#[should_panic(expected = "Not the oracle's submitter")]
+fn test_replaced_submitter_cannot_attest() {
+    let env = create_test_env();
+    let (client, identity, market_id) = delegated_setup(&env);
+    let old_key = Address::generate(&env);
+    client.set_submitter(&identity, &old_key);
+    client.set_submitter(&identity, &Address::generate(&env));
+
+    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
+    client.submit_attestation_as(&old_key, &identity, &market_id, &1u32, &data_hash, &None);
+}
+
+#[test]
+fn test_identity_key_still_attests_directly_with_submitter_set() {
+    let env = create_test_env();
+    let (client, identity, market_id) = delegated_setup(&env);
+    client.set_submitter(&identity, &Address::generate(&env));
+
+    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
+    client.submit_attestation(&identity, &market_id, &0u32, &data_hash, &None);
+
+    let attestation = client.get_attestation(&market_id, &identity).unwrap();
+    assert_eq!(attestation.attestor, identity);
+    assert_eq!(attestation.submitter, identity);
+}
+
+// ===== TIMESTAMP BOUNDARY TESTS =====
+// Deadlines are inclusive, windows are [start, start + duration)
+
+const BOUNDARY_RESOLUTION_TIME: u64 = 1000;
+const DISPUTE_PERIOD: u64 = 604800;
+
+/// Helper: 2-of-2 oracle with a market registered at BOUNDARY_RESOLUTION_TIME
+fn boundary_setup(
+    env: &Env,
+) -> (
+    OracleManagerClient<'_>,
+    Address,
+    Address,
+    Address,
+    BytesN<32>,
+) {
+    let fixture = OracleFixture::new(env)
+        .with_oracles(2)
+        .with_market([21u8; 32], BOUNDARY_RESOLUTION_TIME);
+    let (oracle1, oracle2, market_id) = (fixture.oracle(0), fixture.oracle(1), fixture.market(0));
+    (fixture.client, fixture.admin, oracle1, oracle2, market_id)
+}
+
+/// Helper: market contract bound to the oracle and closed, ready to resolve
+fn closed_boundary_market(env: &Env, client: &OracleManagerClient, id: &BytesN<32>) -> Address {
+    use boxmeout::market::PredictionMarketClient;
+
+    let market_address = env.register(PredictionMarket, ());
+    let market = PredictionMarketClient::new(env, &market_address);
+    market.initialize(
+        id,
+        &Address::generate(env),
+        &Address::generate(env),
+        &deploy_token(env).address,
+        &client.address,
+        &500u64,
+        &BOUNDARY_RESOLUTION_TIME,
+        &0,
+        &None,
+    );
+    env.ledger().set_timestamp(500);
+    market.close_market(id);
+    market_address
+}
+
+fn attest_both(
+    env: &Env,
+    client: &OracleManagerClient,
+    o1: &Address,
+    o2: &Address,
+    id: &BytesN<32>,
+) {
+    let data_hash = BytesN::from_array(env, &[0u8; 32]);
+    client.submit_attestation(o1, id, &1u32, &data_hash, &None);
+    client.submit_attestation(o2, id, &1u32, &data_hash, &None);
+}
+
+#[test]
+fn test_attestation_accepted_exactly_at_resolution_time() {
+    let env = create_test_env();
+    let (client, _admin, oracle1, _oracle2, market_id) = boundary_setup(&env);
+
+    env.ledger().set_timestamp(BOUNDARY_RESOLUTION_TIME);
+    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
+    client.submit_attestation(&oracle1, &market_id, &1u32, &data_hash, &None);
+
+    assert_eq!(client.get_attestation_counts(&market_id), (1, 0));
+}
+
+#[test]
+#[should_panic(expected = "Cannot attest before resolution time")]
+fn test_attestation_rejected_one_second_before_resolution_time() {
+    let env = create_test_env();
+    let (client, _admin, oracle1, _oracle2, market_id) = boundary_setup(&env);
+
+    env.ledger().set_timestamp(BOUNDARY_RESOLUTION_TIME - 1);
+    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
+    client.submit_attestation(&oracle1, &market_id, &1u32, &data_hash, &None);
+}
+
+#[test]
+fn test_finalize_allowed_exactly_when_dispute_period_ends() {
+    let env = create_test_env();
+    let (client, _admin, oracle1, oracle2, market_id) = boundary_setup(&env);
+    let market_address = closed_boundary_market(&env, &client, &market_id);
+    env.ledger().set_timestamp(BOUNDARY_RESOLUTION_TIME);
+    attest_both(&env, &client, &oracle1, &oracle2, &market_id);
+
+    env.ledger()
+        .set_timestamp(BOUNDARY_RESOLUTION_TIME + DISPUTE_PERIOD);
+    client.finalize_resolution(&market_id, &market_address);
+
+    assert_eq!(client.get_consensus_result(&market_id), 1);
+}
+
+#[test]
+#[should_panic(expected = "Dispute period not elapsed")]
+fn test_finalize_rejected_one_second_before_dispute_period_ends() {
+    let env = create_test_env();
+    let (client, _admin, oracle1, oracle2, market_id) = boundary_setup(&env);
+    let market_address = closed_boundary_market(&env, &client, &market_id);
+    env.ledger().set_timestamp(BOUNDARY_RESOLUTION_TIME);
+    attest_both(&env, &client, &oracle1, &oracle2, &market_id);
+
+    env.ledger()
+        .set_timestamp(BOUNDARY_RESOLUTION_TIME + DISPUTE_PERIOD - 1);
+    client.finalize_resolution(&market_id, &market_address);
+}
+
+/// Helper: emergency override at `at`, approved by the admin and a second signer
+fn override_at(env: &Env, client: &OracleManagerClient, admin: &Address, id: &BytesN<32>, at: u64) {
+    if client.get_admin_signers().len() < 2 {
+        client.add_admin_signer(&Address::generate(env));
+    }
+    let signers = client.get_admin_signers();
+    env.ledger().set_timestamp(at);
+    client.emergency_override(
+        &vec![env, admin.clone(), signers.get(1).unwrap()],
+        id,
+        &0u32,
+        &BytesN::from_array(env, &[7u8; 32]),
+    );
+}
+
+#[test]
+fn test_override_allowed_exactly_when_cooldown_ends() {
+    let env = create_test_env();
+    let (client, admin, _oracle1, _oracle2, market_id) = boundary_setup(&env);
+    let cooldown = client.get_override_cooldown();
+
+    override_at(&env, &client, &admin, &market_id, 5000);
+    override_at(&env, &client, &admin, &market_id, 5000 + cooldown);
+
+    assert_eq!(client.get_last_override_time(), 5000 + cooldown);
+}
+
+#[test]
+#[should_panic(expected = "Cooldown period not elapsed")]
+fn test_override_rejected_one_second_before_cooldown_ends() {
+    let env = create_test_env();
+    let (client, admin, _oracle1, _oracle2, market_id) = boundary_setup(&env);
+    let cooldown = client.get_override_cooldown();
+
+    override_at(&env, &client, &admin, &market_id, 5000);
+    override_at(&env, &client, &admin, &market_id, 5000 + cooldown - 1);
+}
+
+#[test]
+#[should_panic(expected = "Cooldown period not elapsed")]
+fn test_override_cooldown_holds_when_ledger_time_regresses() {
+    let env = create_test_env();
+    let (client, admin, _oracle1, _oracle2, market_id) = boundary_setup(&env);
+
+    // now < last_override_time: subtracting the two would underflow
+    override_at(&env, &client, &admin, &market_id, 5000);
+    override_at(&env, &client, &admin, &market_id, 4000);
+}
+
+// ===== RESOLUTION SUBSCRIPTION TESTS =====
+
+/// Subscriber whose callback always fails
+#[soroban_sdk::contract]
+struct FailingSubscriber;
+
+#[soroban_sdk::contractimpl]
+impl FailingSubscriber {
+    pub fn on_resolution(_env: Env, _round: boxmeout::oracle::RoundData) {
+        panic!("subscriber failure");
+    }
+}
+
+#[test]
+fn test_failing_subscriber_does_not_block_resolution() {
+    let env = create_test_env();
+    let (client, admin, oracle1, oracle2, market_id) = boundary_setup(&env);
+    let market_address = closed_boundary_market(&env, &client, &market_id);
+    let subscriber = env.register(FailingSubscriber, ());
+    client.subscribe_resolution(&subscriber, &market_id);
+    env.ledger().set_timestamp(BOUNDARY_RESOLUTION_TIME);
+    attest_both(&env, &client, &oracle1, &oracle2, &market_id);
+
+    env.ledger()
+        .set_timestamp(BOUNDARY_RESOLUTION_TIME + DISPUTE_PERIOD);
+    client.finalize_resolution(&market_id, &market_address);
+    let round = client.latest_round_data(&market_id).unwrap();
+    assert_eq!(
+        (round.round_id, round.outcome, round.overridden),
+        (1, 1, false)
+    );
+    assert_eq!(
+        round.attestor_digest,
+        client.get_resolution_proof(&market_id).attestor_digest
+    );
+
+    // An override publishes a new round on top of the finalized one
+    override_at(
+        &env,
+        &client,
+        &admin,
+        &market_id,
+        BOUNDARY_RESOLUTION_TIME + DISPUTE_PERIOD + 1,
+    );
+    let round = client.latest_round_data(&market_id).unwrap();
+    assert_eq!(
+        (round.round_id, round.outcome, round.overridden),
+        (2, 0, true)
+    );
+}
+
+#[test]
+fn test_resolution_subscriptions_are_bounded_and_removable() {
+    let env = create_test_env();
+    let (client, _admin, _oracle1, _oracle2, market_id) = boundary_setup(&env);
+    assert!(client.latest_round_data(&market_id).is_none());
+
+    let first = Address::generate(&env);
+    client.subscribe_resolution(&first, &market_id);
+    assert!(client.try_subscribe_resolution(&first, &market_id).is_err());
+    for _ in 1..MAX_RESOLUTION_SUBSCRIBERS {
+        client.subscribe_resolution(&Address::generate(&env), &market_id);
+    }
+    assert!(client
+        .try_subscribe_resolution(&Address::generate(&env), &market_id)
+        .is_err());
+
+    client.unsubscribe_resolution(&first, &market_id);
+    assert_eq!(
+        client.get_resolution_subscribers(&market_id).len(),
+        MAX_RESOLUTION_SUBSCRIBERS - 1
+    );
+    assert!(!client
+        .get_resolution_subscribers(&market_id)
+        .contains(first));
+}
+
+// ===== ALERT HOOK TESTS =====
+
+/// Alert hook that keeps every alert it receives
+#[soroban_sdk::contract]
+struct RecordingHook;
+
+#[soroban_sdk::contractimpl]
+impl RecordingHook {
+    pub fn on_alert(env: Env, kind: Symbol, market_id: BytesN<32>, severity: u32) {
+        let key = Symbol::new(&env, "alerts");
+        let mut alerts: soroban_sdk::Vec<(Symbol, BytesN<32>, u32)> = env
+            .storage()
+            .instance()
+            .get(&key)
+            .unwrap_or(soroban_sdk::Vec::new(&env));
+        alerts.push_back((kind, market_id, severity));
+        env.storage().instance().set(&key, &alerts);
+    }
+
+    pub fn alerts(env: Env) -> soroban_sdk::Vec<(Symbol, BytesN<32>, u32)> {
+        env.storage()
+            .instance()
+            .get(&Symbol::new(&env, "alerts"))
+            .unwrap_or(soroban_sdk::Vec::new(&env))
+    }
+}
+
+/// Alert hook that always traps
+#[soroban_sdk::contract]
+struct TrappingHook;
+
+#[soroban_sdk::contractimpl]
+impl TrappingHook {
+    pub fn on_alert(_env: Env, _kind: Symbol, _market_id: BytesN<32>, _severity: u32) {
+        panic!("pager down");
+    }
+}
+
+#[test]
+fn test_override_pages_alert_hook() {
+    let env = create_test_env();
+    let (client, admin, _oracle1, _oracle2, market_id) = boundary_setup(&env);
+    let hook = env.register(RecordingHook, ());
+    client.set_alert_hook(&Some(hook.clone()));
+    assert_eq!(client.get_alert_hook(), Some(hook.clone()));
+
+    override_at(&env, &client, &admin, &market_id, 5000);
+
+    let expected = (
+        Symbol::new(&env, "override"),
+        market_id.clone(),
+        SEVERITY_CRITICAL,
+    );
+    assert_eq!(
+        RecordingHookClient::new(&env, &hook).alerts(),
+        vec![&env, expected]
+    );
+    let log = client.get_alert_log();
+    assert_eq!(log.len(), 1);
+    let delivery = log.get_unchecked(0);
+    assert!(delivery.delivered);
+    assert_eq!(delivery.hook, hook);
+    assert_eq!(delivery.timestamp, 5000);
+
+    // Without a hook nothing is paged or logged
+    client.set_alert_hook(&None);
+    override_at(
+        &env,
+        &client,
+        &admin,
+        &market_id,
+        5000 + client.get_override_cooldown(),
+    );
+    assert_eq!(client.get_alert_log().len(), 1);
+}
+
+#[test]
+fn test_trapping_alert_hook_does_not_block_override() {
+    let env = create_test_env();
+    let (client, admin, _oracle1, _oracle2, market_id) = boundary_setup(&env);
+    let hook = env.register(TrappingHook, ());
+    client.set_alert_hook(&Some(hook.clone()));
+
+    override_at(&env, &client, &admin, &market_id, 5000);
+
+    assert!(client.is_manual_override(&market_id));
+    assert_eq!(client.get_consensus_result(&market_id), 0);
+    assert_eq!(
+        client.get_override_record(&market_id).unwrap().timestamp,
+        5000
+    );
+    // The failed delivery is on record
+    let log = client.get_alert_log();
+    assert_eq!(log.len(), 1);
+    let failure = log.get_unchecked(0);
+    assert!(!failure.delivered);
+    assert_eq!(failure.kind, Symbol::new(&env, "override"));
+    assert_eq!(failure.market_id, market_id);
+    assert_eq!(failure.hook, hook);
+}
+
+#[test]
+#[should_panic]
+fn test_set_alert_hook_requires_admin() {
+    let env = create_test_env();
+    let (client, _admin, _oracle1, _oracle2, _market_id) = boundary_setup(&env);
+    env.set_auths(&[]);
+    client.set_alert_hook(&Some(Address::generate(&env)));
+}
+
+// ===== TIE-BREAK TESTS =====
+
+/// Helper: 2-of-2 oracle with a market split 1 YES / 1 NO
+fn tied_market(env: &Env) -> (OracleManagerClient<'_>, BytesN<32>) {
+    let (client, _admin, oracle1, oracle2, market_id) = boundary_setup(env);
+    env.ledger().set_timestamp(BOUNDARY_RESOLUTION_TIME);
+    let data_hash = BytesN::from_array(env, &[0u8; 32]);
+    client.submit_attestation(&oracle1, &market_id, &1u32, &data_hash, &None);
+    client.submit_attestation(&oracle2, &market_id, &0u32, &data_hash, &None);
+    (client, market_id)
+}
+
+#[test]
+fn test_tie_stays_unresolved_by_default() {
+    let env = create_test_env();
+    let (client, market_id) = tied_market(&env);
+
+    assert_eq!(client.get_tie_break_mode(), TieBreakMode::None);
+    assert_eq!(client.check_consensus(&market_id), (false, 0));
+}
+
+#[test]
+fn test_random_tie_break_is_stable_for_market_and_ledger() {
+    let env = create_test_env();
+    let (client, market_id) = tied_market(&env);
+    client.set_tie_break_mode(&TieBreakMode::Random);
+    env.ledger().set_sequence_number(777);
+
+    let (reached, outcome) = client.check_consensus(&market_id);
+    assert!(reached);
+    assert_eq!(client.check_consensus(&market_id), (true, outcome));
+
+    let seed = Bytes::from_array(&env, &market_id.to_array());
+    assert_eq!(outcome as u64, rand::deterministic_choice(&env, &[seed], 2));
+}
+
+/// Helper: three oracles (required consensus 2) and a scalar market past resolution
+fn scalar_setup(env: &Env) -> (OracleManagerClient<'_>, [Address; 3], BytesN<32>) {
+    let fixture = OracleFixture::new(env)
+        .with_oracles(3)
+        .with_market([31u8; 32], BOUNDARY_RESOLUTION_TIME);
+    env.ledger().set_timestamp(BOUNDARY_RESOLUTION_TIME);
+
+    let oracles = [fixture.oracle(0), fixture.oracle(1), fixture.oracle(2)];
+    let market_id = fixture.market(0);
+    (fixture.client, oracles, market_id)
+}
+
+/// Helper: halve an oracle's stake through an upheld challenge on a throwaway market
+fn slash_once(env: &Env, client: &OracleManagerClient, oracle: &Address, seed: u8) {
+    let market_id = BytesN::from_array(env, &[seed; 32]);
+    client.register_market(
+        &market_id,
+        &BOUNDARY_RESOLUTION_TIME,
+        &Symbol::new(env, CATEGORY_ANY),
+    );
+    client.submit_attestation(
+        oracle,
+        &market_id,
+        &1u32,
+        &BytesN::from_array(env, &[0u8; 32]),
+        &None,
+    );
+    client.challenge_attestation(
+        &Address::generate(env),
+        oracle,
+        &market_id,
+        &Symbol::new(env, "wrong"),
+    );
+    client.resolve_challenge(oracle, &market_id, &true);
+}
+
+#[test]
+fn test_value_consensus_weighted_and_plain_medians_differ() {
+    let env = create_test_env();
+    let (client, [heavy, light1, light2], market_id) = scalar_setup(&env);
+    // Stakes: heavy 10000, light oracles 2500 each
+    for (i, oracle) in [&light1, &light2].iter().enumerate() {
+        slash_once(&env, &client, oracle, 100 + 2 * i as u8);
+        slash_once(&env, &client, oracle, 101 + 2 * i as u8);
+    }
+    assert_eq!(client.get_oracle_stake(&light1), 2_500);
+
+    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
+    client.submit_value(&heavy, &market_id, &300, &data_hash, &None);
+    client.submit_value(&light1, &market_id, &100, &data_hash, &None);
+    client.submit_value(&light2, &market_id, &200, &data_hash, &None);
+
+    // Staking disabled: plain median
+    assert_eq!(client.check_value_consensus(&market_id), (true, 200));
+
+    client.set_staking_config(&true, &5000u32);
+    assert_eq!(client.check_value_consensus(&market_id), (true, 300));
+    assert_eq!(client.get_participation_bps(&market_id), 10_000);
+}
+
+#[test]
+fn test_value_consensus_requires_participating_stake() {
+    let env = create_test_env();
+    let (client, [oracle1, oracle2, oracle3], market_id) = scalar_setup(&env);
+    client.set_staking_config(&true, &6667u32);
+
+    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
+    client.submit_value(&oracle1, &market_id, &42, &data_hash, &None);
+    client.submit_value(&oracle2, &market_id, &44, &data_hash, &None);
+
+    // Two of three equal stakes is 66.66%, below the floor
+    assert_eq!(client.get_participation_bps(&market_id), 6666);
+    assert_eq!(client.check_value_consensus(&market_id), (false, 0));
+
+    client.submit_value(&oracle3, &market_id, &43, &data_hash, &None);
+    assert_eq!(client.get_participation_bps(&market_id), 10_000);
+    assert_eq!(client.check_value_consensus(&market_id), (true, 43));
+}
+
+#[test]
+fn test_value_consensus_waits_for_required_count() {
+    let env = create_test_env();
+    let (client, [oracle1, _, _], market_id) = scalar_setup(&env);
+    assert!(!client.is_staking_enabled());
+
+    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
+    client.submit_value(&oracle1, &market_id, &42, &data_hash, &None);
+
+    assert_eq!(client.get_value(&market_id, &oracle1), Some(42));
+    assert_eq!(client.check_value_consensus(&market_id), (false, 0));
+}
+
+#[test]
+#[should_panic(expected = "Oracle already attested")]
+fn test_submit_value_twice_rejected() {
+    let env = create_test_env();
+    let (client, [oracle1, _, _], market_id) = scalar_setup(&env);
+    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
+
+    client.submit_value(&oracle1, &market_id, &42, &data_hash, &None);
+    client.submit_value(&oracle1, &market_id, &43, &data_hash, &None);
+}
+
+// ===== BATCHED RESOLUTION STATES =====
+
+/// Helper: MAX_RESOLUTION_BATCH markets, every third one voted to YES consensus
+fn resolution_batch_setup(env: &Env) -> (OracleManagerClient<'_>, soroban_sdk::Vec<BytesN<32>>) {
+    let mut fixture = OracleFixture::new(env).with_oracles(2);
+    env.ledger().set_timestamp(BOUNDARY_RESOLUTION_TIME);
+    for i in 0..MAX_RESOLUTION_BATCH {
+        fixture = fixture.with_market([100 + i as u8; 32], BOUNDARY_RESOLUTION_TIME - i as u64);
+        if i % 3 == 0 {
+            for oracle in fixture.oracles.iter() {
+                fixture.attest(&oracle, &fixture.market(i), 1);
+            }
+        }
+    }
+    (fixture.client, fixture.markets)
+}
+
+#[test]
+fn test_resolution_states_match_single_getter_in_order() {
+    let env = create_test_env();
+    let (client, registered) = resolution_batch_setup(&env);
+    let unknown = BytesN::from_array(&env, &[250u8; 32]);
+
+    let mut ids = soroban_sdk::Vec::new(&env);
+    ids.push_back(registered.get(4).unwrap());
+    ids.push_back(unknown.clone());
+    ids.push_back(registered.get(3).unwrap());
+    ids.push_back(registered.get(0).unwrap());
+
+    let states = client.get_resolution_states(&ids);
+    assert_eq!(states.len(), 4);
+    for (id, state) in ids.iter().zip(states.iter()) {
+        assert_eq!(state, client.get_market_resolution_state(&id));
+    }
+    assert_eq!(states.get(1).unwrap(), None);
+
+    let pending = states.get(0).unwrap().unwrap();
+    assert_eq!(pending.market_id, registered.get(4).unwrap());
+    assert_eq!(pending.resolution_time, BOUNDARY_RESOLUTION_TIME - 4);
+    assert!(!pending.consensus_reached);
+    let voted = states.get(2).unwrap().unwrap();
+    assert_eq!(voted.tally, (2, 0));
+    assert!(voted.consensus_reached && !voted.finalized);
+    assert_eq!(voted.consensus_outcome, 1);
+}
+
+#[test]
+fn test_resolution_states_full_batch_fits_budget() {
+    let env = create_test_env();
+    let (client, market_ids) = resolution_batch_setup(&env);
+
+    env.cost_estimate().budget().reset_default();
+    let states = client.get_resolution_states(&market_ids);
+    assert_eq!(states.len(), MAX_RESOLUTION_BATCH);
+    // Well under the 100M instruction simulation limit
+    assert!(env.cost_estimate().budget().cpu_instruction_cost() < 25_000_000);
+}
+
+#[test]
+#[should_panic(expected = "Too many markets")]
+fn test_resolution_states_rejects_oversized_batch() {
+    let env = create_test_env();
+    let (client, mut market_ids) = resolution_batch_setup(&env);
+    market_ids.push_back(BytesN::from_array(&env, &[250u8; 32]));
+    client.get_resolution_states(&market_ids);
+}
+
+/// Helper: sha256(payload || metadata), the strict-mode data_hash
+fn commitment(env: &Env, payload: &[u8], metadata: &Bytes) -> BytesN<32> {
+    let mut preimage = Bytes::from_slice(env, payload);
+    preimage.append(metadata);
+    env.crypto().sha256(&preimage).into()
+}
+
+#[test]
+fn test_attestation_metadata_stored_and_archived() {
+    let env = create_test_env();
+    let score = Bytes::from_slice(&env, b"final:3-1");
+    let (client, market_id, attestors) = finalized_market_with_metadata(&env, &Some(score.clone()));
+
+    let attestation = client
+        .get_attestation(&market_id, &attestors.get(0).unwrap())
+        .unwrap();
+    assert_eq!(attestation.metadata, Some(score.clone()));
+    assert_eq!(attestation.data_hash, BytesN::from_array(&env, &[0u8; 32]));
+
+    let archive = client.cleanup_market(&market_id);
+    assert_eq!(archive.metadata.len(), 3);
+    for (i, (attestor, blob)) in archive.metadata.iter().enumerate() {
+        assert_eq!(attestor, attestors.get(i as u32).unwrap());
+        assert_eq!(blob, score);
+    }
+}
+
+#[test]
+fn test_attestation_metadata_size_bound() {
+    let env = create_test_env();
+    let (client, oracles, market_id) = scalar_setup(&env);
+    let data_hash = BytesN::from_array(&env, &[0u8; 32]);
+
+    let oversized = Bytes::from_array(&env, &[7u8; MAX_METADATA_LEN as usize + 1]);
+    assert_eq!(
+        client.try_submit_attestation(
+            &oracles[0],
+            &market_id,
+            &1u32,
+            &data_hash,
+            &Some(oversized.clone())
+        ),
+        Err(Ok(OracleError::MetadataTooLarge.into()))
+    );
+    assert_eq!(
+        client.try_submit_value(
+            &oracles[0],
+            &market_id,
+            &42i128,
+            &data_hash,
+            &Some(oversized)
+        ),
+        Err(Ok(OracleError::MetadataTooLarge.into()))
+    );
+
+    let largest = Bytes::from_array(&env, &[7u8; MAX_METADATA_LEN as usize]);
+    client.submit_attestation(
+        &oracles[0],
+        &market_id,
+        &1u32,
+        &data_hash,
+        &Some(largest.clone()),
+    );
+    client.submit_value(
+        &oracles[0],
+        &market_id,
+        &42i128,
+        &data_hash,
+        &Some(largest.clone()),
+    );
+    assert_eq!(
+        client.get_value_metadata(&market_id, &oracles[0]),
+        Some(largest)
+    );
+    assert_eq!(client.get_value_metadata(&market_id, &oracles[1]), None);
+}
+
+#[test]
+fn test_strict_data_hash_commits_to_metadata() {
+    let env = create_test_env();
+    let (client, oracles, market_id) = scalar_setup(&env);
+    client.set_strict_data_hash(&true);
+    assert!(client.is_strict_data_hash());
+
+    let score = Bytes::from_slice(&env, b"final:3-1");
+    let yes_hash = commitment(&env, &1u32.to_be_bytes(), &score);
+
+    // A hash over the outcome alone, or the wrong outcome, is rejected
+    let outcome_only = commitment(&env, &1u32.to_be_bytes(), &Bytes::new(&env));
+    assert_eq!(
+        client.try_submit_attestation(
+            &oracles[0],
+            &market_id,
+            &1u32,
+            &outcome_only,
+            &Some(score.clone())
+        ),
+        Err(Ok(OracleError::DataHashMismatch.into()))
+    );
+    assert_eq!(
+        client.try_submit_attestation(
+            &oracles[0],
+            &market_id,
+            &0u32,
+            &yes_hash,
+            &Some(score.clone())
+        ),
+        Err(Ok(OracleError::DataHashMismatch.into()))
+    );
+
+    client.submit_attestation(
+        &oracles[0],
+        &market_id,
+        &1u32,
+        &yes_hash,
+        &Some(score.clone()),
+    );
+    client.submit_attestation(&oracles[1], &market_id, &1u32, &outcome_only, &None);
+
+    let value_hash = commitment(&env, &42i128.to_be_bytes(), &score);
+    assert_eq!(
+        client.try_submit_value(
+            &oracles[0],
+            &market_id,
+            &41i128,
+            &value_hash,
+            &Some(score.clone())
+        ),
+        Err(Ok(OracleError::DataHashMismatch.into()))
+    );
+    client.submit_value(&oracles[0], &market_id, &42i128, &value_hash, &Some(score));
+}
+
+// ===== ATTESTATION INVALIDATION TESTS =====
+
+/// Helper: boundary market with both oracles agreeing on YES and a standby third oracle
+fn agreed_market(
+    env: &Env,
+) -> (
+    OracleManagerClient<'_>,
+    Address,
+    Address,
+    Address,
+    BytesN<32>,
+) {
+    let (client, _admin, oracle1, oracle2, market_id) = boundary_setup(env);
+    let standby = Address::generate(env);
+    client.register_oracle(&standby, &Symbol::new(env, "O3"), &Vec::new(env));
+    env.ledger().set_timestamp(BOUNDARY_RESOLUTION_TIME);
+    attest_both(env, &client, &oracle1, &oracle2, &market_id);
+    assert_eq!(client.check_consensus(&market_id), (true, 1));
+    (client, oracle1, oracle2, standby, market_id)
+}
+
+/// Helper: challenge `oracle`'s attestation and uphold it
+fn uphold_challenge(env: &Env, client: &OracleManagerClient, oracle: &Address, id: &BytesN<32>) {
+    client.challenge_attestation(
+        &Address::generate(env),
+        oracle,
+        id,
+        &Symbol::new(env, "dishonest"),
+    );
+    client.resolve_challenge(oracle, id, &true);
+}
+
+#[test]
+fn test_upheld_challenge_clears_consensus_and_third_oracle_restores_it() {
+    let env = create_test_env();
+    let (client, _oracle1, oracle2, standby, market_id) = agreed_market(&env);
+
+    uphold_challenge(&env, &client, &oracle2, &market_id);
+    assert_eq!(client.check_consensus(&market_id), (false, 0));
+    assert_eq!(client.get_attestation_counts(&market_id), (1, 0));
+    assert!(client.get_attestation(&market_id, &oracle2).is_none());
+    let state = client.get_market_resolution_state(&market_id).unwrap();
+    assert!(!state.consensus_reached);
+    assert_eq!(state.tally, (1, 0));
+
+    client.submit_attestation(
+        &standby,
+        &market_id,
+        &1u32,
+        &BytesN::from_array(&env, &[0u8; 32]),
+        &None,
+    );
+    assert_eq!(client.check_consensus(&market_id), (true, 1));
+    assert_eq!(client.get_attestation_counts(&market_id), (2, 0));
+}
+
+#[test]
+fn test_rejected_challenge_keeps_vote() {
+    let env = create_test_env();
+    let (client, oracle1, _oracle2, _standby, market_id) = agreed_market(&env);
+
+    client.challenge_attestation(
+        &Address::generate(&env),
+        &oracle1,
+        &market_id,
+        &Symbol::new(&env, "dishonest"),
+    );
+    client.resolve_challenge(&oracle1, &market_id, &false);
+    assert_eq!(client.check_consensus(&market_id), (true, 1));
+    assert_eq!(client.get_reopened_until(&market_id), 0);
+}
+
+#[test]
+#[should_panic(expected = "Attestation was invalidated")]
+fn test_voided_oracle_cannot_attest_again() {
+    let env = create_test_env();
+    let (client, _oracle1, oracle2, _standby, market_id) = agreed_market(&env);
+    uphold_challenge(&env, &client, &oracle2, &market_id);
+
+    client.submit_attestation(
+        &oracle2,
+        &market_id,
+        &1u32,
+        &BytesN::from_array(&env, &[0u8; 32]),
+        &None,
+    );
+}
+
+#[test]
+fn test_invalidation_reopens_finalization_for_configured_window() {
+    let env = create_test_env();
+    let (client, oracle1, _oracle2, standby, market_id) = agreed_market(&env);
+    let market_address = closed_boundary_market(&env, &client, &market_id);
+    client.set_reattestation_window(&3600);
+
+    // Voided just as the dispute period ends
+    let voided_at = BOUNDARY_RESOLUTION_TIME + DISPUTE_PERIOD;
+    env.ledger().set_timestamp(voided_at);
+    client.invalidate_attestation(&market_id, &oracle1);
+    assert_eq!(client.get_reopened_until(&market_id), voided_at + 3600);
+    client.submit_attestation(
+        &standby,
+        &market_id,
+        &1u32,
+        &BytesN::from_array(&env, &[0u8; 32]),
+        &None,
+    );
+
+    env.ledger().set_timestamp(voided_at + 3599);
+    let early = client.try_finalize_resolution(&market_id, &market_address);
+    assert!(early.is_err());
+
+    env.ledger().set_timestamp(voided_at + 3600);
+    client.finalize_resolution(&market_id, &market_address);
+    assert_eq!(client.get_consensus_result(&market_id), 1);
+}
+
+#[test]
+#[should_panic(expected = "Market already finalized")]
+fn test_invalidate_attestation_rejects_finalized_market() {
+    let env = create_test_env();
+    let (client, oracle1, _oracle2, _standby, market_id) = agreed_market(&env);
+    let market_address = closed_boundary_market(&env, &client, &market_id);
+    env.ledger()
+        .set_timestamp(BOUNDARY_RESOLUTION_TIME + DISPUTE_PERIOD);
+    client.finalize_resolution(&market_id, &market_address);
+
+    client.invalidate_attestation(&market_id, &oracle1);
+}
+
+#[test]
+fn test_invalidate_attestation_requires_admin() {
+    let env = create_test_env();
+    let (client, oracle1, _oracle2, _standby, market_id) = agreed_market(&env);
+
+    env.set_auths(&[]);
+    let result = client.try_invalidate_attestation(&market_id, &oracle1);
+    assert!(result.is_err());
+}
+
+// ===== MARKET REGISTRAR TESTS =====
+
+#[test]
+fn test_registrar_registers_market() {
+    let env = create_test_env();
+    let fixture = OracleFixture::new(&env);
+    let registrar = Address::generate(&env);
+    let market_id = BytesN::from_array(&env, &[21u8; 32]);
+
+    assert!(!fixture.client.is_registrar(&registrar));
+    fixture.client.add_registrar(&fixture.admin, &registrar);
+    let (topics, data) = oracle_event(&env, &fixture.client.address, "registrar");
+    let expected: Vec<Val> = (Symbol::new(&env, "registrar"), registrar.clone()).into_val(&env);
+    assert_eq!(topics, expected);
+    assert_eq!(<(bool,)>::try_from_val(&env, &data).unwrap(), (true,));
+    assert!(fixture.client.is_registrar(&registrar));
+
+    fixture.client.register_market_as(
+        &registrar,
+        &market_id,
+        &1000,
+        &Symbol::new(&env, CATEGORY_ANY),
+    );
+    assert_eq!(
+        fixture.client.get_market_resolution_time(&market_id),
+        Some(1000)
+    );
+    assert_eq!(
+        fixture.client.get_market_registrar(&market_id),
+        Some(registrar)
+    );
+}
+
+#[test]
+fn test_admin_registration_is_recorded() {
+    let env = create_test_env();
+    let fixture = OracleFixture::new(&env);
+    let direct = BytesN::from_array(&env, &[22u8; 32]);
+    let via_role_path = BytesN::from_array(&env, &[23u8; 32]);
+
+    fixture
+        .client
+        .register_market(&direct, &1000, &Symbol::new(&env, CATEGORY_ANY));
+    fixture.client.register_market_as(
+        &fixture.admin,
+        &via_role_path,
+        &1000,
+        &Symbol::new(&env, CATEGORY_ANY),
+    );
+
+    assert_eq!(
+        fixture.client.get_market_registrar(&direct),
+        Some(fixture.admin.clone())
+    );
+    assert_eq!(
+        fixture.client.get_market_registrar(&via_role_path),
+        Some(fixture.admin.clone())
+    );
+    assert_eq!(
+        fixture
+            .client
+            .get_market_registrar(&BytesN::from_array(&env, &[24u8; 32])),
+        None
+    );
+}
+
+#[test]
+fn test_revoked_registrar_rejected_but_markets_kept() {
+    let env = create_test_env();
+    let fixture = OracleFixture::new(&env).with_oracles(2);
+    let registrar = Address::generate(&env);
+    let earlier = BytesN::from_array(&env, &[25u8; 32]);
+    let later = BytesN::from_array(&env, &[26u8; 32]);
+
+    fixture.client.add_registrar(&fixture.admin, &registrar);
+    fixture
+        .client
+        .register_market_as(&registrar, &earlier, &0, &Symbol::new(&env, CATEGORY_ANY));
+    fixture.client.remove_registrar(&fixture.admin, &registrar);
+    let (_, data) = oracle_event(&env, &fixture.client.address, "registrar");
+    assert_eq!(<(bool,)>::try_from_val(&env, &data).unwrap(), (false,));
+    assert!(!fixture.client.is_registrar(&registrar));
+
+    let result = fixture.client.try_register_market_as(
+        &registrar,
+        &later,
+        &0,
+        &Symbol::new(&env, CATEGORY_ANY),
+    );
+    assert!(result.is_err());
+    assert_eq!(fixture.client.get_market_resolution_time(&later), None);
+
+    // The earlier market stays registered, audited and attestable
+    assert_eq!(
+        fixture.client.get_market_registrar(&earlier),
+        Some(registrar)
+    );
+    fixture.attest(&fixture.oracle(0), &earlier, 1);
+    assert_eq!(fixture.client.get_attestation_counts(&earlier), (1, 0));
+}
+
+// ===== CATEGORY ROUTING TESTS =====
+
+/// Helper: five oracles and a threshold of 3; oracles 0-1 cover "Boxing", 2-4 "Crypto"
+fn setup_category_oracles(env: &Env) -> OracleFixture {
+    let fixture = OracleFixture::new(env).with_oracles(5).with_threshold(3);
+    for i in 0..5 {
+        let category = if i < 2 { "Boxing" } else { "Crypto" };
+        fixture
+            .client
+            .set_oracle_categories(&fixture.oracle(i), &vec![env, Symbol::new(env, category)]);
+    }
+    fixture
+}
+
+/// Helper: register `id` under `category`, resolvable at 1000, and move past it
+fn register_category_market(fixture: &OracleFixture, id: u8, category: &str) -> BytesN<32> {
+    let env = &fixture.env;
+    let market_id = BytesN::from_array(env, &[id; 32]);
+    fixture
+        .client
+        .register_market(&market_id, &1000, &Symbol::new(env, category));
+    env.ledger().set_timestamp(1000);
+    market_id
+}
+
+#[test]
+#[should_panic(expected = "Oracle not assigned to market category")]
+fn test_out_of_category_attestation_rejected() {
+    let env = create_test_env();
+    let fixture = setup_category_oracles(&env);
+    let market_id = register_category_market(&fixture, 31, "Boxing");
+
+    // A crypto price feed cannot vote on a fight
+    fixture.attest(&fixture.oracle(2), &market_id, 1);
+}
+
+#[test]
+fn test_category_consensus_among_eligible_oracles() {
+    let env = create_test_env();
+    let fixture = setup_category_oracles(&env);
+    let boxing = register_category_market(&fixture, 32, "Boxing");
+    let open = register_category_market(&fixture, 33, CATEGORY_ANY);
+    assert_eq!(
+        fixture.client.get_market_category(&boxing),
+        Symbol::new(&env, "Boxing")
+    );
+
+    // Two of five oracles cover Boxing: both agreeing is consensus
+    fixture.attest(&fixture.oracle(0), &boxing, 1);
+    assert_eq!(fixture.client.check_consensus(&boxing), (false, 0));
+    fixture.attest(&fixture.oracle(1), &boxing, 1);
+    assert_eq!(fixture.client.check_consensus(&boxing), (true, 1));
+
+    // Uncategorized markets keep the global threshold of 3
+    fixture.attest(&fixture.oracle(0), &open, 1);
+    fixture.attest(&fixture.oracle(2), &open, 1);
+    assert_eq!(fixture.client.check_consensus(&open), (false, 0));
+}
+
+#[test]
+fn test_uncovered_category_falls_back_to_any_oracle() {
+    let env = create_test_env();
+    let fixture = setup_category_oracles(&env);
+    let darts = register_category_market(&fixture, 34, "Darts");
+
+    fixture.attest(&fixture.oracle(0), &darts, 0);
+    fixture.attest(&fixture.oracle(2), &darts, 0);
+    fixture.attest(&fixture.oracle(4), &darts, 0);
+    assert_eq!(fixture.client.check_consensus(&darts), (true, 0));
+}
+
+#[test]
+fn test_set_oracle_categories_reassigns_oracle() {
+    let env = create_test_env();
+    let fixture = setup_category_oracles(&env);
+    let boxing = register_category_market(&fixture, 35, "Boxing");
+    let categories = vec![
+        &env,
+        Symbol::new(&env, "Crypto"),
+        Symbol::new(&env, "Boxing"),
+    ];
+
+    fixture
+        .client
+        .set_oracle_categories(&fixture.oracle(2), &categories);
+    let (topics, data) = oracle_event(&env, &fixture.client.address, "oracle_cats");
+    let expected: Vec<Val> = (Symbol::new(&env, "oracle_cats"), fixture.oracle(2)).into_val(&env);
+    assert_eq!(topics, expected);
+    assert_eq!(
+        <(Vec<Symbol>,)>::try_from_val(&env, &data).unwrap(),
+        (categories.clone(),)
+    );
+    assert_eq!(
+        fixture.client.get_oracle_categories(&fixture.oracle(2)),
+        categories
+    );
+
+    // Now three Boxing oracles: the global threshold of 3 applies again
+    fixture.attest(&fixture.oracle(2), &boxing, 1);
+    fixture.attest(&fixture.oracle(0), &boxing, 1);
+    assert_eq!(fixture.client.check_consensus(&boxing), (false, 0));
+    fixture.attest(&fixture.oracle(1), &boxing, 1);
+    assert_eq!(fixture.client.check_consensus(&boxing), (true, 1));
+}
+
+#[test]
+fn test_category_oracle_count_tracks_active_set() {
+    let env = create_test_env();
+    let fixture = setup_category_oracles(&env);
+    let boxing_symbol = Symbol::new(&env, "Boxing");
+    let boxing = register_category_market(&fixture, 36, "Boxing");
+    assert_eq!(fixture.client.get_category_oracle_count(&boxing_symbol), 2);
+
+    // Repeating a category in an assignment counts the oracle once
+    fixture.client.set_oracle_categories(
+        &fixture.oracle(2),
+        &vec![&env, boxing_symbol.clone(), boxing_symbol.clone()],
+    );
+    assert_eq!(fixture.client.get_category_oracle_count(&boxing_symbol), 3);
+    assert_eq!(
+        fixture
+            .client
+            .get_category_oracle_count(&Symbol::new(&env, "Crypto")),
+        2
+    );
+
+    // A deregistered oracle leaves the count and may no longer attest
+    fixture.client.deregister_oracle(&fixture.oracle(0));
+    assert_eq!(fixture.client.get_category_oracle_count(&boxing_symbol), 2);
+    assert!(fixture
+        .client
+        .try_submit_attestation(
+            &fixture.oracle(0),
+            &boxing,
+            &1,
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &None,
+        )
+        .is_err());
+
+    // Re-assigning an inactive oracle leaves the count alone
+    fixture
+        .client
+        .set_oracle_categories(&fixture.oracle(0), &vec![&env, boxing_symbol.clone()]);
+    assert_eq!(fixture.client.get_category_oracle_count(&boxing_symbol), 2);
+
+    // Two remaining Boxing oracles meet the capped threshold
+    fixture.attest(&fixture.oracle(1), &boxing, 1);
+    fixture.attest(&fixture.oracle(2), &boxing, 1);
+    assert_eq!(fixture.client.check_consensus(&boxing), (true, 1));
+}
+
+// ===== ATTESTATION BOND TESTS =====
+
+const BOND: i128 = 100;
+
+/// Stand-in market contract that accepts any resolution
+#[contract]
+struct ResolutionSink;
+
+#[contractimpl]
+impl ResolutionSink {
+    pub fn resolve_with_outcome(_env: Env, _market_id: BytesN<32>, _outcome: u32) {}
+}
+
+/// Helper: three funded oracles, BOND per attestation, votes NO/NO/YES past resolution time
+fn bonded_market(env: &Env) -> (OracleFixture, token::StellarAssetClient<'static>, Address) {
+    let fixture = OracleFixture::new(env)
+        .with_oracles(3)
+        .with_market([41u8; 32], 1000);
+    let token = deploy_token(env);
+    let treasury = Address::generate(env);
+    fund(
+        &token,
+        &[&fixture.oracle(0), &fixture.oracle(1), &fixture.oracle(2)],
+        1000,
+    );
+    fixture
+        .client
+        .set_attestation_bond(&token.address, &treasury, &BOND);
+
+    env.ledger().set_timestamp(1000);
+    let market_id = fixture.market(0);
+    fixture.attest(&fixture.oracle(0), &market_id, 0);
+    fixture.attest(&fixture.oracle(1), &market_id, 0);
+    fixture.attest(&fixture.oracle(2), &market_id, 1);
+    (fixture, token, treasury)
+}
+
+/// Helper: two admin signers approving an override
+fn override_approvers(fixture: &OracleFixture) -> Vec<Address> {
+    fixture
+        .client
+        .add_admin_signer(&Address::generate(&fixture.env));
+    let signers = fixture.client.get_admin_signers();
+    vec![&fixture.env, fixture.admin.clone(), signers.get(1).unwrap()]
+}
+
+#[test]
+fn test_bonds_held_until_outcome() {
+    let env = create_test_env();
+    let (fixture, token, _treasury) = bonded_market(&env);
+    let balance = token::Client::new(&env, &token.address);
+    let market_id = fixture.market(0);
+
+    assert_eq!(balance.balance(&fixture.oracle(0)), 1000 - BOND);
+    assert_eq!(balance.balance(&fixture.client.address), 3 * BOND);
+    let bond = fixture
+        .client
+        .get_bond(&market_id, &fixture.oracle(2))
+        .unwrap();
+    assert_eq!(
+        (bond.payer, bond.amount, bond.outcome),
+        (fixture.oracle(2), BOND, 1)
+    );
+
+    let result = fixture
+        .client
+        .try_reclaim_bond(&fixture.oracle(0), &market_id);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_correct_bond_refunded_and_incorrect_forfeited() {
+    let env = create_test_env();
+    let (fixture, token, treasury) = bonded_market(&env);
+    let balance = token::Client::new(&env, &token.address);
+    let market_id = fixture.market(0);
+    fixture.client.emergency_override(
+        &override_approvers(&fixture),
+        &market_id,
+        &0u32,
+        &BytesN::from_array(&env, &[7u8; 32]),
+    );
+
+    assert_eq!(fixture.client.settle_bonds(&market_id), BOND);
+    assert_eq!(balance.balance(&treasury), BOND);
+    assert_eq!(
+        fixture.client.get_bond(&market_id, &fixture.oracle(2)),
+        None
+    );
+
+    // Correct bonds survive settlement and go back to their oracles
+    assert_eq!(
+        fixture.client.reclaim_bond(&fixture.oracle(0), &market_id),
+        BOND
+    );
+    assert_eq!(
+        fixture.client.reclaim_bond(&fixture.oracle(1), &market_id),
+        BOND
+    );
+    assert_eq!(balance.balance(&fixture.oracle(0)), 1000);
+    assert_eq!(balance.balance(&fixture.client.address), 0);
+
+    // Nothing left to forfeit a second time
+    assert_eq!(fixture.client.settle_bonds(&market_id), 0);
+}
+
+#[test]
+#[should_panic(expected = "Bond forfeited")]
+fn test_incorrect_bond_cannot_be_reclaimed() {
+    let env = create_test_env();
+    let (fixture, _token, _treasury) = bonded_market(&env);
+    let market_id = fixture.market(0);
+    fixture.client.emergency_override(
+        &override_approvers(&fixture),
+        &market_id,
+        &0u32,
+        &BytesN::from_array(&env, &[7u8; 32]),
+    );
+
+    fixture.client.reclaim_bond(&fixture.oracle(2), &market_id);
+}
+
+/// Helper: bind a real market contract to the bonded market and cancel it
+fn cancel_bonded_market(env: &Env, fixture: &OracleFixture) {
+    let market_id = fixture.market(0);
+    let creator = Address::generate(env);
+    let market =
+        boxmeout::market::PredictionMarketClient::new(env, &env.register(PredictionMarket, ()));
+    market.initialize(
+        &market_id,
+        &creator,
+        &Address::generate(env),
+        &deploy_token(env).address,
+        &fixture.client.address,
+        &2000,
+        &3000,
+        &0,
+        &None,
+    );
+    fixture
+        .client
+        .set_market_contract(&market_id, &market.address);
+    market.cancel_market(&creator, &market_id);
+}
+
+#[test]
+fn test_cancelled_market_refunds_every_bond() {
+    let env = create_test_env();
+    let (fixture, token, treasury) = bonded_market(&env);
+    let balance = token::Client::new(&env, &token.address);
+    let market_id = fixture.market(0);
+    cancel_bonded_market(&env, &fixture);
+
+    assert_eq!(fixture.client.settle_bonds(&market_id), 0);
+    assert_eq!(balance.balance(&treasury), 0);
+    for i in 0..3 {
+        assert_eq!(
+            fixture.client.reclaim_bond(&fixture.oracle(i), &market_id),
+            BOND
+        );
+        assert_eq!(balance.balance(&fixture.oracle(i)), 1000);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Outcome not recorded")]
+fn test_bound_uncancelled_market_bonds_stay_held() {
+    let env = create_test_env();
+    let (fixture, _token, _treasury) = bonded_market(&env);
+    let market_id = fixture.market(0);
+    fixture
+        .client
+        .set_market_contract(&market_id, &env.register(PredictionMarket, ()));
+
+    fixture.client.settle_bonds(&market_id);
+}
+
+#[test]
+fn test_unresolvable_market_refunds_every_bond() {
+    let env = create_test_env();
+    let (fixture, token, treasury) = bonded_market(&env);
+    let balance = token::Client::new(&env, &token.address);
+    let market_id = fixture.market(0);
+    fixture.client.mark_unresolvable(
+        &override_approvers(&fixture),
+        &market_id,
+        &BytesN::from_array(&env, &[7u8; 32]),
+        &env.register(ResolutionSink, ()),
+    );
+
+    assert_eq!(fixture.client.settle_bonds(&market_id), 0);
+    assert_eq!(balance.balance(&treasury), 0);
+    for i in 0..3 {
+        fixture.client.reclaim_bond(&fixture.oracle(i), &market_id);
+        assert_eq!(balance.balance(&fixture.oracle(i)), 1000);
+    }
+}
+
+#[test]
+fn test_zero_bond_disables_bonding() {
+    let env = create_test_env();
+    let fixture = OracleFixture::new(&env)
+        .with_oracles(1)
+        .with_market([42u8; 32], 1000);
+    let token = deploy_token(&env);
+    fixture
+        .client
+        .set_attestation_bond(&token.address, &Address::generate(&env), &0);
+
+    // The oracle holds no tokens, so any bond transfer would fail
+    env.ledger().set_timestamp(1000);
+    fixture.attest(&fixture.oracle(0), &fixture.market(0), 1);
+    assert_eq!(
+        fixture
+            .client
+            .get_bond(&fixture.market(0), &fixture.oracle(0)),
+        None
+    );
+}
+
+// ===== OVERRIDE AUDIT LOG TESTS =====
+
+#[test]
+fn test_override_audit_log_pages_across_markets() {
+    let env = create_test_env();
+    let fixture = OracleFixture::new(&env)
+        .with_market([51u8; 32], 1000)
+        .with_market([52u8; 32], 1000)
+        .with_market([53u8; 32], 1000);
+    let approvers = override_approvers(&fixture);
+    let cooldown = fixture.client.get_override_cooldown();
+    let justification = BytesN::from_array(&env, &[7u8; 32]);
+
+    env.ledger().set_timestamp(5000);
+    fixture
+        .client
+        .emergency_override(&approvers, &fixture.market(0), &1u32, &justification);
+    env.ledger().set_timestamp(5000 + cooldown);
+    fixture.client.mark_unresolvable(
+        &approvers,
+        &fixture.market(1),
+        &justification,
+        &env.register(ResolutionSink, ()),
+    );
+    env.ledger().set_timestamp(5000 + 2 * cooldown);
+    fixture
+        .client
+        .emergency_override(&approvers, &fixture.market(2), &0u32, &justification);
+    assert_eq!(fixture.client.get_override_count(), 3);
+
+    let first_page = fixture.client.list_overrides(&0, &2);
+    assert_eq!(first_page.len(), 2);
+    assert_eq!(first_page.get(0).unwrap().market_id, fixture.market(0));
+    assert_eq!(first_page.get(1).unwrap().forced_outcome, OUTCOME_INVALID);
+    let second_page = fixture.client.list_overrides(&2, &2);
+    assert_eq!(second_page.len(), 1);
+    let last = second_page.get(0).unwrap();
+    assert_eq!((last.id, last.market_id.clone()), (2, fixture.market(2)));
+    assert_eq!(last.timestamp, 5000 + 2 * cooldown);
+    assert!(fixture.client.list_overrides(&3, &2).is_empty());
+
+    assert_eq!(
+        fixture.client.get_override_by_id(&1).unwrap().market_id,
+        fixture.market(1)
+    );
+    assert_eq!(fixture.client.get_override_by_id(&3), None);
+}
+
+#[test]
+fn test_override_audit_log_keeps_superseded_records() {
+    let env = create_test_env();
+    let fixture = OracleFixture::new(&env).with_market([54u8; 32], 1000);
+    let approvers = override_approvers(&fixture);
+    let cooldown = fixture.client.get_override_cooldown();
+    let market_id = fixture.market(0);
+    let justification = BytesN::from_array(&env, &[7u8; 32]);
+
+    env.ledger().set_timestamp(5000);
+    fixture
+        .client
+        .emergency_override(&approvers, &market_id, &1u32, &justification);
+    env.ledger().set_timestamp(5000 + cooldown);
+    fixture
+        .client
+        .emergency_override(&approvers, &market_id, &0u32, &justification);
+
+    // The per-market record holds the latest; the log holds both
+    let latest = fixture.client.get_override_record(&market_id).unwrap();
+    assert_eq!((latest.id, latest.forced_outcome), (1, 0));
+    let log = fixture.client.list_overrides(&0, &MAX_OVERRIDE_PAGE);
+    assert_eq!(log.len(), 2);
+    assert_eq!(log.get(0).unwrap().forced_outcome, 1);
+    assert_eq!(log.get(1).unwrap(), latest);
+}
+
+#[test]
+fn test_override_justification_id_binds_market() {
+    let env = create_test_env();
+    let fixture = OracleFixture::new(&env)
+        .with_market([55u8; 32], 1000)
+        .with_market([56u8; 32], 1000);
+    let approvers = override_approvers(&fixture);
+    let cooldown = fixture.client.get_override_cooldown();
+    let justification = BytesN::from_array(&env, &[7u8; 32]);
+
+    env.ledger().set_timestamp(5000);
+    fixture
+        .client
+        .emergency_override(&approvers, &fixture.market(0), &1u32, &justification);
+    env.ledger().set_timestamp(5000 + cooldown);
+    fixture
+        .client
+        .emergency_override(&approvers, &fixture.market(1), &1u32, &justification);
+
+    // The same document yields a distinct id per market
+    let first = fixture.client.get_override_by_id(&0).unwrap();
+    let second = fixture.client.get_override_by_id(&1).unwrap();
+    assert_eq!(
+        first.justification_id,
+        hashing::hash_pair(&env, &fixture.market(0), &justification)
+    );
+    assert_ne!(first.justification_id, second.justification_id);
+}
+
+// ===== EVENT SCHEMA TESTS =====
+
+/// Find the single oracle event from the last invocation whose first topic is `name`
+fn oracle_event(env: &Env, oracle: &Address, name: &str) -> (Vec<Val>, Val) {
+    assert!(
+        ORACLE_EVENTS.contains(&name),
+        "{} is not in ORACLE_EVENTS",
+        name
+    );
+    let mut found = None;
+    for (contract, topics, data) in env.events().all().iter() {
+        if contract != *oracle {
+            continue;
+        }
+        let first = Symbol::try_from_val(env, &topics.get(0).unwrap()).ok();
+        if first == Some(Symbol::new(env, name)) {
+            assert!(found.is_none(), "duplicate {} event", name);
+            found = Some((topics, data));
+        }
+    }
+    found.unwrap_or_else(|| panic!("no {} event", name))
+}
+
+/// Every event an invocation publishes is named in ORACLE_EVENTS
+fn assert_known_events(env: &Env, oracle: &Address) {
+    for (contract, topics, _) in env.events().all().iter() {
+        if contract != *oracle {
+            continue;
+        }
+        let name = Symbol::try_from_val(env, &topics.get(0).unwrap()).unwrap();
+        assert!(
+            ORACLE_EVENTS
+                .iter()
+                .any(|known| Symbol::new(env, known) == name),
+            "unlisted event {:?}",
+            name
+        );
+    }
+}
+
+#[test]
+fn test_market_registered_event() {
+    let env = create_test_env();
+    let fixture = OracleFixture::new(&env);
+    let market_id = BytesN::from_array(&env, &[4u8; 32]);
+
+    fixture
+        .client
+        .register_market(&market_id, &1000, &Symbol::new(&env, CATEGORY_ANY));
+
+    let (topics, data) = oracle_event(&env, &fixture.client.address, "market_reg");
+    let expected: Vec<Val> = (Symbol::new(&env, "market_reg"), market_id).into_val(&env);
+    assert_eq!(topics, expected);
+    assert_eq!(<(u64,)>::try_from_val(&env, &data).unwrap(), (1000,));
+}
+
+#[test]
+fn test_oracle_registered_event() {
+    let env = create_test_env();
+    let fixture = OracleFixture::new(&env);
+    let oracle = Address::generate(&env);
+    env.ledger().set_timestamp(77);
+
+    fixture
+        .client
+        .register_oracle(&oracle, &Symbol::new(&env, "Feed"), &Vec::new(&env));
+
+    let (topics, data) = oracle_event(&env, &fixture.client.address, "oracle_reg");
+    let expected: Vec<Val> = (Symbol::new(&env, "oracle_reg"), oracle).into_val(&env);
+    assert_eq!(topics, expected);
+    assert_eq!(
+        <(Symbol, u64)>::try_from_val(&env, &data).unwrap(),
+        (Symbol::new(&env, "Feed"), 77)
+    );
+}
+
+#[test]
+fn test_settings_events_have_no_entity_topic() {
+    let env = create_test_env();
+    let fixture = OracleFixture::new(&env);
+
+    fixture.client.set_tie_break_mode(&TieBreakMode::Random);
+
+    let (topics, data) = oracle_event(&env, &fixture.client.address, "tie_break");
+    let expected: Vec<Val> = (Symbol::new(&env, "tie_break"),).into_val(&env);
+    assert_eq!(topics, expected);
+    assert_eq!(
+        <(TieBreakMode,)>::try_from_val(&env, &data).unwrap(),
+        (TieBreakMode::Random,)
+    );
+}
+
+#[test]
+fn test_attestation_flow_events_are_listed() {
+    let env = create_test_env();
+    let fixture = OracleFixture::new(&env)
+        .with_oracles(2)
+        .with_market([5u8; 32], 1000);
+    let market_id = fixture.market(0);
+    assert_known_events(&env, &fixture.client.address);
+
+    env.ledger().set_timestamp(1000);
+    for i in 0..2 {
+        fixture.attest(&fixture.oracle(i), &market_id, 1);
+        assert_known_events(&env, &fixture.client.address);
+    }
+    fixture
+        .client
+        .invalidate_attestation(&market_id, &fixture.oracle(1));
+    assert_known_events(&env, &fixture.client.address);
+
+    let (topics, data) = oracle_event(&env, &fixture.client.address, "invalidate");
+    let expected: Vec<Val> = (
+        Symbol::new(&env, "invalidate"),
+        market_id,
+        fixture.oracle(1),
+    )
+        .into_val(&env);
+    assert_eq!(topics, expected);
+    let (outcome, _reopened_until) = <(u32, u64)>::try_from_val(&env, &data).unwrap();
+    assert_eq!(outcome, 1);
 }
@@ -0,0 +1,221 @@
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env, Symbol, Vec,
+};
+
+use boxmeout::factory::{MarketFactory, MarketFactoryClient, MarketParams, RegistryEntryExport};
+use boxmeout::treasury::{Treasury, TreasuryClient};
+
+/// Helper: an initialized, permissionless factory backed by a real treasury and USDC
+fn setup_factory(
+    env: &Env,
+) -> (
+    MarketFactoryClient<'_>,
+    token::StellarAssetClient<'_>,
+    Address,
+) {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let usdc = token::StellarAssetClient::new(
+        env,
+        &env.register_stellar_asset_contract_v2(admin.clone())
+            .address(),
+    );
+    let factory_id = env.register(MarketFactory, ());
+    let treasury_id = env.register(Treasury, ());
+    TreasuryClient::new(env, &treasury_id).initialize(&admin, &usdc.address, &factory_id);
+
+    let factory = MarketFactoryClient::new(env, &factory_id);
+    factory.initialize(&admin, &usdc.address, &treasury_id);
+    factory.set_permissionless(&admin, &true);
+    (factory, usdc, admin)
+}
+
+fn market_params(env: &Env, title: &str, category: &str, operator: Option<Symbol>) -> MarketParams {
+    let closing_time = env.ledger().timestamp() + 86400;
+    MarketParams {
+        title: Symbol::new(env, title),
+        description: Symbol::new(env, "Desc"),
+        category: Symbol::new(env, category),
+        closing_time,
+        resolution_time: closing_time + 3600,
+        operator,
+        nonce: 0,
+        region: Symbol::new(env, "global"),
+    }
+}
+
+/// Helper: a factory with an extra category, an operator and three markets
+fn populated_factory(env: &Env) -> (MarketFactoryClient<'_>, Address) {
+    let (factory, usdc, admin) = setup_factory(env);
+    let kickboxing = Symbol::new(env, "Kickboxing");
+    factory.add_category(&admin, &kickboxing);
+    factory.set_reference_depth(&admin, &kickboxing, &5_000_000);
+    factory.set_resting_floor(&admin, &kickboxing, &2500);
+    let alpha = Symbol::new(env, "alpha");
+    let alpha_admin = Address::generate(env);
+    factory.register_operator(&admin, &alpha, &alpha_admin, &5000);
+
+    let creator = Address::generate(env);
+    usdc.mint(&creator, &30_000_000);
+    let plain = factory.create_market(
+        &creator,
+        &market_params(env, "Plain", "Boxing", None),
+        &None,
+    );
+    env.ledger().with_mut(|li| li.timestamp += 10);
+    let operated = factory.create_market(
+        &creator,
+        &market_params(env, "Operated", "Kickboxing", Some(alpha)),
+        &None,
+    );
+    env.ledger().with_mut(|li| li.timestamp += 10);
+    factory.create_market(&creator, &market_params(env, "Third", "MMA", None), &None);
+
+    factory.set_market_address(&plain, &Address::generate(env));
+    factory.set_market_paused(&alpha_admin, &operated, &true);
+    (factory, creator)
+}
+
+/// Helper: read the whole registry in pages of `page`
+fn export_all(env: &Env, factory: &MarketFactoryClient, page: u32) -> Vec<RegistryEntryExport> {
+    let mut entries = Vec::new(env);
+    loop {
+        let batch = factory.export_registry(&entries.len(), &page);
+        entries.append(&batch);
+        if batch.len() < page {
+            return entries;
+        }
+    }
+}
+
+#[test]
+fn test_export_orders_categories_operators_then_markets() {
+    let env = Env::default();
+    let (factory, _) = populated_factory(&env);
+
+    let entries = export_all(&env, &factory, 2);
+    // Boxing, MMA, Kickboxing; alpha; three markets
+    assert_eq!(entries.len(), 7);
+    assert!(
+        matches!(entries.get_unchecked(2), RegistryEntryExport::Category(c) if c.reference_depth == Some(5_000_000))
+    );
+    assert!(
+        matches!(entries.get_unchecked(3), RegistryEntryExport::Operator(o) if o.operator.fee_share_bps == 5000)
+    );
+    assert!(
+        matches!(entries.get_unchecked(4), RegistryEntryExport::Market(m) if m.record == factory.get_market(&0))
+    );
+    assert!(factory.export_registry(&7, &10).is_empty());
+}
+
+#[test]
+fn test_export_clear_import_restores_listing_getters() {
+    let env = Env::default();
+    let (source, creator) = populated_factory(&env);
+    let entries = export_all(&env, &source, 3);
+
+    // A redeploy starts from an empty registry
+    let (restored, _, admin) = setup_factory(&env);
+    restored.import_registry(&admin, &entries, &false);
+
+    assert_eq!(restored.get_market_count(), 3);
+    assert_eq!(restored.list_markets(&0, &10), source.list_markets(&0, &10));
+    assert_eq!(
+        restored.list_markets_by_creator(&creator, &0, &10),
+        source.list_markets_by_creator(&creator, &0, &10)
+    );
+    for category in source.get_categories().iter() {
+        assert_eq!(
+            restored.list_markets_by_category(&category, &0, &10),
+            source.list_markets_by_category(&category, &0, &10)
+        );
+        assert_eq!(
+            restored.get_reference_depth(&category),
+            source.get_reference_depth(&category)
+        );
+        assert_eq!(
+            restored.get_resting_floor(&category),
+            source.get_resting_floor(&category)
+        );
+    }
+    assert_eq!(restored.get_categories(), source.get_categories());
+
+    let alpha = Symbol::new(&env, "alpha");
+    assert_eq!(restored.get_operator(&alpha), source.get_operator(&alpha));
+    assert_eq!(
+        restored.get_operator_markets(&alpha),
+        source.get_operator_markets(&alpha)
+    );
+    for record in source.list_markets(&0, &10).iter() {
+        let id = record.market_id;
+        assert_eq!(
+            restored.get_market_config(&id),
+            source.get_market_config(&id)
+        );
+        assert_eq!(
+            restored.get_market_region(&id),
+            source.get_market_region(&id)
+        );
+        assert_eq!(restored.is_market_paused(&id), source.is_market_paused(&id));
+        assert_eq!(
+            restored.get_market_metadata(&id),
+            source.get_market_metadata(&id)
+        );
+    }
+    assert_eq!(export_all(&env, &restored, 50), entries);
+}
+
+#[test]
+#[should_panic(expected = "registry not empty")]
+fn test_import_into_populated_registry_requires_force() {
+    let env = Env::default();
+    let (source, _) = populated_factory(&env);
+    let entries = export_all(&env, &source, 50);
+
+    let (target, usdc, admin) = setup_factory(&env);
+    let creator = Address::generate(&env);
+    usdc.mint(&creator, &10_000_000);
+    target.create_market(
+        &creator,
+        &market_params(&env, "Existing", "Boxing", None),
+        &None,
+    );
+
+    target.import_registry(&admin, &entries, &false);
+}
+
+#[test]
+fn test_forced_import_skips_markets_already_listed() {
+    let env = Env::default();
+    let (source, _) = populated_factory(&env);
+    let entries = export_all(&env, &source, 50);
+
+    let (target, _, admin) = setup_factory(&env);
+    target.import_registry(&admin, &entries, &false);
+    target.import_registry(&admin, &entries, &true);
+
+    assert_eq!(target.get_market_count(), 3);
+    assert_eq!(target.list_markets(&0, &10), source.list_markets(&0, &10));
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_import_requires_admin() {
+    let env = Env::default();
+    let (target, _, _) = setup_factory(&env);
+    target.import_registry(&Address::generate(&env), &Vec::new(&env), &false);
+}
+
+#[test]
+fn test_export_of_fresh_factory_lists_default_categories() {
+    let env = Env::default();
+    let (factory, _, _) = setup_factory(&env);
+
+    let entries = factory.export_registry(&0, &10);
+    assert_eq!(entries.len(), 2);
+    assert!(entries
+        .iter()
+        .all(|entry| matches!(entry, RegistryEntryExport::Category(_))));
+}
@@ -0,0 +1,159 @@
+#![no_std]
+//! Example: consuming BOXMEOUT market resolutions from another Soroban contract
+//!
+//! The consumer talks to the OracleManager only through its public interface,
+//! declared below as a `contractclient` trait with mirrored types, exactly as a
+//! third-party contract would without depending on this repository:
+//!
+//! 1. `follow(market_id)` subscribes via `subscribe_resolution`.
+//! 2. When the oracle publishes a result it calls `on_resolution(round)`. The
+//!    consumer checks that the oracle itself is calling and stores the round.
+//!    Soroban forbids re-entering the oracle from inside its own callback, so
+//!    nothing else can be read from it at this point.
+//! 3. `sync(market_id)` is the pull fallback: it reads `latest_round_data` and
+//!    cross-checks it against `get_resolution_proof`, for when a callback failed
+//!    or the consumer subscribed late.
+//! 4. `verify_attestations` shows how an off-chain copy of the attestation
+//!    records is checked against the proof's `attestor_digest`.
+
+use soroban_sdk::{
+    contract, contractclient, contractimpl, contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env,
+    Symbol, Vec,
+};
+
+/// Mirror of the oracle's `RoundData`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundData {
+    pub market_id: BytesN<32>,
+    pub round_id: u32,
+    pub outcome: u32,
+    pub attestor_digest: BytesN<32>,
+    pub updated_at: u64,
+    pub overridden: bool,
+}
+
+/// Mirror of the oracle's `ResolutionProof`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResolutionProof {
+    pub market_id: BytesN<32>,
+    pub final_outcome: u32,
+    pub tally: (u32, u32),
+    pub attestor_digest: BytesN<32>,
+}
+
+/// Mirror of the oracle's `Attestation`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attestation {
+    pub attestor: Address,
+    pub submitter: Address,
+    pub outcome: u32,
+    pub timestamp: u64,
+    pub data_hash: BytesN<32>,
+    pub metadata: Option<Bytes>,
+}
+
+/// The slice of the OracleManager interface a resolution consumer needs
+#[contractclient(name = "ResolutionOracleClient")]
+pub trait ResolutionOracle {
+    fn subscribe_resolution(env: Env, subscriber: Address, market_id: BytesN<32>);
+    fn latest_round_data(env: Env, market_id: BytesN<32>) -> Option<RoundData>;
+    fn get_resolution_proof(env: Env, market_id: BytesN<32>) -> ResolutionProof;
+}
+
+const ORACLE_KEY: &str = "oracle";
+const RESOLUTION_KEY: &str = "resolution";
+
+#[contract]
+pub struct ResolutionConsumer;
+
+#[contractimpl]
+impl ResolutionConsumer {
+    /// Trust `oracle` as the only source of resolutions
+    pub fn initialize(env: Env, oracle: Address) {
+        let key = Symbol::new(&env, ORACLE_KEY);
+        if env.storage().instance().has(&key) {
+            panic!("already initialized");
+        }
+        env.storage().instance().set(&key, &oracle);
+    }
+
+    /// Ask the oracle to call on_resolution when `market_id` resolves
+    pub fn follow(env: Env, market_id: BytesN<32>) {
+        // The consumer subscribes itself; its own address authorizes this call
+        Self::oracle_client(&env).subscribe_resolution(&env.current_contract_address(), &market_id);
+    }
+
+    /// Oracle callback: store a newly published round
+    pub fn on_resolution(env: Env, round: RoundData) {
+        // Anyone can invoke this entry point, so insist the trusted oracle is the caller
+        Self::oracle(&env).require_auth();
+        Self::store_round(&env, round);
+    }
+
+    /// Pull the latest round from the oracle (fallback for a missed callback)
+    pub fn sync(env: Env, market_id: BytesN<32>) -> RoundData {
+        let round = Self::oracle_client(&env)
+            .latest_round_data(&market_id)
+            .expect("market not resolved");
+        let proof = Self::oracle_client(&env).get_resolution_proof(&market_id);
+        if proof.attestor_digest != round.attestor_digest || proof.final_outcome != round.outcome {
+            panic!("round does not match resolution proof");
+        }
+        Self::store_round(&env, round.clone());
+        round
+    }
+
+    /// The consumer's stored copy of a market's resolution
+    pub fn get_resolution(env: Env, market_id: BytesN<32>) -> Option<RoundData> {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, RESOLUTION_KEY), market_id))
+    }
+
+    /// Check an off-chain copy of a market's attestations against the stored round
+    ///
+    /// The digest is sha256 over the XDR of the attestations sorted by attestor.
+    pub fn verify_attestations(
+        env: Env,
+        market_id: BytesN<32>,
+        attestations: Vec<Attestation>,
+    ) -> bool {
+        let Some(round) = Self::get_resolution(env.clone(), market_id) else {
+            return false;
+        };
+        let mut sorted: Vec<Attestation> = Vec::new(&env);
+        for attestation in attestations.iter() {
+            let mut index = sorted.len();
+            while index > 0 && sorted.get_unchecked(index - 1).attestor > attestation.attestor {
+                index -= 1;
+            }
+            sorted.insert(index, attestation);
+        }
+        let digest: BytesN<32> = env.crypto().sha256(&sorted.to_xdr(&env)).into();
+        digest == round.attestor_digest
+    }
+
+    /// Helper: Keep the newest round seen for a market
+    fn store_round(env: &Env, round: RoundData) {
+        let key = (Symbol::new(env, RESOLUTION_KEY), round.market_id.clone());
+        let stored: Option<RoundData> = env.storage().persistent().get(&key);
+        if stored.is_some_and(|stored| stored.round_id >= round.round_id) {
+            return;
+        }
+        env.storage().persistent().set(&key, &round);
+    }
+
+    fn oracle(env: &Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&Symbol::new(env, ORACLE_KEY))
+            .expect("not initialized")
+    }
+
+    fn oracle_client(env: &Env) -> ResolutionOracleClient<'_> {
+        ResolutionOracleClient::new(env, &Self::oracle(env))
+    }
+}
@@ -0,0 +1,189 @@
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, Symbol, Vec,
+};
+
+use boxmeout::market::{PredictionMarket, PredictionMarketClient};
+use boxmeout::oracle::{OracleManager, OracleManagerClient, CATEGORY_ANY};
+use resolution_consumer::{Attestation, ResolutionConsumer, ResolutionConsumerClient, RoundData};
+
+struct Resolution<'a> {
+    oracle: OracleManagerClient<'a>,
+    market: PredictionMarketClient<'a>,
+    market_id: BytesN<32>,
+    attestors: Vec<Address>,
+}
+
+/// Helper: a market with three YES/YES/NO attestations, ready to finalize
+fn attested_market(env: &Env) -> Resolution<'_> {
+    env.mock_all_auths();
+    let oracle = OracleManagerClient::new(env, &env.register(OracleManager, ()));
+    oracle.initialize(&Address::generate(env), &2u32);
+
+    let market_id = BytesN::from_array(env, &[7u8; 32]);
+    let market = PredictionMarketClient::new(env, &env.register(PredictionMarket, ()));
+    market.initialize(
+        &market_id,
+        &Address::generate(env),
+        &Address::generate(env),
+        &env.register_stellar_asset_contract_v2(Address::generate(env))
+            .address(),
+        &oracle.address,
+        &500,
+        &1000,
+        &0,
+        &None,
+    );
+    oracle.register_market(&market_id, &1000, &Symbol::new(env, CATEGORY_ANY));
+    env.ledger().set_timestamp(510);
+    market.close_market(&market_id);
+
+    env.ledger().set_timestamp(1010);
+    let mut attestors = Vec::new(env);
+    for (name, outcome) in [("O1", 1u32), ("O2", 1), ("O3", 0)] {
+        let attestor = Address::generate(env);
+        oracle.register_oracle(&attestor, &Symbol::new(env, name), &Vec::new(env));
+        oracle.submit_attestation(
+            &attestor,
+            &market_id,
+            &outcome,
+            &BytesN::from_array(env, &[0u8; 32]),
+            &None,
+        );
+        attestors.push_back(attestor);
+    }
+    env.ledger().set_timestamp(1000 + 604800 + 10);
+
+    Resolution {
+        oracle,
+        market,
+        market_id,
+        attestors,
+    }
+}
+
+fn register_consumer<'a>(env: &'a Env, oracle: &Address) -> ResolutionConsumerClient<'a> {
+    let consumer = ResolutionConsumerClient::new(env, &env.register(ResolutionConsumer, ()));
+    consumer.initialize(oracle);
+    consumer
+}
+
+/// Helper: the oracle's attestation records in the consumer's mirrored type
+fn attestation_copies(env: &Env, resolution: &Resolution) -> Vec<Attestation> {
+    let mut copies = Vec::new(env);
+    for attestor in resolution.attestors.iter() {
+        let record = resolution
+            .oracle
+            .get_attestation(&resolution.market_id, &attestor)
+            .unwrap();
+        copies.push_back(Attestation {
+            attestor: record.attestor,
+            submitter: record.submitter,
+            outcome: record.outcome,
+            timestamp: record.timestamp,
+            data_hash: record.data_hash,
+            metadata: record.metadata,
+        });
+    }
+    copies
+}
+
+#[test]
+fn test_subscribed_consumer_stores_finalized_round() {
+    let env = Env::default();
+    let resolution = attested_market(&env);
+    let consumer = register_consumer(&env, &resolution.oracle.address);
+    consumer.follow(&resolution.market_id);
+    assert_eq!(
+        resolution
+            .oracle
+            .get_resolution_subscribers(&resolution.market_id)
+            .len(),
+        1
+    );
+
+    resolution
+        .oracle
+        .finalize_resolution(&resolution.market_id, &resolution.market.address);
+
+    let stored = consumer.get_resolution(&resolution.market_id).unwrap();
+    let latest = resolution
+        .oracle
+        .latest_round_data(&resolution.market_id)
+        .unwrap();
+    assert_eq!(stored.round_id, 1);
+    assert_eq!(stored.outcome, 1);
+    assert_eq!(stored.market_id, latest.market_id);
+    assert_eq!(stored.attestor_digest, latest.attestor_digest);
+    assert_eq!(stored.updated_at, latest.updated_at);
+    assert_eq!(
+        stored.attestor_digest,
+        resolution
+            .oracle
+            .get_resolution_proof(&resolution.market_id)
+            .attestor_digest
+    );
+}
+
+#[test]
+fn test_consumer_verifies_off_chain_attestation_copy() {
+    let env = Env::default();
+    let resolution = attested_market(&env);
+    let consumer = register_consumer(&env, &resolution.oracle.address);
+    consumer.follow(&resolution.market_id);
+    resolution
+        .oracle
+        .finalize_resolution(&resolution.market_id, &resolution.market.address);
+
+    let mut copies = attestation_copies(&env, &resolution);
+    assert!(consumer.verify_attestations(&resolution.market_id, &copies));
+
+    // Order does not matter, content does
+    let first = copies.pop_front_unchecked();
+    copies.push_back(first);
+    assert!(consumer.verify_attestations(&resolution.market_id, &copies));
+    let mut tampered = copies.get_unchecked(0);
+    tampered.outcome = 1 - tampered.outcome;
+    copies.set(0, tampered);
+    assert!(!consumer.verify_attestations(&resolution.market_id, &copies));
+}
+
+#[test]
+fn test_late_consumer_syncs_from_latest_round_data() {
+    let env = Env::default();
+    let resolution = attested_market(&env);
+    resolution
+        .oracle
+        .finalize_resolution(&resolution.market_id, &resolution.market.address);
+
+    let consumer = register_consumer(&env, &resolution.oracle.address);
+    assert!(consumer.get_resolution(&resolution.market_id).is_none());
+    let round = consumer.sync(&resolution.market_id);
+    assert_eq!(consumer.get_resolution(&resolution.market_id), Some(round));
+}
+
+#[test]
+fn test_on_resolution_rejects_callers_other_than_the_oracle() {
+    let env = Env::default();
+    let resolution = attested_market(&env);
+    resolution
+        .oracle
+        .finalize_resolution(&resolution.market_id, &resolution.market.address);
+    let consumer = register_consumer(&env, &resolution.oracle.address);
+    let round = resolution
+        .oracle
+        .latest_round_data(&resolution.market_id)
+        .unwrap();
+
+    // Without the oracle's authorization a forged callback is refused
+    env.set_auths(&[]);
+    let forged = RoundData {
+        market_id: round.market_id,
+        round_id: round.round_id,
+        outcome: round.outcome,
+        attestor_digest: round.attestor_digest,
+        updated_at: round.updated_at,
+        overridden: round.overridden,
+    };
+    assert!(consumer.try_on_resolution(&forged).is_err());
+}